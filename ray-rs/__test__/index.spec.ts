@@ -7,6 +7,7 @@ import path from 'node:path'
 import {
   Database,
   JsTraversalDirection,
+  JsWalkControl,
   PropType,
   collectMetrics,
   createBackup,
@@ -110,6 +111,64 @@ test('db-backed traversal APIs', (t) => {
   db.close()
 })
 
+test('walk visits in BFS order with depth limits, edge filters and cycles', (t) => {
+  const db = Database.open(makeDbPath())
+  db.begin()
+  const a = db.createNode('a')
+  const b = db.createNode('b')
+  const c = db.createNode('c')
+  const d = db.createNode('d')
+  const knows = db.getOrCreateEtype('knows')
+  const blocks = db.getOrCreateEtype('blocks')
+  db.addEdge(a, knows, b)
+  db.addEdge(b, knows, c)
+  db.addEdge(c, knows, a)
+  db.addEdge(a, blocks, d)
+  db.commit()
+
+  const record = (startNodes: number[], edgeType: number | null, limit?: number) => {
+    const seen: Array<[number, number, number | null]> = []
+    const visits = db.walk(startNodes, JsTraversalDirection.Out, edgeType, (node, depth, parent) => {
+      seen.push([node, depth, parent ?? null])
+      return limit !== undefined && depth >= limit ? JsWalkControl.SkipChildren : undefined
+    })
+    t.is(visits, seen.length)
+    return seen
+  }
+
+  // The a -> b -> c -> a cycle visits each node once
+  t.deepEqual(record([a], knows), [
+    [a, 0, null],
+    [b, 1, a],
+    [c, 2, b],
+  ])
+  // Without a filter the blocks edge is followed too
+  t.deepEqual(
+    record([a], null).map(([node]) => node),
+    [a, b, d, c],
+  )
+  // Skipping children at depth 1 stops the walk there
+  t.deepEqual(record([a], knows, 1), [
+    [a, 0, null],
+    [b, 1, a],
+  ])
+  t.deepEqual(record([d], knows), [[d, 0, null]])
+
+  const stopped: number[] = []
+  const visits = db.walk([a], JsTraversalDirection.Out, knows, (node) => {
+    stopped.push(node)
+    return node === b ? JsWalkControl.Stop : JsWalkControl.Continue
+  })
+  t.is(visits, 2)
+  t.deepEqual(stopped, [a, b])
+
+  t.throws(() => db.walk([a, 9999], JsTraversalDirection.Out, null, () => undefined), {
+    message: /Start node 9999 does not exist/,
+  })
+
+  db.close()
+})
+
 test('db-backed upsertNode', (t) => {
   const db = Database.open(makeDbPath())
 
@@ -3,7 +3,7 @@ import fs from 'node:fs'
 import os from 'node:os'
 import path from 'node:path'
 
-import { kite, kiteSync, node, edge, prop, optional, Database, bulkWrite } from '../dist/index.js'
+import { kite, kiteSync, node, edge, prop, optional, Database, bulkWrite, TraversalDirection } from '../dist/index.js'
 
 const makeDbPath = () => {
   const dir = fs.mkdtempSync(path.join(os.tmpdir(), 'kitedb-schema-'))
@@ -113,6 +113,40 @@ test('bulkWrite auto-commits in chunks', (t) => {
   db.close()
 })
 
+test('getOutEdges and getInEdges attach neighbor keys with includeKeys', (t) => {
+  const db = Database.open(makeDbPath())
+
+  db.begin()
+  const a = db.createNode('a')
+  const b = db.createNode('b')
+  const anon = db.createNode()
+  const knows = db.getOrCreateEtype('knows')
+  const likes = db.getOrCreateEtype('likes')
+  db.addEdge(a, knows, b)
+  db.addEdge(a, knows, anon)
+  db.addEdge(b, likes, a)
+  db.commit()
+
+  // Lean by default
+  for (const e of db.getOutEdges(a)) {
+    t.is(e.nodeKey, undefined)
+  }
+
+  const out = db.getOutEdges(a, null, true).sort((x, y) => x.nodeId - y.nodeId)
+  t.is(out.length, 2)
+  t.like(out[0], { etype: knows, nodeId: b, nodeKey: 'b' })
+  // A neighbor without a key has no nodeKey
+  t.like(out[1], { etype: knows, nodeId: anon })
+  t.is(out[1].nodeKey, undefined)
+
+  const incoming = db.getInEdges(a, likes, true)
+  t.is(incoming.length, 1)
+  t.like(incoming[0], { etype: likes, nodeId: b, nodeKey: 'b' })
+  t.deepEqual(db.getInEdges(a, knows, true), [])
+
+  db.close()
+})
+
 // =============================================================================
 // Async kite() Tests
 // =============================================================================
@@ -277,6 +311,72 @@ test('transaction commits and rolls back', async (t) => {
   db.close()
 })
 
+test('nested begin joins and an inner rollback makes the outer commit fail', (t) => {
+  const User = node('user', {
+    key: (id: string) => `user:${id}`,
+    props: { name: prop.string('name') },
+  })
+
+  const db = kiteSync(makeDbPath(), { nodes: [User], edges: [] })
+
+  db.begin()
+  db.insert('user').values('alice', { name: 'Alice' }).execute()
+  db.begin()
+  db.insert('user').values('bob', { name: 'Bob' }).execute()
+  db.commit()
+  db.commit()
+  t.truthy(db.get('user', 'alice'))
+  t.truthy(db.get('user', 'bob'))
+
+  db.begin()
+  db.insert('user').values('carol', { name: 'Carol' }).execute()
+  db.begin()
+  db.insert('user').values('dave', { name: 'Dave' }).execute()
+  db.rollback()
+  t.throws(() => db.commit(), { code: 'KITE_TRANSACTION_STATE' })
+  t.is(db.get('user', 'carol'), null)
+  t.is(db.get('user', 'dave'), null)
+
+  // The failed commit ended the transaction, so a new one can start
+  db.begin()
+  db.rollback()
+
+  db.close()
+})
+
+test('onPropChange fires for committed changes only', async (t) => {
+  const User = node('user', {
+    key: (id: string) => `user:${id}`,
+    props: { name: prop.string('name') },
+  })
+
+  const db = kiteSync(makeDbPath(), { nodes: [User], edges: [] })
+  const alice = db.insert('user').values('alice', { name: 'Alice' }).returning() as any
+
+  const changes: Array<{ nodeId: number; oldValue: unknown; newValue: unknown }> = []
+  const id = db.onPropChange(User, 'name', (change) => {
+    changes.push(change)
+  })
+  const settle = () => new Promise<void>((resolve) => setTimeout(resolve, 20))
+
+  db.begin()
+  db.setProp(alice.id, 'name', 'Rolled back')
+  db.rollback()
+  await settle()
+  t.deepEqual(changes, [])
+
+  db.setProp(alice.id, 'name', 'Alicia')
+  await settle()
+  t.deepEqual(changes, [{ nodeId: alice.id, oldValue: 'Alice', newValue: 'Alicia' }])
+
+  t.true(db.offPropChange(id))
+  db.setProp(alice.id, 'name', 'Ignored')
+  await settle()
+  t.is(changes.length, 1)
+
+  db.close()
+})
+
 test('batch executes atomically', async (t) => {
   const User = node('user', {
     key: (id: string) => `user:${id}`,
@@ -313,6 +413,464 @@ test('batch executes atomically', async (t) => {
   db.close()
 })
 
+test('bestEffort batch reports per-op results for native and builder ops', (t) => {
+  const User = node('user', {
+    key: (id: string) => `user:${id}`,
+    props: {
+      name: prop.string('name'),
+    },
+  })
+
+  const db = kiteSync(makeDbPath(), {
+    nodes: [User],
+    edges: [],
+  })
+
+  const native = db.batch(
+    [
+      { op: 'createNode', nodeType: 'user', key: 'alice', props: { name: 'Alice' } },
+      { op: 'createNode', nodeType: 'missing', key: 'x', props: {} },
+      { op: 'createNode', nodeType: 'user', key: 'bob', props: { name: 'Bob' } },
+    ],
+    { bestEffort: true },
+  )
+
+  t.is(native.length, 3)
+  t.like(native[0], { ok: true, type: 'nodeCreated', affected: 1 })
+  t.is(native[1].ok, false)
+  t.is(typeof native[1].error, 'string')
+  t.like(native[2], { ok: true, type: 'nodeCreated', affected: 1 })
+
+  const custom = db.batch(
+    [
+      db.insert('user').values('carol', { name: 'Carol' }),
+      () => {
+        throw new Error('boom')
+      },
+      db.insert('user').values('dave', { name: 'Dave' }),
+    ],
+    { bestEffort: true },
+  )
+
+  t.is(custom.length, 3)
+  t.like(custom[0], { ok: true, type: 'custom', affected: 1 })
+  t.deepEqual(custom[1], { ok: false, error: 'boom' })
+  t.like(custom[2], { ok: true, type: 'custom', affected: 1 })
+
+  for (const key of ['alice', 'bob', 'carol', 'dave']) {
+    t.truthy(db.get('user', key))
+  }
+
+  db.close()
+})
+
+test('json props round-trip objects and arrays and filter by path', (t) => {
+  const Doc = node('doc', {
+    key: (id: string) => `doc:${id}`,
+    props: {
+      settings: prop.json('settings'),
+      tags: prop.json('tags'),
+      embedding: prop.vector('embedding', 2),
+    },
+  })
+
+  const db = kiteSync(makeDbPath(), { nodes: [Doc], edges: [] })
+
+  db.insert('doc')
+    .values('a', {
+      settings: { theme: 'dark', limits: { rows: 10 } },
+      tags: ['x', 1, null],
+      embedding: new Float32Array([0.5, 0.25]),
+    })
+    .execute()
+  db.insert('doc').values('b', { settings: { theme: 'light' }, tags: [] }).execute()
+
+  const a = db.get('doc', 'a') as any
+  t.deepEqual(a.settings, { theme: 'dark', limits: { rows: 10 } })
+  t.deepEqual(a.tags, ['x', 1, null])
+  t.deepEqual(a.embedding, [0.5, 0.25])
+
+  const dark = db.findWhere('doc', 'settings', { jsonPath: '$.theme', eq: 'dark' })
+  t.is(dark.length, 1)
+  t.is(db.findWhere('doc', 'tags', { eq: [] }).length, 1)
+
+  t.throws(() => db.insert('doc').values('c', { settings: new Date() }).execute())
+  t.throws(() => db.insert('doc').values('d', { embedding: new Float64Array([1, 2]) }).execute())
+  t.throws(() => db.insert('doc').values('e', { embedding: [1, 2] as any }).execute(), {
+    message: /is a vector/,
+  })
+
+  db.close()
+})
+
+test('archived nodes stay out of props, counts, neighbors and getByIds', (t) => {
+  const User = node('user', {
+    key: (id: string) => `user:${id}`,
+    props: { name: prop.string('name') },
+  })
+  const follows = edge('follows')
+
+  const db = kiteSync(makeDbPath(), { nodes: [User], edges: [follows] })
+
+  const alice = db.insert('user').values('alice', { name: 'Alice' }).returning() as any
+  const bob = db.insert('user').values('bob', { name: 'Bob' }).returning() as any
+  db.link(alice.id, 'follows', bob.id)
+  t.true(db.archive(bob.id))
+
+  t.is(db.countNodes(), 1)
+  t.is(db.countNodes('user'), 1)
+  t.deepEqual(db.neighbors(alice.id), [])
+  t.is(db.getById(bob.id), null)
+  t.deepEqual(db.getByIds([alice.id, bob.id]).map((n: any) => n.id), [alice.id])
+  t.deepEqual(db.getByIds([bob.id], undefined, { includeMissing: true }), [null])
+  t.false(db.hasEdge(alice.id, follows, bob.id))
+  t.deepEqual(db.hasEdges([{ src: alice.id, edgeType: follows, dst: bob.id }]), [false])
+  t.is(db.countEdges(), 0)
+  t.is(db.countEdges(follows), 0)
+
+  // The cursor skips archived nodes, even across a batch of only archived ones
+  const carol = db.insert('user').values('carol', { name: 'Carol' }).returning() as any
+  const cursor = db.nodeCursor()
+  t.deepEqual(cursor.next(1), [alice.id])
+  t.deepEqual(cursor.next(1), [carol.id])
+  t.deepEqual(cursor.next(1), [])
+  db.delete('user', 'carol')
+
+  const archived = db.getByIds([bob.id], undefined, { includeArchived: true })[0] as any
+  t.is(archived.name, 'Bob')
+  t.false('__archived_at' in archived)
+  t.false(db.hasProp(bob.id, '__archived_at'))
+  t.throws(() => db.setProp(alice.id, '__archived_at', 0))
+
+  const { node: restored, created } = db.getOrInsert('user', 'bob', { name: 'Other' }) as any
+  t.false(created)
+  t.is(restored.id, bob.id)
+  t.is(restored.name, 'Bob')
+  t.false(db.isArchived(bob.id))
+  t.is(db.countNodes(), 2)
+  t.true(db.hasEdge(alice.id, follows, bob.id))
+  t.is(db.countEdges(), 1)
+
+  db.close()
+})
+
+test('virtual props are merged only with includeVirtual', (t) => {
+  const User = node('user', {
+    key: (id: string) => `user:${id}`,
+    props: { first: prop.string('first'), last: prop.string('last') },
+  })
+
+  const db = kiteSync(makeDbPath(), { nodes: [User], edges: [] })
+  const alice = db.insert('user').values('alice', { first: 'Alice', last: 'Liddell' }).returning() as any
+  db.defineVirtualProp(User, 'fullName', (p: any) => `${p.first} ${p.last}`)
+
+  const withVirtual = db.get('user', 'alice', undefined, { includeVirtual: true }) as any
+  t.is(withVirtual.fullName, 'Alice Liddell')
+  t.is((db.getById(alice.id, undefined, { includeVirtual: true }) as any).fullName, 'Alice Liddell')
+
+  const plain = db.get('user', 'alice') as any
+  t.false('fullName' in plain)
+  t.false('fullName' in (db.get('user', 'alice', undefined, { includeVirtual: false }) as any))
+  t.is(plain.first, 'Alice')
+
+  db.close()
+})
+
+test('getMany lines results up with keys and returns null for missing ones', (t) => {
+  const User = node('user', {
+    key: (id: string) => `user:${id}`,
+    props: { name: prop.string('name') },
+  })
+
+  const db = kiteSync(makeDbPath(), { nodes: [User], edges: [] })
+  db.insert('user').values('alice', { name: 'Alice' }).execute()
+  db.insert('user').values('bob', { name: 'Bob' }).execute()
+
+  const nodes = db.getMany(User, ['bob', 'nobody', 'alice', 'bob']) as any[]
+  t.is(nodes.length, 4)
+  t.is(nodes[0].name, 'Bob')
+  t.is(nodes[1], null)
+  t.is(nodes[2].name, 'Alice')
+  t.is(nodes[3].id, nodes[0].id)
+  t.deepEqual(db.getMany(User, []), [])
+
+  db.close()
+})
+
+const lazyUser = node('user', {
+  key: (id: string) => `user:${id}`,
+  props: {
+    name: prop.string('name'),
+    email: prop.string('email'),
+    bio: optional(prop.string('bio')),
+  },
+})
+
+/** Record the props each `get_by_id` call asks for (`'*'` for all of them) */
+const spyPropReads = (db: any): string[] => {
+  const reads: string[] = []
+  const getById = db.get_by_id
+  db.get_by_id = (id: number, props?: string[] | null, options?: unknown) => {
+    reads.push(props ? props.join(',') : '*')
+    return getById.call(db, id, props, options)
+  }
+  return reads
+}
+
+test('lazy get fetches each prop on first access only', (t) => {
+  const db = kiteSync(makeDbPath(), { nodes: [lazyUser], edges: [] })
+  db.insert(lazyUser).values('alice', { name: 'Alice', email: 'a@example.com' }).execute()
+
+  const alice = db.get(lazyUser, 'alice', undefined, { lazy: true }) as any
+  const reads = spyPropReads(db)
+  t.is(alice.key, 'user:alice')
+  t.deepEqual(reads, [])
+
+  t.is(alice.name, 'Alice')
+  t.is(alice.name, 'Alice')
+  t.deepEqual(reads, ['name'])
+  t.is(alice.bio, undefined)
+  t.deepEqual(reads, ['name', 'bio'])
+
+  // Awaiting or serializing probes `then`/`toJSON`, which never hit storage
+  t.is(alice.then, undefined)
+  t.is(alice.toJSON, undefined)
+  t.is(alice[Symbol.iterator], undefined)
+  t.deepEqual(reads, ['name', 'bio'])
+
+  t.true('email' in alice)
+  t.false('bio' in alice)
+  t.deepEqual(reads, ['name', 'bio', 'email'])
+
+  db.close()
+})
+
+test('lazy get enumerates, spreads and serializes loaded props', (t) => {
+  const db = kiteSync(makeDbPath(), { nodes: [lazyUser], edges: [] })
+  db.insert(lazyUser).values('alice', { name: 'Alice', email: 'a@example.com' }).execute()
+
+  const alice = db.get(lazyUser, 'alice', undefined, { lazy: true }) as any
+  const eager = db.get(lazyUser, 'alice') as any
+  t.deepEqual(Object.keys(alice).sort(), Object.keys(eager).sort())
+  t.deepEqual({ ...alice }, { ...eager })
+  t.deepEqual(JSON.parse(JSON.stringify(alice)), JSON.parse(JSON.stringify(eager)))
+  t.deepEqual(alice.materialize(), eager)
+  t.false(Object.keys(alice).includes('materialize'))
+
+  const byId = db.getById(eager.id, undefined, { lazy: true }) as any
+  t.is(byId.email, 'a@example.com')
+  t.deepEqual({ ...byId }, { ...eager })
+
+  db.close()
+})
+
+test('lazy node reads nothing once its node is deleted', (t) => {
+  const db = kiteSync(makeDbPath(), { nodes: [lazyUser], edges: [] })
+  db.insert(lazyUser).values('alice', { name: 'Alice', email: 'a@example.com' }).execute()
+
+  const alice = db.get(lazyUser, 'alice', undefined, { lazy: true }) as any
+  t.is(alice.name, 'Alice')
+  t.true(db.delete(lazyUser, 'alice'))
+  // A new node under the same key is a different node
+  db.insert(lazyUser).values('alice', { name: 'Alice 2', email: 'b@example.com' }).execute()
+
+  // Props already read stay cached; the rest read as unset
+  t.is(alice.name, 'Alice')
+  t.is(alice.email, undefined)
+  t.false('email' in alice)
+  const snapshot = alice.materialize()
+  t.is(snapshot.key, 'user:alice')
+  t.is(snapshot.email, undefined)
+  t.is(snapshot.name, undefined)
+  t.deepEqual(Object.keys(alice).sort(), Object.keys(snapshot).sort())
+
+  db.close()
+})
+
+test('rename and renameOnly project props on get, getById and all', (t) => {
+  const db = kiteSync(makeDbPath(), { nodes: [lazyUser], edges: [] })
+  db.insert(lazyUser).values('alice', { name: 'Alice', email: 'a@example.com' }).execute()
+  const rename = { name: 'username' }
+
+  const renamed = db.get(lazyUser, 'alice', undefined, { rename }) as any
+  t.is(renamed.username, 'Alice')
+  t.is(renamed.email, 'a@example.com')
+  t.false('name' in renamed)
+  t.is(renamed.key, 'user:alice')
+
+  const only = db.getById(renamed.id, undefined, { rename, renameOnly: true }) as any
+  t.deepEqual(only, { id: renamed.id, key: 'user:alice', type: 'user', username: 'Alice' })
+  t.deepEqual(db.getById(renamed.id, undefined, { rename }), renamed)
+
+  t.deepEqual(db.all(lazyUser, { rename }), [renamed])
+  t.deepEqual(db.all(lazyUser, { rename, renameOnly: true }), [only])
+
+  db.close()
+})
+
+test('rename rejects system field targets and lazy reads', (t) => {
+  const db = kiteSync(makeDbPath(), { nodes: [lazyUser], edges: [] })
+  db.insert(lazyUser).values('alice', { name: 'Alice', email: 'a@example.com' }).execute()
+  const aliceId = db.getId(lazyUser, 'alice') as number
+
+  for (const target of ['id', 'key', 'type', '$units', '$out', '$in']) {
+    t.throws(() => db.get(lazyUser, 'alice', undefined, { rename: { name: target } }), {
+      message: /reserved node field/,
+    })
+    t.throws(() => db.getById(aliceId, undefined, { rename: { name: target } }), {
+      message: /reserved node field/,
+    })
+  }
+  for (const target of ['id', 'key', 'type']) {
+    t.throws(() => db.all(lazyUser, { rename: { email: target } }), { message: /reserved node field/ })
+  }
+
+  t.throws(() => db.get(lazyUser, 'alice', undefined, { lazy: true, rename: { name: 'username' } }), {
+    message: /can't be combined with lazy/,
+  })
+  t.throws(() => db.getById(aliceId, undefined, { lazy: true, renameOnly: true }), {
+    message: /can't be combined with lazy/,
+  })
+
+  db.close()
+})
+
+test('getOrInsert creates once, then returns the existing node', (t) => {
+  const db = kiteSync(makeDbPath(), { nodes: [lazyUser], edges: [] })
+
+  const first = db.getOrInsert(lazyUser, 'alice', { name: 'Alice', email: 'a@example.com' })
+  t.true(first.created)
+  t.is(first.node.key, 'user:alice')
+  t.is(first.node.name, 'Alice')
+
+  // Props of a later call are ignored, even invalid or incomplete ones
+  const second = db.getOrInsert(lazyUser, 'alice', { name: 'Someone else' })
+  t.false(second.created)
+  t.is(second.node.id, first.node.id)
+  t.is(second.node.name, 'Alice')
+  t.is(second.node.email, 'a@example.com')
+  t.is(db.countNodes(), 1)
+
+  db.close()
+})
+
+test('getOrInsert checks required props before inserting', (t) => {
+  const db = kiteSync(makeDbPath(), { nodes: [lazyUser], edges: [] })
+
+  t.throws(() => db.getOrInsert(lazyUser, 'alice', { name: 'Alice' }), {
+    message: /Missing required properties for user: email/,
+  })
+  t.is(db.get(lazyUser, 'alice'), null)
+
+  // Optional props may be left out
+  const { node, created } = db.getOrInsert(lazyUser, 'alice', { name: 'Alice', email: 'a@example.com' })
+  t.true(created)
+  t.is(node.bio, undefined)
+
+  db.close()
+})
+
+test('linkByKey refuses to create endpoints with required props', (t) => {
+  const tag = node('tag', {
+    key: (id: string) => `tag:${id}`,
+    props: { label: optional(prop.string('label')) },
+  })
+  const tagged = edge('tagged')
+  const db = kiteSync(makeDbPath(), { nodes: [lazyUser, tag], edges: [tagged] })
+
+  t.throws(() => db.linkByKey(lazyUser, 'alice', tagged, tag, 'rust', { createMissing: true }), {
+    message: /Missing required properties for user: email, name/,
+  })
+  t.is(db.countNodes(), 0)
+
+  db.insert(lazyUser).values('alice', { name: 'Alice', email: 'a@example.com' }).execute()
+  const { src, dst } = db.linkByKey(lazyUser, 'alice', tagged, tag, 'rust', { createMissing: true })
+  t.is(src, db.get(lazyUser, 'alice')?.id)
+  t.is(dst, db.get(tag, 'rust')?.id)
+  t.true(db.hasEdge(src, tagged, dst))
+
+  db.close()
+})
+
+test('get with edges attaches $out and $in neighbor ids per edge type', (t) => {
+  const User = node('user', {
+    key: (id: string) => `user:${id}`,
+    props: { name: prop.string('name') },
+  })
+  const follows = edge('follows')
+  const likes = edge('likes')
+  const db = kiteSync(makeDbPath(), { nodes: [User], edges: [follows, likes] })
+
+  const alice = db.insert(User).values('alice', { name: 'Alice' }).returning() as any
+  const bob = db.insert(User).values('bob', { name: 'Bob' }).returning() as any
+  const carol = db.insert(User).values('carol', { name: 'Carol' }).returning() as any
+  db.link(alice.id, follows, bob.id)
+  db.link(alice.id, follows, carol.id)
+  db.link(carol.id, follows, alice.id)
+  db.link(bob.id, likes, alice.id)
+
+  const got = db.get(User, 'alice', undefined, { edges: ['follows', 'likes'] }) as any
+  t.is(got.name, 'Alice')
+  t.deepEqual(Object.keys(got.$out).sort(), ['follows', 'likes'])
+  t.deepEqual([...got.$out.follows].sort(), [bob.id, carol.id].sort())
+  t.deepEqual(got.$out.likes, [])
+  t.deepEqual(got.$in.follows, [carol.id])
+  t.deepEqual(got.$in.likes, [bob.id])
+
+  // Only the requested edge types are attached, and getById takes the option too
+  const byId = db.getById(bob.id, undefined, { edges: ['likes'] }) as any
+  t.deepEqual(byId.$out, { likes: [alice.id] })
+  t.deepEqual(byId.$in, { likes: [] })
+
+  // Without the option there are no edge fields
+  t.is((db.get(User, 'alice') as any).$out, undefined)
+
+  t.throws(() => db.get(User, 'alice', undefined, { edges: ['missing'] }), { message: /missing/ })
+
+  db.close()
+})
+
+test('neighbors returns distinct neighbor node objects', (t) => {
+  const User = node('user', {
+    key: (id: string) => `user:${id}`,
+    props: { name: prop.string('name'), bio: optional(prop.string('bio')) },
+  })
+  const follows = edge('follows')
+  const likes = edge('likes')
+  const db = kiteSync(makeDbPath(), { nodes: [User], edges: [follows, likes] })
+
+  const alice = db.insert(User).values('alice', { name: 'Alice' }).returning() as any
+  const bob = db.insert(User).values('bob', { name: 'Bob', bio: 'builder' }).returning() as any
+  const carol = db.insert(User).values('carol', { name: 'Carol' }).returning() as any
+  const dave = db.insert(User).values('dave', { name: 'Dave' }).returning() as any
+  db.link(alice.id, follows, bob.id)
+  db.link(alice.id, follows, carol.id)
+  db.link(alice.id, likes, bob.id)
+  db.link(dave.id, follows, alice.id)
+
+  const byKey = (nodes: Array<any>) => [...nodes].sort((a, b) => a.key.localeCompare(b.key))
+
+  const followed = byKey(db.neighbors(alice.id, follows))
+  t.deepEqual(followed, [
+    { id: bob.id, key: 'user:bob', type: 'user', name: 'Bob', bio: 'builder' },
+    { id: carol.id, key: 'user:carol', type: 'user', name: 'Carol' },
+  ])
+
+  // Bob is reached over two edge types but returned once
+  t.deepEqual(byKey(db.neighbors(alice.id)).map((n) => n.key), ['user:bob', 'user:carol'])
+
+  const followers = db.neighbors(alice.id, follows, TraversalDirection.In)
+  t.deepEqual(followers.map((n: any) => n.id), [dave.id])
+
+  // A props selection limits the props on each node
+  const named = byKey(db.neighbors(alice.id, follows, null, ['name']))
+  t.deepEqual(named[0], { id: bob.id, key: 'user:bob', type: 'user', name: 'Bob' })
+
+  t.deepEqual(db.neighbors(carol.id, follows), [])
+
+  db.close()
+})
+
 test('kiteSync() opens database synchronously', (t) => {
   const User = node('user', {
     key: (id: string) => `user:${id}`,
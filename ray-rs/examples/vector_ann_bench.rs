@@ -261,7 +261,7 @@ fn run_ivf_bench(
     let vector = vector_store_vector_by_id(manifest, vector_id)
       .ok_or_else(|| format!("missing vector {vector_id}"))?;
     index
-      .insert(vector_id, vector)
+      .insert(vector_id, vector, false)
       .map_err(|err| err.to_string())?;
   }
   let build_elapsed_ms = build_start.elapsed().as_millis() as f64;
@@ -14,12 +14,39 @@ export declare class Database {
   get path(): string
   /** Check if database is read-only */
   get readOnly(): boolean
-  /** Begin a transaction */
+  /**
+   * Begin a transaction
+   *
+   * If this thread already has one, joins it instead: each nested begin
+   * needs its own commit or rollback, and only the outermost one ends it.
+   */
   begin(readOnly?: boolean | undefined | null): number
+  /**
+   * Begin a read-only transaction pinned to a past MVCC generation
+   *
+   * Fails if the generation's history is no longer retained (past
+   * generations are only kept with `mvccRetainHistory`).
+   */
+  beginReadAt(generation: number): number
+  /** Current MVCC generation (null when MVCC is disabled) */
+  currentGeneration(): number | null
+  /** Oldest MVCC generation still readable via beginReadAt (null when MVCC is disabled) */
+  oldestReadableGeneration(): number | null
   /** Begin a bulk-load transaction (fast path, MVCC disabled) */
   beginBulk(): number
-  /** Commit the current transaction */
+  /**
+   * Commit the current transaction
+   *
+   * A commit that lost an MVCC conflict throws with `code` set to
+   * `KITE_CONFLICT`; the transaction is gone and can be retried.
+   */
   commit(): void
+  /**
+   * Commit the current transaction and summarize what it changed
+   *
+   * Closing a nested transaction level commits nothing and returns zeros.
+   */
+  commitStats(): JsCommitStats
   /** Rollback the current transaction */
   rollback(): void
   /** Check if there's an active transaction */
@@ -40,10 +67,23 @@ export declare class Database {
   getNodeByKey(key: string): number | null
   /** Get the key for a node */
   getNodeKey(nodeId: number): string | null
+  /** Get the keys for several nodes (aligned to input, null when missing) */
+  getNodeKeys(nodeIds: Array<number>): Array<string | null>
+  /**
+   * Find keyed nodes whose key matches a glob (`*` any run, `?` one char)
+   *
+   * Text before the first wildcard narrows the scan to that key range;
+   * patterns starting with a wildcard scan every key.
+   */
+  findKeys(pattern: string, limit: number): Array<JsNodeKey>
   /** List all node IDs */
   listNodes(): Array<number>
   /** Count all nodes */
   countNodes(): number
+  /** List node IDs in `[minId, maxId)`, in ascending order */
+  listNodesInRange(minId: number, maxId: number): Array<number>
+  /** Count nodes in `[minId, maxId)` */
+  countNodesInRange(minId: number, maxId: number): number
   /** Add an edge */
   addEdge(src: number, etype: number, dst: number): void
   /** Add multiple edges in a single WAL record (fast path) */
@@ -52,6 +92,12 @@ export declare class Database {
   addEdgesWithPropsBatch(edges: Array<JsEdgeWithPropsInput>): void
   /** Add an edge by type name */
   addEdgeByName(src: number, etypeName: string, dst: number): void
+  /**
+   * Reject (or allow again) edges from a node to itself for an edge type
+   *
+   * Written in the current transaction and persisted with the database.
+   */
+  setEtypeNoSelfLoops(etype: number, enabled: boolean): void
   /**
    * Upsert an edge (create if missing, update props)
    *
@@ -62,10 +108,18 @@ export declare class Database {
   deleteEdge(src: number, etype: number, dst: number): void
   /** Check if an edge exists */
   edgeExists(src: number, etype: number, dst: number): boolean
-  /** Get outgoing edges for a node */
-  getOutEdges(nodeId: number): Array<JsEdge>
-  /** Get incoming edges for a node */
-  getInEdges(nodeId: number): Array<JsEdge>
+  /**
+   * Get outgoing edges for a node, optionally only those of one edge type
+   *
+   * With `includeKeys`, each edge also carries the destination's key.
+   */
+  getOutEdges(nodeId: number, etype?: number | undefined | null, includeKeys?: boolean | undefined | null): Array<JsEdge>
+  /**
+   * Get incoming edges for a node, optionally only those of one edge type
+   *
+   * With `includeKeys`, each edge also carries the source's key.
+   */
+  getInEdges(nodeId: number, etype?: number | undefined | null, includeKeys?: boolean | undefined | null): Array<JsEdge>
   /** Get out-degree for a node */
   getOutDegree(nodeId: number): number
   /** Get in-degree for a node */
@@ -89,6 +143,12 @@ export declare class Database {
   countEdgesByType(etype: number): number
   /** Count edges by type name */
   countEdgesByName(etypeName: string): number
+  /**
+   * Count edges from nodes in `setA` to nodes in `setB`
+   *
+   * With `undirected: true`, edges from `setB` into `setA` count as well.
+   */
+  countEdgesBetween(setA: Array<number>, setB: Array<number>, etype?: number | undefined | null, undirected?: boolean | undefined | null): number
   /** Stream nodes in batches */
   streamNodes(options?: StreamOptions | undefined | null): Array<Array<number>>
   /** Stream nodes with properties in batches */
@@ -105,8 +165,18 @@ export declare class Database {
   setNodeProp(nodeId: number, keyId: number, value: JsPropValue): void
   /** Set a node property by key name */
   setNodePropByName(nodeId: number, keyName: string, value: JsPropValue): void
+  /** Get a node property by name, resolving the node by its key */
+  getNodePropByKey(key: string, keyName: string): JsPropValue | null
+  /** Set a node property by name, resolving the node by its key */
+  setNodePropByKey(key: string, keyName: string, value: JsPropValue): void
+  /** Delete a node property by name, resolving the node by its key */
+  deleteNodePropByKey(key: string, keyName: string): void
   /** Delete a node property */
   deleteNodeProp(nodeId: number, keyId: number): void
+  /** Set a node property, returning the previous value (null if unset) */
+  replaceNodeProp(nodeId: number, keyId: number, value: JsPropValue): JsPropValue | null
+  /** Delete a node property, returning the removed value (null if unset) */
+  takeNodeProp(nodeId: number, keyId: number): JsPropValue | null
   /** Get a specific node property */
   getNodeProp(nodeId: number, keyId: number): JsPropValue | null
   /** Get all properties for a node (returns array of {key_id, value} pairs) */
@@ -117,18 +187,44 @@ export declare class Database {
   setEdgePropByName(src: number, etype: number, dst: number, keyName: string, value: JsPropValue): void
   /** Delete an edge property */
   deleteEdgeProp(src: number, etype: number, dst: number, keyId: number): void
+  /** Set an edge property, returning the previous value (null if unset) */
+  replaceEdgeProp(src: number, etype: number, dst: number, keyId: number, value: JsPropValue): JsPropValue | null
+  /** Delete an edge property, returning the removed value (null if unset) */
+  takeEdgeProp(src: number, etype: number, dst: number, keyId: number): JsPropValue | null
   /** Get a specific edge property */
   getEdgeProp(src: number, etype: number, dst: number, keyId: number): JsPropValue | null
   /** Get all properties for an edge (returns array of {key_id, value} pairs) */
   getEdgeProps(src: number, etype: number, dst: number): Array<JsNodeProp> | null
-  /** Set a vector embedding for a node */
-  setNodeVector(nodeId: number, propKeyId: number, vector: Array<number>): void
+  /**
+   * Set a vector embedding for a node
+   *
+   * With `normalize: true` the vector is L2-normalized on write. Don't mix
+   * normalized and unnormalized vectors under the same property key.
+   */
+  setNodeVector(nodeId: number, propKeyId: number, vector: Array<number>, normalize?: boolean | undefined | null): void
+  /**
+   * Overwrite specific dimensions of an existing vector, keeping the rest
+   *
+   * Errors if the node has no vector or an index is out of range.
+   */
+  updateNodeVectorDims(nodeId: number, propKeyId: number, updates: Array<VectorDimUpdate>): void
+  /**
+   * L2-normalize every stored vector under a property key
+   *
+   * Requires an active write transaction. Returns the number of vectors rewritten.
+   */
+  renormalizeVectors(propKeyId: number): number
   /** Get a vector embedding for a node */
   getNodeVector(nodeId: number, propKeyId: number): Array<number> | null
   /** Delete a vector embedding for a node */
   deleteNodeVector(nodeId: number, propKeyId: number): void
   /** Check if a node has a vector embedding */
   hasNodeVector(nodeId: number, propKeyId: number): boolean
+  /**
+   * Find the k node vectors nearest to a query, including vectors
+   * set or deleted in the current transaction
+   */
+  searchNodeVectors(propKeyId: number, query: Array<number>, k: number): Array<JsBruteForceResult>
   /** Get or create a label ID */
   getOrCreateLabel(name: string): number
   /** Get label ID by name */
@@ -186,6 +282,37 @@ export declare class Database {
    * @returns Array of traversal results
    */
   traverseDepth(startNodes: Array<number>, edgeType: number | undefined | null, options: JsTraverseOptions): Array<JsTraversalResult>
+  /**
+   * Walk the graph breadth-first, calling `visitor` on each node
+   *
+   * The visitor is called as `visitor(nodeId, depth, parentId)` once per
+   * reachable node (start nodes have depth 0 and a null parent) and may
+   * return `"skipChildren"` to prune the subtree below that node or `"stop"`
+   * to end the walk; returning nothing continues. Every visit crosses the
+   * NAPI boundary, so prefer `traverse`/`traverseDepth` when no per-node
+   * decision is needed. Throws if a start node doesn't exist.
+   *
+   * @param startNodes - Array of starting node IDs
+   * @param direction - Edge direction to follow
+   * @param edgeType - Optional edge type filter
+   * @param visitor - Callback deciding how the walk proceeds
+   * @returns Number of nodes visited
+   */
+  walk(startNodes: Array<number>, direction: JsTraversalDirection, edgeType: number | undefined | null, visitor: (arg0: number, arg1: number, arg2?: number | undefined | null) => JsWalkControl | undefined | null): number
+  /**
+   * Build the breadth-first spanning tree rooted at a node
+   *
+   * Each node reachable within `maxDepth` hops appears once, with the node it
+   * was first discovered from (null for the root), in BFS order. Enough to
+   * draw an expandable tree without reconstructing parents client-side.
+   *
+   * @param source - Root node ID
+   * @param edgeType - Optional edge type filter
+   * @param direction - Edge direction to follow
+   * @param maxDepth - Maximum depth below the root
+   * @returns Tree nodes with their discovery parent and depth
+   */
+  spanningTree(source: number, edgeType: number | undefined | null, direction: JsTraversalDirection, maxDepth: number): Array<JsSpanningTreeNode>
   /**
    * Count traversal results without materializing them
    *
@@ -203,6 +330,22 @@ export declare class Database {
    * @returns Array of node IDs
    */
   traverseNodeIds(startNodes: Array<number>, steps: Array<JsTraversalStep>, limit?: number | undefined | null): Array<number>
+  /**
+   * Traverse steps and return the k cheapest reached nodes
+   *
+   * Each node keeps the smallest accumulated edge weight over all paths
+   * that reach it at a given step; after the last step the k nodes with the
+   * lowest cost are returned, cheapest first. Weights are read from
+   * `weightKey` (missing, non-numeric, or non-positive weights count as 1);
+   * without a weight key every edge costs 1.
+   *
+   * @param startNodes - Array of starting node IDs
+   * @param steps - Array of traversal steps (direction, edgeType)
+   * @param k - Maximum number of results
+   * @param weightKey - Optional edge property holding the weight
+   * @returns Reached nodes with their accumulated cost
+   */
+  traverseTopK(startNodes: Array<number>, steps: Array<JsTraversalStep>, k: number, weightKey?: string | undefined | null): Array<JsTopKResult>
   /**
    * Find shortest path using Dijkstra's algorithm
    *
@@ -277,20 +420,67 @@ export declare class Database {
   vacuumSingleFile(options?: VacuumOptions | undefined | null): void
   /** Resize the WAL region (single-file only) */
   resizeWal(sizeBytes: number, options?: ResizeWalOptions | undefined | null): void
+  /**
+   * Discard WAL records already in the snapshot, e.g. after a backup.
+   * Fails if there are uncheckpointed changes. Returns the bytes discarded.
+   */
+  truncateWalToSnapshot(): number
   /** Get database statistics */
   stats(): DbStats
-  /** Check database integrity */
-  check(): CheckResult
+  /**
+   * Count and size WAL records by type since the last checkpoint
+   *
+   * Sorted by bytes, largest first.
+   */
+  walRecordBreakdown(): Array<JsWalRecordStats>
+  /**
+   * Check database integrity
+   *
+   * Vector dimension and NaN/Inf problems are warnings unless `strict` is
+   * true, in which case they are errors.
+   */
+  check(strict?: boolean | undefined | null): CheckResult
+  /**
+   * Check database integrity and repair what can be fixed
+   *
+   * Dangling edges are only removed when `allowDestructive` is true.
+   */
+  checkAndRepair(allowDestructive?: boolean | undefined | null): RepairResult
   /** Export database to a JSON object */
   exportToObject(options?: ExportOptions | undefined | null): any
   /** Export database to a JSON file */
   exportToJson(path: string, options?: ExportOptions | undefined | null): ExportResult
   /** Export database to JSONL */
   exportToJsonl(path: string, options?: ExportOptions | undefined | null): ExportResult
-  /** Import database from a JSON object */
-  importFromObject(data: any, options?: ImportOptions | undefined | null): ImportResult
-  /** Import database from a JSON file */
-  importFromJson(path: string, options?: ImportOptions | undefined | null): ImportResult
+  /**
+   * Export database to a directory of JSONL shards plus a manifest
+   *
+   * Each shard holds up to `nodesPerShard` nodes and the edges leaving
+   * them; read it back with `importSharded`.
+   */
+  exportSharded(dir: string, options?: ExportOptions | undefined | null): ExportResult
+  /**
+   * Import database from a JSON object
+   *
+   * `onProgress` is called with running totals before each batch commits;
+   * returning `false` rolls back that batch and stops the import.
+   */
+  importFromObject(data: any, options?: ImportOptions | undefined | null, onProgress?: ((arg: ImportProgress) => boolean | undefined | null) | undefined | null): ImportResult
+  /**
+   * Import database from a JSON file
+   *
+   * `onProgress` is called with running totals before each batch commits;
+   * returning `false` rolls back that batch and stops the import.
+   */
+  importFromJson(path: string, options?: ImportOptions | undefined | null, onProgress?: ((arg: ImportProgress) => boolean | undefined | null) | undefined | null): ImportResult
+  /**
+   * Import database from a sharded export directory
+   *
+   * All nodes are imported before any edge, whatever the shard order, so
+   * edges between shards resolve. `onProgress` behaves as in
+   * `importFromJson`.
+   */
+  importSharded(dir: string, options?: ImportOptions | undefined | null, onProgress?: ((arg: ImportProgress) => boolean | undefined | null) | undefined | null): ImportResult
   /** Check if caching is enabled */
   cacheIsEnabled(): boolean
   /** Invalidate all caches for a node */
@@ -309,10 +499,29 @@ export declare class Database {
   cacheClearProperty(): void
   /** Clear only the traversal cache */
   cacheClearTraversal(): void
-  /** Get cache statistics */
+  /**
+   * Evict least recently used query results down to `maxEntries`,
+   * returning how many were evicted
+   */
+  cacheTrimQuery(maxEntries: number): number
+  /**
+   * Evict least recently used traversals down to `maxEntries`, returning
+   * how many were evicted
+   */
+  cacheTrimTraversal(maxEntries: number): number
+  /**
+   * Evict least recently used node and edge props down to `maxEntries`
+   * each, returning how many were evicted
+   */
+  cacheTrimProperty(maxEntries: number): number
+  /** Get cache statistics (null when caching was not enabled at open) */
   cacheStats(): JsCacheStats | null
   /** Reset cache statistics */
   cacheResetStats(): void
+  /** Recoverable issues noticed while opening (e.g. file locking unavailable) */
+  openWarnings(): Array<string>
+  /** Reset the rolling write/read rates reported by collectMetrics */
+  resetOpRates(): void
 }
 
 /**
@@ -468,15 +677,23 @@ export declare class JsIvfIndex {
   /**
    * Insert a vector into the index
    *
-   * The index must be trained first.
+   * The index must be trained first. The vector goes to its nearest
+   * existing centroid without retraining, so recall degrades as the data
+   * drifts from the training set; check needsRetrain() and rebuild
+   * periodically. With `upsert`, an indexed ID is moved instead of
+   * rejected.
    */
-  insert(vectorId: number, vector: Array<number>): void
+  insert(vectorId: number, vector: Array<number>, upsert?: boolean | undefined | null): void
   /**
    * Delete a vector from the index
    *
-   * Requires the vector data to determine which cluster to remove from.
+   * The vector data is no longer needed; see remove().
    */
   delete(vectorId: number, vector: Array<number>): boolean
+  /** Remove a vector by ID (no vector data needed) */
+  remove(vectorId: number): boolean
+  /** Whether inserts and removes since training warrant a full retrain */
+  needsRetrain(): boolean
   /** Clear all data from the index */
   clear(): void
   /**
@@ -569,49 +786,219 @@ export declare class Kite {
   /** Close the database */
   close(): void
   /** Get a node by key (returns node object with props) */
-  get(nodeType: string, key: unknown, props?: Array<string> | undefined | null): object | null
+  get(nodeType: string, key: unknown, props?: Array<string> | undefined | null, options?: JsGetOptions | undefined | null): object | null
   /** Get a node by ID (returns node object with props) */
-  getById(nodeId: number, props?: Array<string> | undefined | null): object | null
+  getById(nodeId: number, props?: Array<string> | undefined | null, options?: JsGetOptions | undefined | null): object | null
+  /**
+   * Register a computed (virtual) prop for a node type
+   *
+   * `compute` is called with the node's stored props whenever a node of the
+   * type is read with `includeVirtual: true`, and its return value is merged
+   * into the result. Registering a name again replaces it. Only
+   * `get` and `getById` apply virtual props; bulk reads (`getMany`,
+   * `getByIds`, `neighbors`, `all`) return stored props only.
+   */
+  defineVirtualProp(nodeType: string, name: string, compute: (props: Record<string, unknown>) => unknown): void
+  /**
+   * Call `callback` after each commit that changes `propName` on a node
+   * of `nodeType`
+   *
+   * The callback gets `{ nodeId, oldValue, newValue }`, with `null` for a
+   * prop that was unset or deleted (including by deleting the node). Calls
+   * are queued onto the event loop, so they run after the write that caused
+   * them, and a registered listener doesn't keep the process alive. Returns
+   * an id for `offPropChange`.
+   */
+  onPropChange(nodeType: string, propName: string, callback: (change: { nodeId: number; oldValue: unknown; newValue: unknown }) => unknown): number
+  /**
+   * Unregister a listener added with `onPropChange`
+   *
+   * Returns false if `id` isn't registered.
+   */
+  offPropChange(id: number): boolean
   /** Get a lightweight node reference by key (no properties) */
   getRef(nodeType: string, key: unknown): object | null
   /** Get a node ID by key (no properties) */
   getId(nodeType: string, key: unknown): number | null
-  /** Get multiple nodes by ID (returns node objects with props) */
-  getByIds(nodeIds: Array<number>, props?: Array<string> | undefined | null): Array<object>
+  /**
+   * Get multiple nodes by key under a single read lock
+   *
+   * Results line up with `keys`; a key with no node yields `null`.
+   */
+  getMany(nodeType: string, keys: Array<unknown>, props?: Array<string> | undefined | null): Array<object | undefined | null>
+  /**
+   * Get multiple nodes by ID (returns node objects with props)
+   *
+   * Missing ids, and archived ones unless `includeArchived` is set, are
+   * dropped unless `includeMissing` is set, which returns `null` in their
+   * place.
+   */
+  getByIds(nodeIds: Array<number>, props?: Array<string> | undefined | null, options?: JsGetByIdsOptions | undefined | null): Array<object | undefined | null>
+  /**
+   * Get the nodes one hop from `nodeId` as node objects
+   *
+   * Direction defaults to outgoing edges; `edgeType` restricts the hop to
+   * one edge type. A neighbor reached over several edges is returned once;
+   * archived neighbors are skipped.
+   */
+  neighbors(nodeId: number, edgeType?: string | undefined | null, direction?: JsTraversalDirection | undefined | null, props?: Array<string> | undefined | null): Array<object>
+  /**
+   * Count a node's edges without materializing them
+   *
+   * Direction defaults to both, where a self-loop counts twice;
+   * `edgeType` restricts the count to one edge type.
+   */
+  degree(nodeId: number, direction?: JsTraversalDirection | undefined | null, edgeType?: string | undefined | null): number
+  /** `degree` for several nodes, as an object keyed by node ID */
+  degrees(nodeIds: Array<number>, direction?: JsTraversalDirection | undefined | null, edgeType?: string | undefined | null): Record<string, number>
   /** Get a node property value */
   getProp(nodeId: number, propName: string): JsPropValue | null
+  /** Get an integer property; throws if it holds another type */
+  getPropInt(nodeId: number, propName: string): number | null
+  /** Get a float property; throws if it holds another type */
+  getPropFloat(nodeId: number, propName: string): number | null
+  /** Get a string property; throws if it holds another type */
+  getPropString(nodeId: number, propName: string): string | null
+  /** Get a boolean property; throws if it holds another type */
+  getPropBool(nodeId: number, propName: string): boolean | null
+  /** Whether a node has a property set, without converting its value */
+  hasProp(nodeId: number, propName: string): boolean
+  /** Number of properties set on a node (0 if it doesn't exist) */
+  propCount(nodeId: number): number
   /** Set a node property value */
   setProp(nodeId: number, propName: string, value: unknown): void
-  /** Set multiple node property values */
+  /** Set a node property, returning the previous value (null if unset) */
+  replaceProp(nodeId: number, propName: string, value: unknown): JsPropValue | null
+  /** Delete a node property, returning the removed value (null if unset) */
+  takeProp(nodeId: number, propName: string): JsPropValue | null
+  /** Get a node property by the node's key; throws if the key is missing */
+  getPropByKey(nodeType: string, key: unknown, propName: string): JsPropValue | null
+  /** Set a node property by the node's key; throws if the key is missing */
+  setPropByKey(nodeType: string, key: unknown, propName: string, value: unknown): void
+  /** Delete a node property by the node's key; throws if the key is missing */
+  delPropByKey(nodeType: string, key: unknown, propName: string): void
+  /**
+   * Set multiple node property values
+   *
+   * A `$units` entry (prop name -> unit) is checked against the props'
+   * declared units and is not stored.
+   */
   setProps(nodeId: number, props: object): void
+  /**
+   * Set the same property value on many nodes in one transaction
+   *
+   * Returns how many nodes were updated; ids with no node are skipped
+   * unless `strict` is set.
+   */
+  setPropBulk(nodeIds: Array<number>, propName: string, value: unknown, options?: JsSetPropBulkOptions | undefined | null): number
+  /**
+   * Set props on every node of a type whose `matchProp` equals `matchValue`
+   *
+   * Runs in one transaction under one lock and returns how many nodes
+   * matched; archived nodes are skipped.
+   */
+  updateWhere(nodeType: string, matchProp: string, matchValue: unknown, setProps: object): number
+  /**
+   * Content version of a node, usable as an HTTP ETag (null if missing)
+   *
+   * Changes whenever one of the node's props is set or removed.
+   */
+  nodeVersion(nodeId: number): string | null
+  /**
+   * Get a node only if it differs from the caller's known version
+   *
+   * Returns `{ notModified, node, version }`: `notModified` is true when
+   * the node still has `knownVersion`; otherwise `node` and `version` hold
+   * its current state, or are null when the node doesn't exist.
+   */
+  getIfChanged(nodeId: number, knownVersion: string): { notModified: boolean; node: object | null; version: string | null }
   /** Check if a node exists */
   exists(nodeId: number): boolean
   /** Delete a node by ID */
   deleteById(nodeId: number): boolean
+  /**
+   * Archive a node: hide it from get/all/traversals without deleting it
+   *
+   * The archive time is kept out of the node's props, and the
+   * `__archived_at` prop name is reserved. Returns false if the node was
+   * already archived.
+   */
+  archive(nodeId: number): boolean
+  /** Restore an archived node; returns false if it wasn't archived */
+  unarchive(nodeId: number): boolean
+  /** Check whether a node is archived */
+  isArchived(nodeId: number): boolean
+  /** Hard-delete nodes archived at least `olderThanMs` ago; returns the count */
+  purgeArchived(olderThanMs: number): number
+  /**
+   * Merge `removeId` into `keepId`: move its edges, props and labels, then
+   * delete it, in one transaction
+   */
+  mergeNodes(keepId: number, removeId: number, options?: JsMergeOptions | undefined | null): JsMergeResult
   /** Delete a node by key */
   deleteByKey(nodeType: string, key: unknown): boolean
   /** Create an insert builder */
   insert(nodeType: string): KiteInsertBuilder
   /** Create an upsert builder */
   upsert(nodeType: string): KiteUpsertBuilder
+  /**
+   * Get the node at `key`, inserting it with `props` if it doesn't exist
+   *
+   * The lookup and the insert happen under one write lock, so no other
+   * writer can insert the key in between. Returns `{ node, created }`;
+   * `props` are ignored when the node already exists, and must set every
+   * required prop when it doesn't. An archived node at `key` is
+   * unarchived and returned.
+   */
+  getOrInsert(nodeType: string, key: unknown, props?: object | undefined | null): object
   /** Create an update builder by node ID */
   updateById(nodeId: number): KiteUpdateBuilder
   /** Create an upsert builder by node ID */
   upsertById(nodeType: string, nodeId: number): KiteUpsertByIdBuilder
   /** Create an update builder by key */
   updateByKey(nodeType: string, key: unknown): KiteUpdateBuilder
-  /** Link two nodes */
-  link(src: number, edgeType: string, dst: number, props?: object | undefined | null): void
+  /**
+   * Link two nodes
+   *
+   * `validFrom`/`validTo` in `options` limit the edge to a time window
+   * (ms since the Unix epoch, `[validFrom, validTo)`), stored as the
+   * reserved `__valid_from`/`__valid_to` edge props; traversals skip
+   * edges outside it when given `asOf`. Linking an existing edge without
+   * a window keeps its current window; unlink it first to drop the window.
+   */
+  link(src: number, edgeType: string, dst: number, props?: object | undefined | null, options?: JsLinkOptions | undefined | null): void
+  /**
+   * Link many node pairs in one transaction
+   *
+   * Each entry is `{ src, edgeType, dst, props? }`. Edges that already
+   * exist keep their props and are counted as `existing`; nothing is
+   * written if any entry fails.
+   */
+  linkMany(edges: Array<object>): JsLinkManyResult
+  /** Link two nodes by key, optionally creating missing endpoints */
+  linkByKey(srcType: string, srcKey: unknown, edgeType: string, dstType: string, dstKey: unknown, options?: JsLinkByKeyOptions | undefined | null): JsLinkByKeyResult
   /** Unlink two nodes */
   unlink(src: number, edgeType: string, dst: number): boolean
   /** Check if an edge exists */
   hasEdge(src: number, edgeType: string, dst: number): boolean
+  /**
+   * Check many edges at once
+   *
+   * Each entry is `{ src, edgeType, dst }`; returns one boolean per entry,
+   * in order, read under a single lock.
+   */
+  hasEdges(edges: Array<object>): Array<boolean>
   /** Get an edge property value */
   getEdgeProp(src: number, edgeType: string, dst: number, propName: string): JsPropValue | null
-  /** Get all edge properties */
-  getEdgeProps(src: number, edgeType: string, dst: number): Record<string, JsPropValue>
-  /** Set an edge property value */
-  setEdgeProp(src: number, edgeType: string, dst: number, propName: string, value: unknown): void
+  /** Get all edge properties (null if the edge doesn't exist) */
+  getEdgeProps(src: number, edgeType: string, dst: number): Record<string, JsPropValue> | null
+  /**
+   * Set an edge property value
+   *
+   * With `upsertEdge`, a missing edge is created in the same transaction.
+   * Returns true if the edge was created.
+   */
+  setEdgeProp(src: number, edgeType: string, dst: number, propName: string, value: unknown, options?: JsSetEdgePropOptions | undefined | null): boolean
   /** Set multiple edge properties */
   setEdgeProps(src: number, edgeType: string, dst: number, props?: object | undefined | null): void
   /** Delete an edge property */
@@ -621,51 +1008,278 @@ export declare class Kite {
   /** Upsert edge properties with a builder */
   upsertEdge(src: number, edgeType: string, dst: number): KiteUpsertEdgeBuilder
   /** List all nodes of a type (returns array of node objects) */
-  all(nodeType: string): Array<object>
-  /** Count nodes (optionally by type) */
+  all(nodeType: string, options?: JsListOptions | undefined | null): Array<object>
+  /** List all nodes carrying a label, whatever their key prefix */
+  allByLabel(labelName: string, options?: JsListOptions | undefined | null): Array<object>
+  /**
+   * Get one page of the nodes of a type as `{ items, nextCursor, hasMore }`
+   *
+   * Only one page of node objects is built per call, so large types can be
+   * listed without holding them all in memory. Pass `nextCursor` back to
+   * get the following page.
+   */
+  allPaged(nodeType: string, options?: PaginationOptions | undefined | null): { items: Array<object>; nextCursor?: string | null; hasMore: boolean }
+  /**
+   * Find node IDs of a type whose ordered prop lies in `[min, max]`
+   *
+   * Both bounds are inclusive and must be the same kind of value. Only props
+   * declared with `ordered: true` are indexed; the index is rebuilt on open.
+   */
+  findByPropRange(nodeType: string, propName: string, min: unknown, max: unknown): Array<number>
+  /**
+   * Find node IDs of a type whose prop matches a filter
+   *
+   * The filter is `{ eq }`, `{ min, max }` (an ordered prop, inclusive) or
+   * `{ jsonPath, eq }` for a value inside a JSON prop. Paths are dotted
+   * (`author.name`, `tags.0`), optionally prefixed by `$.`.
+   */
+  findWhere(nodeType: string, propName: string, filter: object): Array<number>
+  /** Count nodes (optionally by type), archived ones excluded */
   countNodes(nodeType?: string | undefined | null): number
+  /** Count nodes carrying a label, whatever their key prefix */
+  countNodesByLabel(labelName: string): number
+  /**
+   * Count the distinct values a prop takes across nodes of a type
+   *
+   * With `approximate`, uses a HyperLogLog estimate (about 1% error) that
+   * doesn't hold every value in memory. Null, vector and JSON values aren't
+   * counted.
+   */
+  distinctCount(nodeType: string, propName: string, approximate?: boolean | undefined | null): number
+  /** Last key number allocated for an autoincrement node type */
+  keyCounter(nodeType: string): number
   /** Count edges (optionally by type) */
   countEdges(edgeType?: string | undefined | null): number
   /** List all edges (optionally by type) */
-  allEdges(edgeType?: string | undefined | null): Array<JsFullEdge>
+  allEdges(edgeType?: string | undefined | null, options?: JsListOptions | undefined | null): Array<JsFullEdge>
   /** Check if a path exists between two nodes */
   hasPath(source: number, target: number, edgeType?: string | undefined | null): boolean
   /** Get all nodes reachable within a maximum depth */
   reachableFrom(source: number, maxDepth: number, edgeType?: string | undefined | null): Array<number>
+  /**
+   * Open a cursor that walks the nodes reachable from `source` one depth
+   * level at a time via `nextLevel()`
+   */
+  reachableCursor(source: number, edgeType?: string | undefined | null): KiteReachableCursor
+  /**
+   * Get all nodes reachable within a maximum depth, skipping excluded nodes
+   * (treated as deleted) and excluded edges
+   */
+  reachableFromExcluding(source: number, maxDepth: number, edgeType?: string | undefined | null, excludedNodes?: Array<number> | undefined | null, excludedEdges?: Array<JsFullEdge> | undefined | null): Array<number>
+  /**
+   * Get the declared unit of each prop of a node type
+   *
+   * Props declared without a unit are left out.
+   */
+  propUnits(nodeType: string): Record<string, string>
   /** Get all node type names */
   nodeTypes(): Array<string>
   /** Get all edge type names */
   edgeTypes(): Array<string>
   /** Get database statistics */
   stats(): DbStats
+  /**
+   * Count and size WAL records by type since the last checkpoint
+   *
+   * Sorted by bytes, largest first.
+   */
+  walRecordBreakdown(): Array<JsWalRecordStats>
+  /**
+   * Active secondary indexes: unique and ordered props per node type,
+   * then one vector store per property holding embeddings
+   */
+  listIndexes(): Array<JsIndexInfo>
+  /** Recoverable issues noticed while opening (e.g. WAL crash recovery) */
+  openWarnings(): Array<string>
   /** Get a human-readable description of the database */
   describe(): string
-  /** Check database integrity */
-  check(): CheckResult
-  /** Begin a transaction */
+  /**
+   * Compare the live schema against `target` (only `nodes` and `edges` are read)
+   *
+   * Read-only; returns the type and prop changes a migration would apply.
+   */
+  schemaDiff(target: JsKiteOptions): Array<JsSchemaChange>
+  /**
+   * Check database integrity
+   *
+   * Vector dimension and NaN/Inf problems are warnings unless `strict` is
+   * true, in which case they are errors.
+   */
+  check(strict?: boolean | undefined | null): CheckResult
+  /**
+   * Check database integrity and repair what can be fixed
+   *
+   * Dangling edges are only removed when `allowDestructive` is true.
+   */
+  checkAndRepair(allowDestructive?: boolean | undefined | null): RepairResult
+  /**
+   * Begin a transaction
+   *
+   * If this thread already has one, joins it instead: each nested begin
+   * needs its own commit or rollback, and only the outermost one ends it.
+   */
   begin(readOnly?: boolean | undefined | null): number
+  /**
+   * Begin a read-only transaction pinned to a past MVCC generation
+   *
+   * Fails if the generation's history is no longer retained (past
+   * generations are only kept with `mvccRetainHistory`).
+   */
+  beginReadAt(generation: number): number
+  /** Current MVCC generation (null when MVCC is disabled) */
+  currentGeneration(): number | null
   /** Begin a bulk-load transaction (fast path, MVCC disabled) */
   beginBulk(): number
-  /** Commit the current transaction */
+  /**
+   * Commit the current transaction
+   *
+   * A commit that lost an MVCC conflict throws with `code` set to
+   * `KITE_CONFLICT`; the transaction is gone and can be retried.
+   */
   commit(): void
+  /**
+   * Commit the current transaction and summarize what it changed
+   *
+   * Closing a nested transaction level commits nothing and returns zeros.
+   */
+  commitStats(): JsCommitStats
   /** Rollback the current transaction */
   rollback(): void
+  /**
+   * Mark a point in the current transaction that `rollbackTo` can return to
+   *
+   * Names may repeat; `rollbackTo` uses the most recent one.
+   */
+  savepoint(name: string): void
+  /**
+   * Undo the current transaction's changes since savepoint `name`
+   *
+   * The transaction stays open and commit still writes everything done
+   * before the savepoint.
+   */
+  rollbackTo(name: string): void
   /** Check if there's an active transaction */
   hasTransaction(): boolean
+  /** Id, mode and snapshot of the active transaction (null if none) */
+  transactionInfo(): JsTransactionInfo | null
   /** Perform a checkpoint (compact WAL into snapshot) */
   checkpoint(): void
-  /** Execute a batch of operations atomically */
-  batch(ops: Array<object>): Array<object>
+  /**
+   * Discard WAL records already in the snapshot, e.g. after a backup.
+   * Fails if there are uncheckpointed changes. Returns the bytes discarded.
+   */
+  truncateWalToSnapshot(): number
+  /**
+   * Execute a batch of operations
+   *
+   * Atomic by default. With `bestEffort: true` each op is applied in its own
+   * transaction and the results carry `ok` plus `error` for failed ops, so a
+   * few bad rows don't abort the rest.
+   *
+   * A `createAndLink` op creates a node and links it to existing nodes given
+   * as `links: [{ edgeType, nodeId, incoming? }]` (`incoming` points the
+   * edge at the new node).
+   *
+   * Each result has a `type` and an `affected` count (0 when the op was a
+   * no-op), plus `id` for created nodes, `created` for links, `changed`
+   * for prop sets and `deleted` for deletes and unlinks.
+   */
+  batch(ops: Array<object>, options?: JsBatchOptions | undefined | null): Array<object>
+  /**
+   * Execute a batch atomically, replaying it when the commit loses an
+   * MVCC conflict
+   *
+   * Makes up to `maxAttempts` attempts (default: 3). If every attempt
+   * conflicts, the error thrown has `code` `KITE_CONFLICT`; other errors
+   * are thrown right away.
+   */
+  batchRetry(ops: Array<object>, maxAttempts?: number | undefined | null): Array<object>
+  /**
+   * Check batch ops against the schema without applying them
+   *
+   * Returns `{ index, error }` for every op that `batch` would reject up
+   * front: malformed ops or keys, unknown node or edge types, invalid or
+   * missing required props, forbidden self-loops and deletes of unknown
+   * props. An empty array means the batch passed validation.
+   */
+  batchValidate(ops: Array<object>): Array<object>
   /** Begin a traversal from a node ID */
   from(nodeId: number): KiteTraversal
   /** Begin a traversal from multiple nodes */
   fromNodes(nodeIds: Array<number>): KiteTraversal
+  /**
+   * Start an exploration session rooted at a node
+   *
+   * The session's `expand` only returns nodes it hasn't returned before.
+   */
+  exploration(root: number): KiteExploration
+  /** Open a cursor that walks all node IDs in batches via `next(batchSize)` */
+  nodeCursor(): KiteNodeCursor
   /** Begin a path finding query */
   path(source: number, target: number): KitePath
   /** Begin a path finding query to multiple targets */
   pathToAny(source: number, targets: Array<number>): KitePath
 }
 
+/**
+ * Server-side exploration session that remembers returned nodes
+ *
+ * Each `expand` call returns only neighbors the session hasn't returned
+ * before. Call `dispose` when done to release the visited set.
+ */
+export declare class KiteExploration {
+  /** Node the session started from */
+  root(): number
+  /**
+   * Expand a node, returning IDs of neighbors not returned before
+   *
+   * Direction defaults to outgoing edges.
+   */
+  expand(nodeId: number, edgeType?: string | undefined | null, direction?: JsTraversalDirection | undefined | null): Array<number>
+  /** Whether the session has already returned a node */
+  isVisited(nodeId: number): boolean
+  /** Number of nodes returned so far, root included */
+  visitedCount(): number
+  /** Release the visited set; further calls fail */
+  dispose(): void
+}
+
+/**
+ * Cursor that walks every node ID in ascending order, one batch per call
+ *
+ * Only the requested batch is materialized, so huge node sets can be walked
+ * with bounded memory at the caller's pace. Writes and checkpoints between
+ * calls are fine: later batches skip deleted nodes and include new ones.
+ */
+export declare class KiteNodeCursor {
+  /**
+   * Fetch up to `batchSize` more node IDs; an empty array means caught up
+   *
+   * Archived nodes are skipped, as in `all` and `countNodes`. Throws if
+   * node IDs were reset under the cursor, since its position no longer
+   * refers to the same nodes.
+   */
+  next(batchSize: number): Array<number>
+}
+
+/**
+ * Cursor that walks the nodes reachable from a source one BFS level per call
+ *
+ * The visited set and frontier stay on the native side between calls, so a
+ * UI can render each level as it arrives and stop once it has enough.
+ */
+export declare class KiteReachableCursor {
+  /**
+   * IDs of the nodes first reached at the next depth; an empty array means
+   * everything reachable has been returned
+   */
+  nextLevel(): Array<number>
+  /** Depth of the last non-empty level returned (0 before the first) */
+  depth(): number
+  /** Whether every reachable node has been returned */
+  isDone(): boolean
+}
+
 /** Builder for inserting new nodes */
 export declare class KiteInsertBuilder {
   /** Specify values for a single insert */
@@ -707,6 +1321,17 @@ export declare class KiteTraversal {
   in(edgeType?: string | undefined | null): KiteTraversal
   both(edgeType?: string | undefined | null): KiteTraversal
   traverse(edgeType: string | undefined | null, options: JsTraverseOptions): KiteTraversal
+  /** Only walk through nodes of the given types (dropped between hops otherwise) */
+  allowedNodeTypes(nodeTypes: Array<string>): KiteTraversal
+  /** Let hops land on archived nodes (skipped by default) */
+  includeArchived(): KiteTraversal
+  /**
+   * Only follow edges valid at `timestamp` (ms since the Unix epoch)
+   *
+   * Edges linked with `validFrom`/`validTo` are followed only when
+   * `validFrom <= timestamp < validTo`; edges without a window always are.
+   */
+  asOf(timestamp: number): KiteTraversal
   take(limit: number): KiteTraversal
   select(props: Array<string>): KiteTraversal
   nodes(): Array<number>
@@ -729,14 +1354,20 @@ export declare class KiteUpdateBuilder {
 
 /** Builder for updating edge properties */
 export declare class KiteUpdateEdgeBuilder {
+  /** Create the edge if it doesn't exist before applying the updates */
+  ensure(): void
   /** Set an edge property */
   set(propName: string, value: unknown): void
   /** Remove an edge property */
   unset(propName: string): void
   /** Set multiple edge properties at once */
   setAll(props: object): void
-  /** Execute the edge update */
-  execute(): void
+  /**
+   * Execute the edge update
+   *
+   * Returns whether the edge was created, which only happens with `ensure()`.
+   */
+  execute(): boolean
 }
 
 /** Builder for upserting nodes (insert or update) */
@@ -749,6 +1380,16 @@ export declare class KiteUpsertBuilder {
 
 /** Builder for upserting a node by ID */
 export declare class KiteUpsertByIdBuilder {
+  /**
+   * Set the node key
+   *
+   * For a `parts` key spec the key may be a partial object: the given
+   * fields replace those components of the node's current key and the rest
+   * are kept. If the recomputed key differs, the node is re-keyed in the
+   * same transaction as the property updates. If it collides with another
+   * node's key, execute() throws and nothing is written.
+   */
+  key(key: unknown): void
   /** Set a node property */
   set(propName: string, value: unknown): void
   /** Remove a node property */
@@ -812,6 +1453,9 @@ export declare class VectorIndex {
 /** Inspect a backup without restoring it */
 export declare function backupInfo(backupPath: string): BackupResult
 
+/** List restorable backups in a directory, newest first */
+export declare function listBackups(dir: string): Array<BackupResult>
+
 /** Options for creating a backup */
 export interface BackupOptions {
   /** Force a checkpoint before backup (single-file only) */
@@ -864,6 +1508,16 @@ export interface CheckResult {
   warnings: Array<string>
 }
 
+/** Database check-and-repair result */
+export interface RepairResult {
+  /** Repairs that were applied */
+  repaired: Array<string>
+  /** Problems found but left in place (destructive repairs not allowed) */
+  skipped: Array<string>
+  /** Integrity check after repairs */
+  check: CheckResult
+}
+
 export declare function collectMetrics(db: Database): DatabaseMetrics
 
 export declare function collectReplicationLogTransportJson(db: Database, cursor?: string | undefined | null, maxFrames?: number | undefined | null, maxBytes?: number | undefined | null, includePayload?: boolean | undefined | null): string
@@ -954,6 +1608,10 @@ export interface DatabaseMetrics {
   cache: CacheMetrics
   mvcc?: MvccMetrics
   memory: MemoryMetrics
+  /** Mutations per second over the last 10 seconds */
+  writeRate: number
+  /** Reads per second over the last 10 seconds */
+  readRate: number
   /** Timestamp in milliseconds since epoch */
   collectedAt: number
 }
@@ -985,6 +1643,8 @@ export interface DbStats {
   deltaEdgesDeleted: number
   walSegment: number
   walBytes: number
+  /** Deleted snapshot nodes/edges as a fraction of all snapshot entries */
+  fragmentation: number
   recommendCompact: boolean
   mvccStats?: MvccStats
 }
@@ -1010,7 +1670,13 @@ export interface ExportOptions {
   includeNodes?: boolean
   includeEdges?: boolean
   includeSchema?: boolean
+  /** Export node vector embeddings (default: false) */
+  includeVectors?: boolean
   pretty?: boolean
+  /** Only export nodes with any of these labels (and edges among them) */
+  labels?: Array<string>
+  /** Nodes per shard file for `exportSharded` (default: 100000) */
+  nodesPerShard?: number
 }
 
 /** Export result */
@@ -1040,11 +1706,19 @@ export interface ImportOptions {
   batchSize?: number
 }
 
+/** Running totals passed to an import progress callback */
+export interface ImportProgress {
+  nodesImported: number
+  edgesImported: number
+}
+
 /** Import result */
 export interface ImportResult {
   nodeCount: number
   edgeCount: number
   skipped: number
+  /** The progress callback stopped the import; counts cover committed batches */
+  cancelled: boolean
 }
 
 /** Aggregation method for multi-query search */
@@ -1077,6 +1751,9 @@ export interface JsCacheStats {
   queryCacheHits: number
   queryCacheMisses: number
   queryCacheSize: number
+  keyCacheHits: number
+  keyCacheMisses: number
+  keyCacheSize: number
 }
 
 /** Compression type for snapshot building */
@@ -1101,6 +1778,8 @@ export declare const enum JsDistanceMetric {
 export interface JsEdge {
   etype: number
   nodeId: number
+  /** Neighbor's key (only when requested with `includeKeys`) */
+  nodeKey?: string
 }
 
 /** Edge input for bulk loading */
@@ -1117,6 +1796,8 @@ export interface JsEdgeSpec {
   name: string
   /** Property definitions */
   props?: Record<string, JsPropSpec>
+  /** Reject edges from a node to itself (default: false) */
+  noSelfLoops?: boolean
 }
 
 /** Edge input with properties for batch operations */
@@ -1164,7 +1845,12 @@ export interface JsIvfStats {
 
 /** Key specification for a node type */
 export interface JsKeySpec {
-  /** Key generation strategy: "prefix", "template", "parts" */
+  /**
+   * Key generation strategy: "prefix", "template", "parts", "autoincrement"
+   *
+   * "autoincrement" keys are "prefix" + a number allocated by the database
+   * when an insert passes a null/undefined key.
+   */
   kind: string
   /** Key prefix (e.g., "User:") */
   prefix?: string
@@ -1176,6 +1862,88 @@ export interface JsKeySpec {
   separator?: string
 }
 
+/** Options for `batch` */
+export interface JsBatchOptions {
+  /**
+   * Apply each op independently and report per-op `ok`/`error` instead of
+   * failing the whole batch (default: false)
+   */
+  bestEffort?: boolean
+}
+
+/** Options for reading a node */
+export interface JsGetOptions {
+  /**
+   * Merge props computed by `defineVirtualProp` functions; only `get` and
+   * `getById` take this option (default: false)
+   */
+  includeVirtual?: boolean
+  /** Return the node even if it is archived (default: false) */
+  includeArchived?: boolean
+  /**
+   * Return only `id`/`key`/`type` and load props on first access; the
+   * database must stay open while the node is used (default: false)
+   */
+  lazy?: boolean
+  /** Attach `$units` mapping prop names to their declared units (default: false) */
+  includeUnits?: boolean
+  /**
+   * Rename props in the result, as `{ internalName: externalName }`; can't
+   * rename onto `id`, `key`, `type`, `$units`, `$out` or `$in`, or be
+   * combined with `lazy`
+   */
+  rename?: Record<string, string>
+  /** Return only the props named in `rename` (default: false) */
+  renameOnly?: boolean
+  /**
+   * Attach `$out` and `$in`, mapping each of these edge types to the ids
+   * of the node's neighbors over it, read under the same lock as the node
+   */
+  edges?: Array<string>
+}
+
+/** Options for `getByIds` */
+export interface JsGetByIdsOptions {
+  /**
+   * Return `null` in place of ids with no node, so results line up with the
+   * input (default: false, missing ids are dropped)
+   */
+  includeMissing?: boolean
+  /**
+   * Return archived nodes instead of treating them as missing (default:
+   * false)
+   */
+  includeArchived?: boolean
+}
+
+/** Options for listing nodes or edges */
+export interface JsListOptions {
+  /** Include archived nodes, and edges touching them (default: false) */
+  includeArchived?: boolean
+  /**
+   * Rename node props in the result, as `{ internalName: externalName }`;
+   * can't rename onto `id`, `key` or `type`
+   */
+  rename?: Record<string, string>
+  /** Return only the node props named in `rename` (default: false) */
+  renameOnly?: boolean
+}
+
+/** Options for `setEdgeProp` */
+export interface JsSetEdgePropOptions {
+  /** Create the edge first if it doesn't exist (default: false) */
+  upsertEdge?: boolean
+}
+
+/** Options for `setPropBulk` */
+export interface JsSetPropBulkOptions {
+  /**
+   * Fail (writing nothing) if any id has no node, instead of skipping it
+   * (default: false)
+   */
+  strict?: boolean
+}
+
 /** Options for opening a Kite database */
 export interface JsKiteOptions {
   /** Node type definitions */
@@ -1192,6 +1960,11 @@ export interface JsKiteOptions {
   mvccGcIntervalMs?: number
   /** MVCC retention in ms */
   mvccRetentionMs?: number
+  /**
+   * Record version history on every commit so beginReadAt can serve past
+   * generations (costs a version per write)
+   */
+  mvccRetainHistory?: boolean
   /** MVCC max version chain depth */
   mvccMaxChainDepth?: number
   /** Sync mode: "Full", "Normal", or "Off" (default: "Full") */
@@ -1202,12 +1975,90 @@ export interface JsKiteOptions {
   groupCommitWindowMs?: number
   /** WAL size in megabytes (default: 1MB) */
   walSizeMb?: number
+  /** Separate WAL file location (applies when creating the database) */
+  walPath?: string
   /** WAL usage threshold (0.0-1.0) to trigger auto-checkpoint */
   checkpointThreshold?: number
   /** On close, checkpoint if WAL usage is at or above this threshold (default: 0.2) */
   closeCheckpointIfWalUsageAtLeast?: number
 }
 
+/** Options for `link` */
+export interface JsLinkOptions {
+  /** Start of the edge's validity window, ms since the Unix epoch (inclusive) */
+  validFrom?: number
+  /** End of the edge's validity window, ms since the Unix epoch (exclusive) */
+  validTo?: number
+}
+
+/** Options for `linkByKey` */
+export interface JsLinkByKeyOptions {
+  /**
+   * Create endpoint nodes whose keys don't exist yet (default: false).
+   * Fails if an endpoint to create has required props.
+   */
+  createMissing?: boolean
+}
+
+/** Endpoint IDs resolved (or created) by `linkByKey` */
+export interface JsLinkByKeyResult {
+  src: number
+  dst: number
+}
+
+/** Options for `mergeNodes` */
+export interface JsMergeOptions {
+  /**
+   * Which value wins when both nodes set a prop: "keepWins" or "removeWins"
+   * (default: "keepWins")
+   */
+  propStrategy?: string
+}
+
+/** Counts reported by `linkMany` */
+export interface JsLinkManyResult {
+  /** Edges created */
+  added: number
+  /** Edges that already existed and were left as they are */
+  existing: number
+}
+
+/** Counts reported by `mergeNodes` */
+export interface JsMergeResult {
+  /** Edges re-pointed onto the kept node */
+  edgesMoved: number
+  /** Edges dropped because they became self-loops on a `noSelfLoops` type */
+  selfLoopsDropped: number
+  /** Props written to the kept node */
+  propsMerged: number
+}
+
+/** One step returned by `schemaDiff` */
+export interface JsSchemaChange {
+  /** "addType", "removeType", "addProp", "removeProp", or "changePropType" */
+  op: string
+  /** "node" or "edge" */
+  kind: string
+  /** Node or edge type name */
+  name: string
+  /** Prop name (prop changes only) */
+  prop?: string
+  /** Target type of the prop ("addProp" and "changePropType") */
+  propType?: string
+  /** Live type of the prop ("changePropType" only) */
+  previousType?: string
+}
+
+/** File locking behavior for single-file open options */
+export declare const enum JsLockMode {
+  /** Fail to open if the lock can't be taken */
+  Require = 'Require',
+  /** Lock if supported; otherwise open unlocked and report a warning */
+  BestEffort = 'BestEffort',
+  /** Don't lock */
+  None = 'None'
+}
+
 /** Node property key-value pair for JS */
 export interface JsNodeProp {
   keyId: number
@@ -1288,12 +2139,22 @@ export interface JsPqConfig {
 
 /** Property specification for a node or edge type */
 export interface JsPropSpec {
-  /** Property type: "string", "int", "float", "bool", "vector", "any" */
+  /** Property type: "string", "int", "float", "bool", "enum", "vector", "json", "any" */
   type: string
   /** Whether the property is optional (default: false) */
   optional?: boolean
   /** Default value if not provided */
   default?: JsPropValue
+  /** Allowed values for "enum" props */
+  allowed?: Array<string>
+  /** Reject values already held by another node of the same type (default: false) */
+  unique?: boolean
+  /** Keep a sorted index for `findByPropRange` (default: false) */
+  ordered?: boolean
+  /** Unit the values are measured in, e.g. "meters" */
+  unit?: string
+  /** L2-normalize every written vector ("vector" node props only, default: false) */
+  normalize?: boolean
 }
 
 /** Property value wrapper for JS */
@@ -1304,6 +2165,7 @@ export interface JsPropValue {
   floatValue?: number
   stringValue?: string
   vectorValue?: Array<number>
+  jsonValue?: any
 }
 
 /** Options for vector search */
@@ -1334,6 +2196,16 @@ export declare const enum JsSnapshotParseMode {
   Salvage = 'Salvage'
 }
 
+/** A node of a spanning tree, with the node it was first discovered from */
+export interface JsSpanningTreeNode {
+  /** The node ID */
+  nodeId: number
+  /** Node this one was first reached from (null for the root) */
+  parentId?: number
+  /** Hops from the root */
+  depth: number
+}
+
 /**
  * Synchronization mode for WAL writes
  *
@@ -1351,6 +2223,14 @@ export declare const enum JsSyncMode {
   Off = 'Off'
 }
 
+/** A node reached by a weighted top-k traversal */
+export interface JsTopKResult {
+  /** The node ID that was reached */
+  nodeId: number
+  /** Smallest accumulated edge weight along a path that reached it */
+  cost: number
+}
+
 /** Direction for graph traversal */
 export declare const enum JsTraversalDirection {
   /** Follow outgoing edges */
@@ -1373,6 +2253,16 @@ export interface JsTraversalResult {
   edgeDst?: number
   /** Edge type used (if any) */
   edgeType?: number
+  /**
+   * How the edge was followed: "Out" from its source or "In" from its
+   * destination (if any). Tells the two apart in `Both` traversals.
+   */
+  viaDirection?: JsTraversalDirection
+  /**
+   * Reached through a node whose neighbors were sampled, so the results
+   * are approximate (see `maxNeighborsPerNode`)
+   */
+  sampled: boolean
 }
 
 /** A single traversal step */
@@ -1391,6 +2281,77 @@ export interface JsTraverseOptions {
   maxDepth: number
   /** Whether to only visit unique nodes (default: true) */
   unique?: boolean
+  /**
+   * Expand at most this many neighbors per node, sampling nodes with more
+   * (default: expand all). Results are approximate once sampling kicks in.
+   */
+  maxNeighborsPerNode?: number
+  /**
+   * Seed for neighbor sampling; the same seed picks the same neighbors
+   * (default: 0)
+   */
+  sampleSeed?: number
+}
+
+/** The active transaction (from transactionInfo()) */
+export interface JsTransactionInfo {
+  txid: number
+  readOnly: boolean
+  /** MVCC timestamp the transaction reads at (0 when MVCC is disabled) */
+  snapshotTs: number
+}
+
+/** What a committed transaction changed (from commitStats()) */
+export interface JsCommitStats {
+  nodesCreated: number
+  nodesDeleted: number
+  edgesAdded: number
+  edgesDeleted: number
+  /** Node and edge props set (deletes aren't counted) */
+  propsSet: number
+  /** Time from begin to the end of the commit */
+  durationMs: number
+  /** Bytes of WAL records written, BEGIN and COMMIT included */
+  walBytesWritten: number
+}
+
+/** One active secondary index (from listIndexes()) */
+export interface JsIndexInfo {
+  /** "unique", "ordered" or "vector" */
+  kind: string
+  /** Owning node type (unset for vector stores, which span every type) */
+  nodeType?: string
+  /** Indexed property */
+  propName: string
+  /** Indexed nodes, or live vectors for a vector store */
+  entries: number
+  /** Rough in-memory footprint in bytes */
+  estimatedBytes: number
+}
+
+/** A keyed node matched by findKeys() */
+export interface JsNodeKey {
+  id: number
+  key: string
+}
+
+/** WAL usage by record type (from walRecordBreakdown()) */
+export interface JsWalRecordStats {
+  /** Record type name, e.g. "SetNodeProp" */
+  recordType: string
+  count: number
+  /** Bytes including record padding */
+  bytes: number
+}
+
+/** What a `walk` visitor wants to happen next */
+export declare const enum JsWalkControl {
+  /** Keep walking, expanding this node's neighbors */
+  Continue = 'continue',
+  /** Keep walking, but don't expand this node's neighbors */
+  SkipChildren = 'skipChildren',
+  /** End the walk immediately */
+  Stop = 'stop'
 }
 
 /**
@@ -1430,6 +2391,16 @@ export interface MvccStats {
   lastGcTime: number
   committedWritesSize: number
   committedWritesPruned: number
+  /** Keys that currently have a version chain */
+  versionChains: number
+  /** Versions retained across all chains */
+  versionsRetained: number
+  /** Longest version chain */
+  chainDepthMax: number
+  /** Median version chain length */
+  chainDepthP50: number
+  /** 99th percentile version chain length */
+  chainDepthP99: number
 }
 
 /** Page of node IDs */
@@ -1485,12 +2456,22 @@ export interface OpenOptions {
   mvccGcIntervalMs?: number
   /** MVCC retention in ms */
   mvccRetentionMs?: number
+  /**
+   * Record version history on every commit so beginReadAt can serve past
+   * generations (costs a version per write)
+   */
+  mvccRetainHistory?: boolean
   /** MVCC max version chain depth */
   mvccMaxChainDepth?: number
   /** Page size in bytes (default 4096) */
   pageSize?: number
   /** WAL size in bytes (default 1MB) */
   walSize?: number
+  /**
+   * Separate WAL file location, e.g. on faster storage (applies when
+   * creating the database; reopen finds it via the header)
+   */
+  walPath?: string
   /** Enable auto-checkpoint when WAL usage exceeds threshold */
   autoCheckpoint?: boolean
   /** WAL usage threshold (0.0-1.0) to trigger auto-checkpoint */
@@ -1519,6 +2500,11 @@ export interface OpenOptions {
   groupCommitWindowMs?: number
   /** Snapshot parse mode: "Strict" or "Salvage" (single-file only) */
   snapshotParseMode?: JsSnapshotParseMode
+  /**
+   * Lock mode: "Require", "BestEffort", or "None" (default: "None").
+   * With "BestEffort", check `openWarnings()` after opening.
+   */
+  lockMode?: JsLockMode
 }
 
 /** Options for cursor-based pagination */
@@ -1548,7 +2534,8 @@ export declare const enum PropType {
   Int = 'Int',
   Float = 'Float',
   String = 'String',
-  Vector = 'Vector'
+  Vector = 'Vector',
+  Json = 'Json'
 }
 
 /** Property value tag for binary encoding */
@@ -1558,7 +2545,8 @@ export declare const enum PropValueTag {
   I64 = 2,
   F64 = 3,
   String = 4,
-  VectorF32 = 5
+  VectorF32 = 5,
+  Json = 6
 }
 
 /** Options for resizing WAL */
@@ -1598,6 +2586,8 @@ export interface SingleFileOptimizeOptions {
 export interface StreamOptions {
   /** Number of items per batch (default: 1000) */
   batchSize?: number
+  /** Only stream edges whose endpoints are both in this node set */
+  nodeFilter?: Array<number>
 }
 
 /**
@@ -1617,6 +2607,12 @@ export interface VacuumOptions {
   minWalSize?: number
 }
 
+/** A single dimension change for `updateNodeVectorDims` */
+export interface VectorDimUpdate {
+  index: number
+  value: number
+}
+
 /** Options for creating a vector index */
 export interface VectorIndexOptions {
   /** Vector dimensions (required) */
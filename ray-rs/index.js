@@ -615,11 +615,14 @@ module.exports.healthCheck = nativeBinding.healthCheck
 module.exports.JsAggregation = nativeBinding.JsAggregation
 module.exports.JsCompressionType = nativeBinding.JsCompressionType
 module.exports.JsDistanceMetric = nativeBinding.JsDistanceMetric
+module.exports.JsLockMode = nativeBinding.JsLockMode
 module.exports.JsSnapshotParseMode = nativeBinding.JsSnapshotParseMode
 module.exports.JsSyncMode = nativeBinding.JsSyncMode
 module.exports.JsTraversalDirection = nativeBinding.JsTraversalDirection
+module.exports.JsWalkControl = nativeBinding.JsWalkControl
 module.exports.kite = nativeBinding.kite
 module.exports.kiteSync = nativeBinding.kiteSync
+module.exports.listBackups = nativeBinding.listBackups
 module.exports.openDatabase = nativeBinding.openDatabase
 module.exports.pathConfig = nativeBinding.pathConfig
 module.exports.plus100 = nativeBinding.plus100
@@ -58,6 +58,7 @@ from kitedb._kitedb import (
     SnapshotParseMode,
     DbStats,
     CheckResult,
+    CommitStats,
     CacheStats,
     ExportOptions,
     ImportOptions,
@@ -91,6 +92,7 @@ from kitedb._kitedb import (
     TraversalResult as LowLevelTraversalResult,
     PathResult as LowLevelPathResult,
     PathEdge,
+    SpanningTreeNode,
     
     # Vector search classes
     IvfIndex,
@@ -119,6 +121,7 @@ from kitedb._kitedb import (
     create_backup,
     restore_backup,
     get_backup_info,
+    list_backups,
     create_offline_backup,
     version,
     brute_force_search,
@@ -248,6 +251,7 @@ __all__ = [
     "SnapshotParseMode",
     "DbStats",
     "CheckResult",
+    "CommitStats",
     "CacheStats",
     "ExportOptions",
     "ImportOptions",
@@ -281,6 +285,7 @@ __all__ = [
     "LowLevelTraversalResult",
     "LowLevelPathResult",
     "PathEdge",
+    "SpanningTreeNode",
     
     # Vector
     "IvfIndex",
@@ -309,6 +314,7 @@ __all__ = [
     "create_backup",
     "restore_backup",
     "get_backup_info",
+    "list_backups",
     "create_offline_backup",
     "version",
     "brute_force_search",
@@ -31,6 +31,7 @@ class OpenOptions:
     sync_mode: Optional["SyncMode"]
     group_commit_enabled: Optional[bool]
     group_commit_window_ms: Optional[int]
+    wal_path: Optional[str]
     
     def __init__(
         self,
@@ -57,6 +58,7 @@ class OpenOptions:
         sync_mode: Optional["SyncMode"] = None,
         group_commit_enabled: Optional[bool] = None,
         group_commit_window_ms: Optional[int] = None,
+        wal_path: Optional[str] = None,
     ) -> None: ...
 
 class SyncMode:
@@ -85,6 +87,7 @@ class DbStats:
     delta_edges_deleted: int
     wal_segment: int
     wal_bytes: int
+    fragmentation: float
     recommend_compact: bool
     mvcc_stats: Optional[MvccStats]
 
@@ -97,6 +100,21 @@ class MvccStats:
     last_gc_time: int
     committed_writes_size: int
     committed_writes_pruned: int
+    version_chains: int
+    versions_retained: int
+    chain_depth_max: int
+    chain_depth_p50: int
+    chain_depth_p99: int
+
+class CommitStats:
+    """What a committed transaction changed."""
+    nodes_created: int
+    nodes_deleted: int
+    edges_added: int
+    edges_deleted: int
+    props_set: int
+    duration_ms: int
+    wal_bytes_written: int
 
 class CheckResult:
     """Database integrity check result."""
@@ -115,19 +133,28 @@ class CacheStats:
     query_cache_hits: int
     query_cache_misses: int
     query_cache_size: int
+    key_cache_hits: int
+    key_cache_misses: int
+    key_cache_size: int
 
 class ExportOptions:
     """Options for export."""
     include_nodes: Optional[bool]
     include_edges: Optional[bool]
     include_schema: Optional[bool]
+    include_vectors: Optional[bool]
     pretty: Optional[bool]
+    labels: Optional[List[str]]
+    nodes_per_shard: Optional[int]
     def __init__(
         self,
         include_nodes: Optional[bool] = None,
         include_edges: Optional[bool] = None,
         include_schema: Optional[bool] = None,
+        include_vectors: Optional[bool] = None,
         pretty: Optional[bool] = None,
+        labels: Optional[List[str]] = None,
+        nodes_per_shard: Optional[int] = None,
     ) -> None: ...
 
 class ImportOptions:
@@ -154,7 +181,10 @@ class ImportResult:
 class StreamOptions:
     """Options for streaming node/edge batches."""
     batch_size: Optional[int]
-    def __init__(self, batch_size: Optional[int] = None) -> None: ...
+    node_filter: Optional[List[int]]
+    def __init__(
+        self, batch_size: Optional[int] = None, node_filter: Optional[List[int]] = None
+    ) -> None: ...
 
 class PaginationOptions:
     """Options for cursor-based pagination."""
@@ -302,6 +332,10 @@ class PropValue:
     def string(value: str) -> PropValue: ...
     @staticmethod
     def vector(value: List[float]) -> PropValue: ...
+    @staticmethod
+    def json(value: str) -> PropValue:
+        """JSON document from serialized text (raises ValueError if invalid)."""
+        ...
     def value(self) -> Any: ...
 
 class Edge:
@@ -331,6 +365,13 @@ class TraversalResult:
     edge_src: Optional[int]
     edge_dst: Optional[int]
     edge_type: Optional[int]
+    via_direction: Optional[str]
+
+class SpanningTreeNode:
+    """A node of a spanning tree, with the node it was first discovered from."""
+    node_id: int
+    parent_id: Optional[int]
+    depth: int
 
 class PathResult:
     """Result of a pathfinding query."""
@@ -371,6 +412,7 @@ class Database:
     def begin(self, read_only: Optional[bool] = None) -> int: ...
     def begin_bulk(self) -> int: ...
     def commit(self) -> None: ...
+    def commit_stats(self) -> CommitStats: ...
     def rollback(self) -> None: ...
     def has_transaction(self) -> bool: ...
     
@@ -380,6 +422,7 @@ class Database:
     def node_exists(self, node_id: int) -> bool: ...
     def get_node_by_key(self, key: str) -> Optional[int]: ...
     def get_node_key(self, node_id: int) -> Optional[str]: ...
+    def find_keys(self, pattern: str, limit: int) -> List[Tuple[int, str]]: ...
     def list_nodes(self) -> List[int]: ...
     def count_nodes(self) -> int: ...
     def list_nodes_with_prefix(self, prefix: str) -> List[int]: ...
@@ -397,8 +440,8 @@ class Database:
     def delete_edge(self, src: int, etype: int, dst: int) -> None: ...
     def upsert_edge(self, src: int, etype: int, dst: int, props: List[Tuple[int, Optional[PropValue]]]) -> bool: ...
     def edge_exists(self, src: int, etype: int, dst: int) -> bool: ...
-    def get_out_edges(self, node_id: int) -> List[Edge]: ...
-    def get_in_edges(self, node_id: int) -> List[Edge]: ...
+    def get_out_edges(self, node_id: int, etype: Optional[int] = None) -> List[Edge]: ...
+    def get_in_edges(self, node_id: int, etype: Optional[int] = None) -> List[Edge]: ...
     def get_out_degree(self, node_id: int) -> int: ...
     def get_in_degree(self, node_id: int) -> int: ...
     def count_edges(self) -> int: ...
@@ -406,12 +449,22 @@ class Database:
     def list_edges_by_name(self, etype_name: str) -> List[FullEdge]: ...
     def count_edges_by_type(self, etype: int) -> int: ...
     def count_edges_by_name(self, etype_name: str) -> int: ...
+    def count_edges_between(
+        self,
+        set_a: List[int],
+        set_b: List[int],
+        etype: Optional[int] = None,
+        undirected: bool = False,
+    ) -> int: ...
     
     # Property operations
     def set_node_prop(self, node_id: int, key_id: int, value: PropValue) -> None: ...
     def set_node_prop_by_name(self, node_id: int, key_name: str, value: PropValue) -> None: ...
     def delete_node_prop(self, node_id: int, key_id: int) -> None: ...
     def get_node_prop(self, node_id: int, key_id: int) -> Optional[PropValue]: ...
+    def get_node_prop_by_key(self, key: str, key_name: str) -> Optional[PropValue]: ...
+    def set_node_prop_by_key(self, key: str, key_name: str, value: PropValue) -> None: ...
+    def delete_node_prop_by_key(self, key: str, key_name: str) -> None: ...
     def get_node_prop_string(self, node_id: int, key_id: int) -> Optional[str]: ...
     def get_node_prop_int(self, node_id: int, key_id: int) -> Optional[int]: ...
     def get_node_prop_float(self, node_id: int, key_id: int) -> Optional[float]: ...
@@ -431,9 +484,15 @@ class Database:
     
     # Vector operations
     def set_node_vector(self, node_id: int, prop_key_id: int, vector: List[float]) -> None: ...
+    def update_node_vector_dims(
+        self, node_id: int, prop_key_id: int, updates: List[Tuple[int, float]]
+    ) -> None: ...
     def get_node_vector(self, node_id: int, prop_key_id: int) -> Optional[List[float]]: ...
     def delete_node_vector(self, node_id: int, prop_key_id: int) -> None: ...
     def has_node_vector(self, node_id: int, prop_key_id: int) -> bool: ...
+    def search_node_vectors(
+        self, prop_key_id: int, query: List[float], k: int
+    ) -> List[BruteForceResult]: ...
     
     # Schema operations
     def get_or_create_label(self, name: str) -> int: ...
@@ -442,6 +501,7 @@ class Database:
     def get_or_create_etype(self, name: str) -> int: ...
     def get_etype_id(self, name: str) -> Optional[int]: ...
     def get_etype_name(self, id: int) -> Optional[str]: ...
+    def set_etype_no_self_loops(self, etype: int, enabled: bool) -> None: ...
     def get_or_create_propkey(self, name: str) -> int: ...
     def get_propkey_id(self, name: str) -> Optional[int]: ...
     def get_propkey_name(self, id: int) -> Optional[str]: ...
@@ -457,17 +517,20 @@ class Database:
     # Maintenance
     def checkpoint(self) -> None: ...
     def background_checkpoint(self) -> None: ...
+    def truncate_wal_to_snapshot(self) -> int: ...
     def should_checkpoint(self, threshold: Optional[float] = None) -> bool: ...
     def optimize(self) -> None: ...
     def stats(self) -> DbStats: ...
-    def check(self) -> CheckResult: ...
+    def check(self, strict: bool = False) -> CheckResult: ...
 
     # Export / Import
     def export_to_object(self, options: Optional[ExportOptions] = None) -> Any: ...
     def export_to_json(self, path: str, options: Optional[ExportOptions] = None) -> ExportResult: ...
     def export_to_jsonl(self, path: str, options: Optional[ExportOptions] = None) -> ExportResult: ...
+    def export_sharded(self, dir: str, options: Optional[ExportOptions] = None) -> ExportResult: ...
     def import_from_object(self, data: Any, options: Optional[ImportOptions] = None) -> ImportResult: ...
     def import_from_json(self, path: str, options: Optional[ImportOptions] = None) -> ImportResult: ...
+    def import_sharded(self, dir: str, options: Optional[ImportOptions] = None) -> ImportResult: ...
 
     # Streaming / Pagination
     def stream_nodes(self, options: Optional[StreamOptions] = None) -> List[List[int]]: ...
@@ -487,6 +550,9 @@ class Database:
     def cache_clear_key(self) -> None: ...
     def cache_clear_property(self) -> None: ...
     def cache_clear_traversal(self) -> None: ...
+    def cache_trim_query(self, max_entries: int) -> int: ...
+    def cache_trim_traversal(self, max_entries: int) -> int: ...
+    def cache_trim_property(self, max_entries: int) -> int: ...
     def cache_stats(self) -> Optional[CacheStats]: ...
     def cache_reset_stats(self) -> None: ...
     
@@ -506,6 +572,13 @@ class Database:
         direction: Optional[str] = None,
         unique: Optional[bool] = None,
     ) -> List[TraversalResult]: ...
+    def spanning_tree(
+        self,
+        source: int,
+        max_depth: int,
+        etype: Optional[int] = None,
+        direction: Optional[str] = None,
+    ) -> List[SpanningTreeNode]: ...
     def traverse_multi(self, start_ids: List[int], steps: List[Tuple[str, Optional[int]]]) -> List[Tuple[int, Optional[str]]]: ...
     def traverse_multi_count(self, start_ids: List[int], steps: List[Tuple[str, Optional[int]]]) -> int: ...
     
@@ -659,6 +732,7 @@ def health_check(db: Database) -> HealthCheckResult: ...
 def create_backup(db: Database, backup_path: str, options: Optional[BackupOptions] = None) -> BackupResult: ...
 def restore_backup(backup_path: str, restore_path: str, options: Optional[RestoreOptions] = None) -> str: ...
 def get_backup_info(backup_path: str) -> BackupResult: ...
+def list_backups(dir: str) -> List[BackupResult]: ...
 def create_offline_backup(
     db_path: str,
     backup_path: str,
@@ -733,7 +807,7 @@ class IvfIndex:
     def __init__(self, dimensions: int, config: Optional[IvfConfig] = None) -> None: ...
     def add_training_vectors(self, vectors: List[float], num_vectors: int) -> None: ...
     def train(self) -> None: ...
-    def insert(self, vector_id: int, vector: List[float]) -> None: ...
+    def insert(self, vector_id: int, vector: List[float], upsert: bool = False) -> None: ...
     def delete(self, vector_id: int, vector: List[float]) -> bool: ...
     def clear(self) -> None: ...
     def search(
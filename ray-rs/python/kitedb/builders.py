@@ -21,6 +21,7 @@ Example:
 
 from __future__ import annotations
 
+import json
 from dataclasses import dataclass, field
 from typing import (
     TYPE_CHECKING,
@@ -121,6 +122,8 @@ def to_prop_value(prop_def: PropDef[Any], value: Any, PropValue: type) -> PropVa
         return PropValue.bool(bool(value))
     elif prop_def.type == "vector":
         return PropValue.vector([float(v) for v in value])
+    elif prop_def.type == "json":
+        return PropValue.json(json.dumps(value))
     else:
         raise ValueError(f"Unknown property type: {prop_def.type}")
 
@@ -785,9 +785,13 @@ class Kite:
         """Get database statistics."""
         return self._db.stats()
 
-    def check(self) -> Any:
-        """Check database integrity."""
-        result = self._db.check()
+    def check(self, strict: bool = False) -> Any:
+        """Check database integrity.
+
+        Vector dimension and NaN/Inf problems are warnings unless ``strict``
+        is set, in which case they are errors.
+        """
+        result = self._db.check(strict)
         for edge_name, edge_def in self._edges.items():
             if getattr(edge_def, "_etype_id", None) is None:
                 result.warnings.append(
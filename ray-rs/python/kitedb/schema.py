@@ -39,7 +39,7 @@ from typing import (
 # Property Type System
 # ============================================================================
 
-PropType = Literal["string", "int", "float", "bool", "vector"]
+PropType = Literal["string", "int", "float", "bool", "vector", "json"]
 
 # Type variable for property values
 T = TypeVar("T")
@@ -58,6 +58,7 @@ class PropDef(Generic[T]):
     - "float" -> float
     - "bool" -> bool
     - "vector" -> list[float]
+    - "json" -> dict / list (any JSON-serializable value)
     """
     name: str
     type: PropType
@@ -119,6 +120,14 @@ class PropBuilder:
         Stored as float32 arrays.
         """
         return PropDef(name=name, type="vector")
+    
+    @staticmethod
+    def json(name: str) -> PropDef[Any]:
+        """
+        JSON document property.
+        Stored as serialized JSON; reads return the decoded value.
+        """
+        return PropDef(name=name, type="json")
 
 
 # Global prop builder instance
@@ -13,14 +13,19 @@
 
 use crate::core::single_file::{
   close_single_file, close_single_file_with_options, is_single_file_path, open_single_file,
-  single_file_extension, FullEdge, SingleFileCloseOptions, SingleFileDB, SingleFileOpenOptions,
-  SyncMode,
+  single_file_extension, FullEdge, PropChange, PropListenerId, SingleFileCloseOptions,
+  SingleFileDB, SingleFileOpenOptions, SyncMode,
 };
 use crate::error::{KiteError, Result};
 use crate::replication::types::ReplicationRole;
+use crate::streaming::{Page, PaginationOptions};
 use crate::types::*;
+use crate::util::hll::HyperLogLog;
+use xxhash_rust::xxh64::Xxh64;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::hash::Hasher;
+use std::ops::Bound;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
@@ -169,7 +174,31 @@ fn node_by_key(handle: &TxHandle, key: &str) -> Option<NodeId> {
 }
 
 fn node_by_key_db(db: &SingleFileDB, key: &str) -> Option<NodeId> {
-  db.node_by_key(key)
+  db.node_by_key_cached(key)
+}
+
+/// Look up a node by key suffix, creating it when missing and `create` is set
+fn resolve_or_create_by_key(
+  handle: &mut TxHandle,
+  node_def: &NodeDef,
+  key_suffix: &str,
+  create: bool,
+) -> Result<NodeId> {
+  let full_key = node_def.key(key_suffix);
+  if let Some(node_id) = node_by_key(handle, &full_key) {
+    return Ok(node_id);
+  }
+  if !create {
+    return Err(KiteError::KeyNotFound(full_key));
+  }
+  // The endpoint is created without props, so it can't satisfy required ones
+  node_def.check_required_props(&HashMap::new())?;
+  let node_opts = NodeOpts {
+    key: Some(full_key),
+    labels: node_def.label_id.map(|id| vec![id]),
+    props: None,
+  };
+  create_node(handle, node_opts)
 }
 
 fn node_prop(handle: &TxHandle, node_id: NodeId, key_id: PropKeyId) -> Option<PropValue> {
@@ -280,6 +309,21 @@ fn neighbors_in_db(db: &SingleFileDB, node_id: NodeId, etype: Option<ETypeId>) -
   }
 }
 
+fn degree_db(
+  db: &SingleFileDB,
+  node_id: NodeId,
+  direction: TraversalDirection,
+  etype: Option<ETypeId>,
+) -> usize {
+  match direction {
+    TraversalDirection::Out => neighbors_out_db(db, node_id, etype).len(),
+    TraversalDirection::In => neighbors_in_db(db, node_id, etype).len(),
+    TraversalDirection::Both => {
+      neighbors_out_db(db, node_id, etype).len() + neighbors_in_db(db, node_id, etype).len()
+    }
+  }
+}
+
 fn edge_prop_db(
   db: &SingleFileDB,
   src: NodeId,
@@ -367,6 +411,16 @@ pub struct PropDef {
   pub required: bool,
   /// Default value (if any)
   pub default: Option<PropValue>,
+  /// Allowed values (enum properties only)
+  pub allowed: Option<EnumValues>,
+  /// Whether values must be unique across nodes of the owning type
+  pub unique: bool,
+  /// Whether values are kept in a sorted index for range queries
+  pub ordered: bool,
+  /// Unit the values are measured in (e.g. "meters"), metadata only
+  pub unit: Option<String>,
+  /// Whether vector values are kept unit length (vector props only)
+  pub normalize: bool,
 }
 
 /// Property type hints
@@ -376,44 +430,142 @@ pub enum PropType {
   Int,
   Float,
   Bool,
+  /// String constrained to a fixed set of allowed values
+  Enum,
+  /// Structured JSON document
+  Json,
+  /// Float32 embedding vector
+  Vector,
   Any,
 }
 
+/// Allowed value set for an enum property
+///
+/// `Kite::open` adds the values to the prop key's enum dictionary (see
+/// [`SingleFileDB::set_prop_enum_values`]), so they are stored as small
+/// interned ids while reads and exports still see the strings.
+#[derive(Debug, Clone, Default)]
+pub struct EnumValues {
+  values: Vec<String>,
+  lookup: HashSet<String>,
+}
+
+impl EnumValues {
+  pub fn new<I, S>(values: I) -> Self
+  where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+  {
+    let mut out = Self::default();
+    for value in values {
+      let value = value.into();
+      if out.lookup.insert(value.clone()) {
+        out.values.push(value);
+      }
+    }
+    out
+  }
+
+  /// Whether `value` is allowed
+  pub fn contains(&self, value: &str) -> bool {
+    self.lookup.contains(value)
+  }
+
+  /// Allowed values in declaration order
+  pub fn values(&self) -> &[String] {
+    &self.values
+  }
+}
+
 impl PropDef {
-  pub fn string(name: &str) -> Self {
+  /// Optional, unconstrained property of `prop_type`
+  fn of_type(name: &str, prop_type: PropType) -> Self {
     Self {
       name: name.to_string(),
-      prop_type: PropType::String,
+      prop_type,
       required: false,
       default: None,
+      allowed: None,
+      unique: false,
+      ordered: false,
+      unit: None,
+      normalize: false,
     }
   }
 
+  pub fn string(name: &str) -> Self {
+    Self::of_type(name, PropType::String)
+  }
+
   pub fn int(name: &str) -> Self {
-    Self {
-      name: name.to_string(),
-      prop_type: PropType::Int,
-      required: false,
-      default: None,
-    }
+    Self::of_type(name, PropType::Int)
   }
 
   pub fn float(name: &str) -> Self {
-    Self {
-      name: name.to_string(),
-      prop_type: PropType::Float,
-      required: false,
-      default: None,
-    }
+    Self::of_type(name, PropType::Float)
   }
 
   pub fn bool(name: &str) -> Self {
+    Self::of_type(name, PropType::Bool)
+  }
+
+  pub fn json(name: &str) -> Self {
+    Self::of_type(name, PropType::Json)
+  }
+
+  /// Float32 vector property; writes of any other value type are rejected
+  pub fn vector(name: &str) -> Self {
+    Self::of_type(name, PropType::Vector)
+  }
+
+  /// String property restricted to `allowed` values (checked on write, stored as interned ids)
+  pub fn string_enum<I, S>(name: &str, allowed: I) -> Self
+  where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+  {
     Self {
-      name: name.to_string(),
-      prop_type: PropType::Bool,
-      required: false,
-      default: None,
+      allowed: Some(EnumValues::new(allowed)),
+      ..Self::of_type(name, PropType::Enum)
+    }
+  }
+
+  /// Check a value against this property's constraints
+  ///
+  /// Null always passes since it clears the property.
+  pub fn check(&self, value: &PropValue) -> Result<()> {
+    if self.prop_type == PropType::Vector {
+      return match value {
+        PropValue::Null | PropValue::VectorF32(_) => Ok(()),
+        other => Err(KiteError::Constraint(format!(
+          "Property '{}' is a vector, got {:?}",
+          self.name,
+          other.tag()
+        ))),
+      };
     }
+    let Some(allowed) = self.allowed.as_ref() else {
+      return Ok(());
+    };
+    match value {
+      PropValue::Null => Ok(()),
+      PropValue::String(s) if allowed.contains(s) => Ok(()),
+      other => Err(KiteError::Constraint(format!(
+        "Property '{}' must be one of [{}], got {other:?}",
+        self.name,
+        allowed.values().join(", ")
+      ))),
+    }
+  }
+
+  /// Whether writes to this property need checking or indexing
+  pub fn is_constrained(&self) -> bool {
+    self.allowed.is_some() || self.prop_type == PropType::Vector || self.is_indexed()
+  }
+
+  /// Whether this property is tracked by a value index
+  pub fn is_indexed(&self) -> bool {
+    self.unique || self.ordered
   }
 
   pub fn required(mut self) -> Self {
@@ -421,10 +573,48 @@ impl PropDef {
     self
   }
 
+  /// Require values to be unique across nodes of the owning type
+  ///
+  /// Null clears the property and never conflicts. Only applies to node
+  /// properties.
+  pub fn unique(mut self) -> Self {
+    self.unique = true;
+    self
+  }
+
+  /// Keep values in a sorted index so `Kite::find_by_prop_range` can serve
+  /// range queries on this property
+  ///
+  /// Only applies to node properties. The index lives in memory and is
+  /// rebuilt from the stored data each time the database is opened.
+  pub fn ordered(mut self) -> Self {
+    self.ordered = true;
+    self
+  }
+
   pub fn default(mut self, value: PropValue) -> Self {
     self.default = Some(value);
     self
   }
+
+  /// L2-normalize every vector written to this property
+  ///
+  /// Cosine search over the property's key can then score by dot product
+  /// (see [`SingleFileDB::set_vector_normalized`]). Only applies to vector
+  /// node properties.
+  pub fn normalized(mut self) -> Self {
+    self.normalize = true;
+    self
+  }
+
+  /// Record the unit values are measured in
+  ///
+  /// Values themselves are stored as-is; see `Kite::check_units` for
+  /// validating units a writer declares.
+  pub fn unit(mut self, unit: &str) -> Self {
+    self.unit = Some(unit.to_string());
+    self
+  }
 }
 
 /// Node type definition
@@ -440,6 +630,8 @@ pub struct NodeDef {
   pub label_id: Option<LabelId>,
   /// Property key IDs (set after registration)
   pub prop_key_ids: HashMap<String, PropKeyId>,
+  /// Whether key suffixes are allocated from a per-type counter
+  pub autoincrement: bool,
 }
 
 impl NodeDef {
@@ -450,6 +642,7 @@ impl NodeDef {
       key_prefix: key_prefix.to_string(),
       label_id: None,
       prop_key_ids: HashMap::new(),
+      autoincrement: false,
     }
   }
 
@@ -458,10 +651,86 @@ impl NodeDef {
     self
   }
 
+  /// Allocate key suffixes from an incrementing counter (see `Kite::next_key`)
+  ///
+  /// Explicit keys are still accepted; the counter skips over any numeric
+  /// suffix that is already taken.
+  pub fn autoincrement(mut self) -> Self {
+    self.autoincrement = true;
+    self
+  }
+
   /// Generate a full key from a key suffix
   pub fn key(&self, suffix: &str) -> String {
     format!("{}{}", self.key_prefix, suffix)
   }
+
+  /// Check a property write against this node type's schema
+  pub fn check_prop(&self, prop_name: &str, value: &PropValue) -> Result<()> {
+    match self.props.get(prop_name) {
+      Some(prop) => prop.check(value),
+      None => Ok(()),
+    }
+  }
+
+  /// Check that `props` sets every `required` prop of this node type
+  pub fn check_required_props(&self, props: &HashMap<String, PropValue>) -> Result<()> {
+    let mut missing: Vec<&str> = self
+      .props
+      .values()
+      .filter(|prop| prop.required && !props.contains_key(&prop.name))
+      .map(|prop| prop.name.as_str())
+      .collect();
+    if missing.is_empty() {
+      return Ok(());
+    }
+    missing.sort_unstable();
+    Err(KiteError::Constraint(format!(
+      "Missing required properties for {}: {}",
+      self.name,
+      missing.join(", ")
+    )))
+  }
+
+  /// Whether any property of this node type has write constraints
+  pub fn has_constraints(&self) -> bool {
+    self.props.values().any(PropDef::is_constrained)
+  }
+
+  /// Units of the props that declare one, by prop name
+  pub fn prop_units(&self) -> HashMap<String, String> {
+    self
+      .props
+      .values()
+      .filter_map(|prop| Some((prop.name.clone(), prop.unit.clone()?)))
+      .collect()
+  }
+
+  /// Check units a writer declared against the props' declared units
+  pub fn check_units(&self, declared: &HashMap<String, String>) -> Result<()> {
+    for (prop_name, unit) in declared {
+      let expected = self
+        .props
+        .get(prop_name)
+        .and_then(|prop| prop.unit.as_deref());
+      match expected {
+        Some(expected) if expected == unit => {}
+        Some(expected) => {
+          return Err(KiteError::Constraint(format!(
+            "Property '{prop_name}' of {} is in {expected}, got {unit}",
+            self.name
+          )))
+        }
+        None => {
+          return Err(KiteError::Constraint(format!(
+            "Property '{prop_name}' of {} has no unit, got {unit}",
+            self.name
+          )))
+        }
+      }
+    }
+    Ok(())
+  }
 }
 
 /// Edge type definition
@@ -475,6 +744,8 @@ pub struct EdgeDef {
   pub etype_id: Option<ETypeId>,
   /// Property key IDs (set after registration)
   pub prop_key_ids: HashMap<String, PropKeyId>,
+  /// Reject edges from a node to itself
+  pub no_self_loops: bool,
 }
 
 impl EdgeDef {
@@ -484,6 +755,7 @@ impl EdgeDef {
       props: HashMap::new(),
       etype_id: None,
       prop_key_ids: HashMap::new(),
+      no_self_loops: false,
     }
   }
 
@@ -491,1383 +763,1956 @@ impl EdgeDef {
     self.props.insert(prop.name.clone(), prop);
     self
   }
-}
-
-// ============================================================================
-// Node Reference
-// ============================================================================
 
-/// Reference to a node in the database
-#[derive(Debug, Clone)]
-pub struct NodeRef {
-  /// Node ID
-  id: NodeId,
-  /// Full key (if available)
-  key: Option<String>,
-  /// Node type name
-  node_type: Arc<str>,
-}
+  /// Reject edges of this type whose source and destination are the same node
+  ///
+  /// Enforced on every edge-add path, including the raw database handle.
+  pub fn no_self_loops(mut self) -> Self {
+    self.no_self_loops = true;
+    self
+  }
 
-impl NodeRef {
-  pub fn new(id: NodeId, key: Option<String>, node_type: impl Into<Arc<str>>) -> Self {
-    Self {
-      id,
-      key,
-      node_type: node_type.into(),
+  /// Check a property write against this edge type's schema
+  pub fn check_prop(&self, prop_name: &str, value: &PropValue) -> Result<()> {
+    match self.props.get(prop_name) {
+      Some(prop) => prop.check(value),
+      None => Ok(()),
     }
   }
+}
 
-  pub fn id(&self) -> NodeId {
-    self.id
-  }
+/// Whether a schema change concerns a node type or an edge type
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum SchemaKind {
+  Node,
+  Edge,
+}
 
-  pub fn key(&self) -> Option<&str> {
-    self.key.as_deref()
-  }
+/// One step needed to turn the live schema into a target schema
+///
+/// Produced by [`Kite::schema_diff`]. Props of added or removed types are
+/// not listed separately; they come with the type.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum SchemaChange {
+  /// Type present only in the target
+  AddType { kind: SchemaKind, name: String },
+  /// Type present only in the live schema
+  RemoveType { kind: SchemaKind, name: String },
+  /// Prop present only on the target's definition of `owner`
+  AddProp {
+    kind: SchemaKind,
+    owner: String,
+    prop: String,
+    prop_type: PropType,
+  },
+  /// Prop present only on the live definition of `owner`
+  RemoveProp {
+    kind: SchemaKind,
+    owner: String,
+    prop: String,
+  },
+  /// Prop declared on both sides with different types
+  ChangePropType {
+    kind: SchemaKind,
+    owner: String,
+    prop: String,
+    from: PropType,
+    to: PropType,
+  },
+}
 
-  pub fn node_type(&self) -> &str {
-    &self.node_type
+/// Append prop additions, removals, and type changes from `live` to `target`
+fn diff_props(
+  kind: SchemaKind,
+  owner: &str,
+  live: &HashMap<String, PropDef>,
+  target: &HashMap<String, PropDef>,
+  changes: &mut Vec<SchemaChange>,
+) {
+  let mut names: Vec<&String> = live.keys().chain(target.keys()).collect();
+  names.sort();
+  names.dedup();
+  for name in names {
+    let change = match (live.get(name), target.get(name)) {
+      (None, Some(to)) => SchemaChange::AddProp {
+        kind,
+        owner: owner.to_string(),
+        prop: name.clone(),
+        prop_type: to.prop_type,
+      },
+      (Some(_), None) => SchemaChange::RemoveProp {
+        kind,
+        owner: owner.to_string(),
+        prop: name.clone(),
+      },
+      (Some(from), Some(to)) if from.prop_type != to.prop_type => SchemaChange::ChangePropType {
+        kind,
+        owner: owner.to_string(),
+        prop: name.clone(),
+        from: from.prop_type,
+        to: to.prop_type,
+      },
+      _ => continue,
+    };
+    changes.push(change);
   }
+}
 
-  pub fn into_parts(self) -> (NodeId, Option<String>, Arc<str>) {
-    (self.id, self.key, self.node_type)
-  }
+/// Find the node definition whose key prefix matches `key`
+/// Drop edges whose far endpoint (seen from `node_id`) has a key outside `prefixes`
+pub(crate) fn retain_node_types(
+  db: &SingleFileDB,
+  node_id: NodeId,
+  edges: &mut Vec<Edge>,
+  prefixes: &[String],
+) {
+  edges.retain(|edge| {
+    let other = if edge.src == node_id {
+      edge.dst
+    } else {
+      edge.src
+    };
+    db.node_key(other).is_some_and(|key| {
+      prefixes
+        .iter()
+        .any(|prefix| key.starts_with(prefix.as_str()))
+    })
+  });
 }
 
-// ============================================================================
-// Kite Options
-// ============================================================================
+/// Reserved prop holding when a node was archived (ms since the Unix epoch)
+///
+/// Archived nodes stay in the database but are hidden from `get`, `all` and
+/// traversals; see [`Kite::archive`]. The name is reserved: prop reads skip
+/// it and prop writes naming it fail.
+pub const ARCHIVED_AT_PROP: &str = "__archived_at";
 
-/// Options for opening a Kite database
-#[derive(Debug, Clone)]
-pub struct KiteOptions {
-  /// Node type definitions
-  pub nodes: Vec<NodeDef>,
-  /// Edge type definitions
-  pub edges: Vec<EdgeDef>,
-  /// Open in read-only mode
-  pub read_only: bool,
-  /// Create database if it doesn't exist
-  pub create_if_missing: bool,
-  /// Synchronization mode for WAL writes (default: Full)
-  pub sync_mode: SyncMode,
-  /// Enable group commit (coalesce WAL flushes across commits)
-  pub group_commit_enabled: bool,
-  /// Group commit window in milliseconds
-  pub group_commit_window_ms: u64,
-  /// Enable MVCC (snapshot isolation + conflict detection)
-  pub mvcc: bool,
-  /// MVCC GC interval in ms
-  pub mvcc_gc_interval_ms: Option<u64>,
-  /// MVCC retention in ms
-  pub mvcc_retention_ms: Option<u64>,
-  /// MVCC max version chain depth
-  pub mvcc_max_chain_depth: Option<usize>,
-  /// WAL size in bytes (default: 4MB)
-  pub wal_size: Option<usize>,
-  /// WAL usage threshold (0.0-1.0) to trigger auto-checkpoint
-  pub checkpoint_threshold: Option<f64>,
-  /// Close-time WAL usage threshold (0.0-1.0) to trigger blocking checkpoint
-  pub close_checkpoint_if_wal_usage_at_least: Option<f64>,
-  /// Replication role (disabled | primary | replica)
-  pub replication_role: ReplicationRole,
-  /// Optional replication sidecar path override
-  pub replication_sidecar_path: Option<PathBuf>,
-  /// Source primary db path (replica role only)
-  pub replication_source_db_path: Option<PathBuf>,
-  /// Source primary sidecar path override (replica role only)
-  pub replication_source_sidecar_path: Option<PathBuf>,
-  /// Segment rotation threshold in bytes (primary role only)
-  pub replication_segment_max_bytes: Option<u64>,
-  /// Minimum retained entries window (primary role only)
-  pub replication_retention_min_entries: Option<u64>,
-  /// Minimum retained segment age in milliseconds (primary role only)
-  pub replication_retention_min_ms: Option<u64>,
+/// Reserved edge prop: start of the edge's validity window (ms since the
+/// Unix epoch, inclusive)
+///
+/// Set via [`Kite::link_valid`]. Traversals with
+/// [`KiteTraversalBuilder::as_of`] skip edges not valid at that instant.
+pub const VALID_FROM_PROP: &str = "__valid_from";
+
+/// Reserved edge prop: end of the edge's validity window (ms since the Unix
+/// epoch, exclusive); see [`VALID_FROM_PROP`]
+pub const VALID_TO_PROP: &str = "__valid_to";
+
+/// Node IDs scanned per range pass when filling a [`Kite::all_page`] page
+const ALL_PAGE_MIN_WINDOW: NodeId = 4096;
+
+/// Matches the nodes [`Kite::all`] lists for one node type
+struct NodeTypeFilter {
+  prefix: String,
+  node_type: Arc<str>,
+  /// Hide nodes carrying this archive prop; `None` keeps archived nodes
+  archived_key: Option<PropKeyId>,
 }
 
-impl KiteOptions {
-  pub fn new() -> Self {
-    Self {
-      nodes: Vec::new(),
-      edges: Vec::new(),
-      read_only: false,
-      create_if_missing: true,
-      sync_mode: SyncMode::Full,
-      group_commit_enabled: false,
-      group_commit_window_ms: 2,
-      mvcc: false,
-      mvcc_gc_interval_ms: None,
-      mvcc_retention_ms: None,
-      mvcc_max_chain_depth: None,
-      wal_size: None,
-      checkpoint_threshold: None,
-      close_checkpoint_if_wal_usage_at_least: Some(0.2),
-      replication_role: ReplicationRole::Disabled,
-      replication_sidecar_path: None,
-      replication_source_db_path: None,
-      replication_source_sidecar_path: None,
-      replication_segment_max_bytes: None,
-      replication_retention_min_entries: None,
-      replication_retention_min_ms: None,
+impl NodeTypeFilter {
+  fn node_ref(&self, kite: &Kite, node_id: NodeId) -> Option<NodeRef> {
+    let key = kite.node_key_internal(node_id)?;
+    if key.starts_with(&self.prefix) && !is_archived_db(&kite.db, self.archived_key, node_id) {
+      Some(NodeRef::new(
+        node_id,
+        Some(key),
+        Arc::clone(&self.node_type),
+      ))
+    } else {
+      None
     }
   }
+}
 
-  pub fn node(mut self, node: NodeDef) -> Self {
-    self.nodes.push(node);
-    self
-  }
+/// Whether `node_id` carries the archive flag (`archived_key` from
+/// `propkey_id(ARCHIVED_AT_PROP)`; `None` means nothing was ever archived)
+fn is_archived_db(db: &SingleFileDB, archived_key: Option<PropKeyId>, node_id: NodeId) -> bool {
+  archived_key.is_some_and(|key_id| db.node_prop(node_id, key_id).is_some())
+}
 
-  pub fn edge(mut self, edge: EdgeDef) -> Self {
-    self.edges.push(edge);
-    self
-  }
+/// Prop key id for a node prop name supplied by the caller
+///
+/// [`ARCHIVED_AT_PROP`] is reserved: only [`Kite::archive`] and
+/// [`Kite::unarchive`] write it.
+fn node_propkey_id(db: &SingleFileDB, prop_name: &str) -> Result<PropKeyId> {
+  if prop_name == ARCHIVED_AT_PROP {
+    return Err(KiteError::InvalidSchema(
+      format!("Prop name {prop_name} is reserved").into(),
+    ));
+  }
+  Ok(db.propkey_id_or_create(prop_name))
+}
 
-  pub fn read_only(mut self, value: bool) -> Self {
-    self.read_only = value;
-    self
-  }
+/// Prop key id for an edge prop name supplied by the caller
+///
+/// [`VALID_FROM_PROP`] and [`VALID_TO_PROP`] are reserved: only
+/// [`Kite::link_valid`] writes them.
+fn edge_propkey_id(db: &SingleFileDB, prop_name: &str) -> Result<PropKeyId> {
+  if prop_name == VALID_FROM_PROP || prop_name == VALID_TO_PROP {
+    return Err(KiteError::InvalidSchema(
+      format!("Prop name {prop_name} is reserved").into(),
+    ));
+  }
+  Ok(db.propkey_id_or_create(prop_name))
+}
 
-  pub fn sync_mode(mut self, mode: SyncMode) -> Self {
-    self.sync_mode = mode;
-    self
+/// Prop key id for reading a node prop by name; reserved names read as unset
+fn readable_propkey_id(db: &SingleFileDB, prop_name: &str) -> Option<PropKeyId> {
+  if prop_name == ARCHIVED_AT_PROP {
+    return None;
   }
+  db.propkey_id(prop_name)
+}
 
-  /// Enable or disable group commit (coalesce WAL flushes across commits)
-  pub fn group_commit_enabled(mut self, value: bool) -> Self {
-    self.group_commit_enabled = value;
-    self
+/// Hash a node's key and props into its content version
+///
+/// Props are fed in key-id order with a tag per value, so the result only
+/// depends on what the node holds, not on how its props are stored.
+fn node_content_hash(key: Option<&str>, props: &HashMap<PropKeyId, PropValue>) -> u64 {
+  let mut hasher = Xxh64::new(0);
+  let key = key.unwrap_or("");
+  hasher.write_usize(key.len());
+  hasher.write(key.as_bytes());
+
+  let mut entries: Vec<_> = props.iter().collect();
+  entries.sort_unstable_by_key(|(key_id, _)| **key_id);
+  for (key_id, value) in entries {
+    hasher.write_u32(*key_id);
+    hasher.write_u8(value.tag() as u8);
+    match value {
+      PropValue::Null => {}
+      PropValue::Bool(v) => hasher.write_u8(*v as u8),
+      PropValue::I64(v) => hasher.write_i64(*v),
+      PropValue::F64(v) => hasher.write_u64(v.to_bits()),
+      PropValue::String(v) => {
+        hasher.write_usize(v.len());
+        hasher.write(v.as_bytes());
+      }
+      PropValue::VectorF32(v) => {
+        hasher.write_usize(v.len());
+        for x in v {
+          hasher.write_u32(x.to_bits());
+        }
+      }
+      PropValue::Json(v) => {
+        let json = v.to_string();
+        hasher.write_usize(json.len());
+        hasher.write(json.as_bytes());
+      }
+    }
   }
+  hasher.finish()
+}
 
-  /// Set the group commit window in milliseconds
-  pub fn group_commit_window_ms(mut self, value: u64) -> Self {
-    self.group_commit_window_ms = value;
-    self
-  }
+/// Drop edges that aren't valid at `as_of` (ms since the Unix epoch)
+///
+/// An edge is valid over `[VALID_FROM_PROP, VALID_TO_PROP)`; a missing
+/// bound is open, so edges without either prop are always valid.
+pub(crate) fn retain_valid_at(db: &SingleFileDB, edges: &mut Vec<Edge>, as_of: i64) {
+  let from_key = db.propkey_id(VALID_FROM_PROP);
+  let to_key = db.propkey_id(VALID_TO_PROP);
+  if from_key.is_none() && to_key.is_none() {
+    return;
+  }
+  let bound = |edge: &Edge, key_id: Option<PropKeyId>| match key_id {
+    Some(key_id) => match db.edge_prop(edge.src, edge.etype, edge.dst, key_id) {
+      Some(PropValue::I64(ms)) => Some(ms),
+      _ => None,
+    },
+    None => None,
+  };
+  edges.retain(|edge| {
+    bound(edge, from_key).is_none_or(|from| from <= as_of)
+      && bound(edge, to_key).is_none_or(|to| as_of < to)
+  });
+}
 
-  /// Set sync mode to Normal (fsync on checkpoint only)
-  pub fn sync_normal(mut self) -> Self {
-    self.sync_mode = SyncMode::Normal;
-    self
-  }
+/// Drop edges from `node_id` whose other endpoint is archived
+pub(crate) fn retain_unarchived(db: &SingleFileDB, node_id: NodeId, edges: &mut Vec<Edge>) {
+  let Some(archived_key) = db.propkey_id(ARCHIVED_AT_PROP) else {
+    return;
+  };
+  edges.retain(|edge| {
+    let other = if edge.src == node_id {
+      edge.dst
+    } else {
+      edge.src
+    };
+    db.node_prop(other, archived_key).is_none()
+  });
+}
 
-  /// Set sync mode to Off (no fsync)
-  pub fn sync_off(mut self) -> Self {
-    self.sync_mode = SyncMode::Off;
-    self
-  }
+fn now_ms() -> i64 {
+  std::time::SystemTime::now()
+    .duration_since(std::time::UNIX_EPOCH)
+    .map(|d| d.as_millis() as i64)
+    .unwrap_or(0)
+}
 
-  pub fn mvcc(mut self, value: bool) -> Self {
-    self.mvcc = value;
-    self
-  }
+/// Node type owning a key
+///
+/// `validate_options` rules out nested key prefixes, so the only candidate is
+/// the greatest prefix sorting at or before the key: one ordered-map lookup
+/// rather than a scan over every node type.
+fn node_def_for_key<'a>(
+  nodes: &'a HashMap<String, NodeDef>,
+  key_prefix_to_node: &BTreeMap<String, String>,
+  key: &str,
+) -> Option<&'a NodeDef> {
+  let (prefix, name) = key_prefix_to_node
+    .range::<str, _>((Bound::Unbounded, Bound::Included(key)))
+    .next_back()?;
+  if !key.starts_with(prefix.as_str()) {
+    return None;
+  }
+  nodes.get(name)
+}
 
-  pub fn mvcc_gc_interval_ms(mut self, value: u64) -> Self {
-    self.mvcc_gc_interval_ms = Some(value);
-    self
+/// Check schema definitions before anything is registered
+///
+/// Collects every problem instead of stopping at the first, so a schema with
+/// several mistakes is fixed in one pass.
+fn validate_options(options: &KiteOptions) -> Result<()> {
+  let mut problems = Vec::new();
+
+  let mut node_names = HashSet::new();
+  for node_def in &options.nodes {
+    if !node_names.insert(node_def.name.as_str()) {
+      problems.push(format!("duplicate node type '{}'", node_def.name));
+    }
   }
-
-  pub fn mvcc_retention_ms(mut self, value: u64) -> Self {
-    self.mvcc_retention_ms = Some(value);
-    self
+  let mut edge_names = HashSet::new();
+  for edge_def in &options.edges {
+    if !edge_names.insert(edge_def.name.as_str()) {
+      problems.push(format!("duplicate edge type '{}'", edge_def.name));
+    }
   }
 
-  pub fn mvcc_max_chain_depth(mut self, value: usize) -> Self {
-    self.mvcc_max_chain_depth = Some(value);
-    self
+  // Keys are matched to node types by prefix, so a prefix that starts with
+  // another type's prefix makes key ownership ambiguous.
+  for (idx, a) in options.nodes.iter().enumerate() {
+    for b in &options.nodes[idx + 1..] {
+      if a.name == b.name {
+        continue;
+      }
+      if a.key_prefix.starts_with(&b.key_prefix) || b.key_prefix.starts_with(&a.key_prefix) {
+        problems.push(format!(
+          "node types '{}' and '{}' have overlapping key prefixes '{}' and '{}'",
+          a.name, b.name, a.key_prefix, b.key_prefix
+        ));
+      }
+    }
   }
 
-  /// Set WAL size in bytes
-  pub fn wal_size(mut self, value: usize) -> Self {
-    self.wal_size = Some(value);
-    self
+  for node_def in &options.nodes {
+    for prop in node_def.props.values() {
+      check_prop_def(
+        &format!("node type '{}'", node_def.name),
+        prop,
+        &mut problems,
+      );
+    }
   }
-
-  /// Set WAL size in megabytes
-  pub fn wal_size_mb(mut self, value: usize) -> Self {
-    self.wal_size = Some(value.saturating_mul(1024 * 1024));
-    self
+  for edge_def in &options.edges {
+    let owner = format!("edge type '{}'", edge_def.name);
+    for prop in edge_def.props.values() {
+      check_prop_def(&owner, prop, &mut problems);
+      if prop.is_indexed() {
+        problems.push(format!(
+          "{owner} prop '{}' is unique or ordered, which only node props support",
+          prop.name
+        ));
+      }
+      if prop.normalize {
+        problems.push(format!(
+          "{owner} prop '{}' is normalized, which only node props support",
+          prop.name
+        ));
+      }
+    }
   }
 
-  /// Set checkpoint threshold (0.0-1.0)
-  pub fn checkpoint_threshold(mut self, value: f64) -> Self {
-    self.checkpoint_threshold = Some(value.clamp(0.0, 1.0));
-    self
+  // Enum values are interned per prop key, which every type declaring a
+  // prop of that name shares
+  let mut enum_owners: HashMap<&str, (Vec<String>, Vec<String>)> = HashMap::new();
+  let node_props = options.nodes.iter().flat_map(|node_def| {
+    let owner = format!("node type '{}'", node_def.name);
+    node_def
+      .props
+      .values()
+      .map(move |prop| (owner.clone(), prop))
+  });
+  let edge_props = options.edges.iter().flat_map(|edge_def| {
+    let owner = format!("edge type '{}'", edge_def.name);
+    edge_def
+      .props
+      .values()
+      .map(move |prop| (owner.clone(), prop))
+  });
+  for (owner, prop) in node_props.chain(edge_props) {
+    let (enums, others) = enum_owners.entry(prop.name.as_str()).or_default();
+    if prop.allowed.is_some() {
+      enums.push(owner);
+    } else {
+      others.push(owner);
+    }
   }
-
-  /// Set close-time checkpoint threshold (0.0-1.0).
-  ///
-  /// When set, `Kite::close()` checkpoints if WAL usage is at or above this threshold.
-  pub fn close_checkpoint_if_wal_usage_at_least(mut self, value: f64) -> Self {
-    self.close_checkpoint_if_wal_usage_at_least = Some(value.clamp(0.0, 1.0));
-    self
+  for (prop_name, (enums, others)) in enum_owners {
+    if let (Some(enum_owner), Some(other_owner)) = (enums.first(), others.first()) {
+      problems.push(format!(
+        "prop '{prop_name}' is an enum on {enum_owner} but not on {other_owner}; \
+         enum props must be enums everywhere they are declared"
+      ));
+    }
   }
 
-  /// Disable close-time checkpointing in `Kite::close()`.
-  pub fn disable_close_checkpoint(mut self) -> Self {
-    self.close_checkpoint_if_wal_usage_at_least = None;
-    self
+  if problems.is_empty() {
+    return Ok(());
   }
+  problems.sort();
+  Err(KiteError::InvalidSchema(
+    format!(
+      "{} problem(s) in Kite options: {}",
+      problems.len(),
+      problems.join("; ")
+    )
+    .into(),
+  ))
+}
 
-  /// Set replication role (disabled | primary | replica)
-  pub fn replication_role(mut self, role: ReplicationRole) -> Self {
-    self.replication_role = role;
-    self
+fn check_prop_def(owner: &str, prop: &PropDef, problems: &mut Vec<String>) {
+  if prop.normalize && prop.prop_type != PropType::Vector {
+    problems.push(format!(
+      "{owner} prop '{}' is normalized, which only vector props support",
+      prop.name
+    ));
+  }
+  if let Some(allowed) = prop.allowed.as_ref() {
+    if allowed.values().is_empty() {
+      problems.push(format!(
+        "{owner} prop '{}' is an enum with no allowed values",
+        prop.name
+      ));
+    }
   }
-
-  /// Set replication sidecar path (for primary/replica modes)
-  pub fn replication_sidecar_path<P: AsRef<Path>>(mut self, path: P) -> Self {
-    self.replication_sidecar_path = Some(path.as_ref().to_path_buf());
-    self
+  let Some(default) = prop.default.as_ref() else {
+    return;
+  };
+  let expected = match prop.prop_type {
+    PropType::String | PropType::Enum => Some(PropValueTag::String),
+    PropType::Int => Some(PropValueTag::I64),
+    PropType::Float => Some(PropValueTag::F64),
+    PropType::Bool => Some(PropValueTag::Bool),
+    PropType::Vector => Some(PropValueTag::VectorF32),
+    PropType::Json | PropType::Any => None,
+  };
+  let got = default.tag();
+  let type_ok = match expected {
+    None => true,
+    Some(PropValueTag::F64) => matches!(
+      got,
+      PropValueTag::F64 | PropValueTag::I64 | PropValueTag::Null
+    ),
+    Some(tag) => got == tag || got == PropValueTag::Null,
+  };
+  if !type_ok {
+    problems.push(format!(
+      "{owner} prop '{}' has a {got:?} default but is typed {:?}",
+      prop.name, prop.prop_type
+    ));
+  } else if let Err(err) = prop.check(default) {
+    problems.push(format!("{owner} default is not allowed: {err}"));
   }
+}
 
-  /// Set replication source db path (replica role only)
-  pub fn replication_source_db_path<P: AsRef<Path>>(mut self, path: P) -> Self {
-    self.replication_source_db_path = Some(path.as_ref().to_path_buf());
-    self
+/// Add the allowed values of every enum prop to its key's dictionary, so the
+/// database stores them as interned ids
+fn register_enum_values(
+  db: &SingleFileDB,
+  nodes: &HashMap<String, NodeDef>,
+  edges: &HashMap<String, EdgeDef>,
+) -> Result<()> {
+  let node_props = nodes
+    .values()
+    .flat_map(|def| def.props.values().map(|prop| (prop, &def.prop_key_ids)));
+  let edge_props = edges
+    .values()
+    .flat_map(|def| def.props.values().map(|prop| (prop, &def.prop_key_ids)));
+  let mut missing: Vec<(PropKeyId, &EnumValues)> = Vec::new();
+  for (prop, prop_key_ids) in node_props.chain(edge_props) {
+    let (Some(allowed), Some(&key_id)) = (prop.allowed.as_ref(), prop_key_ids.get(&prop.name))
+    else {
+      continue;
+    };
+    let known = db.prop_enum_values(key_id).unwrap_or_default();
+    if allowed.values().iter().any(|value| !known.contains(value)) {
+      missing.push((key_id, allowed));
+    }
   }
-
-  /// Set replication source sidecar path (replica role only)
-  pub fn replication_source_sidecar_path<P: AsRef<Path>>(mut self, path: P) -> Self {
-    self.replication_source_sidecar_path = Some(path.as_ref().to_path_buf());
-    self
+  if missing.is_empty() || db.is_read_only() {
+    return Ok(());
   }
-
-  /// Set replication segment rotation threshold in bytes (primary role only)
-  pub fn replication_segment_max_bytes(mut self, value: u64) -> Self {
-    self.replication_segment_max_bytes = Some(value);
-    self
+  let mut handle = begin_tx(db)?;
+  for (key_id, allowed) in missing {
+    handle
+      .db
+      .set_prop_enum_values(key_id, allowed.values().iter().cloned())?;
   }
+  commit(&mut handle)
+}
 
-  /// Set retention minimum entries to keep when pruning (primary role only)
-  pub fn replication_retention_min_entries(mut self, value: u64) -> Self {
-    self.replication_retention_min_entries = Some(value);
-    self
-  }
+/// Metadata entry holding the last key number allocated for `node_type`
+fn key_counter_metadata_key(node_type: &str) -> String {
+  format!("kite:key_counter:{node_type}")
+}
 
-  /// Set retention minimum segment age in milliseconds (primary role only)
-  pub fn replication_retention_min_ms(mut self, value: u64) -> Self {
-    self.replication_retention_min_ms = Some(value);
-    self
+/// Load the persisted key counter of every `autoincrement` node type
+fn load_key_counters(
+  db: &SingleFileDB,
+  nodes: &HashMap<String, NodeDef>,
+) -> Result<HashMap<String, u64>> {
+  let mut counters = HashMap::new();
+  for node_def in nodes.values().filter(|def| def.autoincrement) {
+    let counter = match db.metadata(&key_counter_metadata_key(&node_def.name))? {
+      Some(bytes) => u64::from_le_bytes(bytes.as_slice().try_into().map_err(|_| {
+        KiteError::Serialization(format!(
+          "Key counter of {} holds {} bytes, expected 8",
+          node_def.name,
+          bytes.len()
+        ))
+      })?),
+      None => 0,
+    };
+    counters.insert(node_def.name.clone(), counter);
   }
+  Ok(counters)
+}
 
-  /// Recommended conservative profile (durability-first).
-  pub fn recommended_safe() -> Self {
-    Self::new()
-      .sync_mode(SyncMode::Full)
-      .group_commit_enabled(false)
-      .checkpoint_threshold(0.5)
+/// Hashable, totally ordered form of an indexed property value
+///
+/// Floats are stored as order-preserving bit patterns so that range scans
+/// over the `BTreeMap` follow numeric order.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+enum IndexValue {
+  String(String),
+  I64(i64),
+  F64(u64),
+  Bool(bool),
+}
+
+impl IndexValue {
+  /// Null, vectors and JSON documents are never indexed
+  fn from_prop(value: &PropValue) -> Option<Self> {
+    match value {
+      PropValue::String(v) => Some(Self::String(v.clone())),
+      PropValue::I64(v) => Some(Self::I64(*v)),
+      PropValue::F64(v) => Some(Self::F64(Self::f64_sort_bits(*v))),
+      PropValue::Bool(v) => Some(Self::Bool(*v)),
+      PropValue::Null | PropValue::VectorF32(_) | PropValue::Json(_) => None,
+    }
   }
 
-  /// Recommended balanced profile (good throughput + durability tradeoff).
-  pub fn recommended_balanced() -> Self {
-    Self::new()
-      .sync_mode(SyncMode::Normal)
-      .group_commit_enabled(true)
-      .group_commit_window_ms(2)
-      .wal_size_mb(64)
-      .checkpoint_threshold(0.5)
+  /// Size of the value plus any string it owns
+  fn estimated_bytes(&self) -> usize {
+    let heap = match self {
+      Self::String(v) => v.len(),
+      Self::I64(_) | Self::F64(_) | Self::Bool(_) => 0,
+    };
+    std::mem::size_of::<Self>() + heap
   }
 
-  /// Recommended profile for reopen-heavy workloads.
-  ///
-  /// Uses a smaller WAL and lower auto-checkpoint threshold to cap replay cost on reopen.
-  pub fn recommended_reopen_heavy() -> Self {
-    Self::new()
-      .sync_mode(SyncMode::Normal)
-      .group_commit_enabled(true)
-      .group_commit_window_ms(2)
-      .wal_size_mb(16)
-      .checkpoint_threshold(0.2)
+  /// Map a float to bits whose unsigned order matches `f64::total_cmp`
+  fn f64_sort_bits(value: f64) -> u64 {
+    let bits = value.to_bits();
+    if bits >> 63 == 1 {
+      !bits
+    } else {
+      bits | (1 << 63)
+    }
   }
 }
 
-impl Default for KiteOptions {
-  fn default() -> Self {
-    Self::new()
-  }
+type IndexSlot = (LabelId, PropKeyId);
+
+#[derive(Debug, Default)]
+struct PropIndexState {
+  by_value: HashMap<IndexSlot, HashMap<IndexValue, NodeId>>,
+  by_order: HashMap<IndexSlot, BTreeMap<IndexValue, Vec<NodeId>>>,
+  by_node: HashMap<NodeId, Vec<(IndexSlot, IndexValue)>>,
 }
 
-/// Preset runtime profile flavors for KiteDB.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
-pub enum KiteRuntimeProfileKind {
-  /// Durability-first defaults.
-  Safe,
-  /// Balanced throughput defaults.
-  Balanced,
-  /// Reopen-heavy defaults (lower WAL replay risk).
-  ReopenHeavy,
+/// Value -> node indexes backing `unique` and `ordered` node properties
+///
+/// The indexes hold committed values only. [`PropIndex::commit`] runs as the
+/// database's commit check, so every commit (through `Kite` or raw
+/// `SingleFileDB` writes) is checked for duplicate unique values under the
+/// commit lock and then folded in; a rolled-back transaction never touches
+/// the index. Lookups layer the calling thread's open transaction on top,
+/// so a transaction sees its own writes.
+///
+/// Nothing here is persisted: `build` recreates both indexes from the
+/// snapshot and WAL-replayed data every time the database is opened.
+#[derive(Debug, Default)]
+struct PropIndex {
+  state: parking_lot::Mutex<PropIndexState>,
+  /// Node types with at least one indexed prop
+  types: Vec<NodeDef>,
+  /// Key IDs of every indexed prop, to skip unrelated writes quickly
+  key_ids: HashSet<PropKeyId>,
 }
 
-/// Runtime profile: open options + optional close policy.
-#[derive(Debug, Clone)]
-pub struct KiteRuntimeProfile {
-  /// Open-time options profile.
-  pub options: KiteOptions,
-  /// Optional close-time checkpoint trigger threshold.
+impl PropIndex {
+  /// Index the current values of every unique and ordered property
   ///
-  /// When set, call `Kite::close_with_checkpoint_if_wal_over(threshold)`.
-  pub close_checkpoint_if_wal_usage_at_least: Option<f64>,
-}
+  /// If the data already holds duplicates (e.g. written before the
+  /// constraint was declared), the first node seen keeps the unique entry.
+  fn build(
+    db: &SingleFileDB,
+    nodes: &HashMap<String, NodeDef>,
+    key_prefix_to_node: &BTreeMap<String, String>,
+  ) -> Self {
+    let types: Vec<NodeDef> = nodes
+      .values()
+      .filter(|def| def.label_id.is_some() && def.props.values().any(PropDef::is_indexed))
+      .cloned()
+      .collect();
+    let key_ids = types
+      .iter()
+      .flat_map(|def| {
+        def
+          .props
+          .values()
+          .filter(|prop| prop.is_indexed())
+          .filter_map(|prop| def.prop_key_ids.get(&prop.name).copied())
+      })
+      .collect();
+    let index = Self {
+      types,
+      key_ids,
+      ..Self::default()
+    };
+    if index.types.is_empty() {
+      return index;
+    }
 
-impl KiteRuntimeProfile {
-  pub fn from_kind(kind: KiteRuntimeProfileKind) -> Self {
-    match kind {
-      KiteRuntimeProfileKind::Safe => Self::safe(),
-      KiteRuntimeProfileKind::Balanced => Self::balanced(),
-      KiteRuntimeProfileKind::ReopenHeavy => Self::reopen_heavy(),
+    {
+      let mut state = index.state.lock();
+      for node_id in db.list_nodes() {
+        let Some(key) = db.node_key(node_id) else {
+          continue;
+        };
+        let Some(node_def) = node_def_for_key(nodes, key_prefix_to_node, &key) else {
+          continue;
+        };
+        let Some(label_id) = node_def.label_id else {
+          continue;
+        };
+        for prop in node_def.props.values().filter(|prop| prop.is_indexed()) {
+          let Some(&key_id) = node_def.prop_key_ids.get(&prop.name) else {
+            continue;
+          };
+          let Some(value) = db
+            .node_prop(node_id, key_id)
+            .and_then(|value| IndexValue::from_prop(&value))
+          else {
+            continue;
+          };
+          state.set(node_id, (label_id, key_id), prop, Some(value));
+        }
+      }
     }
+    index
   }
 
-  pub fn safe() -> Self {
-    Self {
-      options: KiteOptions::recommended_safe(),
-      close_checkpoint_if_wal_usage_at_least: None,
-    }
+  /// Whether the index has anything to maintain
+  fn is_empty(&self) -> bool {
+    self.types.is_empty()
   }
 
-  pub fn balanced() -> Self {
-    Self {
-      options: KiteOptions::recommended_balanced(),
-      close_checkpoint_if_wal_usage_at_least: None,
-    }
+  /// Indexed node type whose key prefix matches `key`
+  fn type_for_key(&self, key: &str) -> Option<&NodeDef> {
+    self
+      .types
+      .iter()
+      .find(|def| key.starts_with(def.key_prefix.as_str()))
   }
 
-  pub fn reopen_heavy() -> Self {
-    Self {
-      options: KiteOptions::recommended_reopen_heavy(),
-      close_checkpoint_if_wal_usage_at_least: Some(0.2),
+  /// Check that `node_id` may take `value` of `prop_name`: fails if the prop
+  /// is unique and another live node of the same type holds it
+  ///
+  /// Only sees committed values and the calling thread's transaction; a
+  /// concurrent transaction taking the same value fails at commit instead.
+  fn check(
+    &self,
+    db: &SingleFileDB,
+    node_def: &NodeDef,
+    node_id: NodeId,
+    prop_name: &str,
+    value: &PropValue,
+  ) -> Result<()> {
+    let Some(prop) = node_def.props.get(prop_name).filter(|prop| prop.unique) else {
+      return Ok(());
+    };
+    let (Some(label_id), Some(&key_id)) = (node_def.label_id, node_def.prop_key_ids.get(prop_name))
+    else {
+      return Ok(());
+    };
+    let Some(index_value) = IndexValue::from_prop(value) else {
+      return Ok(());
+    };
+
+    let slot = (label_id, key_id);
+    let owner = self
+      .holders(db, slot, &index_value, &index_value)
+      .into_iter()
+      .find(|&owner| owner != node_id);
+    match owner {
+      Some(owner) => Err(duplicate_error(db, node_def, &prop.name, value, owner)),
+      None => Ok(()),
     }
   }
-}
 
-/// Convenience helper to open a KiteDB instance.
-pub fn kite<P: AsRef<Path>>(path: P, options: KiteOptions) -> Result<Kite> {
-  Kite::open(path, options)
-}
+  /// Check every value in `props` for `node_id`
+  fn check_all<'p, I>(
+    &self,
+    db: &SingleFileDB,
+    node_def: &NodeDef,
+    node_id: NodeId,
+    props: I,
+  ) -> Result<()>
+  where
+    I: IntoIterator<Item = (&'p str, &'p PropValue)>,
+  {
+    if !node_def.has_constraints() {
+      return Ok(());
+    }
+    for (prop_name, value) in props {
+      self.check(db, node_def, node_id, prop_name, value)?;
+    }
+    Ok(())
+  }
 
-// ============================================================================
-// Kite Database
-// ============================================================================
+  /// Values in `props` that land on an indexed prop, for checking once the
+  /// target node is known
+  fn pending_claims(
+    node_def: Option<&NodeDef>,
+    props: &HashMap<String, PropValue>,
+  ) -> Vec<(String, PropValue)> {
+    let Some(node_def) = node_def.filter(|def| def.has_constraints()) else {
+      return Vec::new();
+    };
+    props
+      .iter()
+      .filter(|(name, _)| node_def.props.get(*name).is_some_and(PropDef::is_indexed))
+      .map(|(name, value)| (name.clone(), value.clone()))
+      .collect()
+  }
 
-/// High-level graph database API
-pub struct Kite {
-  /// Underlying database
-  db: SingleFileDB,
-  /// Close-time checkpoint threshold.
-  close_checkpoint_if_wal_usage_at_least: Option<f64>,
-  /// Node type definitions by name
-  nodes: HashMap<String, NodeDef>,
-  /// Edge type definitions by name
-  edges: HashMap<String, EdgeDef>,
-  /// Key prefix to node def mapping for fast lookups
-  key_prefix_to_node: HashMap<String, String>,
-}
+  /// Node currently holding `value` of a unique prop
+  fn owner(&self, db: &SingleFileDB, slot: IndexSlot, value: &IndexValue) -> Option<NodeId> {
+    self.holders(db, slot, value, value).into_iter().next()
+  }
 
-impl Kite {
-  /// Open or create a Kite database
-  pub fn open<P: AsRef<Path>>(path: P, options: KiteOptions) -> Result<Self> {
-    let path = path.as_ref();
-    if path.exists() && path.is_dir() {
-      return Err(KiteError::InvalidPath(
-        "Directory-format databases are no longer supported; use a .kitedb file path".to_string(),
-      ));
+  /// Unarchived nodes whose current value of an ordered prop lies in
+  /// `[min, max]`, in value order
+  fn range(
+    &self,
+    db: &SingleFileDB,
+    slot: IndexSlot,
+    min: &IndexValue,
+    max: &IndexValue,
+  ) -> Vec<NodeId> {
+    let mut found = self.holders(db, slot, min, max);
+    if let Some(archived_key) = db.propkey_id(ARCHIVED_AT_PROP) {
+      found.retain(|&node_id| !is_archived_db(db, Some(archived_key), node_id));
     }
+    found
+  }
 
-    let mut db_path = PathBuf::from(path);
-    if db_path.extension().is_some() {
-      if !is_single_file_path(&db_path) {
-        let ext = db_path
-          .extension()
-          .map(|value| value.to_string_lossy())
-          .unwrap_or_else(|| "".into());
-        return Err(KiteError::InvalidPath(format!(
-          "Invalid database extension '.{ext}'. Single-file databases must use {} (or pass a path without an extension).",
-          single_file_extension()
-        )));
+  /// Nodes of the slot's type whose value lies in `[min, max]` as the
+  /// calling thread sees it, in value order
+  ///
+  /// Committed entries are kept only while the node still holds the value
+  /// in the caller's view, then nodes the caller's transaction moved into
+  /// the range are added.
+  fn holders(
+    &self,
+    db: &SingleFileDB,
+    slot: IndexSlot,
+    min: &IndexValue,
+    max: &IndexValue,
+  ) -> Vec<NodeId> {
+    let committed: Vec<(IndexValue, NodeId)> = {
+      let state = self.state.lock();
+      let mut entries = Vec::new();
+      if let Some(by_order) = state.by_order.get(&slot) {
+        for (value, node_ids) in by_order.range(min..=max) {
+          entries.extend(node_ids.iter().map(|&node_id| (value.clone(), node_id)));
+        }
+      } else if let Some(by_value) = state.by_value.get(&slot) {
+        if min == max {
+          entries.extend(by_value.get(min).map(|&node_id| (min.clone(), node_id)));
+        } else {
+          entries.extend(
+            by_value
+              .iter()
+              .filter(|(value, _)| (min..=max).contains(value))
+              .map(|(value, &node_id)| (value.clone(), node_id)),
+          );
+          entries.sort();
+        }
       }
-    } else {
-      db_path = PathBuf::from(format!("{}{}", path.display(), single_file_extension()));
-    }
-
-    let close_checkpoint_if_wal_usage_at_least = options
-      .close_checkpoint_if_wal_usage_at_least
-      .map(|value| value.clamp(0.0, 1.0));
+      entries
+    };
 
-    let mut db_options = SingleFileOpenOptions::new()
-      .read_only(options.read_only)
-      .create_if_missing(options.create_if_missing)
-      .sync_mode(options.sync_mode)
-      .group_commit_enabled(options.group_commit_enabled)
-      .group_commit_window_ms(options.group_commit_window_ms)
-      .mvcc(options.mvcc)
-      .replication_role(options.replication_role);
-    if let Some(v) = options.mvcc_gc_interval_ms {
-      db_options = db_options.mvcc_gc_interval_ms(v);
+    let mut matches: Vec<(IndexValue, NodeId)> = committed
+      .into_iter()
+      .filter(|(value, node_id)| holds(db, *node_id, slot.1, value))
+      .collect();
+    let mut seen: HashSet<NodeId> = matches.iter().map(|&(_, node_id)| node_id).collect();
+    let pending = self.pending_values(db, slot);
+    if !pending.is_empty() {
+      for (value, node_id) in pending {
+        if (min..=max).contains(&&value) && seen.insert(node_id) {
+          matches.push((value, node_id));
+        }
+      }
+      matches.sort_by(|a, b| a.0.cmp(&b.0));
     }
-    if let Some(v) = options.mvcc_retention_ms {
-      db_options = db_options.mvcc_retention_ms(v);
+    matches.into_iter().map(|(_, node_id)| node_id).collect()
+  }
+
+  /// Values of `slot` written by the calling thread's open transaction, for
+  /// nodes of the slot's type that still hold them
+  fn pending_values(&self, db: &SingleFileDB, slot: IndexSlot) -> Vec<(IndexValue, NodeId)> {
+    let Some(node_def) = self.types.iter().find(|def| def.label_id == Some(slot.0)) else {
+      return Vec::new();
+    };
+    let Some(handle) = db.current_tx_handle() else {
+      return Vec::new();
+    };
+    let mut candidates = Vec::new();
+    {
+      let tx = handle.lock();
+      let pending = &tx.pending;
+      for (&node_id, node_delta) in pending
+        .created_nodes
+        .iter()
+        .chain(pending.modified_nodes.iter())
+      {
+        let value = node_delta
+          .props
+          .as_ref()
+          .and_then(|props| props.get(&slot.1))
+          .and_then(|value| value.as_deref())
+          .and_then(IndexValue::from_prop);
+        if let Some(value) = value {
+          candidates.push((value, node_id));
+        }
+      }
     }
-    if let Some(v) = options.mvcc_max_chain_depth {
-      db_options = db_options.mvcc_max_chain_depth(v);
+    // Reads go through the transaction, so its lock must be released first
+    candidates.retain(|(value, node_id)| {
+      db.node_key(*node_id)
+        .is_some_and(|key| key.starts_with(node_def.key_prefix.as_str()))
+        && holds(db, *node_id, slot.1, value)
+    });
+    candidates
+  }
+
+  /// Commit check: reject a commit that would give two live nodes of a type
+  /// the same unique value, otherwise apply its indexed writes and deletes
+  ///
+  /// Runs with the commit lock held and no transaction on the thread, so
+  /// database reads see exactly the commits before this one.
+  fn commit(&self, db: &SingleFileDB, pending: &DeltaState) -> Result<()> {
+    let mut updates: Vec<(NodeId, &NodeDef, &PropDef, IndexSlot, Option<PropValue>)> = Vec::new();
+    for (&node_id, node_delta) in pending
+      .created_nodes
+      .iter()
+      .chain(pending.modified_nodes.iter())
+    {
+      if pending.deleted_nodes.contains(&node_id) {
+        continue;
+      }
+      let Some(props) = node_delta.props.as_ref() else {
+        continue;
+      };
+      if !props.keys().any(|key_id| self.key_ids.contains(key_id)) {
+        continue;
+      }
+      let Some(key) = node_delta.key.clone().or_else(|| db.node_key(node_id)) else {
+        continue;
+      };
+      let Some(node_def) = self.type_for_key(&key) else {
+        continue;
+      };
+      let Some(label_id) = node_def.label_id else {
+        continue;
+      };
+      for prop in node_def.props.values().filter(|prop| prop.is_indexed()) {
+        let Some(&key_id) = node_def.prop_key_ids.get(&prop.name) else {
+          continue;
+        };
+        if let Some(value) = props.get(&key_id) {
+          // Pending enum values are still interned ids
+          let value = value
+            .as_deref()
+            .map(|value| db.resolve_prop_value(key_id, value.clone()));
+          updates.push((node_id, node_def, prop, (label_id, key_id), value));
+        }
+      }
     }
-    if let Some(v) = options.wal_size {
-      db_options = db_options.wal_size(v);
+    if updates.is_empty() && pending.deleted_nodes.is_empty() {
+      return Ok(());
     }
-    if let Some(v) = options.checkpoint_threshold {
-      db_options = db_options.checkpoint_threshold(v);
+
+    let mut state = self.state.lock();
+    let new_values: HashMap<(NodeId, IndexSlot), Option<IndexValue>> = updates
+      .iter()
+      .map(|(node_id, _, _, slot, value)| {
+        (
+          (*node_id, *slot),
+          value.as_ref().and_then(IndexValue::from_prop),
+        )
+      })
+      .collect();
+    let mut taken: HashMap<(IndexSlot, IndexValue), NodeId> = HashMap::new();
+    for &(node_id, node_def, prop, slot, ref value) in &updates {
+      let Some(new_value) = new_values.get(&(node_id, slot)).cloned().flatten() else {
+        continue;
+      };
+      if !prop.unique {
+        continue;
+      }
+      let value = value.as_ref().expect("indexed value comes from a set prop");
+      if let Some(&other) = taken.get(&(slot, new_value.clone())) {
+        if other != node_id {
+          return Err(duplicate_error(db, node_def, &prop.name, value, other));
+        }
+      }
+      let owner = state
+        .by_value
+        .get(&slot)
+        .and_then(|by_value| by_value.get(&new_value))
+        .copied();
+      if let Some(owner) = owner.filter(|&owner| {
+        owner != node_id
+          && !pending.deleted_nodes.contains(&owner)
+          && !new_values.contains_key(&(owner, slot))
+          && holds(db, owner, slot.1, &new_value)
+      }) {
+        return Err(duplicate_error(db, node_def, &prop.name, value, owner));
+      }
+      taken.insert((slot, new_value), node_id);
     }
-    if let Some(path) = options.replication_sidecar_path.as_ref() {
-      db_options = db_options.replication_sidecar_path(path);
+
+    for &node_id in &pending.deleted_nodes {
+      state.remove_node(node_id);
     }
-    if let Some(path) = options.replication_source_db_path.as_ref() {
-      db_options = db_options.replication_source_db_path(path);
+    // Vacate every old value before taking new ones, so swaps land intact
+    for &(node_id, _, prop, slot, _) in &updates {
+      state.set(node_id, slot, prop, None);
     }
-    if let Some(path) = options.replication_source_sidecar_path.as_ref() {
-      db_options = db_options.replication_source_sidecar_path(path);
+    for (node_id, _, prop, slot, _) in updates {
+      if let Some(value) = new_values.get(&(node_id, slot)).cloned().flatten() {
+        state.set(node_id, slot, prop, Some(value));
+      }
     }
-    if let Some(v) = options.replication_segment_max_bytes {
-      db_options = db_options.replication_segment_max_bytes(v);
+    Ok(())
+  }
+
+  /// One entry per unique and ordered prop of `nodes`, sorted by node type
+  /// then prop name
+  fn describe(&self, nodes: &HashMap<String, NodeDef>) -> Vec<IndexInfo> {
+    let state = self.state.lock();
+    let mut defs: Vec<&NodeDef> = nodes.values().collect();
+    defs.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut indexes = Vec::new();
+    for node_def in defs {
+      let mut props: Vec<&PropDef> = node_def.props.values().filter(|p| p.is_indexed()).collect();
+      props.sort_by(|a, b| a.name.cmp(&b.name));
+      for prop in props {
+        let slot = node_def
+          .label_id
+          .zip(node_def.prop_key_ids.get(&prop.name).copied());
+        let info = |kind, entries: usize, estimated_bytes: usize| IndexInfo {
+          kind,
+          node_type: Some(node_def.name.clone()),
+          prop_name: prop.name.clone(),
+          entries: entries as u64,
+          estimated_bytes: estimated_bytes as u64,
+        };
+        if prop.unique {
+          let by_value = slot.and_then(|slot| state.by_value.get(&slot));
+          let bytes = by_value.map_or(0, |by_value| {
+            by_value
+              .keys()
+              .map(|value| value.estimated_bytes() + std::mem::size_of::<NodeId>())
+              .sum()
+          });
+          indexes.push(info(
+            IndexKind::Unique,
+            by_value.map_or(0, HashMap::len),
+            bytes,
+          ));
+        }
+        if prop.ordered {
+          let by_order = slot.and_then(|slot| state.by_order.get(&slot));
+          let (entries, bytes) = by_order.map_or((0, 0), |by_order| {
+            by_order
+              .iter()
+              .fold((0, 0), |(entries, bytes), (value, node_ids)| {
+                (
+                  entries + node_ids.len(),
+                  bytes + value.estimated_bytes() + node_ids.len() * std::mem::size_of::<NodeId>(),
+                )
+              })
+          });
+          indexes.push(info(IndexKind::Ordered, entries, bytes));
+        }
+      }
     }
-    if let Some(v) = options.replication_retention_min_entries {
-      db_options = db_options.replication_retention_min_entries(v);
+    indexes
+  }
+}
+
+impl PropIndexState {
+  /// Record `value` (or no value) as what `node_id` holds in `slot`
+  ///
+  /// A unique value already held by another node keeps its owner; that
+  /// only happens for duplicates that predate the constraint.
+  fn set(&mut self, node_id: NodeId, slot: IndexSlot, prop: &PropDef, value: Option<IndexValue>) {
+    self.vacate(node_id, slot);
+    let Some(value) = value else {
+      return;
+    };
+    if prop.unique {
+      self
+        .by_value
+        .entry(slot)
+        .or_default()
+        .entry(value.clone())
+        .or_insert(node_id);
     }
-    if let Some(v) = options.replication_retention_min_ms {
-      db_options = db_options.replication_retention_min_ms(v);
+    if prop.ordered {
+      self
+        .by_order
+        .entry(slot)
+        .or_default()
+        .entry(value.clone())
+        .or_default()
+        .push(node_id);
     }
-    let db = open_single_file(&db_path, db_options)?;
+    self.by_node.entry(node_id).or_default().push((slot, value));
+  }
 
-    // Initialize schema in a transaction
-    let mut nodes: HashMap<String, NodeDef> = HashMap::new();
-    let mut edges: HashMap<String, EdgeDef> = HashMap::new();
-    let mut key_prefix_to_node: HashMap<String, String> = HashMap::new();
+  /// Drop every entry of a deleted node
+  fn remove_node(&mut self, node_id: NodeId) {
+    let Some(entries) = self.by_node.remove(&node_id) else {
+      return;
+    };
+    for (slot, value) in entries {
+      self.remove_entry(node_id, slot, &value);
+    }
+  }
 
-    // Process node definitions
-    for mut node_def in options.nodes {
-      // Define label
-      let label_id = db.label_id_or_create(&node_def.name);
-      node_def.label_id = Some(label_id);
+  /// Drop the entry `node_id` holds in `slot`, if any
+  fn vacate(&mut self, node_id: NodeId, slot: IndexSlot) {
+    let Some(entries) = self.by_node.get_mut(&node_id) else {
+      return;
+    };
+    let Some(pos) = entries.iter().position(|(held, _)| *held == slot) else {
+      return;
+    };
+    let (_, value) = entries.swap_remove(pos);
+    if entries.is_empty() {
+      self.by_node.remove(&node_id);
+    }
+    self.remove_entry(node_id, slot, &value);
+  }
 
-      // Define property keys
-      for prop_name in node_def.props.keys() {
-        let prop_key_id = db.propkey_id_or_create(prop_name);
-        node_def.prop_key_ids.insert(prop_name.clone(), prop_key_id);
+  fn remove_entry(&mut self, node_id: NodeId, slot: IndexSlot, value: &IndexValue) {
+    if let Some(by_value) = self.by_value.get_mut(&slot) {
+      if by_value.get(value) == Some(&node_id) {
+        by_value.remove(value);
       }
-
-      key_prefix_to_node.insert(node_def.key_prefix.clone(), node_def.name.clone());
-      nodes.insert(node_def.name.clone(), node_def);
     }
-
-    // Process edge definitions
-    for mut edge_def in options.edges {
-      // Define edge type
-      let etype_id = db.etype_id_or_create(&edge_def.name);
-      edge_def.etype_id = Some(etype_id);
-
-      // Define property keys
-      for prop_name in edge_def.props.keys() {
-        let prop_key_id = db.propkey_id_or_create(prop_name);
-        edge_def.prop_key_ids.insert(prop_name.clone(), prop_key_id);
+    if let Some(by_order) = self.by_order.get_mut(&slot) {
+      if let Some(node_ids) = by_order.get_mut(value) {
+        node_ids.retain(|&id| id != node_id);
+        if node_ids.is_empty() {
+          by_order.remove(value);
+        }
       }
-
-      edges.insert(edge_def.name.clone(), edge_def);
     }
-
-    Ok(Self {
-      db,
-      close_checkpoint_if_wal_usage_at_least,
-      nodes,
-      edges,
-      key_prefix_to_node,
-    })
   }
+}
 
-  // ========================================================================
-  // Node Operations
-  // ========================================================================
-
-  /// Create a new node
-  pub fn create_node(
-    &mut self,
-    node_type: &str,
-    key_suffix: &str,
-    props: HashMap<String, PropValue>,
-  ) -> Result<NodeRef> {
-    let node_def = self
-      .nodes
-      .get(node_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?
-      .clone();
+/// Whether `node_id` holds `value` of prop `key_id`, as the caller sees it
+fn holds(db: &SingleFileDB, node_id: NodeId, key_id: PropKeyId, value: &IndexValue) -> bool {
+  db.node_prop(node_id, key_id)
+    .and_then(|current| IndexValue::from_prop(&current))
+    .as_ref()
+    == Some(value)
+}
 
-    let full_key = node_def.key(key_suffix);
+/// Error for `value` of a unique prop already held by `owner`
+fn duplicate_error(
+  db: &SingleFileDB,
+  node_def: &NodeDef,
+  prop_name: &str,
+  value: &PropValue,
+  owner: NodeId,
+) -> KiteError {
+  let owner_key = db
+    .node_key(owner)
+    .map(|key| format!(" ({key})"))
+    .unwrap_or_default();
+  KiteError::Constraint(format!(
+    "Property '{prop_name}' of {} must be unique: {value:?} is already used by node {owner}{owner_key}",
+    node_def.name
+  ))
+}
 
-    // Begin transaction
-    let mut handle = begin_tx(&self.db)?;
+// ============================================================================
+// Node Reference
+// ============================================================================
 
-    // Create the node with key
-    let node_opts = NodeOpts {
-      key: Some(full_key.clone()),
-      labels: node_def.label_id.map(|id| vec![id]),
-      props: None,
-    };
-    let node_id = create_node(&mut handle, node_opts)?;
+/// Reference to a node in the database
+#[derive(Debug, Clone)]
+pub struct NodeRef {
+  /// Node ID
+  id: NodeId,
+  /// Full key (if available)
+  key: Option<String>,
+  /// Node type name
+  node_type: Arc<str>,
+}
 
-    // Set properties
-    for (prop_name, value) in props {
-      if let Some(&prop_key_id) = node_def.prop_key_ids.get(&prop_name) {
-        set_node_prop(&mut handle, node_id, prop_key_id, value)?;
-      }
+impl NodeRef {
+  pub fn new(id: NodeId, key: Option<String>, node_type: impl Into<Arc<str>>) -> Self {
+    Self {
+      id,
+      key,
+      node_type: node_type.into(),
     }
+  }
 
-    // Commit
-    commit(&mut handle)?;
+  pub fn id(&self) -> NodeId {
+    self.id
+  }
 
-    Ok(NodeRef::new(node_id, Some(full_key), node_type))
+  pub fn key(&self) -> Option<&str> {
+    self.key.as_deref()
   }
 
-  /// Insert a node using fluent builder API
-  ///
-  /// This method provides a more ergonomic way to create nodes with properties
-  /// using the builder pattern. Use `.values()` to specify the node data,
-  /// then either `.execute()` to insert without returning, or `.returning()`
-  /// to get the created node reference.
-  ///
-  /// # Example
-  /// ```rust,no_run
-  /// # use kitedb::api::kite::Kite;
-  /// # use kitedb::types::PropValue;
-  /// # use std::collections::HashMap;
-  /// # fn main() -> kitedb::error::Result<()> {
-  /// # let mut kite: Kite = unimplemented!();
-  /// # let props: HashMap<String, PropValue> = HashMap::new();
-  /// // Insert and get the node reference
-  /// let user = kite.insert("User")?
-  ///     .values("alice", props)?
-  ///     .returning()?;
-  ///
-  /// // Insert without returning (slightly faster)
-  /// kite.insert("User")?
-  ///     .values("bob", HashMap::new())?
-  ///     .execute()?;
-  /// # Ok(())
-  /// # }
-  /// ```
-  pub fn insert(&mut self, node_type: &str) -> Result<KiteInsertBuilder<'_>> {
-    let key_prefix = self
-      .nodes
-      .get(node_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?
-      .key_prefix
-      .clone();
+  pub fn node_type(&self) -> &str {
+    &self.node_type
+  }
 
-    Ok(KiteInsertBuilder {
-      ray: self,
-      node_type: node_type.to_string(),
-      key_prefix,
-    })
+  pub fn into_parts(self) -> (NodeId, Option<String>, Arc<str>) {
+    (self.id, self.key, self.node_type)
   }
+}
 
-  /// Upsert a node using fluent builder API
-  ///
-  /// Creates the node if it doesn't exist, otherwise updates properties
-  /// on the existing node.
-  pub fn upsert(&mut self, node_type: &str) -> Result<KiteUpsertBuilder<'_>> {
-    let key_prefix = self
-      .nodes
-      .get(node_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?
-      .key_prefix
-      .clone();
+// ============================================================================
+// Node Merging
+// ============================================================================
 
-    Ok(KiteUpsertBuilder {
-      ray: self,
-      node_type: node_type.to_string(),
-      key_prefix,
-    })
-  }
+/// Which side wins when both nodes of a merge set the same property
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum MergePropStrategy {
+  /// Keep the surviving node's value
+  #[default]
+  KeepWins,
+  /// Overwrite with the removed node's value
+  RemoveWins,
+}
 
-  /// Get a node by key (direct read, no transaction overhead)
-  pub fn get(&self, node_type: &str, key_suffix: &str) -> Result<Option<NodeRef>> {
-    let node_def = self
-      .nodes
-      .get(node_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?;
+/// Outcome of [`Kite::merge_nodes`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct MergeResult {
+  /// Edges re-pointed onto the surviving node
+  pub edges_moved: usize,
+  /// Edges dropped because they became self-loops on a `no_self_loops` type
+  pub self_loops_dropped: usize,
+  /// Node properties written to the surviving node
+  pub props_merged: usize,
+}
 
-    let full_key = node_def.key(key_suffix);
+/// One edge for [`Kite::link_many`]
+#[derive(Debug, Clone)]
+pub struct LinkSpec {
+  pub src: NodeId,
+  pub edge_type: String,
+  pub dst: NodeId,
+  pub props: HashMap<String, PropValue>,
+}
 
-    // Direct read without transaction
-    let node_id = node_by_key_db(&self.db, &full_key);
+/// Outcome of [`Kite::link_many`]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct LinkManyResult {
+  /// Edges created
+  pub added: usize,
+  /// Edges that already existed and were left as they are
+  pub existing: usize,
+}
 
-    match node_id {
-      Some(id) => Ok(Some(NodeRef::new(id, Some(full_key), node_type))),
-      None => Ok(None),
-    }
-  }
+/// A condition on one node property, for [`Kite::find_where`]
+#[derive(Debug, Clone, PartialEq)]
+pub enum PropFilter {
+  /// The prop equals a value
+  Eq(PropValue),
+  /// The prop lies in an inclusive range; the prop must be declared ordered
+  Range { min: PropValue, max: PropValue },
+  /// The JSON prop holds `value` at `path` (see [`PropValue::json_path`])
+  JsonPathEq {
+    path: String,
+    value: serde_json::Value,
+  },
+}
 
-  /// Get a node by ID (direct read, no transaction overhead)
-  pub fn node_by_id(&self, node_id: NodeId) -> Result<Option<NodeRef>> {
-    // Direct read without transaction
-    let exists = node_exists_db(&self.db, node_id);
+/// Outcome of [`Kite::get_if_changed`]
+#[derive(Debug, Clone)]
+pub enum NodeIfChanged {
+  /// No node has this ID
+  NotFound,
+  /// The node still matches the caller's known version
+  NotModified,
+  /// The node differs from the caller's known version
+  Modified {
+    node: NodeRef,
+    /// Current version, as returned by [`Kite::node_version`]
+    version: String,
+  },
+}
 
-    if exists {
-      // Look up the node's key from snapshot/delta
-      let key = self.db.node_key(node_id);
+// ============================================================================
+// Kite Options
+// ============================================================================
 
-      // Try to determine node type from key prefix
-      let node_type = if let Some(ref k) = key {
-        // Find matching node def by key prefix
-        self
-          .nodes
-          .values()
-          .find(|def| k.starts_with(&def.key_prefix))
-          .map(|def| def.name.as_str())
-          .unwrap_or("unknown")
-      } else {
-        "unknown"
-      };
+/// Options for opening a Kite database
+#[derive(Debug, Clone)]
+pub struct KiteOptions {
+  /// Node type definitions
+  pub nodes: Vec<NodeDef>,
+  /// Edge type definitions
+  pub edges: Vec<EdgeDef>,
+  /// Open in read-only mode
+  pub read_only: bool,
+  /// Create database if it doesn't exist
+  pub create_if_missing: bool,
+  /// Synchronization mode for WAL writes (default: Full)
+  pub sync_mode: SyncMode,
+  /// Enable group commit (coalesce WAL flushes across commits)
+  pub group_commit_enabled: bool,
+  /// Group commit window in milliseconds
+  pub group_commit_window_ms: u64,
+  /// Enable MVCC (snapshot isolation + conflict detection)
+  pub mvcc: bool,
+  /// MVCC GC interval in ms
+  pub mvcc_gc_interval_ms: Option<u64>,
+  /// MVCC retention in ms
+  pub mvcc_retention_ms: Option<u64>,
+  /// Record version history on every commit so `begin_read_at` can serve
+  /// past generations (costs a version per write)
+  pub mvcc_retain_history: bool,
+  /// MVCC max version chain depth
+  pub mvcc_max_chain_depth: Option<usize>,
+  /// WAL size in bytes (default: 4MB)
+  pub wal_size: Option<usize>,
+  /// Separate WAL file location (applies when the database is created)
+  pub wal_path: Option<PathBuf>,
+  /// WAL usage threshold (0.0-1.0) to trigger auto-checkpoint
+  pub checkpoint_threshold: Option<f64>,
+  /// Close-time WAL usage threshold (0.0-1.0) to trigger blocking checkpoint
+  pub close_checkpoint_if_wal_usage_at_least: Option<f64>,
+  /// Replication role (disabled | primary | replica)
+  pub replication_role: ReplicationRole,
+  /// Optional replication sidecar path override
+  pub replication_sidecar_path: Option<PathBuf>,
+  /// Source primary db path (replica role only)
+  pub replication_source_db_path: Option<PathBuf>,
+  /// Source primary sidecar path override (replica role only)
+  pub replication_source_sidecar_path: Option<PathBuf>,
+  /// Segment rotation threshold in bytes (primary role only)
+  pub replication_segment_max_bytes: Option<u64>,
+  /// Minimum retained entries window (primary role only)
+  pub replication_retention_min_entries: Option<u64>,
+  /// Minimum retained segment age in milliseconds (primary role only)
+  pub replication_retention_min_ms: Option<u64>,
+  /// Cache configuration; hot `get` key lookups are served from its key cache
+  pub cache: Option<CacheOptions>,
+}
 
-      Ok(Some(NodeRef::new(node_id, key, node_type)))
-    } else {
-      Ok(None)
+impl KiteOptions {
+  pub fn new() -> Self {
+    Self {
+      nodes: Vec::new(),
+      edges: Vec::new(),
+      read_only: false,
+      create_if_missing: true,
+      sync_mode: SyncMode::Full,
+      group_commit_enabled: false,
+      group_commit_window_ms: 2,
+      mvcc: false,
+      mvcc_gc_interval_ms: None,
+      mvcc_retention_ms: None,
+      mvcc_retain_history: false,
+      mvcc_max_chain_depth: None,
+      wal_size: None,
+      wal_path: None,
+      checkpoint_threshold: None,
+      close_checkpoint_if_wal_usage_at_least: Some(0.2),
+      replication_role: ReplicationRole::Disabled,
+      replication_sidecar_path: None,
+      replication_source_db_path: None,
+      replication_source_sidecar_path: None,
+      replication_segment_max_bytes: None,
+      replication_retention_min_entries: None,
+      replication_retention_min_ms: None,
+      cache: None,
     }
   }
 
-  /// Check if a node exists (direct read, no transaction overhead)
-  pub fn exists(&self, node_id: NodeId) -> bool {
-    // Direct read without transaction
-    node_exists_db(&self.db, node_id)
+  pub fn node(mut self, node: NodeDef) -> Self {
+    self.nodes.push(node);
+    self
   }
 
-  /// Delete a node
-  pub fn delete_node(&mut self, node_id: NodeId) -> Result<bool> {
-    let mut handle = begin_tx(&self.db)?;
-    let deleted = delete_node(&mut handle, node_id)?;
-    commit(&mut handle)?;
-    Ok(deleted)
+  pub fn edge(mut self, edge: EdgeDef) -> Self {
+    self.edges.push(edge);
+    self
   }
 
-  /// Get a node property (direct read, no transaction overhead)
-  pub fn prop(&self, node_id: NodeId, prop_name: &str) -> Option<PropValue> {
-    let prop_key_id = self.db.propkey_id(prop_name)?;
-    // Direct read without transaction
-    node_prop_db(&self.db, node_id, prop_key_id)
+  pub fn read_only(mut self, value: bool) -> Self {
+    self.read_only = value;
+    self
   }
 
-  /// Set a node property
-  pub fn set_prop(&mut self, node_id: NodeId, prop_name: &str, value: PropValue) -> Result<()> {
-    let prop_key_id = self.db.propkey_id_or_create(prop_name);
+  pub fn sync_mode(mut self, mode: SyncMode) -> Self {
+    self.sync_mode = mode;
+    self
+  }
 
-    let mut handle = begin_tx(&self.db)?;
-    set_node_prop(&mut handle, node_id, prop_key_id, value)?;
-    commit(&mut handle)?;
-    Ok(())
+  /// Enable or disable group commit (coalesce WAL flushes across commits)
+  pub fn group_commit_enabled(mut self, value: bool) -> Self {
+    self.group_commit_enabled = value;
+    self
   }
 
-  /// Set multiple node properties in a single transaction
-  pub fn set_props<I, K>(&mut self, node_id: NodeId, props: I) -> Result<()>
-  where
-    I: IntoIterator<Item = (K, PropValue)>,
-    K: AsRef<str>,
-  {
-    let mut iter = props.into_iter();
-    let Some((first_name, first_value)) = iter.next() else {
-      return Ok(());
-    };
+  /// Set the group commit window in milliseconds
+  pub fn group_commit_window_ms(mut self, value: u64) -> Self {
+    self.group_commit_window_ms = value;
+    self
+  }
 
-    let mut handle = begin_tx(&self.db)?;
+  /// Set sync mode to Normal (fsync on checkpoint only)
+  pub fn sync_normal(mut self) -> Self {
+    self.sync_mode = SyncMode::Normal;
+    self
+  }
 
-    let first_key_id = self.db.propkey_id_or_create(first_name.as_ref());
-    set_node_prop(&mut handle, node_id, first_key_id, first_value)?;
+  /// Set sync mode to Off (no fsync)
+  pub fn sync_off(mut self) -> Self {
+    self.sync_mode = SyncMode::Off;
+    self
+  }
 
-    for (prop_name, value) in iter {
-      let prop_key_id = self.db.propkey_id_or_create(prop_name.as_ref());
-      set_node_prop(&mut handle, node_id, prop_key_id, value)?;
-    }
+  pub fn mvcc(mut self, value: bool) -> Self {
+    self.mvcc = value;
+    self
+  }
 
-    commit(&mut handle)?;
-    Ok(())
+  pub fn mvcc_gc_interval_ms(mut self, value: u64) -> Self {
+    self.mvcc_gc_interval_ms = Some(value);
+    self
   }
 
-  /// Update a node by reference using fluent builder API
-  ///
-  /// # Example
-  /// ```rust,no_run
-  /// # use kitedb::api::kite::Kite;
-  /// # use kitedb::types::PropValue;
-  /// # fn main() -> kitedb::error::Result<()> {
-  /// # let mut kite: Kite = unimplemented!();
-  /// let alice = match kite.get("User", "alice")? {
-  ///   Some(node) => node,
-  ///   None => return Ok(()),
-  /// };
-  /// kite.update(&alice)?
-  ///     .set("name", PropValue::String("Alice Updated".into()))
-  ///     .set("age", PropValue::I64(31))
-  ///     .execute()?;
-  /// # Ok(())
-  /// # }
-  /// ```
-  pub fn update(&mut self, node_ref: &NodeRef) -> Result<KiteUpdateNodeBuilder<'_>> {
-    // Verify node exists
-    let exists = {
-      let mut handle = begin_tx(&self.db)?;
-      let exists = node_exists(&handle, node_ref.id());
-      commit(&mut handle)?;
-      exists
-    };
-
-    if !exists {
-      return Err(KiteError::NodeNotFound(node_ref.id()));
-    }
+  pub fn mvcc_retention_ms(mut self, value: u64) -> Self {
+    self.mvcc_retention_ms = Some(value);
+    self
+  }
 
-    Ok(KiteUpdateNodeBuilder {
-      ray: self,
-      node_id: node_ref.id(),
-      updates: HashMap::new(),
-    })
+  pub fn mvcc_retain_history(mut self, value: bool) -> Self {
+    self.mvcc_retain_history = value;
+    self
   }
 
-  /// Update a node by ID using fluent builder API
-  ///
-  /// # Example
-  /// ```rust,no_run
-  /// # use kitedb::api::kite::Kite;
-  /// # use kitedb::types::{NodeId, PropValue};
-  /// # fn main() -> kitedb::error::Result<()> {
-  /// # let mut kite: Kite = unimplemented!();
-  /// # let node_id: NodeId = 1;
-  /// kite.update_by_id(node_id)?
-  ///     .set("name", PropValue::String("Updated".into()))
-  ///     .execute()?;
-  /// # Ok(())
-  /// # }
-  /// ```
-  pub fn update_by_id(&mut self, node_id: NodeId) -> Result<KiteUpdateNodeBuilder<'_>> {
-    // Verify node exists
-    let exists = {
-      let mut handle = begin_tx(&self.db)?;
-      let exists = node_exists(&handle, node_id);
-      commit(&mut handle)?;
-      exists
-    };
+  pub fn mvcc_max_chain_depth(mut self, value: usize) -> Self {
+    self.mvcc_max_chain_depth = Some(value);
+    self
+  }
 
-    if !exists {
-      return Err(KiteError::NodeNotFound(node_id));
-    }
+  /// Set WAL size in bytes
+  pub fn wal_size(mut self, value: usize) -> Self {
+    self.wal_size = Some(value);
+    self
+  }
 
-    Ok(KiteUpdateNodeBuilder {
-      ray: self,
-      node_id,
-      updates: HashMap::new(),
-    })
+  /// Set WAL size in megabytes
+  pub fn wal_size_mb(mut self, value: usize) -> Self {
+    self.wal_size = Some(value.saturating_mul(1024 * 1024));
+    self
   }
 
-  /// Upsert a node by ID using fluent builder API
-  ///
-  /// Creates the node if missing, otherwise updates properties.
-  pub fn upsert_by_id(
-    &mut self,
-    node_type: &str,
-    node_id: NodeId,
-  ) -> Result<KiteUpsertByIdBuilder<'_>> {
-    let node_def = self
-      .nodes
-      .get(node_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?
-      .clone();
+  /// Keep the WAL in a separate file (e.g. on faster storage)
+  pub fn wal_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+    self.wal_path = Some(path.as_ref().to_path_buf());
+    self
+  }
 
-    Ok(KiteUpsertByIdBuilder {
-      ray: self,
-      node_id,
-      node_def,
-      updates: HashMap::new(),
-    })
+  /// Set checkpoint threshold (0.0-1.0)
+  pub fn checkpoint_threshold(mut self, value: f64) -> Self {
+    self.checkpoint_threshold = Some(value.clamp(0.0, 1.0));
+    self
   }
 
-  /// Update a node by key using fluent builder API
+  /// Set close-time checkpoint threshold (0.0-1.0).
   ///
-  /// # Example
-  /// ```rust,no_run
-  /// # use kitedb::api::kite::Kite;
-  /// # use kitedb::types::PropValue;
-  /// # fn main() -> kitedb::error::Result<()> {
-  /// # let mut kite: Kite = unimplemented!();
-  /// kite.update_by_key("User", "alice")?
-  ///     .set("name", PropValue::String("Alice Updated".into()))
-  ///     .execute()?;
-  /// # Ok(())
-  /// # }
-  /// ```
-  pub fn update_by_key(
-    &mut self,
-    node_type: &str,
-    key_suffix: &str,
-  ) -> Result<KiteUpdateNodeBuilder<'_>> {
-    let full_key = self
-      .nodes
-      .get(node_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?
-      .key(key_suffix);
+  /// When set, `Kite::close()` checkpoints if WAL usage is at or above this threshold.
+  pub fn close_checkpoint_if_wal_usage_at_least(mut self, value: f64) -> Self {
+    self.close_checkpoint_if_wal_usage_at_least = Some(value.clamp(0.0, 1.0));
+    self
+  }
 
-    let node_id = {
-      let mut handle = begin_tx(&self.db)?;
-      let node_id =
-        node_by_key(&handle, &full_key).ok_or_else(|| KiteError::KeyNotFound(full_key.clone()))?;
-      commit(&mut handle)?;
-      node_id
-    };
+  /// Disable close-time checkpointing in `Kite::close()`.
+  pub fn disable_close_checkpoint(mut self) -> Self {
+    self.close_checkpoint_if_wal_usage_at_least = None;
+    self
+  }
 
-    Ok(KiteUpdateNodeBuilder {
-      ray: self,
-      node_id,
-      updates: HashMap::new(),
-    })
+  /// Set replication role (disabled | primary | replica)
+  pub fn replication_role(mut self, role: ReplicationRole) -> Self {
+    self.replication_role = role;
+    self
   }
 
-  // ========================================================================
-  // Edge Operations
-  // ========================================================================
+  /// Set replication sidecar path (for primary/replica modes)
+  pub fn replication_sidecar_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+    self.replication_sidecar_path = Some(path.as_ref().to_path_buf());
+    self
+  }
 
-  /// Create an edge between two nodes
-  pub fn link(&mut self, src: NodeId, edge_type: &str, dst: NodeId) -> Result<()> {
-    let edge_def = self
-      .edges
-      .get(edge_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+  /// Set replication source db path (replica role only)
+  pub fn replication_source_db_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+    self.replication_source_db_path = Some(path.as_ref().to_path_buf());
+    self
+  }
 
-    let etype_id = edge_def
-      .etype_id
-      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+  /// Set replication source sidecar path (replica role only)
+  pub fn replication_source_sidecar_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+    self.replication_source_sidecar_path = Some(path.as_ref().to_path_buf());
+    self
+  }
 
-    let mut handle = begin_tx(&self.db)?;
-    add_edge(&mut handle, src, etype_id, dst)?;
-    commit(&mut handle)?;
-    Ok(())
+  /// Set replication segment rotation threshold in bytes (primary role only)
+  pub fn replication_segment_max_bytes(mut self, value: u64) -> Self {
+    self.replication_segment_max_bytes = Some(value);
+    self
   }
 
-  /// Create an edge between two nodes with properties
-  ///
-  /// # Example
-  /// ```rust,no_run
-  /// # use kitedb::api::kite::{NodeRef, Kite};
-  /// # use kitedb::types::PropValue;
-  /// # use std::collections::HashMap;
-  /// # fn main() -> kitedb::error::Result<()> {
-  /// # let mut kite: Kite = unimplemented!();
-  /// # let alice: NodeRef = unimplemented!();
-  /// # let bob: NodeRef = unimplemented!();
-  /// let mut props = HashMap::new();
-  /// props.insert("weight".to_string(), PropValue::F64(0.5));
-  /// props.insert("since".to_string(), PropValue::String("2024".into()));
-  /// kite.link_with_props(alice.id(), "FOLLOWS", bob.id(), props)?;
-  /// # Ok(())
-  /// # }
-  /// ```
-  pub fn link_with_props(
-    &mut self,
-    src: NodeId,
-    edge_type: &str,
-    dst: NodeId,
-    props: HashMap<String, PropValue>,
-  ) -> Result<()> {
-    let edge_def = self
-      .edges
-      .get(edge_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?
-      .clone();
+  /// Set retention minimum entries to keep when pruning (primary role only)
+  pub fn replication_retention_min_entries(mut self, value: u64) -> Self {
+    self.replication_retention_min_entries = Some(value);
+    self
+  }
 
-    let etype_id = edge_def
-      .etype_id
-      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+  /// Set retention minimum segment age in milliseconds (primary role only)
+  pub fn replication_retention_min_ms(mut self, value: u64) -> Self {
+    self.replication_retention_min_ms = Some(value);
+    self
+  }
 
-    let mut handle = begin_tx(&self.db)?;
-    if props.is_empty() {
-      add_edge(&mut handle, src, etype_id, dst)?;
-    } else {
-      let mut prop_pairs = Vec::with_capacity(props.len());
-      for (prop_name, value) in props {
-        let prop_key_id = if let Some(&id) = edge_def.prop_key_ids.get(&prop_name) {
-          id
-        } else {
-          handle.db.propkey_id_or_create(&prop_name)
-        };
-        prop_pairs.push((prop_key_id, value));
-      }
-      handle
-        .db
-        .add_edge_with_props(src, etype_id, dst, prop_pairs)?;
-    }
+  /// Set cache options (`None` disables caching)
+  pub fn cache(mut self, options: Option<CacheOptions>) -> Self {
+    self.cache = options;
+    self
+  }
 
-    commit(&mut handle)?;
-    Ok(())
+  /// Recommended conservative profile (durability-first).
+  pub fn recommended_safe() -> Self {
+    Self::new()
+      .sync_mode(SyncMode::Full)
+      .group_commit_enabled(false)
+      .checkpoint_threshold(0.5)
   }
 
-  /// Remove an edge between two nodes
-  pub fn unlink(&mut self, src: NodeId, edge_type: &str, dst: NodeId) -> Result<bool> {
-    let edge_def = self
-      .edges
-      .get(edge_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
-
-    let etype_id = edge_def
-      .etype_id
-      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
-
-    let mut handle = begin_tx(&self.db)?;
-    let deleted = delete_edge(&mut handle, src, etype_id, dst)?;
-    commit(&mut handle)?;
-    Ok(deleted)
-  }
-
-  /// Check if an edge exists (direct read, no transaction overhead)
-  pub fn has_edge(&self, src: NodeId, edge_type: &str, dst: NodeId) -> Result<bool> {
-    let edge_def = self
-      .edges
-      .get(edge_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
-
-    let etype_id = edge_def
-      .etype_id
-      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
-
-    // Direct read without transaction
-    Ok(edge_exists_db(&self.db, src, etype_id, dst))
+  /// Recommended balanced profile (good throughput + durability tradeoff).
+  pub fn recommended_balanced() -> Self {
+    Self::new()
+      .sync_mode(SyncMode::Normal)
+      .group_commit_enabled(true)
+      .group_commit_window_ms(2)
+      .wal_size_mb(64)
+      .checkpoint_threshold(0.5)
   }
 
-  /// Get outgoing neighbors of a node (direct read, no transaction overhead)
-  pub fn neighbors_out(&self, node_id: NodeId, edge_type: Option<&str>) -> Result<Vec<NodeId>> {
-    let etype_id = match edge_type {
-      Some(name) => {
-        let edge_def = self
-          .edges
-          .get(name)
-          .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {name}").into()))?;
-        edge_def.etype_id
-      }
-      None => None,
-    };
-
-    // Direct read without transaction
-    Ok(neighbors_out_db(&self.db, node_id, etype_id))
+  /// Recommended profile for reopen-heavy workloads.
+  ///
+  /// Uses a smaller WAL and lower auto-checkpoint threshold to cap replay cost on reopen.
+  pub fn recommended_reopen_heavy() -> Self {
+    Self::new()
+      .sync_mode(SyncMode::Normal)
+      .group_commit_enabled(true)
+      .group_commit_window_ms(2)
+      .wal_size_mb(16)
+      .checkpoint_threshold(0.2)
   }
+}
 
-  /// Get incoming neighbors of a node (direct read, no transaction overhead)
-  pub fn neighbors_in(&self, node_id: NodeId, edge_type: Option<&str>) -> Result<Vec<NodeId>> {
-    let etype_id = match edge_type {
-      Some(name) => {
-        let edge_def = self
-          .edges
-          .get(name)
-          .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {name}").into()))?;
-        edge_def.etype_id
-      }
-      None => None,
-    };
-
-    // Direct read without transaction
-    let neighbors = neighbors_in_db(&self.db, node_id, etype_id);
-    Ok(neighbors)
+impl Default for KiteOptions {
+  fn default() -> Self {
+    Self::new()
   }
+}
 
-  // ========================================================================
-  // Edge Property Operations
-  // ========================================================================
+/// Preset runtime profile flavors for KiteDB.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KiteRuntimeProfileKind {
+  /// Durability-first defaults.
+  Safe,
+  /// Balanced throughput defaults.
+  Balanced,
+  /// Reopen-heavy defaults (lower WAL replay risk).
+  ReopenHeavy,
+}
 
-  /// Get an edge property (direct read, no transaction overhead)
+/// Runtime profile: open options + optional close policy.
+#[derive(Debug, Clone)]
+pub struct KiteRuntimeProfile {
+  /// Open-time options profile.
+  pub options: KiteOptions,
+  /// Optional close-time checkpoint trigger threshold.
   ///
-  /// Returns None if the edge doesn't exist or the property is not set.
-  pub fn edge_prop(
-    &self,
-    src: NodeId,
-    edge_type: &str,
-    dst: NodeId,
-    prop_name: &str,
-  ) -> Result<Option<PropValue>> {
-    let edge_def = self
-      .edges
-      .get(edge_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
-
-    let etype_id = edge_def
-      .etype_id
-      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
-
-    let prop_key_id = match self.db.propkey_id(prop_name) {
-      Some(id) => id,
-      None => return Ok(None), // Unknown property = not set
-    };
+  /// When set, call `Kite::close_with_checkpoint_if_wal_over(threshold)`.
+  pub close_checkpoint_if_wal_usage_at_least: Option<f64>,
+}
 
-    // Direct read without transaction
-    Ok(edge_prop_db(&self.db, src, etype_id, dst, prop_key_id))
+impl KiteRuntimeProfile {
+  pub fn from_kind(kind: KiteRuntimeProfileKind) -> Self {
+    match kind {
+      KiteRuntimeProfileKind::Safe => Self::safe(),
+      KiteRuntimeProfileKind::Balanced => Self::balanced(),
+      KiteRuntimeProfileKind::ReopenHeavy => Self::reopen_heavy(),
+    }
   }
 
-  /// Get all properties for an edge (direct read, no transaction overhead)
-  ///
-  /// Returns None if the edge doesn't exist.
-  pub fn edge_props(
-    &self,
-    src: NodeId,
-    edge_type: &str,
-    dst: NodeId,
-  ) -> Result<Option<HashMap<String, PropValue>>> {
-    let edge_def = self
-      .edges
-      .get(edge_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
-
-    let etype_id = edge_def
-      .etype_id
-      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+  pub fn safe() -> Self {
+    Self {
+      options: KiteOptions::recommended_safe(),
+      close_checkpoint_if_wal_usage_at_least: None,
+    }
+  }
 
-    // Direct read without transaction
-    let props = edge_props_db(&self.db, src, etype_id, dst);
+  pub fn balanced() -> Self {
+    Self {
+      options: KiteOptions::recommended_balanced(),
+      close_checkpoint_if_wal_usage_at_least: None,
+    }
+  }
 
-    // Convert PropKeyId -> String in the result
-    match props {
-      Some(props_by_id) => {
-        let mut result = HashMap::new();
-        for (key_id, value) in props_by_id {
-          if let Some(name) = self.db.propkey_name(key_id) {
-            result.insert(name, value);
-          }
-        }
-        Ok(Some(result))
-      }
-      None => Ok(None),
+  pub fn reopen_heavy() -> Self {
+    Self {
+      options: KiteOptions::recommended_reopen_heavy(),
+      close_checkpoint_if_wal_usage_at_least: Some(0.2),
     }
   }
+}
 
-  /// Set an edge property
-  pub fn set_edge_prop(
-    &mut self,
-    src: NodeId,
-    edge_type: &str,
-    dst: NodeId,
-    prop_name: &str,
-    value: PropValue,
-  ) -> Result<()> {
-    let edge_def = self
-      .edges
-      .get(edge_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+/// Convenience helper to open a KiteDB instance.
+pub fn kite<P: AsRef<Path>>(path: P, options: KiteOptions) -> Result<Kite> {
+  Kite::open(path, options)
+}
 
-    let etype_id = edge_def
-      .etype_id
-      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+// ============================================================================
+// Kite Database
+// ============================================================================
 
-    let prop_key_id = self.db.propkey_id_or_create(prop_name);
+/// High-level graph database API
+pub struct Kite {
+  /// Underlying database
+  db: SingleFileDB,
+  /// Close-time checkpoint threshold.
+  close_checkpoint_if_wal_usage_at_least: Option<f64>,
+  /// Node type definitions by name
+  nodes: HashMap<String, NodeDef>,
+  /// Edge type definitions by name
+  edges: HashMap<String, EdgeDef>,
+  /// Key prefix to node def mapping for fast lookups
+  key_prefix_to_node: BTreeMap<String, String>,
+  /// Whether any node type declares constrained props (skips lookups otherwise)
+  node_constraints: bool,
+  /// Committed values of `unique` and `ordered` node props
+  prop_index: Arc<PropIndex>,
+  /// Last allocated key number per `autoincrement` node type
+  key_counters: parking_lot::Mutex<HashMap<String, u64>>,
+}
 
-    let mut handle = begin_tx(&self.db)?;
-    set_edge_prop(&mut handle, src, etype_id, dst, prop_key_id, value)?;
-    commit(&mut handle)?;
-    Ok(())
-  }
+impl Kite {
+  /// Open or create a Kite database
+  ///
+  /// Schema mistakes (duplicate type names, overlapping key prefixes,
+  /// defaults that don't fit their prop) fail with one
+  /// [`KiteError::InvalidSchema`] listing all of them.
+  pub fn open<P: AsRef<Path>>(path: P, options: KiteOptions) -> Result<Self> {
+    validate_options(&options)?;
+    let path = path.as_ref();
+    if path.exists() && path.is_dir() {
+      return Err(KiteError::InvalidPath(
+        "Directory-format databases are no longer supported; use a .kitedb file path".to_string(),
+      ));
+    }
 
-  /// Set multiple edge properties
-  pub fn set_edge_props(
-    &mut self,
-    src: NodeId,
-    edge_type: &str,
-    dst: NodeId,
-    props: HashMap<String, PropValue>,
-  ) -> Result<()> {
-    if props.is_empty() {
-      return Ok(());
+    let mut db_path = PathBuf::from(path);
+    if db_path.extension().is_some() {
+      if !is_single_file_path(&db_path) {
+        let ext = db_path
+          .extension()
+          .map(|value| value.to_string_lossy())
+          .unwrap_or_else(|| "".into());
+        return Err(KiteError::InvalidPath(format!(
+          "Invalid database extension '.{ext}'. Single-file databases must use {} (or pass a path without an extension).",
+          single_file_extension()
+        )));
+      }
+    } else {
+      db_path = PathBuf::from(format!("{}{}", path.display(), single_file_extension()));
     }
 
-    let edge_def = self
-      .edges
-      .get(edge_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
-
-    let etype_id = edge_def
-      .etype_id
-      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+    let close_checkpoint_if_wal_usage_at_least = options
+      .close_checkpoint_if_wal_usage_at_least
+      .map(|value| value.clamp(0.0, 1.0));
 
-    let mut prop_pairs = Vec::with_capacity(props.len());
-    for (prop_name, value) in props {
-      let prop_key_id = if let Some(&id) = edge_def.prop_key_ids.get(&prop_name) {
-        id
-      } else {
-        self.db.propkey_id_or_create(&prop_name)
-      };
-      prop_pairs.push((prop_key_id, value));
+    let mut db_options = SingleFileOpenOptions::new()
+      .read_only(options.read_only)
+      .create_if_missing(options.create_if_missing)
+      .sync_mode(options.sync_mode)
+      .group_commit_enabled(options.group_commit_enabled)
+      .group_commit_window_ms(options.group_commit_window_ms)
+      .mvcc(options.mvcc)
+      .mvcc_retain_history(options.mvcc_retain_history)
+      .replication_role(options.replication_role);
+    if let Some(v) = options.mvcc_gc_interval_ms {
+      db_options = db_options.mvcc_gc_interval_ms(v);
+    }
+    if let Some(v) = options.mvcc_retention_ms {
+      db_options = db_options.mvcc_retention_ms(v);
+    }
+    if let Some(v) = options.mvcc_max_chain_depth {
+      db_options = db_options.mvcc_max_chain_depth(v);
+    }
+    if let Some(v) = options.wal_size {
+      db_options = db_options.wal_size(v);
+    }
+    if let Some(path) = options.wal_path.as_ref() {
+      db_options = db_options.wal_path(path);
+    }
+    if let Some(v) = options.checkpoint_threshold {
+      db_options = db_options.checkpoint_threshold(v);
+    }
+    if let Some(path) = options.replication_sidecar_path.as_ref() {
+      db_options = db_options.replication_sidecar_path(path);
+    }
+    if let Some(path) = options.replication_source_db_path.as_ref() {
+      db_options = db_options.replication_source_db_path(path);
+    }
+    if let Some(path) = options.replication_source_sidecar_path.as_ref() {
+      db_options = db_options.replication_source_sidecar_path(path);
+    }
+    if let Some(v) = options.replication_segment_max_bytes {
+      db_options = db_options.replication_segment_max_bytes(v);
+    }
+    if let Some(v) = options.replication_retention_min_entries {
+      db_options = db_options.replication_retention_min_entries(v);
     }
+    if let Some(v) = options.replication_retention_min_ms {
+      db_options = db_options.replication_retention_min_ms(v);
+    }
+    if options.cache.is_some() {
+      db_options = db_options.cache(options.cache);
+    }
+    let db = open_single_file(&db_path, db_options)?;
 
-    let mut handle = begin_tx(&self.db)?;
-    handle.db.set_edge_props(src, etype_id, dst, prop_pairs)?;
-    commit(&mut handle)?;
-    Ok(())
-  }
+    // Initialize schema in a transaction
+    let mut nodes: HashMap<String, NodeDef> = HashMap::new();
+    let mut edges: HashMap<String, EdgeDef> = HashMap::new();
+    let mut key_prefix_to_node: BTreeMap<String, String> = BTreeMap::new();
 
-  /// Delete an edge property
-  pub fn del_edge_prop(
-    &mut self,
-    src: NodeId,
-    edge_type: &str,
-    dst: NodeId,
-    prop_name: &str,
-  ) -> Result<()> {
-    let edge_def = self
-      .edges
-      .get(edge_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+    // Process node definitions
+    for mut node_def in options.nodes {
+      // Define label
+      let label_id = db.label_id_or_create(&node_def.name);
+      node_def.label_id = Some(label_id);
 
-    let etype_id = edge_def
-      .etype_id
-      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+      // Define property keys
+      for (prop_name, prop) in &node_def.props {
+        let prop_key_id = node_propkey_id(&db, prop_name)?;
+        node_def.prop_key_ids.insert(prop_name.clone(), prop_key_id);
+        if prop.normalize {
+          db.set_vector_normalized(prop_key_id, true);
+        }
+      }
 
-    let prop_key_id = self
-      .db
-      .propkey_id(prop_name)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown property: {prop_name}").into()))?;
+      key_prefix_to_node.insert(node_def.key_prefix.clone(), node_def.name.clone());
+      nodes.insert(node_def.name.clone(), node_def);
+    }
 
-    let mut handle = begin_tx(&self.db)?;
-    del_edge_prop(&mut handle, src, etype_id, dst, prop_key_id)?;
-    commit(&mut handle)?;
-    Ok(())
-  }
+    // Process edge definitions
+    for mut edge_def in options.edges {
+      // Define edge type
+      let etype_id = db.etype_id_or_create(&edge_def.name);
+      edge_def.etype_id = Some(etype_id);
+      if db.etype_no_self_loops(etype_id) != edge_def.no_self_loops && !db.is_read_only() {
+        let mut handle = begin_tx(&db)?;
+        handle
+          .db
+          .set_etype_no_self_loops(etype_id, edge_def.no_self_loops)?;
+        commit(&mut handle)?;
+      }
 
-  /// Update edge properties using fluent builder API
-  ///
-  /// Returns an `UpdateEdgeBuilder` that allows setting multiple properties
-  /// in a single transaction.
-  ///
-  /// # Example
-  /// ```rust,no_run
-  /// # use kitedb::api::kite::Kite;
-  /// # use kitedb::types::{NodeId, PropValue};
-  /// # fn main() -> kitedb::error::Result<()> {
-  /// # let mut kite: Kite = unimplemented!();
-  /// # let alice_id: NodeId = 1;
-  /// # let bob_id: NodeId = 2;
-  /// kite.update_edge(alice_id, "FOLLOWS", bob_id)?
-  ///    .set("weight", PropValue::F64(0.9))
-  ///    .set("since", PropValue::String("2024".to_string()))
-  ///    .execute()?;
-  /// # Ok(())
-  /// # }
-  /// ```
-  pub fn update_edge(
-    &mut self,
-    src: NodeId,
-    edge_type: &str,
-    dst: NodeId,
-  ) -> Result<KiteUpdateEdgeBuilder<'_>> {
-    let edge_def = self
-      .edges
-      .get(edge_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+      // Define property keys
+      for prop_name in edge_def.props.keys() {
+        let prop_key_id = edge_propkey_id(&db, prop_name)?;
+        edge_def.prop_key_ids.insert(prop_name.clone(), prop_key_id);
+      }
 
-    let etype_id = edge_def
-      .etype_id
-      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+      edges.insert(edge_def.name.clone(), edge_def);
+    }
 
-    Ok(KiteUpdateEdgeBuilder {
-      ray: self,
-      src,
-      etype_id,
-      dst,
-      updates: HashMap::new(),
-    })
-  }
+    register_enum_values(&db, &nodes, &edges)?;
 
-  /// Upsert edge properties using fluent builder API
-  ///
-  /// Creates the edge if missing, otherwise updates properties.
-  pub fn upsert_edge(
-    &mut self,
-    src: NodeId,
-    edge_type: &str,
-    dst: NodeId,
-  ) -> Result<KiteUpsertEdgeBuilder<'_>> {
-    let edge_def = self
-      .edges
-      .get(edge_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
-    let etype_id = edge_def.etype_id.ok_or_else(|| {
-      KiteError::InvalidSchema(format!("Edge type not initialized: {edge_type}").into())
-    })?;
+    let node_constraints = nodes.values().any(NodeDef::has_constraints);
+    let prop_index = Arc::new(PropIndex::build(&db, &nodes, &key_prefix_to_node));
+    if !prop_index.is_empty() {
+      let index = Arc::clone(&prop_index);
+      db.set_commit_check(Some(Arc::new(move |db, pending| index.commit(db, pending))));
+    }
+    let key_counters = parking_lot::Mutex::new(load_key_counters(&db, &nodes)?);
 
-    Ok(KiteUpsertEdgeBuilder {
-      ray: self,
-      src,
-      etype_id,
-      dst,
-      updates: HashMap::new(),
+    Ok(Self {
+      db,
+      close_checkpoint_if_wal_usage_at_least,
+      nodes,
+      edges,
+      key_prefix_to_node,
+      node_constraints,
+      prop_index,
+      key_counters,
     })
   }
 
   // ========================================================================
-  // Listing and Counting
+  // Node Operations
   // ========================================================================
 
-  /// Count all nodes in the database
-  ///
-  /// This is an O(1) operation when possible, using cached counts.
-  pub fn count_nodes(&self) -> u64 {
-    count_nodes(&self.db)
+  /// Create a new node
+  pub fn create_node(
+    &mut self,
+    node_type: &str,
+    key_suffix: &str,
+    props: HashMap<String, PropValue>,
+  ) -> Result<NodeRef> {
+    let node_def = self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?
+      .clone();
+
+    let full_key = node_def.key(key_suffix);
+    for (prop_name, value) in &props {
+      node_def.check_prop(prop_name, value)?;
+    }
+
+    // Begin transaction
+    let mut handle = begin_tx(&self.db)?;
+
+    // Create the node with key
+    let node_opts = NodeOpts {
+      key: Some(full_key.clone()),
+      labels: node_def.label_id.map(|id| vec![id]),
+      props: None,
+    };
+    let node_id = create_node(&mut handle, node_opts)?;
+    self.prop_index.check_all(
+      &self.db,
+      &node_def,
+      node_id,
+      props.iter().map(|(name, value)| (name.as_str(), value)),
+    )?;
+
+    // Set properties
+    for (prop_name, value) in props {
+      if let Some(&prop_key_id) = node_def.prop_key_ids.get(&prop_name) {
+        set_node_prop(&mut handle, node_id, prop_key_id, value)?;
+      }
+    }
+
+    // Commit
+    commit(&mut handle)?;
+
+    Ok(NodeRef::new(node_id, Some(full_key), node_type))
   }
 
-  /// Count nodes of a specific type
+  /// Allocate the next key suffix for an `autoincrement` node type
   ///
-  /// This requires iteration to filter by key prefix.
-  pub fn count_nodes_by_type(&self, node_type: &str) -> Result<u64> {
+  /// Numbers are handed out under a lock, so concurrent inserts never share
+  /// one, and the counter is persisted in the current transaction. Allocate
+  /// inside the transaction that inserts the node (see
+  /// [`Self::with_write_tx`]) so the number is only used up if the insert
+  /// commits; called outside a transaction, the counter is committed in one
+  /// of its own.
+  pub fn next_key(&self, node_type: &str) -> Result<String> {
     let node_def = self
       .nodes
       .get(node_type)
       .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?;
-
-    let prefix = &node_def.key_prefix;
-    let mut count = 0u64;
-
-    for node_id in list_nodes(&self.db) {
-      if let Some(key) = self.node_key_internal(node_id) {
-        if key.starts_with(prefix) {
-          count += 1;
-        }
+    let mut counters = self.key_counters.lock();
+    let counter = counters.get_mut(node_type).ok_or_else(|| {
+      KiteError::InvalidSchema(
+        format!("Node type {node_type} does not use autoincrement keys").into(),
+      )
+    })?;
+    loop {
+      *counter += 1;
+      if self
+        .db
+        .node_by_key(&node_def.key(&counter.to_string()))
+        .is_none()
+      {
+        break;
       }
     }
 
-    Ok(count)
+    let mut handle = begin_tx(&self.db)?;
+    handle
+      .db
+      .set_metadata(&key_counter_metadata_key(node_type), &counter.to_le_bytes())?;
+    commit(&mut handle)?;
+    Ok(counter.to_string())
   }
 
-  /// Count all edges
-  pub fn count_edges(&self) -> u64 {
-    count_edges(&self.db, None)
+  /// Run `f` in one write transaction, committing it if `f` succeeds
+  ///
+  /// Kite calls made by `f` join the transaction, so keys `f` takes from
+  /// [`Self::next_key`] are persisted with the nodes that use them. If `f` or
+  /// the commit fails, the transaction rolls back and its key numbers are
+  /// handed out again. Inside an outer transaction `f` just joins it.
+  pub fn with_write_tx<T>(&mut self, f: impl FnOnce(&mut Self) -> Result<T>) -> Result<T> {
+    if self.db.has_transaction() {
+      self.db.require_write_tx()?;
+      return f(self);
+    }
+
+    self.db.begin(false)?;
+    let result = f(self).and_then(|value| self.db.commit().map(|()| value));
+    if result.is_err() {
+      if self.db.has_transaction() {
+        self.db.rollback()?;
+      }
+      *self.key_counters.lock() = load_key_counters(&self.db, &self.nodes)?;
+    }
+    result
   }
 
-  /// Count edges of a specific type
-  pub fn count_edges_by_type(&self, edge_type: &str) -> Result<u64> {
-    let edge_def = self
-      .edges
-      .get(edge_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
-
-    let etype_id = edge_def
-      .etype_id
-      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
-
-    Ok(count_edges(&self.db, Some(etype_id)))
-  }
-
-  #[cfg(feature = "bench-profile")]
-  pub fn take_profile_snapshot(&self) -> (u64, u64) {
-    self.db.take_profile_snapshot()
-  }
-
-  /// List all node IDs
-  pub fn list_nodes(&self) -> Vec<NodeId> {
-    list_nodes(&self.db)
+  /// Last key number allocated for an `autoincrement` node type
+  ///
+  /// Zero until a key has been allocated.
+  pub fn key_counter(&self, node_type: &str) -> Result<u64> {
+    if !self.nodes.contains_key(node_type) {
+      return Err(KiteError::InvalidSchema(
+        format!("Unknown node type: {node_type}").into(),
+      ));
+    }
+    self
+      .key_counters
+      .lock()
+      .get(node_type)
+      .copied()
+      .ok_or_else(|| {
+        KiteError::InvalidSchema(
+          format!("Node type {node_type} does not use autoincrement keys").into(),
+        )
+      })
   }
 
-  /// Iterate over all nodes of a specific type
+  /// Insert a node using fluent builder API
   ///
-  /// Returns an iterator that yields `NodeRef` for each matching node.
-  /// Filters nodes by matching their key prefix.
+  /// This method provides a more ergonomic way to create nodes with properties
+  /// using the builder pattern. Use `.values()` to specify the node data,
+  /// then either `.execute()` to insert without returning, or `.returning()`
+  /// to get the created node reference.
   ///
   /// # Example
   /// ```rust,no_run
   /// # use kitedb::api::kite::Kite;
+  /// # use kitedb::types::PropValue;
+  /// # use std::collections::HashMap;
   /// # fn main() -> kitedb::error::Result<()> {
-  /// # let kite: Kite = unimplemented!();
-  /// for node_ref in kite.all("User")? {
-  ///     println!("User: {:?}", node_ref.id());
-  /// }
+  /// # let mut kite: Kite = unimplemented!();
+  /// # let props: HashMap<String, PropValue> = HashMap::new();
+  /// // Insert and get the node reference
+  /// let user = kite.insert("User")?
+  ///     .values("alice", props)?
+  ///     .returning()?;
+  ///
+  /// // Insert without returning (slightly faster)
+  /// kite.insert("User")?
+  ///     .values("bob", HashMap::new())?
+  ///     .execute()?;
   /// # Ok(())
   /// # }
   /// ```
-  pub fn all(&self, node_type: &str) -> Result<impl Iterator<Item = NodeRef> + '_> {
-    let node_def = self
+  pub fn insert(&mut self, node_type: &str) -> Result<KiteInsertBuilder<'_>> {
+    let key_prefix = self
       .nodes
       .get(node_type)
       .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?
+      .key_prefix
       .clone();
 
-    let prefix = node_def.key_prefix.clone();
-    let node_type_arc: Arc<str> = node_type.to_string().into();
-
-    Ok(list_nodes(&self.db).into_iter().filter_map(move |node_id| {
-      let key = self.node_key_internal(node_id)?;
-      if key.starts_with(&prefix) {
-        Some(NodeRef::new(node_id, Some(key), Arc::clone(&node_type_arc)))
-      } else {
-        None
-      }
-    }))
-  }
-
-  /// List all edges in the database
-  pub fn list_all_edges(&self) -> Vec<FullEdge> {
-    list_edges(&self.db, ListEdgesOptions::default())
+    Ok(KiteInsertBuilder {
+      ray: self,
+      node_type: node_type.to_string(),
+      key_prefix,
+    })
   }
 
-  /// Iterate over all edges, optionally filtered by type
-  ///
-  /// Returns an iterator that yields edge information.
+  /// Upsert a node using fluent builder API
   ///
-  /// # Example
-  /// ```rust,no_run
-  /// # use kitedb::api::kite::Kite;
-  /// # fn main() -> kitedb::error::Result<()> {
-  /// # let kite: Kite = unimplemented!();
-  /// for edge in kite.all_edges(Some("FOLLOWS"))? {
-  ///     println!("{} -> {}", edge.src, edge.dst);
-  /// }
-  /// # Ok(())
-  /// # }
-  /// ```
-  pub fn all_edges(&self, edge_type: Option<&str>) -> Result<impl Iterator<Item = FullEdge> + '_> {
-    let etype_id = match edge_type {
-      Some(name) => {
-        let edge_def = self
-          .edges
-          .get(name)
-          .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {name}").into()))?;
-        edge_def.etype_id
-      }
-      None => None,
-    };
+  /// Creates the node if it doesn't exist, otherwise updates properties
+  /// on the existing node.
+  pub fn upsert(&mut self, node_type: &str) -> Result<KiteUpsertBuilder<'_>> {
+    let key_prefix = self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?
+      .key_prefix
+      .clone();
 
-    let options = ListEdgesOptions { etype: etype_id };
-    Ok(list_edges(&self.db, options).into_iter())
+    Ok(KiteUpsertBuilder {
+      ray: self,
+      node_type: node_type.to_string(),
+      key_prefix,
+    })
   }
 
-  /// Get a lightweight node reference without loading properties
-  ///
-  /// This is faster than `get()` when you only need the node reference
-  /// for traversals or edge operations.
-  ///
-  /// # Example
-  /// ```rust,no_run
-  /// # use kitedb::api::kite::Kite;
-  /// # fn main() -> kitedb::error::Result<()> {
-  /// # let kite: Kite = unimplemented!();
-  /// let user_ref = kite.node_ref("User", "alice")?;
-  /// if let Some(node) = user_ref {
-  ///     // Can now use node.id() for edges, traversals, etc.
-  /// }
-  /// # Ok(())
-  /// # }
-  /// ```
-  /// Get a lightweight node reference by key (direct read, no transaction overhead)
-  ///
-  /// This is faster than `get()` as it only returns a reference without loading properties.
-  /// Use this when you only need the node ID for traversals or edge operations.
+  /// Get a node by key (direct read, no transaction overhead)
   ///
-  /// # Example
-  /// ```rust,no_run
-  /// # use kitedb::api::kite::Kite;
-  /// # fn main() -> kitedb::error::Result<()> {
-  /// # let kite: Kite = unimplemented!();
-  /// // Fast: only gets reference (~85ns)
-  /// if let Some(node) = kite.node_ref("User", "alice")? {
-  ///     // Can now use node.id() for edges, traversals, etc.
-  ///     let friends = kite.from(node.id()).out(Some("FOLLOWS"))?.to_vec();
-  /// }
-  /// # Ok(())
-  /// # }
-  /// ```
-  pub fn node_ref(&self, node_type: &str, key_suffix: &str) -> Result<Option<NodeRef>> {
+  /// Archived nodes are treated as missing; see [`Self::get_including_archived`].
+  pub fn get(&self, node_type: &str, key_suffix: &str) -> Result<Option<NodeRef>> {
+    let node_ref = self.get_including_archived(node_type, key_suffix)?;
+    Ok(node_ref.filter(|node_ref| !self.is_archived(node_ref.id)))
+  }
+
+  /// Get a node by key, whether or not it is archived
+  pub fn get_including_archived(
+    &self,
+    node_type: &str,
+    key_suffix: &str,
+  ) -> Result<Option<NodeRef>> {
     let node_def = self
       .nodes
       .get(node_type)
@@ -1884,1398 +2729,4759 @@ impl Kite {
     }
   }
 
-  /// Helper to get node key from database
-  fn node_key_internal(&self, node_id: NodeId) -> Option<String> {
-    self.db.node_key(node_id)
-  }
+  /// Get a node by ID (direct read, no transaction overhead)
+  pub fn node_by_id(&self, node_id: NodeId) -> Result<Option<NodeRef>> {
+    // Direct read without transaction
+    let exists = node_exists_db(&self.db, node_id);
 
-  // ========================================================================
-  // Schema Access
-  // ========================================================================
+    if exists {
+      // Look up the node's key from snapshot/delta
+      let key = self.db.node_key(node_id);
 
-  /// Get a node definition by name
-  pub fn node_def(&self, name: &str) -> Option<&NodeDef> {
-    self.nodes.get(name)
-  }
+      // Try to determine node type from key prefix
+      let node_type = key
+        .as_deref()
+        .and_then(|k| node_def_for_key(&self.nodes, &self.key_prefix_to_node, k))
+        .map(|def| def.name.as_str())
+        .unwrap_or("unknown");
 
-  /// Get an edge definition by name
-  pub fn edge_def(&self, name: &str) -> Option<&EdgeDef> {
-    self.edges.get(name)
+      Ok(Some(NodeRef::new(node_id, key, node_type)))
+    } else {
+      Ok(None)
+    }
   }
 
-  /// Get all node type names
-  pub fn node_types(&self) -> Vec<&str> {
-    self.nodes.keys().map(|s| s.as_str()).collect()
+  /// Content version of a node, usable as an HTTP ETag
+  ///
+  /// A hex hash of the node's key and props: it changes whenever a prop is
+  /// set or removed and is stable across reopens. Labels and edges aren't
+  /// covered. `None` if the node doesn't exist.
+  pub fn node_version(&self, node_id: NodeId) -> Option<String> {
+    let props = self.db.node_props(node_id)?;
+    let key = self.db.node_key(node_id);
+    Some(format!(
+      "{:016x}",
+      node_content_hash(key.as_deref(), &props)
+    ))
+  }
+
+  /// Get a node only if its version differs from `known_version`
+  ///
+  /// Lets an API layer answer conditional requests (`If-None-Match`)
+  /// without re-sending nodes the client already has.
+  pub fn get_if_changed(&self, node_id: NodeId, known_version: &str) -> Result<NodeIfChanged> {
+    let Some(version) = self.node_version(node_id) else {
+      return Ok(NodeIfChanged::NotFound);
+    };
+    if version == known_version {
+      return Ok(NodeIfChanged::NotModified);
+    }
+    match self.node_by_id(node_id)? {
+      Some(node) => Ok(NodeIfChanged::Modified { node, version }),
+      None => Ok(NodeIfChanged::NotFound),
+    }
   }
 
-  /// Get all edge type names
-  pub fn edge_types(&self) -> Vec<&str> {
-    self.edges.keys().map(|s| s.as_str()).collect()
+  /// Check if a node exists (direct read, no transaction overhead)
+  pub fn exists(&self, node_id: NodeId) -> bool {
+    // Direct read without transaction
+    node_exists_db(&self.db, node_id)
   }
 
-  // ========================================================================
-  // Traversal
-  // ========================================================================
+  /// Delete a node
+  pub fn delete_node(&mut self, node_id: NodeId) -> Result<bool> {
+    let mut handle = begin_tx(&self.db)?;
+    let deleted = delete_node(&mut handle, node_id)?;
+    commit(&mut handle)?;
+    Ok(deleted)
+  }
 
-  /// Start a traversal from a node
-  ///
-  /// Returns a traversal builder that can be used to chain traversal steps.
+  /// Archive a node: hide it without deleting it
   ///
-  /// # Example
-  ///
-  /// ```rust,no_run
-  /// # use kitedb::api::kite::{NodeRef, Kite};
-  /// # fn main() -> kitedb::error::Result<()> {
-  /// # let kite: Kite = unimplemented!();
-  /// # let alice: NodeRef = unimplemented!();
-  /// let friends = kite
-  ///     .from(alice.id())
-  ///     .out(Some("FOLLOWS"))?
-  ///     .out(Some("FOLLOWS"))?
-  ///     .to_vec();
-  /// # Ok(())
-  /// # }
-  /// ```
-  pub fn from(&self, node_id: NodeId) -> KiteTraversalBuilder<'_> {
-    KiteTraversalBuilder::new(self, vec![node_id])
+  /// Stamps [`ARCHIVED_AT_PROP`] with the current time. Archived nodes, and
+  /// edges touching them, are skipped by `get`, `node_ref`, `all`,
+  /// `all_edges` and traversals unless archived nodes are asked for
+  /// explicitly; lookups by ID are unaffected. Returns false if the node was
+  /// already archived.
+  pub fn archive(&mut self, node_id: NodeId) -> Result<bool> {
+    if !self.exists(node_id) {
+      return Err(KiteError::NodeNotFound(node_id));
+    }
+    if self.is_archived(node_id) {
+      return Ok(false);
+    }
+    let prop_key_id = self.db.propkey_id_or_create(ARCHIVED_AT_PROP);
+    let mut handle = begin_tx(&self.db)?;
+    set_node_prop(&mut handle, node_id, prop_key_id, PropValue::I64(now_ms()))?;
+    commit(&mut handle)?;
+    Ok(true)
   }
 
-  /// Start a traversal from multiple nodes
-  pub fn from_nodes(&self, node_ids: Vec<NodeId>) -> KiteTraversalBuilder<'_> {
-    KiteTraversalBuilder::new(self, node_ids)
+  /// Restore an archived node; returns false if it wasn't archived
+  pub fn unarchive(&mut self, node_id: NodeId) -> Result<bool> {
+    let Some(prop_key_id) = self.db.propkey_id(ARCHIVED_AT_PROP) else {
+      return Ok(false);
+    };
+    if self.db.node_prop(node_id, prop_key_id).is_none() {
+      return Ok(false);
+    }
+    let mut handle = begin_tx(&self.db)?;
+    del_node_prop(&mut handle, node_id, prop_key_id)?;
+    commit(&mut handle)?;
+    Ok(true)
   }
 
-  // ========================================================================
-  // Pathfinding
-  // ========================================================================
+  /// Check whether a node is archived
+  pub fn is_archived(&self, node_id: NodeId) -> bool {
+    is_archived_db(&self.db, self.db.propkey_id(ARCHIVED_AT_PROP), node_id)
+  }
 
-  /// Find the shortest path between two nodes
+  /// Hard-delete nodes archived at least `older_than_ms` ago
   ///
-  /// Returns a path finding builder that can be configured with edge types,
-  /// direction, and maximum depth.
+  /// Deletes them (and their edges) in one transaction and returns how many
+  /// were removed.
+  pub fn purge_archived(&mut self, older_than_ms: u64) -> Result<usize> {
+    let Some(archived_key) = self.db.propkey_id(ARCHIVED_AT_PROP) else {
+      return Ok(0);
+    };
+    let cutoff = now_ms().saturating_sub(older_than_ms.min(i64::MAX as u64) as i64);
+    let expired: Vec<NodeId> = list_nodes(&self.db)
+      .into_iter()
+      .filter(|&node_id| {
+        matches!(
+          self.db.node_prop(node_id, archived_key),
+          Some(PropValue::I64(archived_at)) if archived_at <= cutoff
+        )
+      })
+      .collect();
+    if expired.is_empty() {
+      return Ok(0);
+    }
+
+    let mut handle = begin_tx(&self.db)?;
+    for &node_id in &expired {
+      delete_node(&mut handle, node_id)?;
+    }
+    commit(&mut handle)?;
+    Ok(expired.len())
+  }
+
+  /// Merge `remove_id` into `keep_id` and delete it
   ///
-  /// # Example
-  ///
-  /// ```rust,no_run
-  /// # use kitedb::api::kite::{NodeRef, Kite};
-  /// # fn main() -> kitedb::error::Result<()> {
-  /// # let kite: Kite = unimplemented!();
-  /// # let alice: NodeRef = unimplemented!();
-  /// # let bob: NodeRef = unimplemented!();
-  /// let path = kite
-  ///     .shortest_path(alice.id(), bob.id())
-  ///     .via("FOLLOWS")?
-  ///     .max_depth(5)
-  ///     .find();
-  ///
-  /// if path.found {
-  ///     println!("Path: {:?}", path.path);
-  ///     println!("Total weight: {}", path.total_weight);
-  /// }
-  /// # Ok(())
-  /// # }
-  /// ```
-  pub fn shortest_path(&self, source: NodeId, target: NodeId) -> KitePathBuilder<'_> {
-    KitePathBuilder::new(self, source, target)
-  }
-
-  /// Find shortest paths to any of the target nodes
-  pub fn shortest_path_to_any(&self, source: NodeId, targets: Vec<NodeId>) -> KitePathBuilder<'_> {
-    KitePathBuilder::new_multi(self, source, targets)
-  }
-
-  /// Check if a path exists between two nodes
-  ///
-  /// This is more efficient than `shortest_path()` when you only need to
-  /// know if a path exists, not the path itself.
-  pub fn has_path(
+  /// Re-points every edge of `remove_id` (with its props) onto `keep_id`,
+  /// copies its properties per `strategy`, adds its labels, then deletes
+  /// it, all in one transaction. Edges that turn into self-loops are
+  /// dropped when their type is flagged `no_self_loops`. When `keep_id`
+  /// already has a re-pointed edge, the edge props are combined with the
+  /// same strategy.
+  pub fn merge_nodes(
     &mut self,
-    source: NodeId,
-    target: NodeId,
-    edge_type: Option<&str>,
-  ) -> Result<bool> {
-    let path = self.shortest_path(source, target);
-    let path = if let Some(etype) = edge_type {
-      path.via(etype)?
-    } else {
-      path
-    };
-    Ok(path.find().found)
-  }
+    keep_id: NodeId,
+    remove_id: NodeId,
+    strategy: MergePropStrategy,
+  ) -> Result<MergeResult> {
+    if keep_id == remove_id {
+      return Err(KiteError::InvalidQuery(
+        "Cannot merge a node into itself".into(),
+      ));
+    }
+    for node_id in [keep_id, remove_id] {
+      if !self.exists(node_id) {
+        return Err(KiteError::NodeNotFound(node_id));
+      }
+    }
 
-  /// Get all nodes reachable from a source within a certain depth
-  ///
-  /// # Example
-  ///
-  /// ```rust,no_run
-  /// # use kitedb::api::kite::{NodeRef, Kite};
-  /// # fn main() -> kitedb::error::Result<()> {
-  /// # let kite: Kite = unimplemented!();
-  /// # let alice: NodeRef = unimplemented!();
-  /// let reachable = kite.reachable_from(alice.id(), 3, Some("FOLLOWS"))?;
-  /// println!("Alice can reach {} nodes in 3 hops", reachable.len());
-  /// # Ok(())
-  /// # }
-  /// ```
-  pub fn reachable_from(
-    &self,
-    source: NodeId,
-    max_depth: usize,
-    edge_type: Option<&str>,
-  ) -> Result<Vec<NodeId>> {
-    let etype = match edge_type {
-      Some(name) => {
-        let edge_def = self
-          .edges
-          .get(name)
-          .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {name}").into()))?;
-        edge_def.etype_id
+    let repoint = |node_id: NodeId| {
+      if node_id == remove_id {
+        keep_id
+      } else {
+        node_id
       }
-      None => None,
     };
+    // A loop on `remove_id` shows up in both directions; take it once.
+    let edges: Vec<(NodeId, ETypeId, NodeId)> = self
+      .db
+      .out_edges(remove_id)
+      .into_iter()
+      .map(|(etype, dst)| (remove_id, etype, dst))
+      .chain(
+        self
+          .db
+          .in_edges(remove_id)
+          .into_iter()
+          .filter(|&(_, src)| src != remove_id)
+          .map(|(etype, src)| (src, etype, remove_id)),
+      )
+      .collect();
+    let moved_edges: Vec<_> = edges
+      .into_iter()
+      .map(|(src, etype, dst)| {
+        let props = edge_props_db(&self.db, src, etype, dst).unwrap_or_default();
+        (repoint(src), etype, repoint(dst), props)
+      })
+      .collect();
+    // The archive flag isn't a user prop: `keep_id` stays as archived as it was.
+    let archived_key = self.db.propkey_id(ARCHIVED_AT_PROP);
+    let mut moved_props: Vec<(PropKeyId, PropValue)> = self
+      .db
+      .node_props(remove_id)
+      .unwrap_or_default()
+      .into_iter()
+      .filter(|(key_id, _)| {
+        Some(*key_id) != archived_key
+          && (strategy == MergePropStrategy::RemoveWins
+            || node_prop_db(&self.db, keep_id, *key_id).is_none())
+      })
+      .collect();
+    moved_props.sort_by_key(|(key_id, _)| *key_id);
+    let moved_labels: Vec<LabelId> = self
+      .db
+      .node_labels(remove_id)
+      .into_iter()
+      .filter(|&label_id| !self.db.node_has_label(keep_id, label_id))
+      .collect();
 
-    use super::traversal::{TraversalBuilder, TraversalDirection, TraverseOptions};
-
-    let options = TraverseOptions::new(TraversalDirection::Out, max_depth);
-
-    let results = TraversalBuilder::from_node(source)
-      .traverse(etype, options)
-      .collect_node_ids(|node_id, dir, etype_filter| self.neighbors(node_id, dir, etype_filter));
-
-    Ok(results)
-  }
-
-  // Internal helper to get neighbors for traversal/pathfinding (read-only, no transaction)
-  fn neighbors(
-    &self,
-    node_id: NodeId,
-    direction: super::traversal::TraversalDirection,
-    etype: Option<ETypeId>,
-  ) -> Vec<Edge> {
-    use super::traversal::TraversalDirection;
+    let mut result = MergeResult::default();
+    let mut handle = begin_tx(&self.db)?;
+    // Delete first so unique props held by `remove_id` are free to move.
+    delete_node(&mut handle, remove_id)?;
 
-    let mut edges = Vec::new();
+    for (key_id, value) in moved_props {
+      if let Some(prop_name) = self.db.propkey_name(key_id) {
+        self.check_node_prop(keep_id, &prop_name, &value)?;
+      }
+      set_node_prop(&mut handle, keep_id, key_id, value)?;
+      result.props_merged += 1;
+    }
+    for label_id in moved_labels {
+      handle.db.add_node_label(keep_id, label_id)?;
+    }
 
-    match direction {
-      TraversalDirection::Out => {
-        for (edge_etype, dst) in self.db.out_edges(node_id) {
-          if etype.is_some() && etype != Some(edge_etype) {
-            continue;
-          }
-          edges.push(Edge {
-            src: node_id,
-            etype: edge_etype,
-            dst,
-          });
-        }
+    for (src, etype, dst, props) in moved_edges {
+      if src == dst && self.db.etype_no_self_loops(etype) {
+        result.self_loops_dropped += 1;
+        continue;
       }
-      TraversalDirection::In => {
-        for (edge_etype, src) in self.db.in_edges(node_id) {
-          if etype.is_some() && etype != Some(edge_etype) {
+      if edge_exists(&handle, src, etype, dst) {
+        for (key_id, value) in props {
+          if strategy == MergePropStrategy::KeepWins
+            && edge_prop_db(&self.db, src, etype, dst, key_id).is_some()
+          {
             continue;
           }
-          edges.push(Edge {
-            src,
-            etype: edge_etype,
-            dst: node_id,
-          });
+          set_edge_prop(&mut handle, src, etype, dst, key_id, value)?;
         }
+      } else if props.is_empty() {
+        add_edge(&mut handle, src, etype, dst)?;
+      } else {
+        let mut prop_pairs: Vec<_> = props.into_iter().collect();
+        prop_pairs.sort_by_key(|(key_id, _)| *key_id);
+        handle.db.add_edge_with_props(src, etype, dst, prop_pairs)?;
       }
-      TraversalDirection::Both => {
-        edges.extend(self.neighbors(node_id, TraversalDirection::Out, etype));
-        edges.extend(self.neighbors(node_id, TraversalDirection::In, etype));
-      }
+      result.edges_moved += 1;
     }
 
-    edges
+    commit(&mut handle)?;
+    Ok(result)
   }
 
-  // ========================================================================
-  // Database Maintenance
-  // ========================================================================
+  /// Get a node property (direct read, no transaction overhead)
+  pub fn prop(&self, node_id: NodeId, prop_name: &str) -> Option<PropValue> {
+    let prop_key_id = readable_propkey_id(&self.db, prop_name)?;
+    // Direct read without transaction
+    node_prop_db(&self.db, node_id, prop_key_id)
+  }
 
-  /// Optimize (compact) the database
+  /// Get an integer property
   ///
-  /// This merges the write-ahead log (WAL) into the snapshot, reducing
-  /// file size and improving read performance. This is equivalent to
-  /// "VACUUM" in SQLite.
-  /// Call this periodically to reclaim space from deleted nodes/edges
-  /// and improve read performance.
-  pub fn optimize(&mut self) -> Result<()> {
-    self.db.optimize_single_file(None)
+  /// Returns `Ok(None)` when the prop is absent or null, and
+  /// [`KiteError::PropTypeMismatch`] when it holds another type.
+  pub fn prop_int(&self, node_id: NodeId, prop_name: &str) -> Result<Option<i64>> {
+    self.typed_prop(node_id, prop_name, PropValueTag::I64, |value| match value {
+      PropValue::I64(v) => Some(v),
+      _ => None,
+    })
   }
 
-  /// Get database statistics
-  pub fn stats(&self) -> DbStats {
-    self.db.stats()
+  /// Get a float property; see [`Self::prop_int`]
+  pub fn prop_float(&self, node_id: NodeId, prop_name: &str) -> Result<Option<f64>> {
+    self.typed_prop(node_id, prop_name, PropValueTag::F64, |value| match value {
+      PropValue::F64(v) => Some(v),
+      _ => None,
+    })
   }
 
-  /// Get a human-readable description of the database
-  ///
-  /// Useful for debugging and monitoring. Returns information about:
-  /// - Database path and format
-  /// - Schema (node types and edge types)
-  /// - Current statistics
-  ///
-  /// # Example
-  /// ```rust,no_run
-  /// # use kitedb::api::kite::Kite;
-  /// # fn main() {
-  /// # let kite: Kite = unimplemented!();
-  /// println!("{}", kite.describe());
-  /// // Output:
-  /// // KiteDB at /path/to/db.kitedb (single-file format)
-  /// // Schema:
-  /// //   Node types: User, Post, Comment
-  /// //   Edge types: FOLLOWS, LIKES, WROTE
-  /// // Statistics:
-  /// //   Nodes: 1,234 (snapshot: 1,200, delta: +34)
-  /// //   Edges: 5,678 (snapshot: 5,600, delta: +78)
-  /// # }
-  /// ```
-  pub fn describe(&self) -> String {
-    let stats = self.stats();
-    let path = self.db.path.display();
-    let format = "single-file";
-
-    let node_types: Vec<&str> = self.nodes.keys().map(|s| s.as_str()).collect();
-    let edge_types: Vec<&str> = self.edges.keys().map(|s| s.as_str()).collect();
-
-    let delta_nodes = stats.delta_nodes_created as i64 - stats.delta_nodes_deleted as i64;
-    let delta_edges = stats.delta_edges_added as i64 - stats.delta_edges_deleted as i64;
-
-    format!(
-      "KiteDB at {} ({} format)\n\
-       Schema:\n  \
-         Node types: {}\n  \
-         Edge types: {}\n\
-       Statistics:\n  \
-         Nodes: {} (snapshot: {}, delta: {:+})\n  \
-         Edges: {} (snapshot: {}, delta: {:+})\n  \
-         Recommend compact: {}",
-      path,
-      format,
-      if node_types.is_empty() {
-        "(none)".to_string()
-      } else {
-        node_types.join(", ")
+  /// Get a string property; see [`Self::prop_int`]
+  pub fn prop_string(&self, node_id: NodeId, prop_name: &str) -> Result<Option<String>> {
+    self.typed_prop(
+      node_id,
+      prop_name,
+      PropValueTag::String,
+      |value| match value {
+        PropValue::String(v) => Some(v),
+        _ => None,
       },
-      if edge_types.is_empty() {
-        "(none)".to_string()
-      } else {
-        edge_types.join(", ")
+    )
+  }
+
+  /// Get a boolean property; see [`Self::prop_int`]
+  pub fn prop_bool(&self, node_id: NodeId, prop_name: &str) -> Result<Option<bool>> {
+    self.typed_prop(
+      node_id,
+      prop_name,
+      PropValueTag::Bool,
+      |value| match value {
+        PropValue::Bool(v) => Some(v),
+        _ => None,
       },
-      stats.snapshot_nodes,
-      stats
-        .snapshot_nodes
-        .saturating_sub(stats.delta_nodes_created as u64),
-      delta_nodes,
-      stats.snapshot_edges,
-      stats
-        .snapshot_edges
-        .saturating_sub(stats.delta_edges_added as u64),
-      delta_edges,
-      if stats.recommend_compact { "yes" } else { "no" }
     )
   }
 
-  /// Check database integrity
+  /// Whether a node has a property set, without returning its value
+  pub fn has_prop(&self, node_id: NodeId, prop_name: &str) -> bool {
+    readable_propkey_id(&self.db, prop_name)
+      .is_some_and(|key_id| node_prop_db(&self.db, node_id, key_id).is_some())
+  }
+
+  /// Number of properties set on a node (0 if it doesn't exist)
+  pub fn prop_count(&self, node_id: NodeId) -> usize {
+    let archived_key = self.db.propkey_id(ARCHIVED_AT_PROP);
+    self.db.node_props(node_id).map_or(0, |props| {
+      props
+        .keys()
+        .filter(|&&key_id| Some(key_id) != archived_key)
+        .count()
+    })
+  }
+
+  fn typed_prop<T>(
+    &self,
+    node_id: NodeId,
+    prop_name: &str,
+    expected: PropValueTag,
+    extract: fn(PropValue) -> Option<T>,
+  ) -> Result<Option<T>> {
+    let value = match self.prop(node_id, prop_name) {
+      None | Some(PropValue::Null) => return Ok(None),
+      Some(value) => value,
+    };
+    let got = value.tag();
+    extract(value)
+      .map(Some)
+      .ok_or_else(|| KiteError::PropTypeMismatch {
+        prop: prop_name.to_string(),
+        expected,
+        got,
+      })
+  }
+
+  /// Set a node property
+  pub fn set_prop(&mut self, node_id: NodeId, prop_name: &str, value: PropValue) -> Result<()> {
+    self.check_node_prop(node_id, prop_name, &value)?;
+    let prop_key_id = node_propkey_id(&self.db, prop_name)?;
+
+    let mut handle = begin_tx(&self.db)?;
+    set_node_prop(&mut handle, node_id, prop_key_id, value)?;
+    commit(&mut handle)?;
+    Ok(())
+  }
+
+  /// Set a node property, returning the value it replaced (if any)
   ///
-  /// Performs validation checks on the database structure:
-  /// - Verifies edge reciprocity (for each outgoing edge, a matching incoming edge exists)
-  /// - Checks that all edges reference existing nodes
-  /// - Validates node key mappings
-  ///
-  /// Returns a `CheckResult` with `valid=true` if no errors found, or detailed
-  /// error/warning messages otherwise.
-  ///
-  /// # Example
-  /// ```rust,no_run
-  /// # use kitedb::api::kite::Kite;
-  /// # fn main() -> kitedb::error::Result<()> {
-  /// # let kite: Kite = unimplemented!();
-  /// let result = kite.check()?;
-  /// if !result.valid {
-  ///     for error in &result.errors {
-  ///         eprintln!("Error: {}", error);
-  ///     }
-  /// }
-  /// # Ok(())
-  /// # }
-  /// ```
-  pub fn check(&self) -> Result<CheckResult> {
-    let mut result = self.db.check();
-
-    // Schema consistency - verify all registered edge types have valid IDs
-    for (edge_name, edge_def) in &self.edges {
-      if edge_def.etype_id.is_none() {
-        result
-          .warnings
-          .push(format!("Edge type '{edge_name}' has no assigned etype_id"));
-      }
-    }
+  /// The read and the write share one transaction, so with MVCC enabled a
+  /// concurrent commit to the prop in between makes this one conflict.
+  pub fn replace_prop(
+    &mut self,
+    node_id: NodeId,
+    prop_name: &str,
+    value: PropValue,
+  ) -> Result<Option<PropValue>> {
+    self.check_node_prop(node_id, prop_name, &value)?;
+    let prop_key_id = node_propkey_id(&self.db, prop_name)?;
 
-    Ok(result)
+    let mut handle = begin_tx(&self.db)?;
+    let previous = handle.db.replace_node_prop(node_id, prop_key_id, value)?;
+    commit(&mut handle)?;
+    Ok(previous)
   }
 
-  // ========================================================================
-  // Database Access
-  // ========================================================================
+  /// Delete a node property, returning the value that was removed (if any);
+  /// see [`Self::replace_prop`]
+  pub fn take_prop(&mut self, node_id: NodeId, prop_name: &str) -> Result<Option<PropValue>> {
+    let prop_key_id = node_propkey_id(&self.db, prop_name)?;
 
-  /// Get a reference to the underlying SingleFileDB
-  pub fn raw(&self) -> &SingleFileDB {
-    &self.db
+    let mut handle = begin_tx(&self.db)?;
+    let previous = handle.db.take_node_prop(node_id, prop_key_id)?;
+    commit(&mut handle)?;
+    Ok(previous)
   }
 
-  /// Get a mutable reference to the underlying SingleFileDB
-  pub fn raw_mut(&mut self) -> &mut SingleFileDB {
-    &mut self.db
-  }
+  /// Set multiple node properties in a single transaction
+  pub fn set_props<I, K>(&mut self, node_id: NodeId, props: I) -> Result<()>
+  where
+    I: IntoIterator<Item = (K, PropValue)>,
+    K: AsRef<str>,
+  {
+    let mut iter = props.into_iter();
+    let Some((first_name, first_value)) = iter.next() else {
+      return Ok(());
+    };
 
-  /// Close the database
-  pub fn close(self) -> Result<()> {
-    match self.close_checkpoint_if_wal_usage_at_least {
-      Some(threshold) => close_single_file_with_options(
-        self.db,
-        SingleFileCloseOptions::new().checkpoint_if_wal_usage_at_least(threshold),
-      ),
-      None => close_single_file(self.db),
+    let node_def = self.constrained_node_def(node_id);
+    if let Some(node_def) = node_def {
+      node_def.check_prop(first_name.as_ref(), &first_value)?;
     }
-  }
-
-  /// Close the database and run a blocking checkpoint if WAL usage is above threshold.
-  ///
-  /// Use this for reopen-heavy workloads where you want to cap WAL replay cost on next open.
-  /// Threshold is clamped to [0.0, 1.0].
-  pub fn close_with_checkpoint_if_wal_over(self, threshold: f64) -> Result<()> {
-    close_single_file_with_options(
-      self.db,
-      SingleFileCloseOptions::new().checkpoint_if_wal_usage_at_least(threshold),
-    )
-  }
-}
-
-// ============================================================================
-// Traversal Builder for Kite
-// ============================================================================
 
-use super::traversal::{TraversalBuilder, TraversalDirection, TraversalResult, TraverseOptions};
+    let mut handle = begin_tx(&self.db)?;
 
-/// Traversal builder bound to a Kite database
-///
-/// Provides ergonomic traversal operations using edge type names.
-pub struct KiteTraversalBuilder<'a> {
-  ray: &'a Kite,
-  builder: TraversalBuilder,
-}
+    if let Some(node_def) = node_def {
+      self.prop_index.check(
+        &self.db,
+        node_def,
+        node_id,
+        first_name.as_ref(),
+        &first_value,
+      )?;
+    }
+    let first_key_id = node_propkey_id(&self.db, first_name.as_ref())?;
+    set_node_prop(&mut handle, node_id, first_key_id, first_value)?;
 
-impl<'a> KiteTraversalBuilder<'a> {
-  fn new(ray: &'a Kite, start_nodes: Vec<NodeId>) -> Self {
-    Self {
-      ray,
-      builder: TraversalBuilder::new(start_nodes),
+    for (prop_name, value) in iter {
+      if let Some(node_def) = node_def {
+        node_def.check_prop(prop_name.as_ref(), &value)?;
+        self
+          .prop_index
+          .check(&self.db, node_def, node_id, prop_name.as_ref(), &value)?;
+      }
+      let prop_key_id = node_propkey_id(&self.db, prop_name.as_ref())?;
+      set_node_prop(&mut handle, node_id, prop_key_id, value)?;
     }
+
+    commit(&mut handle)?;
+    Ok(())
   }
 
-  /// Traverse outgoing edges
+  /// Get a node property by the node's key, without a separate id lookup
   ///
-  /// @param edge_type - Edge type name (or None for all types)
-  pub fn out(mut self, edge_type: Option<&str>) -> Result<Self> {
-    let etype = self.resolve_etype(edge_type)?;
-    self.builder = self.builder.out(etype);
-    Ok(self)
+  /// Fails with [`KiteError::KeyNotFound`] when no live node has the key.
+  pub fn prop_by_key(
+    &self,
+    node_type: &str,
+    key_suffix: &str,
+    prop_name: &str,
+  ) -> Result<Option<PropValue>> {
+    let node_id = self.require_node_id(node_type, key_suffix)?;
+    Ok(self.prop(node_id, prop_name))
   }
 
-  /// Traverse incoming edges
-  pub fn r#in(mut self, edge_type: Option<&str>) -> Result<Self> {
-    let etype = self.resolve_etype(edge_type)?;
-    self.builder = self.builder.r#in(etype);
-    Ok(self)
+  /// Set a node property by the node's key; see [`Self::prop_by_key`]
+  pub fn set_prop_by_key(
+    &mut self,
+    node_type: &str,
+    key_suffix: &str,
+    prop_name: &str,
+    value: PropValue,
+  ) -> Result<()> {
+    let node_id = self.require_node_id(node_type, key_suffix)?;
+    self.set_prop(node_id, prop_name, value)
   }
 
-  /// Traverse edges in both directions
-  pub fn both(mut self, edge_type: Option<&str>) -> Result<Self> {
-    let etype = self.resolve_etype(edge_type)?;
-    self.builder = self.builder.both(etype);
-    Ok(self)
+  /// Delete a node property by the node's key; see [`Self::prop_by_key`]
+  pub fn del_prop_by_key(
+    &mut self,
+    node_type: &str,
+    key_suffix: &str,
+    prop_name: &str,
+  ) -> Result<()> {
+    let node_id = self.require_node_id(node_type, key_suffix)?;
+    let Some(prop_key_id) = self.db.propkey_id(prop_name) else {
+      return Ok(());
+    };
+    let mut handle = begin_tx(&self.db)?;
+    del_node_prop(&mut handle, node_id, prop_key_id)?;
+    commit(&mut handle)?;
+    Ok(())
   }
 
-  /// Variable-depth traversal
-  pub fn traverse(mut self, edge_type: Option<&str>, options: TraverseOptions) -> Result<Self> {
-    let etype = self.resolve_etype(edge_type)?;
-    self.builder = self.builder.traverse(etype, options);
-    Ok(self)
+  /// Resolve a live node's id by key, failing when it's missing
+  fn require_node_id(&self, node_type: &str, key_suffix: &str) -> Result<NodeId> {
+    let node_def = self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?;
+    let full_key = node_def.key(key_suffix);
+    node_by_key_db(&self.db, &full_key)
+      .filter(|&id| !self.is_archived(id))
+      .ok_or(KiteError::KeyNotFound(full_key))
   }
 
-  /// Limit the number of results
-  pub fn take(mut self, limit: usize) -> Self {
-    self.builder = self.builder.take(limit);
-    self
+  /// Set the same property value on many nodes in a single transaction
+  ///
+  /// Returns how many nodes were updated. Ids with no node are skipped, or
+  /// fail the call without writing anything when `strict` is set.
+  pub fn set_prop_bulk(
+    &mut self,
+    node_ids: &[NodeId],
+    prop_name: &str,
+    value: PropValue,
+    strict: bool,
+  ) -> Result<usize> {
+    let prop_key_id = node_propkey_id(&self.db, prop_name)?;
+    let mut handle = begin_tx(&self.db)?;
+    let mut seen = HashSet::with_capacity(node_ids.len());
+    let mut updated = 0;
+
+    for &node_id in node_ids {
+      if !seen.insert(node_id) {
+        continue;
+      }
+      if !node_exists(&handle, node_id) {
+        if strict {
+          return Err(KiteError::NodeNotFound(node_id));
+        }
+        continue;
+      }
+      self.check_node_prop(node_id, prop_name, &value)?;
+      set_node_prop(&mut handle, node_id, prop_key_id, value.clone())?;
+      updated += 1;
+    }
+
+    commit(&mut handle)?;
+    Ok(updated)
   }
 
-  /// Select specific properties to load (optimization)
+  /// Set `set_props` on every node of a type whose `match_prop` equals
+  /// `match_value`, in a single transaction
   ///
-  /// Only the specified properties will be loaded when collecting results,
-  /// reducing overhead. This is useful when you only need a few properties
-  /// from nodes that have many properties.
+  /// Returns how many nodes matched. The scan sees the active transaction's
+  /// pending creates and deletes and skips archived nodes, like
+  /// [`Self::all`]; if any write fails, none of them are applied.
+  pub fn update_where(
+    &mut self,
+    node_type: &str,
+    match_prop: &str,
+    match_value: &PropValue,
+    set_props: &HashMap<String, PropValue>,
+  ) -> Result<usize> {
+    let matched: Vec<NodeId> = self
+      .all(node_type)?
+      .map(|node_ref| node_ref.id)
+      .filter(|&node_id| self.prop(node_id, match_prop).as_ref() == Some(match_value))
+      .collect();
+    if matched.is_empty() || set_props.is_empty() {
+      return Ok(0);
+    }
+
+    let prop_key_ids: Vec<(&str, PropKeyId)> = set_props
+      .keys()
+      .map(|prop_name| Ok((prop_name.as_str(), node_propkey_id(&self.db, prop_name)?)))
+      .collect::<Result<_>>()?;
+    let mut handle = begin_tx(&self.db)?;
+    for &node_id in &matched {
+      for &(prop_name, prop_key_id) in &prop_key_ids {
+        let value = &set_props[prop_name];
+        self.check_node_prop(node_id, prop_name, value)?;
+        set_node_prop(&mut handle, node_id, prop_key_id, value.clone())?;
+      }
+    }
+
+    commit(&mut handle)?;
+    Ok(matched.len())
+  }
+
+  /// Update a node by reference using fluent builder API
   ///
   /// # Example
   /// ```rust,no_run
   /// # use kitedb::api::kite::Kite;
-  /// # use kitedb::types::NodeId;
+  /// # use kitedb::types::PropValue;
   /// # fn main() -> kitedb::error::Result<()> {
-  /// # let kite: Kite = unimplemented!();
-  /// # let user_id: NodeId = 1;
-  /// let friends = kite.from(user_id)
-  ///     .out(Some("FOLLOWS"))?
-  ///     .select(&["name", "avatar"]) // Only load name and avatar
-  ///     .to_vec();
+  /// # let mut kite: Kite = unimplemented!();
+  /// let alice = match kite.get("User", "alice")? {
+  ///   Some(node) => node,
+  ///   None => return Ok(()),
+  /// };
+  /// kite.update(&alice)?
+  ///     .set("name", PropValue::String("Alice Updated".into()))
+  ///     .set("age", PropValue::I64(31))
+  ///     .execute()?;
   /// # Ok(())
   /// # }
   /// ```
-  pub fn select(mut self, props: &[&str]) -> Self {
-    self.builder = self.builder.select_props(props);
-    self
-  }
-
-  /// Execute and collect node IDs
-  pub fn to_vec(self) -> Vec<NodeId> {
-    self
-      .builder
-      .collect_node_ids(|node_id, dir, etype| self.ray.neighbors(node_id, dir, etype))
-  }
-
-  /// Execute and get first result
-  pub fn first(self) -> Option<TraversalResult> {
-    self
-      .builder
-      .first(|node_id, dir, etype| self.ray.neighbors(node_id, dir, etype))
-  }
-
-  /// Execute and get first node ID
-  pub fn first_node(self) -> Option<NodeId> {
-    self
-      .builder
-      .first_node(|node_id, dir, etype| self.ray.neighbors(node_id, dir, etype))
-  }
+  pub fn update(&mut self, node_ref: &NodeRef) -> Result<KiteUpdateNodeBuilder<'_>> {
+    // Verify node exists
+    let exists = {
+      let mut handle = begin_tx(&self.db)?;
+      let exists = node_exists(&handle, node_ref.id());
+      commit(&mut handle)?;
+      exists
+    };
 
-  /// Execute and count results
-  pub fn count(self) -> usize {
-    self
-      .builder
-      .count(|node_id, dir, etype| self.ray.neighbors(node_id, dir, etype))
-  }
+    if !exists {
+      return Err(KiteError::NodeNotFound(node_ref.id()));
+    }
 
-  /// Execute and return iterator over traversal results
-  pub fn execute(self) -> impl Iterator<Item = TraversalResult> + 'a {
-    let ray = self.ray;
-    self
-      .builder
-      .execute(move |node_id, dir, etype| ray.neighbors(node_id, dir, etype))
+    Ok(KiteUpdateNodeBuilder {
+      ray: self,
+      node_id: node_ref.id(),
+      updates: HashMap::new(),
+    })
   }
 
-  /// Execute and return iterator over edges only
-  ///
-  /// This is useful when you want to collect the edges traversed rather than nodes.
-  /// Each result contains the source, destination, and edge type of edges encountered.
+  /// Update a node by ID using fluent builder API
   ///
   /// # Example
   /// ```rust,no_run
   /// # use kitedb::api::kite::Kite;
-  /// # use kitedb::types::NodeId;
+  /// # use kitedb::types::{NodeId, PropValue};
   /// # fn main() -> kitedb::error::Result<()> {
-  /// # let kite: Kite = unimplemented!();
-  /// # let user_id: NodeId = 1;
-  /// let edges: Vec<_> = kite.from(user_id)
-  ///     .out(Some("FOLLOWS"))?
-  ///     .edges()
-  ///     .collect();
-  ///
-  /// for edge in edges {
-  ///     println!("{} -[{}]-> {}", edge.src, edge.etype, edge.dst);
-  /// }
+  /// # let mut kite: Kite = unimplemented!();
+  /// # let node_id: NodeId = 1;
+  /// kite.update_by_id(node_id)?
+  ///     .set("name", PropValue::String("Updated".into()))
+  ///     .execute()?;
   /// # Ok(())
   /// # }
   /// ```
-  pub fn edges(self) -> impl Iterator<Item = Edge> + 'a {
-    let ray = self.ray;
-    self
-      .builder
-      .execute(move |node_id, dir, etype| ray.neighbors(node_id, dir, etype))
-      .filter_map(|result| {
-        result.edge.map(|e| Edge {
-          src: e.src,
-          etype: e.etype,
-          dst: e.dst,
-        })
-      })
-  }
-
-  /// Execute and return iterator over full edge details
-  ///
-  /// Similar to `edges()` but returns FullEdge structs.
-  pub fn full_edges(self) -> impl Iterator<Item = FullEdge> + 'a {
-    let ray = self.ray;
-    self
-      .builder
-      .execute(move |node_id, dir, etype| ray.neighbors(node_id, dir, etype))
-      .filter_map(move |result| {
-        result.edge.map(|e| FullEdge {
-          src: e.src,
-          etype: e.etype,
-          dst: e.dst,
-        })
-      })
-  }
+  pub fn update_by_id(&mut self, node_id: NodeId) -> Result<KiteUpdateNodeBuilder<'_>> {
+    // Verify node exists
+    let exists = {
+      let mut handle = begin_tx(&self.db)?;
+      let exists = node_exists(&handle, node_id);
+      commit(&mut handle)?;
+      exists
+    };
 
-  fn resolve_etype(&self, edge_type: Option<&str>) -> Result<Option<ETypeId>> {
-    match edge_type {
-      Some(name) => {
-        let edge_def = self
-          .ray
-          .edges
-          .get(name)
-          .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {name}").into()))?;
-        Ok(edge_def.etype_id)
-      }
-      None => Ok(None),
+    if !exists {
+      return Err(KiteError::NodeNotFound(node_id));
     }
+
+    Ok(KiteUpdateNodeBuilder {
+      ray: self,
+      node_id,
+      updates: HashMap::new(),
+    })
   }
-}
 
-// ============================================================================
-// Path Finding Builder for Kite
-// ============================================================================
+  /// Upsert a node by ID using fluent builder API
+  ///
+  /// Creates the node if missing, otherwise updates properties.
+  pub fn upsert_by_id(
+    &mut self,
+    node_type: &str,
+    node_id: NodeId,
+  ) -> Result<KiteUpsertByIdBuilder<'_>> {
+    let node_def = self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?
+      .clone();
 
-use super::pathfinding::{bfs, dijkstra, yen_k_shortest, PathConfig, PathResult};
+    Ok(KiteUpsertByIdBuilder {
+      ray: self,
+      node_id,
+      node_def,
+      key_suffix: None,
+      updates: HashMap::new(),
+    })
+  }
 
-/// Path finding builder bound to a Kite database
-///
-/// Provides ergonomic pathfinding operations using edge type names.
-pub struct KitePathBuilder<'a> {
-  ray: &'a Kite,
-  source: NodeId,
-  targets: HashSet<NodeId>,
-  allowed_etypes: HashSet<ETypeId>,
-  direction: TraversalDirection,
-  max_depth: usize,
-  weights: HashMap<(NodeId, ETypeId, NodeId), f64>,
-}
+  /// Update a node by key using fluent builder API
+  ///
+  /// # Example
+  /// ```rust,no_run
+  /// # use kitedb::api::kite::Kite;
+  /// # use kitedb::types::PropValue;
+  /// # fn main() -> kitedb::error::Result<()> {
+  /// # let mut kite: Kite = unimplemented!();
+  /// kite.update_by_key("User", "alice")?
+  ///     .set("name", PropValue::String("Alice Updated".into()))
+  ///     .execute()?;
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn update_by_key(
+    &mut self,
+    node_type: &str,
+    key_suffix: &str,
+  ) -> Result<KiteUpdateNodeBuilder<'_>> {
+    let full_key = self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?
+      .key(key_suffix);
 
-impl<'a> KitePathBuilder<'a> {
-  fn new(ray: &'a Kite, source: NodeId, target: NodeId) -> Self {
-    let mut targets = HashSet::new();
-    targets.insert(target);
+    let node_id = {
+      let mut handle = begin_tx(&self.db)?;
+      let node_id =
+        node_by_key(&handle, &full_key).ok_or_else(|| KiteError::KeyNotFound(full_key.clone()))?;
+      commit(&mut handle)?;
+      node_id
+    };
 
-    Self {
-      ray,
-      source,
-      targets,
-      allowed_etypes: HashSet::new(),
-      direction: TraversalDirection::Out,
-      max_depth: 100,
-      weights: HashMap::new(),
-    }
+    Ok(KiteUpdateNodeBuilder {
+      ray: self,
+      node_id,
+      updates: HashMap::new(),
+    })
   }
 
-  fn new_multi(ray: &'a Kite, source: NodeId, targets: Vec<NodeId>) -> Self {
-    Self {
-      ray,
-      source,
-      targets: targets.into_iter().collect(),
-      allowed_etypes: HashSet::new(),
-      direction: TraversalDirection::Out,
-      max_depth: 100,
-      weights: HashMap::new(),
-    }
-  }
+  // ========================================================================
+  // Edge Operations
+  // ========================================================================
 
-  /// Restrict traversal to specific edge type
+  /// Create an edge between two nodes
   ///
-  /// Can be called multiple times to allow multiple edge types.
-  pub fn via(mut self, edge_type: &str) -> Result<Self> {
-    let edge_def =
-      self.ray.edges.get(edge_type).ok_or_else(|| {
-        KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into())
-      })?;
+  /// Linking an edge that already exists is a no-op: it keeps its props and
+  /// any validity window set by [`link_valid`](Self::link_valid).
+  pub fn link(&mut self, src: NodeId, edge_type: &str, dst: NodeId) -> Result<()> {
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
 
-    if let Some(etype_id) = edge_def.etype_id {
-      self.allowed_etypes.insert(etype_id);
-    }
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
 
-    Ok(self)
+    let mut handle = begin_tx(&self.db)?;
+    add_edge(&mut handle, src, etype_id, dst)?;
+    commit(&mut handle)?;
+    Ok(())
   }
 
-  /// Set maximum search depth
-  pub fn max_depth(mut self, depth: usize) -> Self {
-    self.max_depth = depth;
-    self
-  }
+  /// Create an edge, with optional props, that is only valid within a time
+  /// window
+  ///
+  /// `valid_from` (inclusive) and `valid_to` (exclusive) are ms since the
+  /// Unix epoch, stored as the reserved [`VALID_FROM_PROP`] and
+  /// [`VALID_TO_PROP`] edge props; `None` leaves that side open. Linking an
+  /// existing edge replaces its window. Traversals only apply the window
+  /// when asked via [`KiteTraversalBuilder::as_of`].
+  pub fn link_valid(
+    &mut self,
+    src: NodeId,
+    edge_type: &str,
+    dst: NodeId,
+    props: HashMap<String, PropValue>,
+    valid_from: Option<i64>,
+    valid_to: Option<i64>,
+  ) -> Result<()> {
+    if let (Some(from), Some(to)) = (valid_from, valid_to) {
+      if from >= to {
+        return Err(KiteError::InvalidQuery(
+          format!("Empty validity window: valid_from {from} is not before valid_to {to}").into(),
+        ));
+      }
+    }
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+    for (prop_name, value) in &props {
+      edge_def.check_prop(prop_name, value)?;
+    }
 
-  /// Set traversal direction
-  pub fn direction(mut self, direction: TraversalDirection) -> Self {
-    self.direction = direction;
-    self
+    let mut handle = begin_tx(&self.db)?;
+    add_edge(&mut handle, src, etype_id, dst)?;
+    for (prop_name, value) in props {
+      let prop_key_id = match edge_def.prop_key_ids.get(&prop_name) {
+        Some(&id) => id,
+        None => edge_propkey_id(handle.db, &prop_name)?,
+      };
+      set_edge_prop(&mut handle, src, etype_id, dst, prop_key_id, value)?;
+    }
+    for (prop_name, bound) in [(VALID_FROM_PROP, valid_from), (VALID_TO_PROP, valid_to)] {
+      match bound {
+        Some(ms) => {
+          let prop_key_id = handle.db.propkey_id_or_create(prop_name);
+          set_edge_prop(
+            &mut handle,
+            src,
+            etype_id,
+            dst,
+            prop_key_id,
+            PropValue::I64(ms),
+          )?;
+        }
+        None => {
+          let Some(prop_key_id) = handle.db.propkey_id(prop_name) else {
+            continue;
+          };
+          if edge_prop_db(handle.db, src, etype_id, dst, prop_key_id).is_some() {
+            del_edge_prop(&mut handle, src, etype_id, dst, prop_key_id)?;
+          }
+        }
+      }
+    }
+    commit(&mut handle)?;
+    Ok(())
   }
 
-  /// Use bidirectional traversal
-  pub fn bidirectional(mut self) -> Self {
-    self.direction = TraversalDirection::Both;
-    self
-  }
+  /// Create an edge between two nodes addressed by key
+  ///
+  /// Endpoints are resolved by key; when `create_missing` is set, missing
+  /// endpoints are created (with no props) in the same transaction as the
+  /// edge. Creating an endpoint whose type has required props fails with
+  /// [`KiteError::Constraint`]. Returns the `(src, dst)` node IDs.
+  pub fn link_by_key(
+    &mut self,
+    src_type: &str,
+    src_key: &str,
+    edge_type: &str,
+    dst_type: &str,
+    dst_key: &str,
+    create_missing: bool,
+  ) -> Result<(NodeId, NodeId)> {
+    let etype_id = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+    let src_def = self
+      .nodes
+      .get(src_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {src_type}").into()))?;
+    let dst_def = self
+      .nodes
+      .get(dst_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {dst_type}").into()))?;
 
-  /// Find the shortest path using Dijkstra's algorithm
-  pub fn find(self) -> PathResult {
-    let config = PathConfig {
-      source: self.source,
-      targets: self.targets,
-      allowed_etypes: self.allowed_etypes,
-      direction: self.direction,
-      max_depth: self.max_depth,
-    };
+    let mut handle = begin_tx(&self.db)?;
+    let src = resolve_or_create_by_key(&mut handle, src_def, src_key, create_missing)?;
+    let dst = resolve_or_create_by_key(&mut handle, dst_def, dst_key, create_missing)?;
+    add_edge(&mut handle, src, etype_id, dst)?;
+    commit(&mut handle)?;
+    Ok((src, dst))
+  }
 
-    let weights = self.weights;
-    dijkstra(
-      config,
-      |node_id, dir, etype| self.ray.neighbors(node_id, dir, etype),
-      move |src, etype, dst| weights.get(&(src, etype, dst)).copied().unwrap_or(1.0),
+  /// Create an edge between two nodes with properties
+  ///
+  /// # Example
+  /// ```rust,no_run
+  /// # use kitedb::api::kite::{NodeRef, Kite};
+  /// # use kitedb::types::PropValue;
+  /// # use std::collections::HashMap;
+  /// # fn main() -> kitedb::error::Result<()> {
+  /// # let mut kite: Kite = unimplemented!();
+  /// # let alice: NodeRef = unimplemented!();
+  /// # let bob: NodeRef = unimplemented!();
+  /// let mut props = HashMap::new();
+  /// props.insert("weight".to_string(), PropValue::F64(0.5));
+  /// props.insert("since".to_string(), PropValue::String("2024".into()));
+  /// kite.link_with_props(alice.id(), "FOLLOWS", bob.id(), props)?;
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn link_with_props(
+    &mut self,
+    src: NodeId,
+    edge_type: &str,
+    dst: NodeId,
+    props: HashMap<String, PropValue>,
+  ) -> Result<()> {
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?
+      .clone();
+
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+    for (prop_name, value) in &props {
+      edge_def.check_prop(prop_name, value)?;
+    }
+
+    let mut handle = begin_tx(&self.db)?;
+    if props.is_empty() {
+      add_edge(&mut handle, src, etype_id, dst)?;
+    } else {
+      let mut prop_pairs = Vec::with_capacity(props.len());
+      for (prop_name, value) in props {
+        let prop_key_id = if let Some(&id) = edge_def.prop_key_ids.get(&prop_name) {
+          id
+        } else {
+          edge_propkey_id(handle.db, &prop_name)?
+        };
+        prop_pairs.push((prop_key_id, value));
+      }
+      handle
+        .db
+        .add_edge_with_props(src, etype_id, dst, prop_pairs)?;
+    }
+
+    commit(&mut handle)?;
+    Ok(())
+  }
+
+  /// Create many edges in one transaction
+  ///
+  /// Each distinct edge type is resolved once. Edges that already exist,
+  /// including repeats within `links`, keep their props and are counted as
+  /// existing. Nothing is written if any edge fails.
+  pub fn link_many(&mut self, links: Vec<LinkSpec>) -> Result<LinkManyResult> {
+    let mut edge_defs: HashMap<&str, (&EdgeDef, ETypeId)> = HashMap::new();
+    for link in &links {
+      let (edge_def, _) = match edge_defs.get(link.edge_type.as_str()) {
+        Some(&resolved) => resolved,
+        None => {
+          let edge_def = self.edges.get(&link.edge_type).ok_or_else(|| {
+            KiteError::InvalidSchema(format!("Unknown edge type: {}", link.edge_type).into())
+          })?;
+          let etype_id = edge_def
+            .etype_id
+            .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+          edge_defs.insert(link.edge_type.as_str(), (edge_def, etype_id));
+          (edge_def, etype_id)
+        }
+      };
+      for (prop_name, value) in &link.props {
+        edge_def.check_prop(prop_name, value)?;
+      }
+    }
+
+    let mut result = LinkManyResult::default();
+    let mut handle = begin_tx(&self.db)?;
+    for link in &links {
+      let (edge_def, etype_id) = edge_defs[link.edge_type.as_str()];
+      if edge_exists(&handle, link.src, etype_id, link.dst) {
+        result.existing += 1;
+        continue;
+      }
+      if link.props.is_empty() {
+        add_edge(&mut handle, link.src, etype_id, link.dst)?;
+      } else {
+        let prop_pairs = link
+          .props
+          .iter()
+          .map(|(prop_name, value)| {
+            let prop_key_id = match edge_def.prop_key_ids.get(prop_name) {
+              Some(&id) => id,
+              None => edge_propkey_id(handle.db, prop_name)?,
+            };
+            Ok((prop_key_id, value.clone()))
+          })
+          .collect::<Result<Vec<_>>>()?;
+        handle
+          .db
+          .add_edge_with_props(link.src, etype_id, link.dst, prop_pairs)?;
+      }
+      result.added += 1;
+    }
+    commit(&mut handle)?;
+    Ok(result)
+  }
+
+  /// Remove an edge between two nodes
+  pub fn unlink(&mut self, src: NodeId, edge_type: &str, dst: NodeId) -> Result<bool> {
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+
+    let mut handle = begin_tx(&self.db)?;
+    let deleted = delete_edge(&mut handle, src, etype_id, dst)?;
+    commit(&mut handle)?;
+    Ok(deleted)
+  }
+
+  /// Check if an edge exists (direct read, no transaction overhead)
+  ///
+  /// Edges with an archived end read as missing.
+  pub fn has_edge(&self, src: NodeId, edge_type: &str, dst: NodeId) -> Result<bool> {
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+
+    // Direct read without transaction
+    let archived_key = self.db.propkey_id(ARCHIVED_AT_PROP);
+    Ok(self.unarchived_edge_exists(archived_key, src, etype_id, dst))
+  }
+
+  /// Whether the edge exists and neither end is archived (`archived_key`
+  /// from `propkey_id(ARCHIVED_AT_PROP)`)
+  fn unarchived_edge_exists(
+    &self,
+    archived_key: Option<PropKeyId>,
+    src: NodeId,
+    etype_id: ETypeId,
+    dst: NodeId,
+  ) -> bool {
+    edge_exists_db(&self.db, src, etype_id, dst)
+      && !is_archived_db(&self.db, archived_key, src)
+      && !is_archived_db(&self.db, archived_key, dst)
+  }
+
+  /// Check many `(src, edge_type, dst)` edges at once, in input order
+  ///
+  /// Each edge type is resolved once for the whole call, so filtering a
+  /// long list of candidate pairs costs one lookup per edge. Edges with an
+  /// archived end read as missing.
+  pub fn has_edges(&self, edges: &[(NodeId, String, NodeId)]) -> Result<Vec<bool>> {
+    let archived_key = self.db.propkey_id(ARCHIVED_AT_PROP);
+    let mut etype_ids: HashMap<&str, ETypeId> = HashMap::new();
+    let mut out = Vec::with_capacity(edges.len());
+    for (src, edge_type, dst) in edges {
+      let etype_id = match etype_ids.get(edge_type.as_str()) {
+        Some(&id) => id,
+        None => {
+          let (_, id) = self.edge_def_and_id(edge_type)?;
+          etype_ids.insert(edge_type, id);
+          id
+        }
+      };
+      out.push(self.unarchived_edge_exists(archived_key, *src, etype_id, *dst));
+    }
+    Ok(out)
+  }
+
+  /// Get outgoing neighbors of a node (direct read, no transaction overhead)
+  ///
+  /// Archived neighbors are skipped.
+  pub fn neighbors_out(&self, node_id: NodeId, edge_type: Option<&str>) -> Result<Vec<NodeId>> {
+    let etype_id = match edge_type {
+      Some(name) => {
+        let edge_def = self
+          .edges
+          .get(name)
+          .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {name}").into()))?;
+        edge_def.etype_id
+      }
+      None => None,
+    };
+
+    // Direct read without transaction
+    let mut neighbors = neighbors_out_db(&self.db, node_id, etype_id);
+    self.retain_unarchived_ids(&mut neighbors);
+    Ok(neighbors)
+  }
+
+  /// Get incoming neighbors of a node (direct read, no transaction overhead)
+  ///
+  /// Archived neighbors are skipped.
+  pub fn neighbors_in(&self, node_id: NodeId, edge_type: Option<&str>) -> Result<Vec<NodeId>> {
+    let etype_id = match edge_type {
+      Some(name) => {
+        let edge_def = self
+          .edges
+          .get(name)
+          .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {name}").into()))?;
+        edge_def.etype_id
+      }
+      None => None,
+    };
+
+    // Direct read without transaction
+    let mut neighbors = neighbors_in_db(&self.db, node_id, etype_id);
+    self.retain_unarchived_ids(&mut neighbors);
+    Ok(neighbors)
+  }
+
+  /// Drop archived nodes from `node_ids`
+  fn retain_unarchived_ids(&self, node_ids: &mut Vec<NodeId>) {
+    if let Some(archived_key) = self.db.propkey_id(ARCHIVED_AT_PROP) {
+      node_ids.retain(|&node_id| !is_archived_db(&self.db, Some(archived_key), node_id));
+    }
+  }
+
+  /// Edges of `node_id` in `direction` whose other end isn't archived
+  fn unarchived_degree(
+    &self,
+    node_id: NodeId,
+    direction: TraversalDirection,
+    etype_id: Option<ETypeId>,
+  ) -> usize {
+    if self.db.propkey_id(ARCHIVED_AT_PROP).is_none() {
+      return degree_db(&self.db, node_id, direction, etype_id);
+    }
+    let count = |mut neighbors: Vec<NodeId>| {
+      self.retain_unarchived_ids(&mut neighbors);
+      neighbors.len()
+    };
+    match direction {
+      TraversalDirection::Out => count(neighbors_out_db(&self.db, node_id, etype_id)),
+      TraversalDirection::In => count(neighbors_in_db(&self.db, node_id, etype_id)),
+      TraversalDirection::Both => {
+        count(neighbors_out_db(&self.db, node_id, etype_id))
+          + count(neighbors_in_db(&self.db, node_id, etype_id))
+      }
+    }
+  }
+
+  /// Count a node's edges in `direction`, optionally of one edge type
+  ///
+  /// `Both` adds the out- and in-degree, so a self-loop counts twice. Reads
+  /// see the active transaction's pending edges. Edges to archived nodes
+  /// aren't counted.
+  pub fn degree(
+    &self,
+    node_id: NodeId,
+    direction: TraversalDirection,
+    edge_type: Option<&str>,
+  ) -> Result<usize> {
+    let etype_id = match edge_type {
+      Some(name) => Some(self.edge_def_and_id(name)?.1),
+      None => None,
+    };
+    Ok(self.unarchived_degree(node_id, direction, etype_id))
+  }
+
+  /// `degree` for several nodes, keyed by node ID
+  pub fn degrees(
+    &self,
+    node_ids: &[NodeId],
+    direction: TraversalDirection,
+    edge_type: Option<&str>,
+  ) -> Result<HashMap<NodeId, usize>> {
+    let etype_id = match edge_type {
+      Some(name) => Some(self.edge_def_and_id(name)?.1),
+      None => None,
+    };
+    Ok(
+      node_ids
+        .iter()
+        .map(|&node_id| {
+          (
+            node_id,
+            self.unarchived_degree(node_id, direction, etype_id),
+          )
+        })
+        .collect(),
     )
   }
 
-  /// Find the shortest path using BFS (unweighted)
+  // ========================================================================
+  // Edge Property Operations
+  // ========================================================================
+
+  /// Get an edge property (direct read, no transaction overhead)
   ///
-  /// Faster than Dijkstra for unweighted graphs.
-  pub fn find_bfs(self) -> PathResult {
-    let config = PathConfig {
-      source: self.source,
-      targets: self.targets,
-      allowed_etypes: self.allowed_etypes,
-      direction: self.direction,
-      max_depth: self.max_depth,
+  /// Returns None if the edge doesn't exist or the property is not set.
+  pub fn edge_prop(
+    &self,
+    src: NodeId,
+    edge_type: &str,
+    dst: NodeId,
+    prop_name: &str,
+  ) -> Result<Option<PropValue>> {
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+
+    let prop_key_id = match self.db.propkey_id(prop_name) {
+      Some(id) => id,
+      None => return Ok(None), // Unknown property = not set
     };
 
-    bfs(config, |node_id, dir, etype| {
-      self.ray.neighbors(node_id, dir, etype)
-    })
+    // Direct read without transaction
+    Ok(edge_prop_db(&self.db, src, etype_id, dst, prop_key_id))
+  }
+
+  /// Get all properties for an edge (direct read, no transaction overhead)
+  ///
+  /// Returns None if the edge doesn't exist.
+  pub fn edge_props(
+    &self,
+    src: NodeId,
+    edge_type: &str,
+    dst: NodeId,
+  ) -> Result<Option<HashMap<String, PropValue>>> {
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+
+    // Direct read without transaction
+    let props = edge_props_db(&self.db, src, etype_id, dst);
+
+    // Convert PropKeyId -> String in the result
+    match props {
+      Some(props_by_id) => {
+        let mut result = HashMap::new();
+        for (key_id, value) in props_by_id {
+          if let Some(name) = self.db.propkey_name(key_id) {
+            result.insert(name, value);
+          }
+        }
+        Ok(Some(result))
+      }
+      None => Ok(None),
+    }
+  }
+
+  /// Set an edge property
+  pub fn set_edge_prop(
+    &mut self,
+    src: NodeId,
+    edge_type: &str,
+    dst: NodeId,
+    prop_name: &str,
+    value: PropValue,
+  ) -> Result<()> {
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+
+    edge_def.check_prop(prop_name, &value)?;
+    let prop_key_id = edge_propkey_id(&self.db, prop_name)?;
+
+    let mut handle = begin_tx(&self.db)?;
+    set_edge_prop(&mut handle, src, etype_id, dst, prop_key_id, value)?;
+    commit(&mut handle)?;
+    Ok(())
+  }
+
+  /// Set an edge property, creating the edge first if it doesn't exist
+  ///
+  /// Both happen in one transaction. Returns `true` if the edge was created.
+  pub fn upsert_edge_prop(
+    &mut self,
+    src: NodeId,
+    edge_type: &str,
+    dst: NodeId,
+    prop_name: &str,
+    value: PropValue,
+  ) -> Result<bool> {
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+
+    edge_def.check_prop(prop_name, &value)?;
+    let prop_key_id = edge_propkey_id(&self.db, prop_name)?;
+
+    let mut handle = begin_tx(&self.db)?;
+    let created = upsert_edge_with_props(
+      &mut handle,
+      src,
+      etype_id,
+      dst,
+      [(prop_key_id, Some(value))],
+    )?;
+    commit(&mut handle)?;
+    Ok(created)
+  }
+
+  /// Set multiple edge properties
+  pub fn set_edge_props(
+    &mut self,
+    src: NodeId,
+    edge_type: &str,
+    dst: NodeId,
+    props: HashMap<String, PropValue>,
+  ) -> Result<()> {
+    if props.is_empty() {
+      return Ok(());
+    }
+
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+
+    let mut prop_pairs = Vec::with_capacity(props.len());
+    for (prop_name, value) in props {
+      edge_def.check_prop(&prop_name, &value)?;
+      let prop_key_id = if let Some(&id) = edge_def.prop_key_ids.get(&prop_name) {
+        id
+      } else {
+        edge_propkey_id(&self.db, &prop_name)?
+      };
+      prop_pairs.push((prop_key_id, value));
+    }
+
+    let mut handle = begin_tx(&self.db)?;
+    handle.db.set_edge_props(src, etype_id, dst, prop_pairs)?;
+    commit(&mut handle)?;
+    Ok(())
+  }
+
+  /// Delete an edge property
+  pub fn del_edge_prop(
+    &mut self,
+    src: NodeId,
+    edge_type: &str,
+    dst: NodeId,
+    prop_name: &str,
+  ) -> Result<()> {
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+
+    let prop_key_id = self
+      .db
+      .propkey_id(prop_name)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown property: {prop_name}").into()))?;
+
+    let mut handle = begin_tx(&self.db)?;
+    del_edge_prop(&mut handle, src, etype_id, dst, prop_key_id)?;
+    commit(&mut handle)?;
+    Ok(())
+  }
+
+  /// Update edge properties using fluent builder API
+  ///
+  /// Returns an `UpdateEdgeBuilder` that allows setting multiple properties
+  /// in a single transaction.
+  ///
+  /// # Example
+  /// ```rust,no_run
+  /// # use kitedb::api::kite::Kite;
+  /// # use kitedb::types::{NodeId, PropValue};
+  /// # fn main() -> kitedb::error::Result<()> {
+  /// # let mut kite: Kite = unimplemented!();
+  /// # let alice_id: NodeId = 1;
+  /// # let bob_id: NodeId = 2;
+  /// kite.update_edge(alice_id, "FOLLOWS", bob_id)?
+  ///    .set("weight", PropValue::F64(0.9))
+  ///    .set("since", PropValue::String("2024".to_string()))
+  ///    .execute()?;
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn update_edge(
+    &mut self,
+    src: NodeId,
+    edge_type: &str,
+    dst: NodeId,
+  ) -> Result<KiteUpdateEdgeBuilder<'_>> {
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+
+    Ok(KiteUpdateEdgeBuilder {
+      ray: self,
+      src,
+      etype_id,
+      dst,
+      updates: HashMap::new(),
+      ensure: false,
+    })
+  }
+
+  /// Upsert edge properties using fluent builder API
+  ///
+  /// Creates the edge if missing, otherwise updates properties.
+  pub fn upsert_edge(
+    &mut self,
+    src: NodeId,
+    edge_type: &str,
+    dst: NodeId,
+  ) -> Result<KiteUpsertEdgeBuilder<'_>> {
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+    let etype_id = edge_def.etype_id.ok_or_else(|| {
+      KiteError::InvalidSchema(format!("Edge type not initialized: {edge_type}").into())
+    })?;
+
+    Ok(KiteUpsertEdgeBuilder {
+      ray: self,
+      src,
+      etype_id,
+      dst,
+      updates: HashMap::new(),
+    })
+  }
+
+  // ========================================================================
+  // Listing and Counting
+  // ========================================================================
+
+  /// Count all nodes in the database, archived ones excluded
+  ///
+  /// This is an O(1) operation when possible, using cached counts; once a
+  /// node has been archived, archived nodes are found by a scan.
+  pub fn count_nodes(&self) -> u64 {
+    let total = count_nodes(&self.db);
+    let Some(archived_key) = self.db.propkey_id(ARCHIVED_AT_PROP) else {
+      return total;
+    };
+    let archived = list_nodes(&self.db)
+      .into_iter()
+      .filter(|&node_id| is_archived_db(&self.db, Some(archived_key), node_id))
+      .count() as u64;
+    total.saturating_sub(archived)
+  }
+
+  /// Count nodes of a specific type
+  ///
+  /// This requires iteration to filter by key prefix.
+  pub fn count_nodes_by_type(&self, node_type: &str) -> Result<u64> {
+    let node_def = self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?;
+
+    let prefix = &node_def.key_prefix;
+    let archived_key = self.db.propkey_id(ARCHIVED_AT_PROP);
+    let mut count = 0u64;
+
+    for node_id in list_nodes(&self.db) {
+      if let Some(key) = self.node_key_internal(node_id) {
+        if key.starts_with(prefix) && !is_archived_db(&self.db, archived_key, node_id) {
+          count += 1;
+        }
+      }
+    }
+
+    Ok(count)
+  }
+
+  /// Count the distinct values `prop_name` takes across nodes of a type
+  ///
+  /// Scans every (unarchived) node of the type. With `approximate`, values
+  /// go into a HyperLogLog sketch (about 1% error, 16 KiB of memory)
+  /// instead of a set holding every value. Nodes without the prop, or whose
+  /// value is null, a vector or JSON, aren't counted.
+  pub fn distinct_count(&self, node_type: &str, prop_name: &str, approximate: bool) -> Result<u64> {
+    let nodes = self.all(node_type)?;
+    let Some(prop_key_id) = self.db.propkey_id(prop_name) else {
+      return Ok(0);
+    };
+    let values = nodes.filter_map(|node_ref| {
+      let value = self.db.node_prop(node_ref.id(), prop_key_id)?;
+      IndexValue::from_prop(&value)
+    });
+
+    if approximate {
+      let mut sketch = HyperLogLog::default();
+      for value in values {
+        sketch.insert(&value);
+      }
+      Ok(sketch.estimate())
+    } else {
+      Ok(values.collect::<HashSet<_>>().len() as u64)
+    }
+  }
+
+  /// Count all edges
+  ///
+  /// Edges with an archived end aren't counted.
+  pub fn count_edges(&self) -> u64 {
+    self.count_unarchived_edges(None)
+  }
+
+  /// Edges, optionally of one type, whose ends are both unarchived
+  fn count_unarchived_edges(&self, etype_id: Option<ETypeId>) -> u64 {
+    let total = count_edges(&self.db, etype_id);
+    let Some(archived_key) = self.db.propkey_id(ARCHIVED_AT_PROP) else {
+      return total;
+    };
+    let archived: HashSet<NodeId> = list_nodes(&self.db)
+      .into_iter()
+      .filter(|&node_id| is_archived_db(&self.db, Some(archived_key), node_id))
+      .collect();
+    // Every out-edge of an archived node, plus in-edges from unarchived
+    // nodes, so edges between two archived nodes are only counted once
+    let hidden: usize = archived
+      .iter()
+      .map(|&node_id| {
+        neighbors_out_db(&self.db, node_id, etype_id).len()
+          + neighbors_in_db(&self.db, node_id, etype_id)
+            .into_iter()
+            .filter(|src| !archived.contains(src))
+            .count()
+      })
+      .sum();
+    total.saturating_sub(hidden as u64)
+  }
+
+  /// Count edges of a specific type
+  ///
+  /// Edges with an archived end aren't counted.
+  pub fn count_edges_by_type(&self, edge_type: &str) -> Result<u64> {
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+
+    Ok(self.count_unarchived_edges(Some(etype_id)))
+  }
+
+  #[cfg(feature = "bench-profile")]
+  pub fn take_profile_snapshot(&self) -> (u64, u64) {
+    self.db.take_profile_snapshot()
+  }
+
+  /// List all node IDs
+  pub fn list_nodes(&self) -> Vec<NodeId> {
+    list_nodes(&self.db)
+  }
+
+  /// Iterate over all nodes of a specific type
+  ///
+  /// Returns an iterator that yields `NodeRef` for each matching node.
+  /// Filters nodes by matching their key prefix.
+  ///
+  /// # Example
+  /// ```rust,no_run
+  /// # use kitedb::api::kite::Kite;
+  /// # fn main() -> kitedb::error::Result<()> {
+  /// # let kite: Kite = unimplemented!();
+  /// for node_ref in kite.all("User")? {
+  ///     println!("User: {:?}", node_ref.id());
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  ///
+  /// Archived nodes are skipped; see [`Self::all_including_archived`].
+  pub fn all(&self, node_type: &str) -> Result<impl Iterator<Item = NodeRef> + '_> {
+    self.all_nodes_of_type(node_type, false)
+  }
+
+  /// Iterate over all nodes of a specific type, archived ones included
+  pub fn all_including_archived(
+    &self,
+    node_type: &str,
+  ) -> Result<impl Iterator<Item = NodeRef> + '_> {
+    self.all_nodes_of_type(node_type, true)
+  }
+
+  fn all_nodes_of_type(
+    &self,
+    node_type: &str,
+    include_archived: bool,
+  ) -> Result<impl Iterator<Item = NodeRef> + '_> {
+    let filter = self.type_filter(node_type, include_archived)?;
+    Ok(
+      list_nodes(&self.db)
+        .into_iter()
+        .filter_map(move |node_id| filter.node_ref(self, node_id)),
+    )
+  }
+
+  /// One page of the nodes of a type, in ID order
+  ///
+  /// Node IDs are scanned a window at a time starting after the cursor, so
+  /// memory is bounded by the page rather than by the whole type. Like
+  /// [`Self::all`], this sees the current transaction's pending creates and
+  /// deletes. Pass the page's `next_cursor` to continue; `limit` 0 means 100.
+  pub fn all_page(
+    &self,
+    node_type: &str,
+    options: PaginationOptions,
+    include_archived: bool,
+  ) -> Result<Page<NodeRef>> {
+    let filter = self.type_filter(node_type, include_archived)?;
+    let limit = if options.limit == 0 {
+      100
+    } else {
+      options.limit
+    };
+    let mut next_id = match options.cursor.as_deref() {
+      Some(cursor) => {
+        let after = cursor
+          .strip_prefix("n:")
+          .and_then(|id| id.parse::<NodeId>().ok())
+          .ok_or_else(|| KiteError::InvalidQuery(format!("Invalid cursor: {cursor}").into()))?;
+        after + 1
+      }
+      None => 0,
+    };
+    let end_id = self
+      .db
+      .next_node_id
+      .load(std::sync::atomic::Ordering::SeqCst);
+    let window = (limit as NodeId).max(ALL_PAGE_MIN_WINDOW);
+
+    let mut items = Vec::new();
+    let mut has_more = false;
+    'scan: while next_id < end_id {
+      let window_end = next_id.saturating_add(window).min(end_id);
+      for node_id in self.db.list_nodes_in_range(next_id, window_end) {
+        let Some(node_ref) = filter.node_ref(self, node_id) else {
+          continue;
+        };
+        if items.len() == limit {
+          has_more = true;
+          break 'scan;
+        }
+        items.push(node_ref);
+      }
+      next_id = window_end;
+    }
+
+    let next_cursor = if has_more {
+      items.last().map(|node: &NodeRef| format!("n:{}", node.id))
+    } else {
+      None
+    };
+    Ok(Page {
+      items,
+      next_cursor,
+      has_more,
+      total: None,
+    })
+  }
+
+  fn type_filter(&self, node_type: &str, include_archived: bool) -> Result<NodeTypeFilter> {
+    let node_def = self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?;
+    Ok(NodeTypeFilter {
+      prefix: node_def.key_prefix.clone(),
+      node_type: node_type.to_string().into(),
+      archived_key: if include_archived {
+        None
+      } else {
+        self.db.propkey_id(ARCHIVED_AT_PROP)
+      },
+    })
+  }
+
+  /// Count nodes carrying a label, whatever type their key prefix gives them
+  ///
+  /// Every node type adds its own label on insert, but nodes can carry more
+  /// (e.g. both `Admin` and `User`). An unknown label counts zero nodes;
+  /// archived nodes aren't counted.
+  pub fn count_nodes_by_label(&self, label_name: &str) -> u64 {
+    let Some(label_id) = self.db.label_id(label_name) else {
+      return 0;
+    };
+    let archived_key = self.db.propkey_id(ARCHIVED_AT_PROP);
+    list_nodes(&self.db)
+      .into_iter()
+      .filter(|&node_id| {
+        self.db.node_has_label(node_id, label_id)
+          && !is_archived_db(&self.db, archived_key, node_id)
+      })
+      .count() as u64
+  }
+
+  /// Iterate over the nodes carrying a label, independent of key prefix
+  ///
+  /// Each `NodeRef` is typed by its key prefix as in [`Self::node_by_id`].
+  /// Archived nodes are skipped unless `include_archived` is set.
+  pub fn all_by_label(
+    &self,
+    label_name: &str,
+    include_archived: bool,
+  ) -> impl Iterator<Item = NodeRef> + '_ {
+    let label_id = self.db.label_id(label_name);
+    let archived_key = if include_archived {
+      None
+    } else {
+      self.db.propkey_id(ARCHIVED_AT_PROP)
+    };
+    let node_ids = if label_id.is_some() {
+      list_nodes(&self.db)
+    } else {
+      Vec::new()
+    };
+    node_ids.into_iter().filter_map(move |node_id| {
+      let label_id = label_id?;
+      if !self.db.node_has_label(node_id, label_id)
+        || is_archived_db(&self.db, archived_key, node_id)
+      {
+        return None;
+      }
+      self.node_by_id(node_id).ok().flatten()
+    })
+  }
+
+  /// Find nodes of a type whose `ordered` prop lies in `[min, max]`
+  ///
+  /// Both bounds are inclusive and must be the same kind of value; nodes
+  /// holding a value of another kind (e.g. a float when querying an int
+  /// range) don't match. Results come back in value order. Only props
+  /// declared with `PropDef::ordered` have the sorted index this needs; the
+  /// index is in memory only and is rebuilt from the stored data on open.
+  /// It is maintained from every commit, raw [`SingleFileDB`] writes
+  /// included, and includes the calling thread's uncommitted writes.
+  /// Archived nodes are skipped.
+  pub fn find_by_prop_range(
+    &self,
+    node_type: &str,
+    prop_name: &str,
+    min: &PropValue,
+    max: &PropValue,
+  ) -> Result<Vec<NodeId>> {
+    let node_def = self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?;
+    if !node_def
+      .props
+      .get(prop_name)
+      .is_some_and(|prop| prop.ordered)
+    {
+      return Err(KiteError::InvalidSchema(
+        format!("Property '{prop_name}' of {node_type} is not declared ordered").into(),
+      ));
+    }
+    let (Some(label_id), Some(&key_id)) = (node_def.label_id, node_def.prop_key_ids.get(prop_name))
+    else {
+      return Err(KiteError::InvalidSchema("Node type not initialized".into()));
+    };
+
+    let (Some(min), Some(max)) = (IndexValue::from_prop(min), IndexValue::from_prop(max)) else {
+      return Err(KiteError::InvalidQuery(
+        "Range bounds must be string, int, float or bool values".into(),
+      ));
+    };
+    if std::mem::discriminant(&min) != std::mem::discriminant(&max) {
+      return Err(KiteError::InvalidQuery(
+        "Range bounds must be the same kind of value".into(),
+      ));
+    }
+    if min > max {
+      return Ok(Vec::new());
+    }
+
+    Ok(
+      self
+        .prop_index
+        .range(&self.db, (label_id, key_id), &min, &max),
+    )
+  }
+
+  /// Find nodes of a type whose prop matches `filter`
+  ///
+  /// Equality on a `unique` or `ordered` prop and ranges are answered from
+  /// the prop index (see [`find_by_prop_range`](Self::find_by_prop_range));
+  /// other filters check the prop of every node of the type.
+  pub fn find_where(
+    &self,
+    node_type: &str,
+    prop_name: &str,
+    filter: &PropFilter,
+  ) -> Result<Vec<NodeId>> {
+    let node_def = self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?;
+    if let PropFilter::Range { min, max } = filter {
+      return self.find_by_prop_range(node_type, prop_name, min, max);
+    }
+    let Some(key_id) = self.db.propkey_id(prop_name) else {
+      return Ok(Vec::new());
+    };
+
+    if let PropFilter::Eq(value) = filter {
+      let prop = node_def
+        .props
+        .get(prop_name)
+        .filter(|prop| prop.is_indexed());
+      let slot = node_def.label_id.map(|label_id| (label_id, key_id));
+      if let (Some(prop), Some(slot), Some(index_value)) =
+        (prop, slot, IndexValue::from_prop(value))
+      {
+        let mut found = if prop.unique {
+          self
+            .prop_index
+            .owner(&self.db, slot, &index_value)
+            .into_iter()
+            .collect()
+        } else {
+          self
+            .prop_index
+            .range(&self.db, slot, &index_value, &index_value)
+        };
+        self.retain_unarchived_ids(&mut found);
+        return Ok(found);
+      }
+    }
+
+    let matches = |prop: &PropValue| match filter {
+      PropFilter::Eq(value) => prop == value,
+      PropFilter::JsonPathEq { path, value } => prop.json_path(path) == Some(value),
+      PropFilter::Range { .. } => false,
+    };
+    Ok(
+      self
+        .all(node_type)?
+        .map(|node| node.id)
+        .filter(|&node_id| {
+          self
+            .db
+            .node_prop(node_id, key_id)
+            .is_some_and(|prop| matches(&prop))
+        })
+        .collect(),
+    )
+  }
+
+  /// List all edges in the database
+  pub fn list_all_edges(&self) -> Vec<FullEdge> {
+    list_edges(&self.db, ListEdgesOptions::default())
+  }
+
+  /// Iterate over all edges, optionally filtered by type
+  ///
+  /// Returns an iterator that yields edge information.
+  ///
+  /// # Example
+  /// ```rust,no_run
+  /// # use kitedb::api::kite::Kite;
+  /// # fn main() -> kitedb::error::Result<()> {
+  /// # let kite: Kite = unimplemented!();
+  /// for edge in kite.all_edges(Some("FOLLOWS"))? {
+  ///     println!("{} -> {}", edge.src, edge.dst);
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  ///
+  /// Edges touching an archived node are skipped; see
+  /// [`Self::all_edges_including_archived`].
+  pub fn all_edges(&self, edge_type: Option<&str>) -> Result<impl Iterator<Item = FullEdge> + '_> {
+    let archived_key = self.db.propkey_id(ARCHIVED_AT_PROP);
+    Ok(
+      self
+        .all_edges_including_archived(edge_type)?
+        .filter(move |edge| {
+          !is_archived_db(&self.db, archived_key, edge.src)
+            && !is_archived_db(&self.db, archived_key, edge.dst)
+        }),
+    )
+  }
+
+  /// Iterate over all edges (optionally of one type), archived endpoints included
+  pub fn all_edges_including_archived(
+    &self,
+    edge_type: Option<&str>,
+  ) -> Result<impl Iterator<Item = FullEdge> + '_> {
+    let etype_id = match edge_type {
+      Some(name) => {
+        let edge_def = self
+          .edges
+          .get(name)
+          .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {name}").into()))?;
+        edge_def.etype_id
+      }
+      None => None,
+    };
+
+    let options = ListEdgesOptions { etype: etype_id };
+    Ok(list_edges(&self.db, options).into_iter())
+  }
+
+  /// Get a lightweight node reference without loading properties
+  ///
+  /// This is faster than `get()` when you only need the node reference
+  /// for traversals or edge operations.
+  ///
+  /// # Example
+  /// ```rust,no_run
+  /// # use kitedb::api::kite::Kite;
+  /// # fn main() -> kitedb::error::Result<()> {
+  /// # let kite: Kite = unimplemented!();
+  /// let user_ref = kite.node_ref("User", "alice")?;
+  /// if let Some(node) = user_ref {
+  ///     // Can now use node.id() for edges, traversals, etc.
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  /// Get a lightweight node reference by key (direct read, no transaction overhead)
+  ///
+  /// This is faster than `get()` as it only returns a reference without loading properties.
+  /// Use this when you only need the node ID for traversals or edge operations.
+  ///
+  /// # Example
+  /// ```rust,no_run
+  /// # use kitedb::api::kite::Kite;
+  /// # fn main() -> kitedb::error::Result<()> {
+  /// # let kite: Kite = unimplemented!();
+  /// // Fast: only gets reference (~85ns)
+  /// if let Some(node) = kite.node_ref("User", "alice")? {
+  ///     // Can now use node.id() for edges, traversals, etc.
+  ///     let friends = kite.from(node.id()).out(Some("FOLLOWS"))?.to_vec();
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn node_ref(&self, node_type: &str, key_suffix: &str) -> Result<Option<NodeRef>> {
+    let node_def = self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?;
+
+    let full_key = node_def.key(key_suffix);
+
+    // Direct read without transaction
+    let node_id = node_by_key_db(&self.db, &full_key).filter(|&id| !self.is_archived(id));
+
+    match node_id {
+      Some(id) => Ok(Some(NodeRef::new(id, Some(full_key), node_type))),
+      None => Ok(None),
+    }
+  }
+
+  /// Helper to get node key from database
+  fn node_key_internal(&self, node_id: NodeId) -> Option<String> {
+    self.db.node_key(node_id)
+  }
+
+  /// Node definition owning `node_id`, if its type has write constraints
+  fn constrained_node_def(&self, node_id: NodeId) -> Option<&NodeDef> {
+    if !self.node_constraints {
+      return None;
+    }
+    let key = self.node_key_internal(node_id)?;
+    node_def_for_key(&self.nodes, &self.key_prefix_to_node, &key)
+      .filter(|def| def.has_constraints())
+  }
+
+  /// Check a node property write against the schema of the node's type
+  fn check_node_prop(&self, node_id: NodeId, prop_name: &str, value: &PropValue) -> Result<()> {
+    match self.constrained_node_def(node_id) {
+      Some(node_def) => {
+        node_def.check_prop(prop_name, value)?;
+        self
+          .prop_index
+          .check(&self.db, node_def, node_id, prop_name, value)
+      }
+      None => Ok(()),
+    }
+  }
+
+  /// Edge definition for an edge type ID
+  fn edge_def_by_id(&self, etype_id: ETypeId) -> Option<&EdgeDef> {
+    self
+      .edges
+      .values()
+      .find(|def| def.etype_id == Some(etype_id))
+  }
+
+  // ========================================================================
+  // Schema Access
+  // ========================================================================
+
+  /// Get a node definition by name
+  pub fn node_def(&self, name: &str) -> Option<&NodeDef> {
+    self.nodes.get(name)
+  }
+
+  /// Get an edge definition by name
+  pub fn edge_def(&self, name: &str) -> Option<&EdgeDef> {
+    self.edges.get(name)
+  }
+
+  /// Get all node type names
+  pub fn node_types(&self) -> Vec<&str> {
+    self.nodes.keys().map(|s| s.as_str()).collect()
+  }
+
+  /// Get all edge type names
+  pub fn edge_types(&self) -> Vec<&str> {
+    self.edges.keys().map(|s| s.as_str()).collect()
+  }
+
+  /// Units declared on a node type's props (props without one are omitted)
+  pub fn prop_units(&self, node_type: &str) -> Result<HashMap<String, String>> {
+    let node_def = self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?;
+    Ok(node_def.prop_units())
+  }
+
+  /// Check the units a writer declared for props of `node_type`
+  ///
+  /// Fails with [`KiteError::Constraint`] if a declared unit differs from
+  /// the prop's unit, or the prop has none. Props left out aren't checked.
+  pub fn check_units(&self, node_type: &str, declared: &HashMap<String, String>) -> Result<()> {
+    let node_def = self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?;
+    node_def.check_units(declared)
+  }
+
+  /// Like [`Self::check_units`], taking the type from an existing node
+  ///
+  /// Nodes whose key matches no node type have no units, so any declaration
+  /// for them fails.
+  pub fn check_node_units(
+    &self,
+    node_id: NodeId,
+    declared: &HashMap<String, String>,
+  ) -> Result<()> {
+    if declared.is_empty() {
+      return Ok(());
+    }
+    let node_def = self
+      .node_key_internal(node_id)
+      .and_then(|key| node_def_for_key(&self.nodes, &self.key_prefix_to_node, &key))
+      .ok_or(KiteError::NodeNotFound(node_id))?;
+    node_def.check_units(declared)
+  }
+
+  /// Compare the live schema against the node and edge types of `target`
+  ///
+  /// Read-only: returns the changes a migration would have to apply, node
+  /// types first, then edge types, each sorted by name. Only prop names and
+  /// types are compared; key prefixes and prop constraints are ignored.
+  pub fn schema_diff(&self, target: &KiteOptions) -> Vec<SchemaChange> {
+    let mut changes = Vec::new();
+
+    let target_nodes: HashMap<&str, &NodeDef> = target
+      .nodes
+      .iter()
+      .map(|def| (def.name.as_str(), def))
+      .collect();
+    let mut node_names: Vec<&str> = self
+      .nodes
+      .keys()
+      .map(|s| s.as_str())
+      .chain(target_nodes.keys().copied())
+      .collect();
+    node_names.sort_unstable();
+    node_names.dedup();
+    for name in node_names {
+      match (self.nodes.get(name), target_nodes.get(name)) {
+        (None, Some(_)) => changes.push(SchemaChange::AddType {
+          kind: SchemaKind::Node,
+          name: name.to_string(),
+        }),
+        (Some(_), None) => changes.push(SchemaChange::RemoveType {
+          kind: SchemaKind::Node,
+          name: name.to_string(),
+        }),
+        (Some(live), Some(to)) => {
+          diff_props(SchemaKind::Node, name, &live.props, &to.props, &mut changes)
+        }
+        (None, None) => {}
+      }
+    }
+
+    let target_edges: HashMap<&str, &EdgeDef> = target
+      .edges
+      .iter()
+      .map(|def| (def.name.as_str(), def))
+      .collect();
+    let mut edge_names: Vec<&str> = self
+      .edges
+      .keys()
+      .map(|s| s.as_str())
+      .chain(target_edges.keys().copied())
+      .collect();
+    edge_names.sort_unstable();
+    edge_names.dedup();
+    for name in edge_names {
+      match (self.edges.get(name), target_edges.get(name)) {
+        (None, Some(_)) => changes.push(SchemaChange::AddType {
+          kind: SchemaKind::Edge,
+          name: name.to_string(),
+        }),
+        (Some(_), None) => changes.push(SchemaChange::RemoveType {
+          kind: SchemaKind::Edge,
+          name: name.to_string(),
+        }),
+        (Some(live), Some(to)) => {
+          diff_props(SchemaKind::Edge, name, &live.props, &to.props, &mut changes)
+        }
+        (None, None) => {}
+      }
+    }
+
+    changes
+  }
+
+  // ========================================================================
+  // Traversal
+  // ========================================================================
+
+  /// Start a traversal from a node
+  ///
+  /// Returns a traversal builder that can be used to chain traversal steps.
+  ///
+  /// # Example
+  ///
+  /// ```rust,no_run
+  /// # use kitedb::api::kite::{NodeRef, Kite};
+  /// # fn main() -> kitedb::error::Result<()> {
+  /// # let kite: Kite = unimplemented!();
+  /// # let alice: NodeRef = unimplemented!();
+  /// let friends = kite
+  ///     .from(alice.id())
+  ///     .out(Some("FOLLOWS"))?
+  ///     .out(Some("FOLLOWS"))?
+  ///     .to_vec();
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn from(&self, node_id: NodeId) -> KiteTraversalBuilder<'_> {
+    KiteTraversalBuilder::new(self, vec![node_id])
+  }
+
+  /// Start a traversal from multiple nodes
+  pub fn from_nodes(&self, node_ids: Vec<NodeId>) -> KiteTraversalBuilder<'_> {
+    KiteTraversalBuilder::new(self, node_ids)
+  }
+
+  /// Start an incremental exploration session rooted at `root`
+  ///
+  /// Pass the session to [`Self::expand`]; each call returns only neighbors
+  /// the session hasn't returned yet.
+  pub fn exploration(&self, root: NodeId) -> Result<Exploration> {
+    if !self.exists(root) {
+      return Err(KiteError::NodeNotFound(root));
+    }
+    Ok(Exploration::new(root))
+  }
+
+  /// Expand `node_id` within an exploration session
+  ///
+  /// Returns newly discovered neighbors only. Archived nodes are skipped,
+  /// as in traversals.
+  pub fn expand(
+    &self,
+    session: &mut Exploration,
+    node_id: NodeId,
+    edge_type: Option<&str>,
+    direction: TraversalDirection,
+  ) -> Result<Vec<NodeId>> {
+    let etype = match edge_type {
+      Some(name) => {
+        let edge_def = self
+          .edges
+          .get(name)
+          .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {name}").into()))?;
+        edge_def.etype_id
+      }
+      None => None,
+    };
+    Ok(
+      session.expand(node_id, etype, direction, |node_id, direction, etype| {
+        kite_hop(self, None, false, None, node_id, direction, etype)
+      }),
+    )
+  }
+
+  // ========================================================================
+  // Pathfinding
+  // ========================================================================
+
+  /// Find the shortest path between two nodes
+  ///
+  /// Returns a path finding builder that can be configured with edge types,
+  /// direction, and maximum depth.
+  ///
+  /// # Example
+  ///
+  /// ```rust,no_run
+  /// # use kitedb::api::kite::{NodeRef, Kite};
+  /// # fn main() -> kitedb::error::Result<()> {
+  /// # let kite: Kite = unimplemented!();
+  /// # let alice: NodeRef = unimplemented!();
+  /// # let bob: NodeRef = unimplemented!();
+  /// let path = kite
+  ///     .shortest_path(alice.id(), bob.id())
+  ///     .via("FOLLOWS")?
+  ///     .max_depth(5)
+  ///     .find();
+  ///
+  /// if path.found {
+  ///     println!("Path: {:?}", path.path);
+  ///     println!("Total weight: {}", path.total_weight);
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn shortest_path(&self, source: NodeId, target: NodeId) -> KitePathBuilder<'_> {
+    KitePathBuilder::new(self, source, target)
+  }
+
+  /// Find shortest paths to any of the target nodes
+  pub fn shortest_path_to_any(&self, source: NodeId, targets: Vec<NodeId>) -> KitePathBuilder<'_> {
+    KitePathBuilder::new_multi(self, source, targets)
+  }
+
+  /// Check if a path exists between two nodes
+  ///
+  /// This is more efficient than `shortest_path()` when you only need to
+  /// know if a path exists, not the path itself.
+  pub fn has_path(
+    &mut self,
+    source: NodeId,
+    target: NodeId,
+    edge_type: Option<&str>,
+  ) -> Result<bool> {
+    let path = self.shortest_path(source, target);
+    let path = if let Some(etype) = edge_type {
+      path.via(etype)?
+    } else {
+      path
+    };
+    Ok(path.find().found)
+  }
+
+  /// Get all nodes reachable from a source within a certain depth
+  ///
+  /// # Example
+  ///
+  /// ```rust,no_run
+  /// # use kitedb::api::kite::{NodeRef, Kite};
+  /// # fn main() -> kitedb::error::Result<()> {
+  /// # let kite: Kite = unimplemented!();
+  /// # let alice: NodeRef = unimplemented!();
+  /// let reachable = kite.reachable_from(alice.id(), 3, Some("FOLLOWS"))?;
+  /// println!("Alice can reach {} nodes in 3 hops", reachable.len());
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn reachable_from(
+    &self,
+    source: NodeId,
+    max_depth: usize,
+    edge_type: Option<&str>,
+  ) -> Result<Vec<NodeId>> {
+    let etype = match edge_type {
+      Some(name) => {
+        let edge_def = self
+          .edges
+          .get(name)
+          .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {name}").into()))?;
+        edge_def.etype_id
+      }
+      None => None,
+    };
+
+    use super::traversal::{TraversalBuilder, TraversalDirection, TraverseOptions};
+
+    let options = TraverseOptions::new(TraversalDirection::Out, max_depth);
+
+    let results = TraversalBuilder::from_node(source)
+      .traverse(etype, options)
+      .collect_node_ids(|node_id, dir, etype_filter| self.neighbors(node_id, dir, etype_filter));
+
+    Ok(results)
+  }
+
+  /// Get all nodes reachable from a source, skipping excluded nodes and edges
+  ///
+  /// Excluded nodes are treated as if deleted: they are never reached or
+  /// expanded (an excluded source reaches nothing). Excluded edges are never
+  /// followed. Useful for "what's reachable if this goes down" analysis
+  /// without mutating the graph.
+  pub fn reachable_from_excluding(
+    &self,
+    source: NodeId,
+    max_depth: usize,
+    edge_type: Option<&str>,
+    excluded_nodes: &HashSet<NodeId>,
+    excluded_edges: &HashSet<Edge>,
+  ) -> Result<Vec<NodeId>> {
+    let etype = match edge_type {
+      Some(name) => {
+        let edge_def = self
+          .edges
+          .get(name)
+          .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {name}").into()))?;
+        edge_def.etype_id
+      }
+      None => None,
+    };
+
+    if excluded_nodes.contains(&source) {
+      return Ok(Vec::new());
+    }
+
+    use super::traversal::{TraversalBuilder, TraversalDirection, TraverseOptions};
+
+    let options = TraverseOptions::new(TraversalDirection::Out, max_depth);
+
+    let results = TraversalBuilder::from_node(source)
+      .traverse(etype, options)
+      .collect_node_ids(|node_id, dir, etype_filter| {
+        let mut edges = self.neighbors(node_id, dir, etype_filter);
+        edges.retain(|edge| !excluded_edges.contains(edge) && !excluded_nodes.contains(&edge.dst));
+        edges
+      });
+
+    Ok(results)
+  }
+
+  /// Start a level-by-level reachability walk from `source`
+  ///
+  /// Pass the cursor to [`Self::next_reachable_level`]. Taken together, the
+  /// levels are what [`Self::reachable_from`] returns with no depth limit.
+  pub fn reachable_cursor(
+    &self,
+    source: NodeId,
+    edge_type: Option<&str>,
+  ) -> Result<ReachableCursor> {
+    let etype = match edge_type {
+      Some(name) => {
+        let edge_def = self
+          .edges
+          .get(name)
+          .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {name}").into()))?;
+        edge_def.etype_id
+      }
+      None => None,
+    };
+    if !self.exists(source) {
+      return Err(KiteError::NodeNotFound(source));
+    }
+    Ok(ReachableCursor::new(source, etype))
+  }
+
+  /// Advance a reachability cursor by one depth level
+  ///
+  /// Returns the nodes first reached at that depth; empty once the walk is
+  /// done. Writes made between calls are seen by later levels.
+  pub fn next_reachable_level(&self, cursor: &mut ReachableCursor) -> Vec<NodeId> {
+    cursor.next_level(|node_id, direction, etype| self.neighbors(node_id, direction, etype))
+  }
+
+  // Internal helper to get neighbors for traversal/pathfinding (read-only, no transaction)
+  //
+  // Edges to archived nodes are dropped, so walks never reach or pass
+  // through them.
+  fn neighbors(
+    &self,
+    node_id: NodeId,
+    direction: super::traversal::TraversalDirection,
+    etype: Option<ETypeId>,
+  ) -> Vec<Edge> {
+    let mut edges = self.neighbors_including_archived(node_id, direction, etype);
+    retain_unarchived(&self.db, node_id, &mut edges);
+    edges
+  }
+
+  fn neighbors_including_archived(
+    &self,
+    node_id: NodeId,
+    direction: super::traversal::TraversalDirection,
+    etype: Option<ETypeId>,
+  ) -> Vec<Edge> {
+    use super::traversal::TraversalDirection;
+
+    let mut edges = Vec::new();
+
+    match direction {
+      TraversalDirection::Out => {
+        for (edge_etype, dst) in self.db.out_edges(node_id) {
+          if etype.is_some() && etype != Some(edge_etype) {
+            continue;
+          }
+          edges.push(Edge {
+            src: node_id,
+            etype: edge_etype,
+            dst,
+          });
+        }
+      }
+      TraversalDirection::In => {
+        for (edge_etype, src) in self.db.in_edges(node_id) {
+          if etype.is_some() && etype != Some(edge_etype) {
+            continue;
+          }
+          edges.push(Edge {
+            src,
+            etype: edge_etype,
+            dst: node_id,
+          });
+        }
+      }
+      TraversalDirection::Both => {
+        edges.extend(self.neighbors_including_archived(node_id, TraversalDirection::Out, etype));
+        edges.extend(self.neighbors_including_archived(node_id, TraversalDirection::In, etype));
+      }
+    }
+
+    edges
+  }
+
+  /// Key prefixes of `node_types`, for restricting traversal frontiers
+  pub(crate) fn node_type_prefixes<S: AsRef<str>>(&self, node_types: &[S]) -> Result<Vec<String>> {
+    node_types
+      .iter()
+      .map(|node_type| {
+        let node_type = node_type.as_ref();
+        self
+          .nodes
+          .get(node_type)
+          .map(|def| def.key_prefix.clone())
+          .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))
+      })
+      .collect()
+  }
+
+  // ========================================================================
+  // Database Maintenance
+  // ========================================================================
+
+  /// Optimize (compact) the database
+  ///
+  /// This merges the write-ahead log (WAL) into the snapshot, reducing
+  /// file size and improving read performance. This is equivalent to
+  /// "VACUUM" in SQLite.
+  /// Call this periodically to reclaim space from deleted nodes/edges
+  /// and improve read performance.
+  pub fn optimize(&mut self) -> Result<()> {
+    self.db.optimize_single_file(None)
+  }
+
+  /// Get database statistics
+  pub fn stats(&self) -> DbStats {
+    self.db.stats()
+  }
+
+  /// Count and size WAL records by type since the last checkpoint
+  pub fn wal_record_breakdown(&self) -> Result<Vec<WalRecordStats>> {
+    self.db.wal_record_breakdown()
+  }
+
+  /// Get cache statistics (`None` when no cache was configured at open)
+  pub fn cache_stats(&self) -> Option<CacheStats> {
+    self.db.cache_stats()
+  }
+
+  /// Active secondary indexes: `unique` and `ordered` props of every node
+  /// type, then one vector store per property key holding embeddings
+  pub fn list_indexes(&self) -> Result<Vec<IndexInfo>> {
+    let mut indexes = self.prop_index.describe(&self.nodes);
+    indexes.extend(self.db.list_vector_indexes()?);
+    Ok(indexes)
+  }
+
+  /// Recoverable issues noticed while opening (e.g. discarded trailing WAL
+  /// bytes after a crash)
+  pub fn open_warnings(&self) -> &[String] {
+    self.db.open_warnings()
+  }
+
+  /// Get a human-readable description of the database
+  ///
+  /// Useful for debugging and monitoring. Returns information about:
+  /// - Database path and format
+  /// - Schema (node types and edge types)
+  /// - Current statistics
+  ///
+  /// # Example
+  /// ```rust,no_run
+  /// # use kitedb::api::kite::Kite;
+  /// # fn main() {
+  /// # let kite: Kite = unimplemented!();
+  /// println!("{}", kite.describe());
+  /// // Output:
+  /// // KiteDB at /path/to/db.kitedb (single-file format)
+  /// // Schema:
+  /// //   Node types: User, Post, Comment
+  /// //   Edge types: FOLLOWS, LIKES, WROTE
+  /// // Statistics:
+  /// //   Nodes: 1,234 (snapshot: 1,200, delta: +34)
+  /// //   Edges: 5,678 (snapshot: 5,600, delta: +78)
+  /// # }
+  /// ```
+  pub fn describe(&self) -> String {
+    let stats = self.stats();
+    let path = self.db.path.display();
+    let format = "single-file";
+
+    let node_types: Vec<&str> = self.nodes.keys().map(|s| s.as_str()).collect();
+    let edge_types: Vec<&str> = self.edges.keys().map(|s| s.as_str()).collect();
+
+    let delta_nodes = stats.delta_nodes_created as i64 - stats.delta_nodes_deleted as i64;
+    let delta_edges = stats.delta_edges_added as i64 - stats.delta_edges_deleted as i64;
+
+    format!(
+      "KiteDB at {} ({} format)\n\
+       Schema:\n  \
+         Node types: {}\n  \
+         Edge types: {}\n\
+       Statistics:\n  \
+         Nodes: {} (snapshot: {}, delta: {:+})\n  \
+         Edges: {} (snapshot: {}, delta: {:+})\n  \
+         Recommend compact: {}",
+      path,
+      format,
+      if node_types.is_empty() {
+        "(none)".to_string()
+      } else {
+        node_types.join(", ")
+      },
+      if edge_types.is_empty() {
+        "(none)".to_string()
+      } else {
+        edge_types.join(", ")
+      },
+      stats.snapshot_nodes,
+      stats
+        .snapshot_nodes
+        .saturating_sub(stats.delta_nodes_created as u64),
+      delta_nodes,
+      stats.snapshot_edges,
+      stats
+        .snapshot_edges
+        .saturating_sub(stats.delta_edges_added as u64),
+      delta_edges,
+      if stats.recommend_compact { "yes" } else { "no" }
+    )
+  }
+
+  /// Check database integrity
+  ///
+  /// Performs validation checks on the database structure:
+  /// - Verifies edge reciprocity (for each outgoing edge, a matching incoming edge exists)
+  /// - Checks that all edges reference existing nodes
+  /// - Validates node key mappings
+  ///
+  /// Returns a `CheckResult` with `valid=true` if no errors found, or detailed
+  /// error/warning messages otherwise.
+  ///
+  /// # Example
+  /// ```rust,no_run
+  /// # use kitedb::api::kite::Kite;
+  /// # fn main() -> kitedb::error::Result<()> {
+  /// # let kite: Kite = unimplemented!();
+  /// let result = kite.check()?;
+  /// if !result.valid {
+  ///     for error in &result.errors {
+  ///         eprintln!("Error: {}", error);
+  ///     }
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn check(&self) -> Result<CheckResult> {
+    let mut result = self.db.check();
+    self.check_schema(&mut result);
+    Ok(result)
+  }
+
+  /// Like [`Self::check`], but reports vector dimension and NaN/Inf problems
+  /// as errors instead of warnings
+  pub fn check_strict(&self) -> Result<CheckResult> {
+    let mut result = self.db.check_strict();
+    self.check_schema(&mut result);
+    Ok(result)
+  }
+
+  fn check_schema(&self, result: &mut CheckResult) {
+    // Schema consistency - verify all registered edge types have valid IDs
+    for (edge_name, edge_def) in &self.edges {
+      if edge_def.etype_id.is_none() {
+        result
+          .warnings
+          .push(format!("Edge type '{edge_name}' has no assigned etype_id"));
+      }
+    }
+  }
+
+  /// Check database integrity and repair what can be fixed safely
+  ///
+  /// Edges pointing at missing nodes are only removed when
+  /// `allow_destructive` is set. See [`SingleFileDB::check_and_repair`].
+  pub fn check_and_repair(&self, allow_destructive: bool) -> Result<RepairResult> {
+    self.db.check_and_repair(allow_destructive)
+  }
+
+  // ========================================================================
+  // Change Listeners
+  // ========================================================================
+
+  /// Call `listener` after each commit that changes `prop_name` on a node of
+  /// `node_type`
+  ///
+  /// Covers every write path, including explicit transactions. See
+  /// [`SingleFileDB::on_prop_change`] for when listeners run; the returned
+  /// id unregisters the listener through [`Self::remove_prop_listener`].
+  pub fn on_prop_change(
+    &self,
+    node_type: &str,
+    prop_name: &str,
+    listener: impl Fn(&PropChange) + Send + Sync + 'static,
+  ) -> Result<PropListenerId> {
+    let node_def = self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?;
+    let key_id = self.db.propkey_id_or_create(prop_name);
+    let key_prefix = node_def.key_prefix.clone();
+    Ok(self.db.on_prop_change(key_id, move |change| {
+      if change
+        .node_key
+        .as_deref()
+        .is_some_and(|key| key.starts_with(&key_prefix))
+      {
+        listener(change);
+      }
+    }))
+  }
+
+  /// Unregister a listener added with [`Self::on_prop_change`]
+  ///
+  /// Returns false if `id` isn't registered.
+  pub fn remove_prop_listener(&self, id: PropListenerId) -> bool {
+    self.db.remove_prop_listener(id)
+  }
+
+  // ========================================================================
+  // Database Access
+  // ========================================================================
+
+  /// Get a reference to the underlying SingleFileDB
+  pub fn raw(&self) -> &SingleFileDB {
+    &self.db
+  }
+
+  /// Get a mutable reference to the underlying SingleFileDB
+  pub fn raw_mut(&mut self) -> &mut SingleFileDB {
+    &mut self.db
+  }
+
+  /// Close the database
+  pub fn close(self) -> Result<()> {
+    match self.close_checkpoint_if_wal_usage_at_least {
+      Some(threshold) => close_single_file_with_options(
+        self.db,
+        SingleFileCloseOptions::new().checkpoint_if_wal_usage_at_least(threshold),
+      ),
+      None => close_single_file(self.db),
+    }
+  }
+
+  /// Close the database and run a blocking checkpoint if WAL usage is above threshold.
+  ///
+  /// Use this for reopen-heavy workloads where you want to cap WAL replay cost on next open.
+  /// Threshold is clamped to [0.0, 1.0].
+  pub fn close_with_checkpoint_if_wal_over(self, threshold: f64) -> Result<()> {
+    close_single_file_with_options(
+      self.db,
+      SingleFileCloseOptions::new().checkpoint_if_wal_usage_at_least(threshold),
+    )
+  }
+}
+
+// ============================================================================
+// Traversal Builder for Kite
+// ============================================================================
+
+use super::traversal::{
+  Exploration, ReachableCursor, TraversalBuilder, TraversalDirection, TraversalResult,
+  TraverseOptions,
+};
+
+/// Traversal builder bound to a Kite database
+///
+/// Provides ergonomic traversal operations using edge type names.
+pub struct KiteTraversalBuilder<'a> {
+  ray: &'a Kite,
+  builder: TraversalBuilder,
+  /// Key prefixes of the node types hops may land on (None = any)
+  node_prefixes: Option<Vec<String>>,
+  /// Whether hops may land on archived nodes
+  include_archived: bool,
+  /// Only follow edges valid at this instant (ms since the Unix epoch)
+  as_of: Option<i64>,
+}
+
+/// Expand one hop, keeping only neighbors whose type is allowed
+fn kite_hop(
+  ray: &Kite,
+  node_prefixes: Option<&[String]>,
+  include_archived: bool,
+  as_of: Option<i64>,
+  node_id: NodeId,
+  direction: TraversalDirection,
+  etype: Option<ETypeId>,
+) -> Vec<Edge> {
+  let mut edges = ray.neighbors_including_archived(node_id, direction, etype);
+  if let Some(prefixes) = node_prefixes {
+    retain_node_types(&ray.db, node_id, &mut edges, prefixes);
+  }
+  if !include_archived {
+    retain_unarchived(&ray.db, node_id, &mut edges);
+  }
+  if let Some(as_of) = as_of {
+    retain_valid_at(&ray.db, &mut edges, as_of);
+  }
+  edges
+}
+
+impl<'a> KiteTraversalBuilder<'a> {
+  fn new(ray: &'a Kite, start_nodes: Vec<NodeId>) -> Self {
+    Self {
+      ray,
+      builder: TraversalBuilder::new(start_nodes),
+      node_prefixes: None,
+      include_archived: false,
+      as_of: None,
+    }
+  }
+
+  /// Traverse outgoing edges
+  ///
+  /// @param edge_type - Edge type name (or None for all types)
+  pub fn out(mut self, edge_type: Option<&str>) -> Result<Self> {
+    let etype = self.resolve_etype(edge_type)?;
+    self.builder = self.builder.out(etype);
+    Ok(self)
+  }
+
+  /// Traverse incoming edges
+  pub fn r#in(mut self, edge_type: Option<&str>) -> Result<Self> {
+    let etype = self.resolve_etype(edge_type)?;
+    self.builder = self.builder.r#in(etype);
+    Ok(self)
+  }
+
+  /// Traverse edges in both directions
+  pub fn both(mut self, edge_type: Option<&str>) -> Result<Self> {
+    let etype = self.resolve_etype(edge_type)?;
+    self.builder = self.builder.both(etype);
+    Ok(self)
+  }
+
+  /// Variable-depth traversal
+  pub fn traverse(mut self, edge_type: Option<&str>, options: TraverseOptions) -> Result<Self> {
+    let etype = self.resolve_etype(edge_type)?;
+    self.builder = self.builder.traverse(etype, options);
+    Ok(self)
+  }
+
+  /// Only walk through nodes of the given types
+  ///
+  /// Between hops, frontier nodes whose key prefix doesn't belong to one of
+  /// `node_types` are dropped: they are neither returned nor expanded.
+  /// Applies to every step of the traversal; start nodes are kept as given.
+  pub fn allowed_node_types(mut self, node_types: &[&str]) -> Result<Self> {
+    self.node_prefixes = Some(self.ray.node_type_prefixes(node_types)?);
+    Ok(self)
+  }
+
+  /// Let hops land on archived nodes (skipped by default)
+  pub fn include_archived(mut self) -> Self {
+    self.include_archived = true;
+    self
+  }
+
+  /// Only follow edges valid at `timestamp` (ms since the Unix epoch)
+  ///
+  /// Edges linked with [`Kite::link_valid`] are followed only when
+  /// `valid_from <= timestamp < valid_to`; edges without a window are always
+  /// followed. Nodes themselves aren't versioned, so this shows the edges of
+  /// the graph as of that date, not past prop values.
+  pub fn as_of(mut self, timestamp: i64) -> Self {
+    self.as_of = Some(timestamp);
+    self
+  }
+
+  /// Limit the number of results
+  pub fn take(mut self, limit: usize) -> Self {
+    self.builder = self.builder.take(limit);
+    self
+  }
+
+  /// Select specific properties to load (optimization)
+  ///
+  /// Only the specified properties will be loaded when collecting results,
+  /// reducing overhead. This is useful when you only need a few properties
+  /// from nodes that have many properties.
+  ///
+  /// # Example
+  /// ```rust,no_run
+  /// # use kitedb::api::kite::Kite;
+  /// # use kitedb::types::NodeId;
+  /// # fn main() -> kitedb::error::Result<()> {
+  /// # let kite: Kite = unimplemented!();
+  /// # let user_id: NodeId = 1;
+  /// let friends = kite.from(user_id)
+  ///     .out(Some("FOLLOWS"))?
+  ///     .select(&["name", "avatar"]) // Only load name and avatar
+  ///     .to_vec();
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn select(mut self, props: &[&str]) -> Self {
+    self.builder = self.builder.select_props(props);
+    self
+  }
+
+  /// Execute and collect node IDs
+  pub fn to_vec(self) -> Vec<NodeId> {
+    let prefixes = self.node_prefixes.as_deref();
+    let include_archived = self.include_archived;
+    let as_of = self.as_of;
+    self.builder.collect_node_ids(|node_id, dir, etype| {
+      kite_hop(
+        self.ray,
+        prefixes,
+        include_archived,
+        as_of,
+        node_id,
+        dir,
+        etype,
+      )
+    })
+  }
+
+  /// Execute and get first result
+  pub fn first(self) -> Option<TraversalResult> {
+    let prefixes = self.node_prefixes.as_deref();
+    let include_archived = self.include_archived;
+    let as_of = self.as_of;
+    self.builder.first(|node_id, dir, etype| {
+      kite_hop(
+        self.ray,
+        prefixes,
+        include_archived,
+        as_of,
+        node_id,
+        dir,
+        etype,
+      )
+    })
+  }
+
+  /// Execute and get first node ID
+  pub fn first_node(self) -> Option<NodeId> {
+    let prefixes = self.node_prefixes.as_deref();
+    let include_archived = self.include_archived;
+    let as_of = self.as_of;
+    self.builder.first_node(|node_id, dir, etype| {
+      kite_hop(
+        self.ray,
+        prefixes,
+        include_archived,
+        as_of,
+        node_id,
+        dir,
+        etype,
+      )
+    })
+  }
+
+  /// Execute and count results
+  pub fn count(self) -> usize {
+    let prefixes = self.node_prefixes.as_deref();
+    let include_archived = self.include_archived;
+    let as_of = self.as_of;
+    self.builder.count(|node_id, dir, etype| {
+      kite_hop(
+        self.ray,
+        prefixes,
+        include_archived,
+        as_of,
+        node_id,
+        dir,
+        etype,
+      )
+    })
+  }
+
+  /// Execute and return iterator over traversal results
+  pub fn execute(self) -> impl Iterator<Item = TraversalResult> + 'a {
+    let Self {
+      ray,
+      builder,
+      node_prefixes,
+      include_archived,
+      as_of,
+    } = self;
+    builder.execute(move |node_id, dir, etype| {
+      kite_hop(
+        ray,
+        node_prefixes.as_deref(),
+        include_archived,
+        as_of,
+        node_id,
+        dir,
+        etype,
+      )
+    })
+  }
+
+  /// Execute and return iterator over edges only
+  ///
+  /// This is useful when you want to collect the edges traversed rather than nodes.
+  /// Each result contains the source, destination, and edge type of edges encountered.
+  ///
+  /// # Example
+  /// ```rust,no_run
+  /// # use kitedb::api::kite::Kite;
+  /// # use kitedb::types::NodeId;
+  /// # fn main() -> kitedb::error::Result<()> {
+  /// # let kite: Kite = unimplemented!();
+  /// # let user_id: NodeId = 1;
+  /// let edges: Vec<_> = kite.from(user_id)
+  ///     .out(Some("FOLLOWS"))?
+  ///     .edges()
+  ///     .collect();
+  ///
+  /// for edge in edges {
+  ///     println!("{} -[{}]-> {}", edge.src, edge.etype, edge.dst);
+  /// }
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn edges(self) -> impl Iterator<Item = Edge> + 'a {
+    self.execute().filter_map(|result| {
+      result.edge.map(|e| Edge {
+        src: e.src,
+        etype: e.etype,
+        dst: e.dst,
+      })
+    })
+  }
+
+  /// Execute and return iterator over full edge details
+  ///
+  /// Similar to `edges()` but returns FullEdge structs.
+  pub fn full_edges(self) -> impl Iterator<Item = FullEdge> + 'a {
+    self.execute().filter_map(move |result| {
+      result.edge.map(|e| FullEdge {
+        src: e.src,
+        etype: e.etype,
+        dst: e.dst,
+      })
+    })
+  }
+
+  fn resolve_etype(&self, edge_type: Option<&str>) -> Result<Option<ETypeId>> {
+    match edge_type {
+      Some(name) => {
+        let edge_def = self
+          .ray
+          .edges
+          .get(name)
+          .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {name}").into()))?;
+        Ok(edge_def.etype_id)
+      }
+      None => Ok(None),
+    }
+  }
+}
+
+// ============================================================================
+// Path Finding Builder for Kite
+// ============================================================================
+
+use super::pathfinding::{bfs, dijkstra, yen_k_shortest, PathConfig, PathResult};
+
+/// Path finding builder bound to a Kite database
+///
+/// Provides ergonomic pathfinding operations using edge type names.
+pub struct KitePathBuilder<'a> {
+  ray: &'a Kite,
+  source: NodeId,
+  targets: HashSet<NodeId>,
+  allowed_etypes: HashSet<ETypeId>,
+  direction: TraversalDirection,
+  max_depth: usize,
+  weights: HashMap<(NodeId, ETypeId, NodeId), f64>,
+}
+
+impl<'a> KitePathBuilder<'a> {
+  fn new(ray: &'a Kite, source: NodeId, target: NodeId) -> Self {
+    let mut targets = HashSet::new();
+    targets.insert(target);
+
+    Self {
+      ray,
+      source,
+      targets,
+      allowed_etypes: HashSet::new(),
+      direction: TraversalDirection::Out,
+      max_depth: 100,
+      weights: HashMap::new(),
+    }
+  }
+
+  fn new_multi(ray: &'a Kite, source: NodeId, targets: Vec<NodeId>) -> Self {
+    Self {
+      ray,
+      source,
+      targets: targets.into_iter().collect(),
+      allowed_etypes: HashSet::new(),
+      direction: TraversalDirection::Out,
+      max_depth: 100,
+      weights: HashMap::new(),
+    }
+  }
+
+  /// Restrict traversal to specific edge type
+  ///
+  /// Can be called multiple times to allow multiple edge types.
+  pub fn via(mut self, edge_type: &str) -> Result<Self> {
+    let edge_def =
+      self.ray.edges.get(edge_type).ok_or_else(|| {
+        KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into())
+      })?;
+
+    if let Some(etype_id) = edge_def.etype_id {
+      self.allowed_etypes.insert(etype_id);
+    }
+
+    Ok(self)
+  }
+
+  /// Set maximum search depth
+  pub fn max_depth(mut self, depth: usize) -> Self {
+    self.max_depth = depth;
+    self
+  }
+
+  /// Set traversal direction
+  pub fn direction(mut self, direction: TraversalDirection) -> Self {
+    self.direction = direction;
+    self
+  }
+
+  /// Use bidirectional traversal
+  pub fn bidirectional(mut self) -> Self {
+    self.direction = TraversalDirection::Both;
+    self
+  }
+
+  /// Find the shortest path using Dijkstra's algorithm
+  pub fn find(self) -> PathResult {
+    let config = PathConfig {
+      source: self.source,
+      targets: self.targets,
+      allowed_etypes: self.allowed_etypes,
+      direction: self.direction,
+      max_depth: self.max_depth,
+    };
+
+    let weights = self.weights;
+    dijkstra(
+      config,
+      |node_id, dir, etype| self.ray.neighbors(node_id, dir, etype),
+      move |src, etype, dst| weights.get(&(src, etype, dst)).copied().unwrap_or(1.0),
+    )
+  }
+
+  /// Find the shortest path using BFS (unweighted)
+  ///
+  /// Faster than Dijkstra for unweighted graphs.
+  pub fn find_bfs(self) -> PathResult {
+    let config = PathConfig {
+      source: self.source,
+      targets: self.targets,
+      allowed_etypes: self.allowed_etypes,
+      direction: self.direction,
+      max_depth: self.max_depth,
+    };
+
+    bfs(config, |node_id, dir, etype| {
+      self.ray.neighbors(node_id, dir, etype)
+    })
+  }
+
+  /// Find the k shortest paths using Yen's algorithm
+  pub fn find_k_shortest(self, k: usize) -> Vec<PathResult> {
+    let config = PathConfig {
+      source: self.source,
+      targets: self.targets,
+      allowed_etypes: self.allowed_etypes,
+      direction: self.direction,
+      max_depth: self.max_depth,
+    };
+
+    let weights = self.weights;
+    yen_k_shortest(
+      config,
+      k,
+      |node_id, dir, etype| self.ray.neighbors(node_id, dir, etype),
+      move |src, etype, dst| weights.get(&(src, etype, dst)).copied().unwrap_or(1.0),
+    )
+  }
+}
+
+// ============================================================================
+// Batch Operations
+// ============================================================================
+
+/// A batch operation that can be executed atomically with other operations
+#[derive(Debug, Clone)]
+pub enum BatchOp {
+  /// Create a new node
+  CreateNode {
+    node_type: String,
+    key_suffix: String,
+    props: HashMap<String, PropValue>,
+  },
+  /// Delete a node
+  DeleteNode { node_id: NodeId },
+  /// Create an edge
+  Link {
+    src: NodeId,
+    edge_type: String,
+    dst: NodeId,
+  },
+  /// Create an edge with properties
+  LinkWithProps {
+    src: NodeId,
+    edge_type: String,
+    dst: NodeId,
+    props: HashMap<String, PropValue>,
+  },
+  /// Remove an edge
+  Unlink {
+    src: NodeId,
+    edge_type: String,
+    dst: NodeId,
+  },
+  /// Set a node property
+  SetProp {
+    node_id: NodeId,
+    prop_name: String,
+    value: PropValue,
+  },
+  /// Set an edge property
+  SetEdgeProp {
+    src: NodeId,
+    edge_type: String,
+    dst: NodeId,
+    prop_name: String,
+    value: PropValue,
+  },
+  /// Set multiple edge properties
+  SetEdgeProps {
+    src: NodeId,
+    edge_type: String,
+    dst: NodeId,
+    props: HashMap<String, PropValue>,
+  },
+  /// Delete a node property
+  DelProp { node_id: NodeId, prop_name: String },
+  /// Create a node and link it to existing nodes in the same op
+  ///
+  /// Returns `NodeCreated`, so later batches can use the new id.
+  CreateAndLink {
+    node_type: String,
+    key_suffix: String,
+    props: HashMap<String, PropValue>,
+    links: Vec<BatchLink>,
+  },
+}
+
+/// An edge between the node created by [`BatchOp::CreateAndLink`] and an
+/// existing node
+#[derive(Debug, Clone)]
+pub struct BatchLink {
+  pub edge_type: String,
+  /// Existing node at the other end
+  pub node_id: NodeId,
+  /// Point the edge from the existing node to the new one (default: new
+  /// node is the source)
+  pub incoming: bool,
+}
+
+/// Result of a batch operation
+///
+/// The flags say whether the op had an effect, so no-ops in a bulk
+/// mutation can be spotted without re-reading the graph.
+#[derive(Debug, Clone)]
+pub enum BatchResult {
+  /// Node was created, contains the NodeRef
+  NodeCreated(NodeRef),
+  /// Node was deleted (`false` if it didn't exist)
+  NodeDeleted(bool),
+  /// Edge was linked (`false` if it already existed)
+  EdgeCreated(bool),
+  /// Edge was removed (`false` if it didn't exist)
+  EdgeRemoved(bool),
+  /// Property was set (`false` if it already held the value)
+  PropSet(bool),
+  /// Property was deleted (`false` if it wasn't set)
+  PropDeleted(bool),
+}
+
+impl BatchResult {
+  /// Number of things the op changed: 0 for a no-op, else 1
+  pub fn affected(&self) -> usize {
+    match self {
+      BatchResult::NodeCreated(_) => 1,
+      BatchResult::NodeDeleted(changed)
+      | BatchResult::EdgeCreated(changed)
+      | BatchResult::EdgeRemoved(changed)
+      | BatchResult::PropSet(changed)
+      | BatchResult::PropDeleted(changed) => usize::from(*changed),
+    }
+  }
+}
+
+#[derive(Debug, Clone)]
+struct EdgeCacheEntry {
+  etype_id: ETypeId,
+  prop_key_ids: HashMap<String, PropKeyId>,
+}
+
+fn resolve_edge_cache_entry<'a>(
+  edge_cache: &'a mut HashMap<String, EdgeCacheEntry>,
+  edges: &HashMap<String, EdgeDef>,
+  edge_type: &str,
+) -> Result<&'a mut EdgeCacheEntry> {
+  if edge_cache.contains_key(edge_type) {
+    return Ok(edge_cache.get_mut(edge_type).expect("expected value"));
+  }
+
+  let edge_def = edges
+    .get(edge_type)
+    .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+
+  let etype_id = edge_def
+    .etype_id
+    .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+
+  edge_cache.insert(
+    edge_type.to_string(),
+    EdgeCacheEntry {
+      etype_id,
+      prop_key_ids: edge_def.prop_key_ids.clone(),
+    },
+  );
+
+  Ok(edge_cache.get_mut(edge_type).expect("expected value"))
+}
+
+impl Kite {
+  /// Execute multiple operations atomically in a single transaction
+  ///
+  /// All operations succeed or fail together. If any operation fails,
+  /// the entire batch is rolled back.
+  ///
+  /// # Example
+  /// ```rust,no_run
+  /// # use kitedb::api::kite::{BatchOp, Kite, KiteOptions};
+  /// # use std::collections::HashMap;
+  /// # fn main() -> kitedb::error::Result<()> {
+  /// # let options = KiteOptions::default();
+  /// let mut kite = Kite::open("db", options)?;
+  ///
+  /// let results = kite.batch(vec![
+  ///   BatchOp::CreateNode {
+  ///     node_type: "User".into(),
+  ///     key_suffix: "alice".into(),
+  ///     props: HashMap::new(),
+  ///   },
+  ///   BatchOp::CreateNode {
+  ///     node_type: "User".into(),
+  ///     key_suffix: "bob".into(),
+  ///     props: HashMap::new(),
+  ///   },
+  /// ])?;
+  /// # Ok(())
+  /// # }
+  /// ```
+  pub fn batch(&mut self, ops: Vec<BatchOp>) -> Result<Vec<BatchResult>> {
+    let mut handle = begin_tx(&self.db)?;
+    let mut results = Vec::with_capacity(ops.len());
+    let mut edge_cache: HashMap<String, EdgeCacheEntry> = HashMap::new();
+
+    for op in ops {
+      let result = match op {
+        BatchOp::CreateNode {
+          node_type,
+          key_suffix,
+          props,
+        } => BatchResult::NodeCreated(self.batch_create_node(
+          &mut handle,
+          node_type,
+          &key_suffix,
+          props,
+        )?),
+
+        BatchOp::CreateAndLink {
+          node_type,
+          key_suffix,
+          props,
+          links,
+        } => {
+          let node_ref = self.batch_create_node(&mut handle, node_type, &key_suffix, props)?;
+          for link in links {
+            let entry = resolve_edge_cache_entry(&mut edge_cache, &self.edges, &link.edge_type)?;
+            let (src, dst) = if link.incoming {
+              (link.node_id, node_ref.id)
+            } else {
+              (node_ref.id, link.node_id)
+            };
+            add_edge(&mut handle, src, entry.etype_id, dst)?;
+          }
+          BatchResult::NodeCreated(node_ref)
+        }
+
+        BatchOp::DeleteNode { node_id } => {
+          BatchResult::NodeDeleted(delete_node(&mut handle, node_id)?)
+        }
+
+        BatchOp::Link {
+          src,
+          edge_type,
+          dst,
+        } => {
+          let entry = resolve_edge_cache_entry(&mut edge_cache, &self.edges, &edge_type)?;
+          let etype_id = entry.etype_id;
+          let created = !handle.db.edge_exists(src, etype_id, dst);
+          add_edge(&mut handle, src, etype_id, dst)?;
+          BatchResult::EdgeCreated(created)
+        }
+
+        BatchOp::LinkWithProps {
+          src,
+          edge_type,
+          dst,
+          props,
+        } => {
+          if let Some(edge_def) = self.edges.get(&edge_type) {
+            for (prop_name, value) in &props {
+              edge_def.check_prop(prop_name, value)?;
+            }
+          }
+          let entry = resolve_edge_cache_entry(&mut edge_cache, &self.edges, &edge_type)?;
+          let etype_id = entry.etype_id;
+          let created = !handle.db.edge_exists(src, etype_id, dst);
+
+          if props.is_empty() {
+            add_edge(&mut handle, src, etype_id, dst)?;
+          } else {
+            let mut prop_pairs = Vec::with_capacity(props.len());
+            for (prop_name, value) in props {
+              let prop_key_id = if let Some(&id) = entry.prop_key_ids.get(&prop_name) {
+                id
+              } else {
+                let key_id = edge_propkey_id(handle.db, &prop_name)?;
+                entry.prop_key_ids.insert(prop_name.clone(), key_id);
+                key_id
+              };
+              prop_pairs.push((prop_key_id, value));
+            }
+            handle
+              .db
+              .add_edge_with_props(src, etype_id, dst, prop_pairs)?;
+          }
+
+          BatchResult::EdgeCreated(created)
+        }
+
+        BatchOp::Unlink {
+          src,
+          edge_type,
+          dst,
+        } => {
+          let edge_def = self.edges.get(&edge_type).ok_or_else(|| {
+            KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into())
+          })?;
+
+          let etype_id = edge_def
+            .etype_id
+            .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+
+          let deleted = delete_edge(&mut handle, src, etype_id, dst)?;
+          BatchResult::EdgeRemoved(deleted)
+        }
+
+        BatchOp::SetProp {
+          node_id,
+          prop_name,
+          value,
+        } => {
+          self.check_node_prop(node_id, &prop_name, &value)?;
+          // Use handle.db to access schema methods while handle is active
+          let prop_key_id = node_propkey_id(handle.db, &prop_name)?;
+          let changed = handle.db.node_prop(node_id, prop_key_id).as_ref() != Some(&value);
+          set_node_prop(&mut handle, node_id, prop_key_id, value)?;
+          BatchResult::PropSet(changed)
+        }
+
+        BatchOp::SetEdgeProp {
+          src,
+          edge_type,
+          dst,
+          prop_name,
+          value,
+        } => {
+          if let Some(edge_def) = self.edges.get(&edge_type) {
+            edge_def.check_prop(&prop_name, &value)?;
+          }
+          let entry = resolve_edge_cache_entry(&mut edge_cache, &self.edges, &edge_type)?;
+          let etype_id = entry.etype_id;
+
+          let prop_key_id = if let Some(&id) = entry.prop_key_ids.get(&prop_name) {
+            id
+          } else {
+            let key_id = edge_propkey_id(handle.db, &prop_name)?;
+            entry.prop_key_ids.insert(prop_name.clone(), key_id);
+            key_id
+          };
+
+          let changed = handle
+            .db
+            .edge_prop(src, etype_id, dst, prop_key_id)
+            .as_ref()
+            != Some(&value);
+          set_edge_prop(&mut handle, src, etype_id, dst, prop_key_id, value)?;
+          BatchResult::PropSet(changed)
+        }
+
+        BatchOp::SetEdgeProps {
+          src,
+          edge_type,
+          dst,
+          props,
+        } => {
+          if let Some(edge_def) = self.edges.get(&edge_type) {
+            for (prop_name, value) in &props {
+              edge_def.check_prop(prop_name, value)?;
+            }
+          }
+          let entry = resolve_edge_cache_entry(&mut edge_cache, &self.edges, &edge_type)?;
+          let etype_id = entry.etype_id;
+
+          let mut prop_pairs = Vec::with_capacity(props.len());
+          for (prop_name, value) in props {
+            let prop_key_id = if let Some(&id) = entry.prop_key_ids.get(&prop_name) {
+              id
+            } else {
+              let key_id = edge_propkey_id(handle.db, &prop_name)?;
+              entry.prop_key_ids.insert(prop_name.clone(), key_id);
+              key_id
+            };
+            prop_pairs.push((prop_key_id, value));
+          }
+          let changed = prop_pairs.iter().any(|(key_id, value)| {
+            handle.db.edge_prop(src, etype_id, dst, *key_id).as_ref() != Some(value)
+          });
+
+          handle.db.set_edge_props(src, etype_id, dst, prop_pairs)?;
+          BatchResult::PropSet(changed)
+        }
+
+        BatchOp::DelProp { node_id, prop_name } => {
+          let prop_key_id = handle.db.propkey_id(&prop_name).ok_or_else(|| {
+            KiteError::InvalidSchema(format!("Unknown property: {prop_name}").into())
+          })?;
+          let deleted = handle.db.node_prop(node_id, prop_key_id).is_some();
+          del_node_prop(&mut handle, node_id, prop_key_id)?;
+          BatchResult::PropDeleted(deleted)
+        }
+      };
+
+      results.push(result);
+    }
+
+    // Commit the entire batch
+    commit(&mut handle)?;
+
+    Ok(results)
+  }
+
+  /// Create a node within a batch's transaction
+  fn batch_create_node(
+    &self,
+    handle: &mut TxHandle,
+    node_type: String,
+    key_suffix: &str,
+    props: HashMap<String, PropValue>,
+  ) -> Result<NodeRef> {
+    let node_def = self
+      .nodes
+      .get(&node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?;
+
+    let full_key = node_def.key(key_suffix);
+    for (prop_name, value) in &props {
+      node_def.check_prop(prop_name, value)?;
+    }
+
+    let node_opts = NodeOpts {
+      key: Some(full_key.clone()),
+      labels: node_def.label_id.map(|id| vec![id]),
+      props: None,
+    };
+    let node_id = create_node(handle, node_opts)?;
+    self.prop_index.check_all(
+      &self.db,
+      node_def,
+      node_id,
+      props.iter().map(|(name, value)| (name.as_str(), value)),
+    )?;
+
+    // Set properties
+    for (prop_name, value) in props {
+      if let Some(&prop_key_id) = node_def.prop_key_ids.get(&prop_name) {
+        set_node_prop(handle, node_id, prop_key_id, value)?;
+      }
+    }
+
+    Ok(NodeRef::new(node_id, Some(full_key), node_type))
+  }
+
+  /// Execute a batch of operations independently, with a result per op
+  ///
+  /// Unlike `batch`, each op runs in its own transaction: a failed op is
+  /// rolled back on its own and the remaining ops still apply. Meant for bulk
+  /// loads where a few bad rows shouldn't abort the import. Fails up front if
+  /// a transaction is already open, since the ops would then share it.
+  pub fn batch_best_effort(&mut self, ops: Vec<BatchOp>) -> Result<Vec<Result<BatchResult>>> {
+    if self.db.has_transaction() {
+      return Err(KiteError::TransactionInProgress);
+    }
+    Ok(
+      ops
+        .into_iter()
+        .map(|op| self.batch(vec![op]).map(|mut results| results.remove(0)))
+        .collect(),
+    )
+  }
+
+  /// Run `batch`, replaying the ops when the commit loses an MVCC conflict
+  ///
+  /// Makes up to `max_attempts` attempts (at least one) and returns the last
+  /// conflict if every attempt loses. Other errors fail immediately. Inside
+  /// an outer transaction the batch doesn't commit, so it never conflicts
+  /// here; the outer commit does.
+  pub fn batch_retry(
+    &mut self,
+    ops: Vec<BatchOp>,
+    max_attempts: usize,
+  ) -> Result<Vec<BatchResult>> {
+    self.retry_on_conflict(max_attempts, |ray| ray.batch(ops.clone()))
+  }
+
+  /// Call `attempt` until it stops failing with a conflict, at most
+  /// `max_attempts` times (at least once)
+  fn retry_on_conflict<T>(
+    &mut self,
+    max_attempts: usize,
+    mut attempt: impl FnMut(&mut Self) -> Result<T>,
+  ) -> Result<T> {
+    let mut attempts = 1;
+    loop {
+      match attempt(self) {
+        Err(e) if e.is_conflict() && attempts < max_attempts => attempts += 1,
+        result => return result,
+      }
+    }
+  }
+
+  /// Check a batch against the schema without applying it
+  ///
+  /// Returns the index and error of every op that `batch` would reject up
+  /// front: unknown node or edge types, props failing their type or enum
+  /// checks, self-loops on edge types that forbid them and deletes of
+  /// unknown props. Creates are also checked for missing `required` props,
+  /// which the write path itself doesn't enforce. Failures that depend on
+  /// the data, like unique-value collisions, are only caught by `batch`.
+  pub fn batch_validate(&self, ops: &[BatchOp]) -> Vec<(usize, KiteError)> {
+    ops
+      .iter()
+      .enumerate()
+      .filter_map(|(index, op)| self.validate_batch_op(op).err().map(|e| (index, e)))
+      .collect()
+  }
+
+  /// Validate a single batch op, see `batch_validate`
+  fn validate_batch_op(&self, op: &BatchOp) -> Result<()> {
+    match op {
+      BatchOp::CreateNode {
+        node_type, props, ..
+      } => self.validate_batch_create(node_type, props),
+
+      BatchOp::CreateAndLink {
+        node_type,
+        props,
+        links,
+        ..
+      } => {
+        self.validate_batch_create(node_type, props)?;
+        for link in links {
+          self.edge_def_and_id(&link.edge_type)?;
+        }
+        Ok(())
+      }
+
+      BatchOp::DeleteNode { .. } => Ok(()),
+
+      BatchOp::Link {
+        src,
+        edge_type,
+        dst,
+      } => {
+        let (_, etype_id) = self.edge_def_and_id(edge_type)?;
+        self.db.check_self_loop(*src, etype_id, *dst)
+      }
+
+      BatchOp::LinkWithProps {
+        src,
+        edge_type,
+        dst,
+        props,
+      } => {
+        let (edge_def, etype_id) = self.edge_def_and_id(edge_type)?;
+        for (prop_name, value) in props {
+          edge_def.check_prop(prop_name, value)?;
+        }
+        self.db.check_self_loop(*src, etype_id, *dst)
+      }
+
+      BatchOp::Unlink { edge_type, .. } => self.edge_def_and_id(edge_type).map(|_| ()),
+
+      BatchOp::SetProp {
+        node_id,
+        prop_name,
+        value,
+      } => match self.constrained_node_def(*node_id) {
+        Some(node_def) => node_def.check_prop(prop_name, value),
+        None => Ok(()),
+      },
+
+      BatchOp::SetEdgeProp {
+        edge_type,
+        prop_name,
+        value,
+        ..
+      } => {
+        let (edge_def, _) = self.edge_def_and_id(edge_type)?;
+        edge_def.check_prop(prop_name, value)
+      }
+
+      BatchOp::SetEdgeProps {
+        edge_type, props, ..
+      } => {
+        let (edge_def, _) = self.edge_def_and_id(edge_type)?;
+        for (prop_name, value) in props {
+          edge_def.check_prop(prop_name, value)?;
+        }
+        Ok(())
+      }
+
+      BatchOp::DelProp { prop_name, .. } => self
+        .db
+        .propkey_id(prop_name)
+        .map(|_| ())
+        .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown property: {prop_name}").into())),
+    }
+  }
+
+  /// Validate the node type and props of a batch create
+  fn validate_batch_create(
+    &self,
+    node_type: &str,
+    props: &HashMap<String, PropValue>,
+  ) -> Result<()> {
+    let node_def = self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?;
+    for (prop_name, value) in props {
+      node_def.check_prop(prop_name, value)?;
+    }
+    self.check_required_props(node_type, props)
   }
 
-  /// Find the k shortest paths using Yen's algorithm
-  pub fn find_k_shortest(self, k: usize) -> Vec<PathResult> {
-    let config = PathConfig {
-      source: self.source,
-      targets: self.targets,
-      allowed_etypes: self.allowed_etypes,
-      direction: self.direction,
-      max_depth: self.max_depth,
-    };
+  /// Check that `props` sets every `required` prop of `node_type`
+  ///
+  /// The write path doesn't enforce required props, so callers that create
+  /// nodes on the user's behalf (batch validation, get-or-insert, creating
+  /// missing link endpoints) check them here first.
+  pub fn check_required_props(
+    &self,
+    node_type: &str,
+    props: &HashMap<String, PropValue>,
+  ) -> Result<()> {
+    self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?
+      .check_required_props(props)
+  }
 
-    let weights = self.weights;
-    yen_k_shortest(
-      config,
-      k,
-      |node_id, dir, etype| self.ray.neighbors(node_id, dir, etype),
-      move |src, etype, dst| weights.get(&(src, etype, dst)).copied().unwrap_or(1.0),
-    )
+  /// Resolve an edge type to its definition and initialized ID
+  fn edge_def_and_id(&self, edge_type: &str) -> Result<(&EdgeDef, ETypeId)> {
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+    Ok((edge_def, etype_id))
   }
 }
 
 // ============================================================================
-// Batch Operations
+// Transaction Context
 // ============================================================================
 
-/// A batch operation that can be executed atomically with other operations
-#[derive(Debug, Clone)]
-pub enum BatchOp {
+/// Context for executing operations within a transaction
+///
+/// Provides the same operations as Kite but within an explicit transaction scope.
+/// All operations are committed together when the transaction closure returns Ok,
+/// or rolled back if an error is returned.
+///
+/// Note: TxContext holds references to the schema maps (nodes, edges) separately
+/// from the TxHandle to avoid borrow checker issues.
+pub struct TxContext<'a> {
+  handle: TxHandle<'a>,
+  nodes: &'a HashMap<String, NodeDef>,
+  edges: &'a HashMap<String, EdgeDef>,
+  key_prefix_to_node: &'a BTreeMap<String, String>,
+  node_constraints: bool,
+  prop_index: &'a PropIndex,
+}
+
+impl<'a> TxContext<'a> {
   /// Create a new node
-  CreateNode {
-    node_type: String,
-    key_suffix: String,
+  pub fn create_node(
+    &mut self,
+    node_type: &str,
+    key_suffix: &str,
     props: HashMap<String, PropValue>,
-  },
+  ) -> Result<NodeRef> {
+    let node_def = self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?
+      .clone();
+
+    let full_key = node_def.key(key_suffix);
+    for (prop_name, value) in &props {
+      node_def.check_prop(prop_name, value)?;
+    }
+
+    let node_opts = NodeOpts {
+      key: Some(full_key.clone()),
+      labels: node_def.label_id.map(|id| vec![id]),
+      props: None,
+    };
+    let node_id = create_node(&mut self.handle, node_opts)?;
+    self.prop_index.check_all(
+      self.handle.db,
+      &node_def,
+      node_id,
+      props.iter().map(|(name, value)| (name.as_str(), value)),
+    )?;
+
+    // Set properties
+    for (prop_name, value) in props {
+      if let Some(&prop_key_id) = node_def.prop_key_ids.get(&prop_name) {
+        set_node_prop(&mut self.handle, node_id, prop_key_id, value)?;
+      }
+    }
+
+    Ok(NodeRef::new(node_id, Some(full_key), node_type))
+  }
+
   /// Delete a node
-  DeleteNode { node_id: NodeId },
+  pub fn delete_node(&mut self, node_id: NodeId) -> Result<bool> {
+    delete_node(&mut self.handle, node_id)
+  }
+
   /// Create an edge
-  Link {
-    src: NodeId,
-    edge_type: String,
-    dst: NodeId,
-  },
-  /// Create an edge with properties
-  LinkWithProps {
-    src: NodeId,
-    edge_type: String,
-    dst: NodeId,
-    props: HashMap<String, PropValue>,
-  },
+  pub fn link(&mut self, src: NodeId, edge_type: &str, dst: NodeId) -> Result<()> {
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+
+    add_edge(&mut self.handle, src, etype_id, dst)?;
+    Ok(())
+  }
+
   /// Remove an edge
-  Unlink {
-    src: NodeId,
-    edge_type: String,
-    dst: NodeId,
-  },
+  pub fn unlink(&mut self, src: NodeId, edge_type: &str, dst: NodeId) -> Result<bool> {
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+
+    delete_edge(&mut self.handle, src, etype_id, dst)
+  }
+
   /// Set a node property
-  SetProp {
-    node_id: NodeId,
-    prop_name: String,
-    value: PropValue,
-  },
-  /// Set an edge property
-  SetEdgeProp {
-    src: NodeId,
-    edge_type: String,
-    dst: NodeId,
-    prop_name: String,
-    value: PropValue,
-  },
-  /// Set multiple edge properties
-  SetEdgeProps {
-    src: NodeId,
-    edge_type: String,
-    dst: NodeId,
-    props: HashMap<String, PropValue>,
-  },
+  pub fn set_prop(&mut self, node_id: NodeId, prop_name: &str, value: PropValue) -> Result<()> {
+    if self.node_constraints {
+      if let Some(node_def) = self
+        .handle
+        .db
+        .node_key(node_id)
+        .and_then(|key| node_def_for_key(self.nodes, self.key_prefix_to_node, &key))
+      {
+        node_def.check_prop(prop_name, &value)?;
+        self
+          .prop_index
+          .check(self.handle.db, node_def, node_id, prop_name, &value)?;
+      }
+    }
+    let prop_key_id = node_propkey_id(self.handle.db, prop_name)?;
+    set_node_prop(&mut self.handle, node_id, prop_key_id, value)?;
+    Ok(())
+  }
+
   /// Delete a node property
-  DelProp { node_id: NodeId, prop_name: String },
-}
+  pub fn del_prop(&mut self, node_id: NodeId, prop_name: &str) -> Result<()> {
+    let prop_key_id = self
+      .handle
+      .db
+      .propkey_id(prop_name)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown property: {prop_name}").into()))?;
+    del_node_prop(&mut self.handle, node_id, prop_key_id)?;
+    Ok(())
+  }
 
-/// Result of a batch operation
-#[derive(Debug, Clone)]
-pub enum BatchResult {
-  /// Node was created, contains the NodeRef
-  NodeCreated(NodeRef),
-  /// Node was deleted
-  NodeDeleted(bool),
-  /// Edge was created
-  EdgeCreated,
-  /// Edge was removed
-  EdgeRemoved(bool),
-  /// Property was set
-  PropSet,
-  /// Property was deleted
-  PropDeleted,
-}
+  /// Check if a node exists
+  pub fn exists(&self, node_id: NodeId) -> bool {
+    node_exists(&self.handle, node_id)
+  }
 
-#[derive(Debug, Clone)]
-struct EdgeCacheEntry {
-  etype_id: ETypeId,
-  prop_key_ids: HashMap<String, PropKeyId>,
-}
+  /// Check if an edge exists
+  pub fn has_edge(&self, src: NodeId, edge_type: &str, dst: NodeId) -> Result<bool> {
+    let edge_def = self
+      .edges
+      .get(edge_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
 
-fn resolve_edge_cache_entry<'a>(
-  edge_cache: &'a mut HashMap<String, EdgeCacheEntry>,
-  edges: &HashMap<String, EdgeDef>,
-  edge_type: &str,
-) -> Result<&'a mut EdgeCacheEntry> {
-  if edge_cache.contains_key(edge_type) {
-    return Ok(edge_cache.get_mut(edge_type).expect("expected value"));
+    let etype_id = edge_def
+      .etype_id
+      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+
+    Ok(edge_exists(&self.handle, src, etype_id, dst))
   }
 
-  let edge_def = edges
-    .get(edge_type)
-    .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+  /// Get a node property
+  pub fn prop(&self, node_id: NodeId, prop_name: &str) -> Result<Option<PropValue>> {
+    let prop_key_id = readable_propkey_id(self.handle.db, prop_name)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown property: {prop_name}").into()))?;
 
-  let etype_id = edge_def
-    .etype_id
-    .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+    Ok(node_prop(&self.handle, node_id, prop_key_id))
+  }
 
-  edge_cache.insert(
-    edge_type.to_string(),
-    EdgeCacheEntry {
-      etype_id,
-      prop_key_ids: edge_def.prop_key_ids.clone(),
-    },
-  );
+  /// Get a node by key
+  pub fn get(&self, node_type: &str, key_suffix: &str) -> Result<Option<NodeRef>> {
+    let node_def = self
+      .nodes
+      .get(node_type)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?;
 
-  Ok(edge_cache.get_mut(edge_type).expect("expected value"))
+    let full_key = node_def.key(key_suffix);
+    let node_id = node_by_key(&self.handle, &full_key);
+
+    match node_id {
+      Some(id) => Ok(Some(NodeRef::new(id, Some(full_key), node_type))),
+      None => Ok(None),
+    }
+  }
 }
 
 impl Kite {
-  /// Execute multiple operations atomically in a single transaction
+  /// Execute operations in an explicit transaction
   ///
-  /// All operations succeed or fail together. If any operation fails,
-  /// the entire batch is rolled back.
+  /// The closure receives a TxContext with access to node/edge operations.
+  /// All operations performed through the context are committed together when
+  /// the closure returns Ok, or rolled back if an error is returned.
   ///
   /// # Example
   /// ```rust,no_run
-  /// # use kitedb::api::kite::{BatchOp, Kite, KiteOptions};
+  /// # use kitedb::api::kite::Kite;
+  /// # use kitedb::types::PropValue;
   /// # use std::collections::HashMap;
   /// # fn main() -> kitedb::error::Result<()> {
-  /// # let options = KiteOptions::default();
-  /// let mut kite = Kite::open("db", options)?;
-  ///
-  /// let results = kite.batch(vec![
-  ///   BatchOp::CreateNode {
-  ///     node_type: "User".into(),
-  ///     key_suffix: "alice".into(),
-  ///     props: HashMap::new(),
-  ///   },
-  ///   BatchOp::CreateNode {
-  ///     node_type: "User".into(),
-  ///     key_suffix: "bob".into(),
-  ///     props: HashMap::new(),
-  ///   },
-  /// ])?;
+  /// # let mut kite: Kite = unimplemented!();
+  /// let result = kite.transaction(|ctx| {
+  ///   let alice = ctx.create_node("User", "alice", HashMap::new())?;
+  ///   let bob = ctx.create_node("User", "bob", HashMap::new())?;
+  ///   ctx.link(alice.id(), "FOLLOWS", bob.id())?;
+  ///   Ok((alice, bob))
+  /// })?;
   /// # Ok(())
   /// # }
   /// ```
-  pub fn batch(&mut self, ops: Vec<BatchOp>) -> Result<Vec<BatchResult>> {
-    let mut handle = begin_tx(&self.db)?;
-    let mut results = Vec::with_capacity(ops.len());
-    let mut edge_cache: HashMap<String, EdgeCacheEntry> = HashMap::new();
+  pub fn transaction<T, F>(&mut self, f: F) -> Result<T>
+  where
+    F: FnOnce(&mut TxContext) -> Result<T>,
+  {
+    // Start the transaction
+    let handle = begin_tx(&self.db)?;
 
-    for op in ops {
-      let result = match op {
-        BatchOp::CreateNode {
-          node_type,
-          key_suffix,
-          props,
-        } => {
-          let node_def = self.nodes.get(&node_type).ok_or_else(|| {
-            KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into())
-          })?;
+    // Create context with references to schema maps
+    let mut ctx = TxContext {
+      handle,
+      nodes: &self.nodes,
+      edges: &self.edges,
+      key_prefix_to_node: &self.key_prefix_to_node,
+      node_constraints: self.node_constraints,
+      prop_index: &self.prop_index,
+    };
 
-          let full_key = node_def.key(&key_suffix);
+    match f(&mut ctx) {
+      Ok(result) => {
+        commit(&mut ctx.handle)?;
+        Ok(result)
+      }
+      Err(e) => {
+        rollback(&mut ctx.handle)?;
+        Err(e)
+      }
+    }
+  }
 
-          let node_opts = NodeOpts {
-            key: Some(full_key.clone()),
-            labels: node_def.label_id.map(|id| vec![id]),
-            props: None,
-          };
-          let node_id = create_node(&mut handle, node_opts)?;
+  /// Execute a transaction with a simpler API using a builder pattern
+  ///
+  /// Returns a TxBuilder that collects operations and executes them atomically.
+  pub fn tx(&mut self) -> TxBuilder {
+    TxBuilder { ops: Vec::new() }
+  }
+}
 
-          // Set properties
-          for (prop_name, value) in props {
-            if let Some(&prop_key_id) = node_def.prop_key_ids.get(&prop_name) {
-              set_node_prop(&mut handle, node_id, prop_key_id, value)?;
-            }
-          }
+/// Builder for constructing transactions with a fluent API
+#[derive(Debug, Default)]
+pub struct TxBuilder {
+  ops: Vec<BatchOp>,
+}
 
-          BatchResult::NodeCreated(NodeRef::new(node_id, Some(full_key), node_type))
-        }
+impl TxBuilder {
+  /// Add a create node operation
+  pub fn create_node(
+    mut self,
+    node_type: impl Into<String>,
+    key_suffix: impl Into<String>,
+    props: HashMap<String, PropValue>,
+  ) -> Self {
+    self.ops.push(BatchOp::CreateNode {
+      node_type: node_type.into(),
+      key_suffix: key_suffix.into(),
+      props,
+    });
+    self
+  }
 
-        BatchOp::DeleteNode { node_id } => {
-          let deleted = delete_node(&mut handle, node_id)?;
-          BatchResult::NodeDeleted(deleted)
-        }
+  /// Add a delete node operation
+  pub fn delete_node(mut self, node_id: NodeId) -> Self {
+    self.ops.push(BatchOp::DeleteNode { node_id });
+    self
+  }
 
-        BatchOp::Link {
-          src,
-          edge_type,
-          dst,
-        } => {
-          let entry = resolve_edge_cache_entry(&mut edge_cache, &self.edges, &edge_type)?;
-          let etype_id = entry.etype_id;
-          add_edge(&mut handle, src, etype_id, dst)?;
-          BatchResult::EdgeCreated
-        }
+  /// Add a link operation
+  pub fn link(mut self, src: NodeId, edge_type: impl Into<String>, dst: NodeId) -> Self {
+    self.ops.push(BatchOp::Link {
+      src,
+      edge_type: edge_type.into(),
+      dst,
+    });
+    self
+  }
 
-        BatchOp::LinkWithProps {
-          src,
-          edge_type,
-          dst,
-          props,
-        } => {
-          let entry = resolve_edge_cache_entry(&mut edge_cache, &self.edges, &edge_type)?;
-          let etype_id = entry.etype_id;
+  /// Add a link operation with properties
+  pub fn link_with_props(
+    mut self,
+    src: NodeId,
+    edge_type: impl Into<String>,
+    dst: NodeId,
+    props: HashMap<String, PropValue>,
+  ) -> Self {
+    self.ops.push(BatchOp::LinkWithProps {
+      src,
+      edge_type: edge_type.into(),
+      dst,
+      props,
+    });
+    self
+  }
 
-          if props.is_empty() {
-            add_edge(&mut handle, src, etype_id, dst)?;
-          } else {
-            let mut prop_pairs = Vec::with_capacity(props.len());
-            for (prop_name, value) in props {
-              let prop_key_id = if let Some(&id) = entry.prop_key_ids.get(&prop_name) {
-                id
-              } else {
-                let key_id = handle.db.propkey_id_or_create(&prop_name);
-                entry.prop_key_ids.insert(prop_name.clone(), key_id);
-                key_id
-              };
-              prop_pairs.push((prop_key_id, value));
-            }
-            handle
-              .db
-              .add_edge_with_props(src, etype_id, dst, prop_pairs)?;
-          }
+  /// Add an unlink operation
+  pub fn unlink(mut self, src: NodeId, edge_type: impl Into<String>, dst: NodeId) -> Self {
+    self.ops.push(BatchOp::Unlink {
+      src,
+      edge_type: edge_type.into(),
+      dst,
+    });
+    self
+  }
 
-          BatchResult::EdgeCreated
-        }
+  /// Add a set property operation
+  pub fn set_prop(
+    mut self,
+    node_id: NodeId,
+    prop_name: impl Into<String>,
+    value: PropValue,
+  ) -> Self {
+    self.ops.push(BatchOp::SetProp {
+      node_id,
+      prop_name: prop_name.into(),
+      value,
+    });
+    self
+  }
 
-        BatchOp::Unlink {
-          src,
-          edge_type,
-          dst,
-        } => {
-          let edge_def = self.edges.get(&edge_type).ok_or_else(|| {
-            KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into())
-          })?;
+  /// Add a set edge property operation
+  pub fn set_edge_prop(
+    mut self,
+    src: NodeId,
+    edge_type: impl Into<String>,
+    dst: NodeId,
+    prop_name: impl Into<String>,
+    value: PropValue,
+  ) -> Self {
+    self.ops.push(BatchOp::SetEdgeProp {
+      src,
+      edge_type: edge_type.into(),
+      dst,
+      prop_name: prop_name.into(),
+      value,
+    });
+    self
+  }
 
-          let etype_id = edge_def
-            .etype_id
-            .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+  /// Add a set edge properties operation
+  pub fn set_edge_props(
+    mut self,
+    src: NodeId,
+    edge_type: impl Into<String>,
+    dst: NodeId,
+    props: HashMap<String, PropValue>,
+  ) -> Self {
+    self.ops.push(BatchOp::SetEdgeProps {
+      src,
+      edge_type: edge_type.into(),
+      dst,
+      props,
+    });
+    self
+  }
+
+  /// Add a delete property operation
+  pub fn del_prop(mut self, node_id: NodeId, prop_name: impl Into<String>) -> Self {
+    self.ops.push(BatchOp::DelProp {
+      node_id,
+      prop_name: prop_name.into(),
+    });
+    self
+  }
+
+  /// Execute the transaction on the given Kite instance
+  pub fn execute(self, ray: &mut Kite) -> Result<Vec<BatchResult>> {
+    ray.batch(self.ops)
+  }
+
+  /// Get the operations as a Vec<BatchOp>
+  pub fn into_ops(self) -> Vec<BatchOp> {
+    self.ops
+  }
+}
+
+// ============================================================================
+// Update Node Builder
+// ============================================================================
+
+/// Fluent builder for updating node properties
+///
+/// Created via `kite.update()`, `kite.update_by_id()`, or `kite.update_by_key()`
+/// and allows chaining multiple property set/unset operations before executing
+/// in a single transaction.
+///
+/// # Example
+/// ```rust,no_run
+/// # use kitedb::api::kite::{NodeRef, Kite};
+/// # use kitedb::types::PropValue;
+/// # fn main() -> kitedb::error::Result<()> {
+/// # let mut kite: Kite = unimplemented!();
+/// # let alice: NodeRef = unimplemented!();
+/// // Update by node reference
+/// kite.update(&alice)?
+///     .set("name", PropValue::String("Alice Updated".into()))
+///     .set("age", PropValue::I64(31))
+///     .unset("old_field")
+///     .execute()?;
+///
+/// // Update by key
+/// kite.update_by_key("User", "alice")?
+///     .set("name", PropValue::String("New Name".into()))
+///     .execute()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct KiteUpdateNodeBuilder<'a> {
+  ray: &'a mut Kite,
+  node_id: NodeId,
+  updates: HashMap<String, Option<PropValue>>,
+}
 
-          let deleted = delete_edge(&mut handle, src, etype_id, dst)?;
-          BatchResult::EdgeRemoved(deleted)
-        }
+impl<'a> KiteUpdateNodeBuilder<'a> {
+  /// Set a node property value
+  ///
+  /// The property will be set when `execute()` is called.
+  pub fn set(mut self, prop_name: impl Into<String>, value: PropValue) -> Self {
+    self.updates.insert(prop_name.into(), Some(value));
+    self
+  }
 
-        BatchOp::SetProp {
-          node_id,
-          prop_name,
-          value,
-        } => {
-          // Use handle.db to access schema methods while handle is active
-          let prop_key_id = handle.db.propkey_id_or_create(&prop_name);
-          set_node_prop(&mut handle, node_id, prop_key_id, value)?;
-          BatchResult::PropSet
-        }
+  /// Remove a node property
+  ///
+  /// The property will be deleted when `execute()` is called.
+  pub fn unset(mut self, prop_name: impl Into<String>) -> Self {
+    self.updates.insert(prop_name.into(), None);
+    self
+  }
 
-        BatchOp::SetEdgeProp {
-          src,
-          edge_type,
-          dst,
-          prop_name,
-          value,
-        } => {
-          let entry = resolve_edge_cache_entry(&mut edge_cache, &self.edges, &edge_type)?;
-          let etype_id = entry.etype_id;
+  /// Set multiple properties at once from a HashMap
+  ///
+  /// Convenience method for setting multiple properties.
+  pub fn set_all(mut self, props: HashMap<String, PropValue>) -> Self {
+    for (k, v) in props {
+      self.updates.insert(k, Some(v));
+    }
+    self
+  }
 
-          let prop_key_id = if let Some(&id) = entry.prop_key_ids.get(&prop_name) {
-            id
-          } else {
-            let key_id = handle.db.propkey_id_or_create(&prop_name);
-            entry.prop_key_ids.insert(prop_name.clone(), key_id);
-            key_id
-          };
+  /// Execute the update, applying all property changes in a single transaction
+  pub fn execute(self) -> Result<()> {
+    if self.updates.is_empty() {
+      return Ok(());
+    }
 
-          set_edge_prop(&mut handle, src, etype_id, dst, prop_key_id, value)?;
-          BatchResult::PropSet
+    if let Some(node_def) = self.ray.constrained_node_def(self.node_id) {
+      for (prop_name, value) in &self.updates {
+        if let Some(value) = value {
+          node_def.check_prop(prop_name, value)?;
+          self
+            .ray
+            .prop_index
+            .check(&self.ray.db, node_def, self.node_id, prop_name, value)?;
         }
+      }
+    }
 
-        BatchOp::SetEdgeProps {
-          src,
-          edge_type,
-          dst,
-          props,
-        } => {
-          let entry = resolve_edge_cache_entry(&mut edge_cache, &self.edges, &edge_type)?;
-          let etype_id = entry.etype_id;
+    let mut handle = begin_tx(&self.ray.db)?;
 
-          let mut prop_pairs = Vec::with_capacity(props.len());
-          for (prop_name, value) in props {
-            let prop_key_id = if let Some(&id) = entry.prop_key_ids.get(&prop_name) {
-              id
-            } else {
-              let key_id = handle.db.propkey_id_or_create(&prop_name);
-              entry.prop_key_ids.insert(prop_name.clone(), key_id);
-              key_id
-            };
-            prop_pairs.push((prop_key_id, value));
-          }
+    for (prop_name, value_opt) in self.updates {
+      let prop_key_id = node_propkey_id(&self.ray.db, &prop_name)?;
 
-          handle.db.set_edge_props(src, etype_id, dst, prop_pairs)?;
-          BatchResult::PropSet
+      match value_opt {
+        Some(value) => {
+          set_node_prop(&mut handle, self.node_id, prop_key_id, value)?;
         }
-
-        BatchOp::DelProp { node_id, prop_name } => {
-          let prop_key_id = handle.db.propkey_id(&prop_name).ok_or_else(|| {
-            KiteError::InvalidSchema(format!("Unknown property: {prop_name}").into())
-          })?;
-          del_node_prop(&mut handle, node_id, prop_key_id)?;
-          BatchResult::PropDeleted
+        None => {
+          // Only delete if prop exists
+          del_node_prop(&mut handle, self.node_id, prop_key_id)?;
         }
-      };
-
-      results.push(result);
+      }
     }
 
-    // Commit the entire batch
     commit(&mut handle)?;
+    Ok(())
+  }
 
-    Ok(results)
+  /// Get the node ID being updated
+  pub fn node_id(&self) -> NodeId {
+    self.node_id
   }
 }
 
 // ============================================================================
-// Transaction Context
+// Upsert By ID Builder
 // ============================================================================
 
-/// Context for executing operations within a transaction
-///
-/// Provides the same operations as Kite but within an explicit transaction scope.
-/// All operations are committed together when the transaction closure returns Ok,
-/// or rolled back if an error is returned.
+/// Fluent builder for upserting a node by ID
 ///
-/// Note: TxContext holds references to the schema maps (nodes, edges) separately
-/// from the TxHandle to avoid borrow checker issues.
-pub struct TxContext<'a> {
-  handle: TxHandle<'a>,
-  nodes: &'a HashMap<String, NodeDef>,
-  edges: &'a HashMap<String, EdgeDef>,
+/// Created via `kite.upsert_by_id(node_type, node_id)` and allows chaining
+/// property set/unset operations before executing in a single transaction.
+pub struct KiteUpsertByIdBuilder<'a> {
+  ray: &'a mut Kite,
+  node_id: NodeId,
+  node_def: NodeDef,
+  key_suffix: Option<String>,
+  updates: HashMap<String, Option<PropValue>>,
 }
 
-impl<'a> TxContext<'a> {
-  /// Create a new node
-  pub fn create_node(
-    &mut self,
-    node_type: &str,
-    key_suffix: &str,
-    props: HashMap<String, PropValue>,
-  ) -> Result<NodeRef> {
-    let node_def = self
-      .nodes
-      .get(node_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?
-      .clone();
+impl<'a> KiteUpsertByIdBuilder<'a> {
+  /// Set the node key (suffix after the node type's prefix)
+  ///
+  /// A missing node is created with this key. An existing node is re-keyed
+  /// in the same transaction as the property updates: the old key stops
+  /// resolving and the new one starts, together. If the new key already
+  /// belongs to another node, `execute` fails with `DuplicateKey` and nothing
+  /// is written.
+  pub fn key(mut self, key_suffix: impl Into<String>) -> Self {
+    self.key_suffix = Some(key_suffix.into());
+    self
+  }
 
-    let full_key = node_def.key(key_suffix);
+  /// Set a node property value
+  pub fn set(mut self, prop_name: impl Into<String>, value: PropValue) -> Self {
+    self.updates.insert(prop_name.into(), Some(value));
+    self
+  }
 
-    let node_opts = NodeOpts {
-      key: Some(full_key.clone()),
-      labels: node_def.label_id.map(|id| vec![id]),
+  /// Remove a node property
+  pub fn unset(mut self, prop_name: impl Into<String>) -> Self {
+    self.updates.insert(prop_name.into(), None);
+    self
+  }
+
+  /// Set multiple properties at once from a HashMap
+  pub fn set_all(mut self, props: HashMap<String, PropValue>) -> Self {
+    for (k, v) in props {
+      self.updates.insert(k, Some(v));
+    }
+    self
+  }
+
+  /// Execute the upsert, creating the node if missing
+  pub fn execute(self) -> Result<()> {
+    for (prop_name, value) in &self.updates {
+      if let Some(value) = value {
+        self.node_def.check_prop(prop_name, value)?;
+        self
+          .ray
+          .prop_index
+          .check(&self.ray.db, &self.node_def, self.node_id, prop_name, value)?;
+      }
+    }
+
+    let mut handle = begin_tx(&self.ray.db)?;
+
+    let mut updates = Vec::with_capacity(self.updates.len());
+    for (prop_name, value_opt) in self.updates {
+      let prop_key_id = if let Some(&id) = self.node_def.prop_key_ids.get(&prop_name) {
+        id
+      } else {
+        node_propkey_id(&self.ray.db, &prop_name)?
+      };
+      updates.push((prop_key_id, value_opt));
+    }
+
+    let full_key = self
+      .key_suffix
+      .as_deref()
+      .map(|suffix| self.node_def.key(suffix));
+    let opts = NodeOpts {
+      key: full_key.clone(),
+      labels: self.node_def.label_id.map(|id| vec![id]),
       props: None,
     };
-    let node_id = create_node(&mut self.handle, node_opts)?;
 
-    // Set properties
-    for (prop_name, value) in props {
-      if let Some(&prop_key_id) = node_def.prop_key_ids.get(&prop_name) {
-        set_node_prop(&mut self.handle, node_id, prop_key_id, value)?;
-      }
+    let (node_id, created) =
+      upsert_node_by_id_with_props(&mut handle, self.node_id, opts, updates)?;
+    if let (false, Some(full_key)) = (created, full_key.as_deref()) {
+      handle.db.set_node_key(node_id, full_key)?;
     }
 
-    Ok(NodeRef::new(node_id, Some(full_key), node_type))
+    commit(&mut handle)?;
+    Ok(())
   }
+}
+
+// ============================================================================
+// Insert Builder
+// ============================================================================
+
+/// Fluent builder for inserting nodes
+///
+/// Created via `kite.insert(node_type)` and provides a fluent API for creating
+/// nodes with the `.values().returning()` or `.values().execute()` pattern.
+///
+/// # Example
+/// ```rust,no_run
+/// # use kitedb::api::kite::Kite;
+/// # use kitedb::types::PropValue;
+/// # use std::collections::HashMap;
+/// # fn main() -> kitedb::error::Result<()> {
+/// # let mut kite: Kite = unimplemented!();
+/// # let props: HashMap<String, PropValue> = HashMap::new();
+/// # let alice_props: HashMap<String, PropValue> = HashMap::new();
+/// # let bob_props: HashMap<String, PropValue> = HashMap::new();
+/// // Insert and get the node reference back
+/// let user = kite.insert("User")?
+///     .values("alice", props)?
+///     .returning()?;
+///
+/// // Insert multiple nodes
+/// let users = kite.insert("User")?
+///     .values_many(vec![
+///         ("alice", alice_props),
+///         ("bob", bob_props),
+///     ])?
+///     .returning()?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct KiteInsertBuilder<'a> {
+  ray: &'a mut Kite,
+  node_type: String,
+  key_prefix: String,
+}
 
-  /// Delete a node
-  pub fn delete_node(&mut self, node_id: NodeId) -> Result<bool> {
-    delete_node(&mut self.handle, node_id)
+impl<'a> KiteInsertBuilder<'a> {
+  /// Specify the values for a single node insert
+  ///
+  /// Returns an executor that can either `.execute()` (no return) or
+  /// `.returning()` (returns NodeRef).
+  pub fn values(
+    self,
+    key_suffix: &str,
+    props: HashMap<String, PropValue>,
+  ) -> Result<InsertExecutorSingle<'a>> {
+    let full_key = format!("{}{}", self.key_prefix, key_suffix);
+    Ok(InsertExecutorSingle {
+      ray: self.ray,
+      node_type: self.node_type,
+      full_key,
+      props,
+    })
   }
 
-  /// Create an edge
-  pub fn link(&mut self, src: NodeId, edge_type: &str, dst: NodeId) -> Result<()> {
-    let edge_def = self
-      .edges
-      .get(edge_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
-
-    let etype_id = edge_def
-      .etype_id
-      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+  /// Specify values for multiple nodes
+  ///
+  /// Returns an executor that can either `.execute()` (no return) or
+  /// `.returning()` (returns Vec<NodeRef>).
+  pub fn values_many(
+    self,
+    items: Vec<(&str, HashMap<String, PropValue>)>,
+  ) -> Result<InsertExecutorMultiple<'a>> {
+    let entries: Vec<(String, HashMap<String, PropValue>)> = items
+      .into_iter()
+      .map(|(key_suffix, props)| {
+        let full_key = format!("{}{}", self.key_prefix, key_suffix);
+        (full_key, props)
+      })
+      .collect();
 
-    add_edge(&mut self.handle, src, etype_id, dst)?;
-    Ok(())
+    Ok(InsertExecutorMultiple {
+      ray: self.ray,
+      node_type: self.node_type,
+      entries,
+    })
   }
 
-  /// Remove an edge
-  pub fn unlink(&mut self, src: NodeId, edge_type: &str, dst: NodeId) -> Result<bool> {
-    let edge_def = self
-      .edges
-      .get(edge_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
-
-    let etype_id = edge_def
-      .etype_id
-      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
-
-    delete_edge(&mut self.handle, src, etype_id, dst)
-  }
+  /// Specify values for multiple nodes with owned key suffixes
+  pub fn values_many_owned(
+    self,
+    items: Vec<(String, HashMap<String, PropValue>)>,
+  ) -> Result<InsertExecutorMultiple<'a>> {
+    let entries: Vec<(String, HashMap<String, PropValue>)> = items
+      .into_iter()
+      .map(|(key_suffix, props)| {
+        let full_key = format!("{}{}", self.key_prefix, key_suffix);
+        (full_key, props)
+      })
+      .collect();
 
-  /// Set a node property
-  pub fn set_prop(&mut self, node_id: NodeId, prop_name: &str, value: PropValue) -> Result<()> {
-    let prop_key_id = self.handle.db.propkey_id_or_create(prop_name);
-    set_node_prop(&mut self.handle, node_id, prop_key_id, value)?;
-    Ok(())
+    Ok(InsertExecutorMultiple {
+      ray: self.ray,
+      node_type: self.node_type,
+      entries,
+    })
   }
+}
 
-  /// Delete a node property
-  pub fn del_prop(&mut self, node_id: NodeId, prop_name: &str) -> Result<()> {
-    let prop_key_id = self
-      .handle
-      .db
-      .propkey_id(prop_name)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown property: {prop_name}").into()))?;
-    del_node_prop(&mut self.handle, node_id, prop_key_id)?;
-    Ok(())
-  }
+/// Executor for single node insert
+pub struct InsertExecutorSingle<'a> {
+  ray: &'a mut Kite,
+  node_type: String,
+  full_key: String,
+  props: HashMap<String, PropValue>,
+}
 
-  /// Check if a node exists
-  pub fn exists(&self, node_id: NodeId) -> bool {
-    node_exists(&self.handle, node_id)
-  }
+impl<'a> InsertExecutorSingle<'a> {
+  /// Execute the insert and return the created node reference
+  pub fn returning(self) -> Result<NodeRef> {
+    let node_def = self.ray.nodes.get(&self.node_type);
+    if let Some(node_def) = node_def {
+      for (prop_name, value) in &self.props {
+        node_def.check_prop(prop_name, value)?;
+      }
+    }
 
-  /// Check if an edge exists
-  pub fn has_edge(&self, src: NodeId, edge_type: &str, dst: NodeId) -> Result<bool> {
-    let edge_def = self
-      .edges
-      .get(edge_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type: {edge_type}").into()))?;
+    let node_type: Arc<str> = self.node_type.into();
+    let mut handle = begin_tx(&self.ray.db)?;
 
-    let etype_id = edge_def
-      .etype_id
-      .ok_or_else(|| KiteError::InvalidSchema("Edge type not initialized".into()))?;
+    // Create the node
+    let node_opts = NodeOpts::new().with_key(self.full_key.clone());
+    let node_id = create_node(&mut handle, node_opts)?;
+    if let Some(node_def) = node_def {
+      self.ray.prop_index.check_all(
+        &self.ray.db,
+        node_def,
+        node_id,
+        self
+          .props
+          .iter()
+          .map(|(name, value)| (name.as_str(), value)),
+      )?;
+    }
 
-    Ok(edge_exists(&self.handle, src, etype_id, dst))
-  }
+    // Set properties
+    for (prop_name, value) in self.props {
+      let prop_key_id = node_propkey_id(&self.ray.db, &prop_name)?;
+      set_node_prop(&mut handle, node_id, prop_key_id, value)?;
+    }
 
-  /// Get a node property
-  pub fn prop(&self, node_id: NodeId, prop_name: &str) -> Result<Option<PropValue>> {
-    let prop_key_id = self
-      .handle
-      .db
-      .propkey_id(prop_name)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown property: {prop_name}").into()))?;
+    commit(&mut handle)?;
 
-    Ok(node_prop(&self.handle, node_id, prop_key_id))
+    Ok(NodeRef::new(node_id, Some(self.full_key), node_type))
   }
 
-  /// Get a node by key
-  pub fn get(&self, node_type: &str, key_suffix: &str) -> Result<Option<NodeRef>> {
-    let node_def = self
-      .nodes
-      .get(node_type)
-      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown node type: {node_type}").into()))?;
+  /// Execute the insert without returning the node reference
+  ///
+  /// Slightly more efficient when you don't need the result.
+  pub fn execute(self) -> Result<()> {
+    let _ = self.returning()?;
+    Ok(())
+  }
+}
 
-    let full_key = node_def.key(key_suffix);
-    let node_id = node_by_key(&self.handle, &full_key);
+/// Executor for multiple node insert
+pub struct InsertExecutorMultiple<'a> {
+  ray: &'a mut Kite,
+  node_type: String,
+  entries: Vec<(String, HashMap<String, PropValue>)>,
+}
 
-    match node_id {
-      Some(id) => Ok(Some(NodeRef::new(id, Some(full_key), node_type))),
-      None => Ok(None),
+impl<'a> InsertExecutorMultiple<'a> {
+  /// Execute the insert and return all created node references
+  pub fn returning(self) -> Result<Vec<NodeRef>> {
+    if self.entries.is_empty() {
+      return Ok(Vec::new());
     }
-  }
-}
 
-impl Kite {
-  /// Execute operations in an explicit transaction
-  ///
-  /// The closure receives a TxContext with access to node/edge operations.
-  /// All operations performed through the context are committed together when
-  /// the closure returns Ok, or rolled back if an error is returned.
-  ///
-  /// # Example
-  /// ```rust,no_run
-  /// # use kitedb::api::kite::Kite;
-  /// # use kitedb::types::PropValue;
-  /// # use std::collections::HashMap;
-  /// # fn main() -> kitedb::error::Result<()> {
-  /// # let mut kite: Kite = unimplemented!();
-  /// let result = kite.transaction(|ctx| {
-  ///   let alice = ctx.create_node("User", "alice", HashMap::new())?;
-  ///   let bob = ctx.create_node("User", "bob", HashMap::new())?;
-  ///   ctx.link(alice.id(), "FOLLOWS", bob.id())?;
-  ///   Ok((alice, bob))
-  /// })?;
-  /// # Ok(())
-  /// # }
-  /// ```
-  pub fn transaction<T, F>(&mut self, f: F) -> Result<T>
-  where
-    F: FnOnce(&mut TxContext) -> Result<T>,
-  {
-    // Start the transaction
-    let handle = begin_tx(&self.db)?;
+    let node_def = self.ray.nodes.get(&self.node_type);
+    if let Some(node_def) = node_def {
+      for (_, props) in &self.entries {
+        for (prop_name, value) in props {
+          node_def.check_prop(prop_name, value)?;
+        }
+      }
+    }
 
-    // Create context with references to schema maps
-    let mut ctx = TxContext {
-      handle,
-      nodes: &self.nodes,
-      edges: &self.edges,
-    };
+    let mut handle = begin_tx(&self.ray.db)?;
+    let mut results = Vec::with_capacity(self.entries.len());
+    let node_type: Arc<str> = self.node_type.into();
 
-    match f(&mut ctx) {
-      Ok(result) => {
-        commit(&mut ctx.handle)?;
-        Ok(result)
+    for (full_key, props) in self.entries {
+      // Create the node
+      let node_opts = NodeOpts::new().with_key(full_key.clone());
+      let node_id = create_node(&mut handle, node_opts)?;
+      if let Some(node_def) = node_def {
+        self.ray.prop_index.check_all(
+          &self.ray.db,
+          node_def,
+          node_id,
+          props.iter().map(|(name, value)| (name.as_str(), value)),
+        )?;
       }
-      Err(e) => {
-        rollback(&mut ctx.handle)?;
-        Err(e)
+
+      // Set properties
+      for (prop_name, value) in props {
+        let prop_key_id = node_propkey_id(&self.ray.db, &prop_name)?;
+        set_node_prop(&mut handle, node_id, prop_key_id, value)?;
       }
+
+      results.push(NodeRef::new(
+        node_id,
+        Some(full_key),
+        Arc::clone(&node_type),
+      ));
     }
-  }
 
-  /// Execute a transaction with a simpler API using a builder pattern
-  ///
-  /// Returns a TxBuilder that collects operations and executes them atomically.
-  pub fn tx(&mut self) -> TxBuilder {
-    TxBuilder { ops: Vec::new() }
+    commit(&mut handle)?;
+
+    Ok(results)
+  }
+
+  /// Execute the insert without returning node references
+  pub fn execute(self) -> Result<()> {
+    let _ = self.returning()?;
+    Ok(())
   }
 }
 
-/// Builder for constructing transactions with a fluent API
-#[derive(Debug, Default)]
-pub struct TxBuilder {
-  ops: Vec<BatchOp>,
+// ============================================================================
+// Upsert Builder
+// ============================================================================
+
+/// Fluent builder for upserting nodes
+///
+/// Created via `kite.upsert(node_type)` and provides a fluent API for
+/// creating or updating nodes with the `.values().returning()` or
+/// `.values().execute()` pattern.
+pub struct KiteUpsertBuilder<'a> {
+  ray: &'a mut Kite,
+  node_type: String,
+  key_prefix: String,
 }
 
-impl TxBuilder {
-  /// Add a create node operation
-  pub fn create_node(
-    mut self,
-    node_type: impl Into<String>,
-    key_suffix: impl Into<String>,
+impl<'a> KiteUpsertBuilder<'a> {
+  /// Specify the values for a single upsert
+  pub fn values(
+    self,
+    key_suffix: &str,
     props: HashMap<String, PropValue>,
-  ) -> Self {
-    self.ops.push(BatchOp::CreateNode {
-      node_type: node_type.into(),
-      key_suffix: key_suffix.into(),
+  ) -> Result<UpsertExecutorSingle<'a>> {
+    let full_key = format!("{}{}", self.key_prefix, key_suffix);
+    Ok(UpsertExecutorSingle {
+      ray: self.ray,
+      node_type: self.node_type,
+      full_key,
       props,
-    });
-    self
+    })
   }
 
-  /// Add a delete node operation
-  pub fn delete_node(mut self, node_id: NodeId) -> Self {
-    self.ops.push(BatchOp::DeleteNode { node_id });
-    self
-  }
+  /// Specify values for multiple upserts
+  pub fn values_many(
+    self,
+    items: Vec<(&str, HashMap<String, PropValue>)>,
+  ) -> Result<UpsertExecutorMultiple<'a>> {
+    let entries: Vec<(String, HashMap<String, PropValue>)> = items
+      .into_iter()
+      .map(|(key_suffix, props)| {
+        let full_key = format!("{}{}", self.key_prefix, key_suffix);
+        (full_key, props)
+      })
+      .collect();
 
-  /// Add a link operation
-  pub fn link(mut self, src: NodeId, edge_type: impl Into<String>, dst: NodeId) -> Self {
-    self.ops.push(BatchOp::Link {
-      src,
-      edge_type: edge_type.into(),
-      dst,
-    });
-    self
+    Ok(UpsertExecutorMultiple {
+      ray: self.ray,
+      node_type: self.node_type,
+      entries,
+    })
   }
 
-  /// Add a link operation with properties
-  pub fn link_with_props(
-    mut self,
-    src: NodeId,
-    edge_type: impl Into<String>,
-    dst: NodeId,
-    props: HashMap<String, PropValue>,
-  ) -> Self {
-    self.ops.push(BatchOp::LinkWithProps {
-      src,
-      edge_type: edge_type.into(),
-      dst,
-      props,
-    });
-    self
-  }
+  /// Specify values for multiple upserts with owned key suffixes
+  pub fn values_many_owned(
+    self,
+    items: Vec<(String, HashMap<String, PropValue>)>,
+  ) -> Result<UpsertExecutorMultiple<'a>> {
+    let entries: Vec<(String, HashMap<String, PropValue>)> = items
+      .into_iter()
+      .map(|(key_suffix, props)| {
+        let full_key = format!("{}{}", self.key_prefix, key_suffix);
+        (full_key, props)
+      })
+      .collect();
 
-  /// Add an unlink operation
-  pub fn unlink(mut self, src: NodeId, edge_type: impl Into<String>, dst: NodeId) -> Self {
-    self.ops.push(BatchOp::Unlink {
-      src,
-      edge_type: edge_type.into(),
-      dst,
-    });
-    self
+    Ok(UpsertExecutorMultiple {
+      ray: self.ray,
+      node_type: self.node_type,
+      entries,
+    })
   }
+}
 
-  /// Add a set property operation
-  pub fn set_prop(
-    mut self,
-    node_id: NodeId,
-    prop_name: impl Into<String>,
-    value: PropValue,
-  ) -> Self {
-    self.ops.push(BatchOp::SetProp {
-      node_id,
-      prop_name: prop_name.into(),
-      value,
-    });
-    self
-  }
+/// Executor for single node upsert
+pub struct UpsertExecutorSingle<'a> {
+  ray: &'a mut Kite,
+  node_type: String,
+  full_key: String,
+  props: HashMap<String, PropValue>,
+}
 
-  /// Add a set edge property operation
-  pub fn set_edge_prop(
-    mut self,
-    src: NodeId,
-    edge_type: impl Into<String>,
-    dst: NodeId,
-    prop_name: impl Into<String>,
-    value: PropValue,
-  ) -> Self {
-    self.ops.push(BatchOp::SetEdgeProp {
-      src,
-      edge_type: edge_type.into(),
-      dst,
-      prop_name: prop_name.into(),
-      value,
-    });
-    self
-  }
+impl<'a> UpsertExecutorSingle<'a> {
+  /// Execute the upsert and return the node reference
+  pub fn returning(self) -> Result<NodeRef> {
+    let node_def = self.ray.nodes.get(&self.node_type);
+    if let Some(node_def) = node_def {
+      for (prop_name, value) in &self.props {
+        node_def.check_prop(prop_name, value)?;
+      }
+    }
+    let claims = PropIndex::pending_claims(node_def, &self.props);
 
-  /// Add a set edge properties operation
-  pub fn set_edge_props(
-    mut self,
-    src: NodeId,
-    edge_type: impl Into<String>,
-    dst: NodeId,
-    props: HashMap<String, PropValue>,
-  ) -> Self {
-    self.ops.push(BatchOp::SetEdgeProps {
-      src,
-      edge_type: edge_type.into(),
-      dst,
-      props,
-    });
-    self
+    let node_type: Arc<str> = self.node_type.into();
+    let mut handle = begin_tx(&self.ray.db)?;
+
+    let mut updates = Vec::with_capacity(self.props.len());
+    for (prop_name, value) in self.props {
+      let prop_key_id = node_propkey_id(&self.ray.db, &prop_name)?;
+      let value_opt = match value {
+        PropValue::Null => None,
+        other => Some(other),
+      };
+      updates.push((prop_key_id, value_opt));
+    }
+
+    let (node_id, _) = upsert_node_with_props(&mut handle, &self.full_key, updates)?;
+    if let Some(node_def) = node_def {
+      self.ray.prop_index.check_all(
+        &self.ray.db,
+        node_def,
+        node_id,
+        claims.iter().map(|(name, value)| (name.as_str(), value)),
+      )?;
+    }
+
+    commit(&mut handle)?;
+
+    Ok(NodeRef::new(node_id, Some(self.full_key), node_type))
   }
 
-  /// Add a delete property operation
-  pub fn del_prop(mut self, node_id: NodeId, prop_name: impl Into<String>) -> Self {
-    self.ops.push(BatchOp::DelProp {
-      node_id,
-      prop_name: prop_name.into(),
-    });
-    self
+  /// Execute the upsert without returning the node reference
+  pub fn execute(self) -> Result<()> {
+    let _ = self.returning()?;
+    Ok(())
   }
+}
 
-  /// Execute the transaction on the given Kite instance
-  pub fn execute(self, ray: &mut Kite) -> Result<Vec<BatchResult>> {
-    ray.batch(self.ops)
+/// Executor for multiple node upserts
+pub struct UpsertExecutorMultiple<'a> {
+  ray: &'a mut Kite,
+  node_type: String,
+  entries: Vec<(String, HashMap<String, PropValue>)>,
+}
+
+impl<'a> UpsertExecutorMultiple<'a> {
+  /// Execute the upserts and return node references
+  pub fn returning(self) -> Result<Vec<NodeRef>> {
+    if self.entries.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    let node_def = self.ray.nodes.get(&self.node_type);
+    if let Some(node_def) = node_def {
+      for (_, props) in &self.entries {
+        for (prop_name, value) in props {
+          node_def.check_prop(prop_name, value)?;
+        }
+      }
+    }
+
+    let mut handle = begin_tx(&self.ray.db)?;
+    let mut results = Vec::with_capacity(self.entries.len());
+    let node_type: Arc<str> = self.node_type.into();
+
+    for (full_key, props) in self.entries {
+      let claims = PropIndex::pending_claims(node_def, &props);
+      let mut updates = Vec::with_capacity(props.len());
+      for (prop_name, value) in props {
+        let prop_key_id = node_propkey_id(&self.ray.db, &prop_name)?;
+        let value_opt = match value {
+          PropValue::Null => None,
+          other => Some(other),
+        };
+        updates.push((prop_key_id, value_opt));
+      }
+
+      let (node_id, _) = upsert_node_with_props(&mut handle, &full_key, updates)?;
+      if let Some(node_def) = node_def {
+        self.ray.prop_index.check_all(
+          &self.ray.db,
+          node_def,
+          node_id,
+          claims.iter().map(|(name, value)| (name.as_str(), value)),
+        )?;
+      }
+      results.push(NodeRef::new(
+        node_id,
+        Some(full_key),
+        Arc::clone(&node_type),
+      ));
+    }
+
+    commit(&mut handle)?;
+
+    Ok(results)
   }
 
-  /// Get the operations as a Vec<BatchOp>
-  pub fn into_ops(self) -> Vec<BatchOp> {
-    self.ops
+  /// Execute the upserts without returning node references
+  pub fn execute(self) -> Result<()> {
+    let _ = self.returning()?;
+    Ok(())
   }
 }
 
 // ============================================================================
-// Update Node Builder
+// Update Edge Builder
 // ============================================================================
 
-/// Fluent builder for updating node properties
+/// Fluent builder for updating edge properties
 ///
-/// Created via `kite.update()`, `kite.update_by_id()`, or `kite.update_by_key()`
-/// and allows chaining multiple property set/unset operations before executing
-/// in a single transaction.
+/// Created via `kite.update_edge(src, edge_type, dst)` and allows chaining
+/// multiple property set/unset operations before executing in a single transaction.
 ///
 /// # Example
 /// ```rust,no_run
-/// # use kitedb::api::kite::{NodeRef, Kite};
-/// # use kitedb::types::PropValue;
+/// # use kitedb::api::kite::Kite;
+/// # use kitedb::types::{NodeId, PropValue};
 /// # fn main() -> kitedb::error::Result<()> {
 /// # let mut kite: Kite = unimplemented!();
-/// # let alice: NodeRef = unimplemented!();
-/// // Update by node reference
-/// kite.update(&alice)?
-///     .set("name", PropValue::String("Alice Updated".into()))
-///     .set("age", PropValue::I64(31))
-///     .unset("old_field")
-///     .execute()?;
-///
-/// // Update by key
-/// kite.update_by_key("User", "alice")?
-///     .set("name", PropValue::String("New Name".into()))
-///     .execute()?;
+/// # let alice_id: NodeId = 1;
+/// # let bob_id: NodeId = 2;
+/// kite.update_edge(alice_id, "FOLLOWS", bob_id)?
+///    .set("weight", PropValue::F64(0.9))
+///    .set("since", PropValue::String("2024".to_string()))
+///    .unset("deprecated_field")
+///    .execute()?;
 /// # Ok(())
 /// # }
 /// ```
-pub struct KiteUpdateNodeBuilder<'a> {
+pub struct KiteUpdateEdgeBuilder<'a> {
   ray: &'a mut Kite,
-  node_id: NodeId,
+  src: NodeId,
+  etype_id: ETypeId,
+  dst: NodeId,
   updates: HashMap<String, Option<PropValue>>,
+  ensure: bool,
 }
 
-impl<'a> KiteUpdateNodeBuilder<'a> {
-  /// Set a node property value
+impl<'a> KiteUpdateEdgeBuilder<'a> {
+  /// Create the edge if it doesn't exist before applying the updates
+  ///
+  /// Both happen in the same transaction when `execute()` is called.
+  pub fn ensure(mut self) -> Self {
+    self.ensure = true;
+    self
+  }
+
+  /// Set an edge property value
   ///
   /// The property will be set when `execute()` is called.
   pub fn set(mut self, prop_name: impl Into<String>, value: PropValue) -> Self {
@@ -3283,7 +7489,7 @@ impl<'a> KiteUpdateNodeBuilder<'a> {
     self
   }
 
-  /// Remove a node property
+  /// Remove an edge property
   ///
   /// The property will be deleted when `execute()` is called.
   pub fn unset(mut self, prop_name: impl Into<String>) -> Self {
@@ -3302,60 +7508,93 @@ impl<'a> KiteUpdateNodeBuilder<'a> {
   }
 
   /// Execute the update, applying all property changes in a single transaction
-  pub fn execute(self) -> Result<()> {
-    if self.updates.is_empty() {
-      return Ok(());
+  ///
+  /// Returns whether the edge was created, which only happens with `ensure()`.
+  pub fn execute(self) -> Result<bool> {
+    if self.updates.is_empty() && !self.ensure {
+      return Ok(false);
+    }
+
+    if let Some(edge_def) = self.ray.edge_def_by_id(self.etype_id) {
+      for (prop_name, value) in &self.updates {
+        if let Some(value) = value {
+          edge_def.check_prop(prop_name, value)?;
+        }
+      }
     }
 
     let mut handle = begin_tx(&self.ray.db)?;
 
+    if self.ensure {
+      let mut updates = Vec::with_capacity(self.updates.len());
+      for (prop_name, value_opt) in self.updates {
+        let prop_key_id = node_propkey_id(&self.ray.db, &prop_name)?;
+        updates.push((prop_key_id, value_opt));
+      }
+      let created =
+        upsert_edge_with_props(&mut handle, self.src, self.etype_id, self.dst, updates)?;
+      commit(&mut handle)?;
+      return Ok(created);
+    }
+
     for (prop_name, value_opt) in self.updates {
-      let prop_key_id = self.ray.db.propkey_id_or_create(&prop_name);
+      let prop_key_id = node_propkey_id(&self.ray.db, &prop_name)?;
 
       match value_opt {
         Some(value) => {
-          set_node_prop(&mut handle, self.node_id, prop_key_id, value)?;
+          set_edge_prop(
+            &mut handle,
+            self.src,
+            self.etype_id,
+            self.dst,
+            prop_key_id,
+            value,
+          )?;
         }
         None => {
-          // Only delete if prop exists
-          del_node_prop(&mut handle, self.node_id, prop_key_id)?;
+          // Only delete if prop_key exists
+          if let Some(existing_key_id) = self.ray.db.propkey_id(&prop_name) {
+            del_edge_prop(
+              &mut handle,
+              self.src,
+              self.etype_id,
+              self.dst,
+              existing_key_id,
+            )?;
+          }
         }
       }
     }
 
     commit(&mut handle)?;
-    Ok(())
-  }
-
-  /// Get the node ID being updated
-  pub fn node_id(&self) -> NodeId {
-    self.node_id
+    Ok(false)
   }
 }
 
 // ============================================================================
-// Upsert By ID Builder
+// Upsert Edge Builder
 // ============================================================================
 
-/// Fluent builder for upserting a node by ID
+/// Fluent builder for upserting edge properties
 ///
-/// Created via `kite.upsert_by_id(node_type, node_id)` and allows chaining
+/// Created via `kite.upsert_edge(src, edge_type, dst)` and allows chaining
 /// property set/unset operations before executing in a single transaction.
-pub struct KiteUpsertByIdBuilder<'a> {
+pub struct KiteUpsertEdgeBuilder<'a> {
   ray: &'a mut Kite,
-  node_id: NodeId,
-  node_def: NodeDef,
+  src: NodeId,
+  etype_id: ETypeId,
+  dst: NodeId,
   updates: HashMap<String, Option<PropValue>>,
 }
 
-impl<'a> KiteUpsertByIdBuilder<'a> {
-  /// Set a node property value
+impl<'a> KiteUpsertEdgeBuilder<'a> {
+  /// Set an edge property value
   pub fn set(mut self, prop_name: impl Into<String>, value: PropValue) -> Self {
     self.updates.insert(prop_name.into(), Some(value));
     self
   }
 
-  /// Remove a node property
+  /// Remove an edge property
   pub fn unset(mut self, prop_name: impl Into<String>) -> Self {
     self.updates.insert(prop_name.into(), None);
     self
@@ -3369,27 +7608,25 @@ impl<'a> KiteUpsertByIdBuilder<'a> {
     self
   }
 
-  /// Execute the upsert, creating the node if missing
+  /// Execute the upsert, creating the edge if missing
   pub fn execute(self) -> Result<()> {
+    if let Some(edge_def) = self.ray.edge_def_by_id(self.etype_id) {
+      for (prop_name, value) in &self.updates {
+        if let Some(value) = value {
+          edge_def.check_prop(prop_name, value)?;
+        }
+      }
+    }
+
     let mut handle = begin_tx(&self.ray.db)?;
 
     let mut updates = Vec::with_capacity(self.updates.len());
     for (prop_name, value_opt) in self.updates {
-      let prop_key_id = if let Some(&id) = self.node_def.prop_key_ids.get(&prop_name) {
-        id
-      } else {
-        self.ray.db.propkey_id_or_create(&prop_name)
-      };
+      let prop_key_id = node_propkey_id(&self.ray.db, &prop_name)?;
       updates.push((prop_key_id, value_opt));
     }
 
-    let opts = NodeOpts {
-      key: None,
-      labels: self.node_def.label_id.map(|id| vec![id]),
-      props: None,
-    };
-
-    upsert_node_by_id_with_props(&mut handle, self.node_id, opts, updates)?;
+    upsert_edge_with_props(&mut handle, self.src, self.etype_id, self.dst, updates)?;
 
     commit(&mut handle)?;
     Ok(())
@@ -3397,792 +7634,1442 @@ impl<'a> KiteUpsertByIdBuilder<'a> {
 }
 
 // ============================================================================
-// Insert Builder
+// Tests
 // ============================================================================
 
-/// Fluent builder for inserting nodes
-///
-/// Created via `kite.insert(node_type)` and provides a fluent API for creating
-/// nodes with the `.values().returning()` or `.values().execute()` pattern.
-///
-/// # Example
-/// ```rust,no_run
-/// # use kitedb::api::kite::Kite;
-/// # use kitedb::types::PropValue;
-/// # use std::collections::HashMap;
-/// # fn main() -> kitedb::error::Result<()> {
-/// # let mut kite: Kite = unimplemented!();
-/// # let props: HashMap<String, PropValue> = HashMap::new();
-/// # let alice_props: HashMap<String, PropValue> = HashMap::new();
-/// # let bob_props: HashMap<String, PropValue> = HashMap::new();
-/// // Insert and get the node reference back
-/// let user = kite.insert("User")?
-///     .values("alice", props)?
-///     .returning()?;
-///
-/// // Insert multiple nodes
-/// let users = kite.insert("User")?
-///     .values_many(vec![
-///         ("alice", alice_props),
-///         ("bob", bob_props),
-///     ])?
-///     .returning()?;
-/// # Ok(())
-/// # }
-/// ```
-pub struct KiteInsertBuilder<'a> {
-  ray: &'a mut Kite,
-  node_type: String,
-  key_prefix: String,
-}
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use tempfile::tempdir;
 
-impl<'a> KiteInsertBuilder<'a> {
-  /// Specify the values for a single node insert
-  ///
-  /// Returns an executor that can either `.execute()` (no return) or
-  /// `.returning()` (returns NodeRef).
-  pub fn values(
-    self,
-    key_suffix: &str,
-    props: HashMap<String, PropValue>,
-  ) -> Result<InsertExecutorSingle<'a>> {
-    let full_key = format!("{}{}", self.key_prefix, key_suffix);
-    Ok(InsertExecutorSingle {
-      ray: self.ray,
-      node_type: self.node_type,
-      full_key,
-      props,
-    })
+  fn temp_db_path(temp_dir: &tempfile::TempDir) -> std::path::PathBuf {
+    temp_dir.path().join("test-db")
   }
 
-  /// Specify values for multiple nodes
-  ///
-  /// Returns an executor that can either `.execute()` (no return) or
-  /// `.returning()` (returns Vec<NodeRef>).
-  pub fn values_many(
-    self,
-    items: Vec<(&str, HashMap<String, PropValue>)>,
-  ) -> Result<InsertExecutorMultiple<'a>> {
-    let entries: Vec<(String, HashMap<String, PropValue>)> = items
-      .into_iter()
-      .map(|(key_suffix, props)| {
-        let full_key = format!("{}{}", self.key_prefix, key_suffix);
-        (full_key, props)
-      })
-      .collect();
+  fn create_test_schema() -> KiteOptions {
+    let user = NodeDef::new("User", "user:")
+      .prop(PropDef::string("name").required())
+      .prop(PropDef::int("age"));
 
-    Ok(InsertExecutorMultiple {
-      ray: self.ray,
-      node_type: self.node_type,
-      entries,
-    })
+    let post = NodeDef::new("Post", "post:")
+      .prop(PropDef::string("title").required())
+      .prop(PropDef::string("content"));
+
+    let follows = EdgeDef::new("FOLLOWS");
+    let authored = EdgeDef::new("AUTHORED");
+
+    KiteOptions::new()
+      .node(user)
+      .node(post)
+      .edge(follows)
+      .edge(authored)
+  }
+
+  #[test]
+  fn test_recommended_kite_options_profiles() {
+    let safe = KiteOptions::recommended_safe();
+    assert_eq!(safe.sync_mode, SyncMode::Full);
+    assert!(!safe.group_commit_enabled);
+    assert_eq!(safe.close_checkpoint_if_wal_usage_at_least, Some(0.2));
+
+    let balanced = KiteOptions::recommended_balanced();
+    assert_eq!(balanced.sync_mode, SyncMode::Normal);
+    assert!(balanced.group_commit_enabled);
+    assert_eq!(balanced.group_commit_window_ms, 2);
+    assert_eq!(balanced.wal_size, Some(64 * 1024 * 1024));
+    assert_eq!(balanced.checkpoint_threshold, Some(0.5));
+    assert_eq!(balanced.close_checkpoint_if_wal_usage_at_least, Some(0.2));
+
+    let reopen = KiteOptions::recommended_reopen_heavy();
+    assert_eq!(reopen.sync_mode, SyncMode::Normal);
+    assert!(reopen.group_commit_enabled);
+    assert_eq!(reopen.wal_size, Some(16 * 1024 * 1024));
+    assert_eq!(reopen.checkpoint_threshold, Some(0.2));
+    assert_eq!(reopen.close_checkpoint_if_wal_usage_at_least, Some(0.2));
+  }
+
+  #[test]
+  fn test_runtime_profile_reopen_heavy_has_close_threshold() {
+    let profile = KiteRuntimeProfile::from_kind(KiteRuntimeProfileKind::ReopenHeavy);
+    assert_eq!(profile.options.wal_size, Some(16 * 1024 * 1024));
+    assert_eq!(profile.close_checkpoint_if_wal_usage_at_least, Some(0.2));
   }
 
-  /// Specify values for multiple nodes with owned key suffixes
-  pub fn values_many_owned(
-    self,
-    items: Vec<(String, HashMap<String, PropValue>)>,
-  ) -> Result<InsertExecutorMultiple<'a>> {
-    let entries: Vec<(String, HashMap<String, PropValue>)> = items
-      .into_iter()
-      .map(|(key_suffix, props)| {
-        let full_key = format!("{}{}", self.key_prefix, key_suffix);
-        (full_key, props)
-      })
-      .collect();
+  #[test]
+  fn test_kite_options_close_checkpoint_threshold_configurable() {
+    let options = KiteOptions::new()
+      .close_checkpoint_if_wal_usage_at_least(0.35)
+      .disable_close_checkpoint();
+    assert_eq!(options.close_checkpoint_if_wal_usage_at_least, None);
 
-    Ok(InsertExecutorMultiple {
-      ray: self.ray,
-      node_type: self.node_type,
-      entries,
-    })
+    let clamped = KiteOptions::new().close_checkpoint_if_wal_usage_at_least(1.5);
+    assert_eq!(clamped.close_checkpoint_if_wal_usage_at_least, Some(1.0));
   }
-}
 
-/// Executor for single node insert
-pub struct InsertExecutorSingle<'a> {
-  ray: &'a mut Kite,
-  node_type: String,
-  full_key: String,
-  props: HashMap<String, PropValue>,
-}
+  #[test]
+  fn test_open_database() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = create_test_schema();
 
-impl<'a> InsertExecutorSingle<'a> {
-  /// Execute the insert and return the created node reference
-  pub fn returning(self) -> Result<NodeRef> {
-    let node_type: Arc<str> = self.node_type.into();
-    let mut handle = begin_tx(&self.ray.db)?;
+    let ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
-    // Create the node
-    let node_opts = NodeOpts::new().with_key(self.full_key.clone());
-    let node_id = create_node(&mut handle, node_opts)?;
+    assert_eq!(ray.node_types().len(), 2);
+    assert_eq!(ray.edge_types().len(), 2);
+    assert!(ray.node_def("User").is_some());
+    assert!(ray.edge_def("FOLLOWS").is_some());
 
-    // Set properties
-    for (prop_name, value) in self.props {
-      let prop_key_id = self.ray.db.propkey_id_or_create(&prop_name);
-      set_node_prop(&mut handle, node_id, prop_key_id, value)?;
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_open_rejects_invalid_options_with_all_problems() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = KiteOptions::new()
+      .node(
+        NodeDef::new("User", "user:")
+          .prop(PropDef::int("age").default(PropValue::String("x".into()))),
+      )
+      .node(NodeDef::new("User", "member:"))
+      .node(NodeDef::new("Admin", "user:admin:").prop(PropDef::string_enum("level", ["owner"])))
+      .edge(EdgeDef::new("FOLLOWS").prop(PropDef::string("since").unique()))
+      .edge(EdgeDef::new("FOLLOWS"))
+      .edge(EdgeDef::new("GRANTS").prop(PropDef::string("level")));
+
+    let err = Kite::open(temp_db_path(&temp_dir), options)
+      .err()
+      .expect("expected error");
+    let message = err.to_string();
+    for expected in [
+      "duplicate node type 'User'",
+      "duplicate edge type 'FOLLOWS'",
+      "'User' and 'Admin' have overlapping key prefixes",
+      "prop 'age' has a String default",
+      "prop 'since' is unique or ordered",
+      "prop 'level' is an enum on node type 'Admin' but not on edge type 'GRANTS'",
+    ] {
+      assert!(
+        message.contains(expected),
+        "{expected} missing from {message}"
+      );
     }
+    assert!(!temp_db_path(&temp_dir).exists());
+  }
 
-    commit(&mut handle)?;
+  #[test]
+  fn test_schema_diff_lists_changes_against_target() {
+    let temp_dir = tempdir().expect("expected value");
+    let ray = Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
 
-    Ok(NodeRef::new(node_id, Some(self.full_key), node_type))
-  }
+    assert!(ray.schema_diff(&create_test_schema()).is_empty());
 
-  /// Execute the insert without returning the node reference
-  ///
-  /// Slightly more efficient when you don't need the result.
-  pub fn execute(self) -> Result<()> {
-    let _ = self.returning()?;
-    Ok(())
+    let target = KiteOptions::new()
+      .node(
+        NodeDef::new("User", "user:")
+          .prop(PropDef::string("name").required())
+          .prop(PropDef::string("age"))
+          .prop(PropDef::bool("active")),
+      )
+      .node(NodeDef::new("Comment", "comment:"))
+      .edge(EdgeDef::new("FOLLOWS").prop(PropDef::int("since")))
+      .edge(EdgeDef::new("LIKES"));
+
+    assert_eq!(
+      ray.schema_diff(&target),
+      vec![
+        SchemaChange::AddType {
+          kind: SchemaKind::Node,
+          name: "Comment".into(),
+        },
+        SchemaChange::RemoveType {
+          kind: SchemaKind::Node,
+          name: "Post".into(),
+        },
+        SchemaChange::AddProp {
+          kind: SchemaKind::Node,
+          owner: "User".into(),
+          prop: "active".into(),
+          prop_type: PropType::Bool,
+        },
+        SchemaChange::ChangePropType {
+          kind: SchemaKind::Node,
+          owner: "User".into(),
+          prop: "age".into(),
+          from: PropType::Int,
+          to: PropType::String,
+        },
+        SchemaChange::RemoveType {
+          kind: SchemaKind::Edge,
+          name: "AUTHORED".into(),
+        },
+        SchemaChange::AddProp {
+          kind: SchemaKind::Edge,
+          owner: "FOLLOWS".into(),
+          prop: "since".into(),
+          prop_type: PropType::Int,
+        },
+        SchemaChange::AddType {
+          kind: SchemaKind::Edge,
+          name: "LIKES".into(),
+        },
+      ]
+    );
   }
-}
 
-/// Executor for multiple node insert
-pub struct InsertExecutorMultiple<'a> {
-  ray: &'a mut Kite,
-  node_type: String,
-  entries: Vec<(String, HashMap<String, PropValue>)>,
-}
+  #[test]
+  fn test_node_by_id_infers_type_among_many_prefixes() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut options = KiteOptions::new();
+    for i in 0..40 {
+      options = options.node(NodeDef::new(&format!("Type{i}"), &format!("t{i}:")));
+    }
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
-impl<'a> InsertExecutorMultiple<'a> {
-  /// Execute the insert and return all created node references
-  pub fn returning(self) -> Result<Vec<NodeRef>> {
-    if self.entries.is_empty() {
-      return Ok(Vec::new());
+    for i in [0, 4, 39] {
+      let node = ray
+        .create_node(&format!("Type{i}"), "a", HashMap::new())
+        .expect("expected value");
+      let found = ray
+        .node_by_id(node.id)
+        .expect("expected value")
+        .expect("expected value");
+      assert_eq!(found.node_type(), format!("Type{i}"));
     }
 
-    let mut handle = begin_tx(&self.ray.db)?;
-    let mut results = Vec::with_capacity(self.entries.len());
-    let node_type: Arc<str> = self.node_type.into();
+    ray.raw().begin(false).expect("expected value");
+    let stray = ray.raw().create_node(Some("t4")).expect("expected value");
+    ray.raw().commit().expect("expected value");
+    let found = ray
+      .node_by_id(stray)
+      .expect("expected value")
+      .expect("expected value");
+    assert_eq!(found.node_type(), "unknown");
+  }
 
-    for (full_key, props) in self.entries {
-      // Create the node
-      let node_opts = NodeOpts::new().with_key(full_key.clone());
-      let node_id = create_node(&mut handle, node_opts)?;
+  #[test]
+  fn test_open_database_primary_replication_options() {
+    let temp_dir = tempdir().expect("expected value");
+    let sidecar_path = temp_dir.path().join("replication-sidecar-custom");
+    let options = create_test_schema()
+      .replication_role(ReplicationRole::Primary)
+      .replication_sidecar_path(&sidecar_path)
+      .replication_segment_max_bytes(1024)
+      .replication_retention_min_entries(2);
 
-      // Set properties
-      for (prop_name, value) in props {
-        let prop_key_id = self.ray.db.propkey_id_or_create(&prop_name);
-        set_node_prop(&mut handle, node_id, prop_key_id, value)?;
-      }
+    let ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+    let primary = ray.raw().primary_replication_status();
+    let replica = ray.raw().replica_replication_status();
 
-      results.push(NodeRef::new(
-        node_id,
-        Some(full_key),
-        Arc::clone(&node_type),
-      ));
-    }
+    assert!(primary.is_some());
+    assert!(replica.is_none());
+    let status = primary.expect("expected primary status");
+    assert_eq!(status.role, ReplicationRole::Primary);
+    assert_eq!(status.sidecar_path, sidecar_path);
+  }
 
-    commit(&mut handle)?;
+  #[test]
+  fn test_create_and_find_node() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = create_test_schema();
 
-    Ok(results)
-  }
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
-  /// Execute the insert without returning node references
-  pub fn execute(self) -> Result<()> {
-    let _ = self.returning()?;
-    Ok(())
-  }
-}
+    // Create a user
+    let mut props = HashMap::new();
+    props.insert("name".to_string(), PropValue::String("Alice".to_string()));
+    props.insert("age".to_string(), PropValue::I64(30));
 
-// ============================================================================
-// Upsert Builder
-// ============================================================================
+    let user_ref = ray
+      .create_node("User", "alice", props)
+      .expect("expected value");
+    assert!(user_ref.id > 0);
+    assert_eq!(user_ref.key, Some("user:alice".to_string()));
 
-/// Fluent builder for upserting nodes
-///
-/// Created via `kite.upsert(node_type)` and provides a fluent API for
-/// creating or updating nodes with the `.values().returning()` or
-/// `.values().execute()` pattern.
-pub struct KiteUpsertBuilder<'a> {
-  ray: &'a mut Kite,
-  node_type: String,
-  key_prefix: String,
-}
+    // Get the user
+    let found = ray.get("User", "alice").expect("expected value");
+    assert!(found.is_some());
+    assert_eq!(found.expect("expected value").id, user_ref.id);
 
-impl<'a> KiteUpsertBuilder<'a> {
-  /// Specify the values for a single upsert
-  pub fn values(
-    self,
-    key_suffix: &str,
-    props: HashMap<String, PropValue>,
-  ) -> Result<UpsertExecutorSingle<'a>> {
-    let full_key = format!("{}{}", self.key_prefix, key_suffix);
-    Ok(UpsertExecutorSingle {
-      ray: self.ray,
-      node_type: self.node_type,
-      full_key,
-      props,
-    })
+    // Non-existent user
+    let not_found = ray.get("User", "bob").expect("expected value");
+    assert!(not_found.is_none());
+
+    ray.close().expect("expected value");
   }
 
-  /// Specify values for multiple upserts
-  pub fn values_many(
-    self,
-    items: Vec<(&str, HashMap<String, PropValue>)>,
-  ) -> Result<UpsertExecutorMultiple<'a>> {
-    let entries: Vec<(String, HashMap<String, PropValue>)> = items
-      .into_iter()
-      .map(|(key_suffix, props)| {
-        let full_key = format!("{}{}", self.key_prefix, key_suffix);
-        (full_key, props)
-      })
-      .collect();
+  #[test]
+  fn test_prop_units_are_reported_and_checked() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = KiteOptions::new().node(
+      NodeDef::new("Sample", "sample:")
+        .prop(PropDef::float("depth").unit("meters"))
+        .prop(PropDef::string("site")),
+    );
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
-    Ok(UpsertExecutorMultiple {
-      ray: self.ray,
-      node_type: self.node_type,
-      entries,
-    })
+    let units = ray.prop_units("Sample").expect("expected value");
+    assert_eq!(
+      units,
+      HashMap::from([("depth".to_string(), "meters".to_string())])
+    );
+    assert!(ray.prop_units("Missing").is_err());
+
+    let declared = |prop: &str, unit: &str| HashMap::from([(prop.to_string(), unit.to_string())]);
+    ray
+      .check_units("Sample", &declared("depth", "meters"))
+      .expect("expected value");
+    let err = ray
+      .check_units("Sample", &declared("depth", "feet"))
+      .expect_err("expected unit mismatch");
+    assert!(matches!(err, KiteError::Constraint(_)));
+    let err = ray
+      .check_units("Sample", &declared("site", "meters"))
+      .expect_err("expected unitless prop");
+    assert!(matches!(err, KiteError::Constraint(_)));
+
+    let node = ray
+      .create_node("Sample", "s1", HashMap::new())
+      .expect("expected value");
+    ray
+      .check_node_units(node.id, &declared("depth", "meters"))
+      .expect("expected value");
+    assert!(ray
+      .check_node_units(node.id, &declared("depth", "feet"))
+      .is_err());
+
+    ray.close().expect("expected value");
   }
 
-  /// Specify values for multiple upserts with owned key suffixes
-  pub fn values_many_owned(
-    self,
-    items: Vec<(String, HashMap<String, PropValue>)>,
-  ) -> Result<UpsertExecutorMultiple<'a>> {
-    let entries: Vec<(String, HashMap<String, PropValue>)> = items
-      .into_iter()
-      .map(|(key_suffix, props)| {
-        let full_key = format!("{}{}", self.key_prefix, key_suffix);
-        (full_key, props)
-      })
-      .collect();
+  #[test]
+  fn test_distinct_count_exact_and_approximate() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+
+    for (key, age) in [
+      ("a", Some(30)),
+      ("b", Some(30)),
+      ("c", Some(41)),
+      ("d", None),
+    ] {
+      let mut props = HashMap::new();
+      props.insert("name".to_string(), PropValue::String(key.into()));
+      if let Some(age) = age {
+        props.insert("age".to_string(), PropValue::I64(age));
+      }
+      ray.create_node("User", key, props).expect("expected value");
+    }
+
+    assert_eq!(
+      ray
+        .distinct_count("User", "age", false)
+        .expect("expected value"),
+      2
+    );
+    assert_eq!(
+      ray
+        .distinct_count("User", "age", true)
+        .expect("expected value"),
+      2
+    );
+    assert_eq!(
+      ray
+        .distinct_count("User", "name", false)
+        .expect("expected value"),
+      4
+    );
+    assert_eq!(
+      ray
+        .distinct_count("Post", "title", false)
+        .expect("expected value"),
+      0
+    );
+    assert_eq!(
+      ray
+        .distinct_count("User", "missing", false)
+        .expect("expected value"),
+      0
+    );
+    assert!(ray.distinct_count("Nope", "age", false).is_err());
 
-    Ok(UpsertExecutorMultiple {
-      ray: self.ray,
-      node_type: self.node_type,
-      entries,
-    })
+    ray.close().expect("expected value");
   }
-}
 
-/// Executor for single node upsert
-pub struct UpsertExecutorSingle<'a> {
-  ray: &'a mut Kite,
-  node_type: String,
-  full_key: String,
-  props: HashMap<String, PropValue>,
-}
+  #[test]
+  fn test_enum_prop_rejects_values_outside_allowed_set() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = KiteOptions::new().node(
+      NodeDef::new("Account", "account:")
+        .prop(PropDef::string_enum("status", ["active", "inactive"]).required()),
+    );
 
-impl<'a> UpsertExecutorSingle<'a> {
-  /// Execute the upsert and return the node reference
-  pub fn returning(self) -> Result<NodeRef> {
-    let node_type: Arc<str> = self.node_type.into();
-    let mut handle = begin_tx(&self.ray.db)?;
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
-    let mut updates = Vec::with_capacity(self.props.len());
-    for (prop_name, value) in self.props {
-      let prop_key_id = self.ray.db.propkey_id_or_create(&prop_name);
-      let value_opt = match value {
-        PropValue::Null => None,
-        other => Some(other),
-      };
-      updates.push((prop_key_id, value_opt));
-    }
+    let mut props = HashMap::new();
+    props.insert("status".to_string(), PropValue::String("activ".to_string()));
+    let err = ray
+      .create_node("Account", "a1", props)
+      .expect_err("expected constraint error");
+    assert!(matches!(err, KiteError::Constraint(_)));
+    assert!(ray.get("Account", "a1").expect("expected value").is_none());
 
-    let (node_id, _) = upsert_node_with_props(&mut handle, &self.full_key, updates)?;
+    let mut props = HashMap::new();
+    props.insert(
+      "status".to_string(),
+      PropValue::String("active".to_string()),
+    );
+    let account = ray
+      .create_node("Account", "a1", props)
+      .expect("expected value");
 
-    commit(&mut handle)?;
+    let err = ray
+      .set_prop(account.id, "status", PropValue::I64(1))
+      .expect_err("expected constraint error");
+    assert!(matches!(err, KiteError::Constraint(_)));
+    assert_eq!(
+      ray.prop(account.id, "status"),
+      Some(PropValue::String("active".to_string()))
+    );
 
-    Ok(NodeRef::new(node_id, Some(self.full_key), node_type))
-  }
+    ray
+      .set_prop(
+        account.id,
+        "status",
+        PropValue::String("inactive".to_string()),
+      )
+      .expect("expected value");
+    assert_eq!(
+      ray.prop(account.id, "status"),
+      Some(PropValue::String("inactive".to_string()))
+    );
 
-  /// Execute the upsert without returning the node reference
-  pub fn execute(self) -> Result<()> {
-    let _ = self.returning()?;
-    Ok(())
-  }
-}
+    // Stored as the value's interned id
+    let status = ray.raw().propkey_id("status").expect("expected value");
+    assert_eq!(
+      ray.raw().prop_enum_values(status),
+      Some(vec!["active".to_string(), "inactive".to_string()])
+    );
+    assert_eq!(
+      ray
+        .raw()
+        .delta
+        .read()
+        .node_prop(account.id, status)
+        .flatten(),
+      Some(&PropValue::I64(1))
+    );
 
-/// Executor for multiple node upserts
-pub struct UpsertExecutorMultiple<'a> {
-  ray: &'a mut Kite,
-  node_type: String,
-  entries: Vec<(String, HashMap<String, PropValue>)>,
-}
+    ray.close().expect("expected value");
+  }
 
-impl<'a> UpsertExecutorMultiple<'a> {
-  /// Execute the upserts and return node references
-  pub fn returning(self) -> Result<Vec<NodeRef>> {
-    if self.entries.is_empty() {
-      return Ok(Vec::new());
-    }
+  #[test]
+  fn test_no_self_loops_rejects_only_flagged_edge_types() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = KiteOptions::new()
+      .node(NodeDef::new("User", "user:"))
+      .edge(EdgeDef::new("FOLLOWS").no_self_loops())
+      .edge(EdgeDef::new("LIKES"));
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
-    let mut handle = begin_tx(&self.ray.db)?;
-    let mut results = Vec::with_capacity(self.entries.len());
-    let node_type: Arc<str> = self.node_type.into();
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
 
-    for (full_key, props) in self.entries {
-      let mut updates = Vec::with_capacity(props.len());
-      for (prop_name, value) in props {
-        let prop_key_id = self.ray.db.propkey_id_or_create(&prop_name);
-        let value_opt = match value {
-          PropValue::Null => None,
-          other => Some(other),
-        };
-        updates.push((prop_key_id, value_opt));
-      }
+    let err = ray
+      .link(alice.id, "FOLLOWS", alice.id)
+      .expect_err("expected constraint error");
+    assert!(matches!(err, KiteError::Constraint(_)));
+    assert!(!ray
+      .has_edge(alice.id, "FOLLOWS", alice.id)
+      .expect("expected value"));
 
-      let (node_id, _) = upsert_node_with_props(&mut handle, &full_key, updates)?;
-      results.push(NodeRef::new(
-        node_id,
-        Some(full_key),
-        Arc::clone(&node_type),
-      ));
-    }
+    ray
+      .link(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value");
+    ray
+      .link(alice.id, "LIKES", alice.id)
+      .expect("expected value");
 
-    commit(&mut handle)?;
+    // The raw database handle enforces the same rule
+    let follows = ray.raw().etype_id("FOLLOWS").expect("expected value");
+    ray.raw().begin(false).expect("expected value");
+    assert!(matches!(
+      ray.raw().add_edge(bob.id, follows, bob.id),
+      Err(KiteError::Constraint(_))
+    ));
+    ray.raw().rollback().expect("expected value");
 
-    Ok(results)
+    ray.close().expect("expected value");
   }
 
-  /// Execute the upserts without returning node references
-  pub fn execute(self) -> Result<()> {
-    let _ = self.returning()?;
-    Ok(())
-  }
-}
+  #[test]
+  fn test_set_prop_bulk_tags_existing_nodes() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = KiteOptions::new().node(NodeDef::new("User", "user:"));
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
-// ============================================================================
-// Update Edge Builder
-// ============================================================================
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
+    let missing = bob.id + 100;
 
-/// Fluent builder for updating edge properties
-///
-/// Created via `kite.update_edge(src, edge_type, dst)` and allows chaining
-/// multiple property set/unset operations before executing in a single transaction.
-///
-/// # Example
-/// ```rust,no_run
-/// # use kitedb::api::kite::Kite;
-/// # use kitedb::types::{NodeId, PropValue};
-/// # fn main() -> kitedb::error::Result<()> {
-/// # let mut kite: Kite = unimplemented!();
-/// # let alice_id: NodeId = 1;
-/// # let bob_id: NodeId = 2;
-/// kite.update_edge(alice_id, "FOLLOWS", bob_id)?
-///    .set("weight", PropValue::F64(0.9))
-///    .set("since", PropValue::String("2024".to_string()))
-///    .unset("deprecated_field")
-///    .execute()?;
-/// # Ok(())
-/// # }
-/// ```
-pub struct KiteUpdateEdgeBuilder<'a> {
-  ray: &'a mut Kite,
-  src: NodeId,
-  etype_id: ETypeId,
-  dst: NodeId,
-  updates: HashMap<String, Option<PropValue>>,
-}
+    let updated = ray
+      .set_prop_bulk(
+        &[alice.id, bob.id, alice.id, missing],
+        "imported_batch",
+        PropValue::I64(42),
+        false,
+      )
+      .expect("expected value");
+    assert_eq!(updated, 2);
+    assert_eq!(ray.prop(bob.id, "imported_batch"), Some(PropValue::I64(42)));
 
-impl<'a> KiteUpdateEdgeBuilder<'a> {
-  /// Set an edge property value
-  ///
-  /// The property will be set when `execute()` is called.
-  pub fn set(mut self, prop_name: impl Into<String>, value: PropValue) -> Self {
-    self.updates.insert(prop_name.into(), Some(value));
-    self
+    let strict = ray.set_prop_bulk(
+      &[alice.id, missing],
+      "imported_batch",
+      PropValue::I64(7),
+      true,
+    );
+    assert!(matches!(strict, Err(KiteError::NodeNotFound(id)) if id == missing));
+    assert_eq!(
+      ray.prop(alice.id, "imported_batch"),
+      Some(PropValue::I64(42))
+    );
   }
 
-  /// Remove an edge property
-  ///
-  /// The property will be deleted when `execute()` is called.
-  pub fn unset(mut self, prop_name: impl Into<String>) -> Self {
-    self.updates.insert(prop_name.into(), None);
-    self
-  }
+  #[test]
+  fn test_props_by_key_skip_id_lookup() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = KiteOptions::new().node(NodeDef::new("User", "user:"));
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
-  /// Set multiple properties at once from a HashMap
-  ///
-  /// Convenience method for setting multiple properties.
-  pub fn set_all(mut self, props: HashMap<String, PropValue>) -> Self {
-    for (k, v) in props {
-      self.updates.insert(k, Some(v));
-    }
-    self
+    ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    ray
+      .set_prop_by_key("User", "alice", "age", PropValue::I64(30))
+      .expect("expected value");
+    assert_eq!(
+      ray
+        .prop_by_key("User", "alice", "age")
+        .expect("expected value"),
+      Some(PropValue::I64(30))
+    );
+
+    ray
+      .del_prop_by_key("User", "alice", "age")
+      .expect("expected value");
+    assert_eq!(
+      ray
+        .prop_by_key("User", "alice", "age")
+        .expect("expected value"),
+      None
+    );
+
+    let missing = ray.set_prop_by_key("User", "bob", "age", PropValue::I64(1));
+    assert!(matches!(missing, Err(KiteError::KeyNotFound(ref key)) if key == "user:bob"));
+    assert!(ray.prop_by_key("Ghost", "alice", "age").is_err());
   }
 
-  /// Execute the update, applying all property changes in a single transaction
-  pub fn execute(self) -> Result<()> {
-    if self.updates.is_empty() {
-      return Ok(());
-    }
+  #[test]
+  fn test_merge_nodes_moves_edges_props_and_labels() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = KiteOptions::new()
+      .node(
+        NodeDef::new("User", "user:")
+          .prop(PropDef::string("name"))
+          .prop(PropDef::string("email").unique()),
+      )
+      .edge(EdgeDef::new("FOLLOWS").no_self_loops())
+      .edge(EdgeDef::new("LIKES").prop(PropDef::int("weight")));
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+
+    let mut props = HashMap::new();
+    props.insert("name".to_string(), PropValue::String("Alice".into()));
+    let alice = ray
+      .create_node("User", "alice", props)
+      .expect("expected value");
+    let mut props = HashMap::new();
+    props.insert("name".to_string(), PropValue::String("Alice B.".into()));
+    props.insert(
+      "email".to_string(),
+      PropValue::String("alice@example.com".into()),
+    );
+    let dupe = ray
+      .create_node("User", "alice-b", props)
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
+
+    ray
+      .link(bob.id, "FOLLOWS", dupe.id)
+      .expect("expected value");
+    ray
+      .link(dupe.id, "FOLLOWS", alice.id)
+      .expect("expected value");
+    ray.link(dupe.id, "LIKES", dupe.id).expect("expected value");
+    let mut edge_props = HashMap::new();
+    edge_props.insert("weight".to_string(), PropValue::I64(2));
+    ray
+      .link_with_props(dupe.id, "LIKES", bob.id, edge_props)
+      .expect("expected value");
+    let vip = ray.raw().label_id_or_create("vip");
+    ray.raw().begin(false).expect("expected value");
+    ray
+      .raw()
+      .add_node_label(dupe.id, vip)
+      .expect("expected value");
+    ray.raw().commit().expect("expected value");
+
+    let result = ray
+      .merge_nodes(alice.id, dupe.id, MergePropStrategy::KeepWins)
+      .expect("expected value");
+    assert_eq!(
+      result,
+      MergeResult {
+        edges_moved: 3,
+        self_loops_dropped: 1,
+        props_merged: 1,
+      }
+    );
+
+    assert!(!ray.exists(dupe.id));
+    assert!(ray
+      .has_edge(bob.id, "FOLLOWS", alice.id)
+      .expect("expected value"));
+    assert!(!ray
+      .has_edge(alice.id, "FOLLOWS", alice.id)
+      .expect("expected value"));
+    assert!(ray
+      .has_edge(alice.id, "LIKES", alice.id)
+      .expect("expected value"));
+    assert_eq!(
+      ray
+        .edge_prop(alice.id, "LIKES", bob.id, "weight")
+        .expect("expected value"),
+      Some(PropValue::I64(2))
+    );
+    assert_eq!(
+      ray.prop(alice.id, "name"),
+      Some(PropValue::String("Alice".into()))
+    );
+    assert_eq!(
+      ray.prop(alice.id, "email"),
+      Some(PropValue::String("alice@example.com".into()))
+    );
+    assert!(ray.raw().node_has_label(alice.id, vip));
+
+    // The moved unique value now belongs to the surviving node
+    let mut props = HashMap::new();
+    props.insert(
+      "email".to_string(),
+      PropValue::String("alice@example.com".into()),
+    );
+    assert!(matches!(
+      ray.create_node("User", "carol", props),
+      Err(KiteError::Constraint(_))
+    ));
 
-    let mut handle = begin_tx(&self.ray.db)?;
+    ray.close().expect("expected value");
+  }
 
-    for (prop_name, value_opt) in self.updates {
-      let prop_key_id = self.ray.db.propkey_id_or_create(&prop_name);
+  #[test]
+  fn test_merge_archived_node_keeps_survivor_live() {
+    let temp_dir = tempdir().expect("expected value");
+    let options =
+      KiteOptions::new().node(NodeDef::new("User", "user:").prop(PropDef::string("name")));
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
-      match value_opt {
-        Some(value) => {
-          set_edge_prop(
-            &mut handle,
-            self.src,
-            self.etype_id,
-            self.dst,
-            prop_key_id,
-            value,
-          )?;
-        }
-        None => {
-          // Only delete if prop_key exists
-          if let Some(existing_key_id) = self.ray.db.propkey_id(&prop_name) {
-            del_edge_prop(
-              &mut handle,
-              self.src,
-              self.etype_id,
-              self.dst,
-              existing_key_id,
-            )?;
-          }
-        }
-      }
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    for (key, strategy) in [
+      ("alice-b", MergePropStrategy::RemoveWins),
+      ("alice-c", MergePropStrategy::KeepWins),
+    ] {
+      let mut props = HashMap::new();
+      props.insert("name".to_string(), PropValue::String("Alice".into()));
+      let dupe = ray.create_node("User", key, props).expect("expected value");
+      assert!(ray.archive(dupe.id).expect("expected value"));
+
+      ray
+        .merge_nodes(alice.id, dupe.id, strategy)
+        .expect("expected value");
+      assert!(!ray.is_archived(alice.id));
+      assert!(ray.get("User", "alice").expect("expected value").is_some());
     }
+    assert_eq!(
+      ray.prop(alice.id, "name"),
+      Some(PropValue::String("Alice".into()))
+    );
 
-    commit(&mut handle)?;
-    Ok(())
+    ray.close().expect("expected value");
   }
-}
 
-// ============================================================================
-// Upsert Edge Builder
-// ============================================================================
+  #[test]
+  fn test_link_and_unlink() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = create_test_schema();
 
-/// Fluent builder for upserting edge properties
-///
-/// Created via `kite.upsert_edge(src, edge_type, dst)` and allows chaining
-/// property set/unset operations before executing in a single transaction.
-pub struct KiteUpsertEdgeBuilder<'a> {
-  ray: &'a mut Kite,
-  src: NodeId,
-  etype_id: ETypeId,
-  dst: NodeId,
-  updates: HashMap<String, Option<PropValue>>,
-}
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
-impl<'a> KiteUpsertEdgeBuilder<'a> {
-  /// Set an edge property value
-  pub fn set(mut self, prop_name: impl Into<String>, value: PropValue) -> Self {
-    self.updates.insert(prop_name.into(), Some(value));
-    self
-  }
+    // Create two users
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
 
-  /// Remove an edge property
-  pub fn unset(mut self, prop_name: impl Into<String>) -> Self {
-    self.updates.insert(prop_name.into(), None);
-    self
-  }
+    // Link them
+    ray
+      .link(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value");
 
-  /// Set multiple properties at once from a HashMap
-  pub fn set_all(mut self, props: HashMap<String, PropValue>) -> Self {
-    for (k, v) in props {
-      self.updates.insert(k, Some(v));
-    }
-    self
-  }
+    // Check edge exists
+    assert!(ray
+      .has_edge(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value"));
+    assert!(!ray
+      .has_edge(bob.id, "FOLLOWS", alice.id)
+      .expect("expected value"));
 
-  /// Execute the upsert, creating the edge if missing
-  pub fn execute(self) -> Result<()> {
-    let mut handle = begin_tx(&self.ray.db)?;
+    // Check neighbors
+    let alice_follows = ray
+      .neighbors_out(alice.id, Some("FOLLOWS"))
+      .expect("expected value");
+    assert_eq!(alice_follows, vec![bob.id]);
 
-    let mut updates = Vec::with_capacity(self.updates.len());
-    for (prop_name, value_opt) in self.updates {
-      let prop_key_id = self.ray.db.propkey_id_or_create(&prop_name);
-      updates.push((prop_key_id, value_opt));
-    }
+    let bob_followers = ray
+      .neighbors_in(bob.id, Some("FOLLOWS"))
+      .expect("expected value");
+    assert_eq!(bob_followers, vec![alice.id]);
 
-    upsert_edge_with_props(&mut handle, self.src, self.etype_id, self.dst, updates)?;
+    // Unlink
+    ray
+      .unlink(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value");
+    assert!(!ray
+      .has_edge(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value"));
 
-    commit(&mut handle)?;
-    Ok(())
+    ray.close().expect("expected value");
   }
-}
-
-// ============================================================================
-// Tests
-// ============================================================================
 
-#[cfg(test)]
-mod tests {
-  use super::*;
-  use tempfile::tempdir;
+  #[test]
+  fn test_properties() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = create_test_schema();
 
-  fn temp_db_path(temp_dir: &tempfile::TempDir) -> std::path::PathBuf {
-    temp_dir.path().join("test-db")
-  }
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
-  fn create_test_schema() -> KiteOptions {
-    let user = NodeDef::new("User", "user:")
-      .prop(PropDef::string("name").required())
-      .prop(PropDef::int("age"));
+    // Create a user
+    let mut props = HashMap::new();
+    props.insert("name".to_string(), PropValue::String("Alice".to_string()));
+    let user = ray
+      .create_node("User", "alice", props)
+      .expect("expected value");
 
-    let post = NodeDef::new("Post", "post:")
-      .prop(PropDef::string("title").required())
-      .prop(PropDef::string("content"));
+    // Get property
+    let name = ray.prop(user.id, "name");
+    assert_eq!(name, Some(PropValue::String("Alice".to_string())));
 
-    let follows = EdgeDef::new("FOLLOWS");
-    let authored = EdgeDef::new("AUTHORED");
+    // Set property
+    ray
+      .set_prop(user.id, "age", PropValue::I64(25))
+      .expect("expected value");
+    let age = ray.prop(user.id, "age");
+    assert_eq!(age, Some(PropValue::I64(25)));
 
-    KiteOptions::new()
-      .node(user)
-      .node(post)
-      .edge(follows)
-      .edge(authored)
+    ray.close().expect("expected value");
   }
 
   #[test]
-  fn test_recommended_kite_options_profiles() {
-    let safe = KiteOptions::recommended_safe();
-    assert_eq!(safe.sync_mode, SyncMode::Full);
-    assert!(!safe.group_commit_enabled);
-    assert_eq!(safe.close_checkpoint_if_wal_usage_at_least, Some(0.2));
+  fn test_has_prop_and_prop_count() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    let props = HashMap::from([("name".to_string(), PropValue::String("Alice".into()))]);
+    let user = ray
+      .create_node("User", "alice", props)
+      .expect("expected value");
 
-    let balanced = KiteOptions::recommended_balanced();
-    assert_eq!(balanced.sync_mode, SyncMode::Normal);
-    assert!(balanced.group_commit_enabled);
-    assert_eq!(balanced.group_commit_window_ms, 2);
-    assert_eq!(balanced.wal_size, Some(64 * 1024 * 1024));
-    assert_eq!(balanced.checkpoint_threshold, Some(0.5));
-    assert_eq!(balanced.close_checkpoint_if_wal_usage_at_least, Some(0.2));
+    assert!(ray.has_prop(user.id, "name"));
+    assert!(!ray.has_prop(user.id, "age"));
+    assert!(!ray.has_prop(user.id, "never_defined"));
+    assert_eq!(ray.prop_count(user.id), 1);
 
-    let reopen = KiteOptions::recommended_reopen_heavy();
-    assert_eq!(reopen.sync_mode, SyncMode::Normal);
-    assert!(reopen.group_commit_enabled);
-    assert_eq!(reopen.wal_size, Some(16 * 1024 * 1024));
-    assert_eq!(reopen.checkpoint_threshold, Some(0.2));
-    assert_eq!(reopen.close_checkpoint_if_wal_usage_at_least, Some(0.2));
+    ray
+      .set_prop(user.id, "age", PropValue::I64(30))
+      .expect("expected value");
+    assert!(ray.has_prop(user.id, "age"));
+    assert_eq!(ray.prop_count(user.id), 2);
+
+    ray
+      .transaction(|ctx| ctx.del_prop(user.id, "name"))
+      .expect("expected value");
+    assert!(!ray.has_prop(user.id, "name"));
+    assert_eq!(ray.prop_count(user.id), 1);
+    assert_eq!(ray.prop_count(9999), 0);
+    ray.close().expect("expected value");
   }
 
   #[test]
-  fn test_runtime_profile_reopen_heavy_has_close_threshold() {
-    let profile = KiteRuntimeProfile::from_kind(KiteRuntimeProfileKind::ReopenHeavy);
-    assert_eq!(profile.options.wal_size, Some(16 * 1024 * 1024));
-    assert_eq!(profile.close_checkpoint_if_wal_usage_at_least, Some(0.2));
+  fn test_node_version_and_get_if_changed() {
+    let temp_dir = tempdir().expect("expected value");
+    let path = temp_db_path(&temp_dir);
+    let mut ray = Kite::open(&path, create_test_schema()).expect("expected value");
+    let props = HashMap::from([("name".to_string(), PropValue::String("Alice".into()))]);
+    let user = ray
+      .create_node("User", "alice", props)
+      .expect("expected value");
+
+    let v1 = ray.node_version(user.id).expect("expected value");
+    assert_eq!(ray.node_version(user.id), Some(v1.clone()));
+    assert!(matches!(
+      ray.get_if_changed(user.id, &v1).expect("expected value"),
+      NodeIfChanged::NotModified
+    ));
+
+    ray
+      .set_prop(user.id, "age", PropValue::I64(30))
+      .expect("expected value");
+    let v2 = ray.node_version(user.id).expect("expected value");
+    assert_ne!(v1, v2);
+    match ray.get_if_changed(user.id, &v1).expect("expected value") {
+      NodeIfChanged::Modified { node, version } => {
+        assert_eq!(node.id, user.id);
+        assert_eq!(version, v2);
+      }
+      other => panic!("expected Modified, got {other:?}"),
+    }
+    ray.close().expect("expected value");
+
+    let mut ray = Kite::open(&path, create_test_schema()).expect("expected value");
+    assert_eq!(ray.node_version(user.id), Some(v2.clone()));
+    ray.delete_node(user.id).expect("expected value");
+    assert_eq!(ray.node_version(user.id), None);
+    assert!(matches!(
+      ray.get_if_changed(user.id, &v2).expect("expected value"),
+      NodeIfChanged::NotFound
+    ));
+    ray.close().expect("expected value");
   }
 
   #[test]
-  fn test_kite_options_close_checkpoint_threshold_configurable() {
-    let options = KiteOptions::new()
-      .close_checkpoint_if_wal_usage_at_least(0.35)
-      .disable_close_checkpoint();
-    assert_eq!(options.close_checkpoint_if_wal_usage_at_least, None);
+  fn test_typed_prop_getters_reject_wrong_type() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+
+    let mut props = HashMap::new();
+    props.insert("name".to_string(), PropValue::String("Alice".to_string()));
+    props.insert("age".to_string(), PropValue::I64(30));
+    let user = ray
+      .create_node("User", "alice", props)
+      .expect("expected value");
+    ray
+      .set_props(
+        user.id,
+        [
+          ("score", PropValue::F64(1.5)),
+          ("active", PropValue::Bool(true)),
+          ("nickname", PropValue::Null),
+        ],
+      )
+      .expect("expected value");
+
+    assert_eq!(
+      ray.prop_int(user.id, "age").expect("expected value"),
+      Some(30)
+    );
+    assert_eq!(
+      ray.prop_float(user.id, "score").expect("expected value"),
+      Some(1.5)
+    );
+    assert_eq!(
+      ray.prop_string(user.id, "name").expect("expected value"),
+      Some("Alice".to_string())
+    );
+    assert_eq!(
+      ray.prop_bool(user.id, "active").expect("expected value"),
+      Some(true)
+    );
+    // Absent and null are not type errors
+    assert_eq!(
+      ray.prop_int(user.id, "missing").expect("expected value"),
+      None
+    );
+    assert_eq!(
+      ray
+        .prop_string(user.id, "nickname")
+        .expect("expected value"),
+      None
+    );
 
-    let clamped = KiteOptions::new().close_checkpoint_if_wal_usage_at_least(1.5);
-    assert_eq!(clamped.close_checkpoint_if_wal_usage_at_least, Some(1.0));
+    assert!(matches!(
+      ray.prop_int(user.id, "score"),
+      Err(KiteError::PropTypeMismatch {
+        expected: PropValueTag::I64,
+        got: PropValueTag::F64,
+        ..
+      })
+    ));
+    assert!(ray.prop_string(user.id, "age").is_err());
+
+    ray.close().expect("expected value");
   }
 
   #[test]
-  fn test_open_database() {
+  fn test_count_nodes() {
     let temp_dir = tempdir().expect("expected value");
     let options = create_test_schema();
 
-    let ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
-    assert_eq!(ray.node_types().len(), 2);
-    assert_eq!(ray.edge_types().len(), 2);
-    assert!(ray.node_def("User").is_some());
-    assert!(ray.edge_def("FOLLOWS").is_some());
+    assert_eq!(ray.count_nodes(), 0);
+
+    ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
+    ray
+      .create_node("Post", "post1", HashMap::new())
+      .expect("expected value");
+
+    assert_eq!(ray.count_nodes(), 3);
 
     ray.close().expect("expected value");
   }
 
   #[test]
-  fn test_open_database_primary_replication_options() {
+  fn test_delete_node() {
     let temp_dir = tempdir().expect("expected value");
-    let sidecar_path = temp_dir.path().join("replication-sidecar-custom");
-    let options = create_test_schema()
-      .replication_role(ReplicationRole::Primary)
-      .replication_sidecar_path(&sidecar_path)
-      .replication_segment_max_bytes(1024)
-      .replication_retention_min_entries(2);
+    let options = create_test_schema();
 
-    let ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
-    let primary = ray.raw().primary_replication_status();
-    let replica = ray.raw().replica_replication_status();
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
-    assert!(primary.is_some());
-    assert!(replica.is_none());
-    let status = primary.expect("expected primary status");
-    assert_eq!(status.role, ReplicationRole::Primary);
-    assert_eq!(status.sidecar_path, sidecar_path);
+    let user = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    assert!(ray.exists(user.id));
+
+    ray.delete_node(user.id).expect("expected value");
+    assert!(!ray.exists(user.id));
+
+    ray.close().expect("expected value");
   }
 
   #[test]
-  fn test_create_and_find_node() {
+  fn test_ref() {
     let temp_dir = tempdir().expect("expected value");
     let options = create_test_schema();
 
     let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
     // Create a user
-    let mut props = HashMap::new();
-    props.insert("name".to_string(), PropValue::String("Alice".to_string()));
-    props.insert("age".to_string(), PropValue::I64(30));
-
-    let user_ref = ray
-      .create_node("User", "alice", props)
+    let user = ray
+      .create_node("User", "alice", HashMap::new())
       .expect("expected value");
-    assert!(user_ref.id > 0);
-    assert_eq!(user_ref.key, Some("user:alice".to_string()));
 
-    // Get the user
-    let found = ray.get("User", "alice").expect("expected value");
-    assert!(found.is_some());
-    assert_eq!(found.expect("expected value").id, user_ref.id);
+    // Get lightweight reference
+    let node_ref = ray.node_ref("User", "alice").expect("expected value");
+    assert!(node_ref.is_some());
+    let node_ref = node_ref.expect("expected value");
+    assert_eq!(node_ref.id(), user.id);
+    assert_eq!(node_ref.key(), Some("user:alice"));
 
     // Non-existent user
-    let not_found = ray.get("User", "bob").expect("expected value");
+    let not_found = ray.node_ref("User", "bob").expect("expected value");
     assert!(not_found.is_none());
 
     ray.close().expect("expected value");
   }
 
   #[test]
-  fn test_link_and_unlink() {
+  fn test_get_serves_hot_keys_from_key_cache() {
     let temp_dir = tempdir().expect("expected value");
-    let options = create_test_schema();
+    let options = create_test_schema().cache(Some(CacheOptions {
+      enabled: true,
+      ..Default::default()
+    }));
 
     let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
-
-    // Create two users
     let alice = ray
       .create_node("User", "alice", HashMap::new())
       .expect("expected value");
+
+    for _ in 0..100 {
+      let node = ray.get("User", "alice").expect("expected value");
+      assert_eq!(node.map(|n| n.id), Some(alice.id));
+    }
+    let stats = ray.cache_stats().expect("expected value");
+    assert_eq!(stats.key_cache_misses, 1);
+    assert_eq!(stats.key_cache_hits, 99);
+
+    // Negative lookups are cached too, and commits invalidate them
+    assert!(ray.get("User", "bob").expect("expected value").is_none());
+    assert!(ray.get("User", "bob").expect("expected value").is_none());
     let bob = ray
       .create_node("User", "bob", HashMap::new())
       .expect("expected value");
+    assert_eq!(
+      ray
+        .get("User", "bob")
+        .expect("expected value")
+        .map(|n| n.id),
+      Some(bob.id)
+    );
 
-    // Link them
-    ray
-      .link(alice.id, "FOLLOWS", bob.id)
-      .expect("expected value");
+    ray.delete_node(alice.id).expect("expected value");
+    assert!(ray.get("User", "alice").expect("expected value").is_none());
 
-    // Check edge exists
-    assert!(ray
-      .has_edge(alice.id, "FOLLOWS", bob.id)
-      .expect("expected value"));
-    assert!(!ray
-      .has_edge(bob.id, "FOLLOWS", alice.id)
-      .expect("expected value"));
+    ray.close().expect("expected value");
+  }
 
-    // Check neighbors
-    let alice_follows = ray
-      .neighbors_out(alice.id, Some("FOLLOWS"))
-      .expect("expected value");
-    assert_eq!(alice_follows, vec![bob.id]);
+  #[test]
+  fn test_json_props_round_trip_and_filter_by_path() {
+    let temp_dir = tempdir().expect("expected value");
+    let options =
+      create_test_schema().node(NodeDef::new("Doc", "doc:").prop(PropDef::json("meta")));
 
-    let bob_followers = ray
-      .neighbors_in(bob.id, Some("FOLLOWS"))
-      .expect("expected value");
-    assert_eq!(bob_followers, vec![alice.id]);
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options.clone()).expect("expected value");
+    let meta = serde_json::json!({"author": {"name": "ada"}, "tags": ["a", "b"]});
+    let mut props = HashMap::new();
+    props.insert("meta".to_string(), PropValue::Json(meta.clone()));
+    let first = ray.create_node("Doc", "1", props).expect("expected value");
+    let mut props = HashMap::new();
+    props.insert(
+      "meta".to_string(),
+      PropValue::Json(serde_json::json!({"author": {"name": "bob"}})),
+    );
+    ray.create_node("Doc", "2", props).expect("expected value");
 
-    // Unlink
-    ray
-      .unlink(alice.id, "FOLLOWS", bob.id)
-      .expect("expected value");
-    assert!(!ray
-      .has_edge(alice.id, "FOLLOWS", bob.id)
-      .expect("expected value"));
+    let by_path = |path: &str, value: serde_json::Value| PropFilter::JsonPathEq {
+      path: path.to_string(),
+      value,
+    };
+    let found = |ray: &Kite| {
+      ray
+        .find_where(
+          "Doc",
+          "meta",
+          &by_path("$.author.name", serde_json::json!("ada")),
+        )
+        .expect("expected value")
+    };
+    assert_eq!(found(&ray), vec![first.id]);
+    assert!(ray
+      .find_where("Doc", "meta", &by_path("tags.1", serde_json::json!("b")))
+      .expect("expected value")
+      .contains(&first.id));
+    assert_eq!(
+      ray
+        .find_where(
+          "Doc",
+          "meta",
+          &PropFilter::Eq(PropValue::Json(meta.clone()))
+        )
+        .expect("expected value"),
+      vec![first.id]
+    );
 
+    // Reopen (WAL replay), then checkpoint into a snapshot and reopen again
+    ray.close().expect("expected value");
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options.clone()).expect("expected value");
+    assert_eq!(
+      ray.prop(first.id, "meta"),
+      Some(PropValue::Json(meta.clone()))
+    );
+    ray.optimize().expect("expected value");
+    ray.close().expect("expected value");
+    let ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+    assert_eq!(ray.prop(first.id, "meta"), Some(PropValue::Json(meta)));
+    assert_eq!(found(&ray), vec![first.id]);
     ray.close().expect("expected value");
   }
 
   #[test]
-  fn test_properties() {
+  fn test_vector_props_reject_other_value_types() {
     let temp_dir = tempdir().expect("expected value");
-    let options = create_test_schema();
-
+    let options =
+      create_test_schema().node(NodeDef::new("Doc", "doc:").prop(PropDef::vector("embedding")));
     let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
-    // Create a user
     let mut props = HashMap::new();
-    props.insert("name".to_string(), PropValue::String("Alice".to_string()));
-    let user = ray
-      .create_node("User", "alice", props)
-      .expect("expected value");
+    props.insert(
+      "embedding".to_string(),
+      PropValue::VectorF32(vec![0.5, 0.25]),
+    );
+    let doc = ray.create_node("Doc", "1", props).expect("expected value");
+
+    // A plain array arrives as JSON and must not be stored silently
+    let err = ray
+      .set_prop(
+        doc.id,
+        "embedding",
+        PropValue::Json(serde_json::json!([0.5, 0.25])),
+      )
+      .expect_err("json array written to a vector prop");
+    assert!(matches!(err, KiteError::Constraint(_)));
+    assert_eq!(
+      ray.prop(doc.id, "embedding"),
+      Some(PropValue::VectorF32(vec![0.5, 0.25]))
+    );
+    ray.close().expect("expected value");
+  }
 
-    // Get property
-    let name = ray.prop(user.id, "name");
-    assert_eq!(name, Some(PropValue::String("Alice".to_string())));
+  #[test]
+  fn test_exploration_expands_only_new_neighbors() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    let mut ids = Vec::new();
+    for key in ["a", "b", "c"] {
+      let node = ray
+        .create_node("User", key, HashMap::new())
+        .expect("expected value");
+      ids.push(node.id);
+    }
+    let (a, b, c) = (ids[0], ids[1], ids[2]);
+    ray.link(a, "FOLLOWS", b).expect("expected value");
+    ray.link(a, "FOLLOWS", c).expect("expected value");
+    ray.link(b, "FOLLOWS", c).expect("expected value");
 
-    // Set property
-    ray
-      .set_prop(user.id, "age", PropValue::I64(25))
-      .expect("expected value");
-    let age = ray.prop(user.id, "age");
-    assert_eq!(age, Some(PropValue::I64(25)));
+    let mut session = ray.exploration(a).expect("expected value");
+    assert_eq!(
+      ray
+        .expand(&mut session, a, Some("FOLLOWS"), TraversalDirection::Out)
+        .expect("expected value"),
+      vec![b, c]
+    );
+    assert!(ray
+      .expand(&mut session, b, None, TraversalDirection::Both)
+      .expect("expected value")
+      .is_empty());
+    assert!(ray.exploration(9999).is_err());
+    ray.close().expect("expected value");
+  }
 
+  #[test]
+  fn test_reachable_cursor_returns_one_level_per_call() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    let mut ids = Vec::new();
+    for key in ["a", "b", "c", "d"] {
+      let node = ray
+        .create_node("User", key, HashMap::new())
+        .expect("expected value");
+      ids.push(node.id);
+    }
+    let (a, b, c, d) = (ids[0], ids[1], ids[2], ids[3]);
+    ray.link(a, "FOLLOWS", b).expect("expected value");
+    ray.link(a, "FOLLOWS", c).expect("expected value");
+    ray.link(b, "FOLLOWS", c).expect("expected value");
+    ray.link(c, "FOLLOWS", a).expect("expected value");
+    ray.link(a, "AUTHORED", d).expect("expected value");
+
+    let mut cursor = ray
+      .reachable_cursor(a, Some("FOLLOWS"))
+      .expect("expected value");
+    assert_eq!(ray.next_reachable_level(&mut cursor), vec![b, c]);
+    assert_eq!(cursor.depth(), 1);
+
+    // Writes between calls show up in later levels
+    ray.link(c, "FOLLOWS", d).expect("expected value");
+    assert_eq!(ray.next_reachable_level(&mut cursor), vec![d]);
+    assert!(ray.next_reachable_level(&mut cursor).is_empty());
+    assert!(cursor.is_done());
+    assert_eq!(cursor.depth(), 2);
+
+    assert!(ray.reachable_cursor(9999, None).is_err());
+    assert!(ray.reachable_cursor(a, Some("LIKES")).is_err());
     ray.close().expect("expected value");
   }
 
   #[test]
-  fn test_count_nodes() {
+  fn test_archive_hides_node_until_unarchived() {
     let temp_dir = tempdir().expect("expected value");
     let options = create_test_schema();
 
     let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
-
-    assert_eq!(ray.count_nodes(), 0);
-
-    ray
+    let alice = ray
       .create_node("User", "alice", HashMap::new())
       .expect("expected value");
-    ray
+    let bob = ray
       .create_node("User", "bob", HashMap::new())
       .expect("expected value");
     ray
-      .create_node("Post", "post1", HashMap::new())
+      .link(alice.id, "FOLLOWS", bob.id)
       .expect("expected value");
 
-    assert_eq!(ray.count_nodes(), 3);
+    assert!(ray.archive(bob.id).expect("expected value"));
+    assert!(!ray.archive(bob.id).expect("expected value"));
+    assert!(ray.is_archived(bob.id));
+
+    assert!(ray.get("User", "bob").expect("expected value").is_none());
+    assert!(ray
+      .get_including_archived("User", "bob")
+      .expect("expected value")
+      .is_some());
+    assert_eq!(ray.all("User").expect("expected value").count(), 1);
+    assert_eq!(
+      ray
+        .all_including_archived("User")
+        .expect("expected value")
+        .count(),
+      2
+    );
+    assert_eq!(ray.all_edges(None).expect("expected value").count(), 0);
+    assert!(!ray
+      .has_edge(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value"));
+    assert_eq!(
+      ray
+        .has_edges(&[(alice.id, "FOLLOWS".to_string(), bob.id)])
+        .expect("expected value"),
+      vec![false]
+    );
+    assert_eq!(ray.count_edges(), 0);
+    assert_eq!(
+      ray.count_edges_by_type("FOLLOWS").expect("expected value"),
+      0
+    );
+    assert!(ray
+      .from(alice.id)
+      .out(Some("FOLLOWS"))
+      .expect("expected value")
+      .to_vec()
+      .is_empty());
+    assert_eq!(
+      ray
+        .from(alice.id)
+        .out(Some("FOLLOWS"))
+        .expect("expected value")
+        .include_archived()
+        .to_vec(),
+      vec![bob.id]
+    );
+
+    // Not old enough to purge yet
+    assert_eq!(ray.purge_archived(60_000).expect("expected value"), 0);
+
+    assert!(ray.unarchive(bob.id).expect("expected value"));
+    assert!(ray.get("User", "bob").expect("expected value").is_some());
+    assert_eq!(ray.all_edges(None).expect("expected value").count(), 1);
+    assert!(ray
+      .has_edge(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value"));
+    assert_eq!(ray.count_edges(), 1);
+
+    // Edges between two archived nodes, and self-loops, are hidden once
+    let carol = ray
+      .create_node("User", "carol", HashMap::new())
+      .expect("expected value");
+    for (src, dst) in [
+      (bob.id, carol.id),
+      (carol.id, bob.id),
+      (carol.id, carol.id),
+      (carol.id, alice.id),
+    ] {
+      ray.link(src, "FOLLOWS", dst).expect("expected value");
+    }
+    assert_eq!(ray.count_edges(), 5);
+    ray.archive(carol.id).expect("expected value");
+    assert_eq!(ray.count_edges(), 1);
+    ray.archive(bob.id).expect("expected value");
+    assert_eq!(ray.count_edges(), 0);
+    ray.unarchive(carol.id).expect("expected value");
+    assert_eq!(
+      ray.count_edges_by_type("FOLLOWS").expect("expected value"),
+      2
+    );
+
+    assert_eq!(ray.purge_archived(0).expect("expected value"), 1);
+    assert!(!ray.exists(bob.id));
+    assert!(ray.exists(alice.id));
+    // Only carol's self-loop and her edge to alice are left
+    assert_eq!(ray.count_edges(), 2);
 
     ray.close().expect("expected value");
   }
 
   #[test]
-  fn test_delete_node() {
+  fn test_archived_node_in_the_middle_of_a_path_blocks_walks() {
     let temp_dir = tempdir().expect("expected value");
-    let options = create_test_schema();
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    let ids: Vec<NodeId> = ["a", "b", "c"]
+      .iter()
+      .map(|name| {
+        ray
+          .create_node("User", name, HashMap::new())
+          .expect("expected value")
+          .id
+      })
+      .collect();
+    let (a, b, c) = (ids[0], ids[1], ids[2]);
+    ray.link(a, "FOLLOWS", b).expect("expected value");
+    ray.link(b, "FOLLOWS", c).expect("expected value");
+    assert_eq!(
+      ray
+        .reachable_from(a, 5, None)
+        .expect("expected value")
+        .len(),
+      2
+    );
 
-    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+    ray.archive(b).expect("expected value");
 
-    let user = ray
-      .create_node("User", "alice", HashMap::new())
-      .expect("expected value");
-    assert!(ray.exists(user.id));
+    assert!(ray
+      .reachable_from(a, 5, None)
+      .expect("expected value")
+      .is_empty());
+    assert!(ray
+      .reachable_from_excluding(a, 5, None, &HashSet::new(), &HashSet::new())
+      .expect("expected value")
+      .is_empty());
+    let mut cursor = ray.reachable_cursor(a, None).expect("expected value");
+    assert!(ray.next_reachable_level(&mut cursor).is_empty());
+
+    assert!(!ray.shortest_path(a, c).find().found);
+    assert!(!ray.shortest_path(a, c).find_bfs().found);
+    assert!(ray.shortest_path(a, c).find_k_shortest(2).is_empty());
+    assert!(!ray.shortest_path(a, b).find().found);
+
+    assert!(ray
+      .neighbors_out(a, None)
+      .expect("expected value")
+      .is_empty());
+    assert!(ray
+      .neighbors_in(c, None)
+      .expect("expected value")
+      .is_empty());
+    assert_eq!(
+      ray
+        .degree(a, TraversalDirection::Out, None)
+        .expect("expected value"),
+      0
+    );
+    assert_eq!(
+      ray
+        .degrees(&[c], TraversalDirection::Both, None)
+        .expect("expected value")[&c],
+      0
+    );
+
+    ray.unarchive(b).expect("expected value");
+    assert!(ray.shortest_path(a, c).find().found);
+    assert_eq!(ray.neighbors_out(a, None).expect("expected value"), vec![b]);
+    ray.close().expect("expected value");
+  }
 
-    ray.delete_node(user.id).expect("expected value");
-    assert!(!ray.exists(user.id));
+  #[test]
+  fn test_indexed_lookups_skip_archived_nodes() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = KiteOptions::new().node(
+      NodeDef::new("Item", "item:")
+        .prop(PropDef::int("rank").ordered())
+        .prop(PropDef::string("sku").unique()),
+    );
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+    let mut props = HashMap::new();
+    props.insert("rank".to_string(), PropValue::I64(1));
+    props.insert("sku".to_string(), PropValue::String("s1".into()));
+    let item = ray.create_node("Item", "1", props).expect("expected value");
+    ray.archive(item.id).expect("expected value");
 
+    assert!(ray
+      .find_by_prop_range("Item", "rank", &PropValue::I64(0), &PropValue::I64(5))
+      .expect("expected value")
+      .is_empty());
+    for (prop_name, value) in [
+      ("rank", PropValue::I64(1)),
+      ("sku", PropValue::String("s1".into())),
+    ] {
+      assert!(ray
+        .find_where("Item", prop_name, &PropFilter::Eq(value))
+        .expect("expected value")
+        .is_empty());
+    }
     ray.close().expect("expected value");
   }
 
   #[test]
-  fn test_ref() {
+  fn test_archive_flag_is_not_a_user_prop() {
+    use crate::export::{export_to_object_single, import_from_object_single, ExportOptions};
+
     let temp_dir = tempdir().expect("expected value");
     let options = create_test_schema();
 
     let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
-
-    // Create a user
-    let user = ray
+    let alice = ray
       .create_node("User", "alice", HashMap::new())
       .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
+    ray
+      .set_prop(bob.id, "name", PropValue::String("Bob".into()))
+      .expect("expected value");
+    ray.archive(bob.id).expect("expected value");
+
+    assert_eq!(ray.prop(bob.id, ARCHIVED_AT_PROP), None);
+    assert!(!ray.has_prop(bob.id, ARCHIVED_AT_PROP));
+    assert_eq!(ray.prop_count(bob.id), 1);
+    assert_eq!(ray.count_nodes(), 1);
+    assert_eq!(ray.count_nodes_by_type("User").expect("expected value"), 1);
+    assert_eq!(ray.count_nodes_by_label("User"), 1);
+
+    let reserved = |result: Result<()>| matches!(result, Err(KiteError::InvalidSchema(_)));
+    assert!(reserved(ray.set_prop(
+      alice.id,
+      ARCHIVED_AT_PROP,
+      PropValue::I64(0)
+    )));
+    assert!(reserved(
+      ray.set_props(alice.id, [(ARCHIVED_AT_PROP, PropValue::I64(0))])
+    ));
+    assert!(reserved(ray.update_by_id(alice.id).and_then(|update| {
+      update.set(ARCHIVED_AT_PROP, PropValue::I64(0)).execute()
+    })));
+    assert!(!ray.is_archived(alice.id));
+    assert!(Kite::open(
+      temp_dir.path().join("reserved.kitedb"),
+      KiteOptions::new().node(NodeDef::new("Doc", "doc:").prop(PropDef::int(ARCHIVED_AT_PROP))),
+    )
+    .is_err());
 
-    // Get lightweight reference
-    let node_ref = ray.node_ref("User", "alice").expect("expected value");
-    assert!(node_ref.is_some());
-    let node_ref = node_ref.expect("expected value");
-    assert_eq!(node_ref.id(), user.id);
-    assert_eq!(node_ref.key(), Some("user:alice"));
-
-    // Non-existent user
-    let not_found = ray.node_ref("User", "bob").expect("expected value");
-    assert!(not_found.is_none());
-
+    let exported =
+      export_to_object_single(ray.raw(), ExportOptions::default()).expect("expected value");
+    let bob_export = exported
+      .nodes
+      .iter()
+      .find(|node| node.id == bob.id)
+      .expect("expected value");
+    assert!(bob_export.archived_at.is_some());
+    assert!(!bob_export.props.contains_key(ARCHIVED_AT_PROP));
     ray.close().expect("expected value");
+
+    let copy_dir = tempdir().expect("expected value");
+    let copy = Kite::open(temp_db_path(&copy_dir), create_test_schema()).expect("expected value");
+    import_from_object_single(copy.raw(), &exported, Default::default()).expect("expected value");
+    let bob_copy = copy
+      .get_including_archived("User", "bob")
+      .expect("expected value")
+      .expect("expected value");
+    assert!(copy.is_archived(bob_copy.id));
+    assert!(copy.get("User", "bob").expect("expected value").is_none());
+    copy.close().expect("expected value");
   }
 
   #[test]
@@ -4369,6 +9256,70 @@ mod tests {
     ray.close().expect("expected value");
   }
 
+  #[test]
+  fn test_traversal_allowed_node_types() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = create_test_schema();
+
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
+    let post = ray
+      .create_node("Post", "p1", HashMap::new())
+      .expect("expected value");
+    let charlie = ray
+      .create_node("User", "charlie", HashMap::new())
+      .expect("expected value");
+
+    // alice -> bob -> charlie, and alice -> post -> charlie
+    ray
+      .link(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value");
+    ray
+      .link(bob.id, "FOLLOWS", charlie.id)
+      .expect("expected value");
+    ray
+      .link(alice.id, "AUTHORED", post.id)
+      .expect("expected value");
+    ray
+      .link(post.id, "FOLLOWS", charlie.id)
+      .expect("expected value");
+
+    let users = ray
+      .from(alice.id)
+      .out(None)
+      .expect("expected value")
+      .allowed_node_types(&["User"])
+      .expect("expected value")
+      .to_vec();
+    assert_eq!(users, vec![bob.id]);
+
+    // The post is dropped from the frontier, so charlie is reached only via bob
+    let mut reached: Vec<_> = ray
+      .from(alice.id)
+      .traverse(None, TraverseOptions::new(TraversalDirection::Out, 2))
+      .expect("expected value")
+      .allowed_node_types(&["User"])
+      .expect("expected value")
+      .execute()
+      .map(|result| (result.node_id, result.edge.map(|edge| edge.src)))
+      .collect();
+    reached.sort();
+    assert_eq!(
+      reached,
+      vec![(bob.id, Some(alice.id)), (charlie.id, Some(bob.id))]
+    );
+
+    assert!(ray.from(alice.id).allowed_node_types(&["Comment"]).is_err());
+
+    ray.close().expect("expected value");
+  }
+
   #[test]
   fn test_traversal_first() {
     let temp_dir = tempdir().expect("expected value");
@@ -4544,6 +9495,101 @@ mod tests {
     ray.close().expect("expected value");
   }
 
+  #[test]
+  fn test_all_page_walks_type_in_bounded_pages() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    let mut users = Vec::new();
+    for i in 0..5 {
+      let user = ray
+        .create_node("User", &format!("u{i}"), HashMap::new())
+        .expect("expected value");
+      users.push(user.id);
+      ray
+        .create_node("Post", &format!("p{i}"), HashMap::new())
+        .expect("expected value");
+    }
+
+    let page_ids = |page: &Page<NodeRef>| page.items.iter().map(|n| n.id).collect::<Vec<_>>();
+    let options = |cursor: Option<String>| PaginationOptions { limit: 2, cursor };
+    let first = ray
+      .all_page("User", options(None), false)
+      .expect("expected value");
+    assert_eq!(page_ids(&first), users[..2]);
+    assert!(first.has_more);
+    let second = ray
+      .all_page("User", options(first.next_cursor), false)
+      .expect("expected value");
+    assert_eq!(page_ids(&second), users[2..4]);
+
+    // Pending creates and deletes in the open transaction are honored
+    ray.raw().begin(false).expect("expected value");
+    ray.delete_node(users[4]).expect("expected value");
+    let extra = ray
+      .create_node("User", "u5", HashMap::new())
+      .expect("expected value");
+    let last = ray
+      .all_page("User", options(second.next_cursor), false)
+      .expect("expected value");
+    assert_eq!(page_ids(&last), vec![extra.id]);
+    assert!(!last.has_more);
+    assert_eq!(last.next_cursor, None);
+    ray.raw().rollback().expect("expected value");
+
+    let bad_cursor = options(Some("bogus".into()));
+    assert!(ray.all_page("User", bad_cursor, false).is_err());
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_count_and_list_nodes_by_label() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
+    let post = ray
+      .create_node("Post", "p1", HashMap::new())
+      .expect("expected value");
+
+    let admin = ray.raw().label_id_or_create("Admin");
+    ray.raw().begin(false).expect("expected value");
+    ray
+      .raw()
+      .add_node_label(alice.id, admin)
+      .expect("expected value");
+    ray
+      .raw()
+      .add_node_label(post.id, admin)
+      .expect("expected value");
+    ray.raw().commit().expect("expected value");
+
+    assert_eq!(ray.count_nodes_by_label("Admin"), 2);
+    assert_eq!(ray.count_nodes_by_label("User"), 2);
+    assert_eq!(ray.count_nodes_by_label("Missing"), 0);
+
+    let admins: Vec<_> = ray
+      .all_by_label("Admin", false)
+      .map(|node| (node.id, node.node_type().to_string()))
+      .collect();
+    assert_eq!(
+      admins,
+      vec![
+        (alice.id, "User".to_string()),
+        (post.id, "Post".to_string())
+      ]
+    );
+    let users: Vec<_> = ray.all_by_label("User", false).map(|n| n.id).collect();
+    assert_eq!(users, vec![alice.id, bob.id]);
+    assert_eq!(ray.all_by_label("Missing", false).count(), 0);
+    ray.close().expect("expected value");
+  }
+
   #[test]
   fn test_reachable_from() {
     let temp_dir = tempdir().expect("expected value");
@@ -4592,6 +9638,69 @@ mod tests {
     ray.close().expect("expected value");
   }
 
+  #[test]
+  fn test_reachable_from_excluding() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = create_test_schema();
+
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+
+    // alice -> bob -> dave, alice -> charlie -> dave
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
+    let charlie = ray
+      .create_node("User", "charlie", HashMap::new())
+      .expect("expected value");
+    let dave = ray
+      .create_node("User", "dave", HashMap::new())
+      .expect("expected value");
+    for (src, dst) in [
+      (alice.id, bob.id),
+      (bob.id, dave.id),
+      (alice.id, charlie.id),
+      (charlie.id, dave.id),
+    ] {
+      ray.link(src, "FOLLOWS", dst).expect("expected value");
+    }
+    let follows = ray.edges["FOLLOWS"].etype_id.expect("expected value");
+
+    // Losing bob still leaves dave reachable through charlie
+    let without_bob = ray
+      .reachable_from_excluding(alice.id, 3, None, &HashSet::from([bob.id]), &HashSet::new())
+      .expect("expected value");
+    assert!(!without_bob.contains(&bob.id));
+    assert!(without_bob.contains(&charlie.id));
+    assert!(without_bob.contains(&dave.id));
+
+    // Losing bob and the charlie -> dave edge cuts dave off
+    let cut = HashSet::from([Edge {
+      src: charlie.id,
+      etype: follows,
+      dst: dave.id,
+    }]);
+    let isolated = ray
+      .reachable_from_excluding(alice.id, 3, None, &HashSet::from([bob.id]), &cut)
+      .expect("expected value");
+    assert_eq!(isolated, vec![charlie.id]);
+
+    let none = ray
+      .reachable_from_excluding(
+        alice.id,
+        3,
+        None,
+        &HashSet::from([alice.id]),
+        &HashSet::new(),
+      )
+      .expect("expected value");
+    assert!(none.is_empty());
+
+    ray.close().expect("expected value");
+  }
+
   #[test]
   fn test_k_shortest_paths() {
     let temp_dir = tempdir().expect("expected value");
@@ -4687,53 +9796,261 @@ mod tests {
   }
 
   #[test]
-  fn test_batch_create_and_link() {
+  fn test_batch_create_and_link() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = create_test_schema();
+
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+
+    // First batch: create nodes
+    let results = ray
+      .batch(vec![
+        BatchOp::CreateNode {
+          node_type: "User".into(),
+          key_suffix: "alice".into(),
+          props: HashMap::new(),
+        },
+        BatchOp::CreateNode {
+          node_type: "User".into(),
+          key_suffix: "bob".into(),
+          props: HashMap::new(),
+        },
+      ])
+      .expect("expected value");
+
+    // Extract node IDs from results
+    let alice_id = match &results[0] {
+      BatchResult::NodeCreated(node_ref) => node_ref.id(),
+      _ => panic!("Expected NodeCreated"),
+    };
+    let bob_id = match &results[1] {
+      BatchResult::NodeCreated(node_ref) => node_ref.id(),
+      _ => panic!("Expected NodeCreated"),
+    };
+
+    // Second batch: create edge
+    ray
+      .batch(vec![BatchOp::Link {
+        src: alice_id,
+        edge_type: "FOLLOWS".into(),
+        dst: bob_id,
+      }])
+      .expect("expected value");
+
+    // Verify edge was created
+    assert!(ray
+      .has_edge(alice_id, "FOLLOWS", bob_id)
+      .expect("expected value"));
+
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_batch_create_and_link_op_links_new_node() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
+
+    let results = ray
+      .batch(vec![BatchOp::CreateAndLink {
+        node_type: "Post".into(),
+        key_suffix: "p1".into(),
+        props: HashMap::new(),
+        links: vec![
+          BatchLink {
+            edge_type: "AUTHORED".into(),
+            node_id: alice.id,
+            incoming: true,
+          },
+          BatchLink {
+            edge_type: "FOLLOWS".into(),
+            node_id: bob.id,
+            incoming: false,
+          },
+        ],
+      }])
+      .expect("expected value");
+    let post_id = match &results[0] {
+      BatchResult::NodeCreated(node_ref) => node_ref.id(),
+      _ => panic!("Expected NodeCreated"),
+    };
+
+    assert!(ray
+      .has_edge(alice.id, "AUTHORED", post_id)
+      .expect("expected value"));
+    assert!(ray
+      .has_edge(post_id, "FOLLOWS", bob.id)
+      .expect("expected value"));
+
+    // An unknown edge type rolls back the node too
+    let failed = ray.batch(vec![BatchOp::CreateAndLink {
+      node_type: "Post".into(),
+      key_suffix: "p2".into(),
+      props: HashMap::new(),
+      links: vec![BatchLink {
+        edge_type: "NOPE".into(),
+        node_id: alice.id,
+        incoming: false,
+      }],
+    }]);
+    assert!(failed.is_err());
+    assert!(ray.get("Post", "p2").expect("expected value").is_none());
+  }
+
+  #[test]
+  fn test_on_prop_change_reports_committed_changes() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    let seen = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    ray
+      .on_prop_change("User", "name", move |change| {
+        sink
+          .lock()
+          .push((change.old_value.clone(), change.new_value.clone()));
+      })
+      .expect("expected value");
+    assert!(ray.on_prop_change("Comment", "name", |_| {}).is_err());
+
+    let name = |value: &str| PropValue::String(value.into());
+    let alice = ray
+      .create_node(
+        "User",
+        "alice",
+        HashMap::from([("name".into(), name("Alice"))]),
+      )
+      .expect("expected value")
+      .id;
+    ray
+      .set_prop(alice, "name", name("Alice"))
+      .expect("expected value");
+    ray
+      .set_prop(alice, "age", PropValue::I64(30))
+      .expect("expected value");
+    ray
+      .create_node(
+        "Post",
+        "hello",
+        HashMap::from([("title".into(), name("Hello"))]),
+      )
+      .expect("expected value");
+    ray
+      .transaction(|ctx| {
+        ctx.set_prop(alice, "name", name("Al"))?;
+        ctx.set_prop(alice, "name", name("Ally"))
+      })
+      .expect("expected value");
+
+    assert_eq!(
+      *seen.lock(),
+      vec![
+        (None, Some(name("Alice"))),
+        (Some(name("Alice")), Some(name("Ally"))),
+      ]
+    );
+  }
+
+  #[test]
+  fn test_on_prop_change_reports_deletes_and_unregisters() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    let seen = Arc::new(parking_lot::Mutex::new(Vec::new()));
+    let sink = Arc::clone(&seen);
+    let id = ray
+      .on_prop_change("User", "name", move |change| {
+        sink.lock().push((
+          change.node_key.clone(),
+          change.old_value.clone(),
+          change.new_value.clone(),
+        ));
+      })
+      .expect("expected value");
+
+    let name = |value: &str| PropValue::String(value.into());
+    let alice = ray
+      .create_node(
+        "User",
+        "alice",
+        HashMap::from([("name".into(), name("Alice"))]),
+      )
+      .expect("expected value")
+      .id;
+    ray.delete_node(alice).expect("expected value");
+    assert_eq!(
+      *seen.lock(),
+      vec![
+        (Some("user:alice".to_string()), None, Some(name("Alice"))),
+        (Some("user:alice".to_string()), Some(name("Alice")), None),
+      ]
+    );
+
+    assert!(ray.remove_prop_listener(id));
+    assert!(!ray.remove_prop_listener(id));
+    assert!(ray.raw().prop_listeners.read().is_empty());
+    ray
+      .create_node("User", "bob", HashMap::from([("name".into(), name("Bob"))]))
+      .expect("expected value");
+    assert_eq!(seen.lock().len(), 2);
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_link_many_counts_added_and_existing_edges() {
     let temp_dir = tempdir().expect("expected value");
-    let options = create_test_schema();
-
-    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
-
-    // First batch: create nodes
-    let results = ray
-      .batch(vec![
-        BatchOp::CreateNode {
-          node_type: "User".into(),
-          key_suffix: "alice".into(),
-          props: HashMap::new(),
-        },
-        BatchOp::CreateNode {
-          node_type: "User".into(),
-          key_suffix: "bob".into(),
-          props: HashMap::new(),
-        },
-      ])
-      .expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value")
+      .id;
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value")
+      .id;
+    ray.link(alice, "FOLLOWS", bob).expect("expected value");
 
-    // Extract node IDs from results
-    let alice_id = match &results[0] {
-      BatchResult::NodeCreated(node_ref) => node_ref.id(),
-      _ => panic!("Expected NodeCreated"),
-    };
-    let bob_id = match &results[1] {
-      BatchResult::NodeCreated(node_ref) => node_ref.id(),
-      _ => panic!("Expected NodeCreated"),
+    let link = |src, edge_type: &str, dst| LinkSpec {
+      src,
+      edge_type: edge_type.into(),
+      dst,
+      props: HashMap::new(),
     };
-
-    // Second batch: create edge
-    ray
-      .batch(vec![BatchOp::Link {
-        src: alice_id,
-        edge_type: "FOLLOWS".into(),
-        dst: bob_id,
-      }])
+    let result = ray
+      .link_many(vec![
+        link(alice, "FOLLOWS", bob),
+        link(bob, "FOLLOWS", alice),
+        link(bob, "FOLLOWS", alice),
+        link(alice, "AUTHORED", bob),
+      ])
       .expect("expected value");
-
-    // Verify edge was created
+    assert_eq!(
+      result,
+      LinkManyResult {
+        added: 2,
+        existing: 2,
+      }
+    );
+    assert!(ray.has_edge(bob, "FOLLOWS", alice).expect("expected value"));
     assert!(ray
-      .has_edge(alice_id, "FOLLOWS", bob_id)
+      .has_edge(alice, "AUTHORED", bob)
       .expect("expected value"));
 
-    ray.close().expect("expected value");
+    let err = ray.link_many(vec![
+      link(bob, "AUTHORED", alice),
+      link(bob, "LIKES", alice),
+    ]);
+    assert!(err.is_err());
+    assert!(!ray
+      .has_edge(bob, "AUTHORED", alice)
+      .expect("expected value"));
   }
 
   #[test]
@@ -4967,6 +10284,489 @@ mod tests {
     ray.close().expect("expected value");
   }
 
+  #[test]
+  fn test_link_valid_and_as_of_traversal() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    let ids: Vec<NodeId> = ["alice", "bob", "carol", "dave"]
+      .iter()
+      .map(|key| {
+        ray
+          .create_node("User", key, HashMap::new())
+          .expect("expected value")
+          .id
+      })
+      .collect();
+    let (alice, bob, carol, dave) = (ids[0], ids[1], ids[2], ids[3]);
+    ray
+      .link_valid(alice, "FOLLOWS", bob, HashMap::new(), Some(100), Some(200))
+      .expect("expected value");
+    ray
+      .link_valid(alice, "FOLLOWS", carol, HashMap::new(), Some(150), None)
+      .expect("expected value");
+    ray.link(alice, "FOLLOWS", dave).expect("expected value");
+
+    let as_of = |ray: &Kite, ts: i64| {
+      let mut ids = ray
+        .from(alice)
+        .out(Some("FOLLOWS"))
+        .expect("expected value")
+        .as_of(ts)
+        .to_vec();
+      ids.sort_unstable();
+      ids
+    };
+    assert_eq!(as_of(&ray, 50), vec![dave]);
+    assert_eq!(as_of(&ray, 100), vec![bob, dave]);
+    assert_eq!(as_of(&ray, 175), vec![bob, carol, dave]);
+    // valid_to is exclusive
+    assert_eq!(as_of(&ray, 200), vec![carol, dave]);
+    // Without as_of every edge is followed
+    let all = ray
+      .from(alice)
+      .out(Some("FOLLOWS"))
+      .expect("expected value")
+      .count();
+    assert_eq!(all, 3);
+
+    // Relinking replaces the window; None reopens that side
+    ray
+      .link_valid(alice, "FOLLOWS", bob, HashMap::new(), None, Some(200))
+      .expect("expected value");
+    assert_eq!(as_of(&ray, 50), vec![bob, dave]);
+    assert!(ray
+      .link_valid(alice, "FOLLOWS", bob, HashMap::new(), Some(5), Some(5))
+      .is_err());
+
+    // A plain link of an existing edge leaves its window alone
+    ray.link(alice, "FOLLOWS", bob).expect("expected value");
+    assert_eq!(as_of(&ray, 250), vec![carol, dave]);
+
+    // The window props are reserved for link_valid
+    let reserved = |result: Result<()>| matches!(result, Err(KiteError::InvalidSchema(_)));
+    assert!(reserved(ray.set_edge_prop(
+      alice,
+      "FOLLOWS",
+      dave,
+      VALID_FROM_PROP,
+      PropValue::I64(0)
+    )));
+    assert!(reserved(ray.link_valid(
+      alice,
+      "FOLLOWS",
+      dave,
+      HashMap::from([(VALID_TO_PROP.to_string(), PropValue::I64(0))]),
+      None,
+      None
+    )));
+    assert_eq!(as_of(&ray, 50), vec![bob, dave]);
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_batch_results_report_no_ops() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
+
+    let ops = || {
+      vec![
+        BatchOp::Link {
+          src: alice.id,
+          edge_type: "FOLLOWS".into(),
+          dst: bob.id,
+        },
+        BatchOp::SetProp {
+          node_id: alice.id,
+          prop_name: "age".into(),
+          value: PropValue::I64(30),
+        },
+      ]
+    };
+    let first = ray.batch(ops()).expect("expected value");
+    assert!(matches!(first[0], BatchResult::EdgeCreated(true)));
+    assert!(matches!(first[1], BatchResult::PropSet(true)));
+
+    // Replaying the same ops changes nothing
+    let again = ray.batch(ops()).expect("expected value");
+    assert!(matches!(again[0], BatchResult::EdgeCreated(false)));
+    assert!(matches!(again[1], BatchResult::PropSet(false)));
+    assert_eq!(again.iter().map(BatchResult::affected).sum::<usize>(), 0);
+
+    let results = ray
+      .batch(vec![
+        BatchOp::DelProp {
+          node_id: bob.id,
+          prop_name: "age".into(),
+        },
+        BatchOp::Unlink {
+          src: bob.id,
+          edge_type: "FOLLOWS".into(),
+          dst: alice.id,
+        },
+        BatchOp::DelProp {
+          node_id: alice.id,
+          prop_name: "age".into(),
+        },
+      ])
+      .expect("expected value");
+    assert!(matches!(results[0], BatchResult::PropDeleted(false)));
+    assert!(matches!(results[1], BatchResult::EdgeRemoved(false)));
+    assert!(matches!(results[2], BatchResult::PropDeleted(true)));
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_degree_counts_by_direction_and_type() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
+    let post = ray
+      .create_node("Post", "p1", HashMap::new())
+      .expect("expected value");
+    ray
+      .link(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value");
+    ray
+      .link(bob.id, "FOLLOWS", alice.id)
+      .expect("expected value");
+    ray
+      .link(alice.id, "AUTHORED", post.id)
+      .expect("expected value");
+
+    let degree = |direction, edge_type| {
+      ray
+        .degree(alice.id, direction, edge_type)
+        .expect("expected value")
+    };
+    assert_eq!(degree(TraversalDirection::Out, None), 2);
+    assert_eq!(degree(TraversalDirection::Out, Some("FOLLOWS")), 1);
+    assert_eq!(degree(TraversalDirection::In, None), 1);
+    assert_eq!(degree(TraversalDirection::Both, Some("FOLLOWS")), 2);
+    assert!(ray
+      .degree(alice.id, TraversalDirection::Out, Some("BLOCKS"))
+      .is_err());
+
+    let degrees = ray
+      .degrees(&[alice.id, post.id], TraversalDirection::In, None)
+      .expect("expected value");
+    assert_eq!(degrees.get(&alice.id), Some(&1));
+    assert_eq!(degrees.get(&post.id), Some(&1));
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_update_where_sets_props_on_matching_nodes() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    for (key, age) in [("alice", 30), ("bob", 30), ("carol", 41)] {
+      ray
+        .create_node(
+          "User",
+          key,
+          HashMap::from([("age".to_string(), PropValue::I64(age))]),
+        )
+        .expect("expected value");
+    }
+
+    let updated = ray
+      .update_where(
+        "User",
+        "age",
+        &PropValue::I64(30),
+        &HashMap::from([("name".to_string(), PropValue::String("thirty".into()))]),
+      )
+      .expect("expected value");
+    assert_eq!(updated, 2);
+
+    let name = |ray: &Kite, key: &str| {
+      let node = ray
+        .get("User", key)
+        .expect("expected value")
+        .expect("expected value");
+      ray.prop(node.id, "name")
+    };
+    assert_eq!(
+      name(&ray, "alice"),
+      Some(PropValue::String("thirty".into()))
+    );
+    assert_eq!(name(&ray, "bob"), Some(PropValue::String("thirty".into())));
+    assert_eq!(name(&ray, "carol"), None);
+
+    let none = ray
+      .update_where(
+        "User",
+        "age",
+        &PropValue::I64(99),
+        &HashMap::from([("name".to_string(), PropValue::String("x".into()))]),
+      )
+      .expect("expected value");
+    assert_eq!(none, 0);
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_has_edges_checks_pairs_in_order() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
+    ray
+      .link(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value");
+
+    let found = ray
+      .has_edges(&[
+        (alice.id, "FOLLOWS".to_string(), bob.id),
+        (bob.id, "FOLLOWS".to_string(), alice.id),
+        (alice.id, "AUTHORED".to_string(), bob.id),
+        (alice.id, "FOLLOWS".to_string(), bob.id),
+      ])
+      .expect("expected value");
+    assert_eq!(found, vec![true, false, false, true]);
+    assert!(ray
+      .has_edges(&[(alice.id, "BLOCKS".to_string(), bob.id)])
+      .is_err());
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_replace_and_take_prop_return_previous_values() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+
+    assert_eq!(
+      ray
+        .replace_prop(alice.id, "name", PropValue::String("Alice".into()))
+        .expect("expected value"),
+      None
+    );
+    assert_eq!(
+      ray
+        .replace_prop(alice.id, "name", PropValue::String("Al".into()))
+        .expect("expected value"),
+      Some(PropValue::String("Alice".into()))
+    );
+    assert_eq!(
+      ray.take_prop(alice.id, "name").expect("expected value"),
+      Some(PropValue::String("Al".into()))
+    );
+    assert_eq!(
+      ray.take_prop(alice.id, "name").expect("expected value"),
+      None
+    );
+    assert!(ray
+      .replace_prop(alice.id, ARCHIVED_AT_PROP, PropValue::I64(1))
+      .is_err());
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_batch_retry_applies_ops_and_fails_fast_on_other_errors() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+
+    let results = ray
+      .batch_retry(
+        vec![BatchOp::CreateNode {
+          node_type: "User".into(),
+          key_suffix: "alice".into(),
+          props: HashMap::new(),
+        }],
+        3,
+      )
+      .expect("expected value");
+    assert!(matches!(results[0], BatchResult::NodeCreated(_)));
+    assert!(ray.get("User", "alice").expect("expected value").is_some());
+
+    let err = ray
+      .batch_retry(
+        vec![BatchOp::CreateNode {
+          node_type: "Robot".into(),
+          key_suffix: "r2".into(),
+          props: HashMap::new(),
+        }],
+        3,
+      )
+      .expect_err("unknown type should fail");
+    assert!(matches!(err, KiteError::InvalidSchema(_)));
+    ray.close().expect("expected value");
+  }
+
+  /// Runs `ops` the way `batch` does, but lets another thread commit a write
+  /// to `node_id`'s `name` before the commit when `interfere` is set, so the
+  /// commit loses a real write-write conflict
+  fn batch_with_interference(
+    ray: &mut Kite,
+    ops: Vec<BatchOp>,
+    node_id: NodeId,
+    interfere: bool,
+  ) -> Result<Vec<BatchResult>> {
+    if !interfere {
+      return ray.batch(ops);
+    }
+    ray.raw().begin(false)?;
+    let results = ray.batch(ops)?;
+    let db = ray.raw();
+    let key_id = db.propkey_id_or_create("name");
+    std::thread::scope(|scope| {
+      scope
+        .spawn(|| {
+          db.begin(false).expect("expected value");
+          db.set_node_prop(node_id, key_id, PropValue::String("Other".into()))
+            .expect("expected value");
+          db.commit().expect("first committer wins");
+        })
+        .join()
+        .expect("expected value");
+    });
+    db.commit()?;
+    Ok(results)
+  }
+
+  #[test]
+  fn test_batch_retry_replays_after_conflict() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema().mvcc(true)).expect("expected value");
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    let ops = vec![BatchOp::SetProp {
+      node_id: alice.id,
+      prop_name: "name".into(),
+      value: PropValue::String("Alice".into()),
+    }];
+
+    let mut attempts = 0;
+    let results = ray
+      .retry_on_conflict(3, |ray| {
+        attempts += 1;
+        batch_with_interference(ray, ops.clone(), alice.id, attempts == 1)
+      })
+      .expect("second attempt should commit");
+    assert_eq!(attempts, 2);
+    assert_eq!(results.len(), 1);
+    assert_eq!(
+      ray.prop(alice.id, "name"),
+      Some(PropValue::String("Alice".into()))
+    );
+    assert!(!ray.raw().has_transaction());
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_batch_retry_gives_up_after_max_attempts() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema().mvcc(true)).expect("expected value");
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    let ops = vec![BatchOp::SetProp {
+      node_id: alice.id,
+      prop_name: "name".into(),
+      value: PropValue::String("Alice".into()),
+    }];
+
+    let mut attempts = 0;
+    let err = ray
+      .retry_on_conflict(3, |ray| {
+        attempts += 1;
+        batch_with_interference(ray, ops.clone(), alice.id, true)
+      })
+      .expect_err("every attempt conflicts");
+    assert!(err.is_conflict());
+    assert_eq!(attempts, 3);
+    assert_eq!(
+      ray.prop(alice.id, "name"),
+      Some(PropValue::String("Other".into()))
+    );
+
+    attempts = 0;
+    let err = ray
+      .retry_on_conflict(0, |ray| {
+        attempts += 1;
+        batch_with_interference(ray, ops.clone(), alice.id, true)
+      })
+      .expect_err("a zero limit still makes one attempt");
+    assert!(err.is_conflict());
+    assert_eq!(attempts, 1);
+    assert!(!ray.raw().has_transaction());
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_batch_validate_reports_bad_ops_without_writing() {
+    let temp_dir = tempdir().expect("expected value");
+    let mut ray =
+      Kite::open(temp_db_path(&temp_dir), create_test_schema()).expect("expected value");
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+
+    let ops = vec![
+      BatchOp::CreateNode {
+        node_type: "User".into(),
+        key_suffix: "bob".into(),
+        props: HashMap::from([("name".to_string(), PropValue::String("Bob".into()))]),
+      },
+      BatchOp::CreateNode {
+        node_type: "Robot".into(),
+        key_suffix: "r2".into(),
+        props: HashMap::new(),
+      },
+      BatchOp::CreateNode {
+        node_type: "Post".into(),
+        key_suffix: "p1".into(),
+        props: HashMap::new(),
+      },
+      BatchOp::Link {
+        src: alice.id,
+        edge_type: "BLOCKS".into(),
+        dst: alice.id,
+      },
+      BatchOp::DelProp {
+        node_id: alice.id,
+        prop_name: "nickname".into(),
+      },
+    ];
+    let errors = ray.batch_validate(&ops);
+    let indexes: Vec<usize> = errors.iter().map(|(index, _)| *index).collect();
+    assert_eq!(indexes, vec![1, 2, 3, 4]);
+    assert!(matches!(errors[0].1, KiteError::InvalidSchema(_)));
+    assert!(errors[1].1.to_string().contains("title"));
+
+    // Nothing was written
+    assert!(ray.get("User", "bob").expect("expected value").is_none());
+    assert!(ray.batch_validate(&ops[..1]).is_empty());
+    ray.close().expect("expected value");
+  }
+
   #[test]
   fn test_batch_delete_operations() {
     let temp_dir = tempdir().expect("expected value");
@@ -5012,6 +10812,50 @@ mod tests {
     ray.close().expect("expected value");
   }
 
+  #[test]
+  fn test_batch_best_effort_continues_past_failures() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = create_test_schema();
+
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+
+    let results = ray
+      .batch_best_effort(vec![
+        BatchOp::CreateNode {
+          node_type: "User".into(),
+          key_suffix: "alice".into(),
+          props: HashMap::new(),
+        },
+        BatchOp::CreateNode {
+          node_type: "Ghost".into(),
+          key_suffix: "boo".into(),
+          props: HashMap::new(),
+        },
+        BatchOp::CreateNode {
+          node_type: "User".into(),
+          key_suffix: "bob".into(),
+          props: HashMap::new(),
+        },
+      ])
+      .expect("expected value");
+
+    assert_eq!(results.len(), 3);
+    assert!(matches!(results[0], Ok(BatchResult::NodeCreated(_))));
+    assert!(matches!(results[1], Err(KiteError::InvalidSchema(_))));
+    assert!(matches!(results[2], Ok(BatchResult::NodeCreated(_))));
+    assert_eq!(ray.count_nodes(), 2);
+
+    // An open transaction would make the ops share it
+    ray.raw().begin(false).expect("expected value");
+    assert!(matches!(
+      ray.batch_best_effort(Vec::new()),
+      Err(KiteError::TransactionInProgress)
+    ));
+    ray.raw().rollback().expect("expected value");
+
+    ray.close().expect("expected value");
+  }
+
   // ============================================================================
   // Transaction Tests
   // ============================================================================
@@ -5192,109 +11036,653 @@ mod tests {
     ray.close().expect("expected value");
   }
 
-  // ============================================================================
-  // TxBuilder Tests
-  // ============================================================================
-
+  // ============================================================================
+  // TxBuilder Tests
+  // ============================================================================
+
+  #[test]
+  fn test_tx_builder() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = create_test_schema();
+
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+
+    // Use the builder pattern
+    let results = ray
+      .tx()
+      .create_node("User", "alice", HashMap::new())
+      .create_node("User", "bob", HashMap::new())
+      .execute(&mut ray)
+      .expect("expected value");
+
+    assert_eq!(results.len(), 2);
+
+    // Extract IDs and create edges
+    let alice_id = match &results[0] {
+      BatchResult::NodeCreated(node_ref) => node_ref.id(),
+      _ => panic!("Expected NodeCreated"),
+    };
+    let bob_id = match &results[1] {
+      BatchResult::NodeCreated(node_ref) => node_ref.id(),
+      _ => panic!("Expected NodeCreated"),
+    };
+
+    ray
+      .tx()
+      .link(alice_id, "FOLLOWS", bob_id)
+      .set_prop(alice_id, "name", PropValue::String("Alice".into()))
+      .execute(&mut ray)
+      .expect("expected value");
+
+    assert!(ray
+      .has_edge(alice_id, "FOLLOWS", bob_id)
+      .expect("expected value"));
+    assert_eq!(
+      ray.prop(alice_id, "name"),
+      Some(PropValue::String("Alice".into()))
+    );
+
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_tx_builder_into_ops() {
+    // Test that into_ops returns the operations without executing
+    let ops = TxBuilder::default()
+      .create_node("User", "alice", HashMap::new())
+      .link(1, "FOLLOWS", 2)
+      .set_prop(1, "name", PropValue::String("Test".into()))
+      .into_ops();
+
+    assert_eq!(ops.len(), 3);
+  }
+
+  // ============================================================================
+  // Edge Property Tests
+  // ============================================================================
+
+  #[test]
+  fn test_link_with_props() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = create_test_schema();
+
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
+
+    // Link with properties
+    let mut props = HashMap::new();
+    props.insert("weight".to_string(), PropValue::F64(0.8));
+    props.insert("since".to_string(), PropValue::String("2024".into()));
+
+    ray
+      .link_with_props(alice.id, "FOLLOWS", bob.id, props)
+      .expect("expected value");
+
+    // Verify edge exists
+    assert!(ray
+      .has_edge(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value"));
+
+    // Verify edge properties
+    let weight = ray
+      .edge_prop(alice.id, "FOLLOWS", bob.id, "weight")
+      .expect("expected value");
+    assert_eq!(weight, Some(PropValue::F64(0.8)));
+
+    let since = ray
+      .edge_prop(alice.id, "FOLLOWS", bob.id, "since")
+      .expect("expected value");
+    assert_eq!(since, Some(PropValue::String("2024".into())));
+
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_unique_prop_rejects_duplicates() {
+    let temp_dir = tempdir().expect("expected value");
+    let path = temp_db_path(&temp_dir);
+    let schema = || {
+      KiteOptions::new().node(NodeDef::new("User", "user:").prop(PropDef::string("email").unique()))
+    };
+    let email =
+      |value: &str| HashMap::from([("email".to_string(), PropValue::String(value.into()))]);
+
+    let mut ray = Kite::open(&path, schema()).expect("expected value");
+    let alice = ray
+      .create_node("User", "alice", email("a@example.com"))
+      .expect("expected value");
+
+    let err = ray
+      .create_node("User", "bob", email("a@example.com"))
+      .expect_err("expected error");
+    assert!(
+      matches!(err, KiteError::Constraint(ref msg) if msg.contains(&format!("node {}", alice.id)))
+    );
+    assert!(ray.get("User", "bob").expect("expected value").is_none());
+
+    let bob = ray
+      .create_node("User", "bob", email("b@example.com"))
+      .expect("expected value");
+    assert!(matches!(
+      ray.set_prop(bob.id, "email", PropValue::String("a@example.com".into())),
+      Err(KiteError::Constraint(_))
+    ));
+    // Re-setting a node's own value is fine
+    ray
+      .set_prop(alice.id, "email", PropValue::String("a@example.com".into()))
+      .expect("expected value");
+
+    // Values free up once their owner moves off them or is deleted
+    ray
+      .set_prop(alice.id, "email", PropValue::String("c@example.com".into()))
+      .expect("expected value");
+    ray
+      .set_prop(bob.id, "email", PropValue::String("a@example.com".into()))
+      .expect("expected value");
+    ray.delete_node(alice.id).expect("expected value");
+    ray
+      .create_node("User", "carol", email("c@example.com"))
+      .expect("expected value");
+    ray.close().expect("expected value");
+
+    // The index is rebuilt on open
+    let mut ray = Kite::open(&path, schema()).expect("expected value");
+    assert!(matches!(
+      ray.create_node("User", "dave", email("c@example.com")),
+      Err(KiteError::Constraint(_))
+    ));
+    ray
+      .create_node("User", "dave", email("d@example.com"))
+      .expect("expected value");
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_unique_prop_checked_at_commit() {
+    let temp_dir = tempdir().expect("expected value");
+    let schema = KiteOptions::new()
+      .node(NodeDef::new("User", "user:").prop(PropDef::string("email").unique()));
+    let email =
+      |value: &str| HashMap::from([("email".to_string(), PropValue::String(value.into()))]);
+    let mut ray = Kite::open(temp_db_path(&temp_dir), schema).expect("expected value");
+    let alice = ray
+      .create_node("User", "alice", email("a@example.com"))
+      .expect("expected value");
+
+    // A rolled-back delete doesn't free the deleted node's value
+    ray.raw().begin(false).expect("expected value");
+    ray.delete_node(alice.id).expect("expected value");
+    ray
+      .create_node("User", "bob", email("a@example.com"))
+      .expect("expected value");
+    ray.raw().rollback().expect("expected value");
+    assert!(matches!(
+      ray.create_node("User", "carol", email("a@example.com")),
+      Err(KiteError::Constraint(_))
+    ));
+
+    // Of two open transactions taking the same value, the second to
+    // commit fails and leaves nothing behind
+    let bob = ray
+      .create_node("User", "bob", email("b@example.com"))
+      .expect("expected value");
+    let carol = ray
+      .create_node("User", "carol", email("c@example.com"))
+      .expect("expected value");
+    let key_id = ray.raw().propkey_id("email").expect("expected value");
+    let db = ray.raw();
+    let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+    let (go_tx, go_rx) = std::sync::mpsc::channel();
+    std::thread::scope(|scope| {
+      let other = scope.spawn(move || {
+        db.begin(false)?;
+        db.set_node_prop(bob.id, key_id, PropValue::String("x@example.com".into()))?;
+        ready_tx.send(()).expect("expected value");
+        go_rx.recv().expect("expected value");
+        db.commit()
+      });
+      ready_rx.recv().expect("expected value");
+      db.begin(false).expect("expected value");
+      db.set_node_prop(carol.id, key_id, PropValue::String("x@example.com".into()))
+        .expect("expected value");
+      db.commit().expect("expected value");
+      go_tx.send(()).expect("expected value");
+      let result = other.join().expect("expected value");
+      assert!(matches!(result, Err(KiteError::Constraint(ref msg)) if msg.contains("user:carol")));
+    });
+    assert!(!ray.raw().has_transaction());
+    assert_eq!(
+      ray.prop(bob.id, "email"),
+      Some(PropValue::String("b@example.com".into()))
+    );
+    assert_eq!(
+      ray
+        .find_where(
+          "User",
+          "email",
+          &PropFilter::Eq(PropValue::String("x@example.com".into()))
+        )
+        .expect("expected value"),
+      vec![carol.id]
+    );
+    ray
+      .set_prop(bob.id, "email", PropValue::String("a2@example.com".into()))
+      .expect("expected value");
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_list_indexes_reports_prop_and_vector_indexes() {
+    let temp_dir = tempdir().expect("expected value");
+    let path = temp_db_path(&temp_dir);
+    let schema = || {
+      KiteOptions::new().node(
+        NodeDef::new("User", "user:")
+          .prop(PropDef::string("email").unique())
+          .prop(PropDef::int("age").ordered()),
+      )
+    };
+    let props = |email: &str, age: i64| {
+      HashMap::from([
+        ("email".to_string(), PropValue::String(email.into())),
+        ("age".to_string(), PropValue::I64(age)),
+      ])
+    };
+
+    let mut ray = Kite::open(&path, schema()).expect("expected value");
+    let alice = ray
+      .create_node("User", "alice", props("a@example.com", 30))
+      .expect("expected value");
+    ray
+      .create_node("User", "bob", props("b@example.com", 30))
+      .expect("expected value");
+
+    let indexes = ray.list_indexes().expect("expected value");
+    let summary: Vec<_> = indexes
+      .iter()
+      .map(|index| (index.kind, index.prop_name.as_str(), index.entries))
+      .collect();
+    assert_eq!(
+      summary,
+      vec![
+        (IndexKind::Ordered, "age", 2),
+        (IndexKind::Unique, "email", 2)
+      ]
+    );
+    assert!(indexes
+      .iter()
+      .all(|index| index.estimated_bytes > 0 && index.node_type.as_deref() == Some("User")));
+
+    // Vector stores created at runtime show up too
+    let embedding = ray.raw().propkey_id_or_create("embedding");
+    ray.raw().begin(false).expect("expected value");
+    ray
+      .raw()
+      .set_node_vector(alice.id, embedding, &[0.1, 0.2, 0.3])
+      .expect("expected value");
+    ray.raw().commit().expect("expected value");
+    let vector = ray
+      .list_indexes()
+      .expect("expected value")
+      .into_iter()
+      .find(|index| index.kind == IndexKind::Vector)
+      .expect("expected value");
+    assert_eq!(vector.prop_name, "embedding");
+    assert_eq!(vector.node_type, None);
+    assert_eq!(vector.entries, 1);
+    assert!(vector.estimated_bytes >= 12);
+    ray.close().expect("expected value");
+
+    // Everything is rebuilt or reloaded on open
+    let ray = Kite::open(&path, schema()).expect("expected value");
+    let indexes = ray.list_indexes().expect("expected value");
+    assert_eq!(indexes.len(), 3);
+    assert!(indexes.iter().all(|index| index.entries > 0));
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_find_by_prop_range_uses_ordered_index() {
+    let temp_dir = tempdir().expect("expected value");
+    let path = temp_db_path(&temp_dir);
+    let schema =
+      || KiteOptions::new().node(NodeDef::new("User", "user:").prop(PropDef::int("age").ordered()));
+    let age = |value: i64| HashMap::from([("age".to_string(), PropValue::I64(value))]);
+    let range = |ray: &Kite, min: i64, max: i64| {
+      ray
+        .find_by_prop_range("User", "age", &PropValue::I64(min), &PropValue::I64(max))
+        .expect("expected value")
+    };
+
+    let mut ray = Kite::open(&path, schema()).expect("expected value");
+    let alice = ray
+      .create_node("User", "alice", age(30))
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", age(-5))
+      .expect("expected value");
+    let carol = ray
+      .create_node("User", "carol", age(42))
+      .expect("expected value");
+
+    assert_eq!(range(&ray, -10, 40), vec![bob.id, alice.id]);
+    assert_eq!(range(&ray, 30, 30), vec![alice.id]);
+    assert!(range(&ray, 40, 30).is_empty());
+
+    // Updates and deletes are reflected without stale hits
+    ray
+      .set_prop(alice.id, "age", PropValue::I64(50))
+      .expect("expected value");
+    ray.delete_node(bob.id).expect("expected value");
+    assert!(range(&ray, -10, 40).is_empty());
+    assert_eq!(range(&ray, 40, 60), vec![carol.id, alice.id]);
+
+    // A transaction sees its own update, and rolling it back leaves the
+    // committed entry in place
+    ray.raw().begin(false).expect("expected value");
+    ray
+      .set_prop(alice.id, "age", PropValue::I64(70))
+      .expect("expected value");
+    assert_eq!(range(&ray, 40, 60), vec![carol.id]);
+    assert_eq!(range(&ray, 60, 80), vec![alice.id]);
+    ray.raw().rollback().expect("expected value");
+    assert_eq!(range(&ray, 40, 60), vec![carol.id, alice.id]);
+    assert!(range(&ray, 60, 80).is_empty());
+
+    // Raw database writes are indexed too
+    let age_key = ray.raw().propkey_id("age").expect("expected value");
+    ray.raw().begin(false).expect("expected value");
+    ray
+      .raw()
+      .set_node_prop(carol.id, age_key, PropValue::I64(10))
+      .expect("expected value");
+    ray.raw().commit().expect("expected value");
+    assert_eq!(range(&ray, 0, 20), vec![carol.id]);
+    ray
+      .set_prop(carol.id, "age", PropValue::I64(42))
+      .expect("expected value");
+
+    // Archived nodes are skipped
+    ray.archive(carol.id).expect("expected value");
+    assert_eq!(range(&ray, 40, 60), vec![alice.id]);
+    ray.unarchive(carol.id).expect("expected value");
+
+    assert!(matches!(
+      ray.find_by_prop_range("User", "name", &PropValue::I64(0), &PropValue::I64(1)),
+      Err(KiteError::InvalidSchema(_))
+    ));
+    assert!(matches!(
+      ray.find_by_prop_range("User", "age", &PropValue::I64(0), &PropValue::F64(1.0)),
+      Err(KiteError::InvalidQuery(_))
+    ));
+    ray.close().expect("expected value");
+
+    // The index is rebuilt on open
+    let ray = Kite::open(&path, schema()).expect("expected value");
+    assert_eq!(range(&ray, 0, 100), vec![carol.id, alice.id]);
+    ray.close().expect("expected value");
+  }
+
+  #[test]
+  fn test_find_where_eq_uses_index_when_declared() {
+    let temp_dir = tempdir().expect("expected value");
+    let schema = KiteOptions::new().node(
+      NodeDef::new("User", "user:")
+        .prop(PropDef::string("email").unique())
+        .prop(PropDef::int("age").ordered())
+        .prop(PropDef::string("city")),
+    );
+    let mut ray = Kite::open(temp_db_path(&temp_dir), schema).expect("expected value");
+    let user = |email: &str, age: i64, city: &str| {
+      HashMap::from([
+        ("email".to_string(), PropValue::String(email.into())),
+        ("age".to_string(), PropValue::I64(age)),
+        ("city".to_string(), PropValue::String(city.into())),
+      ])
+    };
+    let alice = ray
+      .create_node("User", "alice", user("a@x", 30, "oslo"))
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", user("b@x", 30, "oslo"))
+      .expect("expected value");
+    let eq = |ray: &Kite, prop: &str, value: PropValue| {
+      ray
+        .find_where("User", prop, &PropFilter::Eq(value))
+        .expect("expected value")
+    };
+
+    assert_eq!(
+      eq(&ray, "email", PropValue::String("b@x".into())),
+      vec![bob.id]
+    );
+    assert_eq!(eq(&ray, "age", PropValue::I64(30)), vec![alice.id, bob.id]);
+    let mut in_oslo = eq(&ray, "city", PropValue::String("oslo".into()));
+    in_oslo.sort_unstable();
+    assert_eq!(in_oslo, vec![alice.id, bob.id]);
+
+    // Changed values are not matched through a stale index entry
+    ray
+      .set_prop(bob.id, "email", PropValue::String("c@x".into()))
+      .expect("expected value");
+    assert!(eq(&ray, "email", PropValue::String("b@x".into())).is_empty());
+    assert_eq!(
+      ray
+        .find_where(
+          "User",
+          "age",
+          &PropFilter::Range {
+            min: PropValue::I64(0),
+            max: PropValue::I64(40),
+          },
+        )
+        .expect("expected value"),
+      vec![alice.id, bob.id]
+    );
+    ray.close().expect("expected value");
+  }
+
   #[test]
-  fn test_tx_builder() {
+  fn test_autoincrement_keys_allocate_distinct_numbers() {
     let temp_dir = tempdir().expect("expected value");
-    let options = create_test_schema();
+    let path = temp_db_path(&temp_dir);
+    let schema = || {
+      KiteOptions::new()
+        .node(NodeDef::new("Ticket", "ticket:").autoincrement())
+        .node(NodeDef::new("User", "user:"))
+    };
 
-    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+    let mut ray = Kite::open(&path, schema()).expect("expected value");
+    assert_eq!(ray.key_counter("Ticket").expect("expected value"), 0);
+    let first = ray.next_key("Ticket").expect("expected value");
+    assert_eq!(first, "1");
+    ray
+      .create_node("Ticket", &first, HashMap::new())
+      .expect("expected value");
 
-    // Use the builder pattern
-    let results = ray
-      .tx()
-      .create_node("User", "alice", HashMap::new())
-      .create_node("User", "bob", HashMap::new())
-      .execute(&mut ray)
+    // Explicit numeric keys are skipped over
+    ray
+      .create_node("Ticket", "2", HashMap::new())
+      .expect("expected value");
+    assert_eq!(ray.next_key("Ticket").expect("expected value"), "3");
+
+    let keys: HashSet<String> = std::thread::scope(|scope| {
+      let handles: Vec<_> = (0..4)
+        .map(|_| {
+          scope.spawn(|| {
+            (0..25)
+              .map(|_| ray.next_key("Ticket").expect("expected value"))
+              .collect::<Vec<_>>()
+          })
+        })
+        .collect();
+      handles
+        .into_iter()
+        .flat_map(|handle| handle.join().expect("expected value"))
+        .collect()
+    });
+    assert_eq!(keys.len(), 100);
+    assert_eq!(ray.key_counter("Ticket").expect("expected value"), 103);
+
+    assert!(matches!(
+      ray.next_key("User"),
+      Err(KiteError::InvalidSchema(_))
+    ));
+    ray
+      .create_node("Ticket", "7", HashMap::new())
       .expect("expected value");
+    ray.close().expect("expected value");
 
-    assert_eq!(results.len(), 2);
+    // The counter is persisted, so it resumes where it left off
+    let ray = Kite::open(&path, schema()).expect("expected value");
+    assert_eq!(ray.key_counter("Ticket").expect("expected value"), 103);
+    assert_eq!(ray.next_key("Ticket").expect("expected value"), "104");
+    ray.close().expect("expected value");
+  }
 
-    // Extract IDs and create edges
-    let alice_id = match &results[0] {
-      BatchResult::NodeCreated(node_ref) => node_ref.id(),
-      _ => panic!("Expected NodeCreated"),
+  #[test]
+  fn test_autoincrement_key_of_failed_insert_is_handed_out_again() {
+    let temp_dir = tempdir().expect("expected value");
+    let path = temp_db_path(&temp_dir);
+    let schema = || {
+      KiteOptions::new().node(
+        NodeDef::new("Ticket", "ticket:")
+          .autoincrement()
+          .prop(PropDef::string_enum("status", ["open"])),
+      )
     };
-    let bob_id = match &results[1] {
-      BatchResult::NodeCreated(node_ref) => node_ref.id(),
-      _ => panic!("Expected NodeCreated"),
+    let insert = |ray: &mut Kite, status: &str| {
+      let mut props = HashMap::new();
+      props.insert("status".to_string(), PropValue::String(status.to_string()));
+      ray.with_write_tx(|ray| {
+        let key = ray.next_key("Ticket")?;
+        ray.create_node("Ticket", &key, props)
+      })
     };
 
-    ray
-      .tx()
-      .link(alice_id, "FOLLOWS", bob_id)
-      .set_prop(alice_id, "name", PropValue::String("Alice".into()))
-      .execute(&mut ray)
-      .expect("expected value");
+    let mut ray = Kite::open(&path, schema()).expect("expected value");
+    let err = insert(&mut ray, "closed").expect_err("expected constraint error");
+    assert!(matches!(err, KiteError::Constraint(_)));
+    assert_eq!(ray.key_counter("Ticket").expect("expected value"), 0);
 
-    assert!(ray
-      .has_edge(alice_id, "FOLLOWS", bob_id)
-      .expect("expected value"));
-    assert_eq!(
-      ray.prop(alice_id, "name"),
-      Some(PropValue::String("Alice".into()))
-    );
+    let ticket = insert(&mut ray, "open").expect("expected value");
+    assert_eq!(ticket.key(), Some("ticket:1"));
+    ray.close().expect("expected value");
 
+    let ray = Kite::open(&path, schema()).expect("expected value");
+    assert_eq!(ray.key_counter("Ticket").expect("expected value"), 1);
     ray.close().expect("expected value");
   }
 
   #[test]
-  fn test_tx_builder_into_ops() {
-    // Test that into_ops returns the operations without executing
-    let ops = TxBuilder::default()
-      .create_node("User", "alice", HashMap::new())
-      .link(1, "FOLLOWS", 2)
-      .set_prop(1, "name", PropValue::String("Test".into()))
-      .into_ops();
+  fn test_autoincrement_keys_not_reused_after_delete_and_reopen() {
+    let temp_dir = tempdir().expect("expected value");
+    let path = temp_db_path(&temp_dir);
+    let schema = || KiteOptions::new().node(NodeDef::new("Ticket", "ticket:").autoincrement());
+
+    let mut ray = Kite::open(&path, schema()).expect("expected value");
+    let mut highest = 0;
+    for _ in 0..3 {
+      let key = ray.next_key("Ticket").expect("expected value");
+      highest = ray
+        .create_node("Ticket", &key, HashMap::new())
+        .expect("expected value")
+        .id();
+    }
+    ray.delete_node(highest).expect("expected value");
+    ray.close().expect("expected value");
 
-    assert_eq!(ops.len(), 3);
-  }
+    let mut ray = Kite::open(&path, schema()).expect("expected value");
+    assert_eq!(ray.next_key("Ticket").expect("expected value"), "4");
 
-  // ============================================================================
-  // Edge Property Tests
-  // ============================================================================
+    // The counter also survives a checkpoint into the snapshot
+    ray.optimize().expect("expected value");
+    ray.close().expect("expected value");
+    let ray = Kite::open(&path, schema()).expect("expected value");
+    assert_eq!(ray.next_key("Ticket").expect("expected value"), "5");
+    ray.close().expect("expected value");
+  }
 
   #[test]
-  fn test_link_with_props() {
+  fn test_link_by_key_creates_missing_endpoints() {
     let temp_dir = tempdir().expect("expected value");
-    let options = create_test_schema();
+    // No required props, so missing endpoints can be created bare
+    let options = KiteOptions::new()
+      .node(NodeDef::new("User", "user:").prop(PropDef::string("name")))
+      .edge(EdgeDef::new("FOLLOWS"));
 
     let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
     let alice = ray
       .create_node("User", "alice", HashMap::new())
       .expect("expected value");
+
+    // Missing endpoint without create_missing fails and leaves nothing behind
+    let err = ray
+      .link_by_key("User", "alice", "FOLLOWS", "User", "bob", false)
+      .expect_err("expected error");
+    assert!(matches!(err, KiteError::KeyNotFound(ref key) if key == "user:bob"));
+    assert_eq!(ray.count_nodes(), 1);
+
+    let (src, dst) = ray
+      .link_by_key("User", "alice", "FOLLOWS", "User", "bob", true)
+      .expect("expected value");
+    assert_eq!(src, alice.id);
     let bob = ray
-      .create_node("User", "bob", HashMap::new())
+      .get("User", "bob")
+      .expect("expected value")
+      .expect("bob created");
+    assert_eq!(dst, bob.id);
+    assert!(ray.has_edge(src, "FOLLOWS", dst).expect("expected value"));
+
+    // Existing endpoints are resolved, not recreated
+    let again = ray
+      .link_by_key("User", "bob", "FOLLOWS", "User", "alice", true)
       .expect("expected value");
+    assert_eq!(again, (bob.id, alice.id));
+    assert_eq!(ray.count_nodes(), 2);
 
-    // Link with properties
-    let mut props = HashMap::new();
-    props.insert("weight".to_string(), PropValue::F64(0.8));
-    props.insert("since".to_string(), PropValue::String("2024".into()));
+    ray.close().expect("expected value");
+  }
 
-    ray
-      .link_with_props(alice.id, "FOLLOWS", bob.id, props)
-      .expect("expected value");
+  #[test]
+  fn test_link_by_key_rejects_creating_endpoints_with_required_props() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = KiteOptions::new()
+      .node(NodeDef::new("User", "user:").prop(PropDef::string("name").required()))
+      .node(NodeDef::new("Tag", "tag:").prop(PropDef::string("label")))
+      .edge(EdgeDef::new("TAGGED"));
 
-    // Verify edge exists
-    assert!(ray
-      .has_edge(alice.id, "FOLLOWS", bob.id)
-      .expect("expected value"));
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
 
-    // Verify edge properties
-    let weight = ray
-      .edge_prop(alice.id, "FOLLOWS", bob.id, "weight")
-      .expect("expected value");
-    assert_eq!(weight, Some(PropValue::F64(0.8)));
+    // Alice would be created without her required name; the tag isn't kept either
+    let err = ray
+      .link_by_key("User", "alice", "TAGGED", "Tag", "rust", true)
+      .expect_err("expected error");
+    assert!(
+      matches!(err, KiteError::Constraint(ref msg) if msg == "Missing required properties for User: name")
+    );
+    assert_eq!(ray.count_nodes(), 0);
 
-    let since = ray
-      .edge_prop(alice.id, "FOLLOWS", bob.id, "since")
+    // Existing endpoints need no validation; types without required props
+    // can still be created
+    let mut props = HashMap::new();
+    props.insert("name".to_string(), PropValue::String("Alice".into()));
+    let alice = ray
+      .create_node("User", "alice", props)
       .expect("expected value");
-    assert_eq!(since, Some(PropValue::String("2024".into())));
+    let (src, dst) = ray
+      .link_by_key("User", "alice", "TAGGED", "Tag", "rust", true)
+      .expect("expected value");
+    assert_eq!(src, alice.id);
+    assert!(ray.has_edge(src, "TAGGED", dst).expect("expected value"));
+    assert_eq!(ray.count_nodes(), 2);
 
     ray.close().expect("expected value");
   }
@@ -5341,6 +11729,43 @@ mod tests {
     ray.close().expect("expected value");
   }
 
+  #[test]
+  fn test_upsert_edge_prop_creates_missing_edge() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = create_test_schema();
+
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
+
+    let created = ray
+      .upsert_edge_prop(alice.id, "FOLLOWS", bob.id, "weight", PropValue::F64(0.5))
+      .expect("expected value");
+    assert!(created);
+    assert!(ray
+      .has_edge(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value"));
+
+    let created = ray
+      .upsert_edge_prop(alice.id, "FOLLOWS", bob.id, "weight", PropValue::F64(0.9))
+      .expect("expected value");
+    assert!(!created);
+    assert_eq!(
+      ray
+        .edge_prop(alice.id, "FOLLOWS", bob.id, "weight")
+        .expect("expected value"),
+      Some(PropValue::F64(0.9))
+    );
+    assert_eq!(ray.count_edges(), 1);
+
+    ray.close().expect("expected value");
+  }
+
   #[test]
   fn test_set_edge_props() {
     let temp_dir = tempdir().expect("expected value");
@@ -5417,6 +11842,36 @@ mod tests {
     ray.close().expect("expected value");
   }
 
+  #[test]
+  fn test_edge_props_propless_edge_vs_missing_edge() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = create_test_schema();
+
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
+    ray
+      .link(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value");
+
+    let props = ray
+      .edge_props(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value");
+    assert_eq!(props, Some(HashMap::new()));
+
+    let props = ray
+      .edge_props(bob.id, "FOLLOWS", alice.id)
+      .expect("expected value");
+    assert_eq!(props, None);
+
+    ray.close().expect("expected value");
+  }
+
   #[test]
   fn test_del_edge_prop() {
     let temp_dir = tempdir().expect("expected value");
@@ -5558,6 +12013,57 @@ mod tests {
     ray.close().expect("expected value");
   }
 
+  #[test]
+  fn test_update_edge_builder_ensure_creates_missing_edge() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = create_test_schema();
+
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    let bob = ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
+
+    let created = ray
+      .update_edge(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value")
+      .ensure()
+      .set("since", PropValue::I64(2020))
+      .execute()
+      .expect("expected value");
+    assert!(created);
+    assert!(ray
+      .has_edge(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value"));
+    assert_eq!(
+      ray
+        .edge_prop(alice.id, "FOLLOWS", bob.id, "since")
+        .expect("expected value"),
+      Some(PropValue::I64(2020))
+    );
+
+    // The edge exists now, so only the props change
+    let created = ray
+      .update_edge(alice.id, "FOLLOWS", bob.id)
+      .expect("expected value")
+      .ensure()
+      .set("since", PropValue::I64(2021))
+      .execute()
+      .expect("expected value");
+    assert!(!created);
+    assert_eq!(
+      ray
+        .edge_prop(alice.id, "FOLLOWS", bob.id, "since")
+        .expect("expected value"),
+      Some(PropValue::I64(2021))
+    );
+
+    ray.close().expect("expected value");
+  }
+
   #[test]
   fn test_update_edge_builder_set_all() {
     let temp_dir = tempdir().expect("expected value");
@@ -6045,6 +12551,46 @@ mod tests {
     ray.close().expect("expected value");
   }
 
+  #[test]
+  fn test_upsert_by_id_rekeys_existing_node() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = create_test_schema();
+
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+    let alice = ray
+      .create_node("User", "alice", HashMap::new())
+      .expect("expected value");
+    ray
+      .create_node("User", "bob", HashMap::new())
+      .expect("expected value");
+
+    ray
+      .upsert_by_id("User", alice.id)
+      .expect("expected value")
+      .key("alicia")
+      .set("age", PropValue::I64(30))
+      .execute()
+      .expect("expected value");
+    assert!(ray.get("User", "alice").expect("expected value").is_none());
+    let renamed = ray
+      .get("User", "alicia")
+      .expect("expected value")
+      .expect("expected value");
+    assert_eq!(renamed.id, alice.id);
+
+    // Colliding with another node's key aborts the whole upsert.
+    let result = ray
+      .upsert_by_id("User", alice.id)
+      .expect("expected value")
+      .key("bob")
+      .set("age", PropValue::I64(99))
+      .execute();
+    assert!(matches!(result, Err(KiteError::DuplicateKey(_))));
+    assert_eq!(ray.prop(alice.id, "age"), Some(PropValue::I64(30)));
+
+    ray.close().expect("expected value");
+  }
+
   #[test]
   fn test_update_node_unset() {
     let temp_dir = tempdir().expect("expected value");
@@ -6225,4 +12771,43 @@ mod tests {
 
     ray.close().expect("expected value");
   }
+
+  #[test]
+  fn test_stats_fragmentation_drives_recommend_compact() {
+    let temp_dir = tempdir().expect("expected value");
+    let options = create_test_schema();
+
+    let mut ray = Kite::open(temp_db_path(&temp_dir), options).expect("expected value");
+
+    let mut users = Vec::new();
+    for i in 0..20 {
+      let user = ray
+        .create_node("User", &format!("u{i}"), HashMap::new())
+        .expect("expected value");
+      users.push(user.id);
+    }
+    ray.raw().checkpoint().expect("expected value");
+
+    let stats = ray.stats();
+    assert_eq!(stats.fragmentation, 0.0);
+    assert!(!stats.recommend_compact);
+
+    // 1 of 20 snapshot entries deleted: below the 10% threshold
+    ray.delete_node(users[0]).expect("expected value");
+    let stats = ray.stats();
+    assert!((stats.fragmentation - 0.05).abs() < 1e-9);
+    assert!(!stats.recommend_compact);
+
+    ray.delete_node(users[1]).expect("expected value");
+    let stats = ray.stats();
+    assert!((stats.fragmentation - 0.1).abs() < 1e-9);
+    assert!(stats.recommend_compact);
+
+    ray.raw().checkpoint().expect("expected value");
+    let stats = ray.stats();
+    assert_eq!(stats.fragmentation, 0.0);
+    assert!(!stats.recommend_compact);
+
+    ray.close().expect("expected value");
+  }
 }
@@ -46,6 +46,7 @@ use std::sync::Arc;
 /// - `Float` -> 64-bit IEEE 754 floats (PropValueTag::F64)
 /// - `Bool` -> booleans (PropValueTag::Bool)
 /// - `Vector` -> float32 vectors for embeddings (PropValueTag::VectorF32)
+/// - `Json` -> structured JSON documents (PropValueTag::Json)
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum SchemaType {
   String,
@@ -53,6 +54,7 @@ pub enum SchemaType {
   Float,
   Bool,
   Vector,
+  Json,
 }
 
 impl SchemaType {
@@ -64,6 +66,7 @@ impl SchemaType {
       SchemaType::Float => PropValueTag::F64,
       SchemaType::Bool => PropValueTag::Bool,
       SchemaType::Vector => PropValueTag::VectorF32,
+      SchemaType::Json => PropValueTag::Json,
     }
   }
 }
@@ -76,6 +79,7 @@ impl fmt::Display for SchemaType {
       SchemaType::Float => write!(f, "float"),
       SchemaType::Bool => write!(f, "bool"),
       SchemaType::Vector => write!(f, "vector"),
+      SchemaType::Json => write!(f, "json"),
     }
   }
 }
@@ -136,6 +140,7 @@ impl PropDef {
       (SchemaType::Float, PropValue::F64(_)) => true,
       (SchemaType::Bool, PropValue::Bool(_)) => true,
       (SchemaType::Vector, PropValue::VectorF32(_)) => true,
+      (SchemaType::Json, PropValue::Json(_)) => true,
       (_, PropValue::Null) => self.optional,
       _ => false,
     }
@@ -202,6 +207,13 @@ pub mod prop {
   pub fn vector(name: &str) -> PropDef {
     PropDef::new(name, SchemaType::Vector)
   }
+
+  /// Create a JSON document property
+  ///
+  /// Stored as serialized JSON (maps to PropValueTag::Json)
+  pub fn json(name: &str) -> PropDef {
+    PropDef::new(name, SchemaType::Json)
+  }
 }
 
 // ============================================================================
@@ -941,6 +953,7 @@ mod tests {
     assert_eq!(SchemaType::Float.to_tag(), PropValueTag::F64);
     assert_eq!(SchemaType::Bool.to_tag(), PropValueTag::Bool);
     assert_eq!(SchemaType::Vector.to_tag(), PropValueTag::VectorF32);
+    assert_eq!(SchemaType::Json.to_tag(), PropValueTag::Json);
   }
 
   #[test]
@@ -5,7 +5,10 @@
 //! Ported from src/api/traversal.ts
 
 use crate::types::{ETypeId, Edge, NodeId, PropValue};
-use std::collections::{HashMap, HashSet, VecDeque};
+use rand::rngs::StdRng;
+use rand::SeedableRng;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, HashMap, HashSet, VecDeque};
 use std::sync::Arc;
 
 /// Type alias for edge filter predicates
@@ -41,6 +44,11 @@ pub struct TraverseOptions {
   pub where_edge: Option<EdgeFilter>,
   /// Node filter predicate for variable-depth traversal
   pub where_node: Option<NodeFilter>,
+  /// Expand at most this many neighbors per node, sampled when a node has
+  /// more (None = expand all). Results are approximate once sampling kicks in.
+  pub max_neighbors_per_node: Option<usize>,
+  /// Seed for neighbor sampling; the same seed picks the same neighbors
+  pub sample_seed: u64,
 }
 
 impl std::fmt::Debug for TraverseOptions {
@@ -52,6 +60,8 @@ impl std::fmt::Debug for TraverseOptions {
       .field("unique", &self.unique)
       .field("where_edge", &self.where_edge.as_ref().map(|_| "<fn>"))
       .field("where_node", &self.where_node.as_ref().map(|_| "<fn>"))
+      .field("max_neighbors_per_node", &self.max_neighbors_per_node)
+      .field("sample_seed", &self.sample_seed)
       .finish()
   }
 }
@@ -65,6 +75,8 @@ impl Default for TraverseOptions {
       unique: true,
       where_edge: None,
       where_node: None,
+      max_neighbors_per_node: None,
+      sample_seed: 0,
     }
   }
 }
@@ -78,6 +90,8 @@ impl TraverseOptions {
       unique: true,
       where_edge: None,
       where_node: None,
+      max_neighbors_per_node: None,
+      sample_seed: 0,
     }
   }
 
@@ -108,6 +122,43 @@ impl TraverseOptions {
     self.where_node = Some(Arc::new(predicate));
     self
   }
+
+  /// Expand at most `max` neighbors per node, sampling supernodes
+  ///
+  /// Nodes with more neighbors get `max` of them picked deterministically
+  /// from `seed` and the node, and results reached through them are marked
+  /// `sampled`. The traversal is then approximate: nodes only reachable
+  /// through skipped neighbors are missed.
+  pub fn with_max_neighbors_per_node(mut self, max: usize, seed: u64) -> Self {
+    self.max_neighbors_per_node = Some(max);
+    self.sample_seed = seed;
+    self
+  }
+}
+
+/// Keep at most `max` of `items`, chosen deterministically from `seed` and
+/// `node_id`, in their original order. Returns whether any were dropped.
+fn sample_neighbors<T>(items: &mut Vec<T>, max: Option<usize>, seed: u64, node_id: NodeId) -> bool {
+  let Some(max) = max else {
+    return false;
+  };
+  if items.len() <= max {
+    return false;
+  }
+  let mut rng = StdRng::seed_from_u64(seed ^ node_id.wrapping_mul(0x9E37_79B9_7F4A_7C15));
+  let mut keep = rand::seq::index::sample(&mut rng, items.len(), max).into_vec();
+  keep.sort_unstable();
+  let mut keep = keep.into_iter().peekable();
+  let mut index = 0;
+  items.retain(|_| {
+    let kept = keep.peek() == Some(&index);
+    if kept {
+      keep.next();
+    }
+    index += 1;
+    kept
+  });
+  true
 }
 
 /// Raw edge data without any property loading
@@ -116,6 +167,24 @@ pub struct RawEdge {
   pub src: NodeId,
   pub dst: NodeId,
   pub etype: ETypeId,
+  /// How the edge was followed: `Out` from `src`, or `In` from `dst`
+  /// (never `Both`; self-loops count as `Out`)
+  pub via_direction: TraversalDirection,
+}
+
+impl RawEdge {
+  /// Edge reached from `from` while traversing in `direction`
+  pub fn followed(edge: Edge, from: NodeId, direction: TraversalDirection) -> Self {
+    let via_direction = match direction {
+      TraversalDirection::Both if edge.src == from => TraversalDirection::Out,
+      TraversalDirection::Both => TraversalDirection::In,
+      dir => dir,
+    };
+    Self {
+      via_direction,
+      ..Self::from(edge)
+    }
+  }
 }
 
 impl From<Edge> for RawEdge {
@@ -124,6 +193,7 @@ impl From<Edge> for RawEdge {
       src: edge.src,
       dst: edge.dst,
       etype: edge.etype,
+      via_direction: TraversalDirection::Out,
     }
   }
 }
@@ -143,6 +213,9 @@ pub struct TraversalResult {
   pub node_id: NodeId,
   pub edge: Option<RawEdge>,
   pub depth: usize,
+  /// Reached through a node whose neighbors were sampled (see
+  /// `TraverseOptions::max_neighbors_per_node`)
+  pub sampled: bool,
 }
 
 /// Edge info for filter predicates
@@ -581,6 +654,142 @@ impl TraversalBuilder {
   {
     RawEdgeIterator::new(self, neighbors)
   }
+
+  /// Follow the steps and return the `k` cheapest reached nodes
+  ///
+  /// Each node keeps the smallest accumulated `edge_weight` over all paths
+  /// that reach it at a step; a variable-depth step keeps the cheapest cost
+  /// over every depth in `min_depth..=max_depth`. Results are `(node, cost)`
+  /// pairs, cheapest first with ties broken by node ID. Filters, `take`,
+  /// and `unique` don't apply.
+  pub fn top_k<F, W>(&self, k: usize, neighbors: F, edge_weight: W) -> Vec<(NodeId, f64)>
+  where
+    F: Fn(NodeId, TraversalDirection, Option<ETypeId>) -> Vec<Edge>,
+    W: Fn(NodeId, ETypeId, NodeId) -> f64,
+  {
+    if k == 0 {
+      return Vec::new();
+    }
+    let relax =
+      |frontier: &HashMap<NodeId, f64>, direction: TraversalDirection, etype: Option<ETypeId>| {
+        let mut next: HashMap<NodeId, f64> = HashMap::new();
+        for (&node_id, &cost) in frontier {
+          for edge in neighbors(node_id, direction, etype) {
+            let other = if edge.src == node_id {
+              edge.dst
+            } else {
+              edge.src
+            };
+            let total = cost + edge_weight(edge.src, edge.etype, edge.dst);
+            next
+              .entry(other)
+              .and_modify(|best| *best = best.min(total))
+              .or_insert(total);
+          }
+        }
+        next
+      };
+
+    let mut frontier: HashMap<NodeId, f64> = self.start_nodes.iter().map(|&id| (id, 0.0)).collect();
+    for step in &self.steps {
+      frontier = match step {
+        TraversalStep::SingleHop {
+          direction, etype, ..
+        } => relax(&frontier, *direction, *etype),
+        TraversalStep::Traverse { etype, options } => {
+          let mut reached: HashMap<NodeId, f64> = HashMap::new();
+          let mut layer = frontier;
+          for depth in 1..=options.max_depth {
+            layer = relax(&layer, options.direction, *etype);
+            if layer.is_empty() {
+              break;
+            }
+            if depth >= options.min_depth {
+              for (&node_id, &cost) in &layer {
+                reached
+                  .entry(node_id)
+                  .and_modify(|best| *best = best.min(cost))
+                  .or_insert(cost);
+              }
+            }
+          }
+          reached
+        }
+      };
+    }
+
+    // Bounded max-heap: the most expensive of the current k sits on top
+    let mut heap: BinaryHeap<CostEntry> = BinaryHeap::with_capacity(k.min(frontier.len()) + 1);
+    for (node_id, cost) in frontier {
+      heap.push(CostEntry { cost, node_id });
+      if heap.len() > k {
+        heap.pop();
+      }
+    }
+    heap
+      .into_sorted_vec()
+      .into_iter()
+      .map(|entry| (entry.node_id, entry.cost))
+      .collect()
+  }
+}
+
+/// Heap entry for [`TraversalBuilder::top_k`], ordered by the full `f64` cost
+#[derive(Debug, Clone, Copy)]
+struct CostEntry {
+  cost: f64,
+  node_id: NodeId,
+}
+
+impl PartialEq for CostEntry {
+  fn eq(&self, other: &Self) -> bool {
+    self.cmp(other) == Ordering::Equal
+  }
+}
+
+impl Eq for CostEntry {}
+
+impl Ord for CostEntry {
+  fn cmp(&self, other: &Self) -> Ordering {
+    self
+      .cost
+      .total_cmp(&other.cost)
+      .then(self.node_id.cmp(&other.node_id))
+  }
+}
+
+impl PartialOrd for CostEntry {
+  fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+    Some(self.cmp(other))
+  }
+}
+
+/// Edge weight for a stored weight property value
+///
+/// Numbers are used as-is, booleans count as 1 or 0, and numeric strings are
+/// parsed. Missing, non-numeric, non-finite, or non-positive weights count
+/// as 1.
+pub fn prop_value_to_weight(value: Option<PropValue>) -> f64 {
+  let weight = match value {
+    Some(PropValue::Bool(v)) => {
+      if v {
+        1.0
+      } else {
+        0.0
+      }
+    }
+    Some(PropValue::I64(v)) => v as f64,
+    Some(PropValue::F64(v)) => v,
+    Some(PropValue::String(v)) => v.parse::<f64>().unwrap_or(1.0),
+    Some(PropValue::VectorF32(_)) | Some(PropValue::Json(_)) => 1.0,
+    Some(PropValue::Null) | None => 1.0,
+  };
+
+  if weight.is_finite() && weight > 0.0 {
+    weight
+  } else {
+    1.0
+  }
 }
 
 // ============================================================================
@@ -630,6 +839,7 @@ where
         node_id,
         edge: None,
         depth: 0,
+        sampled: false,
       });
     }
 
@@ -705,7 +915,7 @@ where
           continue;
         }
 
-        let raw_edge = RawEdge::from(edge);
+        let raw_edge = RawEdge::followed(edge, result.node_id, direction);
 
         // Apply step-level edge filter
         if let Some(ref edge_filter) = step_edge_filter {
@@ -734,6 +944,7 @@ where
           node_id: neighbor_id,
           edge: Some(raw_edge),
           depth: result.depth + 1,
+          sampled: result.sampled,
         });
       }
     }
@@ -754,14 +965,14 @@ where
       HashSet::new()
     };
 
-    // BFS queue: (node_id, depth)
-    let mut queue: VecDeque<(NodeId, usize)> = self
+    // BFS queue: (node_id, depth, reached through a sampled node)
+    let mut queue: VecDeque<(NodeId, usize, bool)> = self
       .current_frontier
       .drain(..)
-      .map(|r| (r.node_id, 0))
+      .map(|r| (r.node_id, 0, r.sampled))
       .collect();
 
-    while let Some((current_id, depth)) = queue.pop_front() {
+    while let Some((current_id, depth, sampled_via)) = queue.pop_front() {
       if depth >= options.max_depth {
         continue;
       }
@@ -772,69 +983,78 @@ where
         dir => vec![dir],
       };
 
+      let mut edges: Vec<(TraversalDirection, Edge)> = Vec::new();
       for dir in directions {
-        let edges = (self.neighbors)(current_id, dir, etype);
-
-        for edge in edges {
-          let neighbor_id = match dir {
-            TraversalDirection::Out => edge.dst,
-            TraversalDirection::In => edge.src,
-            TraversalDirection::Both => unreachable!(),
-          };
+        let dir_edges = (self.neighbors)(current_id, dir, etype);
+        edges.extend(dir_edges.into_iter().map(|edge| (dir, edge)));
+      }
+      let sampled = sample_neighbors(
+        &mut edges,
+        options.max_neighbors_per_node,
+        options.sample_seed,
+        current_id,
+      ) || sampled_via;
+
+      for (dir, edge) in edges {
+        let neighbor_id = match dir {
+          TraversalDirection::Out => edge.dst,
+          TraversalDirection::In => edge.src,
+          TraversalDirection::Both => unreachable!(),
+        };
 
-          // Check uniqueness
-          if options.unique && local_visited.contains(&neighbor_id) {
-            continue;
-          }
+        // Check uniqueness
+        if options.unique && local_visited.contains(&neighbor_id) {
+          continue;
+        }
 
-          let raw_edge = RawEdge::from(edge);
+        let raw_edge = RawEdge::followed(edge, current_id, dir);
 
-          // Apply edge filter from TraverseOptions
-          if let Some(ref edge_filter) = options.where_edge {
-            let edge_info = EdgeInfo::from(raw_edge);
-            if !edge_filter(&edge_info) {
-              continue;
-            }
+        // Apply edge filter from TraverseOptions
+        if let Some(ref edge_filter) = options.where_edge {
+          let edge_info = EdgeInfo::from(raw_edge);
+          if !edge_filter(&edge_info) {
+            continue;
           }
+        }
 
-          // Apply node filter from TraverseOptions
-          if let Some(ref node_filter) = options.where_node {
-            let node_info = NodeInfo {
-              id: neighbor_id,
-              props: HashMap::new(),
-            };
-            if !node_filter(&node_info) {
-              continue;
-            }
+        // Apply node filter from TraverseOptions
+        if let Some(ref node_filter) = options.where_node {
+          let node_info = NodeInfo {
+            id: neighbor_id,
+            props: HashMap::new(),
+          };
+          if !node_filter(&node_info) {
+            continue;
           }
+        }
 
-          if options.unique {
-            local_visited.insert(neighbor_id);
-          }
+        if options.unique {
+          local_visited.insert(neighbor_id);
+        }
 
-          // Also check global visited set
-          if self.unique_nodes && self.visited.contains(&neighbor_id) {
-            continue;
-          }
-          if self.unique_nodes {
-            self.visited.insert(neighbor_id);
-          }
+        // Also check global visited set
+        if self.unique_nodes && self.visited.contains(&neighbor_id) {
+          continue;
+        }
+        if self.unique_nodes {
+          self.visited.insert(neighbor_id);
+        }
 
-          let new_depth = depth + 1;
+        let new_depth = depth + 1;
 
-          // Yield if at or past min_depth
-          if new_depth >= options.min_depth {
-            results.push_back(TraversalResult {
-              node_id: neighbor_id,
-              edge: Some(raw_edge),
-              depth: new_depth,
-            });
-          }
+        // Yield if at or past min_depth
+        if new_depth >= options.min_depth {
+          results.push_back(TraversalResult {
+            node_id: neighbor_id,
+            edge: Some(raw_edge),
+            depth: new_depth,
+            sampled,
+          });
+        }
 
-          // Continue BFS if not at max depth
-          if new_depth < options.max_depth {
-            queue.push_back((neighbor_id, new_depth));
-          }
+        // Continue BFS if not at max depth
+        if new_depth < options.max_depth {
+          queue.push_back((neighbor_id, new_depth, sampled));
         }
       }
     }
@@ -975,7 +1195,9 @@ where
 
           let edges = (self.neighbors)(node_id, *direction, *etype);
           for edge in edges {
-            self.pending_edges.push_back(RawEdge::from(edge));
+            self
+              .pending_edges
+              .push_back(RawEdge::followed(edge, node_id, *direction));
           }
         }
       } else {
@@ -1229,6 +1451,200 @@ impl TraversalBuilder {
   }
 }
 
+// ============================================================================
+// Spanning Tree
+// ============================================================================
+
+/// A node of a spanning tree, with the node it was first discovered from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SpanningTreeNode {
+  /// Node ID
+  pub node_id: NodeId,
+  /// Node this one was first reached from (None for the root)
+  pub parent_id: Option<NodeId>,
+  /// Hops from the root
+  pub depth: usize,
+}
+
+/// Build the breadth-first spanning tree rooted at `source`
+///
+/// Every node reachable within `max_depth` hops appears exactly once, paired
+/// with the node it was first discovered from, so its depth is its shortest
+/// hop distance. Results are in BFS order, starting with the root.
+pub fn spanning_tree<F>(
+  source: NodeId,
+  etype: Option<ETypeId>,
+  direction: TraversalDirection,
+  max_depth: usize,
+  neighbors: F,
+) -> Vec<SpanningTreeNode>
+where
+  F: Fn(NodeId, TraversalDirection, Option<ETypeId>) -> Vec<Edge>,
+{
+  let mut tree = vec![SpanningTreeNode {
+    node_id: source,
+    parent_id: None,
+    depth: 0,
+  }];
+  let mut visited: HashSet<NodeId> = HashSet::from([source]);
+  let mut next = 0;
+
+  while next < tree.len() {
+    let SpanningTreeNode { node_id, depth, .. } = tree[next];
+    next += 1;
+    if depth >= max_depth {
+      continue;
+    }
+    for edge in neighbors(node_id, direction, etype) {
+      let other = if edge.src == node_id {
+        edge.dst
+      } else {
+        edge.src
+      };
+      if visited.insert(other) {
+        tree.push(SpanningTreeNode {
+          node_id: other,
+          parent_id: Some(node_id),
+          depth: depth + 1,
+        });
+      }
+    }
+  }
+
+  tree
+}
+
+// ============================================================================
+// Exploration Session
+// ============================================================================
+
+/// Incremental exploration that only reports each node once
+///
+/// Interactive explorers expand one node at a time and keep overlapping
+/// regions on screen. The session remembers every node it has returned
+/// (starting with the root), so [`Exploration::expand`] yields only newly
+/// discovered neighbors. Drop the session to release the visited set.
+#[derive(Debug, Clone)]
+pub struct Exploration {
+  root: NodeId,
+  visited: HashSet<NodeId>,
+}
+
+impl Exploration {
+  /// Start a session at `root`, which counts as already visited
+  pub fn new(root: NodeId) -> Self {
+    Self {
+      root,
+      visited: HashSet::from([root]),
+    }
+  }
+
+  /// Node the session started from
+  pub fn root(&self) -> NodeId {
+    self.root
+  }
+
+  /// Neighbors of `node_id` not returned before, in edge order
+  ///
+  /// `node_id` itself is marked visited, so expanding a node reached some
+  /// other way doesn't report it later.
+  pub fn expand<F>(
+    &mut self,
+    node_id: NodeId,
+    etype: Option<ETypeId>,
+    direction: TraversalDirection,
+    neighbors: F,
+  ) -> Vec<NodeId>
+  where
+    F: Fn(NodeId, TraversalDirection, Option<ETypeId>) -> Vec<Edge>,
+  {
+    self.visited.insert(node_id);
+    neighbors(node_id, direction, etype)
+      .into_iter()
+      .map(|edge| {
+        if edge.src == node_id {
+          edge.dst
+        } else {
+          edge.src
+        }
+      })
+      .filter(|&other| self.visited.insert(other))
+      .collect()
+  }
+
+  /// Whether the session has already returned `node_id`
+  pub fn is_visited(&self, node_id: NodeId) -> bool {
+    self.visited.contains(&node_id)
+  }
+
+  /// Number of nodes returned so far, root included
+  pub fn visited_count(&self) -> usize {
+    self.visited.len()
+  }
+}
+
+// ============================================================================
+// Reachability Cursor
+// ============================================================================
+
+/// Breadth-first reachability walk that returns one depth level per call
+///
+/// Progressive UIs can render level by level and stop once they have
+/// enough, instead of waiting for a whole reachability query. The cursor
+/// keeps its visited set and current frontier between calls.
+#[derive(Debug, Clone)]
+pub struct ReachableCursor {
+  etype: Option<ETypeId>,
+  depth: usize,
+  frontier: Vec<NodeId>,
+  visited: HashSet<NodeId>,
+}
+
+impl ReachableCursor {
+  /// Start at `source`, which counts as visited and is never returned
+  pub fn new(source: NodeId, etype: Option<ETypeId>) -> Self {
+    Self {
+      etype,
+      depth: 0,
+      frontier: vec![source],
+      visited: HashSet::from([source]),
+    }
+  }
+
+  /// Nodes first reached at the next depth over outgoing edges, in
+  /// discovery order
+  ///
+  /// Returns an empty level once everything reachable has been returned.
+  pub fn next_level<F>(&mut self, neighbors: F) -> Vec<NodeId>
+  where
+    F: Fn(NodeId, TraversalDirection, Option<ETypeId>) -> Vec<Edge>,
+  {
+    let mut level = Vec::new();
+    for &node_id in &self.frontier {
+      for edge in neighbors(node_id, TraversalDirection::Out, self.etype) {
+        if self.visited.insert(edge.dst) {
+          level.push(edge.dst);
+        }
+      }
+    }
+    if !level.is_empty() {
+      self.depth += 1;
+    }
+    self.frontier.clone_from(&level);
+    level
+  }
+
+  /// Depth of the last non-empty level returned (0 before the first)
+  pub fn depth(&self) -> usize {
+    self.depth
+  }
+
+  /// Whether every reachable node has been returned
+  pub fn is_done(&self) -> bool {
+    self.frontier.is_empty()
+  }
+}
+
 // ============================================================================
 // Extended Result Types with Properties
 // ============================================================================
@@ -1484,6 +1900,56 @@ mod tests {
     }
   }
 
+  #[test]
+  fn test_spanning_tree_records_first_discovery_parent() {
+    let neighbors = mock_graph();
+
+    let tree = spanning_tree(1, None, TraversalDirection::Out, 10, &neighbors);
+    let parents: Vec<_> = tree
+      .iter()
+      .map(|n| (n.node_id, n.parent_id, n.depth))
+      .collect();
+    assert_eq!(
+      parents,
+      vec![
+        (1, None, 0),
+        (2, Some(1), 1),
+        (4, Some(1), 1),
+        (3, Some(2), 2),
+        (5, Some(2), 2),
+      ]
+    );
+
+    // Walking both ways doesn't revisit the node a hop came from
+    let tree = spanning_tree(3, Some(1), TraversalDirection::Both, 10, &neighbors);
+    let parents: Vec<_> = tree.iter().map(|n| (n.node_id, n.parent_id)).collect();
+    assert_eq!(parents, vec![(3, None), (2, Some(3)), (1, Some(2))]);
+
+    let tree = spanning_tree(3, Some(1), TraversalDirection::Both, 1, &neighbors);
+    assert_eq!(tree.len(), 2);
+  }
+
+  #[test]
+  fn test_exploration_returns_each_node_once() {
+    let neighbors = mock_graph();
+    let mut session = Exploration::new(1);
+
+    assert_eq!(
+      session.expand(1, None, TraversalDirection::Out, &neighbors),
+      vec![2, 4]
+    );
+    assert_eq!(
+      session.expand(2, None, TraversalDirection::Both, &neighbors),
+      vec![3, 5]
+    );
+    // Everything around 1 is already known
+    assert!(session
+      .expand(1, None, TraversalDirection::Both, &neighbors)
+      .is_empty());
+    assert!(session.is_visited(5));
+    assert_eq!(session.visited_count(), 5);
+  }
+
   #[test]
   fn test_single_hop_out() {
     let neighbors = mock_graph();
@@ -1539,6 +2005,44 @@ mod tests {
     assert_eq!(results[0].node_id, 2);
   }
 
+  #[test]
+  fn test_both_reports_via_direction() {
+    let neighbors = mock_graph();
+
+    let via = |results: Vec<TraversalResult>| {
+      let mut via: Vec<_> = results
+        .into_iter()
+        .map(|r| (r.node_id, r.edge.expect("expected value").via_direction))
+        .collect();
+      via.sort_by_key(|(node_id, _)| *node_id);
+      via
+    };
+    let expected = vec![
+      (1, TraversalDirection::In),
+      (3, TraversalDirection::Out),
+      (5, TraversalDirection::Out),
+    ];
+
+    let single_hop = TraversalBuilder::from_node(2)
+      .both(None)
+      .execute(&neighbors);
+    assert_eq!(via(single_hop.collect()), expected);
+
+    let traverse = TraversalBuilder::from_node(2)
+      .traverse(None, TraverseOptions::new(TraversalDirection::Both, 1))
+      .execute(&neighbors);
+    assert_eq!(via(traverse.collect()), expected);
+
+    let incoming: Vec<_> = TraversalBuilder::from_node(3)
+      .r#in(None)
+      .execute(&neighbors)
+      .collect();
+    assert_eq!(
+      incoming[0].edge.map(|edge| edge.via_direction),
+      Some(TraversalDirection::In)
+    );
+  }
+
   #[test]
   fn test_take_limit() {
     let neighbors = mock_graph();
@@ -1605,6 +2109,55 @@ mod tests {
     assert_eq!(results[0].node_id, 3);
   }
 
+  #[test]
+  fn test_traverse_samples_supernode_neighbors() {
+    // Node 1 is a supernode with 100 out-neighbors, each leading on to one more
+    let neighbors = |node_id: NodeId, direction: TraversalDirection, _: Option<ETypeId>| match (
+      direction, node_id,
+    ) {
+      (TraversalDirection::Out, 1) => (100..200)
+        .map(|dst| Edge {
+          src: 1,
+          etype: 1,
+          dst,
+        })
+        .collect(),
+      (TraversalDirection::Out, 100..=199) => vec![Edge {
+        src: node_id,
+        etype: 1,
+        dst: node_id + 1000,
+      }],
+      _ => Vec::new(),
+    };
+    let run = |seed: u64| -> Vec<TraversalResult> {
+      let options =
+        TraverseOptions::new(TraversalDirection::Out, 2).with_max_neighbors_per_node(10, seed);
+      TraversalBuilder::from_node(1)
+        .traverse(None, options)
+        .execute(neighbors)
+        .collect()
+    };
+
+    let results = run(7);
+    assert_eq!(results.iter().filter(|r| r.depth == 1).count(), 10);
+    assert_eq!(results.iter().filter(|r| r.depth == 2).count(), 10);
+    assert!(results.iter().all(|r| r.sampled));
+
+    // Same seed, same sample
+    let ids = |results: &[TraversalResult]| results.iter().map(|r| r.node_id).collect::<Vec<_>>();
+    assert_eq!(ids(&results), ids(&run(7)));
+
+    // Under the cap nothing is sampled
+    let options =
+      TraverseOptions::new(TraversalDirection::Out, 2).with_max_neighbors_per_node(1000, 7);
+    let results: Vec<_> = TraversalBuilder::from_node(1)
+      .traverse(None, options)
+      .execute(neighbors)
+      .collect();
+    assert_eq!(results.len(), 200);
+    assert!(results.iter().all(|r| !r.sampled));
+  }
+
   #[test]
   fn test_multiple_start_nodes() {
     let neighbors = mock_graph();
@@ -1912,6 +2465,7 @@ mod tests {
       src: 1,
       dst: 2,
       etype: 3,
+      via_direction: TraversalDirection::Out,
     };
 
     let edge_info = EdgeInfo::from(raw_edge);
@@ -2096,6 +2650,7 @@ mod tests {
       src: 1,
       dst: 2,
       etype: 3,
+      via_direction: TraversalDirection::Out,
     };
 
     let mut props = HashMap::new();
@@ -2248,4 +2803,75 @@ mod tests {
     assert_eq!(results.len(), 1);
     assert_eq!(results[0].node_id, 2);
   }
+
+  #[test]
+  fn test_top_k_prefers_cheapest_path_over_shortest() {
+    // 1 --(10)--> 4 directly, or 1 --(1)--> 2 --(1)--> 4; 1 --(1e-9)--> 3
+    let edges: Vec<(Edge, f64)> = vec![
+      (
+        Edge {
+          src: 1,
+          etype: 1,
+          dst: 4,
+        },
+        10.0,
+      ),
+      (
+        Edge {
+          src: 1,
+          etype: 1,
+          dst: 2,
+        },
+        1.0,
+      ),
+      (
+        Edge {
+          src: 2,
+          etype: 1,
+          dst: 4,
+        },
+        1.0,
+      ),
+      (
+        Edge {
+          src: 1,
+          etype: 1,
+          dst: 3,
+        },
+        1.0 + 1e-9,
+      ),
+    ];
+    let neighbors = |node_id: NodeId, _dir: TraversalDirection, _etype: Option<ETypeId>| {
+      edges
+        .iter()
+        .filter(|(edge, _)| edge.src == node_id)
+        .map(|(edge, _)| *edge)
+        .collect::<Vec<_>>()
+    };
+    let weight = |src: NodeId, _etype: ETypeId, dst: NodeId| {
+      edges
+        .iter()
+        .find(|(edge, _)| edge.src == src && edge.dst == dst)
+        .map(|(_, weight)| *weight)
+        .unwrap_or(1.0)
+    };
+
+    let builder = TraversalBuilder::from_node(1)
+      .traverse(None, TraverseOptions::new(TraversalDirection::Out, 2));
+    let hits = builder.top_k(3, neighbors, weight);
+    // Costs differing below f32 precision still order correctly
+    assert_eq!(hits, vec![(2, 1.0), (3, 1.0 + 1e-9), (4, 2.0)]);
+
+    let hits = builder.top_k(1, neighbors, weight);
+    assert_eq!(hits, vec![(2, 1.0)]);
+    assert!(builder.top_k(0, neighbors, weight).is_empty());
+    // A huge k is only bounded by the reached nodes
+    assert_eq!(builder.top_k(usize::MAX, neighbors, weight).len(), 3);
+
+    // A single hop can only take the direct, expensive edge to 4
+    let hits = TraversalBuilder::from_node(1)
+      .out(Some(1))
+      .top_k(3, neighbors, weight);
+    assert_eq!(hits.last(), Some(&(4, 10.0)));
+  }
 }
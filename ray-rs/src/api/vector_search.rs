@@ -413,7 +413,7 @@ impl VectorIndex {
         if let Some(stored_vector) = vector_store_node_vector(&self.manifest, node_id) {
           let insert_result = match index {
             BuiltIndex::Ivf(ivf_index) => ivf_index
-              .insert(vector_id as u64, stored_vector)
+              .insert(vector_id as u64, stored_vector, true)
               .map_err(ivf_error_to_index_error),
             BuiltIndex::IvfPq(ivf_pq_index) => ivf_pq_index
               .insert(vector_id as u64, stored_vector)
@@ -546,7 +546,7 @@ impl VectorIndex {
         for (i, &vector_id) in vector_ids.iter().enumerate() {
           let offset = i * dimensions;
           let vector = &training_data[offset..offset + dimensions];
-          if let Err(err) = index.insert(vector_id, vector) {
+          if let Err(err) = index.insert(vector_id, vector, false) {
             self.index = None;
             self.needs_training = true;
             return Err(ivf_error_to_index_error(err));
@@ -3,12 +3,16 @@
 //! Core implementation used by bindings.
 
 use std::fs;
+use std::io::{Read, Seek, SeekFrom, Write};
 use std::path::{Path, PathBuf};
 use std::time::{SystemTime, UNIX_EPOCH};
 
-use crate::constants::EXT_KITEDB;
-use crate::core::single_file::SingleFileDB;
+use crate::constants::{DB_FLAG_EXTERNAL_WAL, EXT_KITEDB, MAX_PAGE_SIZE, MIN_READER_SINGLE_FILE};
+use crate::core::single_file::{
+  close_single_file, open_single_file, SingleFileDB, SingleFileOpenOptions,
+};
 use crate::error::{KiteError, Result};
+use crate::types::DbHeaderV1;
 
 /// Backup options
 #[derive(Debug, Clone)]
@@ -51,6 +55,11 @@ pub struct BackupResult {
   pub kind: String,
 }
 
+/// Back up an open single-file database
+///
+/// A database keeping its WAL in an external file is always checkpointed
+/// first, and the backup gets a WAL region of its own, so it doesn't depend
+/// on the WAL file. Commits made after that checkpoint are not in it.
 pub fn create_backup_single_file(
   db: &SingleFileDB,
   backup_path: impl AsRef<Path>,
@@ -68,7 +77,17 @@ pub fn create_backup_single_file(
     backup_path = PathBuf::from(format!("{}{}", backup_path.to_string_lossy(), EXT_KITEDB));
   }
 
-  if options.checkpoint && !db.read_only {
+  let external_wal = db.header.read().wal_path.is_some();
+  if external_wal {
+    if !db.read_only {
+      db.checkpoint()?;
+    } else if db.header.read().wal_head != 0 {
+      return Err(KiteError::Internal(format!(
+        "Cannot back up {} read-only: its external WAL holds records not yet checkpointed",
+        db.path.display()
+      )));
+    }
+  } else if options.checkpoint && !db.read_only {
     db.checkpoint()?;
   }
 
@@ -78,8 +97,13 @@ pub fn create_backup_single_file(
     remove_existing(&backup_path)?;
   }
 
-  copy_file_with_size(&db.path, &backup_path)?;
-  let size = fs::metadata(&backup_path)?.len();
+  let size = if external_wal {
+    // Keep checkpoints from rewriting the file mid-copy
+    let _pager = db.pager.lock();
+    copy_without_external_wal(&db.path, &backup_path)?
+  } else {
+    copy_file_with_size(&db.path, &backup_path)?
+  };
 
   Ok(backup_result(
     &backup_path,
@@ -114,6 +138,10 @@ pub fn restore_backup(
     ));
   }
 
+  if let Some(wal_path) = recorded_external_wal(&backup_path)? {
+    return Err(external_wal_error(&backup_path, &wal_path));
+  }
+
   if !restore_path.to_string_lossy().ends_with(EXT_KITEDB) {
     restore_path = PathBuf::from(format!("{}{}", restore_path.to_string_lossy(), EXT_KITEDB));
   }
@@ -151,6 +179,47 @@ pub fn backup_info(backup_path: impl AsRef<Path>) -> Result<BackupResult> {
   }
 }
 
+/// List the restorable `.kitedb` backups in a directory, newest first.
+///
+/// Entries [`restore_backup`] would refuse are skipped: directories, files
+/// that aren't KiteDB databases, and copies of databases that keep their WAL
+/// in an external file. Failing to read the directory is an I/O error.
+pub fn list_backups(dir: impl AsRef<Path>) -> Result<Vec<BackupResult>> {
+  let mut backups = Vec::new();
+  for entry in fs::read_dir(dir.as_ref())? {
+    let path = entry?.path();
+    if !path.to_string_lossy().ends_with(EXT_KITEDB) {
+      continue;
+    }
+    let info = match backup_info(&path) {
+      Ok(info) => info,
+      // A directory, or an entry removed since it was listed
+      Err(KiteError::Internal(_)) => continue,
+      Err(KiteError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => continue,
+      Err(err) => return Err(err),
+    };
+    match recorded_external_wal(&path) {
+      Ok(None) => {}
+      Err(KiteError::Io(err)) if err.kind() == std::io::ErrorKind::NotFound => continue,
+      Err(KiteError::Io(err)) => return Err(KiteError::Io(err)),
+      // External WAL, or a header that doesn't parse
+      Ok(Some(_)) | Err(_) => continue,
+    }
+    backups.push(info);
+  }
+
+  backups.sort_by(|a, b| {
+    b.timestamp_ms
+      .cmp(&a.timestamp_ms)
+      .then_with(|| a.path.cmp(&b.path))
+  });
+  Ok(backups)
+}
+
+/// Back up a closed single-file database
+///
+/// A database keeping its WAL in an external file is opened and
+/// checkpointed first, and the backup gets a WAL region of its own.
 pub fn create_offline_backup(
   db_path: impl AsRef<Path>,
   backup_path: impl AsRef<Path>,
@@ -178,12 +247,29 @@ pub fn create_offline_backup(
     ));
   }
 
+  let header = read_header(&db_path)?;
+  if header.wal_path.is_some() {
+    let wal_size = header.wal_page_count as usize * header.page_size as usize;
+    let db = open_single_file(
+      &db_path,
+      SingleFileOpenOptions::new()
+        .page_size(header.page_size as usize)
+        .wal_size(wal_size),
+    )?;
+    let checkpointed = db.checkpoint();
+    close_single_file(db)?;
+    checkpointed?;
+  }
+
   ensure_parent_dir(&backup_path)?;
   if options.overwrite && backup_path.exists() {
     remove_existing(&backup_path)?;
   }
-  copy_file_with_size(&db_path, &backup_path)?;
-  let size = fs::metadata(&backup_path)?.len();
+  let size = if header.wal_path.is_some() {
+    copy_without_external_wal(&db_path, &backup_path)?
+  } else {
+    copy_file_with_size(&db_path, &backup_path)?
+  };
   Ok(backup_result(
     &backup_path,
     size,
@@ -226,7 +312,169 @@ fn remove_existing(path: &Path) -> Result<()> {
   Ok(())
 }
 
+/// Header of the database file at `path`
+fn read_header(path: &Path) -> Result<DbHeaderV1> {
+  let mut page = Vec::with_capacity(MAX_PAGE_SIZE);
+  fs::File::open(path)?
+    .take(MAX_PAGE_SIZE as u64)
+    .read_to_end(&mut page)?;
+  DbHeaderV1::parse(&page)
+}
+
+/// External WAL path recorded in the header of the database file at `path`
+///
+/// A plain copy of such a file would share the original's live WAL and miss
+/// the records not yet checkpointed, so restore refuses them.
+fn recorded_external_wal(path: &Path) -> Result<Option<String>> {
+  Ok(read_header(path)?.wal_path)
+}
+
+/// Copy a checkpointed database with an external WAL to `dst` as a
+/// database with an empty WAL region in its own file
+///
+/// The WAL page range of the main file is never written while the WAL is
+/// external, so the copy only needs a header without the WAL path.
+fn copy_without_external_wal(src: &Path, dst: &Path) -> Result<u64> {
+  fs::copy(src, dst)?;
+  let mut header = read_header(dst)?;
+  header.flags &= !DB_FLAG_EXTERNAL_WAL;
+  header.min_reader_version = MIN_READER_SINGLE_FILE;
+  header.wal_path = None;
+  header.wal_head = 0;
+  header.wal_tail = 0;
+  header.wal_primary_head = 0;
+  header.wal_secondary_head = 0;
+  header.active_wal_region = 0;
+
+  let mut file = fs::OpenOptions::new().write(true).open(dst)?;
+  let wal_end = (header.wal_start_page + header.wal_page_count) * header.page_size as u64;
+  if file.metadata()?.len() < wal_end {
+    file.set_len(wal_end)?;
+  }
+  file.seek(SeekFrom::Start(0))?;
+  file.write_all(&header.serialize_to_page())?;
+  file.sync_all()?;
+  Ok(file.metadata()?.len())
+}
+
+fn external_wal_error(db_path: &Path, wal_path: &str) -> KiteError {
+  KiteError::Internal(format!(
+    "Cannot back up or restore {}: it keeps its WAL in {wal_path}",
+    db_path.display()
+  ))
+}
+
 fn copy_file_with_size(src: &Path, dst: &Path) -> Result<u64> {
   fs::copy(src, dst)?;
   Ok(fs::metadata(dst)?.len())
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::single_file::{close_single_file, open_single_file, SingleFileOpenOptions};
+  use tempfile::tempdir;
+
+  #[test]
+  fn test_backup_of_external_wal_database_is_self_contained() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("data.kitedb");
+    let wal_path = temp_dir.path().join("data.wal");
+    let online_path = temp_dir.path().join("online.kitedb");
+    let offline_path = temp_dir.path().join("offline.kitedb");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new().wal_path(&wal_path))
+      .expect("expected value");
+    db.begin(false).expect("expected value");
+    let first = db.create_node(Some("first")).expect("expected value");
+    db.commit().expect("expected value");
+    create_backup_single_file(&db, &online_path, BackupOptions::default()).expect("expected value");
+
+    // Left in the external WAL at close
+    db.begin(false).expect("expected value");
+    let second = db.create_node(Some("second")).expect("expected value");
+    db.commit().expect("expected value");
+    close_single_file(db).expect("expected value");
+
+    create_offline_backup(&db_path, &offline_path, OfflineBackupOptions::default())
+      .expect("expected value");
+
+    for (backup_path, nodes) in [
+      (&online_path, vec![first]),
+      (&offline_path, vec![first, second]),
+    ] {
+      assert_eq!(
+        recorded_external_wal(backup_path).expect("expected value"),
+        None
+      );
+      let restored_path = temp_dir.path().join(format!("restored-{}", nodes.len()));
+      let restored_path = restore_backup(backup_path, &restored_path, RestoreOptions::default())
+        .expect("expected value");
+      let restored =
+        open_single_file(&restored_path, SingleFileOpenOptions::new()).expect("expected value");
+      for node in &nodes {
+        assert!(restored.node_exists(*node));
+      }
+      assert_eq!(restored.count_nodes(), nodes.len());
+      close_single_file(restored).expect("expected value");
+    }
+
+    // The original still opens against its own WAL
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    assert!(db.node_exists(second));
+    close_single_file(db).expect("expected value");
+
+    // A plain copy still needs the WAL file, so restore refuses it
+    let copy_path = temp_dir.path().join("copy.kitedb");
+    fs::copy(&db_path, &copy_path).expect("expected value");
+    let restored = restore_backup(
+      &copy_path,
+      temp_dir.path().join("restored.kitedb"),
+      RestoreOptions::default(),
+    );
+    assert!(matches!(restored, Err(KiteError::Internal(message)) if message.contains("WAL")));
+  }
+
+  #[test]
+  fn test_list_backups_skips_unrestorable_entries() {
+    let temp_dir = tempdir().expect("expected value");
+    let backups_dir = temp_dir.path().join("backups");
+    fs::create_dir(&backups_dir).expect("expected value");
+
+    let db_path = temp_dir.path().join("data.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    let backup_path = backups_dir.join("good.kitedb");
+    create_backup_single_file(&db, &backup_path, BackupOptions::default()).expect("expected value");
+    close_single_file(db).expect("expected value");
+
+    // A copy of a database with an external WAL, which restore refuses
+    let ext_db_path = temp_dir.path().join("ext.kitedb");
+    let db = open_single_file(
+      &ext_db_path,
+      SingleFileOpenOptions::new().wal_path(temp_dir.path().join("ext.wal")),
+    )
+    .expect("expected value");
+    close_single_file(db).expect("expected value");
+    fs::copy(&ext_db_path, backups_dir.join("external-wal.kitedb")).expect("expected value");
+
+    fs::write(backups_dir.join("notes.txt"), "not a backup").expect("expected value");
+    fs::write(backups_dir.join("garbage.kitedb"), "not a database").expect("expected value");
+    fs::create_dir(backups_dir.join("dir.kitedb")).expect("expected value");
+
+    let listed = list_backups(&backups_dir).expect("expected value");
+    assert_eq!(listed.len(), 1);
+    assert_eq!(listed[0].path, backup_path.to_string_lossy());
+    assert_eq!(listed[0].kind, "single-file");
+    restore_backup(
+      &listed[0].path,
+      temp_dir.path().join("restored.kitedb"),
+      RestoreOptions::default(),
+    )
+    .expect("expected value");
+
+    let missing = list_backups(temp_dir.path().join("missing"));
+    assert!(
+      matches!(missing, Err(KiteError::Io(ref err)) if err.kind() == std::io::ErrorKind::NotFound)
+    );
+  }
+}
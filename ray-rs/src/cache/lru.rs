@@ -254,6 +254,18 @@ impl<K: Hash + Eq + Clone, V> LruCache<K, V> {
     }
   }
 
+  /// Remove and return the least recently used entry
+  /// O(1) time complexity
+  pub fn pop_lru(&mut self) -> Option<(K, V)> {
+    let tail_ptr = self.tail?;
+    self.remove_node(tail_ptr);
+    // SAFETY: tail_ptr was linked in this cache; it is unlinked above and
+    // dropped from the map below, so we own it now
+    let node = unsafe { Box::from_raw(tail_ptr.as_ptr()) };
+    self.map.remove(&node.key);
+    Some((node.key, node.value))
+  }
+
   /// Check if a key exists in the cache
   /// O(1) time complexity
   ///
@@ -567,6 +579,22 @@ mod tests {
     assert_eq!(cache.get(&"a"), None);
   }
 
+  #[test]
+  fn test_pop_lru() {
+    let mut cache = LruCache::new(3);
+    cache.set("a", 1);
+    cache.set("b", 2);
+    cache.set("c", 3);
+    cache.get(&"a");
+
+    assert_eq!(cache.pop_lru(), Some(("b", 2)));
+    assert_eq!(cache.pop_lru(), Some(("c", 3)));
+    assert_eq!(cache.len(), 1);
+    assert_eq!(cache.pop_lru(), Some(("a", 1)));
+    assert_eq!(cache.pop_lru(), None);
+    assert!(cache.is_empty());
+  }
+
   #[test]
   fn test_peek() {
     let mut cache = LruCache::new(3);
@@ -92,6 +92,9 @@ impl CacheManagerStats {
       query_cache_hits: self.query_cache_hits,
       query_cache_misses: self.query_cache_misses,
       query_cache_size: self.query_cache_size,
+      key_cache_hits: self.key_cache_hits,
+      key_cache_misses: self.key_cache_misses,
+      key_cache_size: self.key_cache_size,
     }
   }
 }
@@ -143,6 +146,10 @@ pub struct CacheManager {
   key_cache_hits: u64,
   key_cache_misses: u64,
 
+  /// Bumped whenever key entries are dropped, so a lookup computed before an
+  /// invalidation is never written back afterwards
+  key_generation: u64,
+
   /// Whether caching is enabled
   enabled: bool,
 }
@@ -168,6 +175,7 @@ impl CacheManager {
       key_cache: LruCache::new(DEFAULT_KEY_CACHE_SIZE),
       key_cache_hits: 0,
       key_cache_misses: 0,
+      key_generation: 0,
       enabled,
     }
   }
@@ -187,6 +195,7 @@ impl CacheManager {
       key_cache: LruCache::new(key_cache_size),
       key_cache_hits: 0,
       key_cache_misses: 0,
+      key_generation: 0,
       enabled,
     }
   }
@@ -361,12 +370,40 @@ impl CacheManager {
     self.key_cache.set(key.into(), node_id);
   }
 
+  /// Set a node ID in cache by key, unless key entries were invalidated since
+  /// `generation` was read via [`Self::key_generation`]
+  pub fn set_node_by_key_if_current(
+    &mut self,
+    key: impl Into<Arc<str>>,
+    node_id: Option<NodeId>,
+    generation: u64,
+  ) {
+    if generation == self.key_generation {
+      self.set_node_by_key(key, node_id);
+    }
+  }
+
+  /// Current key invalidation generation
+  pub fn key_generation(&self) -> u64 {
+    self.key_generation
+  }
+
   /// Invalidate a cached key lookup
   pub fn invalidate_key(&mut self, key: &str) {
     if !self.enabled {
       return;
     }
     self.key_cache.delete(key);
+    self.key_generation += 1;
+  }
+
+  /// Drop every cached key lookup, keeping the hit/miss counters
+  pub fn invalidate_all_keys(&mut self) {
+    if !self.enabled {
+      return;
+    }
+    self.key_cache.clear();
+    self.key_generation += 1;
   }
 
   // ========================================================================
@@ -413,6 +450,7 @@ impl CacheManager {
     self.key_cache.clear();
     self.key_cache_hits = 0;
     self.key_cache_misses = 0;
+    self.key_generation += 1;
   }
 
   /// Clear only the query cache (useful for manual invalidation)
@@ -431,6 +469,7 @@ impl CacheManager {
     self.key_cache.clear();
     self.key_cache_hits = 0;
     self.key_cache_misses = 0;
+    self.key_generation += 1;
   }
 
   /// Clear only the property cache
@@ -449,6 +488,38 @@ impl CacheManager {
     self.traversal_cache.clear();
   }
 
+  /// Evict least recently used query results down to `max_entries`
+  ///
+  /// Returns the number evicted. Unlike [`Self::clear_query_cache`], the
+  /// most recently used results and the hit/miss counters are kept.
+  pub fn trim_query_cache(&mut self, max_entries: usize) -> usize {
+    if !self.enabled {
+      return 0;
+    }
+    self.query_cache.trim(max_entries)
+  }
+
+  /// Evict least recently used traversals down to `max_entries`
+  ///
+  /// Returns the number evicted.
+  pub fn trim_traversal_cache(&mut self, max_entries: usize) -> usize {
+    if !self.enabled {
+      return 0;
+    }
+    self.traversal_cache.trim(max_entries)
+  }
+
+  /// Evict least recently used node and edge props down to `max_entries`
+  /// each
+  ///
+  /// Returns the number evicted across both.
+  pub fn trim_property_cache(&mut self, max_entries: usize) -> usize {
+    if !self.enabled {
+      return 0;
+    }
+    self.property_cache.trim(max_entries)
+  }
+
   // ========================================================================
   // Statistics API
   // ========================================================================
@@ -787,6 +858,45 @@ mod tests {
     assert_eq!(cache.node_by_key("alice"), None);
   }
 
+  #[test]
+  fn test_trim_individual_caches() {
+    let mut cache = make_enabled_cache();
+
+    for i in 0..4 {
+      cache.set_query(format!("q{i}"), i);
+      cache.set_traversal(i, Some(1), TraversalDirection::Out, vec![]);
+      cache.set_node_prop(i, 10, Some(PropValue::I64(i as i64)));
+    }
+    cache.set_edge_prop(1, 1, 2, 10, None);
+    cache.set_edge_prop(2, 1, 3, 10, None);
+    // Touch the oldest entries so they survive the trim
+    let _: Option<&u64> = cache.query("q0");
+    cache.traversal(0, Some(1), TraversalDirection::Out);
+
+    assert_eq!(cache.trim_query_cache(2), 2);
+    let q0: Option<&u64> = cache.query("q0");
+    assert_eq!(q0, Some(&0));
+    let q1: Option<&u64> = cache.query("q1");
+    assert!(q1.is_none());
+    assert_eq!(cache.trim_query_cache(2), 0);
+
+    assert_eq!(cache.trim_traversal_cache(1), 3);
+    assert!(cache
+      .traversal(0, Some(1), TraversalDirection::Out)
+      .is_some());
+    assert!(cache
+      .traversal(3, Some(1), TraversalDirection::Out)
+      .is_none());
+
+    // Node and edge props are each trimmed to the limit
+    assert_eq!(cache.trim_property_cache(1), 4);
+    assert_eq!(cache.node_prop(3, 10), Some(&Some(PropValue::I64(3))));
+    assert_eq!(cache.edge_prop(2, 1, 3, 10), Some(&None));
+    assert_eq!(cache.edge_prop(1, 1, 2, 10), None);
+
+    assert_eq!(CacheManager::disabled().trim_query_cache(0), 0);
+  }
+
   #[test]
   fn test_stats() {
     let mut cache = make_enabled_cache();
@@ -271,6 +271,40 @@ impl PropertyCache {
     self.misses = 0;
   }
 
+  /// Evict least recently used entries until the node and edge property
+  /// caches each hold at most `max_entries`
+  ///
+  /// Returns the number of entries evicted across both.
+  pub fn trim(&mut self, max_entries: usize) -> usize {
+    let mut evicted = 0;
+    while self.node_cache.len() > max_entries {
+      let Some((key, _)) = self.node_cache.pop_lru() else {
+        break;
+      };
+      if let Some(keys) = self.node_key_index.get_mut(&key.0) {
+        keys.remove(&key);
+        if keys.is_empty() {
+          self.node_key_index.remove(&key.0);
+        }
+      }
+      evicted += 1;
+    }
+    while self.edge_cache.len() > max_entries {
+      let Some((key, _)) = self.edge_cache.pop_lru() else {
+        break;
+      };
+      let edge_index_key = (key.0, key.1, key.2);
+      if let Some(keys) = self.edge_key_index.get_mut(&edge_index_key) {
+        keys.remove(&key);
+        if keys.is_empty() {
+          self.edge_key_index.remove(&edge_index_key);
+        }
+      }
+      evicted += 1;
+    }
+    evicted
+  }
+
   /// Get cache statistics
   pub fn stats(&self) -> PropertyCacheStats {
     PropertyCacheStats {
@@ -224,6 +224,17 @@ impl QueryCache {
     self.expirations = 0;
   }
 
+  /// Evict least recently used results until at most `max_entries` remain
+  ///
+  /// Returns the number of results evicted.
+  pub fn trim(&mut self, max_entries: usize) -> usize {
+    let mut evicted = 0;
+    while self.cache.len() > max_entries && self.cache.pop_lru().is_some() {
+      evicted += 1;
+    }
+    evicted
+  }
+
   /// Get cache statistics
   pub fn stats(&self) -> QueryCacheStats {
     QueryCacheStats {
@@ -255,6 +255,33 @@ impl TraversalCache {
     self.misses = 0;
   }
 
+  /// Evict least recently used traversals until at most `max_entries`
+  /// remain, dropping them from the invalidation index too
+  ///
+  /// Returns the number of traversals evicted.
+  pub fn trim(&mut self, max_entries: usize) -> usize {
+    let mut evicted = 0;
+    while self.cache.len() > max_entries {
+      let Some((key, cached)) = self.cache.pop_lru() else {
+        break;
+      };
+      let source = key >> 11;
+      let direction_bit = key & 1;
+      let others = cached.neighbors.iter().map(|edge| {
+        if direction_bit == 0 {
+          edge.dst
+        } else {
+          edge.src
+        }
+      });
+      for node_id in std::iter::once(source).chain(others) {
+        self.remove_from_node_index(node_id, key);
+      }
+      evicted += 1;
+    }
+    evicted
+  }
+
   /// Get cache statistics
   pub fn stats(&self) -> TraversalCacheStats {
     TraversalCacheStats {
@@ -309,6 +336,16 @@ impl TraversalCache {
     self.node_key_index.entry(node_id).or_default().insert(key);
   }
 
+  /// Remove a key from the node index, dropping the node's entry once empty
+  fn remove_from_node_index(&mut self, node_id: NodeId, key: TraversalKey) {
+    if let Some(keys) = self.node_key_index.get_mut(&node_id) {
+      keys.remove(&key);
+      if keys.is_empty() {
+        self.node_key_index.remove(&node_id);
+      }
+    }
+  }
+
   /// Invalidate specific traversals for a node
   fn invalidate_node_traversals(
     &mut self,
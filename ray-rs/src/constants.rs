@@ -15,13 +15,13 @@ pub const MAGIC_SNAPSHOT: u32 = 0x31534447;
 // Current versions
 // ============================================================================
 
-pub const VERSION_SNAPSHOT: u32 = 4;
+pub const VERSION_SNAPSHOT: u32 = 5;
 
 // ============================================================================
 // Minimum reader versions
 // ============================================================================
 
-pub const MIN_READER_SNAPSHOT: u32 = 4;
+pub const MIN_READER_SNAPSHOT: u32 = 5;
 
 // ============================================================================
 // Alignment requirements
@@ -43,8 +43,10 @@ pub const MAGIC_KITEDB: [u8; 16] = [
 ];
 
 /// Single-file format version
-pub const VERSION_SINGLE_FILE: u32 = 1;
+pub const VERSION_SINGLE_FILE: u32 = 2;
 pub const MIN_READER_SINGLE_FILE: u32 = 1;
+/// Readers older than this ignore `DB_FLAG_EXTERNAL_WAL` and its path
+pub const MIN_READER_EXTERNAL_WAL: u32 = 2;
 
 /// Single-file extension
 pub const EXT_KITEDB: &str = ".kitedb";
@@ -86,6 +88,26 @@ pub const LOCK_BYTE_RANGE: usize = 512;
 pub const DB_FLAG_WAL_MODE: u32 = 1 << 0;
 pub const DB_FLAG_COMPRESSION: u32 = 1 << 1;
 pub const DB_FLAG_ENCRYPTED: u32 = 1 << 2;
+/// WAL pages live in a separate file whose path is stored in the header
+pub const DB_FLAG_EXTERNAL_WAL: u32 = 1 << 3;
+
+/// Offset of the external WAL path (u16 length + UTF-8 bytes) in the header page
+pub const DB_HEADER_WAL_PATH_OFFSET: usize = 192;
+
+/// Offset of the CRC32C of the external WAL path (length prefix included),
+/// inside the range covered by the header checksum
+pub const DB_HEADER_WAL_PATH_CRC_OFFSET: usize = 164;
+
+/// Offset of the random database id, inside the range covered by the header
+/// checksum. An external WAL file repeats it so a WAL is never replayed into
+/// another database.
+pub const DB_HEADER_DB_ID_OFFSET: usize = 168;
+
+/// Magic bytes opening an external WAL file: "KiteDB WAL 1\0\0\0\0" (16 bytes)
+pub const MAGIC_WAL_FILE: [u8; 16] = [
+  0x4b, 0x69, 0x74, 0x65, 0x44, 0x42, 0x20, 0x57, // "KiteDB W"
+  0x41, 0x4c, 0x20, 0x31, 0x00, 0x00, 0x00, 0x00, // "AL 1\0\0\0\0"
+];
 
 // ============================================================================
 // Thresholds for compact recommendation
@@ -97,6 +119,10 @@ pub const COMPACT_EDGE_RATIO: f64 = 0.1;
 pub const COMPACT_NODE_RATIO: f64 = 0.1;
 /// 64MB
 pub const COMPACT_WAL_SIZE: usize = 64 * 1024 * 1024;
+/// Pending deletions reach 10% of snapshot entries (see `DbStats::fragmentation`)
+pub const COMPACT_FRAGMENTATION_RATIO: f64 = 0.1;
+/// WAL buffer 80% full
+pub const COMPACT_WAL_USAGE_RATIO: f64 = 0.8;
 
 // ============================================================================
 // Delta set upgrade threshold
@@ -113,6 +113,24 @@ impl DeltaState {
     self.key_index_deleted.clear();
     self.incoming_edge_sources.clear();
     self.pending_vectors.clear();
+    self.metadata.clear();
+  }
+
+  /// Whether the delta holds no uncheckpointed changes
+  pub fn is_empty(&self) -> bool {
+    self.created_nodes.is_empty()
+      && self.deleted_nodes.is_empty()
+      && self.modified_nodes.is_empty()
+      && self.out_add.is_empty()
+      && self.out_del.is_empty()
+      && self.edge_props.is_empty()
+      && self.new_labels.is_empty()
+      && self.new_etypes.is_empty()
+      && self.new_propkeys.is_empty()
+      && self.key_index.is_empty()
+      && self.key_index_deleted.is_empty()
+      && self.pending_vectors.is_empty()
+      && self.metadata.is_empty()
   }
 
   /// Get count of edges added for a source node
@@ -151,10 +169,33 @@ impl DeltaState {
 
     // Add to key index if key provided
     if let Some(k) = key {
+      self.key_index_deleted.remove(k);
       self.key_index.insert(k.to_string(), node_id);
     }
   }
 
+  /// Change the key of an existing node
+  ///
+  /// `old_key` is the key the node currently resolves to (which may live in
+  /// the snapshot); it is tombstoned so snapshot lookups stop matching it.
+  pub fn set_node_key(&mut self, node_id: NodeId, old_key: Option<&str>, new_key: &str) {
+    if let Some(old) = old_key {
+      if old != new_key {
+        self.key_index.remove(old);
+        self.key_index_deleted.insert(old.to_string());
+      }
+    }
+    self.key_index_deleted.remove(new_key);
+    self.key_index.insert(new_key.to_string(), node_id);
+
+    let node_delta = if let Some(node_delta) = self.created_nodes.get_mut(&node_id) {
+      node_delta
+    } else {
+      self.modified_nodes.entry(node_id).or_default()
+    };
+    node_delta.key = Some(new_key.to_string());
+  }
+
   /// Delete a node
   pub fn delete_node(&mut self, node_id: NodeId) {
     // If it was just created in this delta, remove it instead
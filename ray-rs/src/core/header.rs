@@ -42,16 +42,50 @@ impl DbHeaderV1 {
       });
     }
 
+    let min_reader_version = read_u32(data, 24);
+    if min_reader_version > VERSION_SINGLE_FILE {
+      return Err(KiteError::VersionMismatch {
+        required: min_reader_version,
+        current: VERSION_SINGLE_FILE,
+      });
+    }
+
     // Parse fields
     let mut magic = [0u8; 16];
     magic.copy_from_slice(&data[0..16]);
 
+    let flags = read_u32(data, 28);
+    let wal_path = if flags & DB_FLAG_EXTERNAL_WAL != 0 {
+      let len = read_u16(data, DB_HEADER_WAL_PATH_OFFSET) as usize;
+      let start = DB_HEADER_WAL_PATH_OFFSET + 2;
+      let bytes = data.get(start..start + len).ok_or_else(|| {
+        KiteError::InvalidSnapshot(format!("External WAL path length {len} exceeds header"))
+      })?;
+
+      // The path lies past the header checksum; its own CRC lies within it
+      let path_crc = read_u32(data, DB_HEADER_WAL_PATH_CRC_OFFSET);
+      let computed_path_crc = crc32c(&data[DB_HEADER_WAL_PATH_OFFSET..start + len]);
+      if path_crc != computed_path_crc {
+        return Err(KiteError::CrcMismatch {
+          stored: path_crc,
+          computed: computed_path_crc,
+        });
+      }
+
+      Some(
+        String::from_utf8(bytes.to_vec())
+          .map_err(|_| KiteError::InvalidSnapshot("External WAL path is not UTF-8".to_string()))?,
+      )
+    } else {
+      None
+    };
+
     Ok(Self {
       magic,
       page_size: read_u32(data, 16),
       version: read_u32(data, 20),
-      min_reader_version: read_u32(data, 24),
-      flags: read_u32(data, 28),
+      min_reader_version,
+      flags,
       change_counter: read_u64(data, 32),
       db_size_pages: read_u64(data, 40),
       snapshot_start_page: read_u64(data, 48),
@@ -70,6 +104,8 @@ impl DbHeaderV1 {
       wal_secondary_head: read_u64(data, 152),
       active_wal_region: data[160],
       checkpoint_in_progress: data[161],
+      db_id: read_u64(data, DB_HEADER_DB_ID_OFFSET),
+      wal_path,
     })
   }
 
@@ -110,6 +146,16 @@ impl DbHeaderV1 {
     write_u64(&mut buf, 152, self.wal_secondary_head);
     buf[160] = self.active_wal_region;
     buf[161] = self.checkpoint_in_progress;
+    write_u64(&mut buf, DB_HEADER_DB_ID_OFFSET, self.db_id);
+
+    if let Some(wal_path) = &self.wal_path {
+      let bytes = wal_path.as_bytes();
+      write_u16(&mut buf, DB_HEADER_WAL_PATH_OFFSET, bytes.len() as u16);
+      let start = DB_HEADER_WAL_PATH_OFFSET + 2;
+      buf[start..start + bytes.len()].copy_from_slice(bytes);
+      let path_crc = crc32c(&buf[DB_HEADER_WAL_PATH_OFFSET..start + bytes.len()]);
+      write_u32(&mut buf, DB_HEADER_WAL_PATH_CRC_OFFSET, path_crc);
+    }
 
     // Compute and write header checksum
     let header_crc = crc32c(&buf[0..176]);
@@ -122,6 +168,11 @@ impl DbHeaderV1 {
     buf
   }
 
+  /// Longest external WAL path that fits in a header page of `page_size` bytes
+  pub fn max_wal_path_len(page_size: usize) -> usize {
+    page_size - 4 - DB_HEADER_WAL_PATH_OFFSET - 2
+  }
+
   /// Create a new header with default values
   pub fn new(page_size: u32, wal_pages: u64) -> Self {
     let mut magic = [0u8; 16];
@@ -151,6 +202,57 @@ impl DbHeaderV1 {
       wal_secondary_head: 0,
       active_wal_region: 0,
       checkpoint_in_progress: 0,
+      db_id: rand::random::<u64>().max(1),
+      wal_path: None,
     }
   }
 }
+
+/// Size of the external WAL file header, at the start of its first page
+const WAL_FILE_HEADER_SIZE: usize = 36;
+
+/// Serialize the first page of an external WAL file.
+///
+/// WAL pages follow it, so WAL page `i` lives at file page `i + 1`.
+pub fn serialize_wal_file_header(page_size: usize, db_id: u64) -> Vec<u8> {
+  let mut buf = vec![0u8; page_size];
+  buf[0..16].copy_from_slice(&MAGIC_WAL_FILE);
+  write_u32(&mut buf, 16, page_size as u32);
+  write_u64(&mut buf, 24, db_id);
+  let crc = crc32c(&buf[0..32]);
+  write_u32(&mut buf, 32, crc);
+  buf
+}
+
+/// Check that an external WAL file header belongs to the database `db_id`
+pub fn check_wal_file_header(data: &[u8], page_size: usize, db_id: u64) -> Result<()> {
+  if data.len() < WAL_FILE_HEADER_SIZE || data[0..16] != MAGIC_WAL_FILE {
+    return Err(KiteError::InvalidWal(
+      "External WAL file has no KiteDB WAL header".to_string(),
+    ));
+  }
+
+  let stored_crc = read_u32(data, 32);
+  let computed_crc = crc32c(&data[0..32]);
+  if stored_crc != computed_crc {
+    return Err(KiteError::CrcMismatch {
+      stored: stored_crc,
+      computed: computed_crc,
+    });
+  }
+
+  let wal_page_size = read_u32(data, 16) as usize;
+  if wal_page_size != page_size {
+    return Err(KiteError::InvalidWal(format!(
+      "External WAL page size {wal_page_size} does not match the database page size {page_size}"
+    )));
+  }
+
+  let wal_db_id = read_u64(data, 24);
+  if wal_db_id != db_id {
+    return Err(KiteError::InvalidWal(format!(
+      "External WAL belongs to database {wal_db_id:016x}, not {db_id:016x}"
+    )));
+  }
+  Ok(())
+}
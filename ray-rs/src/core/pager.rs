@@ -15,6 +15,14 @@ use crate::constants::{
 };
 use crate::error::{KiteError, Result};
 
+/// Separate file holding the WAL page range of a single-file database
+struct WalFile {
+  file: File,
+  path: PathBuf,
+  start_page: u32,
+  page_count: u32,
+}
+
 /// FilePager implementation for single-file database
 pub struct FilePager {
   file: File,
@@ -24,6 +32,8 @@ pub struct FilePager {
   free_pages: HashSet<u32>,
   /// Cached mmap for the entire file (lazily created)
   mmap: Option<Mmap>,
+  /// External WAL file; pages in its range are redirected there
+  wal_file: Option<WalFile>,
 }
 
 impl FilePager {
@@ -37,6 +47,7 @@ impl FilePager {
       file_size,
       free_pages: HashSet::new(),
       mmap: None,
+      wal_file: None,
     })
   }
 
@@ -49,6 +60,7 @@ impl FilePager {
       file_size,
       free_pages: HashSet::new(),
       mmap: None,
+      wal_file: None,
     }
   }
 
@@ -67,6 +79,68 @@ impl FilePager {
     self.file_size
   }
 
+  /// Redirect the WAL page range `start_page..start_page + page_count` to a
+  /// separate file. The file's first page is its header (see
+  /// `serialize_wal_file_header`); the file is grown to hold the whole range.
+  pub fn attach_wal_file(
+    &mut self,
+    file: File,
+    path: PathBuf,
+    start_page: u32,
+    page_count: u32,
+  ) -> Result<()> {
+    let required_size = (page_count as u64 + 1) * self.page_size as u64;
+    if file.metadata()?.len() < required_size {
+      file.set_len(required_size)?;
+    }
+    self.wal_file = Some(WalFile {
+      file,
+      path,
+      start_page,
+      page_count,
+    });
+    Ok(())
+  }
+
+  /// Path of the external WAL file, if one is attached
+  pub fn wal_file_path(&self) -> Option<&Path> {
+    self.wal_file.as_ref().map(|wal| wal.path.as_path())
+  }
+
+  /// Update the WAL page range size after a WAL resize. No-op without an
+  /// external WAL file.
+  pub fn set_wal_page_count(&mut self, page_count: u32) -> Result<()> {
+    if let Some(wal) = self.wal_file.as_mut() {
+      wal
+        .file
+        .set_len((page_count as u64 + 1) * self.page_size as u64)?;
+      wal.page_count = page_count;
+    }
+    Ok(())
+  }
+
+  /// Drop the WAL pages of the external WAL file, releasing their disk
+  /// blocks. The header page is kept and the file keeps its length as a
+  /// sparse file. No-op without one.
+  pub fn clear_wal_file(&mut self) -> Result<()> {
+    if let Some(wal) = self.wal_file.as_mut() {
+      let len = (wal.page_count as u64 + 1) * self.page_size as u64;
+      wal.file.set_len(self.page_size as u64)?;
+      wal.file.set_len(len)?;
+    }
+    Ok(())
+  }
+
+  /// Map a page to its offset in the external WAL file, if it belongs there
+  fn wal_file_offset(&self, page_num: u32) -> Option<u64> {
+    let wal = self.wal_file.as_ref()?;
+    if page_num >= wal.start_page && page_num < wal.start_page + wal.page_count {
+      Some((page_num - wal.start_page + 1) as u64 * self.page_size as u64)
+    } else {
+      None
+    }
+  }
+
   /// Calculate the page number range for the lock byte region
   fn lock_byte_page_range(&self) -> (u32, u32) {
     let start = (LOCK_BYTE_OFFSET / self.page_size as u64) as u32;
@@ -82,6 +156,15 @@ impl FilePager {
 
   /// Read a single page by page number
   pub fn read_page(&mut self, page_num: u32) -> Result<Vec<u8>> {
+    if let Some(offset) = self.wal_file_offset(page_num) {
+      let mut buffer = vec![0u8; self.page_size];
+      if let Some(wal) = self.wal_file.as_mut() {
+        wal.file.seek(SeekFrom::Start(offset))?;
+        wal.file.read_exact(&mut buffer)?;
+      }
+      return Ok(buffer);
+    }
+
     let offset = page_num as u64 * self.page_size as u64;
 
     // Safety check: don't read beyond file size
@@ -116,6 +199,14 @@ impl FilePager {
       )));
     }
 
+    if let Some(offset) = self.wal_file_offset(page_num) {
+      if let Some(wal) = self.wal_file.as_mut() {
+        wal.file.seek(SeekFrom::Start(offset))?;
+        wal.file.write_all(data)?;
+      }
+      return Ok(());
+    }
+
     let offset = page_num as u64 * self.page_size as u64;
 
     // Extend file if necessary
@@ -255,6 +346,10 @@ impl FilePager {
     {
       self.file.sync_all()?;
     }
+
+    if let Some(wal) = self.wal_file.as_ref() {
+      wal.file.sync_all()?;
+    }
     Ok(())
   }
 
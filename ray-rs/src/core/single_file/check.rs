@@ -1,9 +1,36 @@
 //! Integrity checks for SingleFileDB.
 
-use crate::types::CheckResult;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use crate::error::{KiteError, Result};
+use crate::types::{CheckResult, ETypeId, NodeId, PropKeyId, PropValue, RepairResult};
 
 use super::SingleFileDB;
 
+/// Per-prop-key tally of vectors that break dimension or finiteness rules
+#[derive(Default)]
+struct VectorScan {
+  dimensions: Option<usize>,
+  wrong_dims: usize,
+  first_wrong_dims: Option<(NodeId, usize)>,
+  non_finite: usize,
+  first_non_finite: Option<NodeId>,
+}
+
+impl VectorScan {
+  fn record(&mut self, node_id: NodeId, vector: &[f32]) {
+    let dimensions = *self.dimensions.get_or_insert(vector.len());
+    if vector.len() != dimensions {
+      self.wrong_dims += 1;
+      self.first_wrong_dims.get_or_insert((node_id, vector.len()));
+    }
+    if vector.iter().any(|value| !value.is_finite()) {
+      self.non_finite += 1;
+      self.first_non_finite.get_or_insert(node_id);
+    }
+  }
+}
+
 impl SingleFileDB {
   /// Check database integrity.
   ///
@@ -11,17 +38,35 @@ impl SingleFileDB {
   /// - Verifies edge endpoints exist
   /// - Validates edge existence via edge_exists
   /// - Compares list counts against count_* helpers
+  /// - Verifies vectors under each prop key share a dimension and hold no
+  ///   NaN/Inf (reported as warnings; see [`Self::check_strict`])
+  /// - Verifies every JSON prop value in the snapshot parses
   pub fn check(&self) -> CheckResult {
+    self.check_with_vectors(false)
+  }
+
+  /// Check database integrity, reporting vector problems as errors
+  pub fn check_strict(&self) -> CheckResult {
+    self.check_with_vectors(true)
+  }
+
+  fn check_with_vectors(&self, strict_vectors: bool) -> CheckResult {
     let mut errors = Vec::new();
     let mut warnings = Vec::new();
 
+    if let Some(snapshot) = self.snapshot.read().as_ref() {
+      if let Err(err) = snapshot.validate_json_props() {
+        errors.push(err.to_string());
+      }
+    }
+
     let all_nodes = self.list_nodes();
     let node_count = all_nodes.len();
 
     if node_count == 0 {
       warnings.push("No nodes in database".to_string());
       return CheckResult {
-        valid: true,
+        valid: errors.is_empty(),
         errors,
         warnings,
       };
@@ -70,10 +115,306 @@ impl SingleFileDB {
       ));
     }
 
+    let vector_issues = self.vector_issues_for(all_nodes);
+    if strict_vectors {
+      errors.extend(vector_issues);
+    } else {
+      warnings.extend(vector_issues);
+    }
+
     CheckResult {
       valid: errors.is_empty(),
       errors,
       warnings,
     }
   }
+
+  /// Describe vectors that mix dimensions or hold NaN/Inf, one line per
+  /// problem per prop key
+  pub(crate) fn vector_issues(&self) -> Vec<String> {
+    self.vector_issues_for(self.list_nodes())
+  }
+
+  /// Covers vector-store embeddings and `VectorF32` node props. A prop key
+  /// is measured against its vector store's dimension, or else the first
+  /// vector seen in node ID order.
+  fn vector_issues_for(&self, mut nodes: Vec<NodeId>) -> Vec<String> {
+    nodes.sort_unstable();
+    let mut store_keys: Vec<PropKeyId> = self.vector_prop_keys().into_iter().collect();
+    store_keys.extend(
+      self
+        .delta
+        .read()
+        .pending_vectors
+        .keys()
+        .map(|&(_, key)| key),
+    );
+    store_keys.sort_unstable();
+    store_keys.dedup();
+
+    let mut scans: BTreeMap<PropKeyId, VectorScan> = BTreeMap::new();
+    for &key_id in &store_keys {
+      if self.ensure_vector_store_loaded(key_id).is_ok() {
+        if let Some(store) = self.vector_stores.read().get(&key_id) {
+          scans.entry(key_id).or_default().dimensions = Some(store.config.dimensions);
+        }
+      }
+    }
+
+    for &node_id in &nodes {
+      for &key_id in &store_keys {
+        if let Some(vector) = self.node_vector(node_id, key_id) {
+          scans.entry(key_id).or_default().record(node_id, &vector);
+        }
+      }
+      let mut vector_props: Vec<(PropKeyId, Vec<f32>)> = self
+        .node_props(node_id)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(|(key_id, value)| match value {
+          PropValue::VectorF32(vector) => Some((key_id, vector)),
+          _ => None,
+        })
+        .collect();
+      vector_props.sort_unstable_by_key(|(key_id, _)| *key_id);
+      for (key_id, vector) in vector_props {
+        scans.entry(key_id).or_default().record(node_id, &vector);
+      }
+    }
+
+    let mut issues = Vec::new();
+    for (key_id, scan) in scans {
+      let name = self
+        .propkey_name(key_id)
+        .unwrap_or_else(|| format!("#{key_id}"));
+      if let (Some(dimensions), Some((node_id, got))) = (scan.dimensions, scan.first_wrong_dims) {
+        issues.push(format!(
+          "Vector prop '{name}': {} vector(s) don't have {dimensions} dimensions (e.g. node {node_id} has {got})",
+          scan.wrong_dims
+        ));
+      }
+      if let Some(node_id) = scan.first_non_finite {
+        issues.push(format!(
+          "Vector prop '{name}': {} vector(s) contain NaN or Inf (e.g. node {node_id})",
+          scan.non_finite
+        ));
+      }
+    }
+    issues
+  }
+
+  /// Check database integrity and repair what can be fixed.
+  ///
+  /// - Re-adds the missing half of an asymmetric out/in adjacency when both
+  ///   endpoints exist
+  /// - Checkpoints to rebuild snapshot counts that disagree with the listings
+  /// - Removes edges referencing non-existent nodes, only when
+  ///   `allow_destructive` is set (otherwise they are reported as skipped)
+  ///
+  /// Must be called outside a transaction. The returned check reflects the
+  /// database after repairs.
+  pub fn check_and_repair(&self, allow_destructive: bool) -> Result<RepairResult> {
+    if self.read_only {
+      return Err(KiteError::ReadOnly);
+    }
+    if self.has_transaction() {
+      return Err(KiteError::TransactionInProgress);
+    }
+
+    let mut repaired = Vec::new();
+    let mut skipped = Vec::new();
+    let mut restore = Vec::new();
+    let mut dangling = Vec::new();
+
+    // Outgoing edges must be mirrored by an incoming edge on the destination
+    let mut in_cache: HashMap<NodeId, HashSet<(ETypeId, NodeId)>> = HashMap::new();
+    for edge in self.list_edges(None) {
+      if !self.node_exists(edge.src) || !self.node_exists(edge.dst) {
+        dangling.push((edge.src, edge.etype, edge.dst));
+        continue;
+      }
+      let incoming = in_cache
+        .entry(edge.dst)
+        .or_insert_with(|| self.in_edges(edge.dst).into_iter().collect());
+      if !incoming.contains(&(edge.etype, edge.src)) {
+        restore.push((edge.src, edge.etype, edge.dst));
+      }
+    }
+
+    // Incoming edges must be mirrored by an outgoing edge on the source
+    let mut out_cache: HashMap<NodeId, HashSet<(ETypeId, NodeId)>> = HashMap::new();
+    for dst in self.list_nodes() {
+      for (etype, src) in self.in_edges(dst) {
+        let outgoing = out_cache
+          .entry(src)
+          .or_insert_with(|| self.out_edges(src).into_iter().collect());
+        if outgoing.contains(&(etype, dst)) {
+          continue;
+        }
+        if self.node_exists(src) {
+          restore.push((src, etype, dst));
+        } else {
+          dangling.push((src, etype, dst));
+        }
+      }
+    }
+
+    restore.sort_unstable();
+    restore.dedup();
+    dangling.sort_unstable();
+    dangling.dedup();
+
+    if !allow_destructive {
+      for (src, etype, dst) in dangling.drain(..) {
+        skipped.push(format!(
+          "Dangling edge {src} -[{etype}]-> {dst} left in place (destructive repairs disabled)"
+        ));
+      }
+    }
+
+    if !restore.is_empty() || !dangling.is_empty() {
+      let tx = self.begin_guard(false)?;
+      for &(src, etype, dst) in &restore {
+        self.add_edge(src, etype, dst)?;
+        repaired.push(format!(
+          "Restored adjacency for edge {src} -[{etype}]-> {dst}"
+        ));
+      }
+      for &(src, etype, dst) in &dangling {
+        self.delete_edge(src, etype, dst)?;
+        repaired.push(format!("Removed dangling edge {src} -[{etype}]-> {dst}"));
+      }
+      tx.commit()?;
+    }
+
+    // Snapshot + delta counts should add up to what the listings return.
+    // Deleting a snapshot node drops its edges implicitly, so edge counts are
+    // only comparable while no nodes are deleted.
+    let stats = self.stats();
+    let expected_nodes = (stats.snapshot_nodes as usize + stats.delta_nodes_created)
+      .saturating_sub(stats.delta_nodes_deleted);
+    let expected_edges = (stats.snapshot_edges as usize + stats.delta_edges_added)
+      .saturating_sub(stats.delta_edges_deleted);
+    let node_count = self.count_nodes();
+    let edge_count = self.count_edges();
+    let edges_mismatch = stats.delta_nodes_deleted == 0 && expected_edges != edge_count;
+    if expected_nodes != node_count || edges_mismatch {
+      self.checkpoint()?;
+      repaired.push(format!(
+        "Rebuilt counts via checkpoint (nodes {expected_nodes} -> {node_count}, edges {expected_edges} -> {edge_count})"
+      ));
+    }
+
+    Ok(RepairResult {
+      repaired,
+      skipped,
+      check: self.check(),
+    })
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::core::single_file::{close_single_file, open_single_file, SingleFileOpenOptions};
+  use crate::types::{EdgePatch, PropValue};
+  use tempfile::tempdir;
+
+  #[test]
+  fn test_check_reports_inconsistent_vectors() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("vectors.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let a = db.create_node(Some("a")).expect("expected value");
+    let b = db.create_node(Some("b")).expect("expected value");
+    let c = db.create_node(Some("c")).expect("expected value");
+    let embedding = db.define_propkey("embedding").expect("expected value");
+    let features = db.define_propkey("features").expect("expected value");
+    db.set_node_vector(a, embedding, &[1.0, 0.0, 0.0])
+      .expect("expected value");
+    db.set_node_vector(b, embedding, &[0.0, 1.0, 0.0])
+      .expect("expected value");
+    db.set_node_prop(a, features, PropValue::VectorF32(vec![1.0, 2.0]))
+      .expect("expected value");
+    db.commit().expect("expected value");
+
+    let clean = db.check();
+    assert!(clean.valid);
+    assert!(clean.warnings.is_empty(), "{:?}", clean.warnings);
+
+    db.begin(false).expect("expected value");
+    db.set_node_prop(b, features, PropValue::VectorF32(vec![1.0, 2.0, 3.0]))
+      .expect("expected value");
+    db.set_node_prop(c, features, PropValue::VectorF32(vec![f32::NAN, 1.0]))
+      .expect("expected value");
+    db.commit().expect("expected value");
+
+    let lenient = db.check();
+    assert!(lenient.valid);
+    assert_eq!(
+      lenient.warnings,
+      vec![
+        format!(
+          "Vector prop 'features': 1 vector(s) don't have 2 dimensions (e.g. node {b} has 3)"
+        ),
+        format!("Vector prop 'features': 1 vector(s) contain NaN or Inf (e.g. node {c})"),
+      ]
+    );
+
+    let strict = db.check_strict();
+    assert!(!strict.valid);
+    assert_eq!(strict.errors, lenient.warnings);
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_check_and_repair_restores_adjacency_and_gates_destructive_fixes() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("repair.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let a = db.create_node(Some("a")).expect("expected value");
+    let b = db.create_node(Some("b")).expect("expected value");
+    let etype = db.define_etype("KNOWS").expect("expected value");
+    db.commit().expect("expected value");
+
+    let healthy = db.check_and_repair(true).expect("expected value");
+    assert!(healthy.repaired.is_empty());
+    assert!(healthy.check.valid);
+
+    // Simulate a crash leaving half-written adjacency
+    {
+      let mut delta = db.delta.write();
+      delta
+        .out_add
+        .entry(a)
+        .or_default()
+        .insert(EdgePatch { etype, other: b });
+      delta
+        .out_add
+        .entry(b)
+        .or_default()
+        .insert(EdgePatch { etype, other: 999 });
+    }
+    assert!(!db.check().valid);
+
+    let result = db.check_and_repair(false).expect("expected value");
+    assert_eq!(result.repaired.len(), 1);
+    assert_eq!(result.skipped.len(), 1);
+    assert!(db.in_edges(b).contains(&(etype, a)));
+    assert!(!result.check.valid);
+
+    let result = db.check_and_repair(true).expect("expected value");
+    assert_eq!(result.repaired.len(), 1);
+    assert!(result.skipped.is_empty());
+    assert!(result.check.valid);
+    assert!(db.edge_exists(a, etype, b));
+
+    db.begin(false).expect("expected value");
+    assert!(db.check_and_repair(true).is_err());
+    db.rollback().expect("expected value");
+    close_single_file(db).expect("expected value");
+  }
 }
@@ -10,14 +10,22 @@ use crate::core::snapshot::reader::SnapshotData;
 use crate::core::snapshot::writer::{
   build_snapshot_to_memory, EdgeData, NodeData, SnapshotBuildInput,
 };
+use crate::core::wal::record::{parse_wal_record, ParsedWalRecord};
 use crate::error::{KiteError, Result};
 use crate::types::*;
 use crate::util::mmap::map_file;
 use crate::vector::types::VectorManifest;
 
-use super::vector::vector_store_state_from_snapshot;
+use super::recovery::{committed_transactions, replay_wal_record, unfinished_transaction_records};
+use super::vector::{
+  apply_replayed_vectors, materialize_vector_store_from_lazy_entries,
+  vector_store_state_from_snapshot,
+};
 use super::{CheckpointStatus, SingleFileDB};
 
+/// Snapshot pages a background checkpoint writes per pager lock
+const CHECKPOINT_CHUNK_PAGES: u64 = 256;
+
 type GraphData = (
   Vec<NodeData>,
   Vec<EdgeData>,
@@ -25,6 +33,7 @@ type GraphData = (
   HashMap<ETypeId, String>,
   HashMap<PropKeyId, String>,
   HashMap<PropKeyId, VectorManifest>,
+  HashMap<String, Vec<u8>>,
 );
 
 impl SingleFileDB {
@@ -37,9 +46,13 @@ impl SingleFileDB {
   /// This:
   /// 1. Collects all graph data from snapshot + delta
   /// 2. Builds a new snapshot in memory
-  /// 3. Writes the new snapshot to disk (after WAL)
-  /// 4. Updates header to point to new snapshot
-  /// 5. Clears WAL and delta
+  /// 3. Flushes the WAL and sets checkpointInProgress (for crash recovery)
+  /// 4. Writes the new snapshot to pages the live snapshot doesn't use
+  /// 5. Updates header to point to new snapshot
+  /// 6. Clears WAL and delta
+  ///
+  /// A crash before step 5 leaves the header on the previous snapshot, and
+  /// the next open discards the partial new one.
   pub fn checkpoint(&self) -> Result<()> {
     if self.read_only {
       return Err(KiteError::ReadOnly);
@@ -51,7 +64,8 @@ impl SingleFileDB {
     }
 
     // Collect all graph data
-    let (nodes, edges, labels, etypes, propkeys, vector_stores) = self.collect_graph_data()?;
+    let (nodes, edges, labels, etypes, propkeys, vector_stores, metadata) =
+      self.collect_graph_data()?;
 
     // Get current header state
     let header = self.header.read().clone();
@@ -66,24 +80,48 @@ impl SingleFileDB {
       etypes,
       propkeys,
       vector_stores: Some(vector_stores),
+      metadata,
       compression: self.checkpoint_compression.clone(),
     })?;
 
-    // Calculate where to place new snapshot (after WAL)
-    let wal_end_page = header.wal_start_page + header.wal_page_count;
-    let new_snapshot_start_page = wal_end_page;
+    // Place the new snapshot where it can't clobber the live one
     let new_snapshot_page_count =
       pages_to_store(snapshot_buffer.len(), header.page_size as usize) as u64;
+    let new_snapshot_start_page = copy_on_write_start_page(&header, new_snapshot_page_count);
+
+    // Make the previous snapshot plus WAL durable and mark the checkpoint,
+    // so a crash while writing falls back to them
+    {
+      let mut pager = self.pager.lock();
+      let mut wal_buffer = self.wal_buffer.lock();
+      let mut header = self.header.write();
+      wal_buffer.flush(&mut pager)?;
+      header.wal_head = wal_buffer.head();
+      header.wal_tail = wal_buffer.tail();
+      header.wal_primary_head = wal_buffer.primary_head();
+      header.wal_secondary_head = wal_buffer.secondary_head();
+      header.checkpoint_in_progress = 1;
+      header.change_counter += 1;
+
+      let header_bytes = header.serialize_to_page();
+      pager.write_page(0, &header_bytes)?;
+      pager.sync()?;
+    }
 
     // Write snapshot to file
     {
       let mut pager = self.pager.lock();
-      self.write_snapshot_pages(
+      if let Err(err) = self.write_snapshot_pages(
         &mut pager,
         new_snapshot_start_page as u32,
         &snapshot_buffer,
         header.page_size as usize,
-      )?;
+      ) {
+        // The header still points at the previous snapshot; the next
+        // checkpoint or open overwrites or discards the partial one
+        self.header.write().checkpoint_in_progress = 0;
+        return Err(err);
+      }
     }
 
     // Update header
@@ -102,6 +140,7 @@ impl SingleFileDB {
       // Reset WAL
       header.wal_head = 0;
       header.wal_tail = 0;
+      header.checkpoint_in_progress = 0;
       wal_buffer.reset();
 
       // Increment change counter
@@ -124,9 +163,12 @@ impl SingleFileDB {
 
   /// Reload snapshot from disk after checkpoint
   pub(crate) fn reload_snapshot(&self) -> Result<()> {
-    let header = self.header.read();
+    let (snapshot_start_page, snapshot_page_count) = {
+      let header = self.header.read();
+      (header.snapshot_start_page, header.snapshot_page_count)
+    };
 
-    if header.snapshot_page_count == 0 {
+    if snapshot_page_count == 0 {
       // No snapshot to load
       *self.snapshot.write() = None;
       self.vector_stores.write().clear();
@@ -134,19 +176,7 @@ impl SingleFileDB {
       return Ok(());
     }
 
-    // Calculate snapshot offset in bytes
-    let snapshot_offset = (header.snapshot_start_page * header.page_size as u64) as usize;
-
-    // Re-mmap the file and parse snapshot
-    let pager = self.pager.lock();
-    let new_snapshot = SnapshotData::parse_at_offset(
-      std::sync::Arc::new({
-        // Safety handled inside map_file (native mmap) or in-memory read (wasm).
-        map_file(pager.file())?
-      }),
-      snapshot_offset,
-      &crate::core::snapshot::reader::ParseSnapshotOptions::default(),
-    )?;
+    let new_snapshot = self.parse_snapshot_at(snapshot_start_page)?;
 
     // Update the snapshot
     *self.snapshot.write() = Some(new_snapshot);
@@ -161,6 +191,24 @@ impl SingleFileDB {
     Ok(())
   }
 
+  /// Map the file and parse the snapshot starting at `start_page`
+  fn parse_snapshot_at(&self, start_page: u64) -> Result<SnapshotData> {
+    // Calculate snapshot offset in bytes
+    let page_size = self.header.read().page_size as u64;
+    let snapshot_offset = (start_page * page_size) as usize;
+
+    // Re-mmap the file and parse snapshot
+    let pager = self.pager.lock();
+    SnapshotData::parse_at_offset(
+      std::sync::Arc::new({
+        // Safety handled inside map_file (native mmap) or in-memory read (wasm).
+        map_file(pager.file())?
+      }),
+      snapshot_offset,
+      &crate::core::snapshot::reader::ParseSnapshotOptions::default(),
+    )
+  }
+
   // ========================================================================
   // Background Checkpoint (Non-Blocking)
   // ========================================================================
@@ -181,16 +229,17 @@ impl SingleFileDB {
 
   /// Trigger a background checkpoint (non-blocking)
   ///
-  /// This switches writes to secondary WAL region immediately and starts
-  /// the checkpoint process. Writes can continue while checkpoint is running.
+  /// Copy-on-write: other threads keep committing while the new snapshot is
+  /// built and written, and only wait at the final swap.
   ///
   /// Steps:
-  /// 1. Switch writes to secondary WAL region
-  /// 2. Set checkpointInProgress flag (for crash recovery)
-  /// 3. Build new snapshot from primary WAL + current snapshot + delta
-  /// 4. Write new snapshot to disk
-  /// 5. Merge secondary into primary, update header
-  /// 6. Clear checkpointInProgress flag
+  /// 1. Switch writes to secondary WAL region and set checkpointInProgress
+  ///    (for crash recovery); the primary region is now frozen
+  /// 2. Rebuild the state as of the switch from the snapshot and the frozen
+  ///    primary WAL, without touching the live delta
+  /// 3. Write the new snapshot to pages the live snapshot doesn't use
+  /// 4. Swap: merge secondary into primary, point the header at the new
+  ///    snapshot and rebuild the delta from the remaining WAL
   pub fn background_checkpoint(&self) -> Result<()> {
     if self.read_only {
       return Err(KiteError::ReadOnly);
@@ -215,29 +264,17 @@ impl SingleFileDB {
     }
 
     // Step 1: Switch writes to secondary region
-    {
-      let mut pager = self.pager.lock();
-      let mut wal_buffer = self.wal_buffer.lock();
-      let mut header = self.header.write();
-
-      // Switch WAL to secondary region
-      wal_buffer.switch_to_secondary();
-
-      // Update header to reflect the switch
-      header.active_wal_region = 1;
-      header.checkpoint_in_progress = 1;
-      header.wal_primary_head = wal_buffer.primary_head();
-      header.wal_secondary_head = wal_buffer.secondary_head();
-      header.change_counter += 1;
-
-      // Write header to disk
-      let header_bytes = header.serialize_to_page();
-      pager.write_page(0, &header_bytes)?;
-      pager.sync()?;
-    }
+    let frozen_wal = match self.switch_to_secondary_wal() {
+      Ok(frozen_wal) => frozen_wal,
+      Err(e) => {
+        // Nothing was switched if the flush failed, so the WAL needs no fixing
+        *self.checkpoint_status.lock() = CheckpointStatus::Idle;
+        return Err(e);
+      }
+    };
 
-    // Step 2-4: Build and write snapshot, get the info
-    let snapshot_info = match self.build_and_write_snapshot() {
+    // Step 2-3: Build and write snapshot, get the info
+    let (snapshot_info, carried) = match self.build_and_write_snapshot(frozen_wal) {
       Ok(info) => info,
       Err(e) => {
         // On error, try to recover
@@ -246,17 +283,83 @@ impl SingleFileDB {
       }
     };
 
-    // Step 5: Complete the checkpoint
-    self.complete_background_checkpoint(snapshot_info)?;
+    // Step 4: Complete the checkpoint
+    let result = self.complete_background_checkpoint(snapshot_info, &carried);
 
-    Ok(())
+    // Mark as idle
+    *self.checkpoint_status.lock() = CheckpointStatus::Idle;
+
+    result
+  }
+
+  /// Switch writes to the secondary WAL region and set
+  /// checkpointInProgress (step 1 of a background checkpoint)
+  ///
+  /// Returns the `(tail, head)` of the now frozen primary region, with every
+  /// record in it on disk so the snapshot build can read them without the
+  /// WAL buffer.
+  fn switch_to_secondary_wal(&self) -> Result<(u64, u64)> {
+    let mut pager = self.pager.lock();
+    let mut wal_buffer = self.wal_buffer.lock();
+    let mut header = self.header.write();
+
+    let frozen_wal = (wal_buffer.tail(), wal_buffer.primary_head());
+    wal_buffer.flush(&mut pager)?;
+    wal_buffer.switch_to_secondary();
+
+    // Update header to reflect the switch
+    header.active_wal_region = 1;
+    header.checkpoint_in_progress = 1;
+    header.wal_primary_head = wal_buffer.primary_head();
+    header.wal_secondary_head = wal_buffer.secondary_head();
+    header.change_counter += 1;
+
+    // Write header to disk
+    let header_bytes = header.serialize_to_page();
+    pager.write_page(0, &header_bytes)?;
+    pager.sync()?;
+    Ok(frozen_wal)
   }
 
   /// Build and write the snapshot (called during background checkpoint)
-  /// Returns (new_gen, new_snapshot_start_page, new_snapshot_page_count)
-  fn build_and_write_snapshot(&self) -> Result<(u64, u64, u64)> {
-    // Collect all graph data (reads from snapshot + delta)
-    let (nodes, edges, labels, etypes, propkeys, vector_stores) = self.collect_graph_data()?;
+  ///
+  /// Returns (new_gen, new_snapshot_start_page, new_snapshot_page_count) and
+  /// the primary records of transactions still open at the switch, which the
+  /// snapshot leaves out.
+  fn build_and_write_snapshot(
+    &self,
+    (wal_tail, wal_primary_head): (u64, u64),
+  ) -> Result<((u64, u64, u64), Vec<ParsedWalRecord>)> {
+    let records = self.read_frozen_wal(wal_tail, wal_primary_head)?;
+    let committed = committed_transactions(&records);
+    let carried = unfinished_transaction_records(&records);
+
+    // Collect all graph data (snapshot + frozen WAL, not the live delta)
+    let (nodes, edges, labels, etypes, propkeys, vector_stores, metadata) = {
+      let snapshot = self.snapshot.read();
+      let mut delta = self.replay_onto(snapshot.as_ref(), &committed);
+      let (mut stores, mut lazy_entries) = match snapshot.as_ref() {
+        Some(snapshot) => vector_store_state_from_snapshot(snapshot)?,
+        None => Default::default(),
+      };
+      apply_replayed_vectors(
+        snapshot.as_ref(),
+        &mut stores,
+        &mut lazy_entries,
+        &mut delta,
+      )?;
+      if let Some(snapshot) = snapshot.as_ref() {
+        for prop_key_id in lazy_entries.keys().copied().collect::<Vec<_>>() {
+          materialize_vector_store_from_lazy_entries(
+            snapshot,
+            &mut stores,
+            &mut lazy_entries,
+            prop_key_id,
+          )?;
+        }
+      }
+      self.graph_data_from(snapshot.as_ref(), &delta, stores)?
+    };
 
     // Get current header state
     let header = self.header.read().clone();
@@ -271,38 +374,62 @@ impl SingleFileDB {
       etypes,
       propkeys,
       vector_stores: Some(vector_stores),
+      metadata,
       compression: self.checkpoint_compression.clone(),
     })?;
 
-    // Calculate where to place new snapshot (after WAL)
-    let wal_end_page = header.wal_start_page + header.wal_page_count;
-    let new_snapshot_start_page = wal_end_page;
+    // Place the new snapshot where it can't clobber the live one
     let new_snapshot_page_count =
       pages_to_store(snapshot_buffer.len(), header.page_size as usize) as u64;
-
-    // Write snapshot to file
-    {
+    let new_snapshot_start_page = copy_on_write_start_page(&header, new_snapshot_page_count);
+
+    // Write snapshot to file, a chunk per pager lock so commits interleave
+    let page_size = header.page_size as usize;
+    let mut pages_written = 0u64;
+    while pages_written < new_snapshot_page_count {
+      let chunk_pages = (new_snapshot_page_count - pages_written).min(CHECKPOINT_CHUNK_PAGES);
+      let start = pages_written as usize * page_size;
+      let end = ((pages_written + chunk_pages) as usize * page_size).min(snapshot_buffer.len());
       let mut pager = self.pager.lock();
-      self.write_snapshot_pages(
+      write_pages(
         &mut pager,
-        new_snapshot_start_page as u32,
-        &snapshot_buffer,
-        header.page_size as usize,
+        (new_snapshot_start_page + pages_written) as u32,
+        &snapshot_buffer[start..end],
+        page_size,
       )?;
+      pages_written += chunk_pages;
     }
 
-    Ok((new_gen, new_snapshot_start_page, new_snapshot_page_count))
+    // Sync through a second handle so commits don't wait on the fsync
+    let file = self.pager.lock().file().try_clone()?;
+    file.sync_all()?;
+
+    Ok((
+      (new_gen, new_snapshot_start_page, new_snapshot_page_count),
+      carried,
+    ))
   }
 
   /// Complete the background checkpoint
-  fn complete_background_checkpoint(&self, snapshot_info: (u64, u64, u64)) -> Result<()> {
+  fn complete_background_checkpoint(
+    &self,
+    snapshot_info: (u64, u64, u64),
+    carried: &[ParsedWalRecord],
+  ) -> Result<()> {
     let (new_gen, new_snapshot_start_page, new_snapshot_page_count) = snapshot_info;
 
+    // Parse the new snapshot before blocking anyone
+    let new_snapshot = Some(self.parse_snapshot_at(new_snapshot_start_page)?);
+
     // Mark as completing (brief lock period)
     *self.checkpoint_status.lock() = CheckpointStatus::Completing;
 
+    // Commits stay blocked until the rebuilt delta is in place, so none can
+    // land in the old delta after its WAL records were read below
+    let _commit_guard = self.commit_lock.lock();
+
     // Merge secondary records into primary and update header
-    {
+    let records = {
       let mut pager = self.pager.lock();
       let mut wal_buffer = self.wal_buffer.lock();
       let mut header = self.header.write();
@@ -310,7 +437,7 @@ impl SingleFileDB {
       let old_snapshot_page_count = header.snapshot_page_count;
 
       // Merge secondary WAL records into primary
-      wal_buffer.merge_secondary_into_primary(&mut pager)?;
+      wal_buffer.merge_secondary_into_primary(carried, &mut pager)?;
       wal_buffer.flush(&mut pager)?;
 
       // Update header with new snapshot location
@@ -342,41 +469,68 @@ impl SingleFileDB {
           old_snapshot_page_count as u32,
         );
       }
-    }
 
-    // Clear delta
-    self.delta.write().clear();
+      wal_buffer.scan_records(&mut pager)?
+    };
 
-    // Reload the new snapshot
-    self.reload_snapshot()?;
+    // Rebuild the delta from what's left in the WAL: writes made during the
+    // checkpoint and the start of transactions still open
+    let committed = committed_transactions(&records);
+    let mut delta = self.replay_onto(new_snapshot.as_ref(), &committed);
+    let (mut stores, mut lazy_entries) = match new_snapshot.as_ref() {
+      Some(snapshot) => vector_store_state_from_snapshot(snapshot)?,
+      None => Default::default(),
+    };
+    apply_replayed_vectors(
+      new_snapshot.as_ref(),
+      &mut stores,
+      &mut lazy_entries,
+      &mut delta,
+    )?;
 
-    // Mark as idle
-    *self.checkpoint_status.lock() = CheckpointStatus::Idle;
+    *self.snapshot.write() = new_snapshot;
+    *self.vector_stores.write() = stores;
+    *self.vector_store_lazy_entries.write() = lazy_entries;
+    {
+      let mut live_delta = self.delta.write();
+      *live_delta = delta;
+      self.checkpoint_epoch.fetch_add(1, Ordering::SeqCst);
+    }
 
     Ok(())
   }
 
   /// Recover from a checkpoint error
+  ///
+  /// Folds both WAL regions back into the primary one; the live delta never
+  /// stopped tracking every commit, so only the on-disk WAL needs fixing.
   fn recover_from_checkpoint_error(&self) {
-    // Try to switch back to primary region and clear the checkpoint flag
-    if let Some(mut pager) = self.pager.try_lock() {
-      if let Some(mut wal_buffer) = self.wal_buffer.try_lock() {
-        if let Some(mut header) = self.header.try_write() {
-          // Switch back to primary
-          wal_buffer.switch_to_primary(false);
-
-          // Clear checkpoint flag
-          header.active_wal_region = 0;
-          header.checkpoint_in_progress = 0;
-
-          // Try to write header
-          let header_bytes = header.serialize_to_page();
-          if let Err(err) = pager.write_page(0, &header_bytes) {
-            eprintln!("Warning: Failed to write checkpoint header during recovery: {err}");
-          }
-          if let Err(err) = pager.sync() {
-            eprintln!("Warning: Failed to sync checkpoint header during recovery: {err}");
-          }
+    {
+      let mut pager = self.pager.lock();
+      let mut wal_buffer = self.wal_buffer.lock();
+      let mut header = self.header.write();
+
+      let merged = wal_buffer
+        .recover_incomplete_checkpoint(&mut pager)
+        .and_then(|()| wal_buffer.flush(&mut pager));
+      if let Err(err) = merged {
+        eprintln!("Warning: Failed to merge WAL regions during checkpoint recovery: {err}");
+      } else {
+        header.wal_head = wal_buffer.head();
+        header.wal_tail = wal_buffer.tail();
+        header.wal_primary_head = wal_buffer.primary_head();
+        header.wal_secondary_head = wal_buffer.secondary_head();
+        header.active_wal_region = 0;
+        header.checkpoint_in_progress = 0;
+        header.change_counter += 1;
+
+        // Try to write header
+        let header_bytes = header.serialize_to_page();
+        if let Err(err) = pager.write_page(0, &header_bytes) {
+          eprintln!("Warning: Failed to write checkpoint header during recovery: {err}");
+        }
+        if let Err(err) = pager.sync() {
+          eprintln!("Warning: Failed to sync checkpoint header during recovery: {err}");
         }
       }
     }
@@ -385,6 +539,81 @@ impl SingleFileDB {
     *self.checkpoint_status.lock() = CheckpointStatus::Idle;
   }
 
+  /// Records in the primary WAL region frozen by the switch to the
+  /// secondary region, read a page per pager lock
+  fn read_frozen_wal(&self, tail: u64, head: u64) -> Result<Vec<ParsedWalRecord>> {
+    if head <= tail {
+      return Ok(Vec::new());
+    }
+    let (wal_start_page, page_size) = {
+      let header = self.header.read();
+      (header.wal_start_page, header.page_size as u64)
+    };
+    let first_page = tail / page_size;
+    let mut wal_data = Vec::new();
+    for page in first_page..head.div_ceil(page_size) {
+      let page_data = self
+        .pager
+        .lock()
+        .read_page((wal_start_page + page) as u32)?;
+      wal_data.extend_from_slice(&page_data);
+    }
+
+    let base = (first_page * page_size) as usize;
+    let end = head as usize - base;
+    let mut offset = tail as usize - base;
+    let mut records = Vec::new();
+    while offset < end {
+      let Some(record) = parse_wal_record(&wal_data, offset) else {
+        break;
+      };
+      offset = record.record_end;
+      records.push(record);
+    }
+    Ok(records)
+  }
+
+  /// Replay committed transactions onto an empty delta over `snapshot`, as
+  /// open does. Allocators and schema maps are already up to date, so the
+  /// replay's updates to them are discarded.
+  fn replay_onto(
+    &self,
+    snapshot: Option<&SnapshotData>,
+    committed: &[(TxId, Vec<&ParsedWalRecord>)],
+  ) -> DeltaState {
+    let mut delta = DeltaState::new();
+    let mut next_node_id = 0;
+    let mut next_label_id = 0;
+    let mut next_etype_id = 0;
+    let mut next_propkey_id = 0;
+    let mut label_names = self.label_names.read().clone();
+    let mut label_ids = self.label_ids.read().clone();
+    let mut etype_names = self.etype_names.read().clone();
+    let mut etype_ids = self.etype_ids.read().clone();
+    let mut propkey_names = self.propkey_names.read().clone();
+    let mut propkey_ids = self.propkey_ids.read().clone();
+    for (_txid, records) in committed {
+      for record in records {
+        replay_wal_record(
+          record,
+          snapshot,
+          &mut delta,
+          &mut next_node_id,
+          &mut next_label_id,
+          &mut next_etype_id,
+          &mut next_propkey_id,
+          &mut label_names,
+          &mut label_ids,
+          &mut etype_names,
+          &mut etype_ids,
+          &mut propkey_names,
+          &mut propkey_ids,
+        );
+      }
+    }
+    delta
+  }
+
   /// Write snapshot buffer to file pages
   pub(crate) fn write_snapshot_pages(
     &self,
@@ -393,24 +622,7 @@ impl SingleFileDB {
     buffer: &[u8],
     page_size: usize,
   ) -> Result<()> {
-    let num_pages = pages_to_store(buffer.len(), page_size);
-
-    // Ensure file is large enough
-    let required_pages = start_page + num_pages;
-    let current_pages = (pager.file_size() as usize).div_ceil(page_size);
-
-    if required_pages as usize > current_pages {
-      pager.allocate_pages(required_pages - current_pages as u32)?;
-    }
-
-    // Write pages
-    for i in 0..num_pages {
-      let mut page_data = vec![0u8; page_size];
-      let src_offset = i as usize * page_size;
-      let src_end = std::cmp::min(src_offset + page_size, buffer.len());
-      page_data[..src_end - src_offset].copy_from_slice(&buffer[src_offset..src_end]);
-      pager.write_page(start_page + i, &page_data)?;
-    }
+    write_pages(pager, start_page, buffer, page_size)?;
 
     // Sync to disk
     pager.sync()?;
@@ -420,14 +632,27 @@ impl SingleFileDB {
 
   /// Collect all graph data from snapshot + delta
   pub(crate) fn collect_graph_data(&self) -> Result<GraphData> {
+    self.materialize_all_vector_stores()?;
+    let vector_stores = self.vector_stores.read().clone();
+    let delta = self.delta.read();
+    let snapshot = self.snapshot.read();
+    self.graph_data_from(snapshot.as_ref(), &delta, vector_stores)
+  }
+
+  /// Graph data for `snapshot` overlaid with `delta`, with `vector_stores`
+  /// holding every vector
+  fn graph_data_from(
+    &self,
+    snapshot: Option<&SnapshotData>,
+    delta: &DeltaState,
+    vector_stores_for_snapshot: HashMap<PropKeyId, VectorManifest>,
+  ) -> Result<GraphData> {
     let mut nodes = Vec::new();
     let mut edges = Vec::new();
     let mut labels = HashMap::new();
     let mut etypes = HashMap::new();
     let mut propkeys = HashMap::new();
 
-    let delta = self.delta.read();
-
     // First, copy schema from our in-memory maps
     for (&id, name) in self.label_ids.read().iter() {
       labels.insert(id, name.clone());
@@ -440,7 +665,7 @@ impl SingleFileDB {
     }
 
     // Collect nodes from snapshot
-    if let Some(ref snapshot) = *self.snapshot.read() {
+    if let Some(snapshot) = snapshot {
       let num_nodes = snapshot.header.num_nodes as usize;
 
       for phys in 0..num_nodes {
@@ -454,8 +679,12 @@ impl SingleFileDB {
           continue;
         }
 
-        // Get key
-        let key = snapshot.node_key(phys as u32);
+        // Get key (a re-keyed node carries its new key in the delta)
+        let key = delta
+          .modified_nodes
+          .get(&node_id)
+          .and_then(|d| d.key.clone())
+          .or_else(|| snapshot.node_key(phys as u32));
 
         // Get properties from snapshot
         let mut props = HashMap::new();
@@ -622,9 +851,6 @@ impl SingleFileDB {
 
     // Snapshot persistence now stores ANN vectors only in dedicated
     // vector-store sections. Remove duplicate vector payloads from node props.
-    self.materialize_all_vector_stores()?;
-    let vector_stores_for_snapshot: HashMap<PropKeyId, VectorManifest> =
-      self.vector_stores.read().clone();
     if !vector_stores_for_snapshot.is_empty() {
       for node in &mut nodes {
         node.props.retain(|prop_key_id, value| {
@@ -634,6 +860,17 @@ impl SingleFileDB {
       }
     }
 
+    let mut metadata = match snapshot {
+      Some(snapshot) => snapshot.metadata()?,
+      None => HashMap::new(),
+    };
+    for (key, value) in &delta.metadata {
+      match value {
+        Some(value) => metadata.insert(key.clone(), value.clone()),
+        None => metadata.remove(key),
+      };
+    }
+
     Ok((
       nodes,
       edges,
@@ -641,6 +878,7 @@ impl SingleFileDB {
       etypes,
       propkeys,
       vector_stores_for_snapshot,
+      metadata,
     ))
   }
 
@@ -650,3 +888,349 @@ impl SingleFileDB {
     usage >= threshold
   }
 }
+
+/// First page for a new snapshot of `page_count` pages that leaves the live
+/// snapshot intact, so readers and crash recovery can keep using it until
+/// the header switches over
+///
+/// Prefers the spot right after the WAL; otherwise goes past the live
+/// snapshot, leaving a gap that vacuum reclaims.
+fn copy_on_write_start_page(header: &DbHeaderV1, page_count: u64) -> u64 {
+  let wal_end_page = header.wal_start_page + header.wal_page_count;
+  if header.snapshot_page_count == 0 || wal_end_page + page_count <= header.snapshot_start_page {
+    wal_end_page
+  } else {
+    wal_end_page.max(header.snapshot_start_page + header.snapshot_page_count)
+  }
+}
+
+/// Write `buffer` to consecutive pages from `start_page`, growing the file
+/// as needed (no sync)
+fn write_pages(
+  pager: &mut FilePager,
+  start_page: u32,
+  buffer: &[u8],
+  page_size: usize,
+) -> Result<()> {
+  let num_pages = pages_to_store(buffer.len(), page_size);
+
+  // Ensure file is large enough
+  let required_pages = start_page + num_pages;
+  let current_pages = (pager.file_size() as usize).div_ceil(page_size);
+
+  if required_pages as usize > current_pages {
+    pager.allocate_pages(required_pages - current_pages as u32)?;
+  }
+
+  // Write pages
+  for i in 0..num_pages {
+    let mut page_data = vec![0u8; page_size];
+    let src_offset = i as usize * page_size;
+    let src_end = std::cmp::min(src_offset + page_size, buffer.len());
+    page_data[..src_end - src_offset].copy_from_slice(&buffer[src_offset..src_end]);
+    pager.write_page(start_page + i, &page_data)?;
+  }
+
+  Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::single_file::{close_single_file, open_single_file, SingleFileOpenOptions};
+  use std::sync::atomic::AtomicBool;
+  use std::sync::Barrier;
+  use tempfile::tempdir;
+
+  /// Where [`staged_checkpoint`] pauses
+  #[derive(Debug, Clone, Copy, PartialEq, Eq)]
+  enum Stage {
+    /// Writes switched to the secondary WAL region, snapshot not built yet
+    Switched,
+    /// New snapshot written, header and WAL not swapped yet
+    Written,
+  }
+
+  /// Run a background checkpoint one step at a time, calling `pause` between
+  /// steps with no locks held; `pause` returning false stops there, as a
+  /// crash would
+  fn staged_checkpoint(db: &SingleFileDB, mut pause: impl FnMut(Stage) -> bool) {
+    *db.checkpoint_status.lock() = CheckpointStatus::Running;
+    let frozen_wal = db.switch_to_secondary_wal().expect("expected value");
+    if !pause(Stage::Switched) {
+      return;
+    }
+    let (snapshot_info, carried) = db
+      .build_and_write_snapshot(frozen_wal)
+      .expect("expected value");
+    if !pause(Stage::Written) {
+      return;
+    }
+    db.complete_background_checkpoint(snapshot_info, &carried)
+      .expect("expected value");
+    *db.checkpoint_status.lock() = CheckpointStatus::Idle;
+  }
+
+  fn create_named(db: &SingleFileDB, key: &str) -> NodeId {
+    let name = db.propkey_id_or_create("name");
+    db.begin(false).expect("expected value");
+    let node_id = db.create_node(Some(key)).expect("expected value");
+    db.set_node_prop(node_id, name, PropValue::String(key.to_string()))
+      .expect("expected value");
+    db.commit().expect("expected value");
+    node_id
+  }
+
+  fn assert_named(db: &SingleFileDB, keys: &[(&str, NodeId)]) {
+    let name = db.propkey_id("name").expect("expected value");
+    assert_eq!(db.count_nodes(), keys.len());
+    for &(key, node_id) in keys {
+      assert_eq!(db.node_by_key(key), Some(node_id), "{key}");
+      assert_eq!(
+        db.node_prop(node_id, name),
+        Some(PropValue::String(key.to_string()))
+      );
+    }
+  }
+
+  #[test]
+  fn test_background_checkpoint_keeps_concurrent_writes() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("concurrent-checkpoint.kitedb");
+    let options = || {
+      SingleFileOpenOptions::new()
+        .auto_checkpoint(false)
+        .wal_size(32 * 1024 * 1024)
+    };
+
+    // Enough data that each checkpoint takes a while to build and write
+    let db = open_single_file(&db_path, options()).expect("expected value");
+    let name = db.propkey_id_or_create("name");
+    for batch in 0..20 {
+      db.begin(false).expect("expected value");
+      for i in 0..1_000 {
+        let node_id = db
+          .create_node(Some(&format!("seed{batch}-{i}")))
+          .expect("expected value");
+        db.set_node_prop(node_id, name, PropValue::String(format!("node {i}")))
+          .expect("expected value");
+      }
+      db.commit().expect("expected value");
+    }
+
+    let done = AtomicBool::new(false);
+    let written = std::thread::scope(|scope| {
+      scope.spawn(|| {
+        for _ in 0..3 {
+          db.background_checkpoint().expect("expected value");
+        }
+        done.store(true, Ordering::SeqCst);
+      });
+
+      let mut written = Vec::new();
+      while !done.load(Ordering::SeqCst) {
+        db.begin(false).expect("expected value");
+        let key = format!("live{}", written.len());
+        let node_id = db.create_node(Some(&key)).expect("expected value");
+        db.set_node_prop(node_id, name, PropValue::I64(written.len() as i64))
+          .expect("expected value");
+        db.commit().expect("expected value");
+        written.push((key, node_id));
+      }
+      written
+    });
+
+    assert_eq!(db.checkpoint_status(), CheckpointStatus::Idle);
+
+    let check = |db: &SingleFileDB| {
+      assert_eq!(db.count_nodes(), 20_000 + written.len());
+      for (i, (key, node_id)) in written.iter().enumerate() {
+        assert_eq!(db.node_by_key(key), Some(*node_id));
+        assert_eq!(db.node_prop(*node_id, name), Some(PropValue::I64(i as i64)));
+      }
+    };
+    check(&db);
+    close_single_file(db).expect("expected value");
+
+    let db = open_single_file(&db_path, options()).expect("expected value");
+    check(&db);
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_background_checkpoint_keeps_open_transaction() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("checkpoint-open-tx.kitedb");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    db.begin(false).expect("expected value");
+    db.create_node(Some("before")).expect("expected value");
+    db.commit().expect("expected value");
+
+    // The transaction's first records land in the WAL region being
+    // checkpointed, its commit after the swap
+    db.begin(false).expect("expected value");
+    let open = db.create_node(Some("open")).expect("expected value");
+    db.background_checkpoint().expect("expected value");
+    let after = db.create_node(Some("after")).expect("expected value");
+    db.add_edge(open, db.etype_id_or_create("NEXT"), after)
+      .expect("expected value");
+    db.commit().expect("expected value");
+
+    assert_eq!(db.node_by_key("open"), Some(open));
+    assert_eq!(db.node_by_key("after"), Some(after));
+    close_single_file(db).expect("expected value");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    assert!(db.node_by_key("before").is_some());
+    assert_eq!(db.node_by_key("open"), Some(open));
+    assert_eq!(db.node_by_key("after"), Some(after));
+    assert_eq!(db.out_degree(open), 1);
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_commit_while_background_checkpoint_is_held() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("checkpoint-held.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    let before = create_named(&db, "before");
+
+    // The checkpoint thread parks at each stage until this thread has
+    // committed; a commit waiting on the checkpoint would never return
+    let reached = Barrier::new(2);
+    let resume = Barrier::new(2);
+    let written = std::thread::scope(|scope| {
+      scope.spawn(|| {
+        staged_checkpoint(&db, |_| {
+          reached.wait();
+          resume.wait();
+          true
+        })
+      });
+
+      let mut written = Vec::new();
+      for key in ["switched", "written"] {
+        reached.wait();
+        assert!(db.is_checkpoint_running());
+        written.push((key, create_named(&db, key)));
+        resume.wait();
+      }
+      written
+    });
+
+    assert_eq!(db.checkpoint_status(), CheckpointStatus::Idle);
+    let expected = [("before", before), written[0], written[1]];
+    assert_named(&db, &expected);
+    close_single_file(db).expect("expected value");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    assert_named(&db, &expected);
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_commit_between_snapshot_write_and_swap() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("checkpoint-before-swap.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    let before = create_named(&db, "before");
+    let name = db.propkey_id("name").expect("expected value");
+
+    let mut late = None;
+    staged_checkpoint(&db, |stage| {
+      if stage == Stage::Written {
+        // Lands in the secondary region after the snapshot was built from
+        // the frozen primary one, and overwrites a prop the snapshot holds
+        late = Some(create_named(&db, "late"));
+        db.begin(false).expect("expected value");
+        db.set_node_prop(before, name, PropValue::String("renamed".into()))
+          .expect("expected value");
+        db.commit().expect("expected value");
+      }
+      true
+    });
+    let late = late.expect("expected value");
+
+    let check = |db: &SingleFileDB| {
+      assert_eq!(db.count_nodes(), 2);
+      assert_eq!(db.node_by_key("late"), Some(late));
+      assert_eq!(
+        db.node_prop(before, name),
+        Some(PropValue::String("renamed".into()))
+      );
+    };
+    check(&db);
+    // The late writes stay in the WAL, on top of the new snapshot
+    assert!(db.wal_buffer.lock().head() > 0);
+    close_single_file(db).expect("expected value");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    check(&db);
+    db.checkpoint().expect("expected value");
+    check(&db);
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_crash_during_background_checkpoint() {
+    for crash_at in [Stage::Switched, Stage::Written] {
+      let temp_dir = tempdir().expect("expected value");
+      let db_path = temp_dir.path().join("checkpoint-crash.kitedb");
+      let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+      let before = create_named(&db, "before");
+      db.checkpoint().expect("expected value");
+      let primary = create_named(&db, "primary");
+      let live_pages = {
+        let header = db.header.read();
+        header.snapshot_start_page + header.snapshot_page_count
+      };
+
+      let mut secondary = None;
+      staged_checkpoint(&db, |stage| {
+        if stage == Stage::Switched {
+          secondary = Some(create_named(&db, "secondary"));
+        }
+        stage != crash_at
+      });
+      let secondary = secondary.expect("expected value");
+      assert!(db.header.read().checkpoint_in_progress != 0);
+      drop(db);
+
+      let expected = [
+        ("before", before),
+        ("primary", primary),
+        ("secondary", secondary),
+      ];
+      let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+      assert_named(&db, &expected);
+      assert_eq!(db.header.read().checkpoint_in_progress, 0);
+      assert_eq!(db.header.read().active_wal_region, 0);
+      assert!(
+        db.pager.lock().file_size() <= live_pages * db.header.read().page_size as u64,
+        "partial snapshot left behind after crash at {crash_at:?}"
+      );
+      assert!(db
+        .open_warnings()
+        .iter()
+        .any(|warning| warning.contains("checkpoint interrupted")));
+
+      // Writes and checkpoints carry on as usual after recovery
+      let after = create_named(&db, "after");
+      db.background_checkpoint().expect("expected value");
+      close_single_file(db).expect("expected value");
+
+      let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+      assert_named(
+        &db,
+        &[
+          ("before", before),
+          ("primary", primary),
+          ("secondary", secondary),
+          ("after", after),
+        ],
+      );
+      close_single_file(db).expect("expected value");
+    }
+  }
+}
@@ -2,13 +2,14 @@
 
 use std::sync::atomic::Ordering;
 
-use crate::core::pager::pages_to_store;
+use crate::core::pager::{pages_to_store, FilePager};
 use crate::core::snapshot::writer::{build_snapshot_to_memory, SnapshotBuildInput};
 use crate::core::wal::buffer::WalBuffer;
 use crate::error::{KiteError, Result};
 use crate::util::compression::CompressionOptions;
 
-use super::SingleFileDB;
+use super::recovery::unfinished_transaction_records;
+use super::{CheckpointStatus, SingleFileDB};
 
 /// Options for single-file optimize operation
 #[derive(Debug, Clone, Default)]
@@ -17,6 +18,13 @@ pub struct SingleFileOptimizeOptions {
   pub compression: Option<CompressionOptions>,
 }
 
+/// Progress of an optimize, reported after each chunk of the new snapshot
+#[derive(Debug, Clone, Copy, Default)]
+pub struct OptimizeProgress {
+  pub pages_written: u64,
+  pub total_pages: u64,
+}
+
 /// Options for vacuum operation
 #[derive(Debug, Clone)]
 pub struct VacuumOptions {
@@ -56,11 +64,34 @@ impl Default for VacuumOptions {
 /// Minimum WAL pages to keep (64KB at 4KB page size)
 const MIN_WAL_PAGES: u64 = 16;
 
+/// Snapshot pages optimize writes per pager lock (1MB at 4KB page size)
+const OPTIMIZE_CHUNK_PAGES: u64 = 256;
+
 impl SingleFileDB {
   /// Optimize (compact) a single-file database.
   ///
   /// This merges snapshot + delta into a new snapshot and clears WAL.
   pub fn optimize_single_file(&self, options: Option<SingleFileOptimizeOptions>) -> Result<()> {
+    self.optimize_single_file_with_progress(options, |_| {})
+  }
+
+  /// Optimize with a progress callback
+  ///
+  /// The new snapshot is written in bounded chunks, releasing the pager
+  /// between them so readers aren't held up for the whole write.
+  /// `on_progress` runs after each chunk. Commits wait until the optimize
+  /// finishes: it resets the WAL and delta, so nothing may land after the
+  /// graph data is collected. An interrupted optimize leaves the header on
+  /// the old snapshot and is simply run again; there is no partial state to
+  /// resume from.
+  pub fn optimize_single_file_with_progress<F>(
+    &self,
+    options: Option<SingleFileOptimizeOptions>,
+    mut on_progress: F,
+  ) -> Result<()>
+  where
+    F: FnMut(&OptimizeProgress),
+  {
     if self.read_only {
       return Err(KiteError::ReadOnly);
     }
@@ -69,14 +100,40 @@ impl SingleFileDB {
       return Err(KiteError::TransactionInProgress);
     }
 
-    if self.is_checkpoint_running() {
-      // Wait for background checkpoint to complete (mirrors TS behavior)
-      while self.is_checkpoint_running() {
-        std::thread::sleep(std::time::Duration::from_millis(1));
+    self.with_checkpoint_slot(|| self.optimize_exclusive(options, &mut on_progress))
+  }
+
+  /// Run `f` holding the checkpoint slot
+  ///
+  /// Waits for a running checkpoint (mirrors TS behavior), then keeps others
+  /// from starting, so none swaps in a snapshot of its own meanwhile.
+  fn with_checkpoint_slot<R>(&self, f: impl FnOnce() -> Result<R>) -> Result<R> {
+    loop {
+      let mut status = self.checkpoint_status.lock();
+      if *status == CheckpointStatus::Idle {
+        *status = CheckpointStatus::Running;
+        break;
       }
+      drop(status);
+      std::thread::sleep(std::time::Duration::from_millis(1));
     }
 
-    let (nodes, edges, labels, etypes, propkeys, vector_stores) = self.collect_graph_data()?;
+    let result = f();
+    *self.checkpoint_status.lock() = CheckpointStatus::Idle;
+    result
+  }
+
+  /// Body of an optimize, run while holding the checkpoint slot
+  fn optimize_exclusive(
+    &self,
+    options: Option<SingleFileOptimizeOptions>,
+    on_progress: &mut dyn FnMut(&OptimizeProgress),
+  ) -> Result<()> {
+    let _commit_guard = self.commit_lock.lock();
+    self.wait_for_applying_commits();
+
+    let (nodes, edges, labels, etypes, propkeys, vector_stores, metadata) =
+      self.collect_graph_data()?;
 
     let header = self.header.read().clone();
     let old_snapshot_start_page = header.snapshot_start_page;
@@ -92,6 +149,7 @@ impl SingleFileDB {
       etypes,
       propkeys,
       vector_stores: Some(vector_stores),
+      metadata,
       compression,
     })?;
 
@@ -100,14 +158,26 @@ impl SingleFileDB {
     let new_snapshot_page_count =
       pages_to_store(snapshot_buffer.len(), header.page_size as usize) as u64;
 
-    {
-      let mut pager = self.pager.lock();
-      self.write_snapshot_pages(
-        &mut pager,
-        new_snapshot_start_page as u32,
-        &snapshot_buffer,
-        header.page_size as usize,
-      )?;
+    let page_size = header.page_size as usize;
+    let mut pages_written = 0u64;
+    while pages_written < new_snapshot_page_count {
+      let chunk_pages = (new_snapshot_page_count - pages_written).min(OPTIMIZE_CHUNK_PAGES);
+      let start = pages_written as usize * page_size;
+      let end = ((pages_written + chunk_pages) as usize * page_size).min(snapshot_buffer.len());
+      {
+        let mut pager = self.pager.lock();
+        self.write_snapshot_pages(
+          &mut pager,
+          (new_snapshot_start_page + pages_written) as u32,
+          &snapshot_buffer[start..end],
+          page_size,
+        )?;
+      }
+      pages_written += chunk_pages;
+      on_progress(&OptimizeProgress {
+        pages_written,
+        total_pages: new_snapshot_page_count,
+      });
     }
 
     {
@@ -122,9 +192,12 @@ impl SingleFileDB {
       header.max_node_id = self.next_node_id.load(Ordering::SeqCst).saturating_sub(1);
       header.next_tx_id = self.next_tx_id.load(Ordering::SeqCst);
 
-      header.wal_head = 0;
-      header.wal_tail = 0;
-      wal_buffer.reset();
+      self.reset_wal_keeping_open(&mut pager, &mut wal_buffer)?;
+      header.wal_head = wal_buffer.head();
+      header.wal_tail = wal_buffer.tail();
+      header.wal_primary_head = wal_buffer.primary_head();
+      header.wal_secondary_head = wal_buffer.secondary_head();
+      header.active_wal_region = 0;
 
       header.change_counter += 1;
 
@@ -146,6 +219,22 @@ impl SingleFileDB {
     Ok(())
   }
 
+  /// Empty the WAL except for the records of transactions still open
+  ///
+  /// Callers hold `commit_lock` and have folded every committed record into
+  /// the new snapshot, so only open transactions need their records kept.
+  fn reset_wal_keeping_open(
+    &self,
+    pager: &mut FilePager,
+    wal_buffer: &mut WalBuffer,
+  ) -> Result<()> {
+    wal_buffer.flush(pager)?;
+    let records = wal_buffer.scan_records(pager)?;
+    let carried = unfinished_transaction_records(&records);
+    wal_buffer.merge_secondary_into_primary(&carried, pager)?;
+    wal_buffer.flush(pager)
+  }
+
   /// Vacuum operation - shrink file by reclaiming free pages.
   pub fn vacuum_single_file(&self, options: Option<VacuumOptions>) -> Result<()> {
     if self.read_only {
@@ -183,6 +272,14 @@ impl SingleFileDB {
     };
     let new_wal_end_page = new_header.wal_start_page + new_wal_page_count;
 
+    if can_shrink_wal {
+      // Pages past the new WAL end belong to the main file again
+      self
+        .pager
+        .lock()
+        .set_wal_page_count(new_wal_page_count as u32)?;
+    }
+
     if new_header.snapshot_page_count > 0 {
       let current_snapshot_start = new_header.snapshot_start_page;
       let new_snapshot_start = new_wal_end_page;
@@ -245,6 +342,59 @@ impl SingleFileDB {
     Ok(())
   }
 
+  /// Discard WAL records already captured by the snapshot.
+  ///
+  /// Meant for after a backup: it resets the WAL without waiting for the
+  /// next checkpoint, and fails with [`KiteError::UncheckpointedChanges`] if
+  /// the delta holds changes the snapshot doesn't have.
+  /// An external WAL file (see `wal_path`) also releases its disk blocks;
+  /// the in-file WAL region keeps its fixed size. Returns the WAL bytes
+  /// discarded.
+  pub fn truncate_wal_to_snapshot(&self) -> Result<u64> {
+    if self.read_only {
+      return Err(KiteError::ReadOnly);
+    }
+
+    if self.has_any_transaction() {
+      return Err(KiteError::TransactionInProgress);
+    }
+
+    self.with_checkpoint_slot(|| {
+      // Checked under the commit lock so no commit lands between the check
+      // and the reset
+      let _commit_guard = self.commit_lock.lock();
+      self.wait_for_applying_commits();
+      if !self.delta.read().is_empty() {
+        return Err(KiteError::UncheckpointedChanges);
+      }
+
+      let mut pager = self.pager.lock();
+      let mut wal_buffer = self.wal_buffer.lock();
+      let mut header = self.header.write();
+
+      let used = wal_buffer.used();
+      self.reset_wal_keeping_open(&mut pager, &mut wal_buffer)?;
+      let discarded = used.saturating_sub(wal_buffer.used());
+      header.wal_head = wal_buffer.head();
+      header.wal_tail = wal_buffer.tail();
+      header.wal_primary_head = wal_buffer.primary_head();
+      header.wal_secondary_head = wal_buffer.secondary_head();
+      header.active_wal_region = 0;
+      header.max_node_id = self.next_node_id.load(Ordering::SeqCst).saturating_sub(1);
+      header.next_tx_id = self.next_tx_id.load(Ordering::SeqCst);
+      header.change_counter += 1;
+
+      let header_bytes = header.serialize_to_page();
+      pager.write_page(0, &header_bytes)?;
+      pager.sync()?;
+      if wal_buffer.used() == 0 {
+        pager.clear_wal_file()?;
+      }
+
+      Ok(discarded)
+    })
+  }
+
   /// Resize the WAL region (single-file only).
   ///
   /// This operation is offline (no active transactions). By default it
@@ -304,6 +454,11 @@ impl SingleFileDB {
     let mut new_header = header.clone();
     let new_wal_end_page = new_header.wal_start_page + new_wal_page_count;
 
+    self
+      .pager
+      .lock()
+      .set_wal_page_count(new_wal_page_count as u32)?;
+
     if new_header.snapshot_page_count > 0 {
       let current_snapshot_start = new_header.snapshot_start_page;
       if current_snapshot_start != new_wal_end_page {
@@ -375,6 +530,7 @@ impl SingleFileDB {
 mod tests {
   use super::*;
   use crate::core::single_file::{close_single_file, open_single_file, SingleFileOpenOptions};
+  use crate::types::PropValue;
   use tempfile::tempdir;
 
   #[test]
@@ -396,4 +552,137 @@ mod tests {
 
     Ok(())
   }
+
+  #[test]
+  fn test_truncate_wal_to_snapshot() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("truncate-wal.kitedb");
+
+    let db = open_single_file(
+      &db_path,
+      SingleFileOpenOptions::new().auto_checkpoint(false),
+    )?;
+    db.begin(false)?;
+    db.create_node(Some("a"))?;
+    db.commit()?;
+
+    // Uncheckpointed changes keep the WAL
+    assert!(matches!(
+      db.truncate_wal_to_snapshot(),
+      Err(KiteError::UncheckpointedChanges)
+    ));
+    assert!(db.wal_stats().used > 0);
+    close_single_file(db)?;
+
+    // ...including across a reopen, where the delta is rebuilt from the WAL
+    let db = open_single_file(
+      &db_path,
+      SingleFileOpenOptions::new().auto_checkpoint(false),
+    )?;
+    assert!(matches!(
+      db.truncate_wal_to_snapshot(),
+      Err(KiteError::UncheckpointedChanges)
+    ));
+    assert!(db.node_by_key("a").is_some());
+
+    db.checkpoint()?;
+    // A node created and deleted again leaves WAL records but no delta
+    db.begin(false)?;
+    let temp = db.create_node(None)?;
+    db.delete_node(temp)?;
+    db.commit()?;
+    assert!(db.delta.read().is_empty());
+
+    let discarded = db.truncate_wal_to_snapshot()?;
+    assert!(discarded > 0);
+    assert_eq!(db.wal_stats().used, 0);
+    close_single_file(db)?;
+
+    let reopened = open_single_file(&db_path, SingleFileOpenOptions::new())?;
+    assert!(reopened.node_by_key("a").is_some());
+    assert_eq!(reopened.header.read().wal_head, 0);
+    close_single_file(reopened)?;
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_optimize_reports_chunked_progress() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("optimize-progress.kitedb");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new())?;
+    db.begin(false)?;
+    let blob = db.define_propkey("blob")?;
+    // Pseudo-random text so snapshot compression can't shrink it to one chunk
+    let mut seed = 0x2545_f491_4f6c_dd1du64;
+    for i in 0..300 {
+      let node = db.create_node(Some(&format!("n:{i}")))?;
+      let text: String = (0..8 * 1024)
+        .map(|_| {
+          seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1);
+          char::from(b'!' + (seed >> 58) as u8)
+        })
+        .collect();
+      db.set_node_prop(node, blob, PropValue::String(text))?;
+    }
+    db.commit()?;
+
+    let mut reports = Vec::new();
+    db.optimize_single_file_with_progress(None, |progress| reports.push(*progress))?;
+
+    let total = reports.last().expect("progress reported").total_pages;
+    assert!(
+      reports.len() > 1,
+      "expected several chunks for {total} pages"
+    );
+    assert!(reports
+      .windows(2)
+      .all(|w| w[0].pages_written < w[1].pages_written));
+    assert_eq!(reports.last().map(|p| p.pages_written), Some(total));
+    close_single_file(db)?;
+
+    let reopened = open_single_file(&db_path, SingleFileOpenOptions::new())?;
+    assert_eq!(reopened.count_nodes(), 300);
+    close_single_file(reopened)?;
+
+    Ok(())
+  }
+
+  #[test]
+  fn test_commit_during_optimize_survives_reopen() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("optimize-concurrent.kitedb");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new())?;
+    db.begin(false)?;
+    db.create_node(Some("before"))?;
+    db.commit()?;
+
+    std::thread::scope(|scope| -> Result<()> {
+      let mut writer = None;
+      db.optimize_single_file_with_progress(None, |_| {
+        if writer.is_none() {
+          // Starts mid-optimize; its commit must not be lost to the WAL reset
+          writer = Some(scope.spawn(|| -> Result<()> {
+            db.begin(false)?;
+            db.create_node(Some("during"))?;
+            db.commit()
+          }));
+          // Give the writer time to reach its commit before the WAL reset
+          std::thread::sleep(std::time::Duration::from_millis(50));
+        }
+      })?;
+      writer.expect("progress reported").join().expect("writer")
+    })?;
+    assert!(db.node_by_key("during").is_some());
+    close_single_file(db)?;
+
+    let reopened = open_single_file(&db_path, SingleFileOpenOptions::new())?;
+    assert!(reopened.node_by_key("before").is_some());
+    assert!(reopened.node_by_key("during").is_some());
+    close_single_file(reopened)?;
+
+    Ok(())
+  }
 }
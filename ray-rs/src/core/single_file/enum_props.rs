@@ -0,0 +1,316 @@
+//! Interned values for enum properties
+//!
+//! A prop key with an enum dictionary stores each string value as its index
+//! in the dictionary (a `PropValue::I64`), in the WAL, the delta and
+//! snapshots alike; reads turn the ids back into strings. Dictionaries only
+//! grow, so ids stay stable, and they live in the database metadata.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use crate::core::snapshot::reader::SnapshotData;
+use crate::error::{KiteError, Result};
+use crate::types::*;
+
+use super::SingleFileDB;
+
+/// Metadata key prefix holding the enum dictionary of a prop key
+const ENUM_VALUES_METADATA_PREFIX: &str = "propkey:enum_values:";
+
+fn enum_values_metadata_key(key_id: PropKeyId) -> String {
+  format!("{ENUM_VALUES_METADATA_PREFIX}{key_id}")
+}
+
+/// Values of an enum prop key, indexed by their interned id
+#[derive(Debug, Default)]
+pub(crate) struct EnumDictionary {
+  values: Vec<String>,
+  ids: HashMap<String, u32>,
+}
+
+impl EnumDictionary {
+  fn new(values: Vec<String>) -> Self {
+    let ids = values
+      .iter()
+      .enumerate()
+      .map(|(id, value)| (value.clone(), id as u32))
+      .collect();
+    Self { values, ids }
+  }
+
+  /// Parse a dictionary written by [`Self::to_bytes`]
+  fn from_bytes(bytes: &[u8]) -> Option<Self> {
+    let mut values = Vec::new();
+    let mut rest = bytes;
+    while !rest.is_empty() {
+      let (len, tail) = rest.split_first_chunk::<4>()?;
+      let len = u32::from_le_bytes(*len) as usize;
+      if tail.len() < len {
+        return None;
+      }
+      let (value, tail) = tail.split_at(len);
+      values.push(String::from_utf8(value.to_vec()).ok()?);
+      rest = tail;
+    }
+    Some(Self::new(values))
+  }
+
+  fn to_bytes(&self) -> Vec<u8> {
+    let mut out = Vec::new();
+    for value in &self.values {
+      out.extend_from_slice(&(value.len() as u32).to_le_bytes());
+      out.extend_from_slice(value.as_bytes());
+    }
+    out
+  }
+
+  /// Values in id order
+  pub(crate) fn values(&self) -> &[String] {
+    &self.values
+  }
+}
+
+/// Enum dictionaries in the committed metadata, by prop key
+///
+/// Malformed entries are skipped, leaving their key without a dictionary.
+pub(crate) fn load_enum_props(
+  snapshot: Option<&SnapshotData>,
+  delta: &DeltaState,
+) -> Result<HashMap<PropKeyId, Arc<EnumDictionary>>> {
+  let mut entries = match snapshot {
+    Some(snapshot) => snapshot.metadata()?,
+    None => HashMap::new(),
+  };
+  for (key, value) in &delta.metadata {
+    match value {
+      Some(value) => entries.insert(key.clone(), value.clone()),
+      None => entries.remove(key),
+    };
+  }
+  let mut dictionaries = HashMap::new();
+  for (key, value) in entries {
+    let Some(key_id) = parse_metadata_key(&key) else {
+      continue;
+    };
+    if let Some(dictionary) = EnumDictionary::from_bytes(&value) {
+      dictionaries.insert(key_id, Arc::new(dictionary));
+    }
+  }
+  Ok(dictionaries)
+}
+
+fn parse_metadata_key(key: &str) -> Option<PropKeyId> {
+  key.strip_prefix(ENUM_VALUES_METADATA_PREFIX)?.parse().ok()
+}
+
+impl SingleFileDB {
+  /// Store string values of `key_id` as small ids into an enum dictionary
+  ///
+  /// `values` are appended to the key's dictionary if missing; existing ids
+  /// never change. The dictionary is written in the current write
+  /// transaction and applies to writes once it commits. From then on the
+  /// key only takes null or one of its values: writes store the value's id
+  /// and reads return the string again.
+  pub fn set_prop_enum_values<I, S>(&self, key_id: PropKeyId, values: I) -> Result<()>
+  where
+    I: IntoIterator<Item = S>,
+    S: Into<String>,
+  {
+    let key = enum_values_metadata_key(key_id);
+    let mut dictionary = match self.metadata(&key)? {
+      Some(bytes) => EnumDictionary::from_bytes(&bytes).ok_or_else(|| {
+        KiteError::Serialization(format!(
+          "Malformed enum values metadata for prop key {key_id}"
+        ))
+      })?,
+      None => EnumDictionary::default(),
+    };
+    let mut changed = false;
+    for value in values {
+      let value = value.into();
+      if !dictionary.ids.contains_key(&value) {
+        dictionary
+          .ids
+          .insert(value.clone(), dictionary.values.len() as u32);
+        dictionary.values.push(value);
+        changed = true;
+      }
+    }
+    if !changed {
+      return Ok(());
+    }
+    self.set_metadata(&key, &dictionary.to_bytes())
+  }
+
+  /// Committed enum values of `key_id` in id order, if it has a dictionary
+  pub fn prop_enum_values(&self, key_id: PropKeyId) -> Option<Vec<String>> {
+    self
+      .enum_props
+      .read()
+      .get(&key_id)
+      .map(|dictionary| dictionary.values().to_vec())
+  }
+
+  /// Apply committed metadata writes to the cached enum dictionaries
+  pub(crate) fn apply_enum_props_metadata(&self, metadata: &HashMap<String, Option<Vec<u8>>>) {
+    for (key, value) in metadata {
+      let Some(key_id) = parse_metadata_key(key) else {
+        continue;
+      };
+      let dictionary = value.as_deref().and_then(EnumDictionary::from_bytes);
+      let mut dictionaries = self.enum_props.write();
+      match dictionary {
+        Some(dictionary) => {
+          dictionaries.insert(key_id, Arc::new(dictionary));
+        }
+        None => {
+          dictionaries.remove(&key_id);
+        }
+      }
+    }
+  }
+
+  /// The value to store for a write of `value` under `key_id`
+  ///
+  /// Strings of an enum key become their id; any other non-null value is
+  /// rejected, since it would read back as an enum value.
+  pub(crate) fn intern_prop_value(&self, key_id: PropKeyId, value: PropValue) -> Result<PropValue> {
+    let dictionaries = self.enum_props.read();
+    let Some(dictionary) = dictionaries.get(&key_id) else {
+      return Ok(value);
+    };
+    let id = match &value {
+      PropValue::Null => return Ok(value),
+      PropValue::String(s) => dictionary.ids.get(s).copied(),
+      _ => None,
+    };
+    match id {
+      Some(id) => Ok(PropValue::I64(id as i64)),
+      None => {
+        let name = self
+          .propkey_name(key_id)
+          .unwrap_or_else(|| format!("prop_{key_id}"));
+        Err(KiteError::Constraint(format!(
+          "Property '{name}' must be one of [{}], got {value:?}",
+          dictionary.values().join(", ")
+        )))
+      }
+    }
+  }
+
+  /// [`Self::intern_prop_value`] for every value in `props`
+  pub(crate) fn intern_prop_values(
+    &self,
+    props: Vec<(PropKeyId, PropValue)>,
+  ) -> Result<Vec<(PropKeyId, PropValue)>> {
+    if self.enum_props.read().is_empty() {
+      return Ok(props);
+    }
+    props
+      .into_iter()
+      .map(|(key_id, value)| Ok((key_id, self.intern_prop_value(key_id, value)?)))
+      .collect()
+  }
+
+  /// The value a read of `key_id` returns for `stored`
+  ///
+  /// Enum ids become their string. Strings stored before the key had a
+  /// dictionary are returned as they are.
+  pub(crate) fn resolve_prop_value(&self, key_id: PropKeyId, stored: PropValue) -> PropValue {
+    let PropValue::I64(id) = stored else {
+      return stored;
+    };
+    let dictionaries = self.enum_props.read();
+    let value = dictionaries
+      .get(&key_id)
+      .and_then(|dictionary| dictionary.values().get(usize::try_from(id).ok()?));
+    match value {
+      Some(value) => PropValue::String(value.clone()),
+      None => stored,
+    }
+  }
+
+  /// [`Self::resolve_prop_value`] for every value in `props`
+  pub(crate) fn resolve_prop_values(
+    &self,
+    mut props: HashMap<PropKeyId, PropValue>,
+  ) -> HashMap<PropKeyId, PropValue> {
+    if self.enum_props.read().is_empty() {
+      return props;
+    }
+    for (&key_id, value) in props.iter_mut() {
+      let stored = std::mem::replace(value, PropValue::Null);
+      *value = self.resolve_prop_value(key_id, stored);
+    }
+    props
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::core::single_file::{close_single_file, open_single_file, SingleFileOpenOptions};
+  use crate::error::KiteError;
+  use crate::types::PropValue;
+  use tempfile::tempdir;
+
+  #[test]
+  fn enum_values_are_stored_as_ids_and_read_as_strings() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("enum-props.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let node = db.create_node(Some("n1")).expect("expected value");
+    let status = db.define_propkey("status").expect("expected value");
+    db.set_prop_enum_values(status, ["active", "inactive"])
+      .expect("expected value");
+    db.commit().expect("expected value");
+
+    db.begin(false).expect("expected value");
+    db.set_node_prop(node, status, PropValue::String("inactive".to_string()))
+      .expect("expected value");
+    let err = db
+      .set_node_prop(node, status, PropValue::String("activ".to_string()))
+      .expect_err("expected constraint error");
+    assert!(matches!(err, KiteError::Constraint(_)));
+    let err = db
+      .set_node_prop(node, status, PropValue::I64(0))
+      .expect_err("expected constraint error");
+    assert!(matches!(err, KiteError::Constraint(_)));
+    db.commit().expect("expected value");
+
+    assert_eq!(
+      db.delta.read().node_prop(node, status).flatten(),
+      Some(&PropValue::I64(1))
+    );
+    assert_eq!(
+      db.node_prop(node, status),
+      Some(PropValue::String("inactive".to_string()))
+    );
+
+    // Appending keeps the existing ids
+    db.begin(false).expect("expected value");
+    db.set_prop_enum_values(status, ["pending", "active"])
+      .expect("expected value");
+    db.commit().expect("expected value");
+    assert_eq!(
+      db.prop_enum_values(status),
+      Some(vec![
+        "active".to_string(),
+        "inactive".to_string(),
+        "pending".to_string()
+      ])
+    );
+
+    db.checkpoint().expect("expected value");
+    close_single_file(db).expect("expected value");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    assert_eq!(
+      db.node_props(node)
+        .and_then(|props| props.get(&status).cloned()),
+      Some(PropValue::String("inactive".to_string()))
+    );
+    close_single_file(db).expect("expected value");
+  }
+}
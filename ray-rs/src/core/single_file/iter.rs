@@ -2,9 +2,13 @@
 //!
 //! Provides iterators over nodes and database statistics.
 
+use crate::constants::{COMPACT_FRAGMENTATION_RATIO, COMPACT_WAL_USAGE_RATIO};
+use crate::error::{KiteError, Result};
 use crate::mvcc::visibility::{edge_exists as mvcc_edge_exists, node_exists as mvcc_node_exists};
 use crate::types::*;
 use std::collections::HashSet;
+use std::ops::Range;
+use std::sync::atomic::Ordering;
 
 use super::SingleFileDB;
 
@@ -35,82 +39,111 @@ pub struct NodeIterator {
 
 impl NodeIterator {
   pub(crate) fn new(db: &SingleFileDB) -> Self {
+    Self::with_range(db, None)
+  }
+
+  /// Collect node IDs, optionally restricted to `range`
+  pub(crate) fn with_range(db: &SingleFileDB, range: Option<Range<NodeId>>) -> Self {
     let mut nodes = Vec::new();
-    let tx_handle = db.current_tx_handle();
-    let tx_guard = tx_handle.as_ref().map(|tx| tx.lock());
-    let pending = tx_guard.as_ref().map(|tx| &tx.pending);
-    let mut txid = 0;
-    let mut tx_snapshot_ts = 0;
-    let vc_guard = if let Some(mvcc) = db.mvcc.as_ref() {
-      if let Some(tx) = tx_guard.as_ref() {
-        txid = tx.txid;
-        tx_snapshot_ts = tx.snapshot_ts;
-      } else {
-        tx_snapshot_ts = mvcc.tx_manager.lock().next_commit_ts();
-      }
-      Some(mvcc.version_chain.lock())
-    } else {
-      None
-    };
-    let delta = db.delta.read();
-    let snapshot = db.snapshot.read();
+    visit_nodes(db, range.as_ref(), |node_id| nodes.push(node_id));
 
-    // 1. Collect nodes from snapshot (excluding deleted)
-    if let Some(ref snap) = *snapshot {
-      let num_nodes = snap.header.num_nodes as u32;
-      for phys in 0..num_nodes {
-        if let Some(node_id) = snap.node_id(phys) {
-          // Skip if deleted in delta
-          let node_visible = vc_guard
-            .as_ref()
-            .and_then(|vc| vc.node_version(node_id))
-            .map(|version| mvcc_node_exists(Some(version), tx_snapshot_ts, txid));
-          if pending.is_some_and(|p| p.is_node_deleted(node_id)) {
-            continue;
-          }
-          if node_visible == Some(false) {
-            continue;
-          }
-          if node_visible.is_none() && delta.is_node_deleted(node_id) {
-            continue;
-          }
-          nodes.push(node_id);
-        }
-      }
-    }
+    // Sort for consistent ordering
+    nodes.sort_unstable();
 
-    // 2. Add nodes created in delta (excluding deleted)
-    for &node_id in delta.created_nodes.keys() {
-      if pending.is_some_and(|p| p.is_node_deleted(node_id)) {
-        continue;
+    Self { nodes, index: 0 }
+  }
+}
+
+/// Call `visit` once for every visible node ID, optionally restricted to `range`
+///
+/// IDs come unordered: snapshot nodes first, then delta and pending ones.
+/// Snapshot nodes are addressed by ID, so a range maps to a contiguous scan
+/// of the ID -> phys table instead of a full pass.
+fn visit_nodes(db: &SingleFileDB, range: Option<&Range<NodeId>>, mut visit: impl FnMut(NodeId)) {
+  let in_range = |node_id: NodeId| range.is_none_or(|r| r.contains(&node_id));
+  let tx_handle = db.current_tx_handle();
+  let tx_guard = tx_handle.as_ref().map(|tx| tx.lock());
+  let pending = tx_guard.as_ref().map(|tx| &tx.pending);
+  let mut txid = 0;
+  let mut tx_snapshot_ts = 0;
+  let vc_guard = if let Some(mvcc) = db.mvcc.as_ref() {
+    if let Some(tx) = tx_guard.as_ref() {
+      txid = tx.txid;
+      tx_snapshot_ts = tx.snapshot_ts;
+    } else {
+      tx_snapshot_ts = mvcc.tx_manager.lock().next_commit_ts();
+    }
+    Some(mvcc.version_chain.lock())
+  } else {
+    None
+  };
+  let delta = db.delta.read();
+  let snapshot = db.snapshot.read();
+
+  // 1. Collect nodes from snapshot (excluding deleted)
+  if let Some(ref snap) = *snapshot {
+    let snapshot_ids: Box<dyn Iterator<Item = NodeId>> = match range {
+      Some(r) => {
+        let end = r.end.min(snap.max_node_id().saturating_add(1));
+        Box::new((r.start..end).filter(|&node_id| snap.has_node(node_id)))
       }
+      None => {
+        let num_nodes = snap.header.num_nodes as u32;
+        Box::new((0..num_nodes).filter_map(|phys| snap.node_id(phys)))
+      }
+    };
+    for node_id in snapshot_ids {
+      // Skip if deleted in delta
       let node_visible = vc_guard
         .as_ref()
         .and_then(|vc| vc.node_version(node_id))
         .map(|version| mvcc_node_exists(Some(version), tx_snapshot_ts, txid));
+      if pending.is_some_and(|p| p.is_node_deleted(node_id)) {
+        continue;
+      }
       if node_visible == Some(false) {
         continue;
       }
-      if node_visible.is_none() && delta.deleted_nodes.contains(&node_id) {
+      if node_visible.is_none() && delta.is_node_deleted(node_id) {
         continue;
       }
-      nodes.push(node_id);
+      visit(node_id);
     }
+  }
+  let in_snapshot = |node_id: NodeId| snapshot.as_ref().is_some_and(|snap| snap.has_node(node_id));
 
-    // 3. Add nodes created in pending (excluding deleted)
-    if let Some(pending_delta) = pending {
-      for &node_id in pending_delta.created_nodes.keys() {
-        if !pending_delta.deleted_nodes.contains(&node_id) {
-          nodes.push(node_id);
-        }
-      }
+  // 2. Add nodes created in delta (excluding deleted)
+  for &node_id in delta.created_nodes.keys() {
+    if !in_range(node_id) || in_snapshot(node_id) {
+      continue;
     }
+    if pending.is_some_and(|p| p.is_node_deleted(node_id)) {
+      continue;
+    }
+    let node_visible = vc_guard
+      .as_ref()
+      .and_then(|vc| vc.node_version(node_id))
+      .map(|version| mvcc_node_exists(Some(version), tx_snapshot_ts, txid));
+    if node_visible == Some(false) {
+      continue;
+    }
+    if node_visible.is_none() && delta.deleted_nodes.contains(&node_id) {
+      continue;
+    }
+    visit(node_id);
+  }
 
-    // Sort for consistent ordering
-    nodes.sort_unstable();
-    nodes.dedup();
-
-    Self { nodes, index: 0 }
+  // 3. Add nodes created in pending (excluding deleted)
+  if let Some(pending_delta) = pending {
+    for &node_id in pending_delta.created_nodes.keys() {
+      if in_range(node_id)
+        && !pending_delta.deleted_nodes.contains(&node_id)
+        && !delta.created_nodes.contains_key(&node_id)
+        && !in_snapshot(node_id)
+      {
+        visit(node_id);
+      }
+    }
   }
 }
 
@@ -135,6 +168,62 @@ impl Iterator for NodeIterator {
 
 impl ExactSizeIterator for NodeIterator {}
 
+// ============================================================================
+// Node Cursor
+// ============================================================================
+
+/// Node IDs scanned per range pass when filling a cursor batch
+const NODE_CURSOR_MIN_WINDOW: NodeId = 4096;
+
+/// Pull-based cursor over node IDs in ascending order
+///
+/// Unlike [`NodeIterator`], nothing is collected up front: each
+/// [`next_batch`](Self::next_batch) scans only the ID ranges it needs, so
+/// memory stays bounded by the batch size. The position is a node ID, so the
+/// cursor stays valid across reads, writes, and checkpoints; nodes created
+/// past the position show up in later batches, and deleted ones are skipped.
+#[derive(Debug, Clone)]
+pub struct NodeCursor {
+  /// First ID the next batch may return
+  next_id: NodeId,
+  /// ID allocator position when the cursor last read
+  id_high_water: NodeId,
+}
+
+impl NodeCursor {
+  /// Fetch up to `batch_size` more node IDs
+  ///
+  /// Returns an empty batch once the cursor has passed every allocated ID
+  /// (later calls pick up nodes created since). Fails with
+  /// [`KiteError::CursorInvalidated`] if the ID allocator moved backwards,
+  /// which means IDs may have been reused under the cursor.
+  pub fn next_batch(&mut self, db: &SingleFileDB, batch_size: usize) -> Result<Vec<NodeId>> {
+    let end_id = db.next_node_id.load(Ordering::SeqCst);
+    if end_id < self.id_high_water {
+      return Err(KiteError::CursorInvalidated(format!(
+        "node IDs were reset (allocator at {end_id}, cursor saw {})",
+        self.id_high_water
+      )));
+    }
+    self.id_high_water = end_id;
+
+    let mut batch = Vec::new();
+    let window = (batch_size as NodeId).max(NODE_CURSOR_MIN_WINDOW);
+    while batch.len() < batch_size && self.next_id < end_id {
+      let window_end = self.next_id.saturating_add(window).min(end_id);
+      let wanted = batch_size - batch.len();
+      let mut ids = NodeIterator::with_range(db, Some(self.next_id..window_end));
+      batch.extend(ids.by_ref().take(wanted));
+      self.next_id = match (ids.len(), batch.last()) {
+        // Stopped mid-window; resume right after the last ID handed out
+        (remaining, Some(&last)) if remaining > 0 => last + 1,
+        _ => window_end,
+      };
+    }
+    Ok(batch)
+  }
+}
+
 // ============================================================================
 // SingleFileDB Implementation - Iteration and Stats
 // ============================================================================
@@ -148,6 +237,17 @@ impl SingleFileDB {
     NodeIterator::new(self)
   }
 
+  /// Open a cursor that walks node IDs in batches
+  ///
+  /// See [`NodeCursor`]; prefer this over `list_nodes()` when the node set
+  /// is too large to hold at once.
+  pub fn node_cursor(&self) -> NodeCursor {
+    NodeCursor {
+      next_id: 0,
+      id_high_water: self.next_node_id.load(Ordering::SeqCst),
+    }
+  }
+
   /// Collect all node IDs into a Vec
   ///
   /// For large databases, prefer `iter_nodes()` to avoid memory allocation.
@@ -157,10 +257,32 @@ impl SingleFileDB {
 
   /// Count total nodes in the database
   ///
-  /// Optimized to avoid full iteration by using snapshot metadata
-  /// and delta size adjustments.
+  /// Counts while scanning, without collecting the IDs.
   pub fn count_nodes(&self) -> usize {
-    self.iter_nodes().len()
+    let mut count = 0;
+    visit_nodes(self, None, |_| count += 1);
+    count
+  }
+
+  /// List node IDs in `[min_id, max_id)`, in ascending order
+  ///
+  /// IDs are assigned sequentially, so disjoint ranges partition the graph
+  /// for parallel workers.
+  pub fn list_nodes_in_range(&self, min_id: NodeId, max_id: NodeId) -> Vec<NodeId> {
+    if min_id >= max_id {
+      return Vec::new();
+    }
+    NodeIterator::with_range(self, Some(min_id..max_id)).collect()
+  }
+
+  /// Count nodes in `[min_id, max_id)`, without collecting their IDs
+  pub fn count_nodes_in_range(&self, min_id: NodeId, max_id: NodeId) -> usize {
+    if min_id >= max_id {
+      return 0;
+    }
+    let mut count = 0;
+    visit_nodes(self, Some(&(min_id..max_id)), |_| count += 1);
+    count
   }
 
   /// Count total edges in the database
@@ -175,6 +297,41 @@ impl SingleFileDB {
     self.list_edges(Some(etype)).len()
   }
 
+  /// Count edges running from `set_a` into `set_b`
+  ///
+  /// Only the out-edges of `set_a` are scanned. With `undirected`, edges from
+  /// `set_b` back into `set_a` are counted too, each edge at most once.
+  pub fn count_edges_between(
+    &self,
+    set_a: &[NodeId],
+    set_b: &[NodeId],
+    etype: Option<ETypeId>,
+    undirected: bool,
+  ) -> usize {
+    let a: HashSet<NodeId> = set_a.iter().copied().collect();
+    let b: HashSet<NodeId> = set_b.iter().copied().collect();
+
+    let mut count = 0;
+    for &src in &a {
+      count += self
+        .out_edges_filtered(src, etype)
+        .iter()
+        .filter(|(_, dst)| b.contains(dst))
+        .count();
+    }
+    if undirected {
+      for &src in &b {
+        count += self
+          .out_edges_filtered(src, etype)
+          .iter()
+          // Skip edges already counted as A -> B
+          .filter(|(_, dst)| a.contains(dst) && !(a.contains(&src) && b.contains(dst)))
+          .count();
+      }
+    }
+    count
+  }
+
   /// List all edges in the database
   ///
   /// Optionally filter by edge type.
@@ -396,19 +553,33 @@ impl SingleFileDB {
       (0, 0, 0)
     };
 
+    let delta_nodes_deleted = delta.deleted_nodes.len();
+    let delta_edges_deleted = delta.total_edges_deleted();
+    let snapshot_entries = snapshot_nodes + snapshot_edges;
+    let fragmentation = if snapshot_entries == 0 {
+      0.0
+    } else {
+      (delta_nodes_deleted + delta_edges_deleted) as f64 / snapshot_entries as f64
+    };
+
     DbStats {
       snapshot_gen: header.active_snapshot_gen,
       snapshot_nodes,
       snapshot_edges,
       snapshot_max_node_id,
       delta_nodes_created: delta.created_nodes.len(),
-      delta_nodes_deleted: delta.deleted_nodes.len(),
+      delta_nodes_deleted,
       delta_edges_added: delta.total_edges_added(),
-      delta_edges_deleted: delta.total_edges_deleted(),
+      delta_edges_deleted,
       wal_segment: 0, // Not applicable for single-file
       wal_bytes: self.wal_stats().used,
-      recommend_compact: self.should_checkpoint(0.8),
+      fragmentation,
+      recommend_compact: fragmentation >= COMPACT_FRAGMENTATION_RATIO
+        || self.should_checkpoint(COMPACT_WAL_USAGE_RATIO),
       mvcc_stats: self.mvcc.as_ref().map(|mvcc| {
+        // Taken before the other MVCC locks; GC acquires the version chain
+        // ahead of the collector.
+        let depth = mvcc.version_chain.lock().depth_stats();
         let tx_mgr = mvcc.tx_manager.lock();
         let gc = mvcc.gc.lock();
         let gc_stats = gc.stats();
@@ -421,6 +592,11 @@ impl SingleFileDB {
           last_gc_time: gc_stats.last_gc_time,
           committed_writes_size: committed.size,
           committed_writes_pruned: committed.pruned,
+          version_chains: depth.chains,
+          versions_retained: depth.total_versions,
+          chain_depth_max: depth.max_depth,
+          chain_depth_p50: depth.p50_depth,
+          chain_depth_p99: depth.p99_depth,
         }
       }),
     }
@@ -430,4 +606,228 @@ impl SingleFileDB {
   pub fn wal_stats(&self) -> crate::core::wal::buffer::WalBufferStats {
     self.wal_buffer.lock().stats()
   }
+
+  /// Count and size WAL records by type since the last checkpoint
+  ///
+  /// Read-only scan of both WAL regions, including writes not yet flushed.
+  /// Sorted by bytes, largest first, to show what dominates WAL growth.
+  pub fn wal_record_breakdown(&self) -> Result<Vec<WalRecordStats>> {
+    let records = {
+      let mut pager = self.pager.lock();
+      let mut wal = self.wal_buffer.lock();
+      wal.records_for_recovery(&mut pager)?
+    };
+
+    let mut breakdown: Vec<WalRecordStats> = Vec::new();
+    for record in &records {
+      let bytes = record.record_end as u64;
+      match breakdown
+        .iter_mut()
+        .find(|stats| stats.record_type == record.record_type)
+      {
+        Some(stats) => {
+          stats.count += 1;
+          stats.bytes += bytes;
+        }
+        None => breakdown.push(WalRecordStats {
+          record_type: record.record_type,
+          count: 1,
+          bytes,
+        }),
+      }
+    }
+    breakdown.sort_by(|a, b| {
+      b.bytes
+        .cmp(&a.bytes)
+        .then((a.record_type as u8).cmp(&(b.record_type as u8)))
+    });
+    Ok(breakdown)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::core::single_file::{close_single_file, open_single_file, SingleFileOpenOptions};
+  use crate::types::{PropValue, WalRecordType};
+  use tempfile::tempdir;
+
+  #[test]
+  fn test_list_nodes_in_range_merges_snapshot_delta_and_pending() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("range.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let snap_ids: Vec<_> = (0..4)
+      .map(|_| db.create_node(None).expect("expected value"))
+      .collect();
+    db.commit().expect("expected value");
+    db.checkpoint().expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let delta_id = db.create_node(None).expect("expected value");
+    db.delete_node(snap_ids[1]).expect("expected value");
+    db.commit().expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let pending_id = db.create_node(None).expect("expected value");
+
+    let all = db.list_nodes();
+    assert_eq!(
+      db.list_nodes_in_range(0, pending_id + 1),
+      all,
+      "full range matches list_nodes"
+    );
+    assert_eq!(
+      db.list_nodes_in_range(snap_ids[0], snap_ids[3]),
+      vec![snap_ids[0], snap_ids[2]]
+    );
+    assert_eq!(
+      db.list_nodes_in_range(snap_ids[3], pending_id + 1),
+      vec![snap_ids[3], delta_id, pending_id]
+    );
+    assert_eq!(db.count_nodes_in_range(delta_id, pending_id + 100), 2);
+    assert_eq!(db.count_nodes_in_range(snap_ids[0], snap_ids[3]), 2);
+    assert_eq!(db.count_nodes_in_range(0, pending_id + 1), all.len());
+    assert_eq!(db.count_nodes(), all.len());
+    assert!(db.list_nodes_in_range(pending_id + 1, u64::MAX).is_empty());
+    assert_eq!(db.count_nodes_in_range(5, 5), 0);
+
+    db.rollback().expect("expected value");
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_node_cursor_walks_in_batches_across_writes() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("cursor.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let ids: Vec<_> = (0..10_000)
+      .map(|_| db.create_node(None).expect("expected value"))
+      .collect();
+    db.commit().expect("expected value");
+    db.checkpoint().expect("expected value");
+
+    let mut cursor = db.node_cursor();
+    let first = cursor.next_batch(&db, 3).expect("expected value");
+    assert_eq!(first, ids[..3]);
+
+    // Deletes behind and ahead of the cursor, plus a checkpoint, between pulls
+    db.begin(false).expect("expected value");
+    db.delete_node(ids[0]).expect("expected value");
+    db.delete_node(ids[3]).expect("expected value");
+    let late = db.create_node(None).expect("expected value");
+    db.commit().expect("expected value");
+    db.checkpoint().expect("expected value");
+
+    let mut seen = first;
+    loop {
+      let batch = cursor.next_batch(&db, 777).expect("expected value");
+      if batch.is_empty() {
+        break;
+      }
+      assert!(batch.len() <= 777);
+      seen.extend(batch);
+    }
+    let mut expected = ids.clone();
+    expected.remove(3);
+    expected.push(late);
+    assert_eq!(seen, expected);
+
+    // Exhausted cursors pick up nodes created afterwards
+    db.begin(false).expect("expected value");
+    let newer = db.create_node(None).expect("expected value");
+    db.commit().expect("expected value");
+    assert_eq!(
+      cursor.next_batch(&db, 10).expect("expected value"),
+      vec![newer]
+    );
+
+    // An allocator that moved backwards may hand out IDs the cursor passed
+    db.next_node_id
+      .store(1, std::sync::atomic::Ordering::SeqCst);
+    assert!(matches!(
+      cursor.next_batch(&db, 10),
+      Err(crate::error::KiteError::CursorInvalidated(_))
+    ));
+
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_count_edges_between_sets() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("between.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let knows = db.define_etype("knows").expect("expected value");
+    let likes = db.define_etype("likes").expect("expected value");
+    let a1 = db.create_node(None).expect("expected value");
+    let a2 = db.create_node(None).expect("expected value");
+    let b1 = db.create_node(None).expect("expected value");
+    let b2 = db.create_node(None).expect("expected value");
+    db.add_edge(a1, knows, b1).expect("expected value");
+    db.add_edge(a1, knows, b2).expect("expected value");
+    db.add_edge(a2, likes, b1).expect("expected value");
+    db.add_edge(a1, knows, a2).expect("expected value");
+    db.add_edge(b2, knows, a2).expect("expected value");
+    db.commit().expect("expected value");
+
+    let set_a = [a1, a2];
+    let set_b = [b1, b2];
+    assert_eq!(db.count_edges_between(&set_a, &set_b, None, false), 3);
+    assert_eq!(
+      db.count_edges_between(&set_a, &set_b, Some(knows), false),
+      2
+    );
+    assert_eq!(db.count_edges_between(&set_a, &set_b, None, true), 4);
+    assert_eq!(db.count_edges_between(&set_b, &set_a, None, false), 1);
+
+    // Overlapping sets must not double count a1 -> a2
+    assert_eq!(db.count_edges_between(&set_a, &set_a, None, true), 1);
+
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_wal_record_breakdown_counts_by_type() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("wal-breakdown.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let node = db.create_node(Some("n")).expect("expected value");
+    let key = db.define_propkey("v").expect("expected value");
+    db.commit().expect("expected value");
+    for i in 0..5 {
+      db.begin(false).expect("expected value");
+      db.set_node_prop(node, key, PropValue::String(format!("value-{i}")))
+        .expect("expected value");
+      db.commit().expect("expected value");
+    }
+
+    let breakdown = db.wal_record_breakdown().expect("expected value");
+    let count_of = |record_type| {
+      breakdown
+        .iter()
+        .find(|stats| stats.record_type == record_type)
+        .map(|stats| stats.count)
+    };
+    assert_eq!(count_of(WalRecordType::SetNodeProp), Some(5));
+    assert_eq!(count_of(WalRecordType::CreateNode), Some(1));
+    assert_eq!(count_of(WalRecordType::Commit), Some(6));
+    assert!(breakdown.windows(2).all(|w| w[0].bytes >= w[1].bytes));
+    let total: u64 = breakdown.iter().map(|stats| stats.bytes).sum();
+    assert_eq!(total, db.wal_stats().used);
+
+    db.checkpoint().expect("expected value");
+    assert!(db
+      .wal_record_breakdown()
+      .expect("expected value")
+      .is_empty());
+    close_single_file(db).expect("expected value");
+  }
 }
@@ -5,10 +5,11 @@
 //!
 //! Ported from src/ray/graph-db/single-file.ts
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::thread::ThreadId;
+use std::time::Instant;
 
 use parking_lot::{Condvar, Mutex, RwLock};
 
@@ -18,6 +19,8 @@ use crate::constants::*;
 use crate::core::pager::FilePager;
 use crate::core::snapshot::reader::SnapshotData;
 use crate::core::wal::buffer::WalBuffer;
+use crate::core::wal::record::WalRecord;
+use crate::metrics::OpRateTracker;
 use crate::mvcc::visibility::{edge_exists as mvcc_edge_exists, node_exists as mvcc_node_exists};
 use crate::mvcc::MvccManager;
 use crate::types::*;
@@ -28,6 +31,7 @@ use crate::vector::types::VectorManifest;
 mod check;
 mod checkpoint;
 mod compactor;
+mod enum_props;
 mod iter;
 mod open;
 mod read;
@@ -42,13 +46,14 @@ mod write;
 mod stress;
 
 // Re-export everything for backward compatibility
-pub use compactor::{ResizeWalOptions, SingleFileOptimizeOptions, VacuumOptions};
+pub use compactor::{OptimizeProgress, ResizeWalOptions, SingleFileOptimizeOptions, VacuumOptions};
 pub use iter::*;
 pub use open::{
-  close_single_file, close_single_file_with_options, open_single_file, SingleFileCloseOptions,
-  SingleFileOpenOptions, SnapshotParseMode, SyncMode,
+  close_single_file, close_single_file_with_options, open_single_file, LockMode,
+  SingleFileCloseOptions, SingleFileOpenOptions, SnapshotParseMode, SyncMode,
 };
 pub use transaction::SingleFileTxGuard;
+pub use vector::NodeVectorHit;
 
 // Also re-export recovery items that are used externally
 pub use recovery::replay_wal_record;
@@ -69,8 +74,63 @@ pub struct SingleFileTxState {
   pub pending: DeltaState,
   pub bulk_load: bool,
   pub pending_wal: Vec<u8>,
+  /// `SetNodeProp` records held back until the next WAL write, so repeated
+  /// sets of the same prop are logged once
+  pub pending_prop_sets: Vec<WalRecord>,
+  /// Slot in `pending_prop_sets` for each (node, prop) held back
+  pub pending_prop_slots: HashMap<(NodeId, PropKeyId), usize>,
+  /// Number of nested `begin` calls joined into this transaction
+  pub depth: u32,
+  /// Set when a nested transaction rolled back; the outer commit must fail
+  pub rollback_only: bool,
+  /// When the transaction began, for [`CommitStats::duration_ms`]
+  pub started_at: Instant,
+  /// Savepoints taken in this transaction, oldest first
+  pub savepoints: Vec<Savepoint>,
+  /// Records held back until commit once a savepoint exists, so a rollback
+  /// to the savepoint can drop them
+  pub deferred_wal: Vec<WalRecord>,
 }
 
+/// Transaction state captured by [`SingleFileDB::savepoint`]
+#[derive(Debug, Clone)]
+pub struct Savepoint {
+  pub name: String,
+  pub pending: DeltaState,
+  pub pending_prop_sets: Vec<WalRecord>,
+  pub pending_prop_slots: HashMap<(NodeId, PropKeyId), usize>,
+  /// Length of `deferred_wal` when the savepoint was taken
+  pub deferred_len: usize,
+}
+
+/// A committed change to a watched node prop, passed to listeners
+/// registered with [`SingleFileDB::on_prop_change`]
+#[derive(Debug, Clone)]
+pub struct PropChange {
+  pub node_id: NodeId,
+  /// Key of the node, if it has one
+  pub node_key: Option<String>,
+  pub key_id: PropKeyId,
+  /// Committed value before the transaction; `None` if unset or the node is new
+  pub old_value: Option<PropValue>,
+  /// Value the transaction committed; `None` if it deleted the prop
+  pub new_value: Option<PropValue>,
+}
+
+/// Callback registered with [`SingleFileDB::on_prop_change`]
+pub type PropChangeListener = std::sync::Arc<dyn Fn(&PropChange) + Send + Sync>;
+
+/// Handle returned by [`SingleFileDB::on_prop_change`], for
+/// [`SingleFileDB::remove_prop_listener`]
+pub type PropListenerId = u64;
+
+/// Check installed with [`SingleFileDB::set_commit_check`]
+///
+/// Gets the database (reads outside a transaction see exactly the commits
+/// before this one) and the committing transaction's changes.
+pub type CommitCheck =
+  std::sync::Arc<dyn Fn(&SingleFileDB, &DeltaState) -> crate::error::Result<()> + Send + Sync>;
+
 impl SingleFileTxState {
   pub fn new(txid: TxId, read_only: bool, snapshot_ts: u64, bulk_load: bool) -> Self {
     Self {
@@ -80,8 +140,35 @@ impl SingleFileTxState {
       pending: DeltaState::new(),
       bulk_load,
       pending_wal: Vec::new(),
+      pending_prop_sets: Vec::new(),
+      pending_prop_slots: HashMap::new(),
+      depth: 0,
+      rollback_only: false,
+      started_at: Instant::now(),
+      savepoints: Vec::new(),
+      deferred_wal: Vec::new(),
+    }
+  }
+
+  /// Hold back a `SetNodeProp` record, replacing any earlier one for the
+  /// same (node, prop)
+  pub fn defer_prop_set(&mut self, node_id: NodeId, key_id: PropKeyId, record: WalRecord) {
+    match self.pending_prop_slots.get(&(node_id, key_id)) {
+      Some(&slot) => self.pending_prop_sets[slot] = record,
+      None => {
+        self
+          .pending_prop_slots
+          .insert((node_id, key_id), self.pending_prop_sets.len());
+        self.pending_prop_sets.push(record);
+      }
     }
   }
+
+  /// Take the held-back prop sets in first-set order
+  pub fn take_prop_sets(&mut self) -> Vec<WalRecord> {
+    self.pending_prop_slots.clear();
+    std::mem::take(&mut self.pending_prop_sets)
+  }
 }
 
 // ============================================================================
@@ -135,6 +222,21 @@ pub struct SingleFileDB {
   pub(crate) etype_names: RwLock<HashMap<String, ETypeId>>,
   /// ID -> edge type name mapping
   pub(crate) etype_ids: RwLock<HashMap<ETypeId, String>>,
+  /// Committed names of edge types that reject edges from a node to itself
+  pub(crate) no_self_loop_etypes: RwLock<HashSet<String>>,
+  /// Whether a commit has recorded the primary epoch (see
+  /// `record_primary_epoch`)
+  pub(crate) primary_epoch_recorded: AtomicBool,
+  /// Committed enum dictionaries by prop key (see `set_prop_enum_values`)
+  pub(crate) enum_props: RwLock<HashMap<PropKeyId, std::sync::Arc<enum_props::EnumDictionary>>>,
+  /// Listeners for committed node prop changes, by prop key (not persisted)
+  pub(crate) prop_listeners: RwLock<HashMap<PropKeyId, Vec<(PropListenerId, PropChangeListener)>>>,
+  /// Next id handed out by `on_prop_change`
+  pub(crate) next_prop_listener_id: AtomicU64,
+  /// Prop keys whose vectors are kept unit length (not persisted)
+  pub(crate) normalized_vector_keys: RwLock<HashSet<PropKeyId>>,
+  /// Runs on each write commit before it reaches the WAL (not persisted)
+  pub(crate) commit_check: RwLock<Option<CommitCheck>>,
   /// Property key name -> ID mapping
   pub(crate) propkey_names: RwLock<HashMap<String, PropKeyId>>,
   /// ID -> property key name mapping
@@ -148,6 +250,11 @@ pub struct SingleFileDB {
   pub(crate) background_checkpoint: bool,
   /// Current checkpoint state
   pub(crate) checkpoint_status: Mutex<CheckpointStatus>,
+  /// Bumped when a background checkpoint swaps in a delta rebuilt from the
+  /// WAL; commits that wrote their WAL before the swap skip applying to it
+  pub(crate) checkpoint_epoch: AtomicU64,
+  /// Commits whose WAL is written but that haven't reached the delta yet
+  pub(crate) applying_commits: AtomicUsize,
 
   /// Vector stores keyed by property key ID
   /// Each property key can have its own vector store with different dimensions
@@ -174,6 +281,16 @@ pub struct SingleFileDB {
   /// Replica replication runtime (enabled only when role=replica)
   pub(crate) replica_replication: Option<crate::replication::replica::ReplicaReplication>,
 
+  /// OS lock held for the lifetime of the handle (see `LockMode`)
+  pub(crate) lock_file: Option<std::fs::File>,
+  /// Recoverable issues noticed while opening
+  pub(crate) open_warnings: Vec<String>,
+
+  /// Rolling mutation rate (one op per WAL record written)
+  pub(crate) write_rate: OpRateTracker,
+  /// Rolling read rate (node/edge/prop/label/key lookups)
+  pub(crate) read_rate: OpRateTracker,
+
   #[cfg(feature = "bench-profile")]
   pub(crate) commit_lock_wait_ns: AtomicU64,
   #[cfg(feature = "bench-profile")]
@@ -215,6 +332,18 @@ impl SingleFileDB {
     self.read_only
   }
 
+  /// Recoverable issues noticed while opening (e.g. locking unavailable,
+  /// discarded trailing WAL bytes, crash recovery)
+  pub fn open_warnings(&self) -> &[String] {
+    &self.open_warnings
+  }
+
+  /// Reset the rolling write/read rate counters reported by metrics
+  pub fn reset_op_rates(&self) {
+    self.write_rate.reset();
+    self.read_rate.reset();
+  }
+
   /// Allocate a new node ID
   pub fn alloc_node_id(&self) -> NodeId {
     self.next_node_id.fetch_add(1, Ordering::SeqCst)
@@ -407,6 +536,21 @@ impl SingleFileDB {
     }
   }
 
+  /// Drop cached key lookups a committing transaction may have changed
+  pub(crate) fn cache_invalidate_pending_keys(&self, pending: &DeltaState) {
+    if let Some(ref mut cache) = *self.cache.write() {
+      if !pending.deleted_nodes.is_empty() {
+        // Deleting a committed node leaves the key index alone, so the keys it
+        // held aren't known here.
+        cache.invalidate_all_keys();
+        return;
+      }
+      for key in pending.key_index.keys().chain(&pending.key_index_deleted) {
+        cache.invalidate_key(key);
+      }
+    }
+  }
+
   /// Clear all caches
   pub fn cache_clear(&self) {
     if let Some(ref mut cache) = *self.cache.write() {
@@ -442,7 +586,37 @@ impl SingleFileDB {
     }
   }
 
+  /// Evict least recently used query results down to `max_entries`,
+  /// returning how many were evicted
+  pub fn cache_trim_query(&self, max_entries: usize) -> usize {
+    match *self.cache.write() {
+      Some(ref mut cache) => cache.trim_query_cache(max_entries),
+      None => 0,
+    }
+  }
+
+  /// Evict least recently used traversals down to `max_entries`, returning
+  /// how many were evicted
+  pub fn cache_trim_traversal(&self, max_entries: usize) -> usize {
+    match *self.cache.write() {
+      Some(ref mut cache) => cache.trim_traversal_cache(max_entries),
+      None => 0,
+    }
+  }
+
+  /// Evict least recently used node and edge props down to `max_entries`
+  /// each, returning how many were evicted
+  pub fn cache_trim_property(&self, max_entries: usize) -> usize {
+    match *self.cache.write() {
+      Some(ref mut cache) => cache.trim_property_cache(max_entries),
+      None => 0,
+    }
+  }
+
   /// Get cache statistics
+  ///
+  /// Returns `None` only when no cache was configured at open; single-file is
+  /// the sole storage backend, so there is no other cache-less path.
   pub fn cache_stats(&self) -> Option<CacheStats> {
     self.cache.read().as_ref().map(|c| c.stats())
   }
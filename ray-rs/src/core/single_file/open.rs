@@ -2,33 +2,41 @@
 //!
 //! Handles opening, creating, and closing single-file databases.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{Read, Write};
 use std::path::{Path, PathBuf};
-use std::sync::atomic::{AtomicU32, AtomicU64, AtomicUsize, Ordering};
+use std::sync::atomic::{AtomicBool, AtomicU32, AtomicU64, AtomicUsize, Ordering};
 use std::sync::Arc;
 #[cfg(feature = "bench-profile")]
 use std::time::Instant;
 
+use fs2::FileExt;
 use parking_lot::{Mutex, RwLock};
 
 use crate::cache::manager::CacheManager;
 use crate::constants::*;
+use crate::core::header::{check_wal_file_header, serialize_wal_file_header};
 use crate::core::pager::{create_pager, is_valid_page_size, open_pager, pages_to_store, FilePager};
 use crate::core::snapshot::reader::SnapshotData;
 use crate::core::wal::buffer::WalBuffer;
 use crate::error::{KiteError, Result};
+use crate::metrics::OpRateTracker;
 use crate::mvcc::{GcConfig, MvccManager};
-use crate::replication::primary::PrimaryReplication;
+use crate::replication::primary::{default_replication_sidecar_path, PrimaryReplication};
 use crate::replication::replica::ReplicaReplication;
 use crate::replication::types::ReplicationRole;
 use crate::types::*;
 use crate::util::compression::CompressionOptions;
 use crate::util::mmap::map_file;
-use crate::vector::store::{create_vector_store, vector_store_delete, vector_store_insert};
-use crate::vector::types::VectorStoreConfig;
 
-use super::recovery::{committed_transactions, replay_wal_record, scan_wal_records};
-use super::vector::{materialize_vector_store_from_lazy_entries, vector_store_state_from_snapshot};
+use super::enum_props::load_enum_props;
+use super::recovery::{
+  committed_transactions, replay_wal_record, scan_wal_records, unfinished_transaction_count,
+};
+use super::replication::load_primary_epoch;
+use super::vector::{apply_replayed_vectors, vector_store_state_from_snapshot};
+use super::write::load_no_self_loop_etypes;
 use super::{CheckpointStatus, SingleFileDB};
 
 // ============================================================================
@@ -66,6 +74,23 @@ pub enum SnapshotParseMode {
   Salvage,
 }
 
+/// OS file locking behavior when opening single-file databases
+///
+/// The lock is held on a `<db path>.lock` sidecar for the lifetime of the
+/// handle: shared for read-only opens, exclusive otherwise.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LockMode {
+  /// Fail to open if the lock can't be taken
+  Require,
+  /// Take the lock if the filesystem supports it; otherwise open unlocked
+  /// and record a warning (see `SingleFileDB::open_warnings`). A lock held
+  /// by another handle still fails the open.
+  BestEffort,
+  /// Don't lock (default)
+  #[default]
+  None,
+}
+
 /// Options for opening a single-file database
 #[derive(Debug, Clone)]
 pub struct SingleFileOpenOptions {
@@ -79,12 +104,19 @@ pub struct SingleFileOpenOptions {
   pub mvcc_gc_interval_ms: Option<u64>,
   /// MVCC retention in ms
   pub mvcc_retention_ms: Option<u64>,
+  /// Record version history on every commit so `begin_read_at` can serve
+  /// past generations within the retention window. Off by default: it costs
+  /// a version per write even when no reader is active.
+  pub mvcc_retain_history: bool,
   /// MVCC max version chain depth
   pub mvcc_max_chain_depth: Option<usize>,
   /// Page size (default 4KB, must be power of 2 between 4KB and 64KB)
   pub page_size: usize,
   /// WAL size in bytes (default 4MB)
   pub wal_size: usize,
+  /// Keep the WAL in a separate file (e.g. on faster storage). Only used when
+  /// creating the database; the path is recorded in the header for reopen.
+  pub wal_path: Option<PathBuf>,
   /// Enable auto-checkpoint when WAL usage exceeds threshold (default true)
   pub auto_checkpoint: bool,
   /// WAL usage threshold (0.0-1.0) to trigger auto-checkpoint (default 0.5)
@@ -103,6 +135,8 @@ pub struct SingleFileOpenOptions {
   pub group_commit_window_ms: u64,
   /// Snapshot parse behavior (default: Strict)
   pub snapshot_parse_mode: SnapshotParseMode,
+  /// File locking behavior (default: None)
+  pub lock_mode: LockMode,
   /// Replication role (default: Disabled)
   pub replication_role: ReplicationRole,
   /// Optional replication sidecar path (defaults to derived from DB path)
@@ -129,9 +163,11 @@ impl Default for SingleFileOpenOptions {
       mvcc: false,
       mvcc_gc_interval_ms: None,
       mvcc_retention_ms: None,
+      mvcc_retain_history: false,
       mvcc_max_chain_depth: None,
       page_size: DEFAULT_PAGE_SIZE,
       wal_size: WAL_DEFAULT_SIZE,
+      wal_path: None,
       auto_checkpoint: true,
       checkpoint_threshold: 0.5,
       background_checkpoint: true,
@@ -144,6 +180,7 @@ impl Default for SingleFileOpenOptions {
       group_commit_enabled: false,
       group_commit_window_ms: 2,
       snapshot_parse_mode: SnapshotParseMode::Strict,
+      lock_mode: LockMode::None,
       replication_role: ReplicationRole::Disabled,
       replication_sidecar_path: None,
       replication_source_db_path: None,
@@ -186,6 +223,11 @@ impl SingleFileOpenOptions {
     self
   }
 
+  pub fn mvcc_retain_history(mut self, value: bool) -> Self {
+    self.mvcc_retain_history = value;
+    self
+  }
+
   pub fn mvcc_max_chain_depth(mut self, value: usize) -> Self {
     self.mvcc_max_chain_depth = Some(value);
     self
@@ -201,6 +243,11 @@ impl SingleFileOpenOptions {
     self
   }
 
+  pub fn wal_path<P: AsRef<Path>>(mut self, path: P) -> Self {
+    self.wal_path = Some(path.as_ref().to_path_buf());
+    self
+  }
+
   pub fn auto_checkpoint(mut self, value: bool) -> Self {
     self.auto_checkpoint = value;
     self
@@ -276,6 +323,12 @@ impl SingleFileOpenOptions {
     self
   }
 
+  /// Set file locking behavior (require | best-effort | none)
+  pub fn lock_mode(mut self, mode: LockMode) -> Self {
+    self.lock_mode = mode;
+    self
+  }
+
   /// Set replication role (disabled | primary | replica)
   pub fn replication_role(mut self, role: ReplicationRole) -> Self {
     self.replication_role = role;
@@ -616,7 +669,10 @@ fn init_mvcc_from_wal(
     gc_config.max_chain_depth = v;
   }
 
-  let mvcc = std::sync::Arc::new(MvccManager::new(next_tx_id, next_commit_ts, gc_config));
+  let mvcc = std::sync::Arc::new(
+    MvccManager::new(next_tx_id, next_commit_ts, gc_config)
+      .with_retained_history(options.mvcc_retain_history),
+  );
 
   if !committed_in_order.is_empty() {
     use crate::core::wal::record::{
@@ -857,6 +913,14 @@ pub fn open_single_file<P: AsRef<Path>>(
     return Err(KiteError::ReadOnly);
   }
 
+  let mut open_warnings = Vec::new();
+  let lock_file = acquire_db_lock(
+    path,
+    options.lock_mode,
+    options.read_only,
+    &mut open_warnings,
+  )?;
+
   // Open or create pager
   let (mut pager, mut header, is_new) = if file_exists {
     // Open existing database
@@ -874,16 +938,63 @@ pub fn open_single_file<P: AsRef<Path>>(
       )));
     }
 
+    match (&header.wal_path, &options.wal_path) {
+      (Some(recorded), requested) => {
+        let wal_file = open_external_wal(recorded, requested.as_deref(), path, &header)?;
+        pager.attach_wal_file(
+          wal_file,
+          PathBuf::from(recorded),
+          header.wal_start_page as u32,
+          header.wal_page_count as u32,
+        )?;
+      }
+      (None, Some(_)) => {
+        return Err(KiteError::InvalidPath(format!(
+          "Database at {} keeps its WAL in the main file; wal_path only applies when creating a database",
+          path.display()
+        )));
+      }
+      (None, None) => {}
+    }
+
     (pager, header, false)
   } else {
+    // Create the external WAL first so an unwritable WAL location fails
+    // before the main file exists
+    let external_wal = match &options.wal_path {
+      Some(wal_path) => Some(create_external_wal(wal_path, options.page_size)?),
+      None => None,
+    };
+
     // Create new database
-    let mut pager = create_pager(path, options.page_size)?;
+    let mut pager = match create_pager(path, options.page_size) {
+      Ok(pager) => pager,
+      Err(error) => {
+        if let Some((_, recorded)) = &external_wal {
+          let _ = std::fs::remove_file(recorded);
+        }
+        return Err(error);
+      }
+    };
 
     // Calculate WAL page count
     let wal_page_count = pages_to_store(options.wal_size, options.page_size) as u64;
 
     // Create initial header
-    let header = DbHeaderV1::new(options.page_size as u32, wal_page_count);
+    let mut header = DbHeaderV1::new(options.page_size as u32, wal_page_count);
+
+    if let Some((mut wal_file, recorded)) = external_wal {
+      wal_file.write_all(&serialize_wal_file_header(options.page_size, header.db_id))?;
+      pager.attach_wal_file(
+        wal_file,
+        PathBuf::from(&recorded),
+        header.wal_start_page as u32,
+        wal_page_count as u32,
+      )?;
+      header.flags |= DB_FLAG_EXTERNAL_WAL;
+      header.min_reader_version = MIN_READER_EXTERNAL_WAL;
+      header.wal_path = Some(recorded);
+    }
 
     // Write header
     let header_bytes = header.serialize_to_page();
@@ -901,11 +1012,23 @@ pub fn open_single_file<P: AsRef<Path>>(
   // Initialize WAL buffer
   let mut wal_buffer = WalBuffer::from_header(&header);
 
-  // Recover from incomplete background checkpoint if needed
+  // Recover from an incomplete checkpoint if needed. The header still points
+  // at the previous snapshot, so only the WAL regions and any partial new
+  // snapshot written past the live pages need fixing.
   if header.checkpoint_in_progress != 0 {
+    open_warnings
+      .push("Recovered from a checkpoint interrupted by an unclean shutdown".to_string());
     wal_buffer.recover_incomplete_checkpoint(&mut pager)?;
     wal_buffer.flush(&mut pager)?;
 
+    let live_pages = header
+      .db_size_pages
+      .max(header.snapshot_start_page + header.snapshot_page_count)
+      .max(header.wal_start_page + header.wal_page_count);
+    if pager.file_size() > live_pages * header.page_size as u64 {
+      pager.truncate_pages(live_pages as u32)?;
+    }
+
     header.active_wal_region = 0;
     header.checkpoint_in_progress = 0;
     header.wal_head = wal_buffer.head();
@@ -985,15 +1108,27 @@ pub fn open_single_file<P: AsRef<Path>>(
   if !is_new && header.wal_head > 0 {
     #[cfg(feature = "bench-profile")]
     let wal_scan_started = Instant::now();
-    _wal_records_storage = Some(scan_wal_records(&mut pager, &header)?);
+    let (wal_records, discarded_bytes) = scan_wal_records(&mut pager, &header)?;
+    _wal_records_storage = Some(wal_records);
     #[cfg(feature = "bench-profile")]
     {
       open_profile.wal_scan_ns = open_profile
         .wal_scan_ns
         .saturating_add(elapsed_ns(wal_scan_started));
     }
+    if discarded_bytes > 0 {
+      open_warnings.push(format!(
+        "Discarded {discarded_bytes} bytes of incomplete trailing WAL record(s)"
+      ));
+    }
     if let Some(ref wal_records) = _wal_records_storage {
       committed_in_order = committed_transactions(wal_records);
+      let unfinished = unfinished_transaction_count(wal_records);
+      if unfinished > 0 {
+        open_warnings.push(format!(
+          "Rolled back {unfinished} transaction(s) left uncommitted by an unclean shutdown"
+        ));
+      }
 
       // Replay committed transactions
       #[cfg(feature = "bench-profile")]
@@ -1049,37 +1184,12 @@ pub fn open_single_file<P: AsRef<Path>>(
   };
 
   // Apply pending vector operations from WAL replay
-  for ((node_id, prop_key_id), operation) in delta.pending_vectors.drain() {
-    if let Some(ref snapshot) = snapshot {
-      materialize_vector_store_from_lazy_entries(
-        snapshot,
-        &mut vector_stores,
-        &mut vector_store_lazy_entries,
-        prop_key_id,
-      )?;
-    }
-
-    match operation {
-      Some(vector) => {
-        // Get or create vector store
-        let store = vector_stores.entry(prop_key_id).or_insert_with(|| {
-          let config = VectorStoreConfig::new(vector.len());
-          create_vector_store(config)
-        });
-        vector_store_insert(store, node_id, vector.as_ref()).map_err(|e| {
-          KiteError::InvalidWal(format!(
-            "Failed to apply vector insert during WAL replay for node {node_id} (prop {prop_key_id}): {e}"
-          ))
-        })?;
-      }
-      None => {
-        // Delete operation
-        if let Some(store) = vector_stores.get_mut(&prop_key_id) {
-          vector_store_delete(store, node_id);
-        }
-      }
-    }
-  }
+  apply_replayed_vectors(
+    snapshot.as_ref(),
+    &mut vector_stores,
+    &mut vector_store_lazy_entries,
+    &mut delta,
+  )?;
   #[cfg(feature = "bench-profile")]
   {
     open_profile.vector_init_ns = open_profile
@@ -1099,6 +1209,23 @@ pub fn open_single_file<P: AsRef<Path>>(
     &delta,
   );
 
+  let primary_epoch = load_primary_epoch(snapshot.as_ref(), &delta)?;
+  if let (ReplicationRole::Primary, Some(epoch)) = (options.replication_role, primary_epoch) {
+    // A primary whose sidecar directory is gone (e.g. a copy that skipped
+    // it) starts a fresh log; replicas have to reseed from it
+    let sidecar_path = options
+      .replication_sidecar_path
+      .clone()
+      .unwrap_or_else(|| default_replication_sidecar_path(path));
+    if !sidecar_path.exists() {
+      open_warnings.push(format!(
+        "Replication sidecar directory {} of this primary (epoch {epoch}) is missing; \
+         created an empty one, replicas must reseed",
+        sidecar_path.display()
+      ));
+    }
+  }
+
   let (primary_replication, replica_replication) = match options.replication_role {
     ReplicationRole::Disabled => (None, None),
     ReplicationRole::Primary => (
@@ -1149,6 +1276,9 @@ pub fn open_single_file<P: AsRef<Path>>(
     }
   }
 
+  let no_self_loop_etypes = load_no_self_loop_etypes(snapshot.as_ref(), &delta)?;
+  let enum_props = load_enum_props(snapshot.as_ref(), &delta)?;
+
   Ok(SingleFileDB {
     path: path.to_path_buf(),
     read_only: options.read_only,
@@ -1172,12 +1302,21 @@ pub fn open_single_file<P: AsRef<Path>>(
     label_ids: RwLock::new(label_ids),
     etype_names: RwLock::new(etype_names),
     etype_ids: RwLock::new(etype_ids),
+    no_self_loop_etypes: RwLock::new(no_self_loop_etypes),
+    primary_epoch_recorded: AtomicBool::new(primary_epoch.is_some()),
+    enum_props: RwLock::new(enum_props),
+    prop_listeners: RwLock::new(HashMap::new()),
+    next_prop_listener_id: AtomicU64::new(1),
+    normalized_vector_keys: RwLock::new(HashSet::new()),
+    commit_check: RwLock::new(None),
     propkey_names: RwLock::new(propkey_names),
     propkey_ids: RwLock::new(propkey_ids),
     auto_checkpoint: options.auto_checkpoint,
     checkpoint_threshold: options.checkpoint_threshold,
     background_checkpoint: options.background_checkpoint,
     checkpoint_status: Mutex::new(CheckpointStatus::Idle),
+    checkpoint_epoch: AtomicU64::new(0),
+    applying_commits: AtomicUsize::new(0),
     vector_stores: RwLock::new(vector_stores),
     vector_store_lazy_entries: RwLock::new(vector_store_lazy_entries),
     cache: RwLock::new(cache),
@@ -1187,6 +1326,10 @@ pub fn open_single_file<P: AsRef<Path>>(
     group_commit_window_ms: options.group_commit_window_ms,
     primary_replication,
     replica_replication,
+    lock_file,
+    open_warnings,
+    write_rate: OpRateTracker::new(),
+    read_rate: OpRateTracker::new(),
     #[cfg(feature = "bench-profile")]
     commit_lock_wait_ns: AtomicU64::new(0),
     #[cfg(feature = "bench-profile")]
@@ -1194,6 +1337,158 @@ pub fn open_single_file<P: AsRef<Path>>(
   })
 }
 
+/// Path of the lock sidecar for a database file
+fn db_lock_path(path: &Path) -> PathBuf {
+  let mut name = path.as_os_str().to_os_string();
+  name.push(".lock");
+  PathBuf::from(name)
+}
+
+/// Take the OS file lock requested by `mode`
+///
+/// Contention always fails the open; other lock errors fail under `Require`
+/// and become a warning under `BestEffort`.
+fn acquire_db_lock(
+  path: &Path,
+  mode: LockMode,
+  read_only: bool,
+  warnings: &mut Vec<String>,
+) -> Result<Option<File>> {
+  if mode == LockMode::None {
+    return Ok(None);
+  }
+
+  let lock_path = db_lock_path(path);
+  let attempt = std::fs::OpenOptions::new()
+    .create(true)
+    .read(true)
+    .write(true)
+    .truncate(false)
+    .open(&lock_path)
+    .and_then(|file| {
+      if read_only {
+        file.try_lock_shared()?;
+      } else {
+        file.try_lock_exclusive()?;
+      }
+      Ok(file)
+    });
+
+  match attempt {
+    Ok(file) => Ok(Some(file)),
+    Err(error) if error.raw_os_error() == fs2::lock_contended_error().raw_os_error() => {
+      Err(KiteError::LockFailed(format!(
+        "database is locked by another handle: {}",
+        lock_path.display()
+      )))
+    }
+    Err(error) if mode == LockMode::Require => Err(KiteError::LockFailed(format!(
+      "could not lock {} ({error})",
+      lock_path.display()
+    ))),
+    Err(error) => {
+      warnings.push(format!(
+        "file locking unavailable ({error}); opened without a lock, so concurrent \
+         writers are not prevented"
+      ));
+      Ok(None)
+    }
+  }
+}
+
+/// Create the external WAL file for a new database.
+///
+/// Returns the file and its absolute path, which is what the header records.
+/// An existing file is never reused, since it may be another database's WAL.
+fn create_external_wal(wal_path: &Path, page_size: usize) -> Result<(File, String)> {
+  let file = std::fs::OpenOptions::new()
+    .read(true)
+    .write(true)
+    .create_new(true)
+    .open(wal_path)
+    .map_err(|error| {
+      KiteError::InvalidPath(format!(
+        "Cannot create WAL file at {} ({error})",
+        wal_path.display()
+      ))
+    })?;
+
+  match recorded_wal_path(wal_path, page_size) {
+    Ok(recorded) => Ok((file, recorded)),
+    Err(error) => {
+      drop(file);
+      let _ = std::fs::remove_file(wal_path);
+      Err(error)
+    }
+  }
+}
+
+/// Absolute path of a new external WAL file, as the header records it
+fn recorded_wal_path(wal_path: &Path, page_size: usize) -> Result<String> {
+  let absolute = std::fs::canonicalize(wal_path)?;
+  let recorded = absolute
+    .to_str()
+    .ok_or_else(|| {
+      KiteError::InvalidPath(format!(
+        "WAL path {} is not valid UTF-8",
+        absolute.display()
+      ))
+    })?
+    .to_string();
+  if recorded.len() > DbHeaderV1::max_wal_path_len(page_size) {
+    return Err(KiteError::InvalidPath(format!(
+      "WAL path {recorded} is too long to record in the database header"
+    )));
+  }
+  Ok(recorded)
+}
+
+/// Open the external WAL file recorded in an existing database's header
+///
+/// A `requested` path must resolve to the recorded one, and the file's own
+/// header must carry this database's id.
+fn open_external_wal(
+  recorded: &str,
+  requested: Option<&Path>,
+  db_path: &Path,
+  header: &DbHeaderV1,
+) -> Result<File> {
+  if let Some(requested) = requested {
+    let matches = std::fs::canonicalize(requested)
+      .map(|resolved| resolved == Path::new(recorded))
+      .unwrap_or(false);
+    if !matches {
+      return Err(KiteError::InvalidPath(format!(
+        "wal_path {} does not match the WAL recorded for {} ({recorded})",
+        requested.display(),
+        db_path.display()
+      )));
+    }
+  }
+
+  if !Path::new(recorded).exists() {
+    return Err(KiteError::InvalidPath(format!(
+      "WAL file not found at {recorded} (recorded by {})",
+      db_path.display()
+    )));
+  }
+
+  let mut file = std::fs::OpenOptions::new()
+    .read(true)
+    .write(true)
+    .open(recorded)
+    .map_err(|error| {
+      KiteError::InvalidPath(format!("Cannot open WAL file at {recorded} ({error})"))
+    })?;
+
+  let mut wal_header = vec![0u8; header.page_size as usize];
+  file
+    .read_exact(&mut wal_header)
+    .map_err(|_| KiteError::InvalidWal(format!("External WAL file at {recorded} is truncated")))?;
+  check_wal_file_header(&wal_header, header.page_size as usize, header.db_id)?;
+  Ok(file)
+}
+
 /// Close a single-file database using custom close options.
 pub fn close_single_file_with_options(
   db: SingleFileDB,
@@ -1354,6 +1649,63 @@ mod tests {
     close_single_file(db).expect("expected value");
   }
 
+  #[test]
+  fn test_recover_partial_checkpoint_snapshot() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("checkpoint-partial.kitedb");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    db.begin(false).expect("expected value");
+    db.create_node(Some("n1")).expect("expected value");
+    db.commit().expect("expected value");
+    db.checkpoint().expect("expected value");
+
+    db.begin(false).expect("expected value");
+    db.create_node(Some("n2")).expect("expected value");
+    db.commit().expect("expected value");
+
+    // Simulate a crash partway through writing the next snapshot: the
+    // marker is set and only the first page of the new snapshot landed
+    let (live_pages, page_size) = {
+      let mut pager = db.pager.lock();
+      let mut header = db.header.write();
+      let live_pages = header.snapshot_start_page + header.snapshot_page_count;
+      let first_page = pager
+        .read_page(header.snapshot_start_page as u32)
+        .expect("expected value");
+      pager.allocate_pages(3).expect("expected value");
+      pager
+        .write_page(live_pages as u32, &first_page)
+        .expect("expected value");
+
+      header.checkpoint_in_progress = 1;
+      header.change_counter += 1;
+      let header_bytes = header.serialize_to_page();
+      pager.write_page(0, &header_bytes).expect("expected value");
+      pager.sync().expect("expected value");
+      (live_pages, header.page_size as u64)
+    };
+    close_single_file(db).expect("expected value");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    assert!(db.node_by_key("n1").is_some());
+    assert!(db.node_by_key("n2").is_some());
+    assert_eq!(db.header.read().checkpoint_in_progress, 0);
+    assert_eq!(db.pager.lock().file_size(), live_pages * page_size);
+    assert!(db
+      .open_warnings()
+      .iter()
+      .any(|warning| warning.contains("checkpoint interrupted")));
+
+    // The next checkpoint and reopen work as usual
+    db.checkpoint().expect("expected value");
+    close_single_file(db).expect("expected value");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    assert!(db.node_by_key("n1").is_some());
+    assert!(db.node_by_key("n2").is_some());
+    close_single_file(db).expect("expected value");
+  }
+
   #[test]
   fn test_group_commit_flush_and_persist() {
     let temp_dir = tempdir().expect("expected value");
@@ -1541,6 +1893,10 @@ mod tests {
     let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
     assert!(db.node_by_key("n1").is_some());
     assert!(db.node_by_key("n2").is_none());
+    assert!(db
+      .open_warnings()
+      .iter()
+      .any(|w| w.contains("incomplete trailing WAL")));
     close_single_file(db).expect("expected value");
   }
 
@@ -1557,7 +1913,8 @@ mod tests {
     // Persist WAL head without a commit record
     {
       let mut pager = db.pager.lock();
-      let wal = db.wal_buffer.lock();
+      let mut wal = db.wal_buffer.lock();
+      wal.flush(&mut pager).expect("expected value");
       let mut header = db.header.write();
 
       header.wal_head = wal.head();
@@ -1576,6 +1933,37 @@ mod tests {
 
     let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
     assert!(db.node_by_key("n1").is_none());
+    assert!(db
+      .open_warnings()
+      .iter()
+      .any(|w| w.contains("left uncommitted")));
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_recover_replays_transactions_in_commit_order() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("wal-commit-order.kitedb");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    db.begin(false).expect("expected value");
+    let node_id = db.create_node(Some("n1")).expect("expected value");
+    db.commit().expect("expected value");
+    let key_id = db.propkey_id_or_create("value");
+    for value in 0..32 {
+      db.begin(false).expect("expected value");
+      db.set_node_prop(node_id, key_id, crate::types::PropValue::I64(value))
+        .expect("expected value");
+      db.commit().expect("expected value");
+    }
+    drop(db);
+
+    // Every write is still in the WAL; the last commit must win
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    assert_eq!(
+      db.node_prop(node_id, key_id),
+      Some(crate::types::PropValue::I64(31))
+    );
     close_single_file(db).expect("expected value");
   }
 
@@ -1667,4 +2055,174 @@ mod tests {
     assert!(header.wal_head > 0);
     close_single_file(reopened).expect("expected value");
   }
+
+  #[test]
+  fn test_lock_mode_rejects_second_handle() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("locked.kitedb");
+
+    let db = open_single_file(
+      &db_path,
+      SingleFileOpenOptions::new().lock_mode(LockMode::Require),
+    )
+    .expect("expected value");
+    assert!(db.open_warnings().is_empty());
+
+    let second = open_single_file(
+      &db_path,
+      SingleFileOpenOptions::new().lock_mode(LockMode::BestEffort),
+    );
+    assert!(matches!(second, Err(KiteError::LockFailed(_))));
+
+    close_single_file(db).expect("expected value");
+    let reopened = open_single_file(
+      &db_path,
+      SingleFileOpenOptions::new().lock_mode(LockMode::BestEffort),
+    )
+    .expect("expected value");
+    assert!(reopened.open_warnings().is_empty());
+    close_single_file(reopened).expect("expected value");
+  }
+
+  #[test]
+  fn test_external_wal_path_survives_reopen() {
+    let temp_dir = tempdir().expect("expected value");
+    let wal_dir = temp_dir.path().join("fast");
+    std::fs::create_dir(&wal_dir).expect("expected value");
+    let db_path = temp_dir.path().join("data.kitedb");
+    let wal_path = wal_dir.join("data.wal");
+
+    let db = open_single_file(
+      &db_path,
+      SingleFileOpenOptions::new()
+        .auto_checkpoint(false)
+        .wal_path(&wal_path),
+    )
+    .expect("expected value");
+    db.begin(false).expect("expected value");
+    let _ = db.create_node(Some("n1")).expect("expected value");
+    db.commit().expect("expected value");
+    close_single_file_with_options(
+      db,
+      SingleFileCloseOptions::new().checkpoint_if_wal_usage_at_least(1.0),
+    )
+    .expect("expected value");
+
+    // Committed records live in the WAL file, not the main file's WAL range
+    let wal_bytes = std::fs::read(&wal_path).expect("expected value");
+    assert!(wal_bytes[DEFAULT_PAGE_SIZE..].iter().any(|&byte| byte != 0));
+
+    // Reopen without the option: the header points at the WAL file
+    let reopened = open_single_file(
+      &db_path,
+      SingleFileOpenOptions::new().auto_checkpoint(false),
+    )
+    .expect("expected value");
+    assert!(reopened.node_by_key("n1").is_some());
+    close_single_file_with_options(
+      reopened,
+      SingleFileCloseOptions::new().checkpoint_if_wal_usage_at_least(1.0),
+    )
+    .expect("expected value");
+
+    let mismatched = open_single_file(
+      &db_path,
+      SingleFileOpenOptions::new().wal_path(temp_dir.path().join("other.wal")),
+    );
+    assert!(matches!(mismatched, Err(KiteError::InvalidPath(_))));
+
+    std::fs::remove_file(&wal_path).expect("expected value");
+    let missing = open_single_file(&db_path, SingleFileOpenOptions::new());
+    match missing {
+      Err(KiteError::InvalidPath(message)) => assert!(message.contains("WAL file not found")),
+      Err(other) => panic!("unexpected error: {other}"),
+      Ok(_) => panic!("expected open to fail without the WAL file"),
+    }
+  }
+
+  #[test]
+  fn test_external_wal_never_reuses_an_existing_file() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("data.kitedb");
+    let wal_path = temp_dir.path().join("data.wal");
+    std::fs::write(&wal_path, b"someone else's WAL").expect("expected value");
+
+    let result = open_single_file(&db_path, SingleFileOpenOptions::new().wal_path(&wal_path));
+    assert!(matches!(result, Err(KiteError::InvalidPath(_))));
+    assert!(!db_path.exists());
+    assert_eq!(
+      std::fs::read(&wal_path).expect("expected value"),
+      b"someone else's WAL"
+    );
+  }
+
+  #[test]
+  fn test_external_wal_header_is_checksummed_and_versioned() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("data.kitedb");
+    let wal_path = temp_dir.path().join("data.wal");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new().wal_path(&wal_path))
+      .expect("expected value");
+    close_single_file(db).expect("expected value");
+
+    let page = std::fs::read(&db_path).expect("expected value")[..DB_HEADER_SIZE].to_vec();
+    let header = DbHeaderV1::parse(&page).expect("expected value");
+    assert_eq!(header.min_reader_version, MIN_READER_EXTERNAL_WAL);
+
+    // A flipped byte in the recorded path is caught
+    let mut corrupted = page.clone();
+    corrupted[DB_HEADER_WAL_PATH_OFFSET + 3] ^= 0x01;
+    assert!(matches!(
+      DbHeaderV1::parse(&corrupted),
+      Err(KiteError::CrcMismatch { .. })
+    ));
+
+    // Readers refuse headers that need a newer version
+    let mut newer = header.clone();
+    newer.min_reader_version = VERSION_SINGLE_FILE + 1;
+    assert!(matches!(
+      DbHeaderV1::parse(&newer.serialize_to_page()),
+      Err(KiteError::VersionMismatch { .. })
+    ));
+  }
+
+  #[test]
+  fn test_external_wal_owned_by_another_database_is_rejected() {
+    let temp_dir = tempdir().expect("expected value");
+    let first_path = temp_dir.path().join("first.kitedb");
+    let first_wal = temp_dir.path().join("first.wal");
+    let second_path = temp_dir.path().join("second.kitedb");
+    let second_wal = temp_dir.path().join("second.wal");
+
+    for (db_path, wal_path) in [(&first_path, &first_wal), (&second_path, &second_wal)] {
+      let db = open_single_file(db_path, SingleFileOpenOptions::new().wal_path(wal_path))
+        .expect("expected value");
+      close_single_file(db).expect("expected value");
+    }
+
+    std::fs::copy(&second_wal, &first_wal).expect("expected value");
+    match open_single_file(&first_path, SingleFileOpenOptions::new()) {
+      Err(KiteError::InvalidWal(message)) => assert!(message.contains("belongs to database")),
+      Err(other) => panic!("unexpected error: {other}"),
+      Ok(_) => panic!("expected open to reject another database's WAL"),
+    }
+
+    let second =
+      open_single_file(&second_path, SingleFileOpenOptions::new()).expect("expected value");
+    close_single_file(second).expect("expected value");
+  }
+
+  #[test]
+  fn test_external_wal_path_must_be_writable() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("data.kitedb");
+
+    let result = open_single_file(
+      &db_path,
+      SingleFileOpenOptions::new().wal_path(temp_dir.path().join("missing-dir").join("data.wal")),
+    );
+    assert!(matches!(result, Err(KiteError::InvalidPath(_))));
+    assert!(!db_path.exists());
+  }
 }
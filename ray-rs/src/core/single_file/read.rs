@@ -5,10 +5,12 @@
 
 use std::collections::HashMap;
 
+use crate::error::Result;
 use crate::mvcc::visibility::{
   edge_exists as mvcc_edge_exists, node_exists as mvcc_node_exists, visible_version,
 };
 use crate::types::*;
+use crate::util::glob;
 
 use super::SingleFileDB;
 
@@ -22,6 +24,13 @@ impl SingleFileDB {
   /// Returns None if the node doesn't exist or is deleted.
   /// Merges properties from snapshot with delta modifications.
   pub fn node_props(&self, node_id: NodeId) -> Option<HashMap<PropKeyId, PropValue>> {
+    let props = self.stored_node_props(node_id)?;
+    Some(self.resolve_prop_values(props))
+  }
+
+  /// [`Self::node_props`] with enum values still interned
+  fn stored_node_props(&self, node_id: NodeId) -> Option<HashMap<PropKeyId, PropValue>> {
+    self.read_rate.record(1);
     let tx_handle = self.current_tx_handle();
     let tx_guard = tx_handle.as_ref().map(|tx| tx.lock());
     let pending = tx_guard.as_ref().map(|tx| &tx.pending);
@@ -160,6 +169,13 @@ impl SingleFileDB {
   ///
   /// Returns None if the node doesn't exist, is deleted, or doesn't have the property.
   pub fn node_prop(&self, node_id: NodeId, key_id: PropKeyId) -> Option<PropValue> {
+    let value = self.stored_node_prop(node_id, key_id)?;
+    Some(self.resolve_prop_value(key_id, value))
+  }
+
+  /// [`Self::node_prop`] with enum values still interned
+  fn stored_node_prop(&self, node_id: NodeId, key_id: PropKeyId) -> Option<PropValue> {
+    self.read_rate.record(1);
     let tx_handle = self.current_tx_handle();
     if let Some(handle) = tx_handle.as_ref() {
       let tx = handle.lock();
@@ -252,6 +268,18 @@ impl SingleFileDB {
     etype: ETypeId,
     dst: NodeId,
   ) -> Option<HashMap<PropKeyId, PropValue>> {
+    let props = self.stored_edge_props(src, etype, dst)?;
+    Some(self.resolve_prop_values(props))
+  }
+
+  /// [`Self::edge_props`] with enum values still interned
+  fn stored_edge_props(
+    &self,
+    src: NodeId,
+    etype: ETypeId,
+    dst: NodeId,
+  ) -> Option<HashMap<PropKeyId, PropValue>> {
+    self.read_rate.record(1);
     let tx_handle = self.current_tx_handle();
     let tx_guard = tx_handle.as_ref().map(|tx| tx.lock());
     let pending = tx_guard.as_ref().map(|tx| &tx.pending);
@@ -417,6 +445,19 @@ impl SingleFileDB {
     dst: NodeId,
     key_id: PropKeyId,
   ) -> Option<PropValue> {
+    let value = self.stored_edge_prop(src, etype, dst, key_id)?;
+    Some(self.resolve_prop_value(key_id, value))
+  }
+
+  /// [`Self::edge_prop`] with enum values still interned
+  fn stored_edge_prop(
+    &self,
+    src: NodeId,
+    etype: ETypeId,
+    dst: NodeId,
+    key_id: PropKeyId,
+  ) -> Option<PropValue> {
+    self.read_rate.record(1);
     let tx_handle = self.current_tx_handle();
     if let Some(handle) = tx_handle.as_ref() {
       let tx = handle.lock();
@@ -561,6 +602,19 @@ impl SingleFileDB {
   /// Merges edges from snapshot with delta additions/deletions.
   /// Filters out edges to deleted nodes.
   pub fn out_edges(&self, node_id: NodeId) -> Vec<(ETypeId, NodeId)> {
+    self.out_edges_filtered(node_id, None)
+  }
+
+  /// Get outgoing edges for a node, optionally only of one edge type
+  ///
+  /// The type filter is applied while iterating, so edges of other types
+  /// are never collected.
+  pub fn out_edges_filtered(
+    &self,
+    node_id: NodeId,
+    etype_filter: Option<ETypeId>,
+  ) -> Vec<(ETypeId, NodeId)> {
+    self.read_rate.record(1);
     let tx_handle = self.current_tx_handle();
     let tx_guard = tx_handle.as_ref().map(|tx| tx.lock());
     let pending = tx_guard.as_ref().map(|tx| &tx.pending);
@@ -596,16 +650,19 @@ impl SingleFileDB {
 
     let snapshot = self.snapshot.read();
     let mut capacity = 0usize;
-    if let Some(ref snap) = *snapshot {
-      if let Some(phys) = snap.phys_node(node_id) {
-        capacity = capacity.saturating_add(snap.out_degree(phys).unwrap_or(0));
+    // Only size up front when every edge is kept.
+    if etype_filter.is_none() {
+      if let Some(ref snap) = *snapshot {
+        if let Some(phys) = snap.phys_node(node_id) {
+          capacity = capacity.saturating_add(snap.out_degree(phys).unwrap_or(0));
+        }
+      }
+      if let Some(added_edges) = delta.out_add.get(&node_id) {
+        capacity = capacity.saturating_add(added_edges.len());
+      }
+      if let Some(added_edges) = pending.and_then(|p| p.out_add.get(&node_id)) {
+        capacity = capacity.saturating_add(added_edges.len());
       }
-    }
-    if let Some(added_edges) = delta.out_add.get(&node_id) {
-      capacity = capacity.saturating_add(added_edges.len());
-    }
-    if let Some(added_edges) = pending.and_then(|p| p.out_add.get(&node_id)) {
-      capacity = capacity.saturating_add(added_edges.len());
     }
     let mut edges = Vec::with_capacity(capacity);
 
@@ -613,6 +670,9 @@ impl SingleFileDB {
     if let Some(ref snap) = *snapshot {
       if let Some(phys) = snap.phys_node(node_id) {
         for (dst_phys, etype) in snap.iter_out_edges(phys) {
+          if etype_filter.is_some_and(|filter| filter != etype) {
+            continue;
+          }
           // Convert physical dst to NodeId
           if let Some(dst_node_id) = snap.node_id(dst_phys) {
             // Skip edges to deleted nodes
@@ -646,6 +706,9 @@ impl SingleFileDB {
     // Add edges from delta
     if let Some(added_edges) = delta.out_add.get(&node_id) {
       for edge_patch in added_edges {
+        if etype_filter.is_some_and(|filter| filter != edge_patch.etype) {
+          continue;
+        }
         // Skip edges to deleted nodes
         let dst_visible = vc_guard
           .as_ref()
@@ -672,6 +735,9 @@ impl SingleFileDB {
 
     if let Some(added_edges) = pending.and_then(|p| p.out_add.get(&node_id)) {
       for edge_patch in added_edges {
+        if etype_filter.is_some_and(|filter| filter != edge_patch.etype) {
+          continue;
+        }
         let dst_visible = vc_guard
           .as_ref()
           .and_then(|vc| vc.node_version(edge_patch.other))
@@ -712,6 +778,19 @@ impl SingleFileDB {
   /// Merges edges from snapshot with delta additions/deletions.
   /// Filters out edges from deleted nodes.
   pub fn in_edges(&self, node_id: NodeId) -> Vec<(ETypeId, NodeId)> {
+    self.in_edges_filtered(node_id, None)
+  }
+
+  /// Get incoming edges for a node, optionally only of one edge type
+  ///
+  /// The type filter is applied while iterating, so edges of other types
+  /// are never collected.
+  pub fn in_edges_filtered(
+    &self,
+    node_id: NodeId,
+    etype_filter: Option<ETypeId>,
+  ) -> Vec<(ETypeId, NodeId)> {
+    self.read_rate.record(1);
     let tx_handle = self.current_tx_handle();
     let tx_guard = tx_handle.as_ref().map(|tx| tx.lock());
     let pending = tx_guard.as_ref().map(|tx| &tx.pending);
@@ -747,16 +826,19 @@ impl SingleFileDB {
 
     let snapshot = self.snapshot.read();
     let mut capacity = 0usize;
-    if let Some(ref snap) = *snapshot {
-      if let Some(phys) = snap.phys_node(node_id) {
-        capacity = capacity.saturating_add(snap.in_degree(phys).unwrap_or(0));
+    // Only size up front when every edge is kept.
+    if etype_filter.is_none() {
+      if let Some(ref snap) = *snapshot {
+        if let Some(phys) = snap.phys_node(node_id) {
+          capacity = capacity.saturating_add(snap.in_degree(phys).unwrap_or(0));
+        }
+      }
+      if let Some(added_edges) = delta.in_add.get(&node_id) {
+        capacity = capacity.saturating_add(added_edges.len());
+      }
+      if let Some(added_edges) = pending.and_then(|p| p.in_add.get(&node_id)) {
+        capacity = capacity.saturating_add(added_edges.len());
       }
-    }
-    if let Some(added_edges) = delta.in_add.get(&node_id) {
-      capacity = capacity.saturating_add(added_edges.len());
-    }
-    if let Some(added_edges) = pending.and_then(|p| p.in_add.get(&node_id)) {
-      capacity = capacity.saturating_add(added_edges.len());
     }
     let mut edges = Vec::with_capacity(capacity);
 
@@ -764,6 +846,9 @@ impl SingleFileDB {
     if let Some(ref snap) = *snapshot {
       if let Some(phys) = snap.phys_node(node_id) {
         for (src_phys, etype, _out_index) in snap.iter_in_edges(phys) {
+          if etype_filter.is_some_and(|filter| filter != etype) {
+            continue;
+          }
           // Convert physical src to NodeId
           if let Some(src_node_id) = snap.node_id(src_phys) {
             // Skip edges from deleted nodes
@@ -797,6 +882,9 @@ impl SingleFileDB {
     // Add edges from delta (in_add stores patches where other=src)
     if let Some(added_edges) = delta.in_add.get(&node_id) {
       for edge_patch in added_edges {
+        if etype_filter.is_some_and(|filter| filter != edge_patch.etype) {
+          continue;
+        }
         // Skip edges from deleted nodes
         let src_visible = vc_guard
           .as_ref()
@@ -823,6 +911,9 @@ impl SingleFileDB {
 
     if let Some(added_edges) = pending.and_then(|p| p.in_add.get(&node_id)) {
       for edge_patch in added_edges {
+        if etype_filter.is_some_and(|filter| filter != edge_patch.etype) {
+          continue;
+        }
         let src_visible = vc_guard
           .as_ref()
           .and_then(|vc| vc.node_version(edge_patch.other))
@@ -872,9 +963,8 @@ impl SingleFileDB {
   /// Returns destination node IDs for edges of the given type.
   pub fn out_neighbors(&self, node_id: NodeId, etype: ETypeId) -> Vec<NodeId> {
     let neighbors: Vec<NodeId> = self
-      .out_edges(node_id)
+      .out_edges_filtered(node_id, Some(etype))
       .into_iter()
-      .filter(|(e, _)| *e == etype)
       .map(|(_, dst)| dst)
       .collect();
     if let Some(mvcc) = self.mvcc.as_ref() {
@@ -899,9 +989,8 @@ impl SingleFileDB {
   /// Returns source node IDs for edges of the given type.
   pub fn in_neighbors(&self, node_id: NodeId, etype: ETypeId) -> Vec<NodeId> {
     let neighbors: Vec<NodeId> = self
-      .in_edges(node_id)
+      .in_edges_filtered(node_id, Some(etype))
       .into_iter()
-      .filter(|(e, _)| *e == etype)
       .map(|(_, src)| src)
       .collect();
     if let Some(mvcc) = self.mvcc.as_ref() {
@@ -923,12 +1012,12 @@ impl SingleFileDB {
 
   /// Check if there are any outgoing edges of a specific type
   pub fn has_out_edges(&self, node_id: NodeId, etype: ETypeId) -> bool {
-    self.out_edges(node_id).iter().any(|(e, _)| *e == etype)
+    !self.out_edges_filtered(node_id, Some(etype)).is_empty()
   }
 
   /// Check if there are any incoming edges of a specific type
   pub fn has_in_edges(&self, node_id: NodeId, etype: ETypeId) -> bool {
-    self.in_edges(node_id).iter().any(|(e, _)| *e == etype)
+    !self.in_edges_filtered(node_id, Some(etype)).is_empty()
   }
 
   // ========================================================================
@@ -937,6 +1026,7 @@ impl SingleFileDB {
 
   /// Check if a node has a specific label
   pub fn node_has_label(&self, node_id: NodeId, label_id: LabelId) -> bool {
+    self.read_rate.record(1);
     let tx_handle = self.current_tx_handle();
     let tx_guard = tx_handle.as_ref().map(|tx| tx.lock());
     let pending = tx_guard.as_ref().map(|tx| &tx.pending);
@@ -1062,6 +1152,7 @@ impl SingleFileDB {
 
   /// Get all labels for a node
   pub fn node_labels(&self, node_id: NodeId) -> Vec<LabelId> {
+    self.read_rate.record(1);
     let tx_handle = self.current_tx_handle();
     let tx_guard = tx_handle.as_ref().map(|tx| tx.lock());
     let pending = tx_guard.as_ref().map(|tx| &tx.pending);
@@ -1180,26 +1271,21 @@ impl SingleFileDB {
   /// Returns the NodeId if found, None otherwise.
   /// Checks delta key index first, then falls back to snapshot.
   pub fn node_by_key(&self, key: &str) -> Option<NodeId> {
+    self.read_rate.record(1);
     let tx_handle = self.current_tx_handle();
     let tx_guard = tx_handle.as_ref().map(|tx| tx.lock());
     let pending = tx_guard.as_ref().map(|tx| &tx.pending);
     let mut txid = 0;
     let mut tx_snapshot_ts = 0;
+    // The tx handle is already locked above; re-locking it would deadlock.
     if let Some(mvcc) = self.mvcc.as_ref() {
-      if let Some(handle) = tx_handle.as_ref() {
-        let tx = handle.lock();
+      if let Some(tx) = tx_guard.as_ref() {
         txid = tx.txid;
         tx_snapshot_ts = tx.snapshot_ts;
-      } else {
-        tx_snapshot_ts = mvcc.tx_manager.lock().next_commit_ts();
-      }
-    }
-
-    if let Some(mvcc) = self.mvcc.as_ref() {
-      if let Some(handle) = tx_handle.as_ref() {
-        let tx = handle.lock();
         let mut tx_mgr = mvcc.tx_manager.lock();
         tx_mgr.record_read(tx.txid, TxKey::Key(key.into()));
+      } else {
+        tx_snapshot_ts = mvcc.tx_manager.lock().next_commit_ts();
       }
     }
 
@@ -1271,10 +1357,50 @@ impl SingleFileDB {
     None
   }
 
+  /// Look up a node by its key, consulting the key cache first
+  ///
+  /// Falls back to [`Self::node_by_key`] when caching is disabled or a
+  /// transaction is active on this thread (pending writes aren't cached).
+  /// Misses are cached, including "not found" results; commits drop the keys
+  /// they touch.
+  pub fn node_by_key_cached(&self, key: &str) -> Option<NodeId> {
+    if self.current_tx_handle().is_some() {
+      return self.node_by_key(key);
+    }
+
+    let generation = {
+      let mut guard = self.cache.write();
+      let Some(cache) = guard.as_mut().filter(|c| c.is_enabled()) else {
+        drop(guard);
+        return self.node_by_key(key);
+      };
+      if let Some(&cached) = cache.node_by_key(key) {
+        return cached;
+      }
+      cache.key_generation()
+    };
+
+    let node_id = self.node_by_key(key);
+    if let Some(ref mut cache) = *self.cache.write() {
+      cache.set_node_by_key_if_current(key, node_id, generation);
+    }
+    node_id
+  }
+
   /// Get the key for a node
   ///
   /// Returns the key string if the node has one, None otherwise.
   pub fn node_key(&self, node_id: NodeId) -> Option<String> {
+    self.node_keys(&[node_id]).pop().flatten()
+  }
+
+  /// Get the keys for several nodes at once
+  ///
+  /// Returns one entry per input id (in order), resolved under a single
+  /// acquisition of the transaction, delta, and snapshot locks. Keys of nodes
+  /// created in the current transaction are included.
+  pub fn node_keys(&self, node_ids: &[NodeId]) -> Vec<Option<String>> {
+    self.read_rate.record(node_ids.len() as u64);
     let tx_handle = self.current_tx_handle();
     let tx_guard = tx_handle.as_ref().map(|tx| tx.lock());
     let pending = tx_guard.as_ref().map(|tx| &tx.pending);
@@ -1292,40 +1418,143 @@ impl SingleFileDB {
       None
     };
 
-    if pending.is_some_and(|p| p.is_node_deleted(node_id)) {
-      return None;
-    }
+    let delta = self.delta.read();
+    let snapshot = self.snapshot.read();
 
-    if let Some(node_delta) = pending.and_then(|p| p.created_nodes.get(&node_id)) {
-      return node_delta.key.clone();
-    }
+    node_ids
+      .iter()
+      .map(|&node_id| {
+        if pending.is_some_and(|p| p.is_node_deleted(node_id)) {
+          return None;
+        }
 
-    let node_visible = vc_guard
-      .as_ref()
-      .and_then(|vc| vc.node_version(node_id))
-      .map(|version| mvcc_node_exists(Some(version), tx_snapshot_ts, txid));
+        if let Some(node_delta) = pending.and_then(|p| p.created_nodes.get(&node_id)) {
+          return node_delta.key.clone();
+        }
+        if let Some(key) = pending
+          .and_then(|p| p.modified_nodes.get(&node_id))
+          .and_then(|d| d.key.clone())
+        {
+          return Some(key);
+        }
 
-    let delta = self.delta.read();
+        let node_visible = vc_guard
+          .as_ref()
+          .and_then(|vc| vc.node_version(node_id))
+          .map(|version| mvcc_node_exists(Some(version), tx_snapshot_ts, txid));
 
-    // Check if node is deleted
-    if node_visible == Some(false) || (node_visible.is_none() && delta.is_node_deleted(node_id)) {
-      return None;
+        // Check if node is deleted
+        if node_visible == Some(false) || (node_visible.is_none() && delta.is_node_deleted(node_id))
+        {
+          return None;
+        }
+
+        // Check created nodes in delta first
+        if let Some(node_delta) = delta.created_nodes.get(&node_id) {
+          return node_delta.key.clone();
+        }
+        if let Some(key) = delta
+          .modified_nodes
+          .get(&node_id)
+          .and_then(|d| d.key.clone())
+        {
+          return Some(key);
+        }
+
+        // Fall back to snapshot
+        let snap = snapshot.as_ref()?;
+        let phys = snap.phys_node(node_id)?;
+        snap.node_key(phys)
+      })
+      .collect()
+  }
+
+  /// Find keyed nodes whose key matches a glob pattern
+  ///
+  /// `*` matches any run of characters and `?` exactly one. Results are in
+  /// key order and capped at `limit`. The literal text before the first
+  /// wildcard narrows the snapshot scan to that key range, so `user:*` only
+  /// visits `user:` keys; a pattern that starts with a wildcard (such as
+  /// `*:admin`) has no prefix and scans every key. The first call after a
+  /// checkpoint builds a key-ordered copy of the snapshot's key index.
+  pub fn find_keys(&self, pattern: &str, limit: usize) -> Vec<(NodeId, String)> {
+    if limit == 0 {
+      return Vec::new();
     }
+    let prefix = glob::literal_prefix(pattern);
+
+    // Keys written since the snapshot, and how many snapshot keys they can
+    // shadow. Snapshot candidates beyond `limit + shadowed` can't be needed.
+    let (mut candidates, shadowed) = {
+      let tx_handle = self.current_tx_handle();
+      let tx_guard = tx_handle.as_ref().map(|tx| tx.lock());
+      let delta = self.delta.read();
+      let mut candidates = Vec::new();
+      let mut shadowed = 0;
+      for state in std::iter::once(&*delta).chain(tx_guard.as_ref().map(|tx| &tx.pending)) {
+        candidates.extend(
+          state
+            .key_index
+            .iter()
+            .filter(|(key, _)| key.starts_with(prefix) && glob::glob_match(pattern, key))
+            .map(|(key, &node_id)| (key.clone(), node_id)),
+        );
+        shadowed +=
+          state.key_index.len() + state.key_index_deleted.len() + state.deleted_nodes.len();
+      }
+      (candidates, shadowed)
+    };
 
-    // Check created nodes in delta first
-    if let Some(node_delta) = delta.created_nodes.get(&node_id) {
-      return node_delta.key.clone();
+    if let Some(ref snap) = *self.snapshot.read() {
+      candidates.extend(
+        snap
+          .keys_with_prefix(prefix)
+          .iter()
+          .filter(|(key, _)| glob::glob_match(pattern, key))
+          .take(limit.saturating_add(shadowed))
+          .cloned(),
+      );
     }
 
-    // Fall back to snapshot
-    let snapshot = self.snapshot.read();
-    if let Some(ref snap) = *snapshot {
-      if let Some(phys) = snap.phys_node(node_id) {
-        return snap.node_key(phys);
+    candidates.sort_unstable();
+    candidates.dedup();
+
+    // Only keep keys that still resolve to the same node from this reader's view
+    let mut found: Vec<(NodeId, String)> = Vec::new();
+    for (key, node_id) in candidates {
+      if found.len() == limit {
+        break;
+      }
+      if found.last().is_some_and(|(_, last)| *last == key) {
+        continue;
+      }
+      if self.node_by_key(&key) == Some(node_id) {
+        found.push((node_id, key));
       }
     }
+    found
+  }
 
-    None
+  // ========================================================================
+  // Metadata
+  // ========================================================================
+
+  /// Read a database metadata entry (see `set_metadata`)
+  ///
+  /// Sees writes made earlier in the current transaction.
+  pub fn metadata(&self, key: &str) -> Result<Option<Vec<u8>>> {
+    if let Some(tx_handle) = self.current_tx_handle() {
+      if let Some(value) = tx_handle.lock().pending.metadata.get(key) {
+        return Ok(value.clone());
+      }
+    }
+    if let Some(value) = self.delta.read().metadata.get(key) {
+      return Ok(value.clone());
+    }
+    match self.snapshot.read().as_ref() {
+      Some(snapshot) => Ok(snapshot.metadata()?.remove(key)),
+      None => Ok(None),
+    }
   }
 }
 
@@ -1339,6 +1568,83 @@ mod tests {
   use std::thread;
   use tempfile::tempdir;
 
+  #[test]
+  fn test_node_keys_aligned_and_sees_pending_tx() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("test-db");
+    let db = open_single_file(db_path, SingleFileOpenOptions::new()).expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let a = db.create_node(Some("a")).expect("expected value");
+    let b = db.create_node(Some("b")).expect("expected value");
+    let unkeyed = db.create_node(None).expect("expected value");
+    db.commit().expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let c = db.create_node(Some("c")).expect("expected value");
+    db.delete_node(b).expect("expected value");
+    let keys = db.node_keys(&[c, a, 9999, b, unkeyed]);
+    assert_eq!(
+      keys,
+      vec![
+        Some("c".to_string()),
+        Some("a".to_string()),
+        None,
+        None,
+        None
+      ]
+    );
+    db.rollback().expect("expected value");
+
+    assert_eq!(db.node_keys(&[c, b]), vec![None, Some("b".to_string())]);
+    assert!(db.node_keys(&[]).is_empty());
+
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_edges_filtered_by_type_across_snapshot_and_delta() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("test-db");
+    let db = open_single_file(db_path, SingleFileOpenOptions::new()).expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let a = db.create_node(Some("a")).expect("expected value");
+    let b = db.create_node(Some("b")).expect("expected value");
+    let c = db.create_node(Some("c")).expect("expected value");
+    let knows = db.define_etype("KNOWS").expect("expected value");
+    let likes = db.define_etype("LIKES").expect("expected value");
+    db.add_edge(a, knows, b).expect("expected value");
+    db.add_edge(a, likes, b).expect("expected value");
+    db.commit().expect("expected value");
+    db.checkpoint().expect("expected value");
+
+    db.begin(false).expect("expected value");
+    db.add_edge(a, knows, c).expect("expected value");
+    db.add_edge(c, likes, b).expect("expected value");
+    db.commit().expect("expected value");
+
+    db.begin(false).expect("expected value");
+    db.add_edge(a, likes, c).expect("expected value");
+    assert_eq!(
+      db.out_edges_filtered(a, Some(knows)),
+      vec![(knows, b), (knows, c)]
+    );
+    assert_eq!(
+      db.out_edges_filtered(a, Some(likes)),
+      vec![(likes, b), (likes, c)]
+    );
+    assert_eq!(db.out_edges_filtered(a, None), db.out_edges(a));
+    assert_eq!(
+      db.in_edges_filtered(b, Some(likes)),
+      vec![(likes, a), (likes, c)]
+    );
+    assert_eq!(db.in_edges_filtered(b, Some(knows)), vec![(knows, a)]);
+    db.rollback().expect("expected value");
+
+    close_single_file(db).expect("expected value");
+  }
+
   #[test]
   fn test_mvcc_label_visibility_across_transactions() {
     let temp_dir = tempdir().expect("expected value");
@@ -1444,10 +1750,13 @@ mod tests {
       cont_rx.recv().expect("expected value");
       let result = db_reader.commit();
       match result {
-        Err(KiteError::Conflict { keys, .. }) => {
+        Err(KiteError::Conflict { keys, details, .. }) => {
           assert!(keys
             .iter()
             .any(|key| key == &format!("neighbors_out:{src}:*")));
+          assert!(details
+            .iter()
+            .any(|info| info.category == "neighbors" && info.conflicting_txid > 0));
         }
         other => panic!("expected conflict, got {other:?}"),
       }
@@ -1467,4 +1776,57 @@ mod tests {
     };
     close_single_file(db).expect("expected value");
   }
+
+  #[test]
+  fn test_find_keys_merges_snapshot_delta_and_pending() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("test-db");
+    let db = open_single_file(db_path, SingleFileOpenOptions::new()).expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let alice = db.create_node(Some("user:alice")).expect("expected value");
+    let bob = db.create_node(Some("user:bob")).expect("expected value");
+    let admin = db.create_node(Some("team:admin")).expect("expected value");
+    db.commit().expect("expected value");
+    db.checkpoint().expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let carol = db.create_node(Some("user:carol")).expect("expected value");
+    db.delete_node(bob).expect("expected value");
+    db.commit().expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let dave = db.create_node(Some("user:dave")).expect("expected value");
+    assert_eq!(
+      db.find_keys("user:*", 10),
+      vec![
+        (alice, "user:alice".to_string()),
+        (carol, "user:carol".to_string()),
+        (dave, "user:dave".to_string()),
+      ]
+    );
+    db.rollback().expect("expected value");
+
+    assert_eq!(
+      db.find_keys("user:*", 1),
+      vec![(alice, "user:alice".to_string())]
+    );
+    assert_eq!(
+      db.find_keys("user:?????", 10),
+      vec![
+        (alice, "user:alice".to_string()),
+        (carol, "user:carol".to_string()),
+      ]
+    );
+    assert_eq!(
+      db.find_keys("*:a*", 10),
+      vec![
+        (admin, "team:admin".to_string()),
+        (alice, "user:alice".to_string()),
+      ]
+    );
+    assert!(db.find_keys("user:*", 0).is_empty());
+
+    close_single_file(db).expect("expected value");
+  }
 }
@@ -2,7 +2,7 @@
 //!
 //! Handles scanning WAL records and replaying them during database open.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 
 use crate::constants::*;
 use crate::core::pager::FilePager;
@@ -14,17 +14,20 @@ use crate::core::wal::record::{
   parse_define_label_payload, parse_define_propkey_payload, parse_del_edge_prop_payload,
   parse_del_node_prop_payload, parse_del_node_vector_payload, parse_delete_edge_payload,
   parse_delete_node_payload, parse_remove_node_label_payload, parse_set_edge_prop_payload,
-  parse_set_edge_props_payload, parse_set_node_prop_payload, parse_set_node_vector_payload,
-  ParsedWalRecord,
+  parse_set_edge_props_payload, parse_set_metadata_payload, parse_set_node_key_payload,
+  parse_set_node_prop_payload, parse_set_node_vector_payload, ParsedWalRecord,
 };
 use crate::error::Result;
 use crate::types::*;
 
 /// Scan WAL records from the WAL area (linear)
+///
+/// Also returns how many bytes before the WAL head were discarded because
+/// scanning hit an incomplete or corrupt record (e.g. a write torn by a crash).
 pub(crate) fn scan_wal_records(
   pager: &mut FilePager,
   header: &DbHeaderV1,
-) -> Result<Vec<ParsedWalRecord>> {
+) -> Result<(Vec<ParsedWalRecord>, u64)> {
   use crate::core::wal::record::parse_wal_record;
 
   let mut records = Vec::new();
@@ -46,7 +49,7 @@ pub(crate) fn scan_wal_records(
 
   // If tail == head, WAL is empty
   if pos == head {
-    return Ok(records);
+    return Ok((records, 0));
   }
 
   // Read the WAL area into memory for scanning
@@ -86,7 +89,7 @@ pub(crate) fn scan_wal_records(
     }
   }
 
-  Ok(records)
+  Ok((records, head.saturating_sub(pos)))
 }
 
 /// Read the entire WAL area into memory
@@ -104,15 +107,62 @@ pub(crate) fn read_wal_area(pager: &mut FilePager, header: &DbHeaderV1) -> Resul
   Ok(wal_data)
 }
 
-/// Extract committed transactions from WAL records
+/// Extract committed transactions from WAL records, in commit order
+///
+/// Replay must follow commit order so later writes to the same node or
+/// property win over earlier ones.
 pub(crate) fn committed_transactions(
   wal_records: &[ParsedWalRecord],
 ) -> Vec<(TxId, Vec<&ParsedWalRecord>)> {
-  extract_committed_transactions(wal_records)
-    .into_iter()
+  let mut committed = extract_committed_transactions(wal_records);
+  wal_records
+    .iter()
+    .filter(|record| record.record_type == WalRecordType::Commit)
+    .filter_map(|record| committed.remove_entry(&record.txid))
     .collect()
 }
 
+/// Records of transactions that haven't committed or rolled back yet
+///
+/// Anything that drops the WAL while such transactions are open must write
+/// these back, or their later commit replays without a BEGIN.
+pub(crate) fn unfinished_transaction_records(
+  wal_records: &[ParsedWalRecord],
+) -> Vec<ParsedWalRecord> {
+  let finished: HashSet<TxId> = wal_records
+    .iter()
+    .filter(|record| {
+      matches!(
+        record.record_type,
+        WalRecordType::Commit | WalRecordType::Rollback
+      )
+    })
+    .map(|record| record.txid)
+    .collect();
+  wal_records
+    .iter()
+    .filter(|record| !finished.contains(&record.txid))
+    .cloned()
+    .collect()
+}
+
+/// Count transactions that began in the WAL but never committed or rolled back
+pub(crate) fn unfinished_transaction_count(wal_records: &[ParsedWalRecord]) -> usize {
+  let mut open: HashSet<TxId> = HashSet::new();
+  for record in wal_records {
+    match record.record_type {
+      WalRecordType::Begin => {
+        open.insert(record.txid);
+      }
+      WalRecordType::Commit | WalRecordType::Rollback => {
+        open.remove(&record.txid);
+      }
+      _ => {}
+    }
+  }
+  open.len()
+}
+
 /// Replay a single WAL record into delta and update allocators/schema
 #[allow(clippy::too_many_arguments)]
 pub fn replay_wal_record(
@@ -166,6 +216,20 @@ pub fn replay_wal_record(
         delta.delete_node(data.node_id);
       }
     }
+    WalRecordType::SetNodeKey => {
+      if let Some(data) = parse_set_node_key_payload(&record.payload) {
+        let old_key = delta
+          .node_delta(data.node_id)
+          .and_then(|d| d.key.clone())
+          .or_else(|| {
+            let snap = snapshot?;
+            snap.node_key(snap.phys_node(data.node_id)?)
+          });
+        if let Some(new_key) = data.key.as_deref() {
+          delta.set_node_key(data.node_id, old_key.as_deref(), new_key);
+        }
+      }
+    }
     WalRecordType::AddEdge => {
       if let Some(data) = parse_add_edge_payload(&record.payload) {
         delta.add_edge(data.src, data.etype, data.dst);
@@ -283,8 +347,57 @@ pub fn replay_wal_record(
           .insert((data.node_id, data.prop_key_id), None);
       }
     }
+    WalRecordType::SetMetadata => {
+      if let Some(data) = parse_set_metadata_payload(&record.payload) {
+        delta.metadata.insert(data.key, data.value);
+      }
+    }
     _ => {
       // Other record types (batch vectors, seal fragment, etc.) - skip for now
     }
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  fn record(record_type: WalRecordType, txid: TxId) -> ParsedWalRecord {
+    ParsedWalRecord {
+      record_type,
+      flags: 0,
+      txid,
+      payload: Vec::new(),
+      record_end: 0,
+    }
+  }
+
+  #[test]
+  fn committed_transactions_follow_commit_order() {
+    // All transactions begin and write before any commits, then commit in
+    // reverse txid order; replay must see the last commit last.
+    let mut records = Vec::new();
+    for txid in 1..=32 {
+      records.push(record(WalRecordType::Begin, txid));
+      records.push(record(WalRecordType::SetNodeProp, txid));
+    }
+    for txid in (1..=32).rev() {
+      let end = if txid == 5 {
+        WalRecordType::Rollback
+      } else {
+        WalRecordType::Commit
+      };
+      records.push(record(end, txid));
+    }
+
+    let order: Vec<TxId> = committed_transactions(&records)
+      .into_iter()
+      .map(|(txid, tx_records)| {
+        assert_eq!(tx_records.len(), 1);
+        txid
+      })
+      .collect();
+    let expected: Vec<TxId> = (1..=32).rev().filter(|&txid| txid != 5).collect();
+    assert_eq!(order, expected);
+  }
+}
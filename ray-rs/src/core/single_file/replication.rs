@@ -1,12 +1,14 @@
 //! Replica-side operations and token wait helpers.
 
+use crate::core::snapshot::reader::SnapshotData;
 use crate::core::wal::record::{
   parse_add_edge_payload, parse_add_edge_props_payload, parse_add_edges_batch_payload,
   parse_add_edges_props_batch_payload, parse_add_node_label_payload, parse_create_node_payload,
   parse_create_nodes_batch_payload, parse_del_edge_prop_payload, parse_del_node_prop_payload,
   parse_del_node_vector_payload, parse_delete_edge_payload, parse_delete_node_payload,
   parse_remove_node_label_payload, parse_set_edge_prop_payload, parse_set_edge_props_payload,
-  parse_set_node_prop_payload, parse_set_node_vector_payload, parse_wal_record, ParsedWalRecord,
+  parse_set_metadata_payload, parse_set_node_key_payload, parse_set_node_prop_payload,
+  parse_set_node_vector_payload, parse_wal_record, ParsedWalRecord,
 };
 use crate::error::{KiteError, Result};
 use crate::replication::manifest::ManifestStore;
@@ -14,7 +16,7 @@ use crate::replication::primary::PrimaryRetentionOutcome;
 use crate::replication::replica::ReplicaReplicationStatus;
 use crate::replication::transport::decode_commit_frame_payload;
 use crate::replication::types::{CommitToken, ReplicationCursor, ReplicationRole};
-use crate::types::WalRecordType;
+use crate::types::{DeltaState, WalRecordType};
 use crate::util::crc::{crc32c, Crc32cHasher};
 use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
 use base64::Engine;
@@ -24,11 +26,15 @@ use std::fs::File;
 use std::io::{BufReader, Read, Write};
 use std::path::Path;
 use std::str::FromStr;
+use std::sync::atomic::Ordering;
 use std::time::{Duration, Instant};
 
 use super::{close_single_file, open_single_file, SingleFileDB, SingleFileOpenOptions};
 
 const REPLICATION_MANIFEST_FILE: &str = "manifest.json";
+/// Metadata entry holding the replication epoch of the first commit made as
+/// primary; it shows the database has been a primary
+pub(crate) const PRIMARY_EPOCH_METADATA_KEY: &str = "replication:primary_epoch";
 const REPLICATION_FRAME_MAGIC: u32 = 0x474F_4C52;
 const REPLICATION_FRAME_VERSION: u16 = 1;
 const REPLICATION_FRAME_FLAG_CRC32_DISABLED: u16 = 0x0001;
@@ -43,7 +49,35 @@ const REPLICA_BOOTSTRAP_MAX_ATTEMPTS: usize = 20;
 const REPLICA_BOOTSTRAP_INITIAL_BACKOFF_MS: u64 = 10;
 const REPLICA_BOOTSTRAP_MAX_BACKOFF_MS: u64 = 320;
 
+/// Epoch recorded by the first commit made as primary, if any
+pub(crate) fn load_primary_epoch(
+  snapshot: Option<&SnapshotData>,
+  delta: &DeltaState,
+) -> Result<Option<u64>> {
+  let value = match delta.metadata.get(PRIMARY_EPOCH_METADATA_KEY) {
+    Some(value) => value.clone(),
+    None => match snapshot {
+      Some(snapshot) => snapshot.metadata()?.remove(PRIMARY_EPOCH_METADATA_KEY),
+      None => None,
+    },
+  };
+  Ok(value.and_then(|bytes| Some(u64::from_le_bytes(bytes.as_slice().try_into().ok()?))))
+}
+
 impl SingleFileDB {
+  /// Record the primary epoch in the current write transaction unless a
+  /// commit already has
+  pub(crate) fn record_primary_epoch(&self) -> Result<()> {
+    let Some(replication) = self.primary_replication.as_ref() else {
+      return Ok(());
+    };
+    if self.primary_epoch_recorded.load(Ordering::SeqCst) {
+      return Ok(());
+    }
+    let epoch = replication.status().epoch;
+    self.set_metadata(PRIMARY_EPOCH_METADATA_KEY, &epoch.to_le_bytes())
+  }
+
   /// Promote this primary instance to the next replication epoch.
   pub fn primary_promote_to_next_epoch(&self) -> Result<u64> {
     self
@@ -982,6 +1016,15 @@ fn apply_wal_record_idempotent(db: &SingleFileDB, record: &ParsedWalRecord) -> R
 
       Ok(())
     }
+    WalRecordType::SetNodeKey => {
+      let data = parse_set_node_key_payload(&record.payload).ok_or_else(|| {
+        KiteError::InvalidReplication("invalid SetNodeKey replication payload".to_string())
+      })?;
+      if let Some(new_key) = data.key.as_deref() {
+        db.set_node_key(data.node_id, new_key)?;
+      }
+      Ok(())
+    }
     WalRecordType::DeleteNode => {
       let data = parse_delete_node_payload(&record.payload).ok_or_else(|| {
         KiteError::InvalidReplication("invalid DeleteNode replication payload".to_string())
@@ -1153,6 +1196,19 @@ fn apply_wal_record_idempotent(db: &SingleFileDB, record: &ParsedWalRecord) -> R
       }
       Ok(())
     }
+    WalRecordType::SetMetadata => {
+      let data = parse_set_metadata_payload(&record.payload).ok_or_else(|| {
+        KiteError::InvalidReplication("invalid SetMetadata replication payload".to_string())
+      })?;
+
+      if db.metadata(&data.key)? != data.value {
+        match data.value {
+          Some(value) => db.set_metadata(&data.key, &value)?,
+          None => db.remove_metadata(&data.key)?,
+        }
+      }
+      Ok(())
+    }
     WalRecordType::DefineLabel | WalRecordType::DefineEtype | WalRecordType::DefinePropkey => {
       // IDs are embedded in mutation records; numeric IDs are sufficient for correctness
       // during V1 replication apply.
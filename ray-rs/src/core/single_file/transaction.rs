@@ -2,24 +2,27 @@
 //!
 //! Handles begin, commit, and rollback operations.
 
+use crate::core::snapshot::reader::SnapshotData;
 use crate::core::wal::record::{
   build_begin_payload, build_commit_payload, build_rollback_payload, WalRecord,
 };
 use crate::error::{KiteError, Result};
+use crate::mvcc::TxManager;
 use crate::replication::primary::PrimaryReplicationStatus;
 use crate::replication::types::CommitToken;
 use crate::types::*;
 use parking_lot::Mutex;
 use std::marker::PhantomData;
 use std::rc::Rc;
-use std::sync::atomic::Ordering;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 use std::time::Duration;
 #[cfg(feature = "bench-profile")]
 use std::time::Instant;
 
 use super::open::SyncMode;
-use super::{SingleFileDB, SingleFileTxState};
+use super::replication::PRIMARY_EPOCH_METADATA_KEY;
+use super::{CommitCheck, PropChange, PropListenerId, Savepoint, SingleFileDB, SingleFileTxState};
 
 /// RAII transaction guard for SingleFileDB.
 /// Rolls back the transaction on drop unless committed or rolled back.
@@ -68,6 +71,52 @@ impl Drop for SingleFileTxGuard<'_> {
   }
 }
 
+/// Whether a record defines schema (applied globally as soon as it's made)
+fn is_definition_record(record_type: WalRecordType) -> bool {
+  matches!(
+    record_type,
+    WalRecordType::DefineLabel | WalRecordType::DefineEtype | WalRecordType::DefinePropkey
+  )
+}
+
+/// Marks a commit as written to the WAL but not yet applied to the delta
+struct ApplyingCommit<'a>(&'a AtomicUsize);
+
+impl<'a> ApplyingCommit<'a> {
+  fn new(count: &'a AtomicUsize) -> Self {
+    count.fetch_add(1, Ordering::SeqCst);
+    Self(count)
+  }
+}
+
+impl Drop for ApplyingCommit<'_> {
+  fn drop(&mut self) {
+    self.0.fetch_sub(1, Ordering::SeqCst);
+  }
+}
+
+/// Keeps a commit marked unapplied in the MVCC tx manager until dropped
+struct UnappliedCommit<'a> {
+  tx_manager: &'a Mutex<TxManager>,
+  commit_ts: Timestamp,
+}
+
+impl<'a> UnappliedCommit<'a> {
+  fn new(tx_manager: &'a Mutex<TxManager>, tx_mgr: &mut TxManager, commit_ts: Timestamp) -> Self {
+    tx_mgr.mark_unapplied(commit_ts);
+    Self {
+      tx_manager,
+      commit_ts,
+    }
+  }
+}
+
+impl Drop for UnappliedCommit<'_> {
+  fn drop(&mut self) {
+    self.tx_manager.lock().mark_applied(self.commit_ts);
+  }
+}
+
 impl SingleFileDB {
   fn begin_with_mode(&self, read_only: bool, bulk_load: bool) -> Result<TxId> {
     if self.read_only && !read_only {
@@ -82,14 +131,22 @@ impl SingleFileDB {
       ));
     }
 
-    let tid = std::thread::current().id();
-    {
-      let current_tx = self.current_tx.lock();
-      if current_tx.contains_key(&tid) {
+    // A nested begin joins the thread's active transaction; only the
+    // outermost commit/rollback ends it.
+    if let Some(handle) = self.current_tx_handle() {
+      let mut tx = handle.lock();
+      if bulk_load || tx.bulk_load {
         return Err(KiteError::TransactionInProgress);
       }
+      if tx.read_only && !read_only {
+        return Err(KiteError::ReadOnly);
+      }
+      tx.depth += 1;
+      return Ok(tx.txid);
     }
 
+    let tid = std::thread::current().id();
+
     let (txid, snapshot_ts) = if let Some(mvcc) = self.mvcc.as_ref() {
       let (txid, snapshot_ts) = {
         let mut tx_mgr = mvcc.tx_manager.lock();
@@ -121,6 +178,10 @@ impl SingleFileDB {
     self.current_tx.lock().insert(tid, tx_state);
     if !read_only {
       self.active_writers.fetch_add(1, Ordering::SeqCst);
+      if let Err(err) = self.record_primary_epoch() {
+        self.rollback()?;
+        return Err(err);
+      }
     }
     Ok(txid)
   }
@@ -144,10 +205,74 @@ impl SingleFileDB {
   }
 
   /// Begin a new transaction
+  ///
+  /// If this thread already has an active transaction, joins it instead and
+  /// returns its ID (each nested `begin` needs a matching commit/rollback).
   pub fn begin(&self, read_only: bool) -> Result<TxId> {
     self.begin_with_mode(read_only, false)
   }
 
+  /// Begin a read-only transaction pinned to a past generation
+  ///
+  /// A generation is the MVCC snapshot timestamp a read transaction starts
+  /// at; see [`current_generation`](Self::current_generation). Every read in
+  /// the transaction is served as of that generation. Fails with
+  /// [`KiteError::GenerationUnavailable`] if the generation's history has been
+  /// pruned (or was never retained) or lies in the future. Requires MVCC;
+  /// past generations are only retained with
+  /// [`mvcc_retain_history`](crate::core::single_file::SingleFileOpenOptions::mvcc_retain_history).
+  pub fn begin_read_at(&self, generation: u64) -> Result<TxId> {
+    let mvcc = self.mvcc.as_ref().ok_or_else(|| {
+      KiteError::Internal("reading at a past generation requires MVCC".to_string())
+    })?;
+    if self.current_tx_handle().is_some() {
+      return Err(KiteError::TransactionInProgress);
+    }
+
+    let txid = {
+      let mut tx_mgr = mvcc.tx_manager.lock();
+      let oldest = tx_mgr.history_floor_ts();
+      let latest = tx_mgr.next_commit_ts();
+      if generation < oldest || generation > latest {
+        return Err(KiteError::GenerationUnavailable {
+          generation,
+          oldest,
+          latest,
+        });
+      }
+      let (txid, _) = tx_mgr.begin_tx_at(generation);
+      txid
+    };
+    self
+      .next_tx_id
+      .store(txid.saturating_add(1), Ordering::SeqCst);
+
+    let tx_state = Arc::new(Mutex::new(SingleFileTxState::new(
+      txid, true, generation, false,
+    )));
+    self
+      .current_tx
+      .lock()
+      .insert(std::thread::current().id(), tx_state);
+    Ok(txid)
+  }
+
+  /// Generation a new read transaction would start at
+  ///
+  /// Returns `None` when MVCC is disabled.
+  pub fn current_generation(&self) -> Option<u64> {
+    let mvcc = self.mvcc.as_ref()?;
+    Some(mvcc.tx_manager.lock().next_commit_ts())
+  }
+
+  /// Oldest generation [`begin_read_at`](Self::begin_read_at) can still serve
+  ///
+  /// Returns `None` when MVCC is disabled.
+  pub fn oldest_readable_generation(&self) -> Option<u64> {
+    let mvcc = self.mvcc.as_ref()?;
+    Some(mvcc.tx_manager.lock().history_floor_ts())
+  }
+
   /// Begin a new transaction guard (rolls back on drop)
   pub fn begin_guard(&self, read_only: bool) -> Result<SingleFileTxGuard<'_>> {
     let txid = self.begin_with_mode(read_only, false)?;
@@ -172,13 +297,13 @@ impl SingleFileDB {
     pending: &DeltaState,
     delta: &DeltaState,
   ) {
-    let Some((commit_ts, has_active_readers)) = commit_ts_for_mvcc else {
+    let Some((commit_ts, record_versions)) = commit_ts_for_mvcc else {
       return;
     };
     let Some(mvcc) = self.mvcc.as_ref() else {
       return;
     };
-    if !has_active_readers {
+    if !record_versions {
       return;
     }
 
@@ -372,18 +497,64 @@ impl SingleFileDB {
   }
 
   /// Commit the current transaction and return replication commit token if enabled.
+  ///
+  /// Committing a nested transaction only closes that nesting level. If a
+  /// nested transaction rolled back, the outermost commit rolls back instead
+  /// and returns [`KiteError::RollbackOnly`].
   pub fn commit_with_token(&self) -> Result<Option<CommitToken>> {
+    self.commit_inner(false).map(|(token, _)| token)
+  }
+
+  /// Commit the current transaction and summarize what it changed
+  ///
+  /// Counts come from the transaction's pending changes, so edges dropped
+  /// along with a deleted node aren't counted as deleted edges. Closing a
+  /// nested level commits nothing and returns all zeros.
+  pub fn commit_stats(&self) -> Result<CommitStats> {
+    self
+      .commit_inner(true)
+      .map(|(_, stats)| stats.unwrap_or_default())
+  }
+
+  fn commit_inner(&self, with_stats: bool) -> Result<(Option<CommitToken>, Option<CommitStats>)> {
+    if let Some(handle) = self.current_tx_handle() {
+      let mut tx = handle.lock();
+      if tx.depth > 0 {
+        tx.depth -= 1;
+        return Ok((None, None));
+      }
+      if tx.rollback_only {
+        drop(tx);
+        self.rollback()?;
+        return Err(KiteError::RollbackOnly);
+      }
+    }
+
     let tx_handle = {
       let tid = std::thread::current().id();
       let mut current_tx = self.current_tx.lock();
       current_tx.remove(&tid).ok_or(KiteError::NoTransaction)?
     };
 
-    let (txid, read_only, bulk_load, pending, pending_wal) = {
+    let (txid, read_only, bulk_load, pending, pending_wal, held_back, started_at) = {
       let mut tx = tx_handle.lock();
+      // Records a savepoint held back go ahead of the deferred prop sets
+      let mut held_back = std::mem::take(&mut tx.deferred_wal);
+      held_back.extend(tx.take_prop_sets());
+      for record in &held_back {
+        tx.pending_wal.extend_from_slice(&record.build());
+      }
       let pending = std::mem::take(&mut tx.pending);
       let pending_wal = std::mem::take(&mut tx.pending_wal);
-      (tx.txid, tx.read_only, tx.bulk_load, pending, pending_wal)
+      (
+        tx.txid,
+        tx.read_only,
+        tx.bulk_load,
+        pending,
+        pending_wal,
+        held_back,
+        tx.started_at,
+      )
     };
 
     if read_only {
@@ -392,12 +563,46 @@ impl SingleFileDB {
         let mut tx_mgr = mvcc.tx_manager.lock();
         tx_mgr.abort_tx(txid);
       }
-      return Ok(None);
+      let stats = with_stats.then(|| CommitStats {
+        duration_ms: started_at.elapsed().as_millis() as u64,
+        ..CommitStats::default()
+      });
+      return Ok((None, stats));
     }
+    let mut stats = with_stats.then(|| {
+      let framing = WalRecord::new(WalRecordType::Begin, txid, build_begin_payload())
+        .estimated_size()
+        + WalRecord::new(WalRecordType::Commit, txid, build_commit_payload()).estimated_size();
+      CommitStats {
+        wal_bytes_written: (pending_wal.len() + framing) as u64,
+        ..CommitStats::from_pending(&pending)
+      }
+    });
     let prev_writers = self.active_writers.fetch_sub(1, Ordering::SeqCst);
     debug_assert!(prev_writers > 0, "active_writers underflow in commit");
 
+    // Serialize commit to preserve WAL ordering without holding the delta lock during I/O.
+    #[cfg(feature = "bench-profile")]
+    let commit_lock_start = Instant::now();
+    let commit_guard = self.commit_lock.lock();
+    #[cfg(feature = "bench-profile")]
+    self.commit_lock_wait_ns.fetch_add(
+      commit_lock_start.elapsed().as_nanos() as u64,
+      Ordering::Relaxed,
+    );
+
+    let commit_check = self.commit_check.read().clone();
+    if let Some(check) = commit_check {
+      // Earlier commits must reach the delta for the check to see them
+      self.wait_for_applying_commits();
+      if let Err(err) = check(self, &pending) {
+        self.abort_rejected_commit(txid, bulk_load)?;
+        return Err(err);
+      }
+    }
+
     let mut commit_ts_for_mvcc = None;
+    let mut unapplied = None;
     if let Some(mvcc) = self.mvcc.as_ref() {
       let mut tx_mgr = mvcc.tx_manager.lock();
       if let Err(err) = mvcc.conflict_detector.validate_commit(&tx_mgr, txid) {
@@ -405,13 +610,32 @@ impl SingleFileDB {
         return Err(KiteError::Conflict {
           txid: err.txid,
           keys: err.conflicting_keys,
+          details: err.details,
         });
       }
 
       let commit_ts = tx_mgr
         .commit_tx(txid)
         .map_err(|e| KiteError::Internal(e.to_string()))?;
-      commit_ts_for_mvcc = Some((commit_ts, tx_mgr.active_count() > 0));
+      let record_versions = tx_mgr.active_count() > 0 || mvcc.retain_history;
+      if !record_versions {
+        // No version chain for this commit, so older snapshots are no longer
+        // reproducible.
+        tx_mgr.advance_history_floor(commit_ts + 1);
+        // Nothing can read the versions left by earlier commits either, and
+        // they would shadow this commit's writes, which only reach the delta.
+        // A commit that isn't in the delta yet appends its versions after
+        // this, and none of its keys are ours or we would have conflicted.
+        mvcc.version_chain.lock().clear();
+      }
+      commit_ts_for_mvcc = Some((commit_ts, record_versions));
+      // Transactions starting before the delta holds this commit must see
+      // its writes as concurrent
+      unapplied = Some(UnappliedCommit::new(
+        &mvcc.tx_manager,
+        &mut tx_mgr,
+        commit_ts,
+      ));
     }
 
     let replication_enabled = self.primary_replication.is_some();
@@ -419,18 +643,21 @@ impl SingleFileDB {
       self.group_commit_enabled && self.sync_mode == SyncMode::Normal && !replication_enabled;
     let mut group_commit_seq = 0u64;
     let mut commit_token = None;
+    let checkpoint_epoch;
+    let applying;
+    let mut prop_changes = Vec::new();
 
     {
-      // Serialize commit to preserve WAL ordering without holding the delta lock during I/O.
-      #[cfg(feature = "bench-profile")]
-      let commit_lock_start = Instant::now();
-      let _commit_guard = self.commit_lock.lock();
-      #[cfg(feature = "bench-profile")]
-      self.commit_lock_wait_ns.fetch_add(
-        commit_lock_start.elapsed().as_nanos() as u64,
-        Ordering::Relaxed,
-      );
-
+      checkpoint_epoch = self.checkpoint_epoch.load(Ordering::SeqCst);
+      // Old values are read before this commit's WAL write, so a checkpoint
+      // that folds the commit into the delta can't hide the change. Earlier
+      // commits must reach the delta first or their values would be missed.
+      if !self.prop_listeners.read().is_empty() {
+        self.wait_for_applying_commits();
+        prop_changes = self.collect_prop_changes(&pending);
+      }
+      // Counted until the delta holds this commit, so optimize can wait on it
+      applying = ApplyingCommit::new(&self.applying_commits);
       let mut pager = self.pager.lock();
       let mut wal = self.wal_buffer.lock();
       if bulk_load {
@@ -442,6 +669,9 @@ impl SingleFileDB {
         let commit_record = WalRecord::new(WalRecordType::Commit, txid, build_commit_payload());
         wal.write_record(&commit_record, &mut pager)?;
       } else {
+        for record in &held_back {
+          wal.write_record(record, &mut pager)?;
+        }
         // Write COMMIT record to WAL
         let record = WalRecord::new(WalRecordType::Commit, txid, build_commit_payload());
         wal.write_record(&record, &mut pager)?;
@@ -508,6 +738,7 @@ impl SingleFileDB {
         commit_token = Some(replication.append_commit_wal_frame(txid, pending_wal)?);
       }
     }
+    drop(commit_guard);
 
     if group_commit_active {
       self.wait_for_group_commit(group_commit_seq)?;
@@ -517,14 +748,31 @@ impl SingleFileDB {
 
     self.apply_mvcc_commit(commit_ts_for_mvcc, txid, &pending, &delta);
 
-    // Apply pending vector operations
-    self.apply_pending_vectors(&pending.pending_vectors)?;
-
-    merge_pending_delta(&mut delta, pending);
+    self.cache_invalidate_pending_keys(&pending);
+    self.apply_no_self_loops_metadata(&pending.metadata);
+    self.apply_enum_props_metadata(&pending.metadata);
+    if pending.metadata.contains_key(PRIMARY_EPOCH_METADATA_KEY) {
+      self.primary_epoch_recorded.store(true, Ordering::SeqCst);
+    }
+    // A background checkpoint that finished after our WAL write rebuilt the
+    // delta and vector stores from the WAL, so they already hold this commit
+    if self.checkpoint_epoch.load(Ordering::SeqCst) == checkpoint_epoch {
+      // Apply pending vector operations
+      self.apply_pending_vectors(&pending.pending_vectors)?;
+      merge_pending_delta(&mut delta, pending);
+    }
     if bulk_load {
       self.cache_clear();
     }
     drop(delta);
+    drop(unapplied);
+    drop(applying);
+    if !prop_changes.is_empty() {
+      self.notify_prop_changes(prop_changes);
+    }
+    if let Some(stats) = stats.as_mut() {
+      stats.duration_ms = started_at.elapsed().as_millis() as u64;
+    }
 
     // Check if auto-checkpoint should be triggered
     // Note: We release all locks above first to avoid deadlock during checkpoint
@@ -545,11 +793,23 @@ impl SingleFileDB {
       }
     }
 
-    Ok(commit_token)
+    Ok((commit_token, stats))
   }
 
   /// Rollback the current transaction
+  ///
+  /// Rolling back a nested transaction closes that nesting level and marks
+  /// the outer transaction rollback-only.
   pub fn rollback(&self) -> Result<()> {
+    if let Some(handle) = self.current_tx_handle() {
+      let mut tx = handle.lock();
+      if tx.depth > 0 {
+        tx.depth -= 1;
+        tx.rollback_only = true;
+        return Ok(());
+      }
+    }
+
     let tx_handle = {
       let tid = std::thread::current().id();
       let mut current_tx = self.current_tx.lock();
@@ -587,6 +847,206 @@ impl SingleFileDB {
     Ok(())
   }
 
+  /// End a transaction whose commit check failed as if it rolled back
+  fn abort_rejected_commit(&self, txid: TxId, bulk_load: bool) -> Result<()> {
+    if let Some(mvcc) = self.mvcc.as_ref() {
+      let mut tx_mgr = mvcc.tx_manager.lock();
+      tx_mgr.abort_tx(txid);
+    }
+    if !bulk_load {
+      let record = WalRecord::new(WalRecordType::Rollback, txid, build_rollback_payload());
+      let mut pager = self.pager.lock();
+      let mut wal = self.wal_buffer.lock();
+      wal.write_record(&record, &mut pager)?;
+    }
+    Ok(())
+  }
+
+  /// Mark a point in the current transaction to roll back to
+  ///
+  /// Names may repeat; [`Self::rollback_to_savepoint`] uses the most recent
+  /// one. Each savepoint copies the transaction's pending changes, and once
+  /// one exists the transaction's data records reach the WAL at commit
+  /// instead of as they're made. Not available in bulk-load transactions.
+  pub fn savepoint(&self, name: &str) -> Result<()> {
+    let handle = self.current_tx_handle().ok_or(KiteError::NoTransaction)?;
+    let mut tx = handle.lock();
+    if tx.bulk_load {
+      return Err(KiteError::Internal(
+        "savepoints are not supported in bulk-load transactions".to_string(),
+      ));
+    }
+    let savepoint = Savepoint {
+      name: name.to_string(),
+      pending: tx.pending.clone(),
+      pending_prop_sets: tx.pending_prop_sets.clone(),
+      pending_prop_slots: tx.pending_prop_slots.clone(),
+      deferred_len: tx.deferred_wal.len(),
+    };
+    tx.savepoints.push(savepoint);
+    Ok(())
+  }
+
+  /// Undo the current transaction's changes since savepoint `name`
+  ///
+  /// The transaction stays open and the savepoint stays set, so it can be
+  /// rolled back to again; savepoints taken after it are dropped. Labels,
+  /// edge types, and prop keys defined since are kept, and under MVCC the
+  /// keys written since still count toward commit conflicts.
+  pub fn rollback_to_savepoint(&self, name: &str) -> Result<()> {
+    let handle = self.current_tx_handle().ok_or(KiteError::NoTransaction)?;
+    let mut guard = handle.lock();
+    let tx = &mut *guard;
+    let index = tx
+      .savepoints
+      .iter()
+      .rposition(|savepoint| savepoint.name == name)
+      .ok_or_else(|| KiteError::UnknownSavepoint(name.to_string()))?;
+    tx.savepoints.truncate(index + 1);
+    let savepoint = &tx.savepoints[index];
+    tx.pending = savepoint.pending.clone();
+    tx.pending_prop_sets = savepoint.pending_prop_sets.clone();
+    tx.pending_prop_slots = savepoint.pending_prop_slots.clone();
+    tx.deferred_wal.truncate(savepoint.deferred_len);
+    Ok(())
+  }
+
+  /// Call `listener` after each commit that changes node prop `key_id`
+  ///
+  /// Listeners run on the committing thread once the commit is applied,
+  /// once per changed (node, prop). Writing a prop's current value again is
+  /// not a change; deleting a node changes each of its watched props to
+  /// `None`. Pass the returned id to [`Self::remove_prop_listener`] to stop.
+  pub fn on_prop_change(
+    &self,
+    key_id: PropKeyId,
+    listener: impl Fn(&PropChange) + Send + Sync + 'static,
+  ) -> PropListenerId {
+    let id = self.next_prop_listener_id.fetch_add(1, Ordering::Relaxed);
+    self
+      .prop_listeners
+      .write()
+      .entry(key_id)
+      .or_default()
+      .push((id, Arc::new(listener)));
+    id
+  }
+
+  /// Install (or with `None`, remove) a check every write commit must pass
+  ///
+  /// The check runs with the commit lock held, after earlier commits are
+  /// applied and before this one reaches the WAL, so no other commit can
+  /// interleave with it. An error ends the transaction as a rollback and
+  /// is returned from `commit`.
+  pub fn set_commit_check(&self, check: Option<CommitCheck>) {
+    *self.commit_check.write() = check;
+  }
+
+  /// Unregister a listener added with [`Self::on_prop_change`]
+  ///
+  /// Returns false if `id` isn't registered.
+  pub fn remove_prop_listener(&self, id: PropListenerId) -> bool {
+    let mut listeners = self.prop_listeners.write();
+    let mut removed = false;
+    listeners.retain(|_, entries| {
+      let before = entries.len();
+      entries.retain(|(entry_id, _)| *entry_id != id);
+      removed |= entries.len() != before;
+      !entries.is_empty()
+    });
+    removed
+  }
+
+  /// Watched prop writes in `pending`, with the committed values they replace
+  ///
+  /// Called with `commit_lock` held, before the commit reaches the WAL.
+  fn collect_prop_changes(&self, pending: &DeltaState) -> Vec<PropChange> {
+    let listeners = self.prop_listeners.read();
+    let delta = self.delta.read();
+    let snapshot = self.snapshot.read();
+
+    let mut deleted: Vec<NodeId> = pending.deleted_nodes.iter().copied().collect();
+    deleted.sort_unstable();
+    let mut key_ids: Vec<PropKeyId> = listeners.keys().copied().collect();
+    key_ids.sort_unstable();
+    let mut deleted_changes = Vec::new();
+    for node_id in deleted {
+      for &key_id in &key_ids {
+        if let Some(old_value) = committed_node_prop(&delta, snapshot.as_ref(), node_id, key_id) {
+          deleted_changes.push(PropChange {
+            node_id,
+            node_key: None,
+            key_id,
+            old_value: Some(old_value),
+            new_value: None,
+          });
+        }
+      }
+    }
+
+    let created = pending.created_nodes.iter().map(|entry| (entry, true));
+    let modified = pending.modified_nodes.iter().map(|entry| (entry, false));
+    let mut changes = Vec::new();
+    for ((&node_id, node_delta), is_new) in created.chain(modified) {
+      let Some(props) = node_delta.props.as_ref() else {
+        continue;
+      };
+      for (&key_id, value) in props {
+        if !listeners.contains_key(&key_id) {
+          continue;
+        }
+        let old_value = if is_new {
+          None
+        } else {
+          committed_node_prop(&delta, snapshot.as_ref(), node_id, key_id)
+        };
+        let new_value = value.as_deref().cloned();
+        if old_value == new_value {
+          continue;
+        }
+        changes.push(PropChange {
+          node_id,
+          node_key: node_delta.key.clone(),
+          key_id,
+          old_value,
+          new_value,
+        });
+      }
+    }
+    drop(snapshot);
+    drop(delta);
+    drop(listeners);
+
+    // Deleted nodes lose their key at merge, so look it up now
+    for change in &mut deleted_changes {
+      change.node_key = self.node_key(change.node_id);
+    }
+    changes.extend(deleted_changes);
+    changes
+  }
+
+  /// Hand committed prop changes to their listeners
+  fn notify_prop_changes(&self, changes: Vec<PropChange>) {
+    for mut change in changes {
+      let Some(listeners) = self.prop_listeners.read().get(&change.key_id).cloned() else {
+        continue;
+      };
+      if change.node_key.is_none() {
+        change.node_key = self.node_key(change.node_id);
+      }
+      let key_id = change.key_id;
+      change.old_value = change
+        .old_value
+        .map(|value| self.resolve_prop_value(key_id, value));
+      change.new_value = change
+        .new_value
+        .map(|value| self.resolve_prop_value(key_id, value));
+      for (_, listener) in &listeners {
+        listener(&change);
+      }
+    }
+  }
+
   /// Check if there's an active transaction
   pub fn has_transaction(&self) -> bool {
     self.current_tx_handle().is_some()
@@ -596,11 +1056,32 @@ impl SingleFileDB {
     !self.current_tx.lock().is_empty()
   }
 
+  /// Wait for commits that wrote their WAL to reach the delta
+  ///
+  /// Callers hold `commit_lock`, so no further commit can start meanwhile.
+  pub(crate) fn wait_for_applying_commits(&self) {
+    while self.applying_commits.load(Ordering::SeqCst) > 0 {
+      std::thread::sleep(Duration::from_millis(1));
+    }
+  }
+
   /// Get the current transaction ID (if any)
   pub fn current_txid(&self) -> Option<TxId> {
     self.current_tx_handle().as_ref().map(|tx| tx.lock().txid)
   }
 
+  /// Id, mode and snapshot of the current transaction (if any)
+  pub fn transaction_info(&self) -> Option<TransactionInfo> {
+    self.current_tx_handle().as_ref().map(|tx| {
+      let tx = tx.lock();
+      TransactionInfo {
+        txid: tx.txid,
+        read_only: tx.read_only,
+        snapshot_ts: tx.snapshot_ts,
+      }
+    })
+  }
+
   /// Get the most recently emitted commit token from primary replication.
   pub fn last_commit_token(&self) -> Option<CommitToken> {
     self
@@ -630,20 +1111,51 @@ impl SingleFileDB {
     tx_handle: &Arc<Mutex<SingleFileTxState>>,
     record: WalRecord,
   ) -> Result<()> {
+    self.write_rate.record(1);
     let mut tx = tx_handle.lock();
-    let record_bytes = record.build();
+    // Held-back prop sets go first so the WAL keeps the tx's write order
+    let mut records = tx.take_prop_sets();
+    if !tx.savepoints.is_empty() && !tx.bulk_load {
+      // Data records wait for commit so a savepoint rollback can drop them.
+      // Schema definitions are global as soon as they're made, so they are
+      // logged right away.
+      tx.deferred_wal.append(&mut records);
+      if !is_definition_record(record.record_type) {
+        tx.deferred_wal.push(record);
+        return Ok(());
+      }
+    }
+    records.push(record);
+    let record_bytes: Vec<u8> = records.iter().flat_map(WalRecord::build).collect();
     if tx.bulk_load {
       tx.pending_wal.extend_from_slice(&record_bytes);
       Ok(())
     } else {
       drop(tx);
-      self.write_wal(record)?;
+      for record in records {
+        self.write_wal(record)?;
+      }
       let mut tx = tx_handle.lock();
       tx.pending_wal.extend_from_slice(&record_bytes);
       Ok(())
     }
   }
 
+  /// Queue a `SetNodeProp` record, coalescing repeated sets of one prop
+  ///
+  /// Only the last value per (node, prop) reaches the WAL, written before
+  /// the tx's next record or at commit.
+  pub(crate) fn write_wal_tx_prop_set(
+    &self,
+    tx_handle: &Arc<Mutex<SingleFileTxState>>,
+    node_id: NodeId,
+    key_id: PropKeyId,
+    record: WalRecord,
+  ) {
+    self.write_rate.record(1);
+    tx_handle.lock().defer_prop_set(node_id, key_id, record);
+  }
+
   fn wait_for_group_commit(&self, seq: u64) -> Result<()> {
     let window_ms = self.group_commit_window_ms;
 
@@ -706,10 +1218,29 @@ impl SingleFileDB {
   }
 }
 
+/// Committed value of a node prop, read from the delta and then the snapshot
+fn committed_node_prop(
+  delta: &DeltaState,
+  snapshot: Option<&SnapshotData>,
+  node_id: NodeId,
+  key_id: PropKeyId,
+) -> Option<PropValue> {
+  if let Some(value) = delta
+    .node_delta(node_id)
+    .and_then(|node_delta| node_delta.props.as_ref())
+    .and_then(|props| props.get(&key_id))
+  {
+    return value.as_deref().cloned();
+  }
+  let snapshot = snapshot?;
+  snapshot.node_prop(snapshot.phys_node(node_id)?, key_id)
+}
+
 fn merge_pending_delta(target: &mut DeltaState, mut pending: DeltaState) {
   target.new_labels.extend(pending.new_labels.drain());
   target.new_etypes.extend(pending.new_etypes.drain());
   target.new_propkeys.extend(pending.new_propkeys.drain());
+  target.metadata.extend(pending.metadata.drain());
 
   for (node_id, mut node_delta) in pending.created_nodes.drain() {
     target.create_node(node_id, node_delta.key.as_deref());
@@ -739,6 +1270,14 @@ fn merge_pending_delta(target: &mut DeltaState, mut pending: DeltaState) {
   }
 
   for (node_id, mut node_delta) in pending.modified_nodes.drain() {
+    if let Some(key) = node_delta.key.take() {
+      // Index entries are merged below; only the node's own key moves here.
+      if let Some(created) = target.created_nodes.get_mut(&node_id) {
+        created.key = Some(key);
+      } else {
+        target.modified_nodes.entry(node_id).or_default().key = Some(key);
+      }
+    }
     if let Some(labels) = node_delta.labels.take() {
       for label_id in labels {
         target.add_node_label(node_id, label_id);
@@ -780,10 +1319,15 @@ fn merge_pending_delta(target: &mut DeltaState, mut pending: DeltaState) {
     }
   }
 
-  target.key_index.extend(pending.key_index.drain());
-  target
-    .key_index_deleted
-    .extend(pending.key_index_deleted.drain());
+  // A key lives in at most one of `key_index` / `key_index_deleted`.
+  for key in pending.key_index_deleted.drain() {
+    target.key_index.remove(&key);
+    target.key_index_deleted.insert(key);
+  }
+  for (key, node_id) in pending.key_index.drain() {
+    target.key_index_deleted.remove(&key);
+    target.key_index.insert(key, node_id);
+  }
 }
 
 #[cfg(test)]
@@ -814,4 +1358,582 @@ mod tests {
 
     Ok(())
   }
+
+  #[test]
+  fn transaction_info_reports_current_transaction() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("tx-info.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new())?;
+    assert_eq!(db.transaction_info(), None);
+
+    let txid = db.begin(true)?;
+    let info = db.transaction_info().expect("expected value");
+    assert_eq!(info.txid, txid);
+    assert!(info.read_only);
+    db.rollback()?;
+    assert_eq!(db.transaction_info(), None);
+
+    let txid = db.begin(false)?;
+    let info = db.transaction_info().expect("expected value");
+    assert_eq!(info.txid, txid);
+    assert!(!info.read_only);
+    db.commit()?;
+    close_single_file(db)?;
+
+    Ok(())
+  }
+
+  #[test]
+  fn nested_begin_joins_outer_transaction() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("tx-nested.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new())?;
+
+    let outer = db.begin(false)?;
+    let inner = db.begin(false)?;
+    assert_eq!(inner, outer);
+    db.create_node(Some("inner"))?;
+    db.commit()?;
+    assert_eq!(db.current_txid(), Some(outer));
+    db.create_node(Some("outer"))?;
+    db.commit()?;
+
+    assert!(!db.has_transaction());
+    assert!(db.node_by_key("inner").is_some());
+    assert!(db.node_by_key("outer").is_some());
+    close_single_file(db)?;
+
+    Ok(())
+  }
+
+  #[test]
+  fn nested_rollback_makes_outer_commit_fail() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("tx-rollback-only.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new())?;
+
+    db.begin(false)?;
+    db.create_node(Some("outer"))?;
+    db.begin(false)?;
+    db.create_node(Some("inner"))?;
+    db.rollback()?;
+    assert!(db.has_transaction());
+
+    assert!(matches!(db.commit(), Err(KiteError::RollbackOnly)));
+    assert!(!db.has_transaction());
+    assert!(db.node_by_key("outer").is_none());
+    assert!(db.node_by_key("inner").is_none());
+
+    db.begin(true)?;
+    assert!(matches!(db.begin(false), Err(KiteError::ReadOnly)));
+    db.commit()?;
+    close_single_file(db)?;
+
+    Ok(())
+  }
+
+  #[test]
+  fn rollback_to_savepoint_keeps_earlier_writes() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("tx-savepoint.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new())?;
+    let before = PropValue::String("before".into());
+
+    db.begin(false)?;
+    let outer = db.create_node(Some("outer"))?;
+    let name = db.define_propkey("name")?;
+    db.set_node_prop(outer, name, before.clone())?;
+    db.savepoint("inner")?;
+    db.create_node(Some("inner"))?;
+    db.set_node_prop(outer, name, PropValue::String("after".into()))?;
+    let tag = db.define_propkey("tag")?;
+    db.rollback_to_savepoint("inner")?;
+
+    assert!(db.has_transaction());
+    assert!(db.node_by_key("inner").is_none());
+    assert_eq!(db.node_prop(outer, name), Some(before.clone()));
+    assert!(matches!(
+      db.rollback_to_savepoint("missing"),
+      Err(KiteError::UnknownSavepoint(_))
+    ));
+
+    db.set_node_prop(outer, tag, PropValue::I64(1))?;
+    db.commit()?;
+
+    // Reopen without closing so the committed WAL records are replayed
+    drop(db);
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new())?;
+    assert!(db.node_by_key("inner").is_none());
+    let outer = db.node_by_key("outer").expect("outer committed");
+    assert_eq!(db.node_prop(outer, name), Some(before));
+    assert_eq!(db.node_prop(outer, tag), Some(PropValue::I64(1)));
+    close_single_file(db)?;
+
+    Ok(())
+  }
+
+  #[test]
+  fn repeated_prop_sets_are_logged_once() -> Result<()> {
+    use crate::core::single_file::recovery::scan_wal_records;
+
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("tx-coalesce.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new())?;
+
+    let txid = db.begin(false)?;
+    let node = db.create_node(Some("n"))?;
+    let key = db.define_propkey("v")?;
+    let other = db.define_propkey("w")?;
+    for i in 0..1000 {
+      db.set_node_prop(node, key, PropValue::I64(i))?;
+    }
+    // A delete in between must stay ordered after the sets before it
+    db.set_node_prop(node, other, PropValue::I64(1))?;
+    db.delete_node_prop(node, other)?;
+    db.commit()?;
+
+    let (records, _) = scan_wal_records(&mut db.pager.lock(), &db.header.read())?;
+    let prop_sets = records
+      .iter()
+      .filter(|r| r.txid == txid && r.record_type == WalRecordType::SetNodeProp)
+      .count();
+    assert_eq!(prop_sets, 2);
+    close_single_file(db)?;
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new())?;
+    let node = db.node_by_key("n").expect("node persisted");
+    let key = db.propkey_id("v").expect("propkey persisted");
+    let other = db.propkey_id("w").expect("propkey persisted");
+    assert_eq!(db.node_prop(node, key), Some(PropValue::I64(999)));
+    assert_eq!(db.node_prop(node, other), None);
+    close_single_file(db)?;
+
+    Ok(())
+  }
+
+  #[test]
+  fn commit_stats_summarizes_transaction() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("tx-commit-stats.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new())?;
+
+    db.begin(false)?;
+    let a = db.create_node(Some("a"))?;
+    let b = db.create_node(Some("b"))?;
+    let c = db.create_node(Some("c"))?;
+    let knows = db.define_etype("KNOWS")?;
+    let name = db.define_propkey("name")?;
+    db.add_edge(a, knows, b)?;
+    db.add_edge(b, knows, c)?;
+    db.commit()?;
+
+    let wal_head = db.header.read().wal_head;
+    db.begin(false)?;
+    let d = db.create_node(Some("d"))?;
+    db.delete_node(c)?;
+    db.delete_edge(a, knows, b)?;
+    db.add_edge(a, knows, d)?;
+    db.set_node_prop(a, name, PropValue::String("Alice".into()))?;
+    db.set_node_prop(d, name, PropValue::String("Dee".into()))?;
+    db.set_edge_prop(a, knows, d, name, PropValue::I64(1))?;
+    let stats = db.commit_stats()?;
+
+    assert_eq!(stats.nodes_created, 1);
+    assert_eq!(stats.nodes_deleted, 1);
+    assert_eq!(stats.edges_added, 1);
+    assert_eq!(stats.edges_deleted, 1);
+    assert_eq!(stats.props_set, 3);
+    assert_eq!(
+      stats.wal_bytes_written,
+      db.header.read().wal_head - wal_head
+    );
+
+    // Closing a nested level commits nothing
+    db.begin(false)?;
+    db.begin(false)?;
+    db.set_node_prop(b, name, PropValue::String("Bea".into()))?;
+    assert_eq!(db.commit_stats()?, CommitStats::default());
+    assert_eq!(db.commit_stats()?.props_set, 1);
+    close_single_file(db)?;
+
+    Ok(())
+  }
+
+  #[test]
+  fn begin_read_at_serves_past_generation() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("tx-read-at.kitedb");
+    let options = SingleFileOpenOptions::new()
+      .mvcc(true)
+      .mvcc_gc_interval_ms(10)
+      .mvcc_retention_ms(60_000)
+      .mvcc_retain_history(true);
+    let db = open_single_file(&db_path, options)?;
+
+    db.begin(false)?;
+    let a = db.create_node(Some("a"))?;
+    let key = db.define_propkey("v")?;
+    db.set_node_prop(a, key, PropValue::I64(1))?;
+    db.commit()?;
+    let gen1 = db.current_generation().expect("mvcc enabled");
+
+    db.begin(false)?;
+    db.set_node_prop(a, key, PropValue::I64(2))?;
+    let b = db.create_node(Some("b"))?;
+    db.commit()?;
+
+    db.begin_read_at(gen1)?;
+    assert_eq!(db.node_prop(a, key), Some(PropValue::I64(1)));
+    assert!(!db.node_exists(b));
+    db.commit()?;
+
+    assert_eq!(db.node_prop(a, key), Some(PropValue::I64(2)));
+    assert!(db.node_exists(b));
+
+    let future = db.current_generation().expect("mvcc enabled") + 1;
+    assert!(matches!(
+      db.begin_read_at(future),
+      Err(KiteError::GenerationUnavailable { .. })
+    ));
+    close_single_file(db)?;
+
+    Ok(())
+  }
+
+  #[test]
+  fn begin_read_at_rejects_unretained_generation() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("tx-read-at-pruned.kitedb");
+    let options = SingleFileOpenOptions::new()
+      .mvcc(true)
+      .mvcc_gc_interval_ms(10)
+      .mvcc_retention_ms(60_000);
+    let db = open_single_file(&db_path, options)?;
+
+    let gen0 = db.current_generation().expect("mvcc enabled");
+    db.begin(false)?;
+    db.create_node(Some("a"))?;
+    db.commit()?;
+
+    // A retention window alone does not record history: without
+    // `mvcc_retain_history`, commits no reader could observe keep no versions.
+    assert!(db.oldest_readable_generation().expect("mvcc enabled") > gen0);
+    assert!(matches!(
+      db.begin_read_at(gen0),
+      Err(KiteError::GenerationUnavailable { generation, .. }) if generation == gen0
+    ));
+    assert!(!db.has_transaction());
+
+    let latest = db.current_generation().expect("mvcc enabled");
+    db.begin_read_at(latest)?;
+    assert!(db.node_by_key("a").is_some());
+    db.commit()?;
+    close_single_file(db)?;
+
+    Ok(())
+  }
+
+  #[test]
+  fn write_order_does_not_change_conflict_outcome() -> Result<()> {
+    use std::sync::mpsc;
+    use std::thread;
+
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("tx-write-order.kitedb");
+    let options = SingleFileOpenOptions::new()
+      .mvcc(true)
+      .mvcc_gc_interval_ms(10);
+    let db = Arc::new(open_single_file(&db_path, options)?);
+
+    db.begin(false)?;
+    let a = db.create_node(Some("a"))?;
+    let b = db.create_node(Some("b"))?;
+    let key_id = db.define_propkey("n")?;
+    db.commit()?;
+
+    // Both transactions write a and b; the one that commits second loses,
+    // whatever order either of them wrote the keys in.
+    let race = |second_order: [NodeId; 2]| -> Vec<String> {
+      let (ready_tx, ready_rx) = mpsc::channel();
+      let (cont_tx, cont_rx) = mpsc::channel();
+      let first = Arc::clone(&db);
+      let handle = thread::spawn(move || {
+        first.begin(false).expect("expected value");
+        for node_id in [a, b] {
+          first
+            .set_node_prop(node_id, key_id, PropValue::I64(1))
+            .expect("expected value");
+        }
+        ready_tx.send(()).expect("expected value");
+        cont_rx.recv().expect("expected value");
+        first.commit().expect("first committer wins");
+      });
+
+      ready_rx.recv().expect("expected value");
+      db.begin(false).expect("expected value");
+      for node_id in second_order {
+        db.set_node_prop(node_id, key_id, PropValue::I64(2))
+          .expect("expected value");
+      }
+      cont_tx.send(()).expect("expected value");
+      handle.join().expect("expected value");
+      match db.commit() {
+        Err(KiteError::Conflict { keys, .. }) => keys,
+        other => panic!("expected conflict, got {other:?}"),
+      }
+    };
+
+    let same_order = race([a, b]);
+    let opposite_order = race([b, a]);
+    assert_eq!(same_order.len(), 2);
+    assert_eq!(same_order, opposite_order);
+
+    let db = match Arc::try_unwrap(db) {
+      Ok(db) => db,
+      Err(_) => panic!("single owner"),
+    };
+    close_single_file(db)?;
+
+    Ok(())
+  }
+
+  #[test]
+  fn commit_without_readers_is_not_shadowed_by_older_versions() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("tx-stale-versions.kitedb");
+    let options = SingleFileOpenOptions::new().mvcc(true);
+    let db = open_single_file(&db_path, options)?;
+
+    db.begin(false)?;
+    let a = db.create_node(Some("a"))?;
+    let key_id = db.define_propkey("n")?;
+    db.commit()?;
+
+    // An open reader on another thread makes this commit keep a version.
+    std::thread::scope(|scope| {
+      let (ready_tx, ready_rx) = std::sync::mpsc::channel();
+      let (done_tx, done_rx) = std::sync::mpsc::channel::<()>();
+      let db = &db;
+      let reader = scope.spawn(move || {
+        db.begin(true).expect("expected value");
+        ready_tx.send(()).expect("expected value");
+        done_rx.recv().expect("expected value");
+        db.rollback().expect("expected value");
+      });
+      ready_rx.recv().expect("expected value");
+      db.begin(false).expect("expected value");
+      db.set_node_prop(a, key_id, PropValue::I64(1))
+        .expect("expected value");
+      db.commit().expect("expected value");
+      done_tx.send(()).expect("expected value");
+      reader.join().expect("expected value");
+    });
+
+    db.begin(false)?;
+    db.set_node_prop(a, key_id, PropValue::I64(2))?;
+    db.commit()?;
+    assert_eq!(db.node_prop(a, key_id), Some(PropValue::I64(2)));
+
+    close_single_file(db)?;
+    Ok(())
+  }
+
+  #[test]
+  fn clearing_versions_keeps_a_commit_not_yet_in_the_delta() -> Result<()> {
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("tx-clear-in-flight.kitedb");
+    let options = SingleFileOpenOptions::new().mvcc(true);
+    let db = open_single_file(&db_path, options)?;
+
+    db.begin(false)?;
+    let a = db.create_node(Some("a"))?;
+    let b = db.create_node(Some("b"))?;
+    let key_id = db.define_propkey("n")?;
+    db.set_node_prop(a, key_id, PropValue::I64(0))?;
+    db.set_node_prop(b, key_id, PropValue::I64(0))?;
+    db.commit()?;
+
+    let mvcc = db.mvcc.as_ref().expect("expected value");
+    let next_commit_ts = || mvcc.tx_manager.lock().next_commit_ts();
+    let wait_for_commit_ts = |ts: Timestamp| {
+      while next_commit_ts() <= ts {
+        std::thread::yield_now();
+      }
+    };
+
+    // The first writer commits while a reader is open, so it keeps versions,
+    // but stalls before the delta. The second commits once the reader is
+    // gone and clears the version chain in the meantime.
+    std::thread::scope(|scope| {
+      let db = &db;
+      let (reader_ready, reader_ready_rx) = std::sync::mpsc::channel();
+      let (reader_done, reader_done_rx) = std::sync::mpsc::channel::<()>();
+      let reader = scope.spawn(move || {
+        db.begin(true).expect("expected value");
+        reader_ready.send(()).expect("expected value");
+        reader_done_rx.recv().expect("expected value");
+        db.rollback().expect("expected value");
+      });
+      reader_ready_rx.recv().expect("expected value");
+
+      let (first_ready, first_ready_rx) = std::sync::mpsc::channel();
+      let (first_go, first_go_rx) = std::sync::mpsc::channel::<()>();
+      let first = scope.spawn(move || {
+        db.begin(false).expect("expected value");
+        db.set_node_prop(a, key_id, PropValue::I64(1))
+          .expect("expected value");
+        first_ready.send(()).expect("expected value");
+        first_go_rx.recv().expect("expected value");
+        db.commit().expect("expected value");
+      });
+      let (second_ready, second_ready_rx) = std::sync::mpsc::channel();
+      let (second_go, second_go_rx) = std::sync::mpsc::channel::<()>();
+      let second = scope.spawn(move || {
+        db.begin(false).expect("expected value");
+        db.set_node_prop(b, key_id, PropValue::I64(1))
+          .expect("expected value");
+        second_ready.send(()).expect("expected value");
+        second_go_rx.recv().expect("expected value");
+        db.commit().expect("expected value");
+      });
+      first_ready_rx.recv().expect("expected value");
+      second_ready_rx.recv().expect("expected value");
+
+      let delta = db.delta.write();
+      let first_ts = next_commit_ts();
+      first_go.send(()).expect("expected value");
+      wait_for_commit_ts(first_ts);
+      reader_done.send(()).expect("expected value");
+      reader.join().expect("expected value");
+      second_go.send(()).expect("expected value");
+      wait_for_commit_ts(first_ts + 1);
+      drop(delta);
+
+      first.join().expect("expected value");
+      second.join().expect("expected value");
+    });
+
+    assert_eq!(db.node_prop(a, key_id), Some(PropValue::I64(1)));
+    assert_eq!(db.node_prop(b, key_id), Some(PropValue::I64(1)));
+    db.begin(true)?;
+    assert_eq!(db.node_prop(a, key_id), Some(PropValue::I64(1)));
+    assert_eq!(db.node_prop(b, key_id), Some(PropValue::I64(1)));
+    db.rollback()?;
+
+    close_single_file(db)?;
+    Ok(())
+  }
+
+  #[test]
+  fn concurrent_increments_are_not_lost() -> Result<()> {
+    const THREADS: i64 = 4;
+    const INCREMENTS: i64 = 25;
+
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("tx-lost-update.kitedb");
+    let options = SingleFileOpenOptions::new().mvcc(true);
+    let db = open_single_file(&db_path, options)?;
+
+    db.begin(false)?;
+    let a = db.create_node(Some("a"))?;
+    let key_id = db.define_propkey("n")?;
+    db.set_node_prop(a, key_id, PropValue::I64(0))?;
+    db.commit()?;
+
+    // Each increment is a read-modify-write; a transaction that read a value
+    // another commit has since replaced must conflict and retry.
+    std::thread::scope(|scope| {
+      for _ in 0..THREADS {
+        let db = &db;
+        scope.spawn(move || {
+          for _ in 0..INCREMENTS {
+            loop {
+              db.begin(false).expect("expected value");
+              let current = match db.node_prop(a, key_id) {
+                Some(PropValue::I64(n)) => n,
+                other => panic!("unexpected value {other:?}"),
+              };
+              db.set_node_prop(a, key_id, PropValue::I64(current + 1))
+                .expect("expected value");
+              match db.commit() {
+                Ok(()) => break,
+                Err(KiteError::Conflict { .. }) => continue,
+                Err(err) => panic!("unexpected error {err}"),
+              }
+            }
+          }
+        });
+      }
+    });
+
+    assert_eq!(
+      db.node_prop(a, key_id),
+      Some(PropValue::I64(THREADS * INCREMENTS))
+    );
+    close_single_file(db)?;
+    Ok(())
+  }
+
+  #[test]
+  fn write_write_conflict_names_category_and_winner() -> Result<()> {
+    use crate::mvcc::ConflictType;
+    use std::sync::mpsc;
+    use std::thread;
+
+    let temp_dir = tempdir()?;
+    let db_path = temp_dir.path().join("tx-conflict-details.kitedb");
+    let options = SingleFileOpenOptions::new()
+      .mvcc(true)
+      .mvcc_gc_interval_ms(10);
+    let db = Arc::new(open_single_file(&db_path, options)?);
+
+    db.begin(false)?;
+    let a = db.create_node(Some("a"))?;
+    let key_id = db.define_propkey("n")?;
+    db.commit()?;
+
+    let (ready_tx, ready_rx) = mpsc::channel();
+    let (cont_tx, cont_rx) = mpsc::channel();
+    let first = Arc::clone(&db);
+    let handle = thread::spawn(move || {
+      let txid = first.begin(false).expect("expected value");
+      first
+        .set_node_prop(a, key_id, PropValue::I64(1))
+        .expect("expected value");
+      ready_tx.send(txid).expect("expected value");
+      cont_rx.recv().expect("expected value");
+      first.commit().expect("first committer wins");
+    });
+
+    let winner = ready_rx.recv().expect("expected value");
+    let loser = db.begin(false)?;
+    db.set_node_prop(a, key_id, PropValue::I64(2))?;
+    cont_tx.send(()).expect("expected value");
+    handle.join().expect("expected value");
+
+    let err = db.commit().expect_err("second committer conflicts");
+    assert!(err.is_conflict());
+    let expected_key = TxKey::NodeProp { node_id: a, key_id }.to_string();
+    assert_eq!(err.conflict_keys(), Some(&[expected_key.clone()][..]));
+    let KiteError::Conflict { txid, details, .. } = &err else {
+      panic!("expected conflict, got {err:?}");
+    };
+    assert_eq!(*txid, loser);
+    assert_eq!(details.len(), 1);
+    assert_eq!(details[0].key, expected_key);
+    assert_eq!(details[0].category, "node_prop");
+    assert_eq!(details[0].conflict_type, ConflictType::WriteWrite);
+    assert_eq!(details[0].conflicting_txid, winner);
+    assert_eq!(err.conflict_details(), Some(&details[..]));
+    assert!(err.to_string().contains(&format!("won by tx {winner}")));
+    assert!(!db.has_transaction());
+
+    let db = match Arc::try_unwrap(db) {
+      Ok(db) => db,
+      Err(_) => panic!("single owner"),
+    };
+    close_single_file(db)?;
+
+    Ok(())
+  }
 }
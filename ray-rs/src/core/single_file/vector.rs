@@ -9,18 +9,32 @@ use crate::core::wal::record::{
 use crate::error::{KiteError, Result};
 use crate::types::*;
 use crate::util::binary::{read_u32, read_u64};
-use crate::vector::ivf::serialize::deserialize_manifest;
 use crate::util::binary::{read_u32_at, read_u64_at};
+use crate::vector::distance::{dot_product, is_normalized, normalize};
+use crate::vector::ivf::serialize::deserialize_manifest;
 use crate::vector::store::{
   create_vector_store, validate_vector, vector_store_delete, vector_store_has, vector_store_insert,
   vector_store_node_vector,
 };
-use crate::vector::types::{VectorManifest, VectorStoreConfig};
+use crate::vector::types::{DistanceMetric, VectorLocation, VectorManifest, VectorStoreConfig};
 use std::collections::HashMap;
 use std::sync::Arc;
 
 use super::SingleFileDB;
 
+/// L2-norm tolerance when deciding whether a stored vector is unit length
+const NORMALIZED_TOLERANCE: f32 = 1e-4;
+
+/// A node vector ranked by [`SingleFileDB::search_node_vectors`]
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NodeVectorHit {
+  pub node_id: NodeId,
+  /// Distance to the query (lower is closer)
+  pub distance: f32,
+  /// Similarity score (higher is more similar)
+  pub similarity: f32,
+}
+
 #[derive(Debug, Clone)]
 pub(crate) struct VectorStoreLazyEntry {
   pub(crate) offset: usize,
@@ -126,6 +140,13 @@ impl SingleFileDB {
 
     // Validate vector before WAL write / queuing pending ops.
     validate_vector(vector).map_err(|e| KiteError::InvalidQuery(e.to_string().into()))?;
+    let normalized;
+    let vector = if self.is_vector_normalized(prop_key_id) {
+      normalized = normalize(vector);
+      &normalized[..]
+    } else {
+      vector
+    };
 
     // Write WAL record
     let record = WalRecord::new(
@@ -147,6 +168,194 @@ impl SingleFileDB {
     Ok(())
   }
 
+  /// Keep every vector written under `prop_key_id` unit length
+  ///
+  /// While set, [`Self::set_node_vector`] and vector-valued
+  /// [`Self::set_node_prop`] writes under the key are L2-normalized, and
+  /// cosine [`Self::search_node_vectors`] scores candidates by a dot product
+  /// with the normalized query instead of computing their norms. The flag
+  /// isn't persisted: `Kite` sets it on open for vector props declared
+  /// `normalized`. Fix up vectors written before it was set with
+  /// [`Self::renormalize_vectors`].
+  pub fn set_vector_normalized(&self, prop_key_id: PropKeyId, normalized: bool) {
+    let mut keys = self.normalized_vector_keys.write();
+    if normalized {
+      keys.insert(prop_key_id);
+    } else {
+      keys.remove(&prop_key_id);
+    }
+  }
+
+  /// Whether vectors under `prop_key_id` are kept unit length
+  pub fn is_vector_normalized(&self, prop_key_id: PropKeyId) -> bool {
+    self.normalized_vector_keys.read().contains(&prop_key_id)
+  }
+
+  /// Set a vector embedding for a node, L2-normalizing it on write
+  ///
+  /// Cosine search treats stored vectors as unit length, so normalizing once
+  /// here keeps reads inside the transaction, the WAL, and the committed
+  /// store consistent. Mixing normalized and unnormalized vectors under the
+  /// same property key is unsupported; use [`Self::renormalize_vectors`] to
+  /// fix up existing data.
+  pub fn set_node_vector_normalized(
+    &self,
+    node_id: NodeId,
+    prop_key_id: PropKeyId,
+    vector: &[f32],
+  ) -> Result<()> {
+    validate_vector(vector).map_err(|e| KiteError::InvalidQuery(e.to_string().into()))?;
+    self.set_node_vector(node_id, prop_key_id, &normalize(vector))
+  }
+
+  /// Patch individual dimensions of an existing vector
+  ///
+  /// `updates` holds `(index, value)` pairs; every other dimension is kept.
+  /// Indices address the vector as currently stored, which cosine stores
+  /// have already normalized.
+  /// The patched vector is written through the WAL like a full
+  /// [`Self::set_node_vector`]. Errors if the node has no vector under
+  /// `prop_key_id` or an index is out of range.
+  pub fn update_node_vector_dims(
+    &self,
+    node_id: NodeId,
+    prop_key_id: PropKeyId,
+    updates: &[(usize, f32)],
+  ) -> Result<()> {
+    self.require_write_tx_handle()?;
+    let Some(current) = self.node_vector(node_id, prop_key_id) else {
+      return Err(KiteError::InvalidQuery(
+        format!("node {node_id} has no vector for prop key {prop_key_id}").into(),
+      ));
+    };
+
+    let mut vector = current.to_vec();
+    for &(index, value) in updates {
+      let Some(slot) = vector.get_mut(index) else {
+        return Err(KiteError::InvalidQuery(
+          format!(
+            "vector dimension {index} out of range (dimensions: {})",
+            current.len()
+          )
+          .into(),
+        ));
+      };
+      *slot = value;
+    }
+
+    self.set_node_vector(node_id, prop_key_id, &vector)
+  }
+
+  /// Rewrite every vector under `prop_key_id` that isn't unit length
+  ///
+  /// Maintenance op for data written before normalization was enabled.
+  /// Requires an active write transaction; returns the number of vectors
+  /// rewritten.
+  pub fn renormalize_vectors(&self, prop_key_id: PropKeyId) -> Result<usize> {
+    self.require_write_tx_handle()?;
+
+    let mut rewritten = 0;
+    for node_id in self.vector_candidate_ids(prop_key_id)? {
+      let Some(vector) = self.node_vector(node_id, prop_key_id) else {
+        continue;
+      };
+      if is_normalized(&vector, NORMALIZED_TOLERANCE) {
+        continue;
+      }
+      self.set_node_vector(node_id, prop_key_id, &normalize(&vector))?;
+      rewritten += 1;
+    }
+
+    Ok(rewritten)
+  }
+
+  /// Find the `k` node vectors under `prop_key_id` nearest to `query`
+  ///
+  /// Brute force over the same view as [`Self::node_vector`], so inside a
+  /// transaction the vectors it set or deleted are searched as if committed.
+  /// Uses the store's metric, or cosine before any vector under the key has
+  /// been committed. Results are nearest first. Cosine search over a key
+  /// marked with [`Self::set_vector_normalized`] skips the per-vector norms.
+  pub fn search_node_vectors(
+    &self,
+    prop_key_id: PropKeyId,
+    query: &[f32],
+    k: usize,
+  ) -> Result<Vec<NodeVectorHit>> {
+    let node_ids = self.vector_candidate_ids(prop_key_id)?;
+    let metric = self
+      .vector_stores
+      .read()
+      .get(&prop_key_id)
+      .map(|store| store.config.metric)
+      .unwrap_or(DistanceMetric::Cosine);
+    let unit_query;
+    let (query, distance_fn) =
+      if metric == DistanceMetric::Cosine && self.is_vector_normalized(prop_key_id) {
+        // Both sides are unit length, so cosine distance is 1 - dot
+        unit_query = normalize(query);
+        let unit_cosine: fn(&[f32], &[f32]) -> f32 = |a, b| 1.0 - dot_product(a, b);
+        (&unit_query[..], unit_cosine)
+      } else {
+        (query, metric.distance_fn())
+      };
+
+    let mut hits = Vec::new();
+    for node_id in node_ids {
+      let Some(vector) = self.node_vector(node_id, prop_key_id) else {
+        continue;
+      };
+      if vector.len() != query.len() {
+        return Err(KiteError::VectorDimensionMismatch {
+          expected: vector.len(),
+          got: query.len(),
+        });
+      }
+      let distance = distance_fn(query, &vector);
+      hits.push(NodeVectorHit {
+        node_id,
+        distance,
+        similarity: metric.distance_to_similarity(distance),
+      });
+    }
+
+    hits.sort_by(|a, b| {
+      a.distance
+        .total_cmp(&b.distance)
+        .then(a.node_id.cmp(&b.node_id))
+    });
+    hits.truncate(k);
+    Ok(hits)
+  }
+
+  /// Nodes that may have a vector under `prop_key_id`: those in the committed
+  /// store plus any set by replayed or current-transaction writes. Deletes
+  /// aren't applied; check each with [`Self::node_vector`].
+  fn vector_candidate_ids(&self, prop_key_id: PropKeyId) -> Result<Vec<NodeId>> {
+    self.ensure_vector_store_loaded(prop_key_id)?;
+
+    let mut node_ids: Vec<NodeId> = self
+      .vector_stores
+      .read()
+      .get(&prop_key_id)
+      .map(|store| store.node_to_vector.keys().copied().collect())
+      .unwrap_or_default();
+    let pending_ids = |pending: &HashMap<(NodeId, PropKeyId), Option<VectorRef>>| {
+      pending
+        .iter()
+        .filter(|(&(_, key_id), op)| key_id == prop_key_id && op.is_some())
+        .map(|(&(node_id, _), _)| node_id)
+        .collect::<Vec<_>>()
+    };
+    node_ids.extend(pending_ids(&self.delta.read().pending_vectors));
+    if let Some(tx_handle) = self.current_tx_handle() {
+      node_ids.extend(pending_ids(&tx_handle.lock().pending.pending_vectors));
+    }
+    node_ids.sort_unstable();
+    node_ids.dedup();
+    Ok(node_ids)
+  }
+
   /// Delete a vector embedding for a node
   ///
   /// Returns Ok(()) even if the vector doesn't exist (idempotent).
@@ -275,6 +484,48 @@ impl SingleFileDB {
     Ok(())
   }
 
+  /// Describe every vector store, loading any still-lazy ones
+  ///
+  /// Sorted by property name.
+  pub fn list_vector_indexes(&self) -> Result<Vec<IndexInfo>> {
+    self.materialize_all_vector_stores()?;
+
+    // node_to_vector, vector_to_node and vector_locations entries per vector
+    let map_entry_bytes = 2 * (std::mem::size_of::<NodeId>() + std::mem::size_of::<u64>())
+      + std::mem::size_of::<u64>()
+      + std::mem::size_of::<VectorLocation>();
+    let mut indexes: Vec<IndexInfo> = self
+      .vector_stores
+      .read()
+      .iter()
+      .map(|(&prop_key_id, store)| {
+        let data_bytes: usize = store
+          .fragments
+          .iter()
+          .map(|fragment| {
+            let rows: usize = fragment
+              .row_groups
+              .iter()
+              .map(|group| group.data.len() * std::mem::size_of::<f32>())
+              .sum();
+            rows + fragment.deletion_bitmap.len() * std::mem::size_of::<u32>()
+          })
+          .sum();
+        IndexInfo {
+          kind: IndexKind::Vector,
+          node_type: None,
+          prop_name: self
+            .propkey_name(prop_key_id)
+            .unwrap_or_else(|| format!("#{prop_key_id}")),
+          entries: store.live_count() as u64,
+          estimated_bytes: (data_bytes + store.live_count() * map_entry_bytes) as u64,
+        }
+      })
+      .collect();
+    indexes.sort_by(|a, b| a.prop_name.cmp(&b.prop_name));
+    Ok(indexes)
+  }
+
   /// Apply pending vector operations (called during commit)
   pub(crate) fn apply_pending_vectors(
     &self,
@@ -470,6 +721,48 @@ pub(crate) fn materialize_vector_store_from_lazy_entries(
   Ok(())
 }
 
+/// Apply the vector writes collected by WAL replay to the stores loaded from
+/// `snapshot`, draining them from `delta`
+pub(crate) fn apply_replayed_vectors(
+  snapshot: Option<&SnapshotData>,
+  vector_stores: &mut HashMap<PropKeyId, VectorManifest>,
+  lazy_entries: &mut HashMap<PropKeyId, VectorStoreLazyEntry>,
+  delta: &mut DeltaState,
+) -> Result<()> {
+  for ((node_id, prop_key_id), operation) in delta.pending_vectors.drain() {
+    if let Some(snapshot) = snapshot {
+      materialize_vector_store_from_lazy_entries(
+        snapshot,
+        vector_stores,
+        lazy_entries,
+        prop_key_id,
+      )?;
+    }
+
+    match operation {
+      Some(vector) => {
+        // Get or create vector store
+        let store = vector_stores.entry(prop_key_id).or_insert_with(|| {
+          let config = VectorStoreConfig::new(vector.len());
+          create_vector_store(config)
+        });
+        vector_store_insert(store, node_id, vector.as_ref()).map_err(|e| {
+          KiteError::InvalidWal(format!(
+            "Failed to apply vector insert during WAL replay for node {node_id} (prop {prop_key_id}): {e}"
+          ))
+        })?;
+      }
+      None => {
+        // Delete operation
+        if let Some(store) = vector_stores.get_mut(&prop_key_id) {
+          vector_store_delete(store, node_id);
+        }
+      }
+    }
+  }
+  Ok(())
+}
+
 fn vector_stores_from_sections(
   snapshot: &SnapshotData,
 ) -> Result<HashMap<PropKeyId, VectorManifest>> {
@@ -623,7 +916,9 @@ fn decode_vector_payload(
 
 #[cfg(test)]
 mod tests {
-  use super::{decode_vector_payload, vector_store_state_from_snapshot, vector_stores_from_snapshot};
+  use super::{
+    decode_vector_payload, vector_store_state_from_snapshot, vector_stores_from_snapshot,
+  };
   use crate::core::single_file::{close_single_file, open_single_file, SingleFileOpenOptions};
   use crate::core::snapshot::reader::SnapshotData;
   use crate::core::snapshot::writer::{build_snapshot_to_memory, NodeData, SnapshotBuildInput};
@@ -682,6 +977,184 @@ mod tests {
     close_single_file(db).expect("expected value");
   }
 
+  #[test]
+  fn test_normalized_vectors_and_renormalize() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("normalize-vectors.kitedb");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    db.begin(false).expect("expected value");
+    let a = db.create_node(None).expect("expected value");
+    let b = db.create_node(None).expect("expected value");
+    let prop_key_id = db.define_propkey("embedding").expect("expected value");
+
+    db.set_node_vector_normalized(a, prop_key_id, &[3.0, 4.0])
+      .expect("expected value");
+    let vec = db.node_vector(a, prop_key_id).expect("expected value");
+    assert!((vec[0] - 0.6).abs() < 1e-6 && (vec[1] - 0.8).abs() < 1e-6);
+    assert!(db
+      .set_node_vector_normalized(a, prop_key_id, &[0.0, 0.0])
+      .is_err());
+
+    db.set_node_vector(b, prop_key_id, &[0.0, 2.0])
+      .expect("expected value");
+    assert_eq!(
+      db.renormalize_vectors(prop_key_id).expect("expected value"),
+      1
+    );
+    let vec = db.node_vector(b, prop_key_id).expect("expected value");
+    assert_eq!(vec.as_ref(), &[0.0, 1.0]);
+    assert_eq!(
+      db.renormalize_vectors(prop_key_id).expect("expected value"),
+      0
+    );
+    db.commit().expect("expected value");
+
+    assert!(db.renormalize_vectors(prop_key_id).is_err());
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_normalized_vector_key_normalizes_writes_and_search() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("normalized-key.kitedb");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    db.begin(false).expect("expected value");
+    let a = db.create_node(None).expect("expected value");
+    let b = db.create_node(None).expect("expected value");
+    let plain = db.define_propkey("plain").expect("expected value");
+    let unit = db.define_propkey("unit").expect("expected value");
+    db.set_vector_normalized(unit, true);
+    assert!(db.is_vector_normalized(unit) && !db.is_vector_normalized(plain));
+
+    for (node_id, vector) in [(a, [3.0, 4.0]), (b, [8.0, 6.0])] {
+      db.set_node_vector(node_id, unit, &vector)
+        .expect("expected value");
+    }
+    let vec = db.node_vector(a, unit).expect("expected value");
+    assert!((vec[0] - 0.6).abs() < 1e-6 && (vec[1] - 0.8).abs() < 1e-6);
+    db.set_node_prop(a, unit, PropValue::VectorF32(vec![0.0, 2.0]))
+      .expect("expected value");
+    assert_eq!(
+      db.node_prop(a, unit),
+      Some(PropValue::VectorF32(vec![0.0, 1.0]))
+    );
+    db.commit().expect("expected value");
+
+    // The dot-product path scores like full cosine, for any query length
+    let query = [2.0_f32, 1.0];
+    let query_norm = query.iter().map(|x| x * x).sum::<f32>().sqrt();
+    let hits = db
+      .search_node_vectors(unit, &query, 2)
+      .expect("expected value");
+    let expected = [(b, [0.8, 0.6]), (a, [0.6, 0.8])];
+    assert_eq!(hits.len(), expected.len());
+    for (hit, (node_id, unit_vec)) in hits.iter().zip(expected) {
+      let cosine = (query[0] * unit_vec[0] + query[1] * unit_vec[1]) / query_norm;
+      assert_eq!(hit.node_id, node_id);
+      assert!((hit.distance - (1.0 - cosine)).abs() < 1e-5);
+    }
+
+    db.set_vector_normalized(unit, false);
+    assert!(!db.is_vector_normalized(unit));
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_search_node_vectors_sees_pending_transaction_writes() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("search-vectors.kitedb");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    db.begin(false).expect("expected value");
+    let a = db.create_node(None).expect("expected value");
+    let b = db.create_node(None).expect("expected value");
+    let prop_key_id = db.define_propkey("embedding").expect("expected value");
+    db.set_node_vector(a, prop_key_id, &[1.0, 0.0])
+      .expect("expected value");
+    db.commit().expect("expected value");
+
+    db.begin(false).expect("expected value");
+    db.set_node_vector(b, prop_key_id, &[0.0, 1.0])
+      .expect("expected value");
+    let hits = db
+      .search_node_vectors(prop_key_id, &[0.1, 1.0], 2)
+      .expect("expected value");
+    assert_eq!(
+      hits.iter().map(|hit| hit.node_id).collect::<Vec<_>>(),
+      vec![b, a]
+    );
+
+    db.delete_node_vector(b, prop_key_id)
+      .expect("expected value");
+    let hits = db
+      .search_node_vectors(prop_key_id, &[0.1, 1.0], 2)
+      .expect("expected value");
+    assert_eq!(
+      hits.iter().map(|hit| hit.node_id).collect::<Vec<_>>(),
+      vec![a]
+    );
+    assert!(db.search_node_vectors(prop_key_id, &[1.0], 1).is_err());
+    db.rollback().expect("expected value");
+
+    db.begin(false).expect("expected value");
+    db.set_node_vector(b, prop_key_id, &[0.0, 1.0])
+      .expect("expected value");
+    db.commit().expect("expected value");
+    let hits = db
+      .search_node_vectors(prop_key_id, &[0.1, 1.0], 1)
+      .expect("expected value");
+    assert_eq!(hits.len(), 1);
+    assert_eq!(hits[0].node_id, b);
+    assert!(hits[0].similarity > 0.9);
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_update_node_vector_dims_patches_in_place() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("patch-vectors.kitedb");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    db.begin(false).expect("expected value");
+    let a = db.create_node(None).expect("expected value");
+    let b = db.create_node(None).expect("expected value");
+    let prop_key_id = db.define_propkey("embedding").expect("expected value");
+    db.set_node_vector(a, prop_key_id, &[0.5, 0.5, 0.5, 0.5])
+      .expect("expected value");
+    db.commit().expect("expected value");
+
+    db.begin(false).expect("expected value");
+    db.update_node_vector_dims(a, prop_key_id, &[(1, 0.0), (3, 0.0)])
+      .expect("expected value");
+    let vec = db.node_vector(a, prop_key_id).expect("expected value");
+    assert_eq!(vec.as_ref(), &[0.5, 0.0, 0.5, 0.0]);
+    assert!(db
+      .update_node_vector_dims(a, prop_key_id, &[(4, 1.0)])
+      .is_err());
+    assert!(db
+      .update_node_vector_dims(b, prop_key_id, &[(0, 1.0)])
+      .is_err());
+    db.commit().expect("expected value");
+
+    assert!(db
+      .update_node_vector_dims(a, prop_key_id, &[(0, 1.0)])
+      .is_err());
+    close_single_file(db).expect("expected value");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    // The committed store normalizes, so compare against the patched
+    // vector's unit form.
+    let vec = db.node_vector(a, prop_key_id).expect("expected value");
+    let expected = normalize(&[0.5, 0.0, 0.5, 0.0]);
+    assert!(vec
+      .iter()
+      .zip(expected.iter())
+      .all(|(got, want)| (got - want).abs() < 1e-6));
+    close_single_file(db).expect("expected value");
+  }
+
   #[test]
   fn test_vector_persistence_across_checkpoint() {
     let temp_dir = tempdir().expect("expected value");
@@ -795,6 +1268,7 @@ mod tests {
       etypes: HashMap::new(),
       propkeys,
       vector_stores: Some(stores),
+      metadata: HashMap::new(),
       compression: None,
     })
     .expect("expected value");
@@ -865,6 +1339,7 @@ mod tests {
       etypes: HashMap::new(),
       propkeys: HashMap::new(),
       vector_stores: None,
+      metadata: HashMap::new(),
       compression: None,
     })
     .expect("expected value");
@@ -3,20 +3,59 @@
 //! Handles all mutation operations: create/delete nodes, add/delete edges,
 //! set/delete properties, and node labels.
 
+use crate::core::snapshot::reader::SnapshotData;
 use crate::core::wal::record::{
   build_add_edge_payload, build_add_edge_props_payload, build_add_edges_batch_payload,
   build_add_edges_props_batch_payload, build_add_node_label_payload, build_create_node_payload,
   build_create_nodes_batch_payload, build_define_etype_payload, build_define_label_payload,
   build_define_propkey_payload, build_del_edge_prop_payload, build_del_node_prop_payload,
   build_delete_edge_payload, build_delete_node_payload, build_remove_node_label_payload,
-  build_set_edge_prop_payload, build_set_edge_props_payload, build_set_node_prop_payload,
-  WalRecord,
+  build_set_edge_prop_payload, build_set_edge_props_payload, build_set_metadata_payload,
+  build_set_node_key_payload, build_set_node_prop_payload, WalRecord,
 };
 use crate::error::{KiteError, Result};
 use crate::types::*;
+use crate::vector::distance::normalize;
+use std::collections::{HashMap, HashSet};
 
 use super::SingleFileDB;
 
+/// Metadata key prefix marking an edge type as rejecting self-loops
+const NO_SELF_LOOPS_METADATA_PREFIX: &str = "etype:no_self_loops:";
+
+fn no_self_loops_metadata_key(etype_name: &str) -> String {
+  format!("{NO_SELF_LOOPS_METADATA_PREFIX}{etype_name}")
+}
+
+/// Edge type names flagged `no_self_loops` in the committed metadata
+pub(crate) fn load_no_self_loop_etypes(
+  snapshot: Option<&SnapshotData>,
+  delta: &DeltaState,
+) -> Result<HashSet<String>> {
+  let mut etypes: HashSet<String> = match snapshot {
+    Some(snapshot) => snapshot
+      .metadata()?
+      .into_keys()
+      .filter_map(|key| {
+        key
+          .strip_prefix(NO_SELF_LOOPS_METADATA_PREFIX)
+          .map(str::to_string)
+      })
+      .collect(),
+    None => HashSet::new(),
+  };
+  for (key, value) in &delta.metadata {
+    if let Some(name) = key.strip_prefix(NO_SELF_LOOPS_METADATA_PREFIX) {
+      if value.is_some() {
+        etypes.insert(name.to_string());
+      } else {
+        etypes.remove(name);
+      }
+    }
+  }
+  Ok(etypes)
+}
+
 impl SingleFileDB {
   // ========================================================================
   // Node Operations
@@ -214,12 +253,127 @@ impl SingleFileDB {
     Ok(())
   }
 
+  /// Change the key of an existing node
+  ///
+  /// The old key is removed from the key index and the new one added in the
+  /// same transaction, so both become visible together at commit. Fails with
+  /// `DuplicateKey` if `new_key` already belongs to a different live node;
+  /// re-keying a node to its current key is a no-op.
+  pub fn set_node_key(&self, node_id: NodeId, new_key: &str) -> Result<()> {
+    let (txid, tx_handle) = self.require_write_tx_handle()?;
+
+    if !self.node_exists(node_id) {
+      return Err(KiteError::NodeNotFound(node_id));
+    }
+    let old_key = self.node_key(node_id);
+    if old_key.as_deref() == Some(new_key) {
+      return Ok(());
+    }
+    if let Some(existing) = self.node_by_key(new_key) {
+      if existing != node_id {
+        return Err(KiteError::DuplicateKey(new_key.to_string()));
+      }
+    }
+
+    let record = WalRecord::new(
+      WalRecordType::SetNodeKey,
+      txid,
+      build_set_node_key_payload(node_id, new_key),
+    );
+    self.write_wal_tx(&tx_handle, record)?;
+
+    let bulk_load = {
+      let mut tx = tx_handle.lock();
+      tx.pending
+        .set_node_key(node_id, old_key.as_deref(), new_key);
+      tx.bulk_load
+    };
+
+    if let Some(mvcc) = self.mvcc.as_ref() {
+      if !bulk_load {
+        let mut tx_mgr = mvcc.tx_manager.lock();
+        tx_mgr.record_write(txid, TxKey::Node(node_id));
+        tx_mgr.record_write(txid, TxKey::Key(new_key.into()));
+        if let Some(old_key) = old_key.as_ref() {
+          tx_mgr.record_write(txid, TxKey::Key(old_key.as_str().into()));
+        }
+      }
+    }
+
+    if !bulk_load {
+      self.cache_invalidate_node(node_id);
+    }
+
+    Ok(())
+  }
+
   // ========================================================================
   // Edge Operations
   // ========================================================================
 
+  /// Reject (or allow again) edges from a node to itself for an edge type
+  ///
+  /// The flag is stored in the database metadata by edge type name, written
+  /// in the current write transaction, so it survives reopening.
+  pub fn set_etype_no_self_loops(&self, etype: ETypeId, enabled: bool) -> Result<()> {
+    let name = self
+      .etype_name(etype)
+      .ok_or_else(|| KiteError::InvalidSchema(format!("Unknown edge type {etype}").into()))?;
+    let key = no_self_loops_metadata_key(&name);
+    if enabled {
+      self.set_metadata(&key, &[])
+    } else {
+      self.remove_metadata(&key)
+    }
+  }
+
+  /// Whether an edge type rejects self-loops
+  ///
+  /// Sees a flag changed earlier in the current transaction.
+  pub fn etype_no_self_loops(&self, etype: ETypeId) -> bool {
+    let Some(name) = self.etype_name(etype) else {
+      return false;
+    };
+    if let Some(tx_handle) = self.current_tx_handle() {
+      let key = no_self_loops_metadata_key(&name);
+      if let Some(value) = tx_handle.lock().pending.metadata.get(&key) {
+        return value.is_some();
+      }
+    }
+    self.no_self_loop_etypes.read().contains(&name)
+  }
+
+  /// Apply committed metadata writes to the cached `no_self_loops` flags
+  pub(crate) fn apply_no_self_loops_metadata(&self, metadata: &HashMap<String, Option<Vec<u8>>>) {
+    let mut etypes = None;
+    for (key, value) in metadata {
+      let Some(name) = key.strip_prefix(NO_SELF_LOOPS_METADATA_PREFIX) else {
+        continue;
+      };
+      let etypes = etypes.get_or_insert_with(|| self.no_self_loop_etypes.write());
+      if value.is_some() {
+        etypes.insert(name.to_string());
+      } else {
+        etypes.remove(name);
+      }
+    }
+  }
+
+  pub(crate) fn check_self_loop(&self, src: NodeId, etype: ETypeId, dst: NodeId) -> Result<()> {
+    if src == dst && self.etype_no_self_loops(etype) {
+      let name = self
+        .etype_name(etype)
+        .unwrap_or_else(|| format!("etype_{etype}"));
+      return Err(KiteError::Constraint(format!(
+        "Edge type '{name}' does not allow self-loops (node {src})"
+      )));
+    }
+    Ok(())
+  }
+
   /// Add an edge
   pub fn add_edge(&self, src: NodeId, etype: ETypeId, dst: NodeId) -> Result<()> {
+    self.check_self_loop(src, etype, dst)?;
     let (txid, tx_handle) = self.require_write_tx_handle()?;
 
     // Write WAL record
@@ -286,6 +440,9 @@ impl SingleFileDB {
     if edges.is_empty() {
       return Ok(());
     }
+    for &(src, etype, dst) in edges {
+      self.check_self_loop(src, etype, dst)?;
+    }
 
     let (txid, tx_handle) = self.require_write_tx_handle()?;
     let record = WalRecord::new(
@@ -367,8 +524,10 @@ impl SingleFileDB {
     if props.is_empty() {
       return self.add_edge(src, etype, dst);
     }
+    self.check_self_loop(src, etype, dst)?;
 
     let (txid, tx_handle) = self.require_write_tx_handle()?;
+    let props = self.intern_prop_values(props)?;
 
     let record = WalRecord::new(
       WalRecordType::AddEdgeProps,
@@ -448,8 +607,15 @@ impl SingleFileDB {
     if edges.is_empty() {
       return Ok(());
     }
+    for (src, etype, dst, _) in &edges {
+      self.check_self_loop(*src, *etype, *dst)?;
+    }
 
     let (txid, tx_handle) = self.require_write_tx_handle()?;
+    let edges = edges
+      .into_iter()
+      .map(|(src, etype, dst, props)| Ok((src, etype, dst, self.intern_prop_values(props)?)))
+      .collect::<Result<Vec<EdgeWithProps>>>()?;
     let mut edge_meta: Vec<(NodeId, ETypeId, NodeId, Vec<PropKeyId>)> =
       Vec::with_capacity(edges.len());
     for (src, etype, dst, props) in edges.iter() {
@@ -644,14 +810,20 @@ impl SingleFileDB {
   /// Set a node property
   pub fn set_node_prop(&self, node_id: NodeId, key_id: PropKeyId, value: PropValue) -> Result<()> {
     let (txid, tx_handle) = self.require_write_tx_handle()?;
+    let value = match value {
+      PropValue::VectorF32(vector) if self.is_vector_normalized(key_id) => {
+        PropValue::VectorF32(normalize(&vector))
+      }
+      other => self.intern_prop_value(key_id, other)?,
+    };
 
-    // Write WAL record
+    // Queue WAL record; repeated sets of this prop are coalesced
     let record = WalRecord::new(
       WalRecordType::SetNodeProp,
       txid,
       build_set_node_prop_payload(node_id, key_id, &value),
     );
-    self.write_wal_tx(&tx_handle, record)?;
+    self.write_wal_tx_prop_set(&tx_handle, node_id, key_id, record);
 
     // Update pending delta
     let bulk_load = {
@@ -687,6 +859,23 @@ impl SingleFileDB {
     self.set_node_prop(node_id, key_id, value)
   }
 
+  /// Set a node property, returning the value it replaced (if any)
+  ///
+  /// The previous value is read through the current transaction, so writes
+  /// made earlier in the same transaction are seen. With MVCC the read is
+  /// tracked, so if another transaction commits the prop before this one,
+  /// this commit fails with a conflict instead of losing that write.
+  pub fn replace_node_prop(
+    &self,
+    node_id: NodeId,
+    key_id: PropKeyId,
+    value: PropValue,
+  ) -> Result<Option<PropValue>> {
+    let previous = self.node_prop(node_id, key_id);
+    self.set_node_prop(node_id, key_id, value)?;
+    Ok(previous)
+  }
+
   /// Delete a node property
   pub fn delete_node_prop(&self, node_id: NodeId, key_id: PropKeyId) -> Result<()> {
     let (txid, tx_handle) = self.require_write_tx_handle()?;
@@ -722,6 +911,54 @@ impl SingleFileDB {
     Ok(())
   }
 
+  /// Delete a node property, returning the value that was removed (if any)
+  pub fn take_node_prop(&self, node_id: NodeId, key_id: PropKeyId) -> Result<Option<PropValue>> {
+    let previous = self.node_prop(node_id, key_id);
+    self.delete_node_prop(node_id, key_id)?;
+    Ok(previous)
+  }
+
+  // ========================================================================
+  // Key-addressed Property Operations
+  // ========================================================================
+
+  /// Get a node property, resolving the node by its key
+  ///
+  /// The key is resolved through the current transaction, like
+  /// [`Self::node_by_key`]. Fails with [`KiteError::KeyNotFound`] when no
+  /// node has the key.
+  pub fn node_prop_by_key(&self, key: &str, key_name: &str) -> Result<Option<PropValue>> {
+    let node_id = self.require_node_by_key(key)?;
+    Ok(
+      self
+        .propkey_id(key_name)
+        .and_then(|key_id| self.node_prop(node_id, key_id)),
+    )
+  }
+
+  /// Set a node property by name, resolving the node by its key
+  pub fn set_node_prop_by_key(&self, key: &str, key_name: &str, value: PropValue) -> Result<()> {
+    let node_id = self.require_node_by_key(key)?;
+    self.set_node_prop_by_name(node_id, key_name, value)
+  }
+
+  /// Delete a node property by name, resolving the node by its key
+  ///
+  /// Deleting a property name that was never defined is a no-op.
+  pub fn delete_node_prop_by_key(&self, key: &str, key_name: &str) -> Result<()> {
+    let node_id = self.require_node_by_key(key)?;
+    match self.propkey_id(key_name) {
+      Some(key_id) => self.delete_node_prop(node_id, key_id),
+      None => Ok(()),
+    }
+  }
+
+  fn require_node_by_key(&self, key: &str) -> Result<NodeId> {
+    self
+      .node_by_key(key)
+      .ok_or_else(|| KiteError::KeyNotFound(key.to_string()))
+  }
+
   // ========================================================================
   // Edge Property Operations
   // ========================================================================
@@ -736,6 +973,7 @@ impl SingleFileDB {
     value: PropValue,
   ) -> Result<()> {
     let (txid, tx_handle) = self.require_write_tx_handle()?;
+    let value = self.intern_prop_value(key_id, value)?;
 
     // Write WAL record
     let record = WalRecord::new(
@@ -789,6 +1027,7 @@ impl SingleFileDB {
     }
 
     let (txid, tx_handle) = self.require_write_tx_handle()?;
+    let props = self.intern_prop_values(props)?;
 
     let key_ids: Vec<PropKeyId> = props.iter().map(|(key_id, _)| *key_id).collect();
 
@@ -844,6 +1083,20 @@ impl SingleFileDB {
     self.set_edge_prop(src, etype, dst, key_id, value)
   }
 
+  /// Set an edge property, returning the value it replaced (if any)
+  pub fn replace_edge_prop(
+    &self,
+    src: NodeId,
+    etype: ETypeId,
+    dst: NodeId,
+    key_id: PropKeyId,
+    value: PropValue,
+  ) -> Result<Option<PropValue>> {
+    let previous = self.edge_prop(src, etype, dst, key_id);
+    self.set_edge_prop(src, etype, dst, key_id, value)?;
+    Ok(previous)
+  }
+
   /// Delete an edge property
   pub fn delete_edge_prop(
     &self,
@@ -893,6 +1146,19 @@ impl SingleFileDB {
     Ok(())
   }
 
+  /// Delete an edge property, returning the value that was removed (if any)
+  pub fn take_edge_prop(
+    &self,
+    src: NodeId,
+    etype: ETypeId,
+    dst: NodeId,
+    key_id: PropKeyId,
+  ) -> Result<Option<PropValue>> {
+    let previous = self.edge_prop(src, etype, dst, key_id);
+    self.delete_edge_prop(src, etype, dst, key_id)?;
+    Ok(previous)
+  }
+
   // ========================================================================
   // Node Label Operations
   // ========================================================================
@@ -1088,4 +1354,334 @@ impl SingleFileDB {
 
     Ok(propkey_id)
   }
+
+  // ========================================================================
+  // Metadata Operations
+  // ========================================================================
+
+  /// Store a database metadata entry, replacing any previous value
+  ///
+  /// Metadata is a small key/value store persisted with the database and
+  /// written in the current transaction, for state that belongs to the
+  /// database rather than to a node or edge. Keys are namespaced by their
+  /// users (e.g. `kite:`).
+  pub fn set_metadata(&self, key: &str, value: &[u8]) -> Result<()> {
+    self.write_metadata(key, Some(value))
+  }
+
+  /// Remove a database metadata entry
+  pub fn remove_metadata(&self, key: &str) -> Result<()> {
+    self.write_metadata(key, None)
+  }
+
+  fn write_metadata(&self, key: &str, value: Option<&[u8]>) -> Result<()> {
+    let (txid, tx_handle) = self.require_write_tx_handle()?;
+
+    let record = WalRecord::new(
+      WalRecordType::SetMetadata,
+      txid,
+      build_set_metadata_payload(key, value),
+    );
+    self.write_wal_tx(&tx_handle, record)?;
+
+    tx_handle
+      .lock()
+      .pending
+      .metadata
+      .insert(key.to_string(), value.map(<[u8]>::to_vec));
+    Ok(())
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use crate::core::single_file::{close_single_file, open_single_file, SingleFileOpenOptions};
+  use crate::error::KiteError;
+  use crate::types::PropValue;
+  use tempfile::tempdir;
+
+  #[test]
+  fn test_replace_and_take_props_return_previous_values() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("previous.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let a = db.create_node(None).expect("expected value");
+    let b = db.create_node(None).expect("expected value");
+    let etype = db.define_etype("KNOWS").expect("expected value");
+    let key = db.define_propkey("weight").expect("expected value");
+    db.add_edge(a, etype, b).expect("expected value");
+    assert_eq!(
+      db.replace_node_prop(a, key, PropValue::I64(1))
+        .expect("expected value"),
+      None
+    );
+    db.commit().expect("expected value");
+
+    db.begin(false).expect("expected value");
+    assert_eq!(
+      db.replace_node_prop(a, key, PropValue::I64(2))
+        .expect("expected value"),
+      Some(PropValue::I64(1))
+    );
+    assert_eq!(
+      db.take_node_prop(a, key).expect("expected value"),
+      Some(PropValue::I64(2))
+    );
+    assert_eq!(db.take_node_prop(a, key).expect("expected value"), None);
+
+    assert_eq!(
+      db.replace_edge_prop(a, etype, b, key, PropValue::F64(0.5))
+        .expect("expected value"),
+      None
+    );
+    assert_eq!(
+      db.take_edge_prop(a, etype, b, key).expect("expected value"),
+      Some(PropValue::F64(0.5))
+    );
+    db.commit().expect("expected value");
+
+    assert!(db.replace_node_prop(a, key, PropValue::Null).is_err());
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_concurrent_replace_node_prop_reports_each_value_once() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("previous-concurrent.kitedb");
+    let options = SingleFileOpenOptions::new().mvcc(true);
+    let db = open_single_file(&db_path, options).expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let a = db.create_node(None).expect("expected value");
+    let key = db.define_propkey("n").expect("expected value");
+    db.set_node_prop(a, key, PropValue::I64(0))
+      .expect("expected value");
+    db.commit().expect("expected value");
+
+    const THREADS: i64 = 4;
+    const WRITES: i64 = 25;
+    let replaced: Vec<(i64, i64)> = std::thread::scope(|scope| {
+      let workers: Vec<_> = (0..THREADS)
+        .map(|t| {
+          let db = &db;
+          scope.spawn(move || {
+            let mut pairs = Vec::new();
+            for i in 1..=WRITES {
+              let value = t * 1000 + i;
+              loop {
+                db.begin(false).expect("expected value");
+                let previous = db
+                  .replace_node_prop(a, key, PropValue::I64(value))
+                  .expect("expected value");
+                match db.commit() {
+                  Ok(()) => {
+                    let Some(PropValue::I64(previous)) = previous else {
+                      panic!("expected an int, got {previous:?}");
+                    };
+                    pairs.push((previous, value));
+                    break;
+                  }
+                  Err(e) if e.is_conflict() => continue,
+                  Err(e) => panic!("unexpected error: {e}"),
+                }
+              }
+            }
+            pairs
+          })
+        })
+        .collect();
+      workers
+        .into_iter()
+        .flat_map(|w| w.join().expect("expected value"))
+        .collect()
+    });
+
+    // Every committed value is replaced exactly once, by the next commit
+    let next: std::collections::HashMap<i64, i64> = replaced.iter().copied().collect();
+    assert_eq!(next.len(), replaced.len(), "a value was reported twice");
+    let mut current = 0;
+    for _ in 0..replaced.len() {
+      current = next[&current];
+    }
+    assert_eq!(db.node_prop(a, key), Some(PropValue::I64(current)));
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_node_props_by_key_resolve_within_transaction() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("props-by-key.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+
+    db.begin(false).expect("expected value");
+    db.create_node(Some("user:alice")).expect("expected value");
+    db.set_node_prop_by_key("user:alice", "age", PropValue::I64(30))
+      .expect("expected value");
+    assert_eq!(
+      db.node_prop_by_key("user:alice", "age")
+        .expect("expected value"),
+      Some(PropValue::I64(30))
+    );
+    db.commit().expect("expected value");
+
+    assert_eq!(
+      db.node_prop_by_key("user:alice", "missing")
+        .expect("expected value"),
+      None
+    );
+    db.begin(false).expect("expected value");
+    db.delete_node_prop_by_key("user:alice", "age")
+      .expect("expected value");
+    db.delete_node_prop_by_key("user:alice", "missing")
+      .expect("expected value");
+    assert!(matches!(
+      db.set_node_prop_by_key("user:bob", "age", PropValue::I64(1)),
+      Err(KiteError::KeyNotFound(ref key)) if key == "user:bob"
+    ));
+    db.commit().expect("expected value");
+
+    assert_eq!(
+      db.node_prop_by_key("user:alice", "age")
+        .expect("expected value"),
+      None
+    );
+    assert!(db.node_prop_by_key("user:bob", "age").is_err());
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_set_node_key_moves_index_entry() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("rekey.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let a = db.create_node(Some("user:a")).expect("expected value");
+    let b = db.create_node(Some("user:b")).expect("expected value");
+    db.commit().expect("expected value");
+    db.checkpoint().expect("expected value");
+
+    db.begin(false).expect("expected value");
+    db.set_node_key(a, "user:c").expect("expected value");
+    assert_eq!(db.node_by_key("user:a"), None);
+    assert_eq!(db.node_by_key("user:c"), Some(a));
+    assert_eq!(db.node_key(a).as_deref(), Some("user:c"));
+    assert!(matches!(
+      db.set_node_key(a, "user:b"),
+      Err(KiteError::DuplicateKey(_))
+    ));
+    db.commit().expect("expected value");
+
+    assert_eq!(db.node_by_key("user:a"), None);
+    assert_eq!(db.node_by_key("user:c"), Some(a));
+    assert_eq!(db.node_by_key("user:b"), Some(b));
+    close_single_file(db).expect("expected value");
+
+    // WAL replay
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    assert_eq!(db.node_by_key("user:a"), None);
+    assert_eq!(db.node_by_key("user:c"), Some(a));
+    db.checkpoint().expect("expected value");
+    close_single_file(db).expect("expected value");
+
+    // Snapshot
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    assert_eq!(db.node_by_key("user:a"), None);
+    assert_eq!(db.node_by_key("user:c"), Some(a));
+    assert_eq!(db.node_key(a).as_deref(), Some("user:c"));
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_metadata_persists_through_wal_and_snapshot() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("metadata.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+
+    assert!(matches!(
+      db.set_metadata("app:a", b"1"),
+      Err(KiteError::NoTransaction)
+    ));
+    db.begin(false).expect("expected value");
+    db.set_metadata("app:a", b"1").expect("expected value");
+    db.set_metadata("app:b", b"2").expect("expected value");
+    assert_eq!(
+      db.metadata("app:a").expect("expected value"),
+      Some(b"1".to_vec())
+    );
+    db.commit().expect("expected value");
+
+    // Rolled-back writes leave no trace
+    db.begin(false).expect("expected value");
+    db.set_metadata("app:a", b"lost").expect("expected value");
+    db.rollback().expect("expected value");
+    assert_eq!(
+      db.metadata("app:a").expect("expected value"),
+      Some(b"1".to_vec())
+    );
+    close_single_file(db).expect("expected value");
+
+    // WAL replay
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    assert_eq!(
+      db.metadata("app:b").expect("expected value"),
+      Some(b"2".to_vec())
+    );
+    db.begin(false).expect("expected value");
+    db.remove_metadata("app:b").expect("expected value");
+    db.commit().expect("expected value");
+    db.checkpoint().expect("expected value");
+    close_single_file(db).expect("expected value");
+
+    // Snapshot
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    assert_eq!(
+      db.metadata("app:a").expect("expected value"),
+      Some(b"1".to_vec())
+    );
+    assert_eq!(db.metadata("app:b").expect("expected value"), None);
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_no_self_loops_flag_persists_and_rolls_back() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("self-loops.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let follows = db.define_etype("FOLLOWS").expect("expected value");
+    let a = db.create_node(None).expect("expected value");
+    db.set_etype_no_self_loops(follows, true)
+      .expect("expected value");
+    assert!(db.etype_no_self_loops(follows));
+    db.commit().expect("expected value");
+
+    // A rolled-back change leaves the committed flag in place
+    db.begin(false).expect("expected value");
+    db.set_etype_no_self_loops(follows, false)
+      .expect("expected value");
+    db.add_edge(a, follows, a).expect("expected value");
+    db.rollback().expect("expected value");
+    assert!(db.etype_no_self_loops(follows));
+    close_single_file(db).expect("expected value");
+
+    // WAL replay, then snapshot
+    for checkpoint in [true, false] {
+      let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+      assert!(db.etype_no_self_loops(follows));
+      db.begin(false).expect("expected value");
+      assert!(matches!(
+        db.add_edge(a, follows, a),
+        Err(KiteError::Constraint(_))
+      ));
+      db.rollback().expect("expected value");
+      if checkpoint {
+        db.checkpoint().expect("expected value");
+      }
+      close_single_file(db).expect("expected value");
+    }
+  }
 }
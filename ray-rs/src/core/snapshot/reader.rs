@@ -34,6 +34,8 @@ pub struct SnapshotData {
   decompressed_cache: RwLock<HashMap<SectionId, Arc<[u8]>>>,
   /// Cache for string table entries (indexed by StringId)
   string_cache: Vec<OnceLock<Arc<str>>>,
+  /// Key index in key order, built on first prefix scan
+  sorted_keys: OnceLock<Vec<(String, NodeId)>>,
 }
 
 /// Borrowed or shared section bytes.
@@ -328,6 +330,7 @@ impl SnapshotData {
       sections,
       decompressed_cache: RwLock::new(HashMap::new()),
       string_cache,
+      sorted_keys: OnceLock::new(),
     })
   }
 
@@ -436,6 +439,7 @@ impl SnapshotData {
       sections,
       decompressed_cache: RwLock::new(HashMap::new()),
       string_cache,
+      sorted_keys: OnceLock::new(),
     })
   }
 
@@ -858,6 +862,30 @@ impl SnapshotData {
     None
   }
 
+  /// Keyed nodes whose key starts with `prefix`, in key order
+  ///
+  /// The persisted key index is ordered by hash, so the first call builds a
+  /// key-ordered copy that lives as long as the snapshot.
+  pub fn keys_with_prefix(&self, prefix: &str) -> &[(String, NodeId)] {
+    let keys = self.sorted_keys.get_or_init(|| {
+      let mut keys = Vec::new();
+      if let Some(key_entries) = self.section_data_shared(SectionId::KeyEntries) {
+        for entry in key_entries.as_ref().chunks_exact(KEY_INDEX_ENTRY_SIZE) {
+          let string_id = read_u32(entry, 8);
+          let node_id = read_u64(entry, 16);
+          if let Some(key) = self.string(string_id) {
+            keys.push((key, node_id));
+          }
+        }
+      }
+      keys.sort_unstable();
+      keys
+    });
+    let start = keys.partition_point(|(key, _)| key.as_str() < prefix);
+    let len = keys[start..].partition_point(|(key, _)| key.starts_with(prefix));
+    &keys[start..start + len]
+  }
+
   /// Binary search for first entry with matching hash
   fn binary_search_key_hash(
     &self,
@@ -1037,6 +1065,40 @@ impl SnapshotData {
     Some(props)
   }
 
+  /// Check that every JSON property value parses
+  ///
+  /// Prop readers return `Option`, so a malformed value reads back as a
+  /// missing property. This scans every prop value, so it runs as part of
+  /// `SingleFileDB::check` rather than on load; values are always written
+  /// as serialized `serde_json` documents and the snapshot CRC covers them.
+  pub(crate) fn validate_json_props(&self) -> Result<()> {
+    if !self.header.flags.contains(SnapshotFlags::HAS_PROPERTIES) {
+      return Ok(());
+    }
+
+    for section in [SectionId::NodePropVals, SectionId::EdgePropVals] {
+      let Some(vals) = self.section_data_shared(section) else {
+        continue;
+      };
+      for (i, val) in vals.as_ref().chunks_exact(PROP_VALUE_DISK_SIZE).enumerate() {
+        if PropValueTag::from_u8(val[0]) != Some(PropValueTag::Json) {
+          continue;
+        }
+        let string_id = read_u64(val, 8) as u32;
+        let valid = self
+          .string(string_id)
+          .is_some_and(|text| serde_json::from_str::<serde_json::Value>(&text).is_ok());
+        if !valid {
+          return Err(KiteError::InvalidSnapshot(format!(
+            "Malformed JSON prop value {i} in {section:?} (string {string_id})"
+          )));
+        }
+      }
+    }
+
+    Ok(())
+  }
+
   /// Decode a property value from disk format
   fn decode_prop_value(&self, vals: &[u8], offset: usize) -> Option<PropValue> {
     if offset + PROP_VALUE_DISK_SIZE > vals.len() {
@@ -1088,6 +1150,10 @@ impl SnapshotData {
 
         Some(PropValue::VectorF32(vec))
       }
+      PropValueTag::Json => {
+        let text = self.string(payload as u32)?;
+        serde_json::from_str(&text).ok().map(PropValue::Json)
+      }
     }
   }
 }
@@ -1230,6 +1296,32 @@ pub struct OutEdgeInfo {
 }
 
 impl SnapshotData {
+  /// Database metadata entries stored in the snapshot
+  pub fn metadata(&self) -> Result<HashMap<String, Vec<u8>>> {
+    let mut entries = HashMap::new();
+    let Some(data) = self.section_data_shared(SectionId::Metadata) else {
+      return Ok(entries);
+    };
+    let data = data.as_ref();
+    let malformed = || KiteError::InvalidSnapshot("Malformed metadata section".to_string());
+
+    let mut offset = 0;
+    let mut take = |len: usize| -> Result<&[u8]> {
+      let end = offset + len;
+      let bytes = data.get(offset..end).ok_or_else(malformed)?;
+      offset = end;
+      Ok(bytes)
+    };
+    let count = read_u32(take(4)?, 0);
+    for _ in 0..count {
+      let key_len = read_u32(take(4)?, 0) as usize;
+      let key = String::from_utf8(take(key_len)?.to_vec()).map_err(|_| malformed())?;
+      let value_len = read_u32(take(4)?, 0) as usize;
+      entries.insert(key, take(value_len)?.to_vec());
+    }
+    Ok(entries)
+  }
+
   /// Get label name by LabelID
   pub fn label_name(&self, label_id: LabelId) -> Option<&str> {
     let label_string_ids = self.section_data_shared(SectionId::LabelStringIds)?;
@@ -1325,6 +1417,7 @@ mod tests {
       etypes: HashMap::new(),
       propkeys: HashMap::from([(1, "embedding".to_string())]),
       vector_stores: None,
+      metadata: HashMap::new(),
       compression: None,
     })
     .expect("snapshot build");
@@ -1364,4 +1457,60 @@ mod tests {
       .iter()
       .any(|entry| entry.section_id == Some(SectionId::VectorData) && entry.bytes > 0));
   }
+
+  #[test]
+  fn test_malformed_json_prop_is_a_corrupt_snapshot() {
+    let mut props = HashMap::new();
+    props.insert(1, PropValue::Json(serde_json::json!({ "tier": 7 })));
+
+    let mut bytes = build_snapshot_to_memory(SnapshotBuildInput {
+      generation: 1,
+      nodes: vec![NodeData {
+        node_id: 1,
+        key: Some("n1".to_string()),
+        labels: Vec::new(),
+        props,
+      }],
+      edges: Vec::new(),
+      labels: HashMap::new(),
+      etypes: HashMap::new(),
+      propkeys: HashMap::from([(1, "settings".to_string())]),
+      vector_stores: None,
+      metadata: HashMap::new(),
+      compression: None,
+    })
+    .expect("snapshot build");
+
+    let dir = tempdir().expect("temp dir");
+    let path = dir.path().join("json-snapshot.gds");
+    fs::write(&path, &bytes).expect("write snapshot");
+    let snapshot = SnapshotData::load(&path).expect("snapshot parse");
+    assert_eq!(
+      snapshot.node_prop(0, 1),
+      Some(PropValue::Json(serde_json::json!({ "tier": 7 })))
+    );
+    drop(snapshot);
+
+    // Break the stored JSON text without changing its length
+    let text = br#"{"tier":7}"#;
+    let pos = bytes
+      .windows(text.len())
+      .position(|window| window == text)
+      .expect("json text in string table");
+    bytes[pos + text.len() - 1] = b'[';
+    fs::write(&path, &bytes).expect("write snapshot");
+
+    let options = ParseSnapshotOptions {
+      skip_crc_validation: true,
+      ..Default::default()
+    };
+    // Loading doesn't scan prop values; the explicit check catches it
+    let snapshot = SnapshotData::load_with_options(&path, &options).expect("snapshot parse");
+    assert_eq!(snapshot.node_prop(0, 1), None);
+    match snapshot.validate_json_props() {
+      Err(KiteError::InvalidSnapshot(msg)) => assert!(msg.contains("Malformed JSON")),
+      Err(other) => panic!("expected InvalidSnapshot, got {other}"),
+      Ok(()) => panic!("expected malformed JSON to be rejected"),
+    }
+  }
 }
@@ -17,8 +17,10 @@ pub struct ParsedSections {
 
 /// Resolve section table size for a snapshot version.
 pub fn section_count_for_version(version: u32) -> usize {
-  if version >= 4 {
+  if version >= 5 {
     SectionId::COUNT
+  } else if version >= 4 {
+    SectionId::COUNT_V4
   } else if version >= 3 {
     SectionId::COUNT_V3
   } else if version >= 2 {
@@ -172,6 +174,7 @@ mod tests {
       etypes: HashMap::new(),
       propkeys: HashMap::new(),
       vector_stores: None,
+      metadata: HashMap::new(),
       compression: None,
     })
     .expect("snapshot build")
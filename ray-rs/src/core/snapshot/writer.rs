@@ -46,6 +46,8 @@ pub struct SnapshotBuildInput {
   pub etypes: HashMap<ETypeId, String>,
   pub propkeys: HashMap<PropKeyId, String>,
   pub vector_stores: Option<HashMap<PropKeyId, VectorManifest>>,
+  /// Database metadata entries (see `SingleFileDB::metadata`)
+  pub metadata: HashMap<String, Vec<u8>>,
   pub compression: Option<CompressionOptions>,
 }
 
@@ -352,6 +354,14 @@ fn encode_prop_value(
       (PropValueTag::String as u8, string_id as u64)
     }
     PropValue::VectorF32(vec) => (PropValueTag::VectorF32 as u8, vectors.push(vec)),
+    PropValue::Json(doc) => {
+      let string_id = string_table
+        .string_to_id
+        .get(&doc.to_string())
+        .copied()
+        .unwrap_or(0);
+      (PropValueTag::Json as u8, string_id as u64)
+    }
   }
 }
 
@@ -453,19 +463,28 @@ fn intern_string_props(nodes: &[NodeData], edges: &[EdgeData], string_table: &mu
     let mut sorted_props: Vec<_> = node.props.iter().collect();
     sorted_props.sort_by_key(|(k, _)| *k);
     for (_, value) in sorted_props {
-      if let PropValue::String(s) = value {
-        string_table.intern(s);
-      }
+      intern_prop_text(value, string_table);
     }
   }
   for edge in edges {
     let mut sorted_props: Vec<_> = edge.props.iter().collect();
     sorted_props.sort_by_key(|(k, _)| *k);
     for (_, value) in sorted_props {
-      if let PropValue::String(s) = value {
-        string_table.intern(s);
-      }
+      intern_prop_text(value, string_table);
+    }
+  }
+}
+
+/// Strings and JSON documents are both stored in the string table
+fn intern_prop_text(value: &PropValue, string_table: &mut StringTable) {
+  match value {
+    PropValue::String(s) => {
+      string_table.intern(s);
     }
+    PropValue::Json(doc) => {
+      string_table.intern(&doc.to_string());
+    }
+    _ => {}
   }
 }
 
@@ -874,6 +893,29 @@ fn add_vector_store_sections(
   true
 }
 
+/// Metadata section: count (4) + repeated (key_len (4) + key + value_len (4) + value)
+fn add_metadata_section(
+  add_section: &mut impl FnMut(SectionId, Vec<u8>),
+  metadata: &HashMap<String, Vec<u8>>,
+) {
+  if metadata.is_empty() {
+    return;
+  }
+
+  let mut ordered: Vec<(&String, &Vec<u8>)> = metadata.iter().collect();
+  ordered.sort_by_key(|(key, _)| *key);
+
+  let mut data = Vec::new();
+  data.extend_from_slice(&(ordered.len() as u32).to_le_bytes());
+  for (key, value) in ordered {
+    data.extend_from_slice(&(key.len() as u32).to_le_bytes());
+    data.extend_from_slice(key.as_bytes());
+    data.extend_from_slice(&(value.len() as u32).to_le_bytes());
+    data.extend_from_slice(value);
+  }
+  add_section(SectionId::Metadata, data);
+}
+
 // ============================================================================
 // Main snapshot building
 // ============================================================================
@@ -891,6 +933,7 @@ pub fn build_snapshot_to_memory(input: SnapshotBuildInput) -> Result<Vec<u8>> {
     etypes,
     propkeys,
     vector_stores,
+    metadata,
     compression,
   } = input;
 
@@ -969,6 +1012,7 @@ pub fn build_snapshot_to_memory(input: SnapshotBuildInput) -> Result<Vec<u8>> {
 
   let has_vectors = add_vector_sections(&mut add_section, vector_table);
   let has_vector_stores = add_vector_store_sections(&mut add_section, vector_stores.as_ref());
+  add_metadata_section(&mut add_section, &metadata);
 
   // Calculate total size and offsets
   let header_size = SNAPSHOT_HEADER_SIZE;
@@ -1172,6 +1216,7 @@ mod tests {
       etypes,
       propkeys,
       vector_stores: None,
+      metadata: HashMap::new(),
       compression: None,
     }
   }
@@ -1266,6 +1311,7 @@ mod tests {
       etypes: HashMap::new(),
       propkeys,
       vector_stores: Some(stores),
+      metadata: HashMap::new(),
       compression: Some(CompressionOptions {
         enabled: true,
         compression_type: CompressionType::Zstd,
@@ -1296,6 +1342,7 @@ mod tests {
       etypes: HashMap::new(),
       propkeys: HashMap::new(),
       vector_stores: None,
+      metadata: HashMap::new(),
       compression: None,
     };
 
@@ -1329,6 +1376,7 @@ mod tests {
       etypes,
       propkeys: HashMap::new(),
       vector_stores: None,
+      metadata: HashMap::new(),
       compression: None,
     };
 
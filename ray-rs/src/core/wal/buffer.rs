@@ -253,7 +253,15 @@ impl WalBuffer {
 
   /// Merge secondary region records into primary region
   /// Called after checkpoint completes to preserve any writes that occurred during checkpoint
-  pub fn merge_secondary_into_primary(&mut self, pager: &mut FilePager) -> Result<()> {
+  ///
+  /// `carried` are primary records the checkpoint didn't absorb (those of
+  /// transactions still open when writes switched regions); they go first so
+  /// each transaction's records stay in order.
+  pub fn merge_secondary_into_primary(
+    &mut self,
+    carried: &[ParsedWalRecord],
+    pager: &mut FilePager,
+  ) -> Result<()> {
     // Read all records from secondary region (if any)
     let has_secondary_records = self.secondary_head > self.secondary_region_start;
     let secondary_records = if has_secondary_records {
@@ -270,8 +278,8 @@ impl WalBuffer {
     self.active_region = 0;
     self.head = 0;
 
-    // Re-write secondary records to primary region
-    for record in secondary_records {
+    // Re-write carried and secondary records to primary region
+    for record in carried.iter().cloned().chain(secondary_records) {
       let ParsedWalRecord {
         record_type,
         txid,
@@ -965,7 +973,7 @@ mod tests {
 
     // Merge secondary into primary (simulates checkpoint completion)
     buffer
-      .merge_secondary_into_primary(&mut pager)
+      .merge_secondary_into_primary(&[], &mut pager)
       .expect("expected value");
     buffer.flush(&mut pager).expect("expected value");
 
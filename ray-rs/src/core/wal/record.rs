@@ -254,6 +254,11 @@ pub fn build_create_nodes_batch_payload(entries: &[(NodeId, Option<&str>)]) -> V
   buffer
 }
 
+/// Build SET_NODE_KEY payload (same format as CREATE_NODE)
+pub fn build_set_node_key_payload(node_id: NodeId, key: &str) -> Vec<u8> {
+  build_create_node_payload(node_id, Some(key))
+}
+
 /// Build DELETE_NODE payload
 pub fn build_delete_node_payload(node_id: NodeId) -> Vec<u8> {
   let mut buffer = vec![0u8; 8];
@@ -378,6 +383,21 @@ pub fn build_define_propkey_payload(propkey_id: PropKeyId, name: &str) -> Vec<u8
   build_define_label_payload(propkey_id, name)
 }
 
+/// Build SET_METADATA payload
+/// Format: key_len (4) + key + present (1) + value_len (4) + value
+pub fn build_set_metadata_payload(key: &str, value: Option<&[u8]>) -> Vec<u8> {
+  let key_bytes = key.as_bytes();
+  let value_bytes = value.unwrap_or_default();
+  let mut buffer = vec![0u8; 4 + key_bytes.len() + 1 + 4 + value_bytes.len()];
+  write_u32(&mut buffer, 0, key_bytes.len() as u32);
+  buffer[4..4 + key_bytes.len()].copy_from_slice(key_bytes);
+  let offset = 4 + key_bytes.len();
+  buffer[offset] = u8::from(value.is_some());
+  write_u32(&mut buffer, offset + 1, value_bytes.len() as u32);
+  buffer[offset + 5..].copy_from_slice(value_bytes);
+  buffer
+}
+
 /// Serialize a property value
 fn prop_value_serialized_len(value: &PropValue) -> usize {
   match value {
@@ -386,6 +406,7 @@ fn prop_value_serialized_len(value: &PropValue) -> usize {
     PropValue::I64(_) | PropValue::F64(_) => 9,
     PropValue::String(s) => 1 + 4 + s.len(),
     PropValue::VectorF32(v) => 1 + 4 + v.len() * 4,
+    PropValue::Json(v) => 1 + 4 + v.to_string().len(),
   }
 }
 
@@ -420,6 +441,12 @@ fn write_prop_value(value: &PropValue, buffer: &mut [u8]) {
         buffer[5 + i * 4..5 + i * 4 + 4].copy_from_slice(&bytes);
       }
     }
+    PropValue::Json(v) => {
+      let json = v.to_string();
+      buffer[0] = 6;
+      write_u32(buffer, 1, json.len() as u32);
+      buffer[5..5 + json.len()].copy_from_slice(json.as_bytes());
+    }
   }
 }
 
@@ -781,6 +808,35 @@ pub fn parse_define_label_payload(payload: &[u8]) -> Option<DefineLabelData> {
   Some(DefineLabelData { label_id, name })
 }
 
+/// Parsed SET_METADATA data
+#[derive(Debug, Clone)]
+pub struct SetMetadataData {
+  pub key: String,
+  /// `None` when the entry is removed
+  pub value: Option<Vec<u8>>,
+}
+
+/// Parse SET_METADATA payload
+pub fn parse_set_metadata_payload(payload: &[u8]) -> Option<SetMetadataData> {
+  if payload.len() < 4 {
+    return None;
+  }
+  let key_len = read_u32(payload, 0) as usize;
+  let offset = 4usize.checked_add(key_len)?;
+  if payload.len() < offset + 5 {
+    return None;
+  }
+  let key = String::from_utf8(payload[4..offset].to_vec()).ok()?;
+  let present = payload[offset] != 0;
+  let value_len = read_u32(payload, offset + 1) as usize;
+  let value_start = offset + 5;
+  let value = payload.get(value_start..value_start.checked_add(value_len)?)?;
+  Some(SetMetadataData {
+    key,
+    value: present.then(|| value.to_vec()),
+  })
+}
+
 /// Parse DEFINE_ETYPE payload (same format as DEFINE_LABEL)
 pub fn parse_define_etype_payload(payload: &[u8]) -> Option<DefineLabelData> {
   parse_define_label_payload(payload)
@@ -809,6 +865,11 @@ pub fn parse_add_node_label_payload(payload: &[u8]) -> Option<AddNodeLabelData>
   })
 }
 
+/// Parse SET_NODE_KEY payload (same format as CREATE_NODE, key required)
+pub fn parse_set_node_key_payload(payload: &[u8]) -> Option<CreateNodeData> {
+  parse_create_node_payload(payload).filter(|data| data.key.is_some())
+}
+
 /// Parse REMOVE_NODE_LABEL payload (same format as ADD_NODE_LABEL)
 pub fn parse_remove_node_label_payload(payload: &[u8]) -> Option<AddNodeLabelData> {
   parse_add_node_label_payload(payload)
@@ -872,6 +933,17 @@ fn parse_prop_value(payload: &[u8], offset: usize) -> Option<(PropValue, usize)>
       }
       Some((PropValue::VectorF32(vector), 5 + dimensions * 4))
     }
+    PropValueTag::Json => {
+      if offset + 5 > payload.len() {
+        return None;
+      }
+      let json_len = read_u32(payload, offset + 1) as usize;
+      if offset + 5 + json_len > payload.len() {
+        return None;
+      }
+      let doc = serde_json::from_slice(&payload[offset + 5..offset + 5 + json_len]).ok()?;
+      Some((PropValue::Json(doc), 5 + json_len))
+    }
   }
 }
 
@@ -1106,6 +1178,20 @@ mod tests {
     assert_eq!(data.dst, 2);
   }
 
+  #[test]
+  fn test_set_metadata_payload() {
+    let payload = build_set_metadata_payload("kite:counter", Some(&[1, 2, 3]));
+    let data = parse_set_metadata_payload(&payload).expect("expected value");
+    assert_eq!(data.key, "kite:counter");
+    assert_eq!(data.value, Some(vec![1, 2, 3]));
+
+    let payload = build_set_metadata_payload("gone", None);
+    let data = parse_set_metadata_payload(&payload).expect("expected value");
+    assert_eq!(data.key, "gone");
+    assert_eq!(data.value, None);
+    assert!(parse_set_metadata_payload(&payload[..payload.len() - 1]).is_none());
+  }
+
   #[test]
   fn test_create_nodes_batch_payload() {
     let entries = vec![(1, Some("a")), (2, None), (3, Some("ccc"))];
@@ -1216,6 +1302,14 @@ mod tests {
     assert_eq!(data.value, PropValue::String("hello world".to_string()));
   }
 
+  #[test]
+  fn test_prop_value_json() {
+    let value = PropValue::Json(serde_json::json!({"tags": ["a", "b"], "n": 1.5}));
+    let payload = build_set_node_prop_payload(7, 3, &value);
+    let data = parse_set_node_prop_payload(&payload).expect("expected value");
+    assert_eq!(data.value, value);
+  }
+
   #[test]
   fn test_prop_value_i64() {
     let value = PropValue::I64(-12345);
@@ -2,7 +2,8 @@
 //!
 //! Uses thiserror for ergonomic error handling
 
-use crate::types::{NodeId, TxId};
+use crate::mvcc::ConflictInfo;
+use crate::types::{NodeId, PropValueTag, TxId};
 use std::borrow::Cow;
 use thiserror::Error;
 
@@ -37,14 +38,30 @@ pub enum KiteError {
   #[error("Duplicate key: {0}")]
   DuplicateKey(String),
 
-  /// Transaction conflict (write-write conflict)
-  #[error("Transaction {txid} conflict on keys: {keys:?}")]
-  Conflict { txid: TxId, keys: Vec<String> },
+  /// Transaction conflict (read-write or write-write)
+  ///
+  /// `details` names the category of each key and the transaction whose
+  /// commit won; it is empty when only the keys are known. Match it with
+  /// `..` so later fields are not a breaking change.
+  #[non_exhaustive]
+  #[error(
+    "Transaction {txid} conflict on keys: {keys:?}{}",
+    describe_conflicts(details)
+  )]
+  Conflict {
+    txid: TxId,
+    keys: Vec<String>,
+    details: Vec<ConflictInfo>,
+  },
 
   /// WAL buffer is full, checkpoint required
   #[error("WAL buffer full: checkpoint required before continuing writes")]
   WalBufferFull,
 
+  /// WAL holds committed changes the snapshot doesn't have yet
+  #[error("WAL holds changes not in the snapshot: checkpoint required first")]
+  UncheckpointedChanges,
+
   /// Attempted write on read-only database
   #[error("Database is read-only")]
   ReadOnly,
@@ -65,10 +82,30 @@ pub enum KiteError {
   #[error("No active transaction")]
   NoTransaction,
 
+  /// Requested snapshot generation is outside the retained history
+  #[error("Generation {generation} is not readable (retained: {oldest}..={latest})")]
+  GenerationUnavailable {
+    generation: u64,
+    oldest: u64,
+    latest: u64,
+  },
+
+  /// A cursor's position no longer means the same thing in this database
+  #[error("Cursor invalidated: {0}")]
+  CursorInvalidated(String),
+
   /// Transaction already exists
   #[error("Transaction already in progress")]
   TransactionInProgress,
 
+  /// Outer commit of a transaction whose nested transaction rolled back
+  #[error("Transaction was marked rollback-only by a nested rollback")]
+  RollbackOnly,
+
+  /// Rollback to a savepoint the transaction doesn't have
+  #[error("Unknown savepoint: {0}")]
+  UnknownSavepoint(String),
+
   /// Database already closed
   #[error("Database is closed")]
   DatabaseClosed,
@@ -117,6 +154,18 @@ pub enum KiteError {
   #[error("Invalid query: {0}")]
   InvalidQuery(Cow<'static, str>),
 
+  /// Property holds a different type than the caller asked for
+  #[error("Property '{prop}' is {got:?}, expected {expected:?}")]
+  PropTypeMismatch {
+    prop: String,
+    expected: PropValueTag,
+    got: PropValueTag,
+  },
+
+  /// Schema constraint violated by a write (e.g. value outside an enum's allowed set)
+  #[error("Constraint violation: {0}")]
+  Constraint(String),
+
   /// Replication metadata/record validation failure
   #[error("Invalid replication state: {0}")]
   InvalidReplication(String),
@@ -130,7 +179,11 @@ pub type Result<T> = std::result::Result<T, KiteError>;
 impl KiteError {
   /// Create a conflict error
   pub fn conflict(txid: TxId, keys: Vec<String>) -> Self {
-    KiteError::Conflict { txid, keys }
+    KiteError::Conflict {
+      txid,
+      keys,
+      details: Vec::new(),
+    }
   }
 
   /// Check if this is a conflict error
@@ -145,6 +198,22 @@ impl KiteError {
       _ => None,
     }
   }
+
+  /// Get per-key conflict details if this is a conflict error
+  pub fn conflict_details(&self) -> Option<&[ConflictInfo]> {
+    match self {
+      KiteError::Conflict { details, .. } => Some(details),
+      _ => None,
+    }
+  }
+}
+
+fn describe_conflicts(details: &[ConflictInfo]) -> String {
+  if details.is_empty() {
+    return String::new();
+  }
+  let parts: Vec<String> = details.iter().map(ConflictInfo::to_string).collect();
+  format!(" [{}]", parts.join("; "))
 }
 
 // ============================================================================
@@ -1,16 +1,18 @@
 //! Export and Import utilities
 //!
-//! JSON and JSONL export/import for SingleFileDB.
+//! JSON and JSONL export/import for SingleFileDB, plus sharded JSONL
+//! directories for exports too large for one file.
 
 use serde::{Deserialize, Serialize};
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::fs::File;
-use std::io::{BufReader, BufWriter, Write};
+use std::io::{BufRead, BufReader, BufWriter, Write};
 use std::path::Path;
 
-use crate::core::single_file::SingleFileDB;
+use crate::api::kite::ARCHIVED_AT_PROP;
+use crate::core::single_file::{SingleFileDB, SingleFileTxGuard};
 use crate::error::{KiteError, Result};
-use crate::types::{ETypeId, NodeId, PropKeyId, PropValue};
+use crate::types::{ETypeId, LabelId, NodeId, PropKeyId, PropValue};
 
 // =============================================================================
 // Types
@@ -21,7 +23,20 @@ pub struct ExportOptions {
   pub include_nodes: bool,
   pub include_edges: bool,
   pub include_schema: bool,
+  /// Export node vector embeddings (off by default; they can dwarf the rest)
+  pub include_vectors: bool,
   pub pretty: bool,
+  /// Only export nodes carrying any of these labels, and edges whose ends
+  /// are both exported (unknown names match nothing)
+  #[serde(default)]
+  pub labels: Option<Vec<String>>,
+  /// Nodes per shard file for [`export_sharded`]
+  #[serde(default = "default_nodes_per_shard")]
+  pub nodes_per_shard: usize,
+}
+
+fn default_nodes_per_shard() -> usize {
+  100_000
 }
 
 impl Default for ExportOptions {
@@ -30,7 +45,10 @@ impl Default for ExportOptions {
       include_nodes: true,
       include_edges: true,
       include_schema: true,
+      include_vectors: false,
       pretty: false,
+      labels: None,
+      nodes_per_shard: default_nodes_per_shard(),
     }
   }
 }
@@ -56,11 +74,20 @@ pub struct ExportedPropValue {
   pub value: serde_json::Value,
 }
 
+// Maps are ordered so the same data always exports to the same bytes
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ExportedNode {
   pub id: u64,
   pub key: Option<String>,
-  pub props: HashMap<String, ExportedPropValue>,
+  pub props: BTreeMap<String, ExportedPropValue>,
+  /// When the node was archived (ms since the Unix epoch); the archive flag
+  /// isn't a user prop, so it stays out of `props`
+  #[serde(default, skip_serializing_if = "Option::is_none")]
+  pub archived_at: Option<i64>,
+  /// Vector embeddings by prop key name
+  #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+  pub vectors: BTreeMap<String, Vec<f32>>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -69,14 +96,14 @@ pub struct ExportedEdge {
   pub dst: u64,
   pub etype: u32,
   pub etype_name: Option<String>,
-  pub props: HashMap<String, ExportedPropValue>,
+  pub props: BTreeMap<String, ExportedPropValue>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, Default)]
 pub struct ExportedSchema {
-  pub labels: HashMap<u32, String>,
-  pub etypes: HashMap<u32, String>,
-  pub prop_keys: HashMap<u32, String>,
+  pub labels: BTreeMap<u32, String>,
+  pub etypes: BTreeMap<u32, String>,
+  pub prop_keys: BTreeMap<u32, String>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -106,6 +133,29 @@ pub struct ImportResult {
   pub node_count: usize,
   pub edge_count: usize,
   pub skipped: usize,
+  /// The progress callback stopped the import; counts cover committed batches
+  pub cancelled: bool,
+}
+
+/// Running totals passed to an import progress callback
+#[derive(Debug, Clone, Copy, Default, Serialize, Deserialize)]
+pub struct ImportProgress {
+  pub nodes_imported: usize,
+  pub edges_imported: usize,
+}
+
+/// Manifest file naming the shards of a sharded export
+pub const SHARD_MANIFEST_FILE: &str = "manifest.json";
+
+/// Contents of [`SHARD_MANIFEST_FILE`]
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShardManifest {
+  pub version: u32,
+  pub exported_at: String,
+  pub schema: ExportedSchema,
+  /// Shard file names, relative to the manifest's directory
+  pub shards: Vec<String>,
+  pub stats: ExportStats,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -154,6 +204,10 @@ fn serialize_prop_value(value: &PropValue) -> ExportedPropValue {
           .collect(),
       ),
     },
+    PropValue::Json(v) => ExportedPropValue {
+      r#type: "json".to_string(),
+      value: v.clone(),
+    },
   }
 }
 
@@ -173,6 +227,7 @@ fn deserialize_prop_value(value: &ExportedPropValue) -> PropValue {
       }
       PropValue::VectorF32(vec)
     }
+    "json" => PropValue::Json(value.value.clone()),
     _ => PropValue::Null,
   }
 }
@@ -205,6 +260,22 @@ fn etype_name_single(db: &SingleFileDB, etype_id: ETypeId) -> String {
     .unwrap_or_else(|| format!("etype_{etype_id}"))
 }
 
+/// Nodes carrying any of the named labels
+fn nodes_with_any_label(db: &SingleFileDB, names: &[String]) -> HashSet<NodeId> {
+  let label_ids: Vec<LabelId> = names.iter().filter_map(|name| db.label_id(name)).collect();
+  if label_ids.is_empty() {
+    return HashSet::new();
+  }
+  db.list_nodes()
+    .into_iter()
+    .filter(|&node_id| {
+      label_ids
+        .iter()
+        .any(|&label_id| db.node_has_label(node_id, label_id))
+    })
+    .collect()
+}
+
 pub fn export_to_object_single(
   db: &SingleFileDB,
   options: ExportOptions,
@@ -219,27 +290,63 @@ pub fn export_to_object_single(
   let mut nodes = Vec::new();
   let mut edges = Vec::new();
 
+  let vector_keys: Vec<PropKeyId> = if options.include_vectors {
+    let mut keys = db.vector_prop_keys();
+    keys.extend(delta.pending_vectors.keys().map(|&(_, key_id)| key_id));
+    let mut keys: Vec<PropKeyId> = keys.into_iter().collect();
+    keys.sort_unstable();
+    keys
+  } else {
+    Vec::new()
+  };
+
+  let selected = options
+    .labels
+    .as_deref()
+    .map(|names| nodes_with_any_label(db, names));
+  let is_selected = |node_id: NodeId| selected.as_ref().is_none_or(|s| s.contains(&node_id));
+
   if options.include_nodes {
-    for node_id in db.list_nodes() {
+    let mut node_ids = db.list_nodes();
+    node_ids.retain(|&node_id| is_selected(node_id));
+    node_ids.sort_unstable();
+    for node_id in node_ids {
       let key = db.node_key(node_id);
-      let mut props = HashMap::new();
+      let mut props = BTreeMap::new();
+      let mut archived_at = None;
       if let Some(props_by_id) = db.node_props(node_id) {
         for (key_id, value) in props_by_id {
           let name = prop_key_name_single(db, key_id);
-          props.insert(name, serialize_prop_value(&value));
+          match value {
+            PropValue::I64(ms) if name == ARCHIVED_AT_PROP => archived_at = Some(ms),
+            value => {
+              props.insert(name, serialize_prop_value(&value));
+            }
+          }
+        }
+      }
+      let mut vectors = BTreeMap::new();
+      for &key_id in &vector_keys {
+        if let Some(vector) = db.node_vector(node_id, key_id) {
+          vectors.insert(prop_key_name_single(db, key_id), vector.to_vec());
         }
       }
       nodes.push(ExportedNode {
         id: node_id,
         key,
         props,
+        archived_at,
+        vectors,
       });
     }
   }
 
   if options.include_edges {
-    for edge in db.list_edges(None) {
-      let mut props = HashMap::new();
+    let mut all_edges = db.list_edges(None);
+    all_edges.retain(|edge| is_selected(edge.src) && is_selected(edge.dst));
+    all_edges.sort_unstable_by_key(|edge| (edge.src, edge.etype, edge.dst));
+    for edge in all_edges {
+      let mut props = BTreeMap::new();
       if let Some(props_by_id) = db.edge_props(edge.src, edge.etype, edge.dst) {
         for (key_id, value) in props_by_id {
           let name = prop_key_name_single(db, key_id);
@@ -302,91 +409,134 @@ pub fn export_to_jsonl<P: AsRef<Path>>(data: &ExportedDatabase, path: P) -> Resu
   let file = File::create(path).map_err(KiteError::Io)?;
   let mut writer = BufWriter::new(file);
 
-  let header = JsonLine::<serde_json::Value> {
-    r#type: "header".to_string(),
-    data: Some(serde_json::json!({
-      "version": data.version,
-      "exportedAt": data.exported_at,
-    })),
-  };
-  writeln!(
-    writer,
-    "{}",
-    serde_json::to_string(&header).map_err(|e| KiteError::Serialization(e.to_string()))?
-  )
-  .map_err(KiteError::Io)?;
-
-  let schema = JsonLine {
-    r#type: "schema".to_string(),
-    data: Some(
-      serde_json::to_value(&data.schema).map_err(|e| KiteError::Serialization(e.to_string()))?,
-    ),
-  };
-  writeln!(
-    writer,
-    "{}",
-    serde_json::to_string(&schema).map_err(|e| KiteError::Serialization(e.to_string()))?
-  )
-  .map_err(KiteError::Io)?;
-
+  let header = serde_json::json!({
+    "version": data.version,
+    "exportedAt": data.exported_at,
+  });
+  write_json_line(&mut writer, "header", &header)?;
+  write_json_line(&mut writer, "schema", &data.schema)?;
   for node in &data.nodes {
-    let line = JsonLine {
-      r#type: "node".to_string(),
-      data: Some(serde_json::to_value(node).map_err(|e| KiteError::Serialization(e.to_string()))?),
-    };
-    writeln!(
-      writer,
-      "{}",
-      serde_json::to_string(&line).map_err(|e| KiteError::Serialization(e.to_string()))?
-    )
-    .map_err(KiteError::Io)?;
+    write_json_line(&mut writer, "node", node)?;
+  }
+  for edge in &data.edges {
+    write_json_line(&mut writer, "edge", edge)?;
   }
 
+  writer.flush().map_err(KiteError::Io)?;
+  Ok(ExportResult {
+    node_count: data.stats.node_count,
+    edge_count: data.stats.edge_count,
+  })
+}
+
+/// Write an export across several JSONL shard files plus a manifest
+///
+/// `dir` is created if needed. Each shard holds up to `nodes_per_shard`
+/// nodes followed by the edges whose source node it holds, so edges can
+/// point at nodes in other shards; [`import_sharded`] handles that.
+pub fn export_sharded<P: AsRef<Path>>(
+  data: &ExportedDatabase,
+  dir: P,
+  nodes_per_shard: usize,
+) -> Result<ExportResult> {
+  let dir = dir.as_ref();
+  std::fs::create_dir_all(dir).map_err(KiteError::Io)?;
+
+  let node_chunks: Vec<&[ExportedNode]> = data.nodes.chunks(nodes_per_shard.max(1)).collect();
+  let shard_count = node_chunks.len().max(1);
+  // Nodes are exported in ID order, so each shard after the first starts
+  // at a known ID and an edge's source picks its shard
+  let shard_starts: Vec<u64> = node_chunks
+    .iter()
+    .skip(1)
+    .map(|nodes| nodes[0].id)
+    .collect();
+  let mut shard_edges: Vec<Vec<&ExportedEdge>> = vec![Vec::new(); shard_count];
   for edge in &data.edges {
-    let line = JsonLine {
-      r#type: "edge".to_string(),
-      data: Some(serde_json::to_value(edge).map_err(|e| KiteError::Serialization(e.to_string()))?),
-    };
-    writeln!(
-      writer,
-      "{}",
-      serde_json::to_string(&line).map_err(|e| KiteError::Serialization(e.to_string()))?
-    )
-    .map_err(KiteError::Io)?;
+    let shard = shard_starts.partition_point(|&start| start <= edge.src);
+    shard_edges[shard].push(edge);
+  }
+
+  let mut shards = Vec::with_capacity(shard_count);
+  for (index, edges) in shard_edges.iter().enumerate() {
+    let name = format!("shard-{index:05}.jsonl");
+    let file = File::create(dir.join(&name)).map_err(KiteError::Io)?;
+    let mut writer = BufWriter::new(file);
+    for node in node_chunks.get(index).copied().unwrap_or_default() {
+      write_json_line(&mut writer, "node", node)?;
+    }
+    for edge in edges {
+      write_json_line(&mut writer, "edge", edge)?;
+    }
+    writer.flush().map_err(KiteError::Io)?;
+    shards.push(name);
   }
 
+  let manifest = ShardManifest {
+    version: data.version,
+    exported_at: data.exported_at.clone(),
+    schema: data.schema.clone(),
+    shards,
+    stats: data.stats.clone(),
+  };
+  let file = File::create(dir.join(SHARD_MANIFEST_FILE)).map_err(KiteError::Io)?;
+  let mut writer = BufWriter::new(file);
+  serde_json::to_writer_pretty(&mut writer, &manifest)
+    .map_err(|e| KiteError::Serialization(e.to_string()))?;
   writer.flush().map_err(KiteError::Io)?;
+
   Ok(ExportResult {
     node_count: data.stats.node_count,
     edge_count: data.stats.edge_count,
   })
 }
 
+fn write_json_line<T: Serialize>(writer: &mut impl Write, kind: &str, data: &T) -> Result<()> {
+  let line = JsonLine {
+    r#type: kind.to_string(),
+    data: Some(data),
+  };
+  let json = serde_json::to_string(&line).map_err(|e| KiteError::Serialization(e.to_string()))?;
+  writeln!(writer, "{json}").map_err(KiteError::Io)
+}
+
 pub fn import_from_object_single(
   db: &SingleFileDB,
   data: &ExportedDatabase,
   options: ImportOptions,
 ) -> Result<ImportResult> {
+  import_from_object_single_with_progress(db, data, options, |_| true)
+}
+
+/// Import with a progress callback
+///
+/// `on_progress` runs before each batch commits, with totals that include
+/// that batch. Returning `false` rolls the batch back and stops the import;
+/// earlier batches stay committed and the result has `cancelled` set.
+pub fn import_from_object_single_with_progress<F>(
+  db: &SingleFileDB,
+  data: &ExportedDatabase,
+  options: ImportOptions,
+  mut on_progress: F,
+) -> Result<ImportResult>
+where
+  F: FnMut(&ImportProgress) -> bool,
+{
   let mut propkey_name_to_id: HashMap<String, PropKeyId> = HashMap::new();
   let mut etype_name_to_id: HashMap<String, ETypeId> = HashMap::new();
 
+  // Schema definitions are WAL-logged, so they need a write transaction
+  let tx = db.begin_guard(false)?;
   for name in data.schema.prop_keys.values() {
-    let id = db
-      .propkey_id(name)
-      .unwrap_or_else(|| db.define_propkey(name).unwrap_or(0));
-    propkey_name_to_id.insert(name.clone(), id);
+    propkey_name_to_id.insert(name.clone(), db.define_propkey(name)?);
   }
   for name in data.schema.etypes.values() {
-    let id = db
-      .etype_id(name)
-      .unwrap_or_else(|| db.define_etype(name).unwrap_or(0));
-    etype_name_to_id.insert(name.clone(), id);
+    etype_name_to_id.insert(name.clone(), db.define_etype(name)?);
   }
   for name in data.schema.labels.values() {
-    let _ = db
-      .label_id(name)
-      .unwrap_or_else(|| db.define_label(name).unwrap_or(0));
+    db.define_label(name)?;
   }
+  tx.commit()?;
 
   let mut old_to_new: HashMap<NodeId, NodeId> = HashMap::new();
   let mut node_count = 0usize;
@@ -412,20 +562,55 @@ pub fn import_from_object_single(
         db.set_node_prop(node_id, key_id, value)?;
       }
     }
+    if let Some(archived_at) = node.archived_at {
+      let key_id = match db.propkey_id(ARCHIVED_AT_PROP) {
+        Some(id) => id,
+        None => db.define_propkey(ARCHIVED_AT_PROP)?,
+      };
+      db.set_node_prop(node_id, key_id, PropValue::I64(archived_at))?;
+    }
+    for (prop_name, vector) in &node.vectors {
+      let key_id = match propkey_name_to_id.get(prop_name) {
+        Some(&id) => id,
+        None => {
+          let id = match db.propkey_id(prop_name) {
+            Some(id) => id,
+            None => db.define_propkey(prop_name)?,
+          };
+          propkey_name_to_id.insert(prop_name.clone(), id);
+          id
+        }
+      };
+      db.set_node_vector(node_id, key_id, vector)?;
+    }
 
     old_to_new.insert(node.id as NodeId, node_id);
     node_count += 1;
     batch_count += 1;
 
     if batch_count >= options.batch_size {
-      tx.commit()?;
+      if !commit_batch(tx, node_count, 0, &mut on_progress)? {
+        return Ok(ImportResult {
+          node_count: node_count - batch_count,
+          edge_count: 0,
+          skipped,
+          cancelled: true,
+        });
+      }
       tx = db.begin_guard(false)?;
       batch_count = 0;
     }
   }
 
   if batch_count > 0 {
-    tx.commit()?;
+    if !commit_batch(tx, node_count, 0, &mut on_progress)? {
+      return Ok(ImportResult {
+        node_count: node_count - batch_count,
+        edge_count: 0,
+        skipped,
+        cancelled: true,
+      });
+    }
   } else {
     tx.rollback()?;
   }
@@ -454,14 +639,28 @@ pub fn import_from_object_single(
     batch_count += 1;
 
     if batch_count >= options.batch_size {
-      tx.commit()?;
+      if !commit_batch(tx, node_count, edge_count, &mut on_progress)? {
+        return Ok(ImportResult {
+          node_count,
+          edge_count: edge_count - batch_count,
+          skipped,
+          cancelled: true,
+        });
+      }
       tx = db.begin_guard(false)?;
       batch_count = 0;
     }
   }
 
   if batch_count > 0 {
-    tx.commit()?;
+    if !commit_batch(tx, node_count, edge_count, &mut on_progress)? {
+      return Ok(ImportResult {
+        node_count,
+        edge_count: edge_count - batch_count,
+        skipped,
+        cancelled: true,
+      });
+    }
   } else {
     tx.rollback()?;
   }
@@ -470,9 +669,35 @@ pub fn import_from_object_single(
     node_count,
     edge_count,
     skipped,
+    cancelled: false,
   })
 }
 
+/// Report progress, then commit the batch, or roll it back if cancelled
+///
+/// Returns whether the batch was committed.
+fn commit_batch<F>(
+  tx: SingleFileTxGuard<'_>,
+  nodes_imported: usize,
+  edges_imported: usize,
+  on_progress: &mut F,
+) -> Result<bool>
+where
+  F: FnMut(&ImportProgress) -> bool,
+{
+  let progress = ImportProgress {
+    nodes_imported,
+    edges_imported,
+  };
+  if on_progress(&progress) {
+    tx.commit()?;
+    Ok(true)
+  } else {
+    tx.rollback()?;
+    Ok(false)
+  }
+}
+
 pub fn import_from_json<P: AsRef<Path>>(path: P) -> Result<ExportedDatabase> {
   let file = File::open(path).map_err(KiteError::Io)?;
   let reader = BufReader::new(file);
@@ -480,3 +705,318 @@ pub fn import_from_json<P: AsRef<Path>>(path: P) -> Result<ExportedDatabase> {
     serde_json::from_reader(reader).map_err(|e| KiteError::Serialization(e.to_string()))?;
   Ok(data)
 }
+
+/// Read a sharded export written by [`export_sharded`]
+///
+/// Every shard listed in the manifest is read, in any order, into one
+/// [`ExportedDatabase`]. Importing that with [`import_from_object_single`]
+/// creates all nodes before any edge, so edges whose endpoints live in
+/// other shards still resolve.
+pub fn import_sharded<P: AsRef<Path>>(dir: P) -> Result<ExportedDatabase> {
+  let dir = dir.as_ref();
+  let file = File::open(dir.join(SHARD_MANIFEST_FILE)).map_err(KiteError::Io)?;
+  let manifest: ShardManifest = serde_json::from_reader(BufReader::new(file))
+    .map_err(|e| KiteError::Serialization(e.to_string()))?;
+
+  let mut nodes = Vec::new();
+  let mut edges = Vec::new();
+  for name in &manifest.shards {
+    let file = File::open(dir.join(name)).map_err(KiteError::Io)?;
+    for line in BufReader::new(file).lines() {
+      let line = line.map_err(KiteError::Io)?;
+      if line.trim().is_empty() {
+        continue;
+      }
+      let parsed: JsonLine<serde_json::Value> =
+        serde_json::from_str(&line).map_err(|e| KiteError::Serialization(e.to_string()))?;
+      let Some(data) = parsed.data else {
+        continue;
+      };
+      match parsed.r#type.as_str() {
+        "node" => nodes
+          .push(serde_json::from_value(data).map_err(|e| KiteError::Serialization(e.to_string()))?),
+        "edge" => edges
+          .push(serde_json::from_value(data).map_err(|e| KiteError::Serialization(e.to_string()))?),
+        other => {
+          return Err(KiteError::Serialization(format!(
+            "Unexpected line type in shard {name}: {other}"
+          )))
+        }
+      }
+    }
+  }
+
+  let stats = ExportStats {
+    node_count: nodes.len(),
+    edge_count: edges.len(),
+  };
+  Ok(ExportedDatabase {
+    version: manifest.version,
+    exported_at: manifest.exported_at,
+    schema: manifest.schema,
+    nodes,
+    edges,
+    stats,
+  })
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::single_file::{close_single_file, open_single_file, SingleFileOpenOptions};
+  use tempfile::tempdir;
+
+  #[test]
+  fn test_vectors_survive_export_import_round_trip() {
+    let temp_dir = tempdir().expect("expected value");
+    let src_path = temp_dir.path().join("src.kitedb");
+    let dst_path = temp_dir.path().join("dst.kitedb");
+    let json_path = temp_dir.path().join("export.json");
+
+    let src = open_single_file(&src_path, SingleFileOpenOptions::new()).expect("expected value");
+    src.begin(false).expect("expected value");
+    let a = src.create_node(Some("doc:a")).expect("expected value");
+    src.create_node(Some("doc:b")).expect("expected value");
+    let embedding = src.define_propkey("embedding").expect("expected value");
+    src
+      .set_node_vector(a, embedding, &[0.1, 0.2, 0.3])
+      .expect("expected value");
+    src.commit().expect("expected value");
+    src.checkpoint().expect("expected value");
+    let stored = src.node_vector(a, embedding).expect("expected value");
+
+    // Vectors are opt-in
+    let data = export_to_object_single(&src, ExportOptions::default()).expect("expected value");
+    assert!(data.nodes.iter().all(|node| node.vectors.is_empty()));
+
+    let options = ExportOptions {
+      include_vectors: true,
+      ..ExportOptions::default()
+    };
+    let data = export_to_object_single(&src, options).expect("expected value");
+    export_to_json(&data, &json_path, false).expect("expected value");
+    close_single_file(src).expect("expected value");
+
+    let dst = open_single_file(&dst_path, SingleFileOpenOptions::new()).expect("expected value");
+    let data = import_from_json(&json_path).expect("expected value");
+    let result =
+      import_from_object_single(&dst, &data, ImportOptions::default()).expect("expected value");
+    assert_eq!(result.node_count, 2);
+
+    let embedding = dst.propkey_id("embedding").expect("expected value");
+    let a = dst.node_by_key("doc:a").expect("expected value");
+    let b = dst.node_by_key("doc:b").expect("expected value");
+    let vector = dst.node_vector(a, embedding).expect("expected value");
+    // The store re-normalizes on insert, so allow for rounding
+    assert_eq!(vector.len(), stored.len());
+    for (got, want) in vector.iter().zip(stored.iter()) {
+      assert!((got - want).abs() < 1e-6);
+    }
+    assert!(dst.node_vector(b, embedding).is_none());
+    close_single_file(dst).expect("expected value");
+  }
+
+  #[test]
+  fn test_import_progress_cancel_rolls_back_current_batch() {
+    let temp_dir = tempdir().expect("expected value");
+    let src_path = temp_dir.path().join("src.kitedb");
+    let dst_path = temp_dir.path().join("dst.kitedb");
+
+    let src = open_single_file(&src_path, SingleFileOpenOptions::new()).expect("expected value");
+    src.begin(false).expect("expected value");
+    for i in 0..10 {
+      src
+        .create_node(Some(&format!("n:{i}")))
+        .expect("expected value");
+    }
+    src.commit().expect("expected value");
+    let data = export_to_object_single(&src, ExportOptions::default()).expect("expected value");
+    close_single_file(src).expect("expected value");
+
+    let dst = open_single_file(&dst_path, SingleFileOpenOptions::new()).expect("expected value");
+    let options = ImportOptions {
+      batch_size: 4,
+      ..ImportOptions::default()
+    };
+    let mut reports = Vec::new();
+    let result = import_from_object_single_with_progress(&dst, &data, options, |progress| {
+      reports.push(progress.nodes_imported);
+      // Stop while the second batch is pending
+      reports.len() < 2
+    })
+    .expect("expected value");
+
+    assert_eq!(reports, vec![4, 8]);
+    assert!(result.cancelled);
+    assert_eq!(result.node_count, 4);
+    assert_eq!(dst.count_nodes(), 4);
+    close_single_file(dst).expect("expected value");
+  }
+
+  #[test]
+  fn test_export_is_deterministic() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("src.kitedb");
+
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+    db.begin(false).expect("expected value");
+    let knows = db.define_etype("KNOWS").expect("expected value");
+    let keys: Vec<PropKeyId> = ["name", "age", "zone", "bio", "rank"]
+      .iter()
+      .map(|name| db.define_propkey(name).expect("expected value"))
+      .collect();
+    let mut nodes = Vec::new();
+    for i in 0..20 {
+      let node = db
+        .create_node(Some(&format!("n:{i}")))
+        .expect("expected value");
+      for (j, &key) in keys.iter().enumerate() {
+        db.set_node_prop(node, key, PropValue::I64((i * j) as i64))
+          .expect("expected value");
+      }
+      nodes.push(node);
+    }
+    for (i, &src) in nodes.iter().enumerate() {
+      let dst = nodes[(i * 7 + 3) % nodes.len()];
+      db.add_edge(src, knows, dst).expect("expected value");
+      for &key in &keys {
+        db.set_edge_prop(src, knows, dst, key, PropValue::Bool(i % 2 == 0))
+          .expect("expected value");
+      }
+    }
+    db.commit().expect("expected value");
+
+    let export = || {
+      let mut data =
+        export_to_object_single(&db, ExportOptions::default()).expect("expected value");
+      // The timestamp is the only field allowed to differ
+      data.exported_at = "0".to_string();
+      serde_json::to_vec(&data).expect("expected value")
+    };
+    let first = export();
+    assert_eq!(first, export());
+
+    let data = export_to_object_single(&db, ExportOptions::default()).expect("expected value");
+    assert!(data.nodes.windows(2).all(|w| w[0].id < w[1].id));
+    assert!(data
+      .edges
+      .windows(2)
+      .all(|w| (w[0].src, w[0].etype, w[0].dst) < (w[1].src, w[1].etype, w[1].dst)));
+    close_single_file(db).expect("expected value");
+  }
+
+  #[test]
+  fn test_sharded_export_round_trip() {
+    let temp_dir = tempdir().expect("expected value");
+    let shard_dir = temp_dir.path().join("shards");
+
+    let src = open_single_file(
+      temp_dir.path().join("src.kitedb"),
+      SingleFileOpenOptions::new(),
+    )
+    .expect("expected value");
+    src.begin(false).expect("expected value");
+    let knows = src.define_etype("KNOWS").expect("expected value");
+    let rank = src.define_propkey("rank").expect("expected value");
+    let nodes: Vec<NodeId> = (0..7)
+      .map(|i| {
+        let node = src
+          .create_node(Some(&format!("n:{i}")))
+          .expect("expected value");
+        src
+          .set_node_prop(node, rank, PropValue::I64(i))
+          .expect("expected value");
+        node
+      })
+      .collect();
+    // Edges in both directions across shard boundaries
+    src
+      .add_edge(nodes[6], knows, nodes[0])
+      .expect("expected value");
+    src
+      .add_edge(nodes[1], knows, nodes[5])
+      .expect("expected value");
+    src.commit().expect("expected value");
+
+    let data = export_to_object_single(&src, ExportOptions::default()).expect("expected value");
+    let result = export_sharded(&data, &shard_dir, 3).expect("expected value");
+    assert_eq!((result.node_count, result.edge_count), (7, 2));
+    close_single_file(src).expect("expected value");
+
+    let mut manifest: ShardManifest = serde_json::from_reader(
+      File::open(shard_dir.join(SHARD_MANIFEST_FILE)).expect("expected value"),
+    )
+    .expect("expected value");
+    assert_eq!(manifest.shards.len(), 3);
+
+    // Shard order doesn't matter: nodes are all imported before edges
+    manifest.shards.reverse();
+    let file = File::create(shard_dir.join(SHARD_MANIFEST_FILE)).expect("expected value");
+    serde_json::to_writer(file, &manifest).expect("expected value");
+
+    let data = import_sharded(&shard_dir).expect("expected value");
+    assert_eq!((data.nodes.len(), data.edges.len()), (7, 2));
+    let dst = open_single_file(
+      temp_dir.path().join("dst.kitedb"),
+      SingleFileOpenOptions::new(),
+    )
+    .expect("expected value");
+    let result =
+      import_from_object_single(&dst, &data, ImportOptions::default()).expect("expected value");
+    assert_eq!((result.node_count, result.edge_count), (7, 2));
+
+    let knows = dst.etype_id("KNOWS").expect("expected value");
+    let n0 = dst.node_by_key("n:0").expect("expected value");
+    let n1 = dst.node_by_key("n:1").expect("expected value");
+    let n5 = dst.node_by_key("n:5").expect("expected value");
+    let n6 = dst.node_by_key("n:6").expect("expected value");
+    assert!(dst.edge_exists(n6, knows, n0));
+    assert!(dst.edge_exists(n1, knows, n5));
+    let rank = dst.propkey_id("rank").expect("expected value");
+    assert_eq!(dst.node_prop(n6, rank), Some(PropValue::I64(6)));
+    close_single_file(dst).expect("expected value");
+  }
+
+  #[test]
+  fn test_export_filters_by_label() {
+    let temp_dir = tempdir().expect("expected value");
+    let db = open_single_file(
+      temp_dir.path().join("db.kitedb"),
+      SingleFileOpenOptions::new(),
+    )
+    .expect("expected value");
+    db.begin(false).expect("expected value");
+    let public = db.define_label("Public").expect("expected value");
+    let shared = db.define_label("Shared").expect("expected value");
+    let private = db.define_label("Private").expect("expected value");
+    let knows = db.define_etype("KNOWS").expect("expected value");
+    let a = db.create_node(Some("a")).expect("expected value");
+    let b = db.create_node(Some("b")).expect("expected value");
+    let c = db.create_node(Some("c")).expect("expected value");
+    db.add_node_label(a, public).expect("expected value");
+    db.add_node_label(b, shared).expect("expected value");
+    db.add_node_label(c, private).expect("expected value");
+    db.add_edge(a, knows, b).expect("expected value");
+    db.add_edge(b, knows, c).expect("expected value");
+    db.commit().expect("expected value");
+
+    let options = ExportOptions {
+      labels: Some(vec!["Public".to_string(), "Shared".to_string()]),
+      ..ExportOptions::default()
+    };
+    let data = export_to_object_single(&db, options).expect("expected value");
+    let ids: Vec<u64> = data.nodes.iter().map(|node| node.id).collect();
+    assert_eq!(ids, vec![a, b]);
+    assert_eq!(data.edges.len(), 1);
+    assert_eq!((data.edges[0].src, data.edges[0].dst), (a, b));
+
+    let options = ExportOptions {
+      labels: Some(vec!["Missing".to_string()]),
+      ..ExportOptions::default()
+    };
+    let data = export_to_object_single(&db, options).expect("expected value");
+    assert!(data.nodes.is_empty() && data.edges.is_empty());
+
+    close_single_file(db).expect("expected value");
+  }
+}
@@ -45,6 +45,10 @@ use crate::replication::primary::PrimaryReplicationStatus;
 use crate::replication::replica::ReplicaReplicationStatus;
 use crate::types::DeltaState;
 
+mod rate;
+
+pub use rate::{OpRateTracker, RATE_WINDOW_SECS};
+
 /// Cache layer metrics
 #[derive(Debug, Clone)]
 pub struct CacheLayerMetrics {
@@ -148,6 +152,10 @@ pub struct DatabaseMetrics {
   pub mvcc: Option<MvccMetrics>,
   pub replication: ReplicationMetrics,
   pub memory: MemoryMetrics,
+  /// Mutations per second over the last `RATE_WINDOW_SECS` seconds
+  pub write_rate: f64,
+  /// Reads per second over the last `RATE_WINDOW_SECS` seconds
+  pub read_rate: f64,
   pub collected_at_ms: i64,
 }
 
@@ -314,6 +322,8 @@ pub fn collect_metrics_single_file(db: &SingleFileDB) -> DatabaseMetrics {
       snapshot_bytes,
       total_estimate_bytes: delta_bytes + cache_bytes + snapshot_bytes,
     },
+    write_rate: db.write_rate.rate(),
+    read_rate: db.read_rate.rate(),
     collected_at_ms: system_time_to_millis(SystemTime::now()),
   }
 }
@@ -2304,8 +2314,7 @@ pub fn health_check_single_file(db: &SingleFileDB) -> HealthCheckResult {
     message: "Database handle is valid".to_string(),
   });
 
-  let delta = db.delta.read();
-  let delta_size = delta_health_size(&delta);
+  let delta_size = delta_health_size(&db.delta.read());
   let delta_ok = delta_size < 100000;
   checks.push(HealthCheckEntry {
     name: "delta_size".to_string(),
@@ -2342,6 +2351,17 @@ pub fn health_check_single_file(db: &SingleFileDB) -> HealthCheckResult {
     });
   }
 
+  let vector_issues = db.vector_issues();
+  checks.push(HealthCheckEntry {
+    name: "vector_consistency".to_string(),
+    passed: vector_issues.is_empty(),
+    message: if vector_issues.is_empty() {
+      "Vectors have consistent dimensions and finite values".to_string()
+    } else {
+      vector_issues.join("; ")
+    },
+  });
+
   if db.read_only {
     checks.push(HealthCheckEntry {
       name: "write_access".to_string(),
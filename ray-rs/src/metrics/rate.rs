@@ -0,0 +1,115 @@
+//! Rolling operation-rate tracking.
+//!
+//! Counts land in per-second buckets over a fixed sliding window, so
+//! recording is a couple of relaxed atomic ops and reading a rate needs no
+//! locks.
+
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::time::Instant;
+
+/// Width of the sliding window, in seconds
+pub const RATE_WINDOW_SECS: u64 = 10;
+
+const BUCKETS: usize = RATE_WINDOW_SECS as usize;
+
+/// Ops/sec over the last `RATE_WINDOW_SECS` seconds
+///
+/// Counts that race with a bucket rolling over to a new second may be
+/// dropped; the rate is meant for dashboards, not accounting.
+#[derive(Debug)]
+pub struct OpRateTracker {
+  origin: Instant,
+  /// Milliseconds since `origin` at the last reset
+  reset_at_ms: AtomicU64,
+  counts: [AtomicU64; BUCKETS],
+  /// Second (since `origin`, plus one) each bucket currently holds; 0 = empty
+  epochs: [AtomicU64; BUCKETS],
+}
+
+impl Default for OpRateTracker {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl OpRateTracker {
+  pub fn new() -> Self {
+    Self {
+      origin: Instant::now(),
+      reset_at_ms: AtomicU64::new(0),
+      counts: std::array::from_fn(|_| AtomicU64::new(0)),
+      epochs: std::array::from_fn(|_| AtomicU64::new(0)),
+    }
+  }
+
+  fn now_ms(&self) -> u64 {
+    self.origin.elapsed().as_millis() as u64
+  }
+
+  /// Record `n` operations
+  pub fn record(&self, n: u64) {
+    let sec = self.now_ms() / 1000;
+    let idx = (sec % RATE_WINDOW_SECS) as usize;
+    let tag = sec + 1;
+    let seen = self.epochs[idx].load(Ordering::Relaxed);
+    if seen != tag
+      && self.epochs[idx]
+        .compare_exchange(seen, tag, Ordering::AcqRel, Ordering::Relaxed)
+        .is_ok()
+    {
+      self.counts[idx].store(0, Ordering::Relaxed);
+    }
+    self.counts[idx].fetch_add(n, Ordering::Relaxed);
+  }
+
+  /// Current rate in operations per second
+  pub fn rate(&self) -> f64 {
+    let now_ms = self.now_ms();
+    let sec = now_ms / 1000;
+    let oldest = (sec + 1).saturating_sub(RATE_WINDOW_SECS);
+
+    let total: u64 = self
+      .epochs
+      .iter()
+      .zip(self.counts.iter())
+      .filter(|(epoch, _)| {
+        let tag = epoch.load(Ordering::Relaxed);
+        tag > oldest && tag <= sec + 1
+      })
+      .map(|(_, count)| count.load(Ordering::Relaxed))
+      .sum();
+
+    let window_start_ms = (oldest * 1000).max(self.reset_at_ms.load(Ordering::Relaxed));
+    // Measure over at least one second so a burst right after open/reset
+    // doesn't read as an extreme rate.
+    let span_ms = now_ms.saturating_sub(window_start_ms).max(1000);
+    total as f64 * 1000.0 / span_ms as f64
+  }
+
+  /// Discard all recorded operations
+  pub fn reset(&self) {
+    for (epoch, count) in self.epochs.iter().zip(self.counts.iter()) {
+      epoch.store(0, Ordering::Relaxed);
+      count.store(0, Ordering::Relaxed);
+    }
+    self.reset_at_ms.store(self.now_ms(), Ordering::Relaxed);
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_rate_counts_recorded_ops_and_resets() {
+    let tracker = OpRateTracker::new();
+    assert_eq!(tracker.rate(), 0.0);
+
+    tracker.record(5);
+    tracker.record(1);
+    assert!(tracker.rate() > 0.0);
+
+    tracker.reset();
+    assert_eq!(tracker.rate(), 0.0);
+  }
+}
@@ -21,6 +21,8 @@ pub struct ConflictError {
   pub txid: TxId,
   /// Keys that caused the conflict
   pub conflicting_keys: Vec<String>,
+  /// Per-key details (empty when built from keys alone)
+  pub details: Vec<ConflictInfo>,
 }
 
 impl ConflictError {
@@ -34,6 +36,26 @@ impl ConflictError {
       message,
       txid,
       conflicting_keys: keys,
+      details: Vec::new(),
+    }
+  }
+
+  /// Build an error that names the winning transaction for each key
+  pub fn with_details(txid: TxId, details: Vec<ConflictInfo>) -> Self {
+    let message = format!(
+      "Transaction {} conflicts with concurrent transactions: {}",
+      txid,
+      details
+        .iter()
+        .map(ConflictInfo::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+    );
+    Self {
+      message,
+      txid,
+      conflicting_keys: details.iter().map(|info| info.key.clone()).collect(),
+      details,
     }
   }
 }
@@ -141,13 +163,15 @@ impl ConflictDetector {
   }
 
   /// Validate transaction can commit (returns error if conflicts found)
+  ///
+  /// The error carries [`ConflictInfo`] for each key, sorted by key.
   pub fn validate_commit(&self, tx_manager: &TxManager, txid: TxId) -> Result<(), ConflictError> {
-    let conflicts = self.check_conflicts(tx_manager, txid);
-    if conflicts.is_empty() {
-      Ok(())
-    } else {
-      Err(ConflictError::new(txid, conflicts))
+    if !self.has_conflicts(tx_manager, txid) {
+      return Ok(());
     }
+    let mut details = self.conflict_details(tx_manager, txid);
+    details.sort_by(|a, b| a.key.cmp(&b.key));
+    Err(ConflictError::with_details(txid, details))
   }
 
   /// Check for a specific key conflict
@@ -184,15 +208,42 @@ pub enum ConflictType {
   WriteWrite,
 }
 
+impl ConflictType {
+  pub fn as_str(&self) -> &'static str {
+    match self {
+      ConflictType::ReadWrite => "read-write",
+      ConflictType::WriteWrite => "write-write",
+    }
+  }
+}
+
 /// Detailed conflict information
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct ConflictInfo {
   /// The conflicting key
   pub key: String,
+  /// What the key guards ("node_prop", "edge", "neighbors", ...)
+  pub category: &'static str,
   /// Type of conflict
   pub conflict_type: ConflictType,
   /// Timestamp of the conflicting write
   pub conflicting_write_ts: u64,
+  /// Transaction that committed the conflicting write
+  pub conflicting_txid: TxId,
+}
+
+impl std::fmt::Display for ConflictInfo {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    write!(
+      f,
+      "{} ({} {}, won by tx {} at ts {})",
+      self.key,
+      self.category,
+      self.conflict_type.as_str(),
+      self.conflicting_txid,
+      self.conflicting_write_ts
+    )
+  }
 }
 
 impl ConflictDetector {
@@ -212,11 +263,13 @@ impl ConflictDetector {
 
     // Check read-write conflicts
     for read_key in &tx.read_set {
-      if let Some(write_ts) = tx_manager.committed_write_ts(read_key, tx_snapshot_ts) {
+      if let Some((write_ts, writer)) = tx_manager.committed_write(read_key, tx_snapshot_ts) {
         conflicts.push(ConflictInfo {
           key: read_key.to_string(),
+          category: read_key.category(),
           conflict_type: ConflictType::ReadWrite,
           conflicting_write_ts: write_ts,
+          conflicting_txid: writer,
         });
       }
     }
@@ -227,11 +280,13 @@ impl ConflictDetector {
       if tx.read_set.contains(write_key) {
         continue;
       }
-      if let Some(write_ts) = tx_manager.committed_write_ts(write_key, tx_snapshot_ts) {
+      if let Some((write_ts, writer)) = tx_manager.committed_write(write_key, tx_snapshot_ts) {
         conflicts.push(ConflictInfo {
           key: write_key.to_string(),
+          category: write_key.category(),
           conflict_type: ConflictType::WriteWrite,
           conflicting_write_ts: write_ts,
+          conflicting_txid: writer,
         });
       }
     }
@@ -375,6 +430,19 @@ mod tests {
     let err = result.unwrap_err();
     assert_eq!(err.txid, txid2);
     assert!(err.conflicting_keys.contains(&key("key").to_string()));
+    assert_eq!(
+      err.details,
+      vec![ConflictInfo {
+        key: key("key").to_string(),
+        category: "node_key",
+        conflict_type: ConflictType::WriteWrite,
+        conflicting_write_ts: 1,
+        conflicting_txid: txid1,
+      }]
+    );
+    assert!(err
+      .to_string()
+      .contains(&format!("won by tx {txid1} at ts 1")));
   }
 
   #[test]
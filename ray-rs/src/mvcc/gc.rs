@@ -169,6 +169,13 @@ impl GarbageCollector {
     let truncated =
       version_chain.truncate_deep_chains(self.config.max_chain_depth, Some(min_active_ts));
 
+    // Snapshots older than the horizon may now be missing versions; truncation
+    // can cut any chain below the oldest active snapshot.
+    tx_manager.advance_history_floor(horizon_ts);
+    if truncated > 0 {
+      tx_manager.advance_history_floor(min_active_ts);
+    }
+
     // Clean up old committed transactions
     let txs_cleaned = self.cleanup_old_transactions(tx_manager, horizon_ts);
     tx_manager.prune_wall_clock_mappings(horizon_ts);
@@ -20,6 +20,8 @@ pub struct MvccManager {
   pub version_chain: Arc<Mutex<VersionChainManager>>,
   pub conflict_detector: ConflictDetector,
   pub gc: Arc<Mutex<GarbageCollector>>,
+  /// Record version chains on every commit, not only while readers are active
+  pub retain_history: bool,
   gc_stop: Arc<AtomicBool>,
   #[cfg(not(target_arch = "wasm32"))]
   gc_handle: Mutex<Option<thread::JoinHandle<()>>>,
@@ -38,6 +40,7 @@ impl MvccManager {
       version_chain: Arc::new(Mutex::new(VersionChainManager::new())),
       conflict_detector: ConflictDetector::new(),
       gc: Arc::new(Mutex::new(GarbageCollector::with_config(gc_config))),
+      retain_history: false,
       gc_stop: Arc::new(AtomicBool::new(false)),
       #[cfg(not(target_arch = "wasm32"))]
       gc_handle: Mutex::new(None),
@@ -46,6 +49,13 @@ impl MvccManager {
     }
   }
 
+  /// Keep version history for every commit so past snapshots stay readable
+  /// within the GC retention window
+  pub fn with_retained_history(mut self, retain: bool) -> Self {
+    self.retain_history = retain;
+    self
+  }
+
   /// Initialize MVCC (starts background GC)
   #[cfg(not(target_arch = "wasm32"))]
   pub fn start(&self) {
@@ -53,6 +53,16 @@
 //! - **Write-Write Conflict**: Transaction wrote a key also written by a concurrent committed transaction
 //!
 //! See [`ConflictDetector`] for conflict detection APIs.
+//!
+//! # Write Ordering
+//!
+//! Transactions take no per-key locks: writes are buffered until commit, and
+//! validation compares the whole read and write sets against committed
+//! writes under the transaction manager's lock. The order in which a
+//! transaction touches keys therefore can't deadlock and doesn't change the
+//! outcome; of two concurrent transactions writing the same keys, the first
+//! to commit wins and the other fails with every overlapping key, sorted.
+//! There is no need to sort keys before writing them.
 
 pub mod conflict;
 pub mod gc;
@@ -67,7 +77,8 @@ pub use gc::{GarbageCollector, GcConfig, GcResult, GcStats, SharedGcState};
 pub use manager::MvccManager;
 pub use tx_manager::{CommittedWritesStats, TxManager, TxManagerError};
 pub use version_chain::{
-  PooledVersion, SoaPropertyVersions, VersionChainCounts, VersionChainManager,
+  PooledVersion, SoaPropertyVersions, VersionChainCounts, VersionChainDepthStats,
+  VersionChainManager,
 };
 pub use visibility::{
   edge_exists, is_visible, node_exists, visible_version, visible_version_mut, EdgeLike,
@@ -4,7 +4,7 @@
 //!
 //! Ported from src/mvcc/tx-manager.ts
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeSet, HashMap, HashSet};
 use std::time::{SystemTime, UNIX_EPOCH};
 
 use crate::types::{MvccTransaction, MvccTxStatus, Timestamp, TxId, TxKey};
@@ -34,14 +34,18 @@ pub struct TxManager {
   next_tx_id: TxId,
   /// Next commit timestamp to assign
   next_commit_ts: Timestamp,
-  /// Inverted index: key -> max commitTs for conflict detection
-  committed_writes: HashMap<TxKey, Timestamp>,
+  /// Inverted index: key -> (max commitTs, writing txid) for conflict detection
+  committed_writes: HashMap<TxKey, (Timestamp, TxId)>,
   /// Map commit timestamp -> wall clock time (ms since epoch)
   commit_ts_to_wall_clock: HashMap<Timestamp, u64>,
   /// O(1) tracking of active transaction count
   active_count: usize,
   /// Total committed write entries pruned (for stats)
   total_pruned: usize,
+  /// Oldest snapshot timestamp whose versions are still fully retained
+  history_floor_ts: Timestamp,
+  /// Commit timestamps whose writes haven't reached the delta yet
+  unapplied_commits: BTreeSet<Timestamp>,
 }
 
 impl TxManager {
@@ -60,6 +64,8 @@ impl TxManager {
       commit_ts_to_wall_clock: HashMap::new(),
       active_count: 0,
       total_pruned: 0,
+      history_floor_ts: initial_commit_ts,
+      unapplied_commits: BTreeSet::new(),
     }
   }
 
@@ -82,9 +88,23 @@ impl TxManager {
   /// Begin a new transaction
   /// Returns transaction ID and snapshot timestamp
   pub fn begin_tx(&mut self) -> (TxId, Timestamp) {
+    // Snapshot at current commit timestamp, but before any commit that isn't
+    // readable yet, so its writes conflict instead of being silently missed
+    let start_ts = self
+      .unapplied_commits
+      .first()
+      .copied()
+      .unwrap_or(self.next_commit_ts);
+    self.begin_tx_at(start_ts)
+  }
+
+  /// Begin a new transaction reading at an explicit snapshot timestamp
+  ///
+  /// The caller is responsible for checking `start_ts` against
+  /// [`history_floor_ts`](Self::history_floor_ts) and `next_commit_ts`.
+  pub fn begin_tx_at(&mut self, start_ts: Timestamp) -> (TxId, Timestamp) {
     let txid = self.next_tx_id;
     self.next_tx_id += 1;
-    let start_ts = self.next_commit_ts; // Snapshot at current commit timestamp
 
     let tx = MvccTransaction {
       txid,
@@ -167,10 +187,10 @@ impl TxManager {
       let existing = self.committed_writes.get(&key).copied();
       let should_update = match existing {
         None => true,
-        Some(existing_ts) => commit_ts > existing_ts,
+        Some((existing_ts, _)) => commit_ts > existing_ts,
       };
       if should_update {
-        self.committed_writes.insert(key, commit_ts);
+        self.committed_writes.insert(key, (commit_ts, txid));
       }
     }
 
@@ -200,6 +220,19 @@ impl TxManager {
     self.active_txs.remove(&txid);
   }
 
+  /// Note that a commit has its timestamp but its writes aren't readable yet
+  ///
+  /// Until [`mark_applied`](Self::mark_applied), new transactions snapshot
+  /// before `commit_ts`.
+  pub fn mark_unapplied(&mut self, commit_ts: Timestamp) {
+    self.unapplied_commits.insert(commit_ts);
+  }
+
+  /// Note that a commit's writes are readable; see [`Self::mark_unapplied`]
+  pub fn mark_applied(&mut self, commit_ts: Timestamp) {
+    self.unapplied_commits.remove(&commit_ts);
+  }
+
   /// Remove a committed transaction (called by GC when safe)
   pub fn remove_tx(&mut self, txid: TxId) {
     if let Some(tx) = self.active_txs.get(&txid) {
@@ -245,6 +278,19 @@ impl TxManager {
     self.next_commit_ts
   }
 
+  /// Oldest snapshot timestamp that can still be read consistently
+  ///
+  /// Snapshots older than this may be missing versions that were pruned or
+  /// never recorded.
+  pub fn history_floor_ts(&self) -> Timestamp {
+    self.history_floor_ts
+  }
+
+  /// Move the history floor forward (never backwards)
+  pub fn advance_history_floor(&mut self, ts: Timestamp) {
+    self.history_floor_ts = self.history_floor_ts.max(ts);
+  }
+
   /// Get all transactions (for debugging/recovery)
   pub fn all_txs(&self) -> impl Iterator<Item = (&TxId, &MvccTransaction)> {
     self.active_txs.iter()
@@ -253,13 +299,22 @@ impl TxManager {
   /// Get committed writes for a key (for conflict detection)
   /// Returns the max commitTs for the key if >= minCommitTs, otherwise None
   pub fn committed_write_ts(&self, key: &TxKey, min_commit_ts: Timestamp) -> Option<Timestamp> {
-    self.committed_writes.get(key).and_then(|&max_ts| {
-      if max_ts >= min_commit_ts {
-        Some(max_ts)
-      } else {
-        None
-      }
-    })
+    self
+      .committed_write(key, min_commit_ts)
+      .map(|(commit_ts, _)| commit_ts)
+  }
+
+  /// Latest committed write for a key as (commitTs, txid), if commitTs >= minCommitTs
+  pub fn committed_write(
+    &self,
+    key: &TxKey,
+    min_commit_ts: Timestamp,
+  ) -> Option<(Timestamp, TxId)> {
+    self
+      .committed_writes
+      .get(key)
+      .copied()
+      .filter(|&(max_ts, _)| max_ts >= min_commit_ts)
   }
 
   /// Check if there's a conflicting write for a key (fast path for conflict detection)
@@ -268,7 +323,7 @@ impl TxManager {
     self
       .committed_writes
       .get(key)
-      .map(|&max_ts| max_ts >= min_commit_ts)
+      .map(|&(max_ts, _)| max_ts >= min_commit_ts)
       .unwrap_or(false)
   }
 
@@ -333,8 +388,11 @@ impl TxManager {
 
   fn prune_committed_writes(&mut self) {
     let min_ts = self.min_active_ts();
-    let mut entries: Vec<(&TxKey, Timestamp)> =
-      self.committed_writes.iter().map(|(k, &v)| (k, v)).collect();
+    let mut entries: Vec<(&TxKey, Timestamp)> = self
+      .committed_writes
+      .iter()
+      .map(|(k, &(ts, _))| (k, ts))
+      .collect();
 
     entries.sort_by_key(|(_, ts)| *ts);
 
@@ -506,6 +564,24 @@ mod tests {
     assert!(tx_mgr.has_conflicting_write(&key("key1"), 1));
   }
 
+  #[test]
+  fn test_begin_tx_snapshots_before_unapplied_commits() {
+    let mut tx_mgr = TxManager::new();
+    let (writer, _) = tx_mgr.begin_tx();
+    tx_mgr.record_write(writer, key("key1"));
+    let commit_ts = tx_mgr.commit_tx(writer).expect("expected value");
+    tx_mgr.mark_unapplied(commit_ts);
+
+    let (reader, start_ts) = tx_mgr.begin_tx();
+    assert_eq!(start_ts, commit_ts);
+    assert!(tx_mgr.has_conflicting_write(&key("key1"), start_ts));
+
+    tx_mgr.mark_applied(commit_ts);
+    tx_mgr.abort_tx(reader);
+    let (_, start_ts) = tx_mgr.begin_tx();
+    assert_eq!(start_ts, commit_ts + 1);
+  }
+
   #[test]
   fn test_commit_tx_not_found() {
     let mut tx_mgr = TxManager::new();
@@ -617,6 +693,7 @@ mod tests {
     assert_eq!(tx_mgr.committed_write_ts(&key("key1"), 1), Some(1));
     assert_eq!(tx_mgr.committed_write_ts(&key("key1"), 2), None);
     assert_eq!(tx_mgr.committed_write_ts(&key("key2"), 0), None);
+    assert_eq!(tx_mgr.committed_write(&key("key1"), 0), Some((1, txid)));
   }
 
   #[test]
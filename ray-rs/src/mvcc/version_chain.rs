@@ -233,6 +233,19 @@ impl<T: Clone> SoaPropertyVersions<T> {
     self.heads.len()
   }
 
+  /// Number of versions in each key's chain
+  pub fn chain_lengths(&self) -> impl Iterator<Item = usize> + '_ {
+    self.heads.values().map(move |&head| {
+      let mut len = 0;
+      let mut idx = head;
+      while idx != NULL_IDX && (idx as usize) < self.prev_idx.len() {
+        len += 1;
+        idx = self.prev_idx[idx as usize];
+      }
+      len
+    })
+  }
+
   /// Check if empty
   pub fn is_empty(&self) -> bool {
     self.heads.is_empty()
@@ -960,6 +973,33 @@ impl VersionChainManager {
     self.legacy_node_labels.clear();
   }
 
+  /// Summarize chain lengths across every versioned node, edge, property and label
+  pub fn depth_stats(&self) -> VersionChainDepthStats {
+    fn record_len<T>(record: &VersionedRecord<T>) -> usize {
+      let mut len = 1;
+      let mut current = record.prev.as_deref();
+      while let Some(prev) = current {
+        len += 1;
+        current = prev.prev.as_deref();
+      }
+      len
+    }
+
+    let mut lengths: Vec<usize> = Vec::new();
+    lengths.extend(self.node_versions.values().map(|r| record_len(r)));
+    lengths.extend(self.edge_versions.values().map(|r| record_len(r)));
+    if self.use_soa {
+      lengths.extend(self.soa_node_props.chain_lengths());
+      lengths.extend(self.soa_edge_props.chain_lengths());
+      lengths.extend(self.soa_node_labels.chain_lengths());
+    } else {
+      lengths.extend(self.legacy_node_props.values().map(|r| record_len(r)));
+      lengths.extend(self.legacy_edge_props.values().map(|r| record_len(r)));
+      lengths.extend(self.legacy_node_labels.values().map(|r| record_len(r)));
+    }
+    VersionChainDepthStats::from_lengths(lengths)
+  }
+
   /// Get counts for statistics
   pub fn counts(&self) -> VersionChainCounts {
     VersionChainCounts {
@@ -1000,6 +1040,39 @@ pub struct VersionChainCounts {
   pub node_label_versions: usize,
 }
 
+/// Distribution of version chain lengths (one chain per versioned key)
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VersionChainDepthStats {
+  /// Number of keys with a version chain
+  pub chains: usize,
+  /// Versions retained across all chains
+  pub total_versions: usize,
+  /// Longest chain
+  pub max_depth: usize,
+  /// Median chain length
+  pub p50_depth: usize,
+  /// 99th percentile chain length
+  pub p99_depth: usize,
+}
+
+impl VersionChainDepthStats {
+  fn from_lengths(mut lengths: Vec<usize>) -> Self {
+    if lengths.is_empty() {
+      return Self::default();
+    }
+    lengths.sort_unstable();
+    // Nearest-rank percentile
+    let percentile = |p: usize| lengths[(p * lengths.len()).div_ceil(100) - 1];
+    Self {
+      chains: lengths.len(),
+      total_versions: lengths.iter().sum(),
+      max_depth: lengths[lengths.len() - 1],
+      p50_depth: percentile(50),
+      p99_depth: percentile(99),
+    }
+  }
+}
+
 // ============================================================================
 // Tests
 // ============================================================================
@@ -1049,6 +1122,37 @@ mod tests {
     assert_eq!(*prev.data, 20);
   }
 
+  #[test]
+  fn test_depth_stats_summarizes_chain_lengths() {
+    for use_soa in [true, false] {
+      let mut mgr = VersionChainManager::with_soa(use_soa);
+      assert_eq!(mgr.depth_stats(), VersionChainDepthStats::default());
+
+      // One prop chain of 4 versions, nine of 1, plus a 2-version edge chain
+      for ts in 1..=4 {
+        mgr.append_node_prop_version(
+          1,
+          1,
+          Some(std::sync::Arc::new(PropValue::I64(ts as i64))),
+          ts,
+          ts,
+        );
+      }
+      for node_id in 2..=10 {
+        mgr.append_node_prop_version(node_id, 1, None, 1, 1);
+      }
+      mgr.append_edge_version(1, 1, 2, true, 1, 1);
+      mgr.append_edge_version(1, 1, 2, false, 2, 2);
+
+      let stats = mgr.depth_stats();
+      assert_eq!(stats.chains, 11);
+      assert_eq!(stats.total_versions, 4 + 9 + 2);
+      assert_eq!(stats.max_depth, 4);
+      assert_eq!(stats.p50_depth, 1);
+      assert_eq!(stats.p99_depth, 4);
+    }
+  }
+
   #[test]
   fn test_version_chain_manager_new() {
     let mgr = VersionChainManager::new();
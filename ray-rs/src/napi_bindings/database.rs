@@ -4,22 +4,26 @@
 
 use napi::bindgen_prelude::*;
 use napi_derive::napi;
+use std::collections::{HashSet, VecDeque};
 use std::path::PathBuf;
 use std::str::FromStr;
 
+use super::error::{closed_error, kite_error_with, ErrorCode};
 use super::traversal::{
-  JsPathConfig, JsPathResult, JsTraversalDirection, JsTraversalResult, JsTraversalStep,
-  JsTraverseOptions,
+  JsPathConfig, JsPathResult, JsSpanningTreeNode, JsTopKResult, JsTraversalDirection,
+  JsTraversalResult, JsTraversalStep, JsTraverseOptions, JsWalkControl,
 };
+use super::vector::JsBruteForceResult;
 use crate::api::kite::KiteRuntimeProfile as RustKiteRuntimeProfile;
 use crate::api::pathfinding::{bfs, dijkstra, yen_k_shortest, PathConfig};
 use crate::api::traversal::{
-  TraversalBuilder as RustTraversalBuilder, TraversalDirection, TraverseOptions,
+  prop_value_to_weight, spanning_tree, TraversalBuilder as RustTraversalBuilder,
+  TraversalDirection, TraverseOptions,
 };
 use crate::backup as core_backup;
 use crate::core::single_file::{
   close_single_file, close_single_file_with_options, is_single_file_path, open_single_file,
-  single_file_extension, ResizeWalOptions as RustResizeWalOptions,
+  single_file_extension, LockMode as RustLockMode, ResizeWalOptions as RustResizeWalOptions,
   SingleFileCloseOptions as RustSingleFileCloseOptions, SingleFileDB as RustSingleFileDB,
   SingleFileOpenOptions as RustOpenOptions,
   SingleFileOptimizeOptions as RustSingleFileOptimizeOptions,
@@ -35,8 +39,10 @@ use crate::replication::replica::ReplicaReplicationStatus;
 use crate::replication::types::{CommitToken, ReplicationRole as RustReplicationRole};
 use crate::streaming;
 use crate::types::{
-  CheckResult as RustCheckResult, ETypeId, Edge, EdgeWithProps as CoreEdgeWithProps, NodeId,
-  PropKeyId, PropValue,
+  CheckResult as RustCheckResult, CommitStats as RustCommitStats, ETypeId, Edge,
+  EdgeWithProps as CoreEdgeWithProps, IndexInfo as RustIndexInfo, NodeId, PropKeyId, PropValue,
+  RepairResult as RustRepairResult, TransactionInfo as RustTransactionInfo,
+  WalRecordStats as RustWalRecordStats,
 };
 use crate::util::compression::{CompressionOptions as CoreCompressionOptions, CompressionType};
 use serde_json;
@@ -91,6 +97,28 @@ impl From<JsSnapshotParseMode> for RustSnapshotParseMode {
   }
 }
 
+/// File locking behavior for single-file open options
+#[napi(string_enum)]
+#[derive(Debug)]
+pub enum JsLockMode {
+  /// Fail to open if the lock can't be taken
+  Require,
+  /// Lock if supported; otherwise open unlocked and report a warning
+  BestEffort,
+  /// Don't lock
+  None,
+}
+
+impl From<JsLockMode> for RustLockMode {
+  fn from(mode: JsLockMode) -> Self {
+    match mode {
+      JsLockMode::Require => RustLockMode::Require,
+      JsLockMode::BestEffort => RustLockMode::BestEffort,
+      JsLockMode::None => RustLockMode::None,
+    }
+  }
+}
+
 /// Replication role for single-file open options
 #[napi(string_enum)]
 #[derive(Debug)]
@@ -128,12 +156,18 @@ pub struct OpenOptions {
   pub mvcc_gc_interval_ms: Option<i64>,
   /// MVCC retention in ms
   pub mvcc_retention_ms: Option<i64>,
+  /// Record version history on every commit so beginReadAt can serve past
+  /// generations (costs a version per write)
+  pub mvcc_retain_history: Option<bool>,
   /// MVCC max version chain depth
   pub mvcc_max_chain_depth: Option<i64>,
   /// Page size in bytes (default 4096)
   pub page_size: Option<u32>,
   /// WAL size in bytes (default 1MB)
   pub wal_size: Option<u32>,
+  /// Separate WAL file location, e.g. on faster storage (applies when
+  /// creating the database; reopen finds it via the header)
+  pub wal_path: Option<String>,
   /// Enable auto-checkpoint when WAL usage exceeds threshold
   pub auto_checkpoint: Option<bool>,
   /// WAL usage threshold (0.0-1.0) to trigger auto-checkpoint
@@ -162,6 +196,9 @@ pub struct OpenOptions {
   pub group_commit_window_ms: Option<i64>,
   /// Snapshot parse mode: "Strict" or "Salvage" (single-file only)
   pub snapshot_parse_mode: Option<JsSnapshotParseMode>,
+  /// Lock mode: "Require", "BestEffort", or "None" (default: "None").
+  /// With "BestEffort", check `openWarnings()` after opening.
+  pub lock_mode: Option<JsLockMode>,
   /// Replication role: "Disabled", "Primary", or "Replica"
   pub replication_role: Option<JsReplicationRole>,
   /// Replication sidecar path override
@@ -198,6 +235,9 @@ impl From<OpenOptions> for RustOpenOptions {
     if let Some(v) = opts.mvcc_retention_ms {
       rust_opts = rust_opts.mvcc_retention_ms(v as u64);
     }
+    if let Some(v) = opts.mvcc_retain_history {
+      rust_opts = rust_opts.mvcc_retain_history(v);
+    }
     if let Some(v) = opts.mvcc_max_chain_depth {
       rust_opts = rust_opts.mvcc_max_chain_depth(v as usize);
     }
@@ -207,6 +247,9 @@ impl From<OpenOptions> for RustOpenOptions {
     if let Some(v) = opts.wal_size {
       rust_opts = rust_opts.wal_size(v as usize);
     }
+    if let Some(path) = opts.wal_path {
+      rust_opts = rust_opts.wal_path(path);
+    }
     if let Some(v) = opts.auto_checkpoint {
       rust_opts = rust_opts.auto_checkpoint(v);
     }
@@ -262,6 +305,9 @@ impl From<OpenOptions> for RustOpenOptions {
     if let Some(mode) = opts.snapshot_parse_mode {
       rust_opts = rust_opts.snapshot_parse_mode(mode.into());
     }
+    if let Some(mode) = opts.lock_mode {
+      rust_opts = rust_opts.lock_mode(mode.into());
+    }
     if let Some(role) = opts.replication_role {
       rust_opts = rust_opts.replication_role(role.into());
     }
@@ -317,11 +363,13 @@ fn open_options_from_kite_profile_options(opts: crate::api::kite::KiteOptions) -
     mvcc: Some(opts.mvcc),
     mvcc_gc_interval_ms: opts.mvcc_gc_interval_ms.and_then(|v| i64::try_from(v).ok()),
     mvcc_retention_ms: opts.mvcc_retention_ms.and_then(|v| i64::try_from(v).ok()),
+    mvcc_retain_history: Some(opts.mvcc_retain_history),
     mvcc_max_chain_depth: opts
       .mvcc_max_chain_depth
       .and_then(|v| i64::try_from(v).ok()),
     page_size: None,
     wal_size: opts.wal_size.and_then(|v| u32::try_from(v).ok()),
+    wal_path: opts.wal_path.map(|p| p.to_string_lossy().to_string()),
     auto_checkpoint: None,
     checkpoint_threshold: opts.checkpoint_threshold,
     background_checkpoint: None,
@@ -336,6 +384,7 @@ fn open_options_from_kite_profile_options(opts: crate::api::kite::KiteOptions) -
     group_commit_enabled: Some(opts.group_commit_enabled),
     group_commit_window_ms: i64::try_from(opts.group_commit_window_ms).ok(),
     snapshot_parse_mode: None,
+    lock_mode: None,
     replication_role: Some(js_replication_role_from_rust(opts.replication_role)),
     replication_sidecar_path: opts
       .replication_sidecar_path
@@ -490,6 +539,14 @@ impl From<SingleFileOptimizeOptions> for RustSingleFileOptimizeOptions {
   }
 }
 
+/// A single dimension change for `updateNodeVectorDims`
+#[napi(object)]
+#[derive(Debug, Clone, Copy)]
+pub struct VectorDimUpdate {
+  pub index: u32,
+  pub value: f64,
+}
+
 // ============================================================================
 // Database Statistics
 // ============================================================================
@@ -507,10 +564,114 @@ pub struct DbStats {
   pub delta_edges_deleted: i64,
   pub wal_segment: i64,
   pub wal_bytes: i64,
+  /// Deleted snapshot nodes/edges as a fraction of all snapshot entries
+  pub fragmentation: f64,
   pub recommend_compact: bool,
   pub mvcc_stats: Option<MvccStats>,
 }
 
+/// WAL usage by record type (from walRecordBreakdown())
+#[napi(object)]
+pub struct JsWalRecordStats {
+  /// Record type name, e.g. "SetNodeProp"
+  pub record_type: String,
+  pub count: i64,
+  /// Bytes including record padding
+  pub bytes: i64,
+}
+
+impl From<RustWalRecordStats> for JsWalRecordStats {
+  fn from(stats: RustWalRecordStats) -> Self {
+    JsWalRecordStats {
+      record_type: format!("{:?}", stats.record_type),
+      count: stats.count as i64,
+      bytes: stats.bytes as i64,
+    }
+  }
+}
+
+/// One active secondary index (from listIndexes())
+#[napi(object)]
+pub struct JsIndexInfo {
+  /// "unique", "ordered" or "vector"
+  pub kind: String,
+  /// Owning node type (unset for vector stores, which span every type)
+  pub node_type: Option<String>,
+  /// Indexed property
+  pub prop_name: String,
+  /// Indexed nodes, or live vectors for a vector store
+  pub entries: i64,
+  /// Rough in-memory footprint in bytes
+  pub estimated_bytes: i64,
+}
+
+impl From<RustIndexInfo> for JsIndexInfo {
+  fn from(info: RustIndexInfo) -> Self {
+    JsIndexInfo {
+      kind: info.kind.as_str().to_string(),
+      node_type: info.node_type,
+      prop_name: info.prop_name,
+      entries: info.entries as i64,
+      estimated_bytes: info.estimated_bytes as i64,
+    }
+  }
+}
+
+/// A keyed node matched by findKeys()
+#[napi(object)]
+pub struct JsNodeKey {
+  pub id: i64,
+  pub key: String,
+}
+
+/// The active transaction (from transactionInfo())
+#[napi(object)]
+pub struct JsTransactionInfo {
+  pub txid: i64,
+  pub read_only: bool,
+  /// MVCC timestamp the transaction reads at (0 when MVCC is disabled)
+  pub snapshot_ts: i64,
+}
+
+impl From<RustTransactionInfo> for JsTransactionInfo {
+  fn from(info: RustTransactionInfo) -> Self {
+    JsTransactionInfo {
+      txid: info.txid as i64,
+      read_only: info.read_only,
+      snapshot_ts: info.snapshot_ts as i64,
+    }
+  }
+}
+
+/// What a committed transaction changed (from commitStats())
+#[napi(object)]
+pub struct JsCommitStats {
+  pub nodes_created: i64,
+  pub nodes_deleted: i64,
+  pub edges_added: i64,
+  pub edges_deleted: i64,
+  /// Node and edge props set (deletes aren't counted)
+  pub props_set: i64,
+  /// Time from begin to the end of the commit
+  pub duration_ms: i64,
+  /// Bytes of WAL records written, BEGIN and COMMIT included
+  pub wal_bytes_written: i64,
+}
+
+impl From<RustCommitStats> for JsCommitStats {
+  fn from(stats: RustCommitStats) -> Self {
+    JsCommitStats {
+      nodes_created: stats.nodes_created as i64,
+      nodes_deleted: stats.nodes_deleted as i64,
+      edges_added: stats.edges_added as i64,
+      edges_deleted: stats.edges_deleted as i64,
+      props_set: stats.props_set as i64,
+      duration_ms: stats.duration_ms as i64,
+      wal_bytes_written: stats.wal_bytes_written as i64,
+    }
+  }
+}
+
 /// MVCC stats (from stats())
 #[napi(object)]
 pub struct MvccStats {
@@ -521,6 +682,16 @@ pub struct MvccStats {
   pub last_gc_time: i64,
   pub committed_writes_size: i64,
   pub committed_writes_pruned: i64,
+  /// Keys that currently have a version chain
+  pub version_chains: i64,
+  /// Versions retained across all chains
+  pub versions_retained: i64,
+  /// Longest version chain
+  pub chain_depth_max: i64,
+  /// Median version chain length
+  pub chain_depth_p50: i64,
+  /// 99th percentile version chain length
+  pub chain_depth_p99: i64,
 }
 
 /// Per-replica lag entry on primary status
@@ -625,7 +796,13 @@ pub struct ExportOptions {
   pub include_nodes: Option<bool>,
   pub include_edges: Option<bool>,
   pub include_schema: Option<bool>,
+  /// Export node vector embeddings (default: false)
+  pub include_vectors: Option<bool>,
   pub pretty: Option<bool>,
+  /// Only export nodes with any of these labels (and edges among them)
+  pub labels: Option<Vec<String>>,
+  /// Nodes per shard file for `exportSharded` (default: 100000)
+  pub nodes_per_shard: Option<i64>,
 }
 
 impl ExportOptions {
@@ -640,9 +817,18 @@ impl ExportOptions {
     if let Some(v) = self.include_schema {
       opts.include_schema = v;
     }
+    if let Some(v) = self.include_vectors {
+      opts.include_vectors = v;
+    }
     if let Some(v) = self.pretty {
       opts.pretty = v;
     }
+    opts.labels = self.labels;
+    if let Some(v) = self.nodes_per_shard {
+      if v > 0 {
+        opts.nodes_per_shard = v as usize;
+      }
+    }
     opts
   }
 }
@@ -682,6 +868,56 @@ pub struct ImportResult {
   pub node_count: i64,
   pub edge_count: i64,
   pub skipped: i64,
+  /// The progress callback stopped the import; counts cover committed batches
+  pub cancelled: bool,
+}
+
+/// Running totals passed to an import progress callback
+#[napi(object)]
+pub struct ImportProgress {
+  pub nodes_imported: i64,
+  pub edges_imported: i64,
+}
+
+/// Run an import, reporting each batch to an optional JS callback
+///
+/// The callback runs before each batch commits; returning `false` rolls that
+/// batch back and stops the import. A throwing callback stops it too, and
+/// the error is rethrown.
+fn import_with_progress(
+  db: &RustSingleFileDB,
+  data: &ray_export::ExportedDatabase,
+  options: ray_export::ImportOptions,
+  on_progress: Option<Function<ImportProgress, Option<bool>>>,
+) -> Result<ImportResult> {
+  let mut callback_error = None;
+  let result = ray_export::import_from_object_single_with_progress(db, data, options, |progress| {
+    let Some(ref callback) = on_progress else {
+      return true;
+    };
+    let progress = ImportProgress {
+      nodes_imported: progress.nodes_imported as i64,
+      edges_imported: progress.edges_imported as i64,
+    };
+    match callback.call(progress) {
+      Ok(keep_going) => keep_going != Some(false),
+      Err(e) => {
+        callback_error = Some(e);
+        false
+      }
+    }
+  })
+  .map_err(|e| Error::from_reason(e.to_string()))?;
+  if let Some(e) = callback_error {
+    return Err(e);
+  }
+
+  Ok(ImportResult {
+    node_count: result.node_count as i64,
+    edge_count: result.edge_count as i64,
+    skipped: result.skipped as i64,
+    cancelled: result.cancelled,
+  })
 }
 
 // =============================================================================
@@ -694,6 +930,8 @@ pub struct ImportResult {
 pub struct StreamOptions {
   /// Number of items per batch (default: 1000)
   pub batch_size: Option<i64>,
+  /// Only stream edges whose endpoints are both in this node set
+  pub node_filter: Option<Vec<i64>>,
 }
 
 impl StreamOptions {
@@ -704,6 +942,9 @@ impl StreamOptions {
     }
     Ok(crate::streaming::StreamOptions {
       batch_size: batch_size as usize,
+      node_filter: self
+        .node_filter
+        .map(|ids| ids.into_iter().map(|id| id as NodeId).collect()),
     })
   }
 }
@@ -719,7 +960,7 @@ pub struct PaginationOptions {
 }
 
 impl PaginationOptions {
-  fn into_rust(self) -> Result<crate::streaming::PaginationOptions> {
+  pub(crate) fn into_rust(self) -> Result<crate::streaming::PaginationOptions> {
     let limit = self.limit.unwrap_or(0);
     if limit < 0 {
       return Err(Error::from_reason("limit must be non-negative"));
@@ -784,6 +1025,27 @@ impl From<RustCheckResult> for CheckResult {
   }
 }
 
+/// Database check-and-repair result
+#[napi(object)]
+pub struct RepairResult {
+  /// Repairs that were applied
+  pub repaired: Vec<String>,
+  /// Problems found but left in place (destructive repairs not allowed)
+  pub skipped: Vec<String>,
+  /// Integrity check after repairs
+  pub check: CheckResult,
+}
+
+impl From<RustRepairResult> for RepairResult {
+  fn from(result: RustRepairResult) -> Self {
+    RepairResult {
+      repaired: result.repaired,
+      skipped: result.skipped,
+      check: result.check.into(),
+    }
+  }
+}
+
 /// Cache statistics
 #[napi(object)]
 pub struct JsCacheStats {
@@ -796,6 +1058,9 @@ pub struct JsCacheStats {
   pub query_cache_hits: i64,
   pub query_cache_misses: i64,
   pub query_cache_size: i64,
+  pub key_cache_hits: i64,
+  pub key_cache_misses: i64,
+  pub key_cache_size: i64,
 }
 
 /// Cache layer metrics
@@ -901,6 +1166,10 @@ pub struct DatabaseMetrics {
   pub mvcc: Option<MvccMetrics>,
   pub replication: ReplicationMetrics,
   pub memory: MemoryMetrics,
+  /// Mutations per second over the last 10 seconds
+  pub write_rate: f64,
+  /// Reads per second over the last 10 seconds
+  pub read_rate: f64,
   /// Timestamp in milliseconds since epoch
   pub collected_at: i64,
 }
@@ -1080,6 +1349,8 @@ impl From<core_metrics::DatabaseMetrics> for DatabaseMetrics {
       mvcc: metrics.mvcc.map(Into::into),
       replication: metrics.replication.into(),
       memory: metrics.memory.into(),
+      write_rate: metrics.write_rate,
+      read_rate: metrics.read_rate,
       collected_at: metrics.collected_at_ms,
     }
   }
@@ -1127,6 +1398,7 @@ pub enum PropType {
   Float,
   String,
   Vector,
+  Json,
 }
 
 /// Property value wrapper for JS
@@ -1139,6 +1411,7 @@ pub struct JsPropValue {
   pub float_value: Option<f64>,
   pub string_value: Option<String>,
   pub vector_value: Option<Vec<f64>>,
+  pub json_value: Option<serde_json::Value>,
 }
 
 impl From<PropValue> for JsPropValue {
@@ -1151,6 +1424,7 @@ impl From<PropValue> for JsPropValue {
         float_value: None,
         string_value: None,
         vector_value: None,
+        json_value: None,
       },
       PropValue::Bool(v) => JsPropValue {
         prop_type: PropType::Bool,
@@ -1159,6 +1433,7 @@ impl From<PropValue> for JsPropValue {
         float_value: None,
         string_value: None,
         vector_value: None,
+        json_value: None,
       },
       PropValue::I64(v) => JsPropValue {
         prop_type: PropType::Int,
@@ -1167,6 +1442,7 @@ impl From<PropValue> for JsPropValue {
         float_value: None,
         string_value: None,
         vector_value: None,
+        json_value: None,
       },
       PropValue::F64(v) => JsPropValue {
         prop_type: PropType::Float,
@@ -1175,6 +1451,7 @@ impl From<PropValue> for JsPropValue {
         float_value: Some(v),
         string_value: None,
         vector_value: None,
+        json_value: None,
       },
       PropValue::String(v) => JsPropValue {
         prop_type: PropType::String,
@@ -1183,6 +1460,7 @@ impl From<PropValue> for JsPropValue {
         float_value: None,
         string_value: Some(v),
         vector_value: None,
+        json_value: None,
       },
       PropValue::VectorF32(v) => JsPropValue {
         prop_type: PropType::Vector,
@@ -1191,6 +1469,16 @@ impl From<PropValue> for JsPropValue {
         float_value: None,
         string_value: None,
         vector_value: Some(v.iter().map(|&x| x as f64).collect()),
+        json_value: None,
+      },
+      PropValue::Json(v) => JsPropValue {
+        prop_type: PropType::Json,
+        bool_value: None,
+        int_value: None,
+        float_value: None,
+        string_value: None,
+        vector_value: None,
+        json_value: Some(v),
       },
     }
   }
@@ -1208,6 +1496,7 @@ impl From<JsPropValue> for PropValue {
         let vector = value.vector_value.unwrap_or_default();
         PropValue::VectorF32(vector.iter().map(|&x| x as f32).collect())
       }
+      PropType::Json => PropValue::Json(value.json_value.unwrap_or_default()),
     }
   }
 }
@@ -1221,6 +1510,31 @@ impl From<JsPropValue> for PropValue {
 pub struct JsEdge {
   pub etype: u32,
   pub node_id: i64,
+  /// Neighbor's key (only when requested with `includeKeys`)
+  pub node_key: Option<String>,
+}
+
+/// Convert `(etype, neighbor)` pairs, resolving neighbor keys in one pass if asked
+fn edges_to_js(
+  db: &RustSingleFileDB,
+  edges: Vec<(ETypeId, NodeId)>,
+  include_keys: bool,
+) -> Vec<JsEdge> {
+  let keys = if include_keys {
+    let ids: Vec<NodeId> = edges.iter().map(|&(_, node_id)| node_id).collect();
+    db.node_keys(&ids)
+  } else {
+    vec![None; edges.len()]
+  };
+  edges
+    .into_iter()
+    .zip(keys)
+    .map(|((etype, node_id), node_key)| JsEdge {
+      etype,
+      node_id: node_id as i64,
+      node_key,
+    })
+    .collect()
 }
 
 /// Full edge representation for JS (src, etype, dst)
@@ -1362,65 +1676,126 @@ impl Database {
   // ========================================================================
 
   /// Begin a transaction
+  ///
+  /// If this thread already has one, joins it instead: each nested begin
+  /// needs its own commit or rollback, and only the outermost one ends it.
   #[napi]
-  pub fn begin(&self, read_only: Option<bool>) -> Result<i64> {
+  pub fn begin(&self, read_only: Option<bool>) -> Result<i64, ErrorCode> {
     let read_only = read_only.unwrap_or(false);
     match self.inner.as_ref() {
       Some(DatabaseInner::SingleFile(db)) => {
         let txid = db
           .begin(read_only)
-          .map_err(|e| Error::from_reason(format!("Failed to begin transaction: {e}")))?;
+          .map_err(|e| kite_error_with("Failed to begin transaction", e))?;
         Ok(txid as i64)
       }
-      None => Err(Error::from_reason("Database is closed")),
+      None => Err(closed_error("Database is closed")),
+    }
+  }
+
+  /// Begin a read-only transaction pinned to a past MVCC generation
+  ///
+  /// Fails if the generation's history is no longer retained (past
+  /// generations are only kept with `mvccRetainHistory`).
+  #[napi]
+  pub fn begin_read_at(&self, generation: i64) -> Result<i64, ErrorCode> {
+    let generation = u64::try_from(generation).map_err(|_| {
+      Error::new(
+        ErrorCode::InvalidQuery,
+        "generation must be non-negative".to_string(),
+      )
+    })?;
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => {
+        let txid = db
+          .begin_read_at(generation)
+          .map_err(|e| kite_error_with("Failed to begin transaction", e))?;
+        Ok(txid as i64)
+      }
+      None => Err(closed_error("Database is closed")),
+    }
+  }
+
+  /// Current MVCC generation (null when MVCC is disabled)
+  #[napi]
+  pub fn current_generation(&self) -> Result<Option<i64>, ErrorCode> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => Ok(db.current_generation().map(|g| g as i64)),
+      None => Err(closed_error("Database is closed")),
+    }
+  }
+
+  /// Oldest MVCC generation still readable via beginReadAt (null when MVCC is disabled)
+  #[napi]
+  pub fn oldest_readable_generation(&self) -> Result<Option<i64>, ErrorCode> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => Ok(db.oldest_readable_generation().map(|g| g as i64)),
+      None => Err(closed_error("Database is closed")),
     }
   }
 
   /// Begin a bulk-load transaction (fast path, MVCC disabled)
   #[napi]
-  pub fn begin_bulk(&self) -> Result<i64> {
+  pub fn begin_bulk(&self) -> Result<i64, ErrorCode> {
     match self.inner.as_ref() {
       Some(DatabaseInner::SingleFile(db)) => {
         let txid = db
           .begin_bulk()
-          .map_err(|e| Error::from_reason(format!("Failed to begin bulk transaction: {e}")))?;
+          .map_err(|e| kite_error_with("Failed to begin bulk transaction", e))?;
         Ok(txid as i64)
       }
-      None => Err(Error::from_reason("Database is closed")),
+      None => Err(closed_error("Database is closed")),
     }
   }
 
   /// Commit the current transaction
+  ///
+  /// A commit that lost an MVCC conflict throws with `code` set to
+  /// `KITE_CONFLICT`; the transaction is gone and can be retried.
   #[napi]
-  pub fn commit(&self) -> Result<()> {
+  pub fn commit(&self) -> Result<(), ErrorCode> {
     match self.inner.as_ref() {
       Some(DatabaseInner::SingleFile(db)) => db
         .commit()
-        .map_err(|e| Error::from_reason(format!("Failed to commit: {e}"))),
-      None => Err(Error::from_reason("Database is closed")),
+        .map_err(|e| kite_error_with("Failed to commit", e)),
+      None => Err(closed_error("Database is closed")),
+    }
+  }
+
+  /// Commit the current transaction and summarize what it changed
+  ///
+  /// Closing a nested transaction level commits nothing and returns zeros.
+  #[napi]
+  pub fn commit_stats(&self) -> Result<JsCommitStats, ErrorCode> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => db
+        .commit_stats()
+        .map(JsCommitStats::from)
+        .map_err(|e| kite_error_with("Failed to commit", e)),
+      None => Err(closed_error("Database is closed")),
     }
   }
 
   /// Commit the current transaction and return replication token when primary replication is enabled.
   #[napi]
-  pub fn commit_with_token(&self) -> Result<Option<String>> {
+  pub fn commit_with_token(&self) -> Result<Option<String>, ErrorCode> {
     match self.inner.as_ref() {
       Some(DatabaseInner::SingleFile(db)) => db
         .commit_with_token()
         .map(|token| token.map(|value| value.to_string()))
-        .map_err(|e| Error::from_reason(format!("Failed to commit with token: {e}"))),
-      None => Err(Error::from_reason("Database is closed")),
+        .map_err(|e| kite_error_with("Failed to commit with token", e)),
+      None => Err(closed_error("Database is closed")),
     }
   }
 
   /// Rollback the current transaction
   #[napi]
-  pub fn rollback(&self) -> Result<()> {
+  pub fn rollback(&self) -> Result<(), ErrorCode> {
     match self.inner.as_ref() {
       Some(DatabaseInner::SingleFile(db)) => db
         .rollback()
-        .map_err(|e| Error::from_reason(format!("Failed to rollback: {e}"))),
-      None => Err(Error::from_reason("Database is closed")),
+        .map_err(|e| kite_error_with("Failed to rollback", e)),
+      None => Err(closed_error("Database is closed")),
     }
   }
 
@@ -1727,6 +2102,35 @@ impl Database {
     }
   }
 
+  /// Get the keys for several nodes (aligned to input, null when missing)
+  #[napi(js_name = "get_node_keys")]
+  pub fn node_keys(&self, node_ids: Vec<i64>) -> Result<Vec<Option<String>>> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => {
+        let ids: Vec<NodeId> = node_ids.into_iter().map(|id| id as NodeId).collect();
+        Ok(db.node_keys(&ids))
+      }
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
+  /// Find keyed nodes whose key matches a glob (`*` any run, `?` one char)
+  ///
+  /// Text before the first wildcard narrows the scan to that key range;
+  /// patterns starting with a wildcard scan every key.
+  #[napi(js_name = "find_keys")]
+  pub fn find_keys(&self, pattern: String, limit: i64) -> Result<Vec<JsNodeKey>> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => Ok(
+        db.find_keys(&pattern, limit.max(0) as usize)
+          .into_iter()
+          .map(|(id, key)| JsNodeKey { id: id as i64, key })
+          .collect(),
+      ),
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
   /// List all node IDs
   #[napi]
   pub fn list_nodes(&self) -> Result<Vec<i64>> {
@@ -1747,6 +2151,31 @@ impl Database {
     }
   }
 
+  /// List node IDs in `[minId, maxId)`, in ascending order
+  #[napi]
+  pub fn list_nodes_in_range(&self, min_id: i64, max_id: i64) -> Result<Vec<i64>> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => Ok(
+        db.list_nodes_in_range(min_id.max(0) as NodeId, max_id.max(0) as NodeId)
+          .into_iter()
+          .map(|id| id as i64)
+          .collect(),
+      ),
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
+  /// Count nodes in `[minId, maxId)`
+  #[napi]
+  pub fn count_nodes_in_range(&self, min_id: i64, max_id: i64) -> Result<i64> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => {
+        Ok(db.count_nodes_in_range(min_id.max(0) as NodeId, max_id.max(0) as NodeId) as i64)
+      }
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
   // ========================================================================
   // Edge Operations
   // ========================================================================
@@ -1823,6 +2252,19 @@ impl Database {
     }
   }
 
+  /// Reject (or allow again) edges from a node to itself for an edge type
+  ///
+  /// Written in the current transaction and persisted with the database.
+  #[napi]
+  pub fn set_etype_no_self_loops(&self, etype: u32, enabled: bool) -> Result<()> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => db
+        .set_etype_no_self_loops(etype as ETypeId, enabled)
+        .map_err(|e| Error::from_reason(format!("Failed to set no_self_loops: {e}"))),
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
   /// Upsert an edge (create if missing, update props)
   ///
   /// Returns true if the edge was created.
@@ -1876,36 +2318,42 @@ impl Database {
     }
   }
 
-  /// Get outgoing edges for a node
+  /// Get outgoing edges for a node, optionally only those of one edge type
+  ///
+  /// With `includeKeys`, each edge also carries the destination's key.
   #[napi(js_name = "get_out_edges")]
-  pub fn out_edges(&self, node_id: i64) -> Result<Vec<JsEdge>> {
+  pub fn out_edges(
+    &self,
+    node_id: i64,
+    etype: Option<u32>,
+    include_keys: Option<bool>,
+  ) -> Result<Vec<JsEdge>> {
     match self.inner.as_ref() {
-      Some(DatabaseInner::SingleFile(db)) => Ok(
-        db.out_edges(node_id as NodeId)
-          .into_iter()
-          .map(|(etype, dst)| JsEdge {
-            etype,
-            node_id: dst as i64,
-          })
-          .collect(),
-      ),
+      Some(DatabaseInner::SingleFile(db)) => Ok(edges_to_js(
+        db,
+        db.out_edges_filtered(node_id as NodeId, etype),
+        include_keys.unwrap_or(false),
+      )),
       None => Err(Error::from_reason("Database is closed")),
     }
   }
 
-  /// Get incoming edges for a node
+  /// Get incoming edges for a node, optionally only those of one edge type
+  ///
+  /// With `includeKeys`, each edge also carries the source's key.
   #[napi(js_name = "get_in_edges")]
-  pub fn in_edges(&self, node_id: i64) -> Result<Vec<JsEdge>> {
+  pub fn in_edges(
+    &self,
+    node_id: i64,
+    etype: Option<u32>,
+    include_keys: Option<bool>,
+  ) -> Result<Vec<JsEdge>> {
     match self.inner.as_ref() {
-      Some(DatabaseInner::SingleFile(db)) => Ok(
-        db.in_edges(node_id as NodeId)
-          .into_iter()
-          .map(|(etype, src)| JsEdge {
-            etype,
-            node_id: src as i64,
-          })
-          .collect(),
-      ),
+      Some(DatabaseInner::SingleFile(db)) => Ok(edges_to_js(
+        db,
+        db.in_edges_filtered(node_id as NodeId, etype),
+        include_keys.unwrap_or(false),
+      )),
       None => Err(Error::from_reason("Database is closed")),
     }
   }
@@ -2006,6 +2454,27 @@ impl Database {
     }
   }
 
+  /// Count edges from nodes in `setA` to nodes in `setB`
+  ///
+  /// With `undirected: true`, edges from `setB` into `setA` count as well.
+  #[napi]
+  pub fn count_edges_between(
+    &self,
+    set_a: Vec<i64>,
+    set_b: Vec<i64>,
+    etype: Option<u32>,
+    undirected: Option<bool>,
+  ) -> Result<i64> {
+    let set_a: Vec<NodeId> = set_a.into_iter().map(|id| id as NodeId).collect();
+    let set_b: Vec<NodeId> = set_b.into_iter().map(|id| id as NodeId).collect();
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => {
+        Ok(db.count_edges_between(&set_a, &set_b, etype, undirected.unwrap_or(false)) as i64)
+      }
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
   // ========================================================================
   // Streaming and Pagination
   // ========================================================================
@@ -2208,6 +2677,45 @@ impl Database {
     }
   }
 
+  /// Get a node property by name, resolving the node by its key
+  #[napi(js_name = "get_node_prop_by_key")]
+  pub fn node_prop_by_key(&self, key: String, key_name: String) -> Result<Option<JsPropValue>> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => db
+        .node_prop_by_key(&key, &key_name)
+        .map(|value| value.map(|v| v.into()))
+        .map_err(|e| Error::from_reason(format!("Failed to get property: {e}"))),
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
+  /// Set a node property by name, resolving the node by its key
+  #[napi]
+  pub fn set_node_prop_by_key(
+    &self,
+    key: String,
+    key_name: String,
+    value: JsPropValue,
+  ) -> Result<()> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => db
+        .set_node_prop_by_key(&key, &key_name, value.into())
+        .map_err(|e| Error::from_reason(format!("Failed to set property: {e}"))),
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
+  /// Delete a node property by name, resolving the node by its key
+  #[napi]
+  pub fn delete_node_prop_by_key(&self, key: String, key_name: String) -> Result<()> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => db
+        .delete_node_prop_by_key(&key, &key_name)
+        .map_err(|e| Error::from_reason(format!("Failed to delete property: {e}"))),
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
   /// Delete a node property
   #[napi]
   pub fn delete_node_prop(&self, node_id: i64, key_id: u32) -> Result<()> {
@@ -2219,6 +2727,35 @@ impl Database {
     }
   }
 
+  /// Set a node property, returning the previous value (null if unset)
+  #[napi]
+  pub fn replace_node_prop(
+    &self,
+    node_id: i64,
+    key_id: u32,
+    value: JsPropValue,
+  ) -> Result<Option<JsPropValue>> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => db
+        .replace_node_prop(node_id as NodeId, key_id as PropKeyId, value.into())
+        .map(|previous| previous.map(|v| v.into()))
+        .map_err(|e| Error::from_reason(format!("Failed to set property: {e}"))),
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
+  /// Delete a node property, returning the removed value (null if unset)
+  #[napi]
+  pub fn take_node_prop(&self, node_id: i64, key_id: u32) -> Result<Option<JsPropValue>> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => db
+        .take_node_prop(node_id as NodeId, key_id as PropKeyId)
+        .map(|previous| previous.map(|v| v.into()))
+        .map_err(|e| Error::from_reason(format!("Failed to delete property: {e}"))),
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
   /// Get a specific node property
   #[napi(js_name = "get_node_prop")]
   pub fn node_prop(&self, node_id: i64, key_id: u32) -> Result<Option<JsPropValue>> {
@@ -2316,32 +2853,80 @@ impl Database {
     }
   }
 
-  /// Get a specific edge property
-  #[napi(js_name = "get_edge_prop")]
-  pub fn edge_prop(
+  /// Set an edge property, returning the previous value (null if unset)
+  #[napi]
+  pub fn replace_edge_prop(
     &self,
     src: i64,
     etype: u32,
     dst: i64,
     key_id: u32,
+    value: JsPropValue,
   ) -> Result<Option<JsPropValue>> {
     match self.inner.as_ref() {
-      Some(DatabaseInner::SingleFile(db)) => Ok(
-        db.edge_prop(
+      Some(DatabaseInner::SingleFile(db)) => db
+        .replace_edge_prop(
           src as NodeId,
           etype as ETypeId,
           dst as NodeId,
           key_id as PropKeyId,
+          value.into(),
         )
-        .map(|v| v.into()),
-      ),
+        .map(|previous| previous.map(|v| v.into()))
+        .map_err(|e| Error::from_reason(format!("Failed to set edge property: {e}"))),
       None => Err(Error::from_reason("Database is closed")),
     }
   }
 
-  /// Get all properties for an edge (returns array of {key_id, value} pairs)
-  #[napi(js_name = "get_edge_props")]
-  pub fn edge_props(&self, src: i64, etype: u32, dst: i64) -> Result<Option<Vec<JsNodeProp>>> {
+  /// Delete an edge property, returning the removed value (null if unset)
+  #[napi]
+  pub fn take_edge_prop(
+    &self,
+    src: i64,
+    etype: u32,
+    dst: i64,
+    key_id: u32,
+  ) -> Result<Option<JsPropValue>> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => db
+        .take_edge_prop(
+          src as NodeId,
+          etype as ETypeId,
+          dst as NodeId,
+          key_id as PropKeyId,
+        )
+        .map(|previous| previous.map(|v| v.into()))
+        .map_err(|e| Error::from_reason(format!("Failed to delete edge property: {e}"))),
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
+  /// Get a specific edge property
+  #[napi(js_name = "get_edge_prop")]
+  pub fn edge_prop(
+    &self,
+    src: i64,
+    etype: u32,
+    dst: i64,
+    key_id: u32,
+  ) -> Result<Option<JsPropValue>> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => Ok(
+        db.edge_prop(
+          src as NodeId,
+          etype as ETypeId,
+          dst as NodeId,
+          key_id as PropKeyId,
+        )
+        .map(|v| v.into()),
+      ),
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
+  /// Get all properties for an edge (returns array of {key_id, value} pairs)
+  #[napi(js_name = "get_edge_props")]
+  pub fn edge_props(&self, src: i64, etype: u32, dst: i64) -> Result<Option<Vec<JsNodeProp>>> {
     match self.inner.as_ref() {
       Some(DatabaseInner::SingleFile(db)) => Ok(
         db.edge_props(src as NodeId, etype as ETypeId, dst as NodeId)
@@ -2364,13 +2949,63 @@ impl Database {
   // ========================================================================
 
   /// Set a vector embedding for a node
+  ///
+  /// With `normalize: true` the vector is L2-normalized on write. Don't mix
+  /// normalized and unnormalized vectors under the same property key.
   #[napi]
-  pub fn set_node_vector(&self, node_id: i64, prop_key_id: u32, vector: Vec<f64>) -> Result<()> {
+  pub fn set_node_vector(
+    &self,
+    node_id: i64,
+    prop_key_id: u32,
+    vector: Vec<f64>,
+    normalize: Option<bool>,
+  ) -> Result<()> {
     let vector_f32: Vec<f32> = vector.iter().map(|&v| v as f32).collect();
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => {
+        let result = if normalize.unwrap_or(false) {
+          db.set_node_vector_normalized(node_id as NodeId, prop_key_id as PropKeyId, &vector_f32)
+        } else {
+          db.set_node_vector(node_id as NodeId, prop_key_id as PropKeyId, &vector_f32)
+        };
+        result.map_err(|e| Error::from_reason(format!("Failed to set vector: {e}")))
+      }
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
+  /// Overwrite specific dimensions of an existing vector, keeping the rest
+  ///
+  /// Errors if the node has no vector or an index is out of range.
+  #[napi]
+  pub fn update_node_vector_dims(
+    &self,
+    node_id: i64,
+    prop_key_id: u32,
+    updates: Vec<VectorDimUpdate>,
+  ) -> Result<()> {
+    let updates: Vec<(usize, f32)> = updates
+      .iter()
+      .map(|u| (u.index as usize, u.value as f32))
+      .collect();
     match self.inner.as_ref() {
       Some(DatabaseInner::SingleFile(db)) => db
-        .set_node_vector(node_id as NodeId, prop_key_id as PropKeyId, &vector_f32)
-        .map_err(|e| Error::from_reason(format!("Failed to set vector: {e}"))),
+        .update_node_vector_dims(node_id as NodeId, prop_key_id as PropKeyId, &updates)
+        .map_err(|e| Error::from_reason(format!("Failed to update vector: {e}"))),
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
+  /// L2-normalize every stored vector under a property key
+  ///
+  /// Requires an active write transaction. Returns the number of vectors rewritten.
+  #[napi]
+  pub fn renormalize_vectors(&self, prop_key_id: u32) -> Result<u32> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => db
+        .renormalize_vectors(prop_key_id as PropKeyId)
+        .map(|count| count as u32)
+        .map_err(|e| Error::from_reason(format!("Failed to renormalize vectors: {e}"))),
       None => Err(Error::from_reason("Database is closed")),
     }
   }
@@ -2409,6 +3044,36 @@ impl Database {
     }
   }
 
+  /// Find the k node vectors nearest to a query, including vectors
+  /// set or deleted in the current transaction
+  #[napi]
+  pub fn search_node_vectors(
+    &self,
+    prop_key_id: u32,
+    query: Vec<f64>,
+    k: u32,
+  ) -> Result<Vec<JsBruteForceResult>> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => {
+        let query: Vec<f32> = query.iter().map(|&v| v as f32).collect();
+        let hits = db
+          .search_node_vectors(prop_key_id as PropKeyId, &query, k as usize)
+          .map_err(|e| Error::from_reason(format!("Failed to search vectors: {e}")))?;
+        Ok(
+          hits
+            .into_iter()
+            .map(|hit| JsBruteForceResult {
+              node_id: hit.node_id as i64,
+              distance: hit.distance as f64,
+              similarity: hit.similarity as f64,
+            })
+            .collect(),
+        )
+      }
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
   // ========================================================================
   // Schema Operations
   // ========================================================================
@@ -2669,6 +3334,118 @@ impl Database {
     }
   }
 
+  /// Walk the graph breadth-first, calling `visitor` on each node
+  ///
+  /// The visitor is called as `visitor(nodeId, depth, parentId)` once per
+  /// reachable node (start nodes have depth 0 and a null parent) and may
+  /// return `"skipChildren"` to prune the subtree below that node or `"stop"`
+  /// to end the walk; returning nothing continues. Every visit crosses the
+  /// NAPI boundary, so prefer `traverse`/`traverseDepth` when no per-node
+  /// decision is needed. Throws if a start node doesn't exist.
+  ///
+  /// @param startNodes - Array of starting node IDs
+  /// @param direction - Edge direction to follow
+  /// @param edgeType - Optional edge type filter
+  /// @param visitor - Callback deciding how the walk proceeds
+  /// @returns Number of nodes visited
+  #[napi]
+  #[allow(clippy::type_complexity)]
+  pub fn walk(
+    &self,
+    start_nodes: Vec<i64>,
+    direction: JsTraversalDirection,
+    edge_type: Option<u32>,
+    visitor: Function<FnArgs<(i64, u32, Option<i64>)>, Option<JsWalkControl>>,
+  ) -> Result<u32> {
+    let db = match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => db,
+      None => return Err(Error::from_reason("Database is closed")),
+    };
+    let direction: TraversalDirection = direction.into();
+
+    let mut visited: HashSet<NodeId> = HashSet::new();
+    let mut queue: VecDeque<(NodeId, u32, Option<NodeId>)> = VecDeque::new();
+    for id in start_nodes {
+      let node_id = id as NodeId;
+      if !db.node_exists(node_id) {
+        return Err(Error::from_reason(format!(
+          "Start node {node_id} does not exist"
+        )));
+      }
+      if visited.insert(node_id) {
+        queue.push_back((node_id, 0, None));
+      }
+    }
+
+    let mut visits = 0u32;
+    while let Some((node_id, depth, parent)) = queue.pop_front() {
+      visits += 1;
+      let control = visitor.call(FnArgs::from((
+        node_id as i64,
+        depth,
+        parent.map(|p| p as i64),
+      )))?;
+      match control.unwrap_or(JsWalkControl::Continue) {
+        JsWalkControl::Continue => {}
+        JsWalkControl::SkipChildren => continue,
+        JsWalkControl::Stop => break,
+      }
+
+      for edge in neighbors_from_single_file(db, node_id, direction, edge_type) {
+        let next = if edge.src == node_id {
+          edge.dst
+        } else {
+          edge.src
+        };
+        if visited.insert(next) {
+          queue.push_back((next, depth + 1, Some(node_id)));
+        }
+      }
+    }
+
+    Ok(visits)
+  }
+
+  /// Build the breadth-first spanning tree rooted at a node
+  ///
+  /// Each node reachable within `maxDepth` hops appears once, with the node it
+  /// was first discovered from (null for the root), in BFS order. Enough to
+  /// draw an expandable tree without reconstructing parents client-side.
+  ///
+  /// @param source - Root node ID
+  /// @param edgeType - Optional edge type filter
+  /// @param direction - Edge direction to follow
+  /// @param maxDepth - Maximum depth below the root
+  /// @returns Tree nodes with their discovery parent and depth
+  #[napi]
+  pub fn spanning_tree(
+    &self,
+    source: i64,
+    edge_type: Option<u32>,
+    direction: JsTraversalDirection,
+    max_depth: u32,
+  ) -> Result<Vec<JsSpanningTreeNode>> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => Ok(
+        spanning_tree(
+          source as NodeId,
+          edge_type,
+          direction.into(),
+          max_depth as usize,
+          |node_id, dir, etype| neighbors_from_single_file(db, node_id, dir, etype),
+        )
+        .into_iter()
+        .map(|node| JsSpanningTreeNode {
+          node_id: node.node_id as i64,
+          parent_id: node.parent_id.map(|id| id as i64),
+          depth: node.depth as u32,
+        })
+        .collect(),
+      ),
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
   /// Count traversal results without materializing them
   ///
   /// @param startNodes - Array of starting node IDs
@@ -2744,6 +3521,70 @@ impl Database {
     }
   }
 
+  /// Traverse steps and return the k cheapest reached nodes
+  ///
+  /// Each node keeps the smallest accumulated edge weight over all paths
+  /// that reach it at a given step; after the last step the k nodes with the
+  /// lowest cost are returned, cheapest first. Weights are read from
+  /// `weightKey` (missing, non-numeric, or non-positive weights count as 1);
+  /// without a weight key every edge costs 1.
+  ///
+  /// @param startNodes - Array of starting node IDs
+  /// @param steps - Array of traversal steps (direction, edgeType)
+  /// @param k - Maximum number of results (at least 1)
+  /// @param weightKey - Optional edge property holding the weight
+  /// @returns Reached nodes with their accumulated cost
+  #[napi]
+  pub fn traverse_top_k(
+    &self,
+    start_nodes: Vec<i64>,
+    steps: Vec<JsTraversalStep>,
+    k: u32,
+    weight_key: Option<String>,
+  ) -> Result<Vec<JsTopKResult>> {
+    if k == 0 {
+      return Err(Error::from_reason("k must be at least 1"));
+    }
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => {
+        let weight_key = match weight_key {
+          Some(name) => Some(
+            db.propkey_id(&name)
+              .ok_or_else(|| Error::from_reason(format!("Unknown property key: {name}")))?,
+          ),
+          None => None,
+        };
+
+        let mut builder =
+          RustTraversalBuilder::new(start_nodes.into_iter().map(|id| id as NodeId).collect());
+        for step in &steps {
+          let etype = step.edge_type;
+          builder = match step.direction {
+            JsTraversalDirection::Out => builder.out(etype),
+            JsTraversalDirection::In => builder.r#in(etype),
+            JsTraversalDirection::Both => builder.both(etype),
+          };
+        }
+
+        Ok(
+          builder
+            .top_k(
+              k as usize,
+              |node_id, dir, etype| neighbors_from_single_file(db, node_id, dir, etype),
+              |src, etype, dst| edge_weight_from_single_file(db, src, etype, dst, weight_key),
+            )
+            .into_iter()
+            .map(|(node_id, cost)| JsTopKResult {
+              node_id: node_id as i64,
+              cost,
+            })
+            .collect(),
+        )
+      }
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
   // ========================================================================
   // Pathfinding (DB-backed)
   // ========================================================================
@@ -2887,6 +3728,8 @@ impl Database {
       min_depth: Some(1),
       max_depth,
       unique: Some(true),
+      max_neighbors_per_node: None,
+      sample_seed: None,
     };
 
     Ok(
@@ -2990,6 +3833,19 @@ impl Database {
     }
   }
 
+  /// Discard WAL records already in the snapshot, e.g. after a backup.
+  /// Fails if there are uncheckpointed changes. Returns the bytes discarded.
+  #[napi(js_name = "truncateWalToSnapshot")]
+  pub fn truncate_wal_to_snapshot(&self) -> Result<i64> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => db
+        .truncate_wal_to_snapshot()
+        .map(|bytes| bytes as i64)
+        .map_err(|e| Error::from_reason(format!("Failed to truncate WAL: {e}"))),
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
   /// Get database statistics
   #[napi]
   pub fn stats(&self) -> Result<DbStats> {
@@ -3007,6 +3863,7 @@ impl Database {
           delta_edges_deleted: s.delta_edges_deleted as i64,
           wal_segment: s.wal_segment as i64,
           wal_bytes: s.wal_bytes as i64,
+          fragmentation: s.fragmentation,
           recommend_compact: s.recommend_compact,
           mvcc_stats: s.mvcc_stats.map(|stats| MvccStats {
             active_transactions: stats.active_transactions as i64,
@@ -3016,6 +3873,11 @@ impl Database {
             last_gc_time: stats.last_gc_time as i64,
             committed_writes_size: stats.committed_writes_size as i64,
             committed_writes_pruned: stats.committed_writes_pruned as i64,
+            version_chains: stats.version_chains as i64,
+            versions_retained: stats.versions_retained as i64,
+            chain_depth_max: stats.chain_depth_max as i64,
+            chain_depth_p50: stats.chain_depth_p50 as i64,
+            chain_depth_p99: stats.chain_depth_p99 as i64,
           }),
         })
       }
@@ -3023,15 +3885,49 @@ impl Database {
     }
   }
 
+  /// Count and size WAL records by type since the last checkpoint
+  ///
+  /// Sorted by bytes, largest first.
+  #[napi]
+  pub fn wal_record_breakdown(&self) -> Result<Vec<JsWalRecordStats>> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => db
+        .wal_record_breakdown()
+        .map(|breakdown| breakdown.into_iter().map(Into::into).collect())
+        .map_err(|e| Error::from_reason(format!("Failed to scan WAL: {e}"))),
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
   /// Check database integrity
+  ///
+  /// Vector dimension and NaN/Inf problems are warnings unless `strict` is
+  /// true, in which case they are errors.
   #[napi]
-  pub fn check(&self) -> Result<CheckResult> {
+  pub fn check(&self, strict: Option<bool>) -> Result<CheckResult> {
     match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) if strict.unwrap_or(false) => {
+        Ok(CheckResult::from(db.check_strict()))
+      }
       Some(DatabaseInner::SingleFile(db)) => Ok(CheckResult::from(db.check())),
       None => Err(Error::from_reason("Database is closed")),
     }
   }
 
+  /// Check database integrity and repair what can be fixed
+  ///
+  /// Dangling edges are only removed when `allowDestructive` is true.
+  #[napi]
+  pub fn check_and_repair(&self, allow_destructive: Option<bool>) -> Result<RepairResult> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => db
+        .check_and_repair(allow_destructive.unwrap_or(false))
+        .map(RepairResult::from)
+        .map_err(|e| Error::from_reason(format!("Failed to repair: {e}"))),
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
   // ========================================================================
   // Export / Import
   // ========================================================================
@@ -3043,7 +3939,10 @@ impl Database {
       include_nodes: None,
       include_edges: None,
       include_schema: None,
+      include_vectors: None,
       pretty: None,
+      labels: None,
+      nodes_per_shard: None,
     });
     let opts = opts.into_rust();
 
@@ -3067,7 +3966,10 @@ impl Database {
       include_nodes: None,
       include_edges: None,
       include_schema: None,
+      include_vectors: None,
       pretty: None,
+      labels: None,
+      nodes_per_shard: None,
     });
     let rust_opts = opts.into_rust();
 
@@ -3098,7 +4000,10 @@ impl Database {
       include_nodes: None,
       include_edges: None,
       include_schema: None,
+      include_vectors: None,
       pretty: None,
+      labels: None,
+      nodes_per_shard: None,
     });
     let rust_opts = opts.into_rust();
 
@@ -3116,12 +4021,52 @@ impl Database {
     })
   }
 
+  /// Export database to a directory of JSONL shards plus a manifest
+  ///
+  /// Each shard holds up to `nodesPerShard` nodes and the edges leaving
+  /// them; read it back with `importSharded`.
+  #[napi]
+  pub fn export_sharded(
+    &self,
+    dir: String,
+    options: Option<ExportOptions>,
+  ) -> Result<ExportResult> {
+    let opts = options.unwrap_or(ExportOptions {
+      include_nodes: None,
+      include_edges: None,
+      include_schema: None,
+      include_vectors: None,
+      pretty: None,
+      labels: None,
+      nodes_per_shard: None,
+    });
+    let rust_opts = opts.into_rust();
+    let nodes_per_shard = rust_opts.nodes_per_shard;
+
+    let data = match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => ray_export::export_to_object_single(db, rust_opts)
+        .map_err(|e| Error::from_reason(e.to_string()))?,
+      None => return Err(Error::from_reason("Database is closed")),
+    };
+
+    let result = ray_export::export_sharded(&data, dir, nodes_per_shard)
+      .map_err(|e| Error::from_reason(e.to_string()))?;
+    Ok(ExportResult {
+      node_count: result.node_count as i64,
+      edge_count: result.edge_count as i64,
+    })
+  }
+
   /// Import database from a JSON object
+  ///
+  /// `onProgress` is called with running totals before each batch commits;
+  /// returning `false` rolls back that batch and stops the import.
   #[napi]
   pub fn import_from_object(
     &self,
     data: serde_json::Value,
     options: Option<ImportOptions>,
+    on_progress: Option<Function<ImportProgress, Option<bool>>>,
   ) -> Result<ImportResult> {
     let opts = options.unwrap_or(ImportOptions {
       skip_existing: None,
@@ -3131,27 +4076,24 @@ impl Database {
     let parsed: ray_export::ExportedDatabase =
       serde_json::from_value(data).map_err(|e| Error::from_reason(e.to_string()))?;
 
-    let result = match self.inner.as_ref() {
+    match self.inner.as_ref() {
       Some(DatabaseInner::SingleFile(db)) => {
-        ray_export::import_from_object_single(db, &parsed, rust_opts)
-          .map_err(|e| Error::from_reason(e.to_string()))?
+        import_with_progress(db, &parsed, rust_opts, on_progress)
       }
-      None => return Err(Error::from_reason("Database is closed")),
-    };
-
-    Ok(ImportResult {
-      node_count: result.node_count as i64,
-      edge_count: result.edge_count as i64,
-      skipped: result.skipped as i64,
-    })
+      None => Err(Error::from_reason("Database is closed")),
+    }
   }
 
   /// Import database from a JSON file
+  ///
+  /// `onProgress` is called with running totals before each batch commits;
+  /// returning `false` rolls back that batch and stops the import.
   #[napi]
   pub fn import_from_json(
     &self,
     path: String,
     options: Option<ImportOptions>,
+    on_progress: Option<Function<ImportProgress, Option<bool>>>,
   ) -> Result<ImportResult> {
     let opts = options.unwrap_or(ImportOptions {
       skip_existing: None,
@@ -3161,19 +4103,39 @@ impl Database {
     let parsed =
       ray_export::import_from_json(path).map_err(|e| Error::from_reason(e.to_string()))?;
 
-    let result = match self.inner.as_ref() {
+    match self.inner.as_ref() {
       Some(DatabaseInner::SingleFile(db)) => {
-        ray_export::import_from_object_single(db, &parsed, rust_opts)
-          .map_err(|e| Error::from_reason(e.to_string()))?
+        import_with_progress(db, &parsed, rust_opts, on_progress)
       }
-      None => return Err(Error::from_reason("Database is closed")),
-    };
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
 
-    Ok(ImportResult {
-      node_count: result.node_count as i64,
-      edge_count: result.edge_count as i64,
-      skipped: result.skipped as i64,
-    })
+  /// Import database from a sharded export directory
+  ///
+  /// All nodes are imported before any edge, whatever the shard order, so
+  /// edges between shards resolve. `onProgress` behaves as in
+  /// `importFromJson`.
+  #[napi]
+  pub fn import_sharded(
+    &self,
+    dir: String,
+    options: Option<ImportOptions>,
+    on_progress: Option<Function<ImportProgress, Option<bool>>>,
+  ) -> Result<ImportResult> {
+    let opts = options.unwrap_or(ImportOptions {
+      skip_existing: None,
+      batch_size: None,
+    });
+    let rust_opts = opts.into_rust();
+    let parsed = ray_export::import_sharded(dir).map_err(|e| Error::from_reason(e.to_string()))?;
+
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => {
+        import_with_progress(db, &parsed, rust_opts, on_progress)
+      }
+      None => Err(Error::from_reason("Database is closed")),
+    }
   }
 
   // ========================================================================
@@ -3285,7 +4247,43 @@ impl Database {
     }
   }
 
-  /// Get cache statistics
+  /// Evict least recently used query results down to `maxEntries`,
+  /// returning how many were evicted
+  #[napi]
+  pub fn cache_trim_query(&self, max_entries: i64) -> Result<i64> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => {
+        Ok(db.cache_trim_query(max_entries.max(0) as usize) as i64)
+      }
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
+  /// Evict least recently used traversals down to `maxEntries`,
+  /// returning how many were evicted
+  #[napi]
+  pub fn cache_trim_traversal(&self, max_entries: i64) -> Result<i64> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => {
+        Ok(db.cache_trim_traversal(max_entries.max(0) as usize) as i64)
+      }
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
+  /// Evict least recently used node and edge props down to `maxEntries` each,
+  /// returning how many were evicted
+  #[napi]
+  pub fn cache_trim_property(&self, max_entries: i64) -> Result<i64> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => {
+        Ok(db.cache_trim_property(max_entries.max(0) as usize) as i64)
+      }
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
+  /// Get cache statistics (null when caching was not enabled at open)
   #[napi]
   pub fn cache_stats(&self) -> Result<Option<JsCacheStats>> {
     match self.inner.as_ref() {
@@ -3299,6 +4297,9 @@ impl Database {
         query_cache_hits: s.query_cache_hits as i64,
         query_cache_misses: s.query_cache_misses as i64,
         query_cache_size: s.query_cache_size as i64,
+        key_cache_hits: s.key_cache_hits as i64,
+        key_cache_misses: s.key_cache_misses as i64,
+        key_cache_size: s.key_cache_size as i64,
       })),
       None => Err(Error::from_reason("Database is closed")),
     }
@@ -3316,6 +4317,27 @@ impl Database {
     }
   }
 
+  /// Recoverable issues noticed while opening (e.g. file locking unavailable)
+  #[napi]
+  pub fn open_warnings(&self) -> Result<Vec<String>> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => Ok(db.open_warnings().to_vec()),
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
+  /// Reset the rolling write/read rates reported by collectMetrics
+  #[napi]
+  pub fn reset_op_rates(&self) -> Result<()> {
+    match self.inner.as_ref() {
+      Some(DatabaseInner::SingleFile(db)) => {
+        db.reset_op_rates();
+        Ok(())
+      }
+      None => Err(Error::from_reason("Database is closed")),
+    }
+  }
+
   // ========================================================================
   // Internal Helpers
   // ========================================================================
@@ -3399,29 +4421,6 @@ fn resolve_weight_key_single_file(
   Ok(None)
 }
 
-fn prop_value_to_weight(value: Option<PropValue>) -> f64 {
-  let weight = match value {
-    Some(PropValue::Bool(v)) => {
-      if v {
-        1.0
-      } else {
-        0.0
-      }
-    }
-    Some(PropValue::I64(v)) => v as f64,
-    Some(PropValue::F64(v)) => v,
-    Some(PropValue::String(v)) => v.parse::<f64>().unwrap_or(1.0),
-    Some(PropValue::VectorF32(_)) => 1.0,
-    Some(PropValue::Null) | None => 1.0,
-  };
-
-  if weight.is_finite() && weight > 0.0 {
-    weight
-  } else {
-    1.0
-  }
-}
-
 fn edge_weight_from_single_file(
   db: &RustSingleFileDB,
   src: NodeId,
@@ -4041,6 +5040,14 @@ pub fn backup_info(backup_path: String) -> Result<BackupResult> {
     .map_err(|e| Error::from_reason(format!("Failed to inspect backup: {e}")))
 }
 
+/// List restorable backups in a directory, newest first
+#[napi]
+pub fn list_backups(dir: String) -> Result<Vec<BackupResult>> {
+  core_backup::list_backups(dir)
+    .map(|backups| backups.into_iter().map(BackupResult::from).collect())
+    .map_err(|e| Error::from_reason(format!("Failed to list backups: {e}")))
+}
+
 /// Create a backup from a database path without opening it
 #[napi]
 pub fn create_offline_backup(
@@ -0,0 +1,151 @@
+//! Error codes for errors thrown to JavaScript
+//!
+//! napi-rs exposes an error's status as `err.code` on the thrown JS error.
+//! Methods returning `Result<T, ErrorCode>` derive that status from the
+//! [`KiteError`] variant, so callers can branch on `err.code` (e.g. retry on
+//! `KITE_CONFLICT`) instead of matching messages.
+
+use napi::{Error, Status};
+
+use crate::error::KiteError;
+
+/// Stable `err.code` values
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ErrorCode {
+  /// MVCC conflict at commit; the transaction can be retried
+  Conflict,
+  /// Node or key doesn't exist
+  NotFound,
+  /// Key already exists
+  DuplicateKey,
+  /// Write to a read-only database
+  ReadOnly,
+  /// Write rejected by a schema constraint
+  Constraint,
+  /// Property holds a different type than requested
+  TypeMismatch,
+  /// Unknown type or malformed schema
+  InvalidSchema,
+  /// Invalid query or builder usage
+  InvalidQuery,
+  /// Operation not valid in the current transaction state
+  TransactionState,
+  /// Database or handle is closed
+  Closed,
+  /// WAL is full until a checkpoint runs
+  WalFull,
+  /// File contents failed validation
+  Corrupt,
+  /// I/O failure
+  Io,
+  /// Any other database error
+  Internal,
+  /// Error raised by the bindings themselves, e.g. a malformed argument
+  Napi(Status),
+}
+
+impl AsRef<str> for ErrorCode {
+  fn as_ref(&self) -> &str {
+    match self {
+      ErrorCode::Conflict => "KITE_CONFLICT",
+      ErrorCode::NotFound => "KITE_NOT_FOUND",
+      ErrorCode::DuplicateKey => "KITE_DUPLICATE_KEY",
+      ErrorCode::ReadOnly => "KITE_READ_ONLY",
+      ErrorCode::Constraint => "KITE_CONSTRAINT",
+      ErrorCode::TypeMismatch => "KITE_TYPE_MISMATCH",
+      ErrorCode::InvalidSchema => "KITE_INVALID_SCHEMA",
+      ErrorCode::InvalidQuery => "KITE_INVALID_QUERY",
+      ErrorCode::TransactionState => "KITE_TRANSACTION_STATE",
+      ErrorCode::Closed => "KITE_CLOSED",
+      ErrorCode::WalFull => "KITE_WAL_FULL",
+      ErrorCode::Corrupt => "KITE_CORRUPT",
+      ErrorCode::Io => "KITE_IO",
+      ErrorCode::Internal => "KITE_INTERNAL",
+      ErrorCode::Napi(status) => status.as_ref(),
+    }
+  }
+}
+
+impl From<&KiteError> for ErrorCode {
+  fn from(e: &KiteError) -> Self {
+    match e {
+      KiteError::Conflict { .. } => ErrorCode::Conflict,
+      KiteError::NodeNotFound(_) | KiteError::KeyNotFound(_) => ErrorCode::NotFound,
+      KiteError::DuplicateKey(_) => ErrorCode::DuplicateKey,
+      KiteError::ReadOnly => ErrorCode::ReadOnly,
+      KiteError::Constraint(_) => ErrorCode::Constraint,
+      KiteError::PropTypeMismatch { .. } => ErrorCode::TypeMismatch,
+      KiteError::InvalidSchema(_) => ErrorCode::InvalidSchema,
+      KiteError::InvalidQuery(_) => ErrorCode::InvalidQuery,
+      KiteError::NoTransaction
+      | KiteError::TransactionInProgress
+      | KiteError::RollbackOnly
+      | KiteError::UnknownSavepoint(_) => ErrorCode::TransactionState,
+      KiteError::DatabaseClosed => ErrorCode::Closed,
+      KiteError::WalBufferFull => ErrorCode::WalFull,
+      KiteError::InvalidMagic { .. }
+      | KiteError::VersionMismatch { .. }
+      | KiteError::CrcMismatch { .. }
+      | KiteError::InvalidSnapshot(_)
+      | KiteError::InvalidWal(_)
+      | KiteError::InvalidSection(_)
+      | KiteError::InvalidPropTag(_)
+      | KiteError::InvalidWalRecordType(_) => ErrorCode::Corrupt,
+      KiteError::Io(_) => ErrorCode::Io,
+      _ => ErrorCode::Internal,
+    }
+  }
+}
+
+/// Convert a database error, keeping its message
+pub(crate) fn kite_error(e: KiteError) -> Error<ErrorCode> {
+  Error::new(ErrorCode::from(&e), e.to_string())
+}
+
+/// Convert a database error, prefixing its message with `context`
+pub(crate) fn kite_error_with(context: &str, e: KiteError) -> Error<ErrorCode> {
+  Error::new(ErrorCode::from(&e), format!("{context}: {e}"))
+}
+
+/// Convert an error raised by the bindings, keeping its napi status as code
+pub(crate) fn napi_error(e: Error) -> Error<ErrorCode> {
+  Error::new(ErrorCode::Napi(e.status), e.reason)
+}
+
+/// Error for a call on a closed handle
+pub(crate) fn closed_error(reason: &str) -> Error<ErrorCode> {
+  Error::new(ErrorCode::Closed, reason.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_conflict_has_its_own_code() {
+    let err = kite_error_with(
+      "Failed to commit",
+      KiteError::conflict(7, vec!["n:1".into()]),
+    );
+    assert_eq!(err.status.as_ref(), "KITE_CONFLICT");
+    assert!(err
+      .reason
+      .starts_with("Failed to commit: Transaction 7 conflict"));
+
+    let err = kite_error(KiteError::Internal("boom".into()));
+    assert_eq!(err.status.as_ref(), "KITE_INTERNAL");
+    assert_eq!(err.reason, "Internal error: boom");
+  }
+
+  #[test]
+  fn test_transaction_state_errors_share_a_code() {
+    for e in [
+      KiteError::NoTransaction,
+      KiteError::TransactionInProgress,
+      KiteError::UnknownSavepoint("sp".into()),
+    ] {
+      assert_eq!(ErrorCode::from(&e), ErrorCode::TransactionState);
+    }
+    assert_eq!(closed_error("Kite is closed").status, ErrorCode::Closed);
+  }
+}
@@ -11,12 +11,53 @@ use std::collections::HashMap;
 use std::sync::Arc;
 
 use crate::api::kite::{Kite as RustKite, NodeRef};
+use crate::error::Result as KiteResult;
 use crate::types::{NodeId, PropValue};
 
-use super::conversion::{js_props_to_map, js_value_to_prop_value, key_suffix_from_js};
+use super::conversion::{
+  insert_key_from_js, js_declared_units, js_props_to_map, js_value_to_prop_value,
+  key_input_from_js, key_suffix_from_js, KeyInput,
+};
 use super::helpers::{node_props, node_to_js};
 use super::key_spec::KeySpec;
 
+/// Check `$units` declared in a props object against the node type's units
+pub(crate) fn check_declared_units(
+  ray: &RwLock<Option<RustKite>>,
+  node_type: &str,
+  props: Option<&Object>,
+) -> Result<()> {
+  let declared = js_declared_units(props)?;
+  if declared.is_empty() {
+    return Ok(());
+  }
+  let guard = ray.read();
+  let ray = guard
+    .as_ref()
+    .ok_or_else(|| Error::from_reason("Kite is closed"))?;
+  ray
+    .check_units(node_type, &declared)
+    .map_err(|e| Error::from_reason(e.to_string()))
+}
+
+pub(crate) fn check_declared_node_units(
+  ray: &RwLock<Option<RustKite>>,
+  node_id: NodeId,
+  props: Option<&Object>,
+) -> Result<()> {
+  let declared = js_declared_units(props)?;
+  if declared.is_empty() {
+    return Ok(());
+  }
+  let guard = ray.read();
+  let ray = guard
+    .as_ref()
+    .ok_or_else(|| Error::from_reason("Kite is closed"))?;
+  ray
+    .check_node_units(node_id, &declared)
+    .map_err(|e| Error::from_reason(e.to_string()))
+}
+
 // =============================================================================
 // Insert Builder
 // =============================================================================
@@ -56,7 +97,8 @@ impl KiteInsertBuilder {
     key: Unknown,
     props: Option<Object>,
   ) -> Result<KiteInsertExecutorSingle> {
-    let key_suffix = key_suffix_from_js(&env, self.key_spec.as_ref(), key)?;
+    let key_suffix = insert_key_from_js(&env, self.key_spec.as_ref(), key)?;
+    check_declared_units(&self.ray, &self.node_type, props.as_ref())?;
     let props_map = js_props_to_map(&env, props)?;
     Ok(KiteInsertExecutorSingle {
       ray: self.ray.clone(),
@@ -74,7 +116,8 @@ impl KiteInsertBuilder {
       let obj = entry.coerce_to_object()?;
       let key: Unknown = obj.get_named_property("key")?;
       let props: Option<Object> = obj.get_named_property("props")?;
-      let key_suffix = key_suffix_from_js(&env, self.key_spec.as_ref(), key)?;
+      let key_suffix = insert_key_from_js(&env, self.key_spec.as_ref(), key)?;
+      check_declared_units(&self.ray, &self.node_type, props.as_ref())?;
       let props_map = js_props_to_map(&env, props)?;
       items.push((key_suffix, props_map));
     }
@@ -91,7 +134,8 @@ impl KiteInsertBuilder {
 pub struct KiteInsertExecutorSingle {
   ray: Arc<RwLock<Option<RustKite>>>,
   node_type: String,
-  key_suffix: String,
+  /// `None` for an autoincrement key allocated at execution
+  key_suffix: Option<String>,
   props: HashMap<String, PropValue>,
 }
 
@@ -101,15 +145,24 @@ impl KiteInsertExecutorSingle {
   #[napi]
   pub fn execute(&mut self) -> Result<()> {
     let props = std::mem::take(&mut self.props);
-    insert_single_execute(&self.ray, &self.node_type, &self.key_suffix, props)
+    insert_single_execute(
+      &self.ray,
+      &self.node_type,
+      self.key_suffix.as_deref(),
+      props,
+    )
   }
 
   /// Execute the insert and return the node
   #[napi]
   pub fn returning(&mut self, env: Env) -> Result<Object<'_>> {
     let props = std::mem::take(&mut self.props);
-    let (node_ref, props) =
-      insert_single_returning(&self.ray, &self.node_type, &self.key_suffix, props)?;
+    let (node_ref, props) = insert_single_returning(
+      &self.ray,
+      &self.node_type,
+      self.key_suffix.as_deref(),
+      props,
+    )?;
     let (node_id, node_key, node_type) = node_ref.into_parts();
     node_to_js(&env, node_id, node_key, &node_type, props)
   }
@@ -120,7 +173,7 @@ impl KiteInsertExecutorSingle {
 pub struct KiteInsertExecutorMany {
   ray: Arc<RwLock<Option<RustKite>>>,
   node_type: String,
-  entries: Vec<(String, HashMap<String, PropValue>)>,
+  entries: Vec<(Option<String>, HashMap<String, PropValue>)>,
 }
 
 #[napi]
@@ -149,10 +202,23 @@ impl KiteInsertExecutorMany {
   }
 }
 
+/// Resolve an insert key, allocating autoincrement keys in the insert's
+/// transaction
+fn resolve_insert_key(
+  ray: &RustKite,
+  node_type: &str,
+  key_suffix: Option<&str>,
+) -> KiteResult<String> {
+  match key_suffix {
+    Some(key_suffix) => Ok(key_suffix.to_string()),
+    None => ray.next_key(node_type),
+  }
+}
+
 fn insert_single_execute(
   ray: &Arc<RwLock<Option<RustKite>>>,
   node_type: &str,
-  key_suffix: &str,
+  key_suffix: Option<&str>,
   props: HashMap<String, PropValue>,
 ) -> Result<()> {
   let mut guard = ray.write();
@@ -161,18 +227,17 @@ fn insert_single_execute(
     .ok_or_else(|| Error::from_reason("Kite is closed"))?;
 
   ray
-    .insert(node_type)
-    .map_err(|e| Error::from_reason(e.to_string()))?
-    .values(key_suffix, props)
-    .map_err(|e| Error::from_reason(e.to_string()))?
-    .execute()
+    .with_write_tx(|ray| {
+      let key_suffix = resolve_insert_key(ray, node_type, key_suffix)?;
+      ray.insert(node_type)?.values(&key_suffix, props)?.execute()
+    })
     .map_err(|e| Error::from_reason(e.to_string()))
 }
 
 fn insert_single_returning(
   ray: &Arc<RwLock<Option<RustKite>>>,
   node_type: &str,
-  key_suffix: &str,
+  key_suffix: Option<&str>,
   props: HashMap<String, PropValue>,
 ) -> Result<(NodeRef, HashMap<String, PropValue>)> {
   let mut guard = ray.write();
@@ -182,11 +247,13 @@ fn insert_single_returning(
 
   let props_for_return = props.clone();
   let node_ref = ray
-    .insert(node_type)
-    .map_err(|e| Error::from_reason(e.to_string()))?
-    .values(key_suffix, props)
-    .map_err(|e| Error::from_reason(e.to_string()))?
-    .returning()
+    .with_write_tx(|ray| {
+      let key_suffix = resolve_insert_key(ray, node_type, key_suffix)?;
+      ray
+        .insert(node_type)?
+        .values(&key_suffix, props)?
+        .returning()
+    })
     .map_err(|e| Error::from_reason(e.to_string()))?;
 
   Ok((node_ref, props_for_return))
@@ -195,7 +262,7 @@ fn insert_single_returning(
 fn insert_many(
   ray: &Arc<RwLock<Option<RustKite>>>,
   node_type: &str,
-  entries: Vec<(String, HashMap<String, PropValue>)>,
+  entries: Vec<(Option<String>, HashMap<String, PropValue>)>,
   load_props: bool,
 ) -> Result<Vec<(NodeRef, Option<HashMap<String, PropValue>>)>> {
   if entries.is_empty() {
@@ -206,27 +273,30 @@ fn insert_many(
   let ray = guard
     .as_mut()
     .ok_or_else(|| Error::from_reason("Kite is closed"))?;
-
-  if !load_props {
-    ray
-      .insert(node_type)
-      .map_err(|e| Error::from_reason(e.to_string()))?
-      .values_many_owned(entries)
-      .map_err(|e| Error::from_reason(e.to_string()))?
-      .execute()
-      .map_err(|e| Error::from_reason(e.to_string()))?;
-    return Ok(Vec::new());
-  }
-
-  let props_for_return: Vec<HashMap<String, PropValue>> =
-    entries.iter().map(|(_, props)| props.clone()).collect();
+  let props_for_return: Vec<HashMap<String, PropValue>> = if load_props {
+    entries.iter().map(|(_, props)| props.clone()).collect()
+  } else {
+    Vec::new()
+  };
 
   let node_refs = ray
-    .insert(node_type)
-    .map_err(|e| Error::from_reason(e.to_string()))?
-    .values_many_owned(entries)
-    .map_err(|e| Error::from_reason(e.to_string()))?
-    .returning()
+    .with_write_tx(|ray| {
+      let entries = entries
+        .into_iter()
+        .map(|(key_suffix, props)| {
+          Ok((
+            resolve_insert_key(ray, node_type, key_suffix.as_deref())?,
+            props,
+          ))
+        })
+        .collect::<KiteResult<Vec<_>>>()?;
+      let insert = ray.insert(node_type)?.values_many_owned(entries)?;
+      if load_props {
+        insert.returning()
+      } else {
+        insert.execute().map(|()| Vec::new())
+      }
+    })
     .map_err(|e| Error::from_reason(e.to_string()))?;
 
   Ok(
@@ -278,6 +348,7 @@ impl KiteUpsertBuilder {
     props: Option<Object>,
   ) -> Result<KiteUpsertExecutorSingle> {
     let key_suffix = key_suffix_from_js(&env, self.key_spec.as_ref(), key)?;
+    check_declared_units(&self.ray, &self.node_type, props.as_ref())?;
     let props_map = js_props_to_map(&env, props)?;
     Ok(KiteUpsertExecutorSingle {
       ray: self.ray.clone(),
@@ -296,6 +367,7 @@ impl KiteUpsertBuilder {
       let key: Unknown = obj.get_named_property("key")?;
       let props: Option<Object> = obj.get_named_property("props")?;
       let key_suffix = key_suffix_from_js(&env, self.key_spec.as_ref(), key)?;
+      check_declared_units(&self.ray, &self.node_type, props.as_ref())?;
       let props_map = js_props_to_map(&env, props)?;
       items.push((key_suffix, props_map));
     }
@@ -500,6 +572,7 @@ impl KiteUpdateBuilder {
   /// Set multiple properties at once
   #[napi]
   pub fn set_all(&mut self, env: Env, props: Object) -> Result<()> {
+    check_declared_node_units(&self.ray, self.node_id, Some(&props))?;
     let props_map = js_props_to_map(&env, Some(props))?;
     for (prop_name, value) in props_map {
       self.updates.insert(prop_name, Some(value));
@@ -545,6 +618,8 @@ pub struct KiteUpsertByIdBuilder {
   pub(crate) ray: Arc<RwLock<Option<RustKite>>>,
   pub(crate) node_type: String,
   pub(crate) node_id: NodeId,
+  pub(crate) key_spec: Arc<KeySpec>,
+  pub(crate) key: Option<KeyInput>,
   pub(crate) updates: HashMap<String, Option<PropValue>>,
 }
 
@@ -553,11 +628,14 @@ impl KiteUpsertByIdBuilder {
     ray: Arc<RwLock<Option<RustKite>>>,
     node_type: String,
     node_id: NodeId,
+    key_spec: Arc<KeySpec>,
   ) -> Self {
     Self {
       ray,
       node_type,
       node_id,
+      key_spec,
+      key: None,
       updates: HashMap::new(),
     }
   }
@@ -565,6 +643,19 @@ impl KiteUpsertByIdBuilder {
 
 #[napi]
 impl KiteUpsertByIdBuilder {
+  /// Set the node key
+  ///
+  /// For a `parts` key spec the key may be a partial object: the given
+  /// fields replace those components of the node's current key and the rest
+  /// are kept. If the recomputed key differs, the node is re-keyed in the
+  /// same transaction as the property updates. If it collides with another
+  /// node's key, execute() throws and nothing is written.
+  #[napi]
+  pub fn key(&mut self, env: Env, key: Unknown) -> Result<()> {
+    self.key = Some(key_input_from_js(&env, self.key_spec.as_ref(), key)?);
+    Ok(())
+  }
+
   /// Set a node property
   #[napi]
   pub fn set(&mut self, env: Env, prop_name: String, value: Unknown) -> Result<()> {
@@ -583,6 +674,7 @@ impl KiteUpsertByIdBuilder {
   /// Set multiple properties at once
   #[napi]
   pub fn set_all(&mut self, env: Env, props: Object) -> Result<()> {
+    check_declared_units(&self.ray, &self.node_type, Some(&props))?;
     let props_map = js_props_to_map(&env, Some(props))?;
     for (prop_name, value) in props_map {
       self.updates.insert(prop_name, Some(value));
@@ -598,9 +690,21 @@ impl KiteUpsertByIdBuilder {
       .as_mut()
       .ok_or_else(|| Error::from_reason("Kite is closed"))?;
 
+    let key_suffix = match &self.key {
+      Some(KeyInput::Suffix(suffix)) => Some(suffix.clone()),
+      Some(KeyInput::Patch(patch)) => {
+        let current_key = ray.raw().node_key(self.node_id);
+        Some(self.key_spec.merge_parts(current_key.as_deref(), patch)?)
+      }
+      None => None,
+    };
+
     let mut builder = ray
       .upsert_by_id(&self.node_type, self.node_id)
       .map_err(|e| Error::from_reason(e.to_string()))?;
+    if let Some(key_suffix) = key_suffix {
+      builder = builder.key(key_suffix);
+    }
 
     for (prop_name, value_opt) in &self.updates {
       builder = match value_opt {
@@ -627,6 +731,7 @@ pub struct KiteUpdateEdgeBuilder {
   pub(crate) edge_type: String,
   pub(crate) dst: NodeId,
   pub(crate) updates: HashMap<String, Option<PropValue>>,
+  pub(crate) ensure: bool,
 }
 
 impl KiteUpdateEdgeBuilder {
@@ -642,12 +747,20 @@ impl KiteUpdateEdgeBuilder {
       edge_type,
       dst,
       updates: HashMap::new(),
+      ensure: false,
     }
   }
 }
 
 #[napi]
 impl KiteUpdateEdgeBuilder {
+  /// Create the edge if it doesn't exist before applying the updates
+  #[napi]
+  pub fn ensure(&mut self) -> Result<()> {
+    self.ensure = true;
+    Ok(())
+  }
+
   /// Set an edge property
   #[napi]
   pub fn set(&mut self, env: Env, prop_name: String, value: Unknown) -> Result<()> {
@@ -674,10 +787,12 @@ impl KiteUpdateEdgeBuilder {
   }
 
   /// Execute the edge update
+  ///
+  /// Returns whether the edge was created, which only happens with `ensure()`.
   #[napi]
-  pub fn execute(&self) -> Result<()> {
-    if self.updates.is_empty() {
-      return Ok(());
+  pub fn execute(&self) -> Result<bool> {
+    if self.updates.is_empty() && !self.ensure {
+      return Ok(false);
     }
 
     let mut guard = self.ray.write();
@@ -689,6 +804,9 @@ impl KiteUpdateEdgeBuilder {
       .update_edge(self.src, &self.edge_type, self.dst)
       .map_err(|e| Error::from_reason(e.to_string()))?;
 
+    if self.ensure {
+      builder = builder.ensure();
+    }
     for (prop_name, value_opt) in &self.updates {
       builder = match value_opt {
         Some(value) => builder.set(prop_name, value.clone()),
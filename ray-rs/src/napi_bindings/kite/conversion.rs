@@ -6,7 +6,7 @@
 use napi::bindgen_prelude::*;
 use std::collections::HashMap;
 
-use crate::api::kite::{PropDef, PropType as KitePropType};
+use crate::api::kite::{PropDef, PropFilter, PropType as KitePropType};
 use crate::types::PropValue;
 
 use super::super::database::{JsPropValue, PropType as DbPropType};
@@ -24,17 +24,26 @@ pub(crate) fn prop_spec_to_def(name: &str, spec: &JsPropSpec) -> Result<PropDef>
     "int" => PropDef::int(name),
     "float" => PropDef::float(name),
     "bool" => PropDef::bool(name),
-    "vector" => PropDef {
-      name: name.to_string(),
-      prop_type: KitePropType::Any,
-      required: false,
-      default: None,
-    },
+    "json" => PropDef::json(name),
+    "vector" => PropDef::vector(name),
     "any" => PropDef {
       name: name.to_string(),
       prop_type: KitePropType::Any,
       required: false,
       default: None,
+      allowed: None,
+      unique: false,
+      ordered: false,
+      unit: None,
+      normalize: false,
+    },
+    "enum" => match spec.allowed.as_ref() {
+      Some(allowed) if !allowed.is_empty() => PropDef::string_enum(name, allowed.iter().cloned()),
+      _ => {
+        return Err(Error::from_reason(format!(
+          "enum prop '{name}' requires a non-empty allowed list"
+        )))
+      }
     },
     other => return Err(Error::from_reason(format!("unknown prop type: {other}"))),
   };
@@ -44,6 +53,22 @@ pub(crate) fn prop_spec_to_def(name: &str, spec: &JsPropSpec) -> Result<PropDef>
     prop = prop.required();
   }
 
+  if spec.unique.unwrap_or(false) {
+    prop = prop.unique();
+  }
+
+  if spec.ordered.unwrap_or(false) {
+    prop = prop.ordered();
+  }
+
+  if let Some(unit) = spec.unit.as_deref() {
+    prop = prop.unit(unit);
+  }
+
+  if spec.normalize.unwrap_or(false) {
+    prop = prop.normalized();
+  }
+
   if let Some(default_value) = spec.r#default.clone() {
     prop = prop.default(default_value.into());
   }
@@ -56,7 +81,11 @@ pub(crate) fn prop_spec_to_def(name: &str, spec: &JsPropSpec) -> Result<PropDef>
 // =============================================================================
 
 /// Convert a JS Unknown value to a Rust PropValue
-pub(crate) fn js_value_to_prop_value(_env: &Env, value: Unknown) -> Result<PropValue> {
+///
+/// A `Float32Array` is stored as a vector; plain objects and arrays are
+/// stored as JSON documents, so they read back unchanged. A plain array
+/// written to a prop declared `vector` is rejected by the schema check.
+pub(crate) fn js_value_to_prop_value(env: &Env, value: Unknown) -> Result<PropValue> {
   match value.get_type()? {
     ValueType::Undefined => Ok(PropValue::Null),
     ValueType::Null => Ok(PropValue::Null),
@@ -73,21 +102,24 @@ pub(crate) fn js_value_to_prop_value(_env: &Env, value: Unknown) -> Result<PropV
     }
     ValueType::Object => {
       let obj = value.coerce_to_object()?;
-      if obj.is_array()? {
-        // SAFETY: value is an array; NAPI will validate element types on cast.
-        let values: Vec<f64> = unsafe { value.cast()? };
-        let values = values.into_iter().map(|v| v as f32).collect();
-        return Ok(PropValue::VectorF32(values));
+      if obj.is_typedarray()? {
+        let values = Float32Array::from_unknown(value)
+          .map_err(|_| Error::from_reason("Vector props must be a Float32Array"))?;
+        return Ok(PropValue::VectorF32(values.to_vec()));
       }
 
       // Check for JsPropValue-style object
-      if obj.has_named_property("propType")? {
+      if !obj.is_array()? && obj.has_named_property("propType")? {
         let prop_type: DbPropType = obj.get_named_property("propType")?;
         let bool_value: Option<bool> = obj.get_named_property("boolValue")?;
         let int_value: Option<i64> = obj.get_named_property("intValue")?;
         let float_value: Option<f64> = obj.get_named_property("floatValue")?;
         let string_value: Option<String> = obj.get_named_property("stringValue")?;
         let vector_value: Option<Vec<f64>> = obj.get_named_property("vectorValue")?;
+        let json_value: Option<serde_json::Value> = match obj.get_named_property("jsonValue")? {
+          Some(value) => Some(js_value_to_json(env, value)?),
+          None => None,
+        };
         let prop_value = JsPropValue {
           prop_type,
           bool_value,
@@ -95,19 +127,124 @@ pub(crate) fn js_value_to_prop_value(_env: &Env, value: Unknown) -> Result<PropV
           float_value,
           string_value,
           vector_value,
+          json_value,
         };
         return Ok(prop_value.into());
       }
 
-      Err(Error::from_reason(
-        "Object props must be plain values or JsPropValue",
-      ))
+      Ok(PropValue::Json(js_value_to_json(env, value)?))
     }
     _ => Err(Error::from_reason("Unsupported prop value type")),
   }
 }
 
+/// Convert a JSON-shaped JS value to a JSON document
+///
+/// Objects must be plain (their prototype is `Object.prototype` or null), so
+/// dates, maps, typed arrays and class instances are rejected rather than
+/// flattened to `{}`. Like `JSON.stringify`, undefined object entries are
+/// dropped and undefined array elements become null.
+pub(crate) fn js_value_to_json(env: &Env, value: Unknown) -> Result<serde_json::Value> {
+  let object: Object = env.get_global()?.get_named_property("Object")?;
+  let object_prototype: Unknown = object.get_named_property("prototype")?;
+  js_value_to_json_with(env, value, &object_prototype)
+}
+
+fn js_value_to_json_with(
+  env: &Env,
+  value: Unknown,
+  object_prototype: &Unknown,
+) -> Result<serde_json::Value> {
+  match value.get_type()? {
+    ValueType::Undefined | ValueType::Null => Ok(serde_json::Value::Null),
+    ValueType::Boolean => Ok(serde_json::Value::Bool(value.coerce_to_bool()?)),
+    ValueType::Number => {
+      let number = value.coerce_to_number()?.get_double()?;
+      if number.fract() == 0.0 && number.abs() < i64::MAX as f64 {
+        return Ok(serde_json::Value::from(number as i64));
+      }
+      serde_json::Number::from_f64(number)
+        .map(serde_json::Value::Number)
+        .ok_or_else(|| Error::from_reason(format!("JSON props can't hold {number}")))
+    }
+    ValueType::String => Ok(serde_json::Value::String(
+      value.coerce_to_string()?.into_utf8()?.as_str()?.to_string(),
+    )),
+    ValueType::BigInt => {
+      // SAFETY: value type checked as BigInt above.
+      let big: BigInt = unsafe { value.cast()? };
+      match big.get_i64() {
+        (v, true) => Ok(serde_json::Value::from(v)),
+        (_, false) => Err(Error::from_reason(
+          "BigInt in a JSON prop doesn't fit in i64",
+        )),
+      }
+    }
+    ValueType::Object => {
+      let obj = value.coerce_to_object()?;
+      if obj.is_array()? {
+        let len = obj.get_array_length()?;
+        let mut items = Vec::with_capacity(len as usize);
+        for index in 0..len {
+          let item: Unknown = obj.get_element(index)?;
+          items.push(js_value_to_json_with(env, item, object_prototype)?);
+        }
+        return Ok(serde_json::Value::Array(items));
+      }
+
+      let prototype = obj.get_prototype()?;
+      let plain = prototype.get_type()? == ValueType::Null
+        || env.strict_equals(prototype, *object_prototype)?;
+      if !plain {
+        return Err(Error::from_reason(
+          "JSON props only hold plain objects, arrays and primitives",
+        ));
+      }
+      let mut map = serde_json::Map::new();
+      for key in Object::keys(&obj)? {
+        let item: Unknown = obj.get_named_property(&key)?;
+        if item.get_type()? != ValueType::Undefined {
+          map.insert(key, js_value_to_json_with(env, item, object_prototype)?);
+        }
+      }
+      Ok(serde_json::Value::Object(map))
+    }
+    _ => Err(Error::from_reason(
+      "JSON props only hold plain objects, arrays and primitives",
+    )),
+  }
+}
+
+/// Convert a JS property filter to a PropFilter
+///
+/// Accepts `{ eq }`, `{ min, max }` or `{ jsonPath, eq }`.
+pub(crate) fn js_prop_filter(env: &Env, filter: &Object) -> Result<PropFilter> {
+  let eq: Option<Unknown> = filter.get_named_property("eq")?;
+  let json_path: Option<String> = filter.get_named_property("jsonPath")?;
+  let min: Option<Unknown> = filter.get_named_property("min")?;
+  let max: Option<Unknown> = filter.get_named_property("max")?;
+  match (json_path, eq, min, max) {
+    (Some(path), Some(value), None, None) => Ok(PropFilter::JsonPathEq {
+      path,
+      value: js_value_to_json(env, value)?,
+    }),
+    (None, Some(value), None, None) => Ok(PropFilter::Eq(js_value_to_prop_value(env, value)?)),
+    (None, None, Some(min), Some(max)) => Ok(PropFilter::Range {
+      min: js_value_to_prop_value(env, min)?,
+      max: js_value_to_prop_value(env, max)?,
+    }),
+    _ => Err(Error::from_reason(
+      "Property filter must be { eq }, { min, max } or { jsonPath, eq }",
+    )),
+  }
+}
+
+/// Props-object entry mapping prop names to units, on reads and writes
+pub(crate) const UNITS_KEY: &str = "$units";
+
 /// Convert a JS Object of properties to a HashMap
+///
+/// A `$units` entry is unit metadata, not a prop, and is left out.
 pub(crate) fn js_props_to_map(
   env: &Env,
   props: Option<Object>,
@@ -119,6 +256,9 @@ pub(crate) fn js_props_to_map(
   };
 
   for name in Object::keys(&props)? {
+    if name == UNITS_KEY {
+      continue;
+    }
     let value: Unknown = props.get_named_property(&name)?;
     result.insert(name, js_value_to_prop_value(env, value)?);
   }
@@ -126,6 +266,23 @@ pub(crate) fn js_props_to_map(
   Ok(result)
 }
 
+/// Units a writer declared under `$units` in a props object (empty if none)
+pub(crate) fn js_declared_units(props: Option<&Object>) -> Result<HashMap<String, String>> {
+  let Some(props) = props else {
+    return Ok(HashMap::new());
+  };
+  if !props.has_named_property(UNITS_KEY)? {
+    return Ok(HashMap::new());
+  }
+  let units: Object = props.get_named_property(UNITS_KEY)?;
+  let mut declared = HashMap::new();
+  for name in Object::keys(&units)? {
+    let unit: String = units.get_named_property(&name)?;
+    declared.insert(name, unit);
+  }
+  Ok(declared)
+}
+
 /// Convert a JS value to a string (for key fields)
 pub(crate) fn js_value_to_string(_env: &Env, value: Unknown, field: &str) -> Result<String> {
   match value.get_type()? {
@@ -182,7 +339,7 @@ pub(crate) fn key_suffix_from_js(env: &Env, spec: &KeySpec, value: Unknown) -> R
         Ok(stripped.to_string())
       } else {
         match spec {
-          KeySpec::Prefix { .. } => Ok(raw),
+          KeySpec::Prefix { .. } | KeySpec::Autoincrement { .. } => Ok(raw),
           _ => Err(Error::from_reason(
             "Key spec requires object or full key string",
           )),
@@ -193,7 +350,7 @@ pub(crate) fn key_suffix_from_js(env: &Env, spec: &KeySpec, value: Unknown) -> R
       let obj = value.coerce_to_object()?;
 
       match spec {
-        KeySpec::Prefix { .. } => {
+        KeySpec::Prefix { .. } | KeySpec::Autoincrement { .. } => {
           if obj.has_named_property("id")? {
             let val: Unknown = obj.get_named_property("id")?;
             return js_value_to_string(env, val, "id");
@@ -228,6 +385,56 @@ pub(crate) fn key_suffix_from_js(env: &Env, spec: &KeySpec, value: Unknown) -> R
         }
       }
     }
+    ValueType::Number | ValueType::BigInt if spec.is_autoincrement() => {
+      js_value_to_string(env, value, "id")
+    }
     _ => Err(Error::from_reason("Invalid key value")),
   }
 }
+
+/// Convert a JS key value for an insert
+///
+/// With an `autoincrement` key spec, a missing (null/undefined) key yields
+/// `None` so the suffix can be allocated when the insert runs.
+pub(crate) fn insert_key_from_js(
+  env: &Env,
+  spec: &KeySpec,
+  value: Unknown,
+) -> Result<Option<String>> {
+  if spec.is_autoincrement() && matches!(value.get_type()?, ValueType::Null | ValueType::Undefined)
+  {
+    return Ok(None);
+  }
+  key_suffix_from_js(env, spec, value).map(Some)
+}
+
+/// Convert a JS key value for a node that may already exist
+///
+/// With a `parts` key spec, an object naming only some of the fields is
+/// returned as a patch to merge with the node's current key; anything else
+/// resolves to a full key suffix as in `key_suffix_from_js`.
+pub(crate) fn key_input_from_js(env: &Env, spec: &KeySpec, value: Unknown) -> Result<KeyInput> {
+  if let (KeySpec::Parts { fields, .. }, ValueType::Object) = (spec, value.get_type()?) {
+    let obj = value.coerce_to_object()?;
+    let names = Object::keys(&obj)?;
+    if fields.iter().any(|field| !names.contains(field)) {
+      let mut patch = HashMap::with_capacity(names.len());
+      for name in names {
+        let val: Unknown = obj.get_named_property(&name)?;
+        let part = js_value_to_string(env, val, &name)?;
+        patch.insert(name, part);
+      }
+      return Ok(KeyInput::Patch(patch));
+    }
+  }
+  key_suffix_from_js(env, spec, value).map(KeyInput::Suffix)
+}
+
+/// Key supplied to an upsert-by-id builder
+#[derive(Clone, Debug)]
+pub(crate) enum KeyInput {
+  /// Full key suffix (without prefix)
+  Suffix(String),
+  /// Partial `parts` components to merge with the current key
+  Patch(HashMap<String, String>),
+}
@@ -0,0 +1,96 @@
+//! Incremental exploration sessions
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+use crate::api::kite::Kite as RustKite;
+use crate::api::traversal::{Exploration, TraversalDirection};
+use crate::types::NodeId;
+
+use crate::napi_bindings::traversal::JsTraversalDirection;
+
+// =============================================================================
+// Exploration Session
+// =============================================================================
+
+/// Server-side exploration session that remembers returned nodes
+///
+/// Each `expand` call returns only neighbors the session hasn't returned
+/// before. Call `dispose` when done to release the visited set.
+#[napi]
+pub struct KiteExploration {
+  ray: Arc<RwLock<Option<RustKite>>>,
+  session: Option<Exploration>,
+}
+
+impl KiteExploration {
+  pub fn new(ray: Arc<RwLock<Option<RustKite>>>, session: Exploration) -> Self {
+    Self {
+      ray,
+      session: Some(session),
+    }
+  }
+
+  fn session(&self) -> Result<&Exploration> {
+    self
+      .session
+      .as_ref()
+      .ok_or_else(|| Error::from_reason("Exploration is disposed"))
+  }
+}
+
+#[napi]
+impl KiteExploration {
+  /// Node the session started from
+  #[napi]
+  pub fn root(&self) -> Result<i64> {
+    Ok(self.session()?.root() as i64)
+  }
+
+  /// Expand a node, returning IDs of neighbors not returned before
+  ///
+  /// Direction defaults to outgoing edges.
+  #[napi]
+  pub fn expand(
+    &mut self,
+    node_id: i64,
+    edge_type: Option<String>,
+    direction: Option<JsTraversalDirection>,
+  ) -> Result<Vec<i64>> {
+    let session = self
+      .session
+      .as_mut()
+      .ok_or_else(|| Error::from_reason("Exploration is disposed"))?;
+    let guard = self.ray.read();
+    let ray = guard
+      .as_ref()
+      .ok_or_else(|| Error::from_reason("Kite is closed"))?;
+    let direction = direction
+      .map(TraversalDirection::from)
+      .unwrap_or(TraversalDirection::Out);
+    let nodes = ray
+      .expand(session, node_id as NodeId, edge_type.as_deref(), direction)
+      .map_err(|e| Error::from_reason(e.to_string()))?;
+    Ok(nodes.into_iter().map(|id| id as i64).collect())
+  }
+
+  /// Whether the session has already returned a node
+  #[napi]
+  pub fn is_visited(&self, node_id: i64) -> Result<bool> {
+    Ok(self.session()?.is_visited(node_id as NodeId))
+  }
+
+  /// Number of nodes returned so far, root included
+  #[napi]
+  pub fn visited_count(&self) -> Result<i64> {
+    Ok(self.session()?.visited_count() as i64)
+  }
+
+  /// Release the visited set; further calls fail
+  #[napi]
+  pub fn dispose(&mut self) {
+    self.session = None;
+  }
+}
@@ -8,17 +8,86 @@ use napi::UnknownRef;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use crate::api::kite::{BatchOp, BatchResult, Kite as RustKite};
+use crate::api::kite::{
+  BatchResult, Kite as RustKite, PropType as KitePropType, SchemaChange, SchemaKind,
+  ARCHIVED_AT_PROP,
+};
 use crate::api::traversal::TraversalDirection;
-use crate::core::single_file::SingleFileDB;
+use crate::core::single_file::{PropChange, SingleFileDB};
 use crate::types::{ETypeId, Edge, NodeId, PropValue};
 
-use super::key_spec::KeySpec;
+use super::conversion::UNITS_KEY;
+use super::types::{JsGetOptions, JsSchemaChange};
 
 // =============================================================================
 // JS Value Output Conversion
 // =============================================================================
 
+/// Name a prop type the way JsPropSpec spells it
+fn prop_type_name(prop_type: KitePropType) -> String {
+  match prop_type {
+    KitePropType::String => "string",
+    KitePropType::Int => "int",
+    KitePropType::Float => "float",
+    KitePropType::Bool => "bool",
+    KitePropType::Enum => "enum",
+    KitePropType::Json => "json",
+    KitePropType::Vector => "vector",
+    KitePropType::Any => "any",
+  }
+  .to_string()
+}
+
+/// Convert a SchemaChange to its JS object form
+pub(crate) fn schema_change_to_js(change: SchemaChange) -> JsSchemaChange {
+  let kind_name = |kind: SchemaKind| match kind {
+    SchemaKind::Node => "node".to_string(),
+    SchemaKind::Edge => "edge".to_string(),
+  };
+  let (op, kind, name, prop, prop_type, previous_type) = match change {
+    SchemaChange::AddType { kind, name } => ("addType", kind, name, None, None, None),
+    SchemaChange::RemoveType { kind, name } => ("removeType", kind, name, None, None, None),
+    SchemaChange::AddProp {
+      kind,
+      owner,
+      prop,
+      prop_type,
+    } => (
+      "addProp",
+      kind,
+      owner,
+      Some(prop),
+      Some(prop_type_name(prop_type)),
+      None,
+    ),
+    SchemaChange::RemoveProp { kind, owner, prop } => {
+      ("removeProp", kind, owner, Some(prop), None, None)
+    }
+    SchemaChange::ChangePropType {
+      kind,
+      owner,
+      prop,
+      from,
+      to,
+    } => (
+      "changePropType",
+      kind,
+      owner,
+      Some(prop),
+      Some(prop_type_name(to)),
+      Some(prop_type_name(from)),
+    ),
+  };
+  JsSchemaChange {
+    op: op.to_string(),
+    kind: kind_name(kind),
+    name,
+    prop,
+    prop_type,
+    previous_type,
+  }
+}
+
 /// Convert a PropValue to a JS Unknown value
 pub(crate) fn prop_value_to_js(env: &Env, value: PropValue) -> Result<Unknown<'_>> {
   match value {
@@ -31,16 +100,22 @@ pub(crate) fn prop_value_to_js(env: &Env, value: PropValue) -> Result<Unknown<'_
       let values: Vec<f64> = v.iter().map(|&value| value as f64).collect();
       values.into_unknown(env)
     }
+    PropValue::Json(v) => env.to_js_value(&v),
   }
 }
 
 /// Convert a BatchResult to a JS Object
+///
+/// Every result carries `type` and `affected` (0 for a no-op, else 1),
+/// plus the flag or node for its kind.
 pub(crate) fn batch_result_to_js(env: &Env, result: BatchResult) -> Result<Object<'static>> {
   let mut obj = Object::new(env)?;
+  obj.set_named_property("affected", result.affected() as u32)?;
   match result {
     BatchResult::NodeCreated(node_ref) => {
       obj.set_named_property("type", "nodeCreated")?;
       let (node_id, node_key, node_type) = node_ref.into_parts();
+      obj.set_named_property("id", node_id as i64)?;
       let node_obj = node_to_js(env, node_id, node_key, &node_type, HashMap::new())?;
       obj.set_named_property("node", node_obj)?;
     }
@@ -48,23 +123,49 @@ pub(crate) fn batch_result_to_js(env: &Env, result: BatchResult) -> Result<Objec
       obj.set_named_property("type", "nodeDeleted")?;
       obj.set_named_property("deleted", deleted)?;
     }
-    BatchResult::EdgeCreated => {
+    BatchResult::EdgeCreated(created) => {
       obj.set_named_property("type", "edgeCreated")?;
+      obj.set_named_property("created", created)?;
     }
     BatchResult::EdgeRemoved(deleted) => {
       obj.set_named_property("type", "edgeRemoved")?;
       obj.set_named_property("deleted", deleted)?;
     }
-    BatchResult::PropSet => {
+    BatchResult::PropSet(changed) => {
       obj.set_named_property("type", "propSet")?;
+      obj.set_named_property("changed", changed)?;
     }
-    BatchResult::PropDeleted => {
+    BatchResult::PropDeleted(deleted) => {
       obj.set_named_property("type", "propDeleted")?;
+      obj.set_named_property("deleted", deleted)?;
     }
   }
   Ok(Object::from_raw(env.raw(), obj.raw()))
 }
 
+/// Convert a best-effort batch op outcome to a JS object
+///
+/// Successful ops carry the usual result fields plus `ok: true`; failed ops
+/// are `{ ok: false, error }`.
+pub(crate) fn batch_outcome_to_js(
+  env: &Env,
+  outcome: std::result::Result<BatchResult, String>,
+) -> Result<Object<'static>> {
+  match outcome {
+    Ok(result) => {
+      let mut obj = batch_result_to_js(env, result)?;
+      obj.set_named_property("ok", true)?;
+      Ok(obj)
+    }
+    Err(error) => {
+      let mut obj = Object::new(env)?;
+      obj.set_named_property("ok", false)?;
+      obj.set_named_property("error", error)?;
+      Ok(Object::from_raw(env.raw(), obj.raw()))
+    }
+  }
+}
+
 /// Create a JS node object with properties
 pub(crate) fn node_to_js(
   env: &Env,
@@ -86,6 +187,38 @@ pub(crate) fn node_to_js(
   Ok(Object::from_raw(env.raw(), obj.raw()))
 }
 
+/// Attach `$units` (prop name -> declared unit) for the node's type
+pub(crate) fn attach_units(ray: &RustKite, obj: &mut Object, node_type: &str) -> Result<()> {
+  let units = ray
+    .prop_units(node_type)
+    .map_err(|e| Error::from_reason(e.to_string()))?;
+  obj.set_named_property(UNITS_KEY, units)
+}
+
+/// Attach `$out` / `$in` (edge type -> neighbor ids) for each of `edge_types`
+pub(crate) fn attach_edges(
+  ray: &RustKite,
+  obj: &mut Object,
+  node_id: NodeId,
+  edge_types: &[String],
+) -> Result<()> {
+  let mut out_edges = HashMap::with_capacity(edge_types.len());
+  let mut in_edges = HashMap::with_capacity(edge_types.len());
+  for edge_type in edge_types {
+    let to_ids = |ids: Vec<NodeId>| ids.into_iter().map(|id| id as i64).collect::<Vec<_>>();
+    let out_ids = ray
+      .neighbors_out(node_id, Some(edge_type))
+      .map_err(|e| Error::from_reason(e.to_string()))?;
+    let in_ids = ray
+      .neighbors_in(node_id, Some(edge_type))
+      .map_err(|e| Error::from_reason(e.to_string()))?;
+    out_edges.insert(edge_type.clone(), to_ids(out_ids));
+    in_edges.insert(edge_type.clone(), to_ids(in_ids));
+  }
+  obj.set_named_property("$out", out_edges)?;
+  obj.set_named_property("$in", in_edges)
+}
+
 // =============================================================================
 // Filter Data Structures
 // =============================================================================
@@ -171,6 +304,18 @@ pub(crate) fn node_filter_arg(env: &Env, data: &NodeFilterData) -> Result<Object
   Ok(Object::from_raw(env.raw(), obj.raw()))
 }
 
+/// Create the `{ nodeId, oldValue, newValue }` object passed to prop change
+/// listeners, with `null` for a missing value
+pub(crate) fn prop_change_arg(env: &Env, change: &PropChange) -> Result<Object<'static>> {
+  let mut obj = Object::new(env)?;
+  obj.set_named_property("nodeId", change.node_id as i64)?;
+  let old_value = change.old_value.clone().unwrap_or(PropValue::Null);
+  obj.set_named_property("oldValue", prop_value_to_js(env, old_value)?)?;
+  let new_value = change.new_value.clone().unwrap_or(PropValue::Null);
+  obj.set_named_property("newValue", prop_value_to_js(env, new_value)?)?;
+  Ok(Object::from_raw(env.raw(), obj.raw()))
+}
+
 /// Create a JS object for edge filtering
 pub(crate) fn edge_filter_arg(env: &Env, data: &EdgeFilterData) -> Result<Object<'static>> {
   let mut obj = Object::new(env)?;
@@ -198,6 +343,37 @@ pub(crate) fn call_filter(
   result.coerce_to_bool()
 }
 
+// =============================================================================
+// Virtual Props
+// =============================================================================
+
+/// Computed props registered per node type, as (prop name, JS function)
+pub(crate) type VirtualProps = HashMap<String, Vec<(String, Arc<UnknownRef<false>>)>>;
+
+/// Merge computed props into a node object
+///
+/// Each function is called with the node's stored props and its return
+/// value is set on `obj` under the virtual prop's name.
+pub(crate) fn apply_virtual_props(
+  env: &Env,
+  obj: &mut Object,
+  stored: &HashMap<String, PropValue>,
+  virtual_props: &[(String, Arc<UnknownRef<false>>)],
+) -> Result<()> {
+  for (name, func_ref) in virtual_props {
+    let mut arg = Object::new(env)?;
+    for (prop_name, value) in stored {
+      arg.set_named_property(prop_name, prop_value_to_js(env, value.clone())?)?;
+    }
+    let func_value = func_ref.get_value(env)?;
+    // SAFETY: define_virtual_prop only registers JS functions.
+    let func: Function<Unknown, Unknown> = unsafe { func_value.cast()? };
+    let value: Unknown = func.call(arg.into_unknown(env)?)?;
+    obj.set_named_property(name, value)?;
+  }
+  Ok(())
+}
+
 // =============================================================================
 // Property Selection Helpers
 // =============================================================================
@@ -207,6 +383,18 @@ pub(crate) fn should_include_prop(selected_props: Option<&HashSet<String>>, name
   selected_props.is_none_or(|set| set.contains(name))
 }
 
+/// Props to materialize for a read: none for a `lazy` read, otherwise the
+/// caller's selection
+pub(crate) fn lazy_or_selected(
+  props: Option<Vec<String>>,
+  options: Option<&JsGetOptions>,
+) -> Option<HashSet<String>> {
+  if options.and_then(|options| options.lazy).unwrap_or(false) {
+    return Some(HashSet::new());
+  }
+  props.map(|props| props.into_iter().collect())
+}
+
 /// Get node properties with optional selection
 pub(crate) fn node_props_selected(
   ray: &RustKite,
@@ -214,10 +402,13 @@ pub(crate) fn node_props_selected(
   selected_props: Option<&HashSet<String>>,
 ) -> HashMap<String, PropValue> {
   let mut props = HashMap::new();
+  if selected_props.is_some_and(HashSet::is_empty) {
+    return props;
+  }
   if let Some(props_by_id) = ray.raw().node_props(node_id) {
     for (key_id, value) in props_by_id {
       if let Some(name) = ray.raw().propkey_name(key_id) {
-        if should_include_prop(selected_props, &name) {
+        if name != ARCHIVED_AT_PROP && should_include_prop(selected_props, &name) {
           props.insert(name, value);
         }
       }
@@ -231,30 +422,62 @@ pub(crate) fn node_props(ray: &RustKite, node_id: NodeId) -> HashMap<String, Pro
   node_props_selected(ray, node_id, None)
 }
 
-// =============================================================================
-// Node Type Inference
-// =============================================================================
-
-/// Infer node type from key prefix
-pub(crate) fn node_type_from_key(
-  node_specs: &HashMap<String, Arc<KeySpec>>,
-  key: &str,
-) -> Option<String> {
-  node_specs
-    .iter()
-    .find(|(_, spec)| key.starts_with(spec.prefix()))
-    .map(|(name, _)| name.clone())
+/// Fields every node object carries, or that read options attach, which a
+/// rename can't target
+const NODE_SYSTEM_FIELDS: [&str; 6] = ["id", "key", "type", UNITS_KEY, "$out", "$in"];
+
+/// A read's `rename` map and `renameOnly` flag, rejecting renames onto
+/// [`NODE_SYSTEM_FIELDS`]
+pub(crate) fn rename_option(
+  rename: Option<&HashMap<String, String>>,
+  rename_only: Option<bool>,
+) -> Result<(Option<&HashMap<String, String>>, bool)> {
+  if let Some((name, external)) = rename
+    .into_iter()
+    .flatten()
+    .find(|(_, external)| NODE_SYSTEM_FIELDS.contains(&external.as_str()))
+  {
+    return Err(Error::from_reason(format!(
+      "Cannot rename prop {name} to {external}: {external} is a reserved node field"
+    )));
+  }
+  Ok((rename, rename_only.unwrap_or(false)))
 }
 
-// =============================================================================
-// Batch Operations
-// =============================================================================
+/// [`rename_option`] for `get`/`getById`, which can't rename a `lazy` read:
+/// its props load later, by their stored names
+pub(crate) fn get_rename_option(
+  options: Option<&JsGetOptions>,
+) -> Result<(Option<&HashMap<String, String>>, bool)> {
+  let Some(options) = options else {
+    return Ok((None, false));
+  };
+  if options.lazy.unwrap_or(false) && (options.rename.is_some() || options.rename_only.is_some()) {
+    return Err(Error::from_reason(
+      "rename and renameOnly can't be combined with lazy",
+    ));
+  }
+  rename_option(options.rename.as_ref(), options.rename_only)
+}
 
-/// Execute a batch of operations
-pub(crate) fn execute_batch_ops(ray: &mut RustKite, ops: Vec<BatchOp>) -> Result<Vec<BatchResult>> {
-  ray
-    .batch(ops)
-    .map_err(|e| Error::from_reason(e.to_string()))
+/// Rename props by an `{ internal: external }` map; with `only`, props not
+/// in the map are dropped, otherwise they pass through unchanged
+pub(crate) fn rename_props(
+  props: HashMap<String, PropValue>,
+  rename: Option<&HashMap<String, String>>,
+  only: bool,
+) -> HashMap<String, PropValue> {
+  let Some(rename) = rename else {
+    return props;
+  };
+  props
+    .into_iter()
+    .filter_map(|(name, value)| match rename.get(&name) {
+      Some(external) => Some((external.clone(), value)),
+      None if only => None,
+      None => Some((name, value)),
+    })
+    .collect()
 }
 
 // =============================================================================
@@ -4,9 +4,11 @@
 //! and handling key generation strategies.
 
 use napi::bindgen_prelude::*;
+use std::collections::HashMap;
+use std::sync::Arc;
 
-use super::types::{JsKeySpec, JsPropSpec};
-use crate::api::kite::{PropDef, PropType as KitePropType};
+use super::types::{JsEdgeSpec, JsKeySpec, JsNodeSpec, JsPropSpec};
+use crate::api::kite::{EdgeDef, NodeDef, PropDef, PropType as KitePropType};
 
 // =============================================================================
 // Key Specs
@@ -25,6 +27,8 @@ pub(crate) enum KeySpec {
     fields: Vec<String>,
     separator: String,
   },
+  /// "prefix" + a number allocated by the database when no key is given
+  Autoincrement { prefix: String },
 }
 
 impl KeySpec {
@@ -34,8 +38,63 @@ impl KeySpec {
       KeySpec::Prefix { prefix } => prefix,
       KeySpec::Template { prefix, .. } => prefix,
       KeySpec::Parts { prefix, .. } => prefix,
+      KeySpec::Autoincrement { prefix } => prefix,
     }
   }
+
+  /// Whether the database allocates key suffixes for this node type
+  pub(crate) fn is_autoincrement(&self) -> bool {
+    matches!(self, KeySpec::Autoincrement { .. })
+  }
+
+  /// Merge a partial set of `parts` components into a node's current key
+  ///
+  /// Components missing from `patch` are taken from `current_key`. Returns
+  /// the recomputed key suffix (without prefix).
+  pub(crate) fn merge_parts(
+    &self,
+    current_key: Option<&str>,
+    patch: &HashMap<String, String>,
+  ) -> Result<String> {
+    let KeySpec::Parts {
+      prefix,
+      fields,
+      separator,
+    } = self
+    else {
+      return Err(Error::from_reason(
+        "Partial key objects require a parts key spec",
+      ));
+    };
+
+    if let Some(unknown) = patch.keys().find(|name| !fields.contains(name)) {
+      return Err(Error::from_reason(format!("Unknown key field: {unknown}")));
+    }
+
+    // The last component absorbs any extra separators, mirroring how the
+    // key was joined.
+    let current: Vec<&str> = match current_key.and_then(|key| key.strip_prefix(prefix.as_str())) {
+      Some(suffix) => suffix.splitn(fields.len(), separator.as_str()).collect(),
+      None => Vec::new(),
+    };
+    if !current.is_empty() && current.len() != fields.len() {
+      return Err(Error::from_reason(
+        "Existing key does not match the parts key spec",
+      ));
+    }
+
+    let mut parts = Vec::with_capacity(fields.len());
+    for (idx, field) in fields.iter().enumerate() {
+      match patch.get(field) {
+        Some(value) => parts.push(value.as_str()),
+        None => match current.get(idx) {
+          Some(value) => parts.push(*value),
+          None => return Err(Error::from_reason(format!("Missing key field: {field}"))),
+        },
+      }
+    }
+    Ok(parts.join(separator))
+  }
 }
 
 /// Parse a JsKeySpec into the internal KeySpec representation
@@ -58,6 +117,11 @@ pub(crate) fn parse_key_spec(node_name: &str, spec: Option<JsKeySpec>) -> Result
       let template = spec
         .template
         .ok_or_else(|| Error::from_reason("template key spec requires template"))?;
+      if !template.contains('{') {
+        return Err(Error::from_reason(format!(
+          "template key spec for '{node_name}' has no {{field}} placeholders: {template}"
+        )));
+      }
       let prefix = spec
         .prefix
         .unwrap_or_else(|| infer_prefix_from_template(&template));
@@ -78,6 +142,9 @@ pub(crate) fn parse_key_spec(node_name: &str, spec: Option<JsKeySpec>) -> Result
         separator: spec.separator.unwrap_or_else(|| ":".to_string()),
       })
     }
+    "autoincrement" => Ok(KeySpec::Autoincrement {
+      prefix: spec.prefix.unwrap_or_else(|| format!("{node_name}:")),
+    }),
     _ => Err(Error::from_reason(format!("unknown key spec kind: {kind}"))),
   }
 }
@@ -90,6 +157,35 @@ pub(crate) fn infer_prefix_from_template(template: &str) -> String {
   }
 }
 
+/// Convert a JsNodeSpec to a NodeDef, along with its parsed key spec
+pub(crate) fn node_spec_to_def(node: &JsNodeSpec) -> Result<(NodeDef, Arc<KeySpec>)> {
+  let key_spec = Arc::new(parse_key_spec(&node.name, node.key.clone())?);
+  let mut node_def = NodeDef::new(&node.name, key_spec.prefix());
+  if key_spec.is_autoincrement() {
+    node_def = node_def.autoincrement();
+  }
+  if let Some(props) = node.props.as_ref() {
+    for (prop_name, prop_spec) in props {
+      node_def = node_def.prop(prop_spec_to_def(prop_name, prop_spec)?);
+    }
+  }
+  Ok((node_def, key_spec))
+}
+
+/// Convert a JsEdgeSpec to an EdgeDef
+pub(crate) fn edge_spec_to_def(edge: &JsEdgeSpec) -> Result<EdgeDef> {
+  let mut edge_def = EdgeDef::new(&edge.name);
+  if let Some(props) = edge.props.as_ref() {
+    for (prop_name, prop_spec) in props {
+      edge_def = edge_def.prop(prop_spec_to_def(prop_name, prop_spec)?);
+    }
+  }
+  if edge.no_self_loops.unwrap_or(false) {
+    edge_def = edge_def.no_self_loops();
+  }
+  Ok(edge_def)
+}
+
 /// Convert a JsPropSpec to a PropDef for schema configuration
 pub(crate) fn prop_spec_to_def(name: &str, spec: &JsPropSpec) -> Result<PropDef> {
   let mut prop = match spec.r#type.as_str() {
@@ -97,17 +193,26 @@ pub(crate) fn prop_spec_to_def(name: &str, spec: &JsPropSpec) -> Result<PropDef>
     "int" => PropDef::int(name),
     "float" => PropDef::float(name),
     "bool" => PropDef::bool(name),
-    "vector" => PropDef {
-      name: name.to_string(),
-      prop_type: KitePropType::Any,
-      required: false,
-      default: None,
-    },
+    "json" => PropDef::json(name),
+    "vector" => PropDef::vector(name),
     "any" => PropDef {
       name: name.to_string(),
       prop_type: KitePropType::Any,
       required: false,
       default: None,
+      allowed: None,
+      unique: false,
+      ordered: false,
+      unit: None,
+      normalize: false,
+    },
+    "enum" => match spec.allowed.as_ref() {
+      Some(allowed) if !allowed.is_empty() => PropDef::string_enum(name, allowed.iter().cloned()),
+      _ => {
+        return Err(Error::from_reason(format!(
+          "enum prop '{name}' requires a non-empty allowed list"
+        )))
+      }
     },
     other => return Err(Error::from_reason(format!("unknown prop type: {other}"))),
   };
@@ -117,7 +222,28 @@ pub(crate) fn prop_spec_to_def(name: &str, spec: &JsPropSpec) -> Result<PropDef>
     prop = prop.required();
   }
 
+  if spec.unique.unwrap_or(false) {
+    prop = prop.unique();
+  }
+
+  if spec.ordered.unwrap_or(false) {
+    prop = prop.ordered();
+  }
+
+  if let Some(unit) = spec.unit.as_deref() {
+    prop = prop.unit(unit);
+  }
+
+  if spec.normalize.unwrap_or(false) {
+    prop = prop.normalized();
+  }
+
   if let Some(default_value) = spec.r#default.clone() {
+    if spec.r#type == "vector" {
+      return Err(Error::from_reason(format!(
+        "vector prop '{name}' can't have a default"
+      )));
+    }
     prop = prop.default(default_value.into());
   }
 
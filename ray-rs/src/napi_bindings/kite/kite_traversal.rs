@@ -9,7 +9,7 @@ use parking_lot::RwLock;
 use std::collections::HashSet;
 use std::sync::Arc;
 
-use crate::api::kite::Kite as RustKite;
+use crate::api::kite::{retain_node_types, retain_unarchived, retain_valid_at, Kite as RustKite};
 use crate::api::traversal::{TraversalBuilder, TraversalDirection, TraversalStep, TraverseOptions};
 use crate::types::{ETypeId, Edge, NodeId};
 
@@ -33,6 +33,9 @@ pub struct KiteTraversal {
   pub(crate) selected_props: Option<Vec<String>>,
   pub(crate) where_edge: Option<Arc<UnknownRef<false>>>,
   pub(crate) where_node: Option<Arc<UnknownRef<false>>>,
+  pub(crate) node_prefixes: Option<Vec<String>>,
+  pub(crate) include_archived: bool,
+  pub(crate) as_of: Option<i64>,
 }
 
 #[derive(Clone, Default)]
@@ -79,6 +82,9 @@ impl KiteTraversal {
       selected_props: self.selected_props.clone(),
       where_edge: self.where_edge.clone(),
       where_node: self.where_node.clone(),
+      node_prefixes: self.node_prefixes.clone(),
+      include_archived: self.include_archived,
+      as_of: self.as_of,
     }
   }
 
@@ -97,6 +103,29 @@ impl KiteTraversal {
   }
 }
 
+/// Expand one hop, keeping only neighbors whose type is allowed
+fn hop(
+  ray: &RustKite,
+  node_prefixes: Option<&[String]>,
+  include_archived: bool,
+  as_of: Option<i64>,
+  node_id: NodeId,
+  direction: TraversalDirection,
+  etype: Option<ETypeId>,
+) -> Vec<Edge> {
+  let mut edges = neighbors(ray.raw(), node_id, direction, etype);
+  if let Some(prefixes) = node_prefixes {
+    retain_node_types(ray.raw(), node_id, &mut edges, prefixes);
+  }
+  if !include_archived {
+    retain_unarchived(ray.raw(), node_id, &mut edges);
+  }
+  if let Some(as_of) = as_of {
+    retain_valid_at(ray.raw(), &mut edges, as_of);
+  }
+  edges
+}
+
 #[napi]
 impl KiteTraversal {
   #[napi(js_name = "whereEdge")]
@@ -182,6 +211,8 @@ impl KiteTraversal {
       unique: options.unique.unwrap_or(true),
       where_edge: None,
       where_node: None,
+      max_neighbors_per_node: options.max_neighbors_per_node.map(|max| max as usize),
+      sample_seed: options.sample_seed.unwrap_or(0) as u64,
     };
     next.steps = next.steps.push(TraversalStep::Traverse {
       etype,
@@ -190,6 +221,41 @@ impl KiteTraversal {
     Ok(next)
   }
 
+  #[napi(js_name = "allowedNodeTypes")]
+  pub fn allowed_node_types(&self, node_types: Vec<String>) -> Result<KiteTraversal> {
+    let prefixes = {
+      let guard = self.ray.read();
+      let ray = guard
+        .as_ref()
+        .ok_or_else(|| Error::from_reason("Kite is closed"))?;
+      ray
+        .node_type_prefixes(&node_types)
+        .map_err(|e| Error::from_reason(e.to_string()))?
+    };
+    let mut next = self.fork();
+    next.node_prefixes = Some(prefixes);
+    Ok(next)
+  }
+
+  /// Let hops land on archived nodes (skipped by default)
+  #[napi(js_name = "includeArchived")]
+  pub fn include_archived(&self) -> Result<KiteTraversal> {
+    let mut next = self.fork();
+    next.include_archived = true;
+    Ok(next)
+  }
+
+  /// Only follow edges valid at `timestamp` (ms since the Unix epoch)
+  ///
+  /// Edges linked with `validFrom`/`validTo` are followed only when
+  /// `validFrom <= timestamp < validTo`; edges without a window always are.
+  #[napi(js_name = "asOf")]
+  pub fn as_of(&self, timestamp: i64) -> Result<KiteTraversal> {
+    let mut next = self.fork();
+    next.as_of = Some(timestamp);
+    Ok(next)
+  }
+
   #[napi]
   pub fn take(&self, limit: i64) -> Result<KiteTraversal> {
     let mut next = self.fork();
@@ -211,6 +277,9 @@ impl KiteTraversal {
       .selected_properties()
       .map(|props| props.iter().cloned().collect::<HashSet<String>>());
 
+    let prefixes = self.node_prefixes.as_deref();
+    let include_archived = self.include_archived;
+    let as_of = self.as_of;
     let items = {
       let ray = self.ray.clone();
       let guard = ray.read();
@@ -219,7 +288,9 @@ impl KiteTraversal {
         .ok_or_else(|| Error::from_reason("Kite is closed"))?;
 
       let results: Vec<_> = builder
-        .execute(|node_id, dir, etype| neighbors(ray.raw(), node_id, dir, etype))
+        .execute(|node_id, dir, etype| {
+          hop(ray, prefixes, include_archived, as_of, node_id, dir, etype)
+        })
         .collect();
 
       let mut items = Vec::with_capacity(results.len());
@@ -272,6 +343,9 @@ impl KiteTraversal {
       .selected_properties()
       .map(|props| props.iter().cloned().collect::<HashSet<String>>());
 
+    let prefixes = self.node_prefixes.as_deref();
+    let include_archived = self.include_archived;
+    let as_of = self.as_of;
     let items = {
       let ray = self.ray.clone();
       let guard = ray.read();
@@ -280,7 +354,9 @@ impl KiteTraversal {
         .ok_or_else(|| Error::from_reason("Kite is closed"))?;
 
       let results: Vec<_> = builder
-        .execute(|node_id, dir, etype| neighbors(ray.raw(), node_id, dir, etype))
+        .execute(|node_id, dir, etype| {
+          hop(ray, prefixes, include_archived, as_of, node_id, dir, etype)
+        })
         .collect();
 
       let mut items = Vec::with_capacity(results.len());
@@ -340,6 +416,9 @@ impl KiteTraversal {
       .selected_properties()
       .map(|props| props.iter().cloned().collect::<HashSet<String>>());
 
+    let prefixes = self.node_prefixes.as_deref();
+    let include_archived = self.include_archived;
+    let as_of = self.as_of;
     let items = {
       let ray = self.ray.clone();
       let guard = ray.read();
@@ -348,7 +427,9 @@ impl KiteTraversal {
         .ok_or_else(|| Error::from_reason("Kite is closed"))?;
 
       let results: Vec<_> = builder
-        .execute(|node_id, dir, etype| neighbors(ray.raw(), node_id, dir, etype))
+        .execute(|node_id, dir, etype| {
+          hop(ray, prefixes, include_archived, as_of, node_id, dir, etype)
+        })
         .collect();
 
       let mut items = Vec::with_capacity(results.len());
@@ -407,6 +488,9 @@ impl KiteTraversal {
       .selected_properties()
       .map(|props| props.iter().cloned().collect::<HashSet<String>>());
 
+    let prefixes = self.node_prefixes.as_deref();
+    let include_archived = self.include_archived;
+    let as_of = self.as_of;
     let items = {
       let ray = self.ray.clone();
       let guard = ray.read();
@@ -415,7 +499,9 @@ impl KiteTraversal {
         .ok_or_else(|| Error::from_reason("Kite is closed"))?;
 
       let results: Vec<_> = builder
-        .execute(|node_id, dir, etype| neighbors(ray.raw(), node_id, dir, etype))
+        .execute(|node_id, dir, etype| {
+          hop(ray, prefixes, include_archived, as_of, node_id, dir, etype)
+        })
         .collect();
 
       let mut items = Vec::with_capacity(results.len());
@@ -5,9 +5,11 @@
 
 mod builders;
 mod conversion;
+mod exploration;
 mod helpers;
 mod key_spec;
 mod kite_traversal;
+mod node_cursor;
 mod pathfinding;
 mod types;
 
@@ -17,30 +19,51 @@ pub use builders::{
   KiteUpdateEdgeBuilder, KiteUpsertBuilder, KiteUpsertByIdBuilder, KiteUpsertEdgeBuilder,
   KiteUpsertExecutorMany, KiteUpsertExecutorSingle,
 };
+pub use exploration::KiteExploration;
 pub use kite_traversal::KiteTraversal;
+pub use node_cursor::{KiteNodeCursor, KiteReachableCursor};
 pub use pathfinding::{JsPathEdge, JsPathResult, KitePath};
-pub use types::{JsEdgeSpec, JsKeySpec, JsKiteOptions, JsNodeSpec, JsPropSpec};
+pub use types::{
+  JsBatchOptions, JsEdgeSpec, JsGetByIdsOptions, JsGetOptions, JsKeySpec, JsKiteOptions,
+  JsLinkByKeyOptions, JsLinkByKeyResult, JsLinkManyResult, JsLinkOptions, JsListOptions,
+  JsMergeOptions, JsMergeResult, JsNodeSpec, JsPropSpec, JsSchemaChange, JsSetEdgePropOptions,
+  JsSetPropBulkOptions,
+};
 
 // Internal imports
-use conversion::js_props_to_map;
-use helpers::{batch_result_to_js, execute_batch_ops, node_props, node_props_selected, node_to_js};
-use key_spec::{parse_key_spec, prop_spec_to_def, KeySpec};
+use conversion::{js_declared_units, js_props_to_map};
+use helpers::{
+  apply_virtual_props, attach_edges, attach_units, batch_outcome_to_js, batch_result_to_js,
+  get_rename_option, lazy_or_selected, neighbors, node_props, node_props_selected, node_to_js,
+  prop_change_arg, rename_option, rename_props, schema_change_to_js, VirtualProps,
+};
+use key_spec::{edge_spec_to_def, node_spec_to_def, KeySpec};
 
 use napi::bindgen_prelude::*;
+use napi::threadsafe_function::ThreadsafeFunctionCallMode;
+use napi::UnknownRef;
 use napi_derive::napi;
 use parking_lot::RwLock;
 use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
-use crate::api::kite::{BatchOp, EdgeDef, Kite as RustKite, KiteOptions, NodeDef};
-use crate::types::NodeId;
+use crate::api::kite::{
+  BatchLink, BatchOp, Kite as RustKite, KiteOptions, LinkSpec, MergePropStrategy, NodeIfChanged,
+};
+use crate::api::traversal::TraversalDirection;
+use crate::core::single_file::{PropChange, SingleFileDB};
+use crate::error::{KiteError, Result as KiteResult};
+use crate::types::{Edge, NodeId};
 
 use super::database::{
-  CheckResult, DbStats, JsPrimaryReplicationStatus, JsReplicaReplicationStatus, MvccStats,
+  CheckResult, DbStats, JsCommitStats, JsIndexInfo, JsPrimaryReplicationStatus,
+  JsReplicaReplicationStatus, JsTransactionInfo, JsWalRecordStats, MvccStats, RepairResult,
 };
-use super::database::{JsFullEdge, JsPropValue};
+use super::database::{JsFullEdge, JsPropValue, PaginationOptions};
+use super::error::{closed_error, kite_error, kite_error_with, napi_error, ErrorCode};
+use super::traversal::JsTraversalDirection;
 
-use conversion::{js_value_to_prop_value, key_suffix_from_js};
+use conversion::{insert_key_from_js, js_prop_filter, js_value_to_prop_value, key_suffix_from_js};
 
 // =============================================================================
 // Kite Handle
@@ -78,6 +101,7 @@ use conversion::{js_value_to_prop_value, key_suffix_from_js};
 pub struct Kite {
   inner: Arc<RwLock<Option<RustKite>>>,
   node_specs: Arc<HashMap<String, Arc<KeySpec>>>,
+  virtual_props: RwLock<VirtualProps>,
 }
 
 impl Kite {
@@ -101,12 +125,265 @@ impl Kite {
     f(ray)
   }
 
+  /// Run a transaction-control call on the raw database with an exclusive
+  /// lock, for methods whose errors carry an `ErrorCode`
+  fn with_raw_coded<R>(
+    &self,
+    f: impl FnOnce(&SingleFileDB) -> Result<R, ErrorCode>,
+  ) -> Result<R, ErrorCode> {
+    let guard = self.inner.write();
+    let ray = guard
+      .as_ref()
+      .ok_or_else(|| closed_error("Kite is closed"))?;
+    f(ray.raw())
+  }
+
   fn key_spec(&self, node_type: &str) -> Result<&Arc<KeySpec>> {
     self
       .node_specs
       .get(node_type)
       .ok_or_else(|| Error::from_reason(format!("Unknown node type: {node_type}")))
   }
+
+  /// Computed props to merge into a read of `node_type`, if requested
+  fn virtual_props_for(
+    &self,
+    node_type: &str,
+    options: Option<&JsGetOptions>,
+  ) -> Option<Vec<(String, Arc<UnknownRef<false>>)>> {
+    let options = options?;
+    if !options.include_virtual.unwrap_or(false) || options.lazy.unwrap_or(false) {
+      return None;
+    }
+    self.virtual_props.read().get(node_type).cloned()
+  }
+
+  /// Apply batch ops independently, reporting each op's outcome
+  fn batch_best_effort(&self, env: &Env, ops: Vec<Object>) -> Result<Vec<Object<'static>>> {
+    let mut rust_ops = Vec::with_capacity(ops.len());
+    let mut parse_errors = Vec::with_capacity(ops.len());
+    for op in ops {
+      match self.parse_batch_op(env, op) {
+        Ok(op) => {
+          rust_ops.push(op);
+          parse_errors.push(None);
+        }
+        Err(e) => parse_errors.push(Some(e.reason.clone())),
+      }
+    }
+
+    let mut applied = self
+      .with_kite_mut(|ray| {
+        if ray.raw().has_transaction() {
+          return Err(Error::from_reason(
+            KiteError::TransactionInProgress.to_string(),
+          ));
+        }
+        // Each op gets its own transaction, keys included
+        Ok(
+          rust_ops
+            .into_iter()
+            .map(|op| {
+              ray.with_write_tx(|ray| {
+                let op = with_next_keys(ray, vec![op])?;
+                ray.batch(op).map(|mut results| results.remove(0))
+              })
+            })
+            .collect::<Vec<_>>(),
+        )
+      })?
+      .into_iter();
+
+    let mut out = Vec::with_capacity(parse_errors.len());
+    for parse_error in parse_errors {
+      let outcome = match parse_error {
+        Some(reason) => Err(reason),
+        None => applied
+          .next()
+          .ok_or_else(|| Error::from_reason("Batch result missing"))?
+          .map_err(|e| e.to_string()),
+      };
+      out.push(batch_outcome_to_js(env, outcome)?);
+    }
+    Ok(out)
+  }
+
+  /// Parse one JS batch op object
+  ///
+  /// A create without a key comes back flagged, with an empty key suffix;
+  /// [`with_next_keys`] allocates its key in the batch's transaction.
+  fn parse_batch_op(&self, env: &Env, op: Object) -> Result<ParsedBatchOp> {
+    let op_name: Option<String> = op.get_named_property("op").ok();
+    let op_name = match op_name {
+      Some(name) => name,
+      None => op.get_named_property("type")?,
+    };
+
+    let op = match op_name.as_str() {
+      "createNode" | "createAndLink" => {
+        let node_type: String = op.get_named_property("nodeType")?;
+        let key: Unknown = op.get_named_property("key")?;
+        let props: Option<Object> = op.get_named_property("props")?;
+        let spec = self.key_spec(&node_type)?;
+        let key_suffix = insert_key_from_js(env, spec.as_ref(), key)?;
+        let next_key = key_suffix.is_none();
+        let key_suffix = key_suffix.unwrap_or_default();
+        let props_map = js_props_to_map(env, props)?;
+        if op_name == "createNode" {
+          return Ok((
+            BatchOp::CreateNode {
+              node_type,
+              key_suffix,
+              props: props_map,
+            },
+            next_key,
+          ));
+        }
+
+        let link_objs: Vec<Object> = op.get_named_property("links")?;
+        let mut links = Vec::with_capacity(link_objs.len());
+        for link in link_objs {
+          let edge_type: String = link.get_named_property("edgeType")?;
+          let node_id: i64 = link.get_named_property("nodeId")?;
+          let incoming: Option<bool> = link.get_named_property("incoming")?;
+          links.push(BatchLink {
+            edge_type,
+            node_id: node_id as NodeId,
+            incoming: incoming.unwrap_or(false),
+          });
+        }
+        return Ok((
+          BatchOp::CreateAndLink {
+            node_type,
+            key_suffix,
+            props: props_map,
+            links,
+          },
+          next_key,
+        ));
+      }
+      "deleteNode" => {
+        let node_id: i64 = op.get_named_property("nodeId")?;
+        Ok(BatchOp::DeleteNode {
+          node_id: node_id as NodeId,
+        })
+      }
+      "link" => {
+        let src: i64 = op.get_named_property("src")?;
+        let dst: i64 = op.get_named_property("dst")?;
+        let edge_type: String = op.get_named_property("edgeType")?;
+        Ok(BatchOp::Link {
+          src: src as NodeId,
+          edge_type,
+          dst: dst as NodeId,
+        })
+      }
+      "linkWithProps" => {
+        let src: i64 = op.get_named_property("src")?;
+        let dst: i64 = op.get_named_property("dst")?;
+        let edge_type: String = op.get_named_property("edgeType")?;
+        let props: Option<Object> = op.get_named_property("props")?;
+        let props_map = js_props_to_map(env, props)?;
+        Ok(BatchOp::LinkWithProps {
+          src: src as NodeId,
+          edge_type,
+          dst: dst as NodeId,
+          props: props_map,
+        })
+      }
+      "unlink" => {
+        let src: i64 = op.get_named_property("src")?;
+        let dst: i64 = op.get_named_property("dst")?;
+        let edge_type: String = op.get_named_property("edgeType")?;
+        Ok(BatchOp::Unlink {
+          src: src as NodeId,
+          edge_type,
+          dst: dst as NodeId,
+        })
+      }
+      "setProp" => {
+        let node_id: i64 = op.get_named_property("nodeId")?;
+        let prop_name: String = op.get_named_property("propName")?;
+        let value: Unknown = op.get_named_property("value")?;
+        let prop_value = js_value_to_prop_value(env, value)?;
+        Ok(BatchOp::SetProp {
+          node_id: node_id as NodeId,
+          prop_name,
+          value: prop_value,
+        })
+      }
+      "setEdgeProp" => {
+        let src: i64 = op.get_named_property("src")?;
+        let dst: i64 = op.get_named_property("dst")?;
+        let edge_type: String = op.get_named_property("edgeType")?;
+        let prop_name: String = op.get_named_property("propName")?;
+        let value: Unknown = op.get_named_property("value")?;
+        let prop_value = js_value_to_prop_value(env, value)?;
+        Ok(BatchOp::SetEdgeProp {
+          src: src as NodeId,
+          edge_type,
+          dst: dst as NodeId,
+          prop_name,
+          value: prop_value,
+        })
+      }
+      "setEdgeProps" => {
+        let src: i64 = op.get_named_property("src")?;
+        let dst: i64 = op.get_named_property("dst")?;
+        let edge_type: String = op.get_named_property("edgeType")?;
+        let props: Option<Object> = op.get_named_property("props")?;
+        let props_map = js_props_to_map(env, props)?;
+        Ok(BatchOp::SetEdgeProps {
+          src: src as NodeId,
+          edge_type,
+          dst: dst as NodeId,
+          props: props_map,
+        })
+      }
+      "delProp" => {
+        let node_id: i64 = op.get_named_property("nodeId")?;
+        let prop_name: String = op.get_named_property("propName")?;
+        Ok(BatchOp::DelProp {
+          node_id: node_id as NodeId,
+          prop_name,
+        })
+      }
+      other => Err(Error::from_reason(format!("Unknown batch op: {other}"))),
+    }?;
+    Ok((op, false))
+  }
+}
+
+/// A parsed batch op, and whether it is a create still needing its
+/// autoincrement key
+type ParsedBatchOp = (BatchOp, bool);
+
+/// Give the creates flagged by `parse_batch_op` their autoincrement key
+///
+/// Run inside the transaction applying the ops, so a batch that fails
+/// doesn't use up the numbers.
+fn with_next_keys(ray: &RustKite, ops: Vec<ParsedBatchOp>) -> KiteResult<Vec<BatchOp>> {
+  ops
+    .into_iter()
+    .map(|(mut op, next_key)| {
+      if next_key {
+        if let BatchOp::CreateNode {
+          node_type,
+          key_suffix,
+          ..
+        }
+        | BatchOp::CreateAndLink {
+          node_type,
+          key_suffix,
+          ..
+        } = &mut op
+        {
+          *key_suffix = ray.next_key(node_type)?;
+        }
+      }
+      Ok(op)
+    })
+    .collect()
 }
 
 #[napi]
@@ -122,6 +399,7 @@ impl Kite {
     kite_opts.mvcc = options.mvcc.unwrap_or(false);
     kite_opts.mvcc_gc_interval_ms = options.mvcc_gc_interval_ms.map(|v| v as u64);
     kite_opts.mvcc_retention_ms = options.mvcc_retention_ms.map(|v| v as u64);
+    kite_opts.mvcc_retain_history = options.mvcc_retain_history.unwrap_or(false);
     kite_opts.mvcc_max_chain_depth = options.mvcc_max_chain_depth.map(|v| v as usize);
     if let Some(mode) = options.sync_mode {
       kite_opts.sync_mode = mode.into();
@@ -139,6 +417,9 @@ impl Kite {
         kite_opts.wal_size = Some((wal_size_mb as usize).saturating_mul(1024 * 1024));
       }
     }
+    if let Some(path) = options.wal_path {
+      kite_opts.wal_path = Some(path.into());
+    }
     if let Some(threshold) = options.checkpoint_threshold {
       kite_opts.checkpoint_threshold = Some(threshold.clamp(0.0, 1.0));
     }
@@ -173,29 +454,14 @@ impl Kite {
       }
     }
 
-    for node in options.nodes {
-      let key_spec = Arc::new(parse_key_spec(&node.name, node.key)?);
-      let prefix = key_spec.prefix().to_string();
-
-      let mut node_def = NodeDef::new(&node.name, &prefix);
-      if let Some(props) = node.props.as_ref() {
-        for (prop_name, prop_spec) in props {
-          node_def = node_def.prop(prop_spec_to_def(prop_name, prop_spec)?);
-        }
-      }
-
-      node_specs.insert(node.name.clone(), Arc::clone(&key_spec));
+    for node in &options.nodes {
+      let (node_def, key_spec) = node_spec_to_def(node)?;
+      node_specs.insert(node.name.clone(), key_spec);
       kite_opts.nodes.push(node_def);
     }
 
-    for edge in options.edges {
-      let mut edge_def = EdgeDef::new(&edge.name);
-      if let Some(props) = edge.props.as_ref() {
-        for (prop_name, prop_spec) in props {
-          edge_def = edge_def.prop(prop_spec_to_def(prop_name, prop_spec)?);
-        }
-      }
-      kite_opts.edges.push(edge_def);
+    for edge in &options.edges {
+      kite_opts.edges.push(edge_spec_to_def(edge)?);
     }
 
     let ray = RustKite::open(path, kite_opts).map_err(|e| Error::from_reason(e.to_string()))?;
@@ -203,6 +469,7 @@ impl Kite {
     Ok(Kite {
       inner: Arc::new(RwLock::new(Some(ray))),
       node_specs: Arc::new(node_specs),
+      virtual_props: RwLock::new(HashMap::new()),
     })
   }
 
@@ -233,27 +500,62 @@ impl Kite {
     node_type: String,
     key: Unknown,
     props: Option<Vec<String>>,
+    options: Option<JsGetOptions>,
   ) -> Result<Option<Object<'_>>> {
     let key_suffix = {
       let spec = self.key_spec(&node_type)?;
       key_suffix_from_js(&env, spec.as_ref(), key)?
     };
-    let selected_props = props.map(|props| props.into_iter().collect::<HashSet<String>>());
-    self.with_kite(move |ray| {
-      let node_ref = ray
-        .get(&node_type, &key_suffix)
-        .map_err(|e| Error::from_reason(e.to_string()))?;
+    let selected_props = lazy_or_selected(props, options.as_ref());
+    let virtual_props = self.virtual_props_for(&node_type, options.as_ref());
+    let include_archived = options
+      .as_ref()
+      .and_then(|opts| opts.include_archived)
+      .unwrap_or(false);
+    let include_units = options
+      .as_ref()
+      .and_then(|opts| opts.include_units)
+      .unwrap_or(false);
+    let (rename, rename_only) = get_rename_option(options.as_ref())?;
+    let edge_types = options.as_ref().and_then(|opts| opts.edges.as_ref());
+    let found = self.with_kite(|ray| {
+      let node_ref = if include_archived {
+        ray.get_including_archived(&node_type, &key_suffix)
+      } else {
+        ray.get(&node_type, &key_suffix)
+      }
+      .map_err(|e| Error::from_reason(e.to_string()))?;
 
       match node_ref {
         Some(node_ref) => {
           let (node_id, node_key, node_type) = node_ref.into_parts();
-          let props = node_props_selected(ray, node_id, selected_props.as_ref());
-          let obj = node_to_js(&env, node_id, node_key, &node_type, props)?;
-          Ok(Some(obj))
+          let props = rename_props(
+            node_props_selected(ray, node_id, selected_props.as_ref()),
+            rename,
+            rename_only,
+          );
+          let stored = virtual_props.as_ref().map(|_| node_props(ray, node_id));
+          let mut obj = node_to_js(&env, node_id, node_key, &node_type, props)?;
+          if include_units {
+            attach_units(ray, &mut obj, &node_type)?;
+          }
+          if let Some(edge_types) = edge_types {
+            attach_edges(ray, &mut obj, node_id, edge_types)?;
+          }
+          Ok(Some((obj, stored)))
         }
         None => Ok(None),
       }
-    })
+    })?;
+
+    // Computed props run after the lock is released so they may read the db
+    let Some((mut obj, stored)) = found else {
+      return Ok(None);
+    };
+    if let (Some(virtual_props), Some(stored)) = (virtual_props, stored) {
+      apply_virtual_props(&env, &mut obj, &stored, &virtual_props)?;
+    }
+    Ok(Some(obj))
   }
 
   /// Get a node by ID (returns node object with props)
@@ -263,24 +565,121 @@ impl Kite {
     env: Env,
     node_id: i64,
     props: Option<Vec<String>>,
+    options: Option<JsGetOptions>,
   ) -> Result<Option<Object<'_>>> {
-    let selected_props = props.map(|props| props.into_iter().collect::<HashSet<String>>());
-    self.with_kite(move |ray| {
+    let selected_props = lazy_or_selected(props, options.as_ref());
+    let include_units = options
+      .as_ref()
+      .and_then(|opts| opts.include_units)
+      .unwrap_or(false);
+    let (rename, rename_only) = get_rename_option(options.as_ref())?;
+    let include_archived = options
+      .as_ref()
+      .and_then(|opts| opts.include_archived)
+      .unwrap_or(false);
+    let edge_types = options.as_ref().and_then(|opts| opts.edges.as_ref());
+    let found = self.with_kite(|ray| {
       let node_ref = ray
         .node_by_id(node_id as NodeId)
-        .map_err(|e| Error::from_reason(e.to_string()))?;
+        .map_err(|e| Error::from_reason(e.to_string()))?
+        .filter(|node_ref| include_archived || !ray.is_archived(node_ref.id()));
       match node_ref {
         Some(node_ref) => {
           let (node_id, node_key, node_type) = node_ref.into_parts();
-          let props = node_props_selected(ray, node_id, selected_props.as_ref());
-          let obj = node_to_js(&env, node_id, node_key, &node_type, props)?;
-          Ok(Some(obj))
+          let props = rename_props(
+            node_props_selected(ray, node_id, selected_props.as_ref()),
+            rename,
+            rename_only,
+          );
+          let virtual_props = self.virtual_props_for(&node_type, options.as_ref());
+          let stored = virtual_props.as_ref().map(|_| node_props(ray, node_id));
+          let mut obj = node_to_js(&env, node_id, node_key, &node_type, props)?;
+          if include_units {
+            attach_units(ray, &mut obj, &node_type)?;
+          }
+          if let Some(edge_types) = edge_types {
+            attach_edges(ray, &mut obj, node_id, edge_types)?;
+          }
+          Ok(Some((obj, virtual_props.zip(stored))))
         }
         None => Ok(None),
       }
+    })?;
+
+    let Some((mut obj, computed)) = found else {
+      return Ok(None);
+    };
+    if let Some((virtual_props, stored)) = computed {
+      apply_virtual_props(&env, &mut obj, &stored, &virtual_props)?;
+    }
+    Ok(Some(obj))
+  }
+
+  /// Register a computed (virtual) prop for a node type
+  ///
+  /// `compute` is called with the node's stored props whenever a node of the
+  /// type is read with `includeVirtual: true`, and its return value is
+  /// merged into the result. Registering a name again replaces it. Only
+  /// `get` and `getById` apply virtual props; bulk reads (`getMany`,
+  /// `getByIds`, `neighbors`, `all`) return stored props only.
+  #[allow(clippy::arc_with_non_send_sync)]
+  #[napi]
+  pub fn define_virtual_prop(
+    &self,
+    env: Env,
+    node_type: String,
+    name: String,
+    compute: UnknownRef<false>,
+  ) -> Result<()> {
+    self.key_spec(&node_type)?;
+    if compute.get_value(&env)?.get_type()? != ValueType::Function {
+      return Err(Error::from_reason("defineVirtualProp requires a function"));
+    }
+    let mut virtual_props = self.virtual_props.write();
+    let entries = virtual_props.entry(node_type).or_default();
+    entries.retain(|(existing, _)| *existing != name);
+    entries.push((name, Arc::new(compute)));
+    Ok(())
+  }
+
+  /// Call `callback` after each commit that changes `prop_name` on a node
+  /// of `node_type`
+  ///
+  /// The callback gets `{ nodeId, oldValue, newValue }`, with `null` for a
+  /// prop that was unset or deleted (including by deleting the node). Calls
+  /// are queued onto the event loop, so they run after the write that caused
+  /// them, and a registered listener doesn't keep the process alive. Returns
+  /// an id for `offPropChange`.
+  #[napi]
+  pub fn on_prop_change(
+    &self,
+    node_type: String,
+    prop_name: String,
+    callback: Function<'_, Object<'static>, Unknown<'static>>,
+  ) -> Result<i64> {
+    let tsfn = callback
+      .build_threadsafe_function::<PropChange>()
+      .weak::<true>()
+      .callee_handled::<false>()
+      .build_callback(|ctx| prop_change_arg(&ctx.env, &ctx.value))?;
+    self.with_kite(|ray| {
+      ray
+        .on_prop_change(&node_type, &prop_name, move |change| {
+          tsfn.call(change.clone(), ThreadsafeFunctionCallMode::NonBlocking);
+        })
+        .map(|id| id as i64)
+        .map_err(|e| Error::from_reason(e.to_string()))
     })
   }
 
+  /// Unregister a listener added with `onPropChange`
+  ///
+  /// Returns false if `id` isn't registered.
+  #[napi]
+  pub fn off_prop_change(&self, id: i64) -> Result<bool> {
+    self.with_kite(|ray| Ok(ray.remove_prop_listener(id as u64)))
+  }
+
   /// Get a lightweight node reference by key (no properties)
   #[napi(js_name = "get_ref")]
   pub fn node_ref(&self, env: Env, node_type: String, key: Unknown) -> Result<Option<Object<'_>>> {
@@ -321,24 +720,139 @@ impl Kite {
     })
   }
 
+  /// Get multiple nodes by key under a single read lock
+  ///
+  /// Results line up with `keys`; a key with no node yields `null`.
+  #[napi]
+  pub fn get_many(
+    &self,
+    env: Env,
+    node_type: String,
+    keys: Vec<Unknown>,
+    props: Option<Vec<String>>,
+  ) -> Result<Vec<Option<Object<'_>>>> {
+    if keys.is_empty() {
+      return Ok(Vec::new());
+    }
+
+    let key_suffixes = {
+      let spec = self.key_spec(&node_type)?;
+      keys
+        .into_iter()
+        .map(|key| key_suffix_from_js(&env, spec.as_ref(), key))
+        .collect::<Result<Vec<_>>>()?
+    };
+    let selected_props = props.map(|props| props.into_iter().collect::<HashSet<String>>());
+    self.with_kite(move |ray| {
+      let mut out = Vec::with_capacity(key_suffixes.len());
+      for key_suffix in &key_suffixes {
+        let node_ref = ray
+          .get(&node_type, key_suffix)
+          .map_err(|e| Error::from_reason(e.to_string()))?;
+        match node_ref {
+          Some(node_ref) => {
+            let (node_id, node_key, node_type) = node_ref.into_parts();
+            let props = node_props_selected(ray, node_id, selected_props.as_ref());
+            out.push(Some(node_to_js(
+              &env, node_id, node_key, &node_type, props,
+            )?));
+          }
+          None => out.push(None),
+        }
+      }
+      Ok(out)
+    })
+  }
+
   /// Get multiple nodes by ID (returns node objects with props)
+  ///
+  /// Missing ids, and archived ones unless `includeArchived` is set, are
+  /// dropped unless `includeMissing` is set, which returns `null` in their
+  /// place.
   #[napi(js_name = "get_by_ids")]
   pub fn by_ids(
     &self,
     env: Env,
     node_ids: Vec<i64>,
     props: Option<Vec<String>>,
-  ) -> Result<Vec<Object<'_>>> {
+    options: Option<JsGetByIdsOptions>,
+  ) -> Result<Vec<Option<Object<'_>>>> {
     if node_ids.is_empty() {
       return Ok(Vec::new());
     }
 
+    let include_missing = options
+      .as_ref()
+      .and_then(|o| o.include_missing)
+      .unwrap_or(false);
+    let include_archived = options
+      .as_ref()
+      .and_then(|o| o.include_archived)
+      .unwrap_or(false);
     let selected_props = props.map(|props| props.into_iter().collect::<HashSet<String>>());
     self.with_kite(move |ray| {
       let mut out = Vec::with_capacity(node_ids.len());
       for node_id in node_ids {
         let node_ref = ray
           .node_by_id(node_id as NodeId)
+          .map_err(|e| Error::from_reason(e.to_string()))?
+          .filter(|node_ref| include_archived || !ray.is_archived(node_ref.id()));
+        if let Some(node_ref) = node_ref {
+          let (node_id, node_key, node_type) = node_ref.into_parts();
+          let props = node_props_selected(ray, node_id, selected_props.as_ref());
+          out.push(Some(node_to_js(
+            &env, node_id, node_key, &node_type, props,
+          )?));
+        } else if include_missing {
+          out.push(None);
+        }
+      }
+      Ok(out)
+    })
+  }
+
+  /// Get the nodes one hop from `nodeId` as node objects
+  ///
+  /// Direction defaults to outgoing edges; `edgeType` restricts the hop to
+  /// one edge type. A neighbor reached over several edges is returned once;
+  /// archived neighbors are skipped.
+  #[napi]
+  pub fn neighbors(
+    &self,
+    env: Env,
+    node_id: i64,
+    edge_type: Option<String>,
+    direction: Option<JsTraversalDirection>,
+    props: Option<Vec<String>>,
+  ) -> Result<Vec<Object<'_>>> {
+    let direction = direction
+      .map(TraversalDirection::from)
+      .unwrap_or(TraversalDirection::Out);
+    let selected_props = props.map(|props| props.into_iter().collect::<HashSet<String>>());
+    self.with_kite(move |ray| {
+      let etype = match edge_type.as_deref() {
+        Some(edge_type) => Some(
+          ray
+            .edge_def(edge_type)
+            .and_then(|def| def.etype_id)
+            .ok_or_else(|| Error::from_reason(format!("Unknown edge type: {edge_type}")))?,
+        ),
+        None => None,
+      };
+
+      let mut seen = HashSet::new();
+      let mut out = Vec::new();
+      for edge in neighbors(ray.raw(), node_id as NodeId, direction, etype) {
+        let other = if edge.src == node_id as NodeId {
+          edge.dst
+        } else {
+          edge.src
+        };
+        if !seen.insert(other) || ray.is_archived(other) {
+          continue;
+        }
+        let node_ref = ray
+          .node_by_id(other)
           .map_err(|e| Error::from_reason(e.to_string()))?;
         if let Some(node_ref) = node_ref {
           let (node_id, node_key, node_type) = node_ref.into_parts();
@@ -350,6 +864,53 @@ impl Kite {
     })
   }
 
+  /// Count a node's edges without materializing them
+  ///
+  /// Direction defaults to both, where a self-loop counts twice;
+  /// `edgeType` restricts the count to one edge type.
+  #[napi]
+  pub fn degree(
+    &self,
+    node_id: i64,
+    direction: Option<JsTraversalDirection>,
+    edge_type: Option<String>,
+  ) -> Result<i64> {
+    let direction = direction
+      .map(TraversalDirection::from)
+      .unwrap_or(TraversalDirection::Both);
+    self.with_kite(|ray| {
+      ray
+        .degree(node_id as NodeId, direction, edge_type.as_deref())
+        .map(|degree| degree as i64)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
+  /// `degree` for several nodes, as an object keyed by node ID
+  #[napi]
+  pub fn degrees(
+    &self,
+    node_ids: Vec<i64>,
+    direction: Option<JsTraversalDirection>,
+    edge_type: Option<String>,
+  ) -> Result<HashMap<String, i64>> {
+    let direction = direction
+      .map(TraversalDirection::from)
+      .unwrap_or(TraversalDirection::Both);
+    let node_ids: Vec<NodeId> = node_ids.into_iter().map(|id| id as NodeId).collect();
+    self.with_kite(|ray| {
+      ray
+        .degrees(&node_ids, direction, edge_type.as_deref())
+        .map(|degrees| {
+          degrees
+            .into_iter()
+            .map(|(node_id, degree)| (node_id.to_string(), degree as i64))
+            .collect()
+        })
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
   /// Get a node property value
   #[napi(js_name = "get_prop")]
   pub fn prop(&self, node_id: i64, prop_name: String) -> Result<Option<JsPropValue>> {
@@ -357,6 +918,58 @@ impl Kite {
     Ok(value.map(JsPropValue::from))
   }
 
+  /// Get an integer property; throws if it holds another type
+  #[napi(js_name = "getPropInt")]
+  pub fn prop_int(&self, node_id: i64, prop_name: String) -> Result<Option<i64>> {
+    self.with_kite(|ray| {
+      ray
+        .prop_int(node_id as NodeId, &prop_name)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
+  /// Get a float property; throws if it holds another type
+  #[napi(js_name = "getPropFloat")]
+  pub fn prop_float(&self, node_id: i64, prop_name: String) -> Result<Option<f64>> {
+    self.with_kite(|ray| {
+      ray
+        .prop_float(node_id as NodeId, &prop_name)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
+  /// Get a string property; throws if it holds another type
+  #[napi(js_name = "getPropString")]
+  pub fn prop_string(&self, node_id: i64, prop_name: String) -> Result<Option<String>> {
+    self.with_kite(|ray| {
+      ray
+        .prop_string(node_id as NodeId, &prop_name)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
+  /// Get a boolean property; throws if it holds another type
+  #[napi(js_name = "getPropBool")]
+  pub fn prop_bool(&self, node_id: i64, prop_name: String) -> Result<Option<bool>> {
+    self.with_kite(|ray| {
+      ray
+        .prop_bool(node_id as NodeId, &prop_name)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
+  /// Whether a node has a property set, without converting its value
+  #[napi]
+  pub fn has_prop(&self, node_id: i64, prop_name: String) -> Result<bool> {
+    self.with_kite(|ray| Ok(ray.has_prop(node_id as NodeId, &prop_name)))
+  }
+
+  /// Number of properties set on a node (0 if it doesn't exist)
+  #[napi]
+  pub fn prop_count(&self, node_id: i64) -> Result<i64> {
+    self.with_kite(|ray| Ok(ray.prop_count(node_id as NodeId) as i64))
+  }
+
   /// Set a node property value
   #[napi]
   pub fn set_prop(&self, env: Env, node_id: i64, prop_name: String, value: Unknown) -> Result<()> {
@@ -368,17 +981,207 @@ impl Kite {
     })
   }
 
+  /// Set a node property, returning the previous value (null if unset)
+  #[napi]
+  pub fn replace_prop(
+    &self,
+    env: Env,
+    node_id: i64,
+    prop_name: String,
+    value: Unknown,
+  ) -> Result<Option<JsPropValue>> {
+    let prop_value = js_value_to_prop_value(&env, value)?;
+    let previous = self.with_kite_mut(|ray| {
+      ray
+        .replace_prop(node_id as NodeId, &prop_name, prop_value)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })?;
+    Ok(previous.map(JsPropValue::from))
+  }
+
+  /// Delete a node property, returning the removed value (null if unset)
+  #[napi]
+  pub fn take_prop(&self, node_id: i64, prop_name: String) -> Result<Option<JsPropValue>> {
+    let previous = self.with_kite_mut(|ray| {
+      ray
+        .take_prop(node_id as NodeId, &prop_name)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })?;
+    Ok(previous.map(JsPropValue::from))
+  }
+
+  /// Get a node property by the node's key; throws if the key is missing
+  #[napi]
+  pub fn get_prop_by_key(
+    &self,
+    env: Env,
+    node_type: String,
+    key: Unknown,
+    prop_name: String,
+  ) -> Result<Option<JsPropValue>> {
+    let key_suffix = {
+      let spec = self.key_spec(&node_type)?;
+      key_suffix_from_js(&env, spec.as_ref(), key)?
+    };
+    let value = self.with_kite(|ray| {
+      ray
+        .prop_by_key(&node_type, &key_suffix, &prop_name)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })?;
+    Ok(value.map(JsPropValue::from))
+  }
+
+  /// Set a node property by the node's key; throws if the key is missing
+  #[napi]
+  pub fn set_prop_by_key(
+    &self,
+    env: Env,
+    node_type: String,
+    key: Unknown,
+    prop_name: String,
+    value: Unknown,
+  ) -> Result<()> {
+    let key_suffix = {
+      let spec = self.key_spec(&node_type)?;
+      key_suffix_from_js(&env, spec.as_ref(), key)?
+    };
+    let prop_value = js_value_to_prop_value(&env, value)?;
+    self.with_kite_mut(|ray| {
+      ray
+        .set_prop_by_key(&node_type, &key_suffix, &prop_name, prop_value)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
+  /// Delete a node property by the node's key; throws if the key is missing
+  #[napi]
+  pub fn del_prop_by_key(
+    &self,
+    env: Env,
+    node_type: String,
+    key: Unknown,
+    prop_name: String,
+  ) -> Result<()> {
+    let key_suffix = {
+      let spec = self.key_spec(&node_type)?;
+      key_suffix_from_js(&env, spec.as_ref(), key)?
+    };
+    self.with_kite_mut(|ray| {
+      ray
+        .del_prop_by_key(&node_type, &key_suffix, &prop_name)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
   /// Set multiple node property values
+  ///
+  /// A `$units` entry (prop name -> unit) is checked against the props'
+  /// declared units and is not stored.
   #[napi]
   pub fn set_props(&self, env: Env, node_id: i64, props: Object) -> Result<()> {
+    let declared = js_declared_units(Some(&props))?;
     let props_map = js_props_to_map(&env, Some(props))?;
     self.with_kite_mut(|ray| {
+      if !declared.is_empty() {
+        ray
+          .check_node_units(node_id as NodeId, &declared)
+          .map_err(|e| Error::from_reason(e.to_string()))?;
+      }
       ray
         .set_props(node_id as NodeId, props_map)
         .map_err(|e| Error::from_reason(e.to_string()))
     })
   }
 
+  /// Set the same property value on many nodes in one transaction
+  ///
+  /// Returns how many nodes were updated; ids with no node are skipped
+  /// unless `strict` is set.
+  #[napi]
+  pub fn set_prop_bulk(
+    &self,
+    env: Env,
+    node_ids: Vec<i64>,
+    prop_name: String,
+    value: Unknown,
+    options: Option<JsSetPropBulkOptions>,
+  ) -> Result<i64> {
+    let prop_value = js_value_to_prop_value(&env, value)?;
+    let strict = options.and_then(|o| o.strict).unwrap_or(false);
+    let node_ids: Vec<NodeId> = node_ids.into_iter().map(|id| id as NodeId).collect();
+    self.with_kite_mut(|ray| {
+      ray
+        .set_prop_bulk(&node_ids, &prop_name, prop_value, strict)
+        .map(|updated| updated as i64)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
+  /// Set props on every node of a type whose `matchProp` equals `matchValue`
+  ///
+  /// Runs in one transaction under one lock and returns how many nodes
+  /// matched; archived nodes are skipped.
+  #[napi]
+  pub fn update_where(
+    &self,
+    env: Env,
+    node_type: String,
+    match_prop: String,
+    match_value: Unknown,
+    set_props: Object,
+  ) -> Result<i64> {
+    let match_value = js_value_to_prop_value(&env, match_value)?;
+    let props_map = js_props_to_map(&env, Some(set_props))?;
+    self.with_kite_mut(|ray| {
+      ray
+        .update_where(&node_type, &match_prop, &match_value, &props_map)
+        .map(|updated| updated as i64)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
+  /// Content version of a node, usable as an HTTP ETag (null if missing)
+  ///
+  /// Changes whenever one of the node's props is set or removed.
+  #[napi]
+  pub fn node_version(&self, node_id: i64) -> Result<Option<String>> {
+    self.with_kite(|ray| Ok(ray.node_version(node_id as NodeId)))
+  }
+
+  /// Get a node only if it differs from the caller's known version
+  ///
+  /// Returns `{ notModified, node, version }`: `notModified` is true when
+  /// the node still has `knownVersion`; otherwise `node` and `version` hold
+  /// its current state, or are null when the node doesn't exist.
+  #[napi]
+  pub fn get_if_changed(
+    &self,
+    env: Env,
+    node_id: i64,
+    known_version: String,
+  ) -> Result<Object<'_>> {
+    let (not_modified, node, version) = self.with_kite(|ray| {
+      let outcome = ray
+        .get_if_changed(node_id as NodeId, &known_version)
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+      match outcome {
+        NodeIfChanged::NotFound => Ok((false, None, None)),
+        NodeIfChanged::NotModified => Ok((true, None, None)),
+        NodeIfChanged::Modified { node, version } => {
+          let (node_id, node_key, node_type) = node.into_parts();
+          let props = node_props(ray, node_id);
+          let obj = node_to_js(&env, node_id, node_key, &node_type, props)?;
+          Ok((false, Some(obj), Some(version)))
+        }
+      }
+    })?;
+    let mut out = Object::new(&env)?;
+    out.set_named_property("notModified", not_modified)?;
+    out.set_named_property("node", node)?;
+    out.set_named_property("version", version)?;
+    Ok(out)
+  }
+
   /// Check if a node exists
   #[napi]
   pub fn exists(&self, node_id: i64) -> Result<bool> {
@@ -395,6 +1198,77 @@ impl Kite {
     })
   }
 
+  /// Archive a node: hide it from get/all/traversals without deleting it
+  ///
+  /// The archive time is kept out of the node's props, and the
+  /// `__archived_at` prop name is reserved. Returns false if the node was
+  /// already archived.
+  #[napi]
+  pub fn archive(&self, node_id: i64) -> Result<bool> {
+    self.with_kite_mut(|ray| {
+      ray
+        .archive(node_id as NodeId)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
+  /// Restore an archived node; returns false if it wasn't archived
+  #[napi]
+  pub fn unarchive(&self, node_id: i64) -> Result<bool> {
+    self.with_kite_mut(|ray| {
+      ray
+        .unarchive(node_id as NodeId)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
+  /// Check whether a node is archived
+  #[napi]
+  pub fn is_archived(&self, node_id: i64) -> Result<bool> {
+    self.with_kite(|ray| Ok(ray.is_archived(node_id as NodeId)))
+  }
+
+  /// Hard-delete nodes archived at least `olderThanMs` ago; returns the count
+  #[napi]
+  pub fn purge_archived(&self, older_than_ms: i64) -> Result<i64> {
+    self.with_kite_mut(|ray| {
+      ray
+        .purge_archived(older_than_ms.max(0) as u64)
+        .map(|count| count as i64)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
+  /// Merge `removeId` into `keepId`: move its edges, props and labels, then
+  /// delete it, in one transaction
+  #[napi]
+  pub fn merge_nodes(
+    &self,
+    keep_id: i64,
+    remove_id: i64,
+    options: Option<JsMergeOptions>,
+  ) -> Result<JsMergeResult> {
+    let strategy = match options.and_then(|o| o.prop_strategy).as_deref() {
+      None | Some("keepWins") => MergePropStrategy::KeepWins,
+      Some("removeWins") => MergePropStrategy::RemoveWins,
+      Some(other) => {
+        return Err(Error::from_reason(format!(
+          "Unknown propStrategy: {other} (expected \"keepWins\" or \"removeWins\")"
+        )))
+      }
+    };
+    self.with_kite_mut(|ray| {
+      ray
+        .merge_nodes(keep_id as NodeId, remove_id as NodeId, strategy)
+        .map(|result| JsMergeResult {
+          edges_moved: result.edges_moved as i64,
+          self_loops_dropped: result.self_loops_dropped as i64,
+          props_merged: result.props_merged as i64,
+        })
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
   /// Delete a node by key
   #[napi]
   pub fn delete_by_key(&self, env: Env, node_type: String, key: Unknown) -> Result<bool> {
@@ -446,6 +1320,65 @@ impl Kite {
     ))
   }
 
+  /// Get the node at `key`, inserting it with `props` if it doesn't exist
+  ///
+  /// The lookup and the insert happen under one write lock, so no other
+  /// writer can insert the key in between. Returns `{ node, created }`;
+  /// `props` are ignored when the node already exists, and must set every
+  /// required prop when it doesn't. An archived node at `key` is
+  /// unarchived and returned.
+  #[napi]
+  pub fn get_or_insert(
+    &self,
+    env: Env,
+    node_type: String,
+    key: Unknown,
+    props: Option<Object>,
+  ) -> Result<Object<'_>> {
+    let key_suffix = {
+      let spec = self.key_spec(&node_type)?;
+      key_suffix_from_js(&env, spec.as_ref(), key)?
+    };
+    let declared = js_declared_units(props.as_ref())?;
+    let props_map = js_props_to_map(&env, props)?;
+    let (node_ref, props, created) = self.with_kite_mut(|ray| {
+      let existing = ray
+        .get_including_archived(&node_type, &key_suffix)
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+      if let Some(node_ref) = existing {
+        ray
+          .unarchive(node_ref.id())
+          .map_err(|e| Error::from_reason(e.to_string()))?;
+        let props = node_props(ray, node_ref.id());
+        return Ok((node_ref, props, false));
+      }
+      if !declared.is_empty() {
+        ray
+          .check_units(&node_type, &declared)
+          .map_err(|e| Error::from_reason(e.to_string()))?;
+      }
+      ray
+        .check_required_props(&node_type, &props_map)
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+      let node_ref = ray
+        .insert(&node_type)
+        .map_err(|e| Error::from_reason(e.to_string()))?
+        .values(&key_suffix, props_map)
+        .map_err(|e| Error::from_reason(e.to_string()))?
+        .returning()
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+      let props = node_props(ray, node_ref.id());
+      Ok((node_ref, props, true))
+    })?;
+
+    let (node_id, node_key, node_type) = node_ref.into_parts();
+    let node = node_to_js(&env, node_id, node_key, &node_type, props)?;
+    let mut out = Object::new(&env)?;
+    out.set_named_property("node", node)?;
+    out.set_named_property("created", created)?;
+    Ok(out)
+  }
+
   /// Create an update builder by node ID
   #[napi]
   pub fn update_by_id(&self, node_id: i64) -> Result<KiteUpdateBuilder> {
@@ -458,10 +1391,12 @@ impl Kite {
   /// Create an upsert builder by node ID
   #[napi]
   pub fn upsert_by_id(&self, node_type: String, node_id: i64) -> Result<KiteUpsertByIdBuilder> {
+    let spec = Arc::clone(self.key_spec(&node_type)?);
     Ok(KiteUpsertByIdBuilder::new(
       self.inner.clone(),
       node_type,
       node_id as NodeId,
+      spec,
     ))
   }
 
@@ -489,6 +1424,12 @@ impl Kite {
   }
 
   /// Link two nodes
+  ///
+  /// `validFrom`/`validTo` in `options` limit the edge to a time window
+  /// (ms since the Unix epoch, `[validFrom, validTo)`), stored as the
+  /// reserved `__valid_from`/`__valid_to` edge props; traversals skip
+  /// edges outside it when given `asOf`. Linking an existing edge without
+  /// a window keeps its current window; unlink it first to drop the window.
   #[napi]
   pub fn link(
     &self,
@@ -497,10 +1438,24 @@ impl Kite {
     edge_type: String,
     dst: i64,
     props: Option<Object>,
+    options: Option<JsLinkOptions>,
   ) -> Result<()> {
     let props_map = js_props_to_map(&env, props)?;
+    let valid_from = options.as_ref().and_then(|opts| opts.valid_from);
+    let valid_to = options.as_ref().and_then(|opts| opts.valid_to);
     self.with_kite_mut(|ray| {
-      if props_map.is_empty() {
+      if valid_from.is_some() || valid_to.is_some() {
+        ray
+          .link_valid(
+            src as NodeId,
+            &edge_type,
+            dst as NodeId,
+            props_map,
+            valid_from,
+            valid_to,
+          )
+          .map_err(|e| Error::from_reason(e.to_string()))
+      } else if props_map.is_empty() {
         ray
           .link(src as NodeId, &edge_type, dst as NodeId)
           .map_err(|e| Error::from_reason(e.to_string()))
@@ -512,6 +1467,77 @@ impl Kite {
     })
   }
 
+  /// Link many node pairs in one transaction
+  ///
+  /// Each entry is `{ src, edgeType, dst, props? }`. Edges that already
+  /// exist keep their props and are counted as `existing`; nothing is
+  /// written if any entry fails.
+  #[napi]
+  pub fn link_many(&self, env: Env, edges: Vec<Object>) -> Result<JsLinkManyResult> {
+    let mut links = Vec::with_capacity(edges.len());
+    for edge in edges {
+      let src: i64 = edge.get_named_property("src")?;
+      let edge_type: String = edge.get_named_property("edgeType")?;
+      let dst: i64 = edge.get_named_property("dst")?;
+      let props: Option<Object> = edge.get_named_property("props")?;
+      links.push(LinkSpec {
+        src: src as NodeId,
+        edge_type,
+        dst: dst as NodeId,
+        props: js_props_to_map(&env, props)?,
+      });
+    }
+    self.with_kite_mut(|ray| {
+      ray
+        .link_many(links)
+        .map(|result| JsLinkManyResult {
+          added: result.added as i64,
+          existing: result.existing as i64,
+        })
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
+  /// Link two nodes by key, optionally creating missing endpoints
+  #[napi]
+  #[allow(clippy::too_many_arguments)]
+  pub fn link_by_key(
+    &self,
+    env: Env,
+    src_type: String,
+    src_key: Unknown,
+    edge_type: String,
+    dst_type: String,
+    dst_key: Unknown,
+    options: Option<JsLinkByKeyOptions>,
+  ) -> Result<JsLinkByKeyResult> {
+    let src_suffix = {
+      let spec = self.key_spec(&src_type)?;
+      key_suffix_from_js(&env, spec.as_ref(), src_key)?
+    };
+    let dst_suffix = {
+      let spec = self.key_spec(&dst_type)?;
+      key_suffix_from_js(&env, spec.as_ref(), dst_key)?
+    };
+    let create_missing = options.and_then(|o| o.create_missing).unwrap_or(false);
+    self.with_kite_mut(|ray| {
+      let (src, dst) = ray
+        .link_by_key(
+          &src_type,
+          &src_suffix,
+          &edge_type,
+          &dst_type,
+          &dst_suffix,
+          create_missing,
+        )
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+      Ok(JsLinkByKeyResult {
+        src: src as i64,
+        dst: dst as i64,
+      })
+    })
+  }
+
   /// Unlink two nodes
   #[napi]
   pub fn unlink(&self, src: i64, edge_type: String, dst: i64) -> Result<bool> {
@@ -532,6 +1558,26 @@ impl Kite {
     })
   }
 
+  /// Check many edges at once
+  ///
+  /// Each entry is `{ src, edgeType, dst }`; returns one boolean per entry,
+  /// in order, read under a single lock.
+  #[napi]
+  pub fn has_edges(&self, edges: Vec<Object>) -> Result<Vec<bool>> {
+    let mut triples = Vec::with_capacity(edges.len());
+    for edge in edges {
+      let src: i64 = edge.get_named_property("src")?;
+      let edge_type: String = edge.get_named_property("edgeType")?;
+      let dst: i64 = edge.get_named_property("dst")?;
+      triples.push((src as NodeId, edge_type, dst as NodeId));
+    }
+    self.with_kite(|ray| {
+      ray
+        .has_edges(&triples)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
   /// Get an edge property value
   #[napi(js_name = "get_edge_prop")]
   pub fn edge_prop(
@@ -549,32 +1595,34 @@ impl Kite {
     Ok(value.map(JsPropValue::from))
   }
 
-  /// Get all edge properties
+  /// Get all edge properties (null if the edge doesn't exist)
   #[napi(js_name = "get_edge_props")]
   pub fn edge_props(
     &self,
     src: i64,
     edge_type: String,
     dst: i64,
-  ) -> Result<HashMap<String, JsPropValue>> {
-    let props = self
-      .with_kite(|ray| {
-        ray
-          .edge_props(src as NodeId, &edge_type, dst as NodeId)
-          .map_err(|e| Error::from_reason(e.to_string()))
-      })?
-      .unwrap_or_default();
+  ) -> Result<Option<HashMap<String, JsPropValue>>> {
+    let props = self.with_kite(|ray| {
+      ray
+        .edge_props(src as NodeId, &edge_type, dst as NodeId)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })?;
 
-    Ok(
+    Ok(props.map(|props| {
       props
         .into_iter()
         .map(|(key, value)| (key, JsPropValue::from(value)))
-        .collect(),
-    )
+        .collect()
+    }))
   }
 
   /// Set an edge property value
+  ///
+  /// With `upsertEdge`, a missing edge is created in the same transaction.
+  /// Returns true if the edge was created.
   #[napi]
+  #[allow(clippy::too_many_arguments)]
   pub fn set_edge_prop(
     &self,
     env: Env,
@@ -583,9 +1631,22 @@ impl Kite {
     dst: i64,
     prop_name: String,
     value: Unknown,
-  ) -> Result<()> {
+    options: Option<JsSetEdgePropOptions>,
+  ) -> Result<bool> {
     let prop_value = js_value_to_prop_value(&env, value)?;
+    let upsert_edge = options.and_then(|o| o.upsert_edge).unwrap_or(false);
     self.with_kite_mut(|ray| {
+      if upsert_edge {
+        return ray
+          .upsert_edge_prop(
+            src as NodeId,
+            &edge_type,
+            dst as NodeId,
+            &prop_name,
+            prop_value,
+          )
+          .map_err(|e| Error::from_reason(e.to_string()));
+      }
       ray
         .set_edge_prop(
           src as NodeId,
@@ -594,6 +1655,7 @@ impl Kite {
           &prop_name,
           prop_value,
         )
+        .map(|()| false)
         .map_err(|e| Error::from_reason(e.to_string()))
     })
   }
@@ -670,32 +1732,151 @@ impl Kite {
       Ok(())
     })?;
 
-    Ok(KiteUpsertEdgeBuilder::new(
-      self.inner.clone(),
-      src as NodeId,
-      edge_type,
-      dst as NodeId,
-    ))
+    Ok(KiteUpsertEdgeBuilder::new(
+      self.inner.clone(),
+      src as NodeId,
+      edge_type,
+      dst as NodeId,
+    ))
+  }
+
+  /// List all nodes of a type (returns array of node objects)
+  #[napi]
+  pub fn all(
+    &self,
+    env: Env,
+    node_type: String,
+    options: Option<JsListOptions>,
+  ) -> Result<Vec<Object<'_>>> {
+    let include_archived = options
+      .as_ref()
+      .and_then(|opts| opts.include_archived)
+      .unwrap_or(false);
+    let (rename, rename_only) = rename_option(
+      options.as_ref().and_then(|opts| opts.rename.as_ref()),
+      options.as_ref().and_then(|opts| opts.rename_only),
+    )?;
+    self.with_kite(|ray| {
+      let node_refs: Vec<_> = if include_archived {
+        ray
+          .all_including_archived(&node_type)
+          .map(|nodes| nodes.collect())
+      } else {
+        ray.all(&node_type).map(|nodes| nodes.collect())
+      }
+      .map_err(|e| Error::from_reason(e.to_string()))?;
+      let mut out = Vec::new();
+      for node_ref in node_refs {
+        let (node_id, node_key, node_type) = node_ref.into_parts();
+        let props = rename_props(node_props(ray, node_id), rename, rename_only);
+        out.push(node_to_js(&env, node_id, node_key, &node_type, props)?);
+      }
+      Ok(out)
+    })
+  }
+
+  /// List all nodes carrying a label, whatever their key prefix
+  #[napi]
+  pub fn all_by_label(
+    &self,
+    env: Env,
+    label_name: String,
+    options: Option<JsListOptions>,
+  ) -> Result<Vec<Object<'_>>> {
+    let include_archived = options
+      .as_ref()
+      .and_then(|opts| opts.include_archived)
+      .unwrap_or(false);
+    let (rename, rename_only) = rename_option(
+      options.as_ref().and_then(|opts| opts.rename.as_ref()),
+      options.as_ref().and_then(|opts| opts.rename_only),
+    )?;
+    self.with_kite(|ray| {
+      let mut out = Vec::new();
+      for node_ref in ray.all_by_label(&label_name, include_archived) {
+        let (node_id, node_key, node_type) = node_ref.into_parts();
+        let props = rename_props(node_props(ray, node_id), rename, rename_only);
+        out.push(node_to_js(&env, node_id, node_key, &node_type, props)?);
+      }
+      Ok(out)
+    })
+  }
+
+  /// Get one page of the nodes of a type as `{ items, nextCursor, hasMore }`
+  ///
+  /// Only one page of node objects is built per call, so large types can be
+  /// listed without holding them all in memory. Pass `nextCursor` back to
+  /// get the following page.
+  #[napi]
+  pub fn all_paged(
+    &self,
+    env: Env,
+    node_type: String,
+    options: Option<PaginationOptions>,
+  ) -> Result<Object<'_>> {
+    let options = options.unwrap_or_default().into_rust()?;
+    let (items, next_cursor, has_more) = self.with_kite(|ray| {
+      let page = ray
+        .all_page(&node_type, options, false)
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+      let mut items = Vec::with_capacity(page.items.len());
+      for node_ref in page.items {
+        let (node_id, node_key, node_type) = node_ref.into_parts();
+        let props = node_props(ray, node_id);
+        items.push(node_to_js(&env, node_id, node_key, &node_type, props)?);
+      }
+      Ok((items, page.next_cursor, page.has_more))
+    })?;
+    let mut out = Object::new(&env)?;
+    out.set_named_property("items", items)?;
+    out.set_named_property("nextCursor", next_cursor)?;
+    out.set_named_property("hasMore", has_more)?;
+    Ok(out)
+  }
+
+  /// Find node IDs of a type whose ordered prop lies in `[min, max]`
+  #[napi]
+  pub fn find_by_prop_range(
+    &self,
+    env: Env,
+    node_type: String,
+    prop_name: String,
+    min: Unknown,
+    max: Unknown,
+  ) -> Result<Vec<i64>> {
+    let min = js_value_to_prop_value(&env, min)?;
+    let max = js_value_to_prop_value(&env, max)?;
+    self.with_kite(|ray| {
+      let nodes = ray
+        .find_by_prop_range(&node_type, &prop_name, &min, &max)
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+      Ok(nodes.into_iter().map(|id| id as i64).collect())
+    })
   }
 
-  /// List all nodes of a type (returns array of node objects)
+  /// Find node IDs of a type whose prop matches a filter
+  ///
+  /// The filter is `{ eq }`, `{ min, max }` (an ordered prop, inclusive) or
+  /// `{ jsonPath, eq }` for a value inside a JSON prop. Paths are dotted
+  /// (`author.name`, `tags.0`), optionally prefixed by `$.`.
   #[napi]
-  pub fn all(&self, env: Env, node_type: String) -> Result<Vec<Object<'_>>> {
+  pub fn find_where(
+    &self,
+    env: Env,
+    node_type: String,
+    prop_name: String,
+    filter: Object,
+  ) -> Result<Vec<i64>> {
+    let filter = js_prop_filter(&env, &filter)?;
     self.with_kite(|ray| {
       let nodes = ray
-        .all(&node_type)
+        .find_where(&node_type, &prop_name, &filter)
         .map_err(|e| Error::from_reason(e.to_string()))?;
-      let mut out = Vec::new();
-      for node_ref in nodes {
-        let (node_id, node_key, node_type) = node_ref.into_parts();
-        let props = node_props(ray, node_id);
-        out.push(node_to_js(&env, node_id, node_key, &node_type, props)?);
-      }
-      Ok(out)
+      Ok(nodes.into_iter().map(|id| id as i64).collect())
     })
   }
 
-  /// Count nodes (optionally by type)
+  /// Count nodes (optionally by type), archived ones excluded
   #[napi]
   pub fn count_nodes(&self, node_type: Option<String>) -> Result<i64> {
     self.with_kite(|ray| match node_type {
@@ -707,6 +1888,43 @@ impl Kite {
     })
   }
 
+  /// Count nodes carrying a label, whatever their key prefix
+  #[napi]
+  pub fn count_nodes_by_label(&self, label_name: String) -> Result<i64> {
+    self.with_kite(|ray| Ok(ray.count_nodes_by_label(&label_name) as i64))
+  }
+
+  /// Count the distinct values a prop takes across nodes of a type
+  ///
+  /// With `approximate`, uses a HyperLogLog estimate (about 1% error) that
+  /// doesn't hold every value in memory. Null, vector and JSON values aren't
+  /// counted.
+  #[napi]
+  pub fn distinct_count(
+    &self,
+    node_type: String,
+    prop_name: String,
+    approximate: Option<bool>,
+  ) -> Result<i64> {
+    self.with_kite(|ray| {
+      ray
+        .distinct_count(&node_type, &prop_name, approximate.unwrap_or(false))
+        .map(|v| v as i64)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
+  /// Last key number allocated for an autoincrement node type
+  #[napi]
+  pub fn key_counter(&self, node_type: String) -> Result<i64> {
+    self.with_kite(|ray| {
+      ray
+        .key_counter(&node_type)
+        .map(|v| v as i64)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
   /// Count edges (optionally by type)
   #[napi]
   pub fn count_edges(&self, edge_type: Option<String>) -> Result<i64> {
@@ -721,13 +1939,28 @@ impl Kite {
 
   /// List all edges (optionally by type)
   #[napi]
-  pub fn all_edges(&self, edge_type: Option<String>) -> Result<Vec<JsFullEdge>> {
+  pub fn all_edges(
+    &self,
+    edge_type: Option<String>,
+    options: Option<JsListOptions>,
+  ) -> Result<Vec<JsFullEdge>> {
+    let include_archived = options
+      .and_then(|opts| opts.include_archived)
+      .unwrap_or(false);
     self.with_kite(|ray| {
-      let edges = ray
-        .all_edges(edge_type.as_deref())
-        .map_err(|e| Error::from_reason(e.to_string()))?;
+      let edges: Vec<_> = if include_archived {
+        ray
+          .all_edges_including_archived(edge_type.as_deref())
+          .map(|edges| edges.collect())
+      } else {
+        ray
+          .all_edges(edge_type.as_deref())
+          .map(|edges| edges.collect())
+      }
+      .map_err(|e| Error::from_reason(e.to_string()))?;
       Ok(
         edges
+          .into_iter()
           .map(|edge| JsFullEdge {
             src: edge.src as i64,
             etype: edge.etype,
@@ -764,6 +1997,73 @@ impl Kite {
     })
   }
 
+  /// Open a cursor that walks the nodes reachable from `source` one depth
+  /// level at a time via `nextLevel()`
+  #[napi]
+  pub fn reachable_cursor(
+    &self,
+    source: i64,
+    edge_type: Option<String>,
+  ) -> Result<KiteReachableCursor> {
+    let cursor = self.with_kite(|ray| {
+      ray
+        .reachable_cursor(source as NodeId, edge_type.as_deref())
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })?;
+    Ok(KiteReachableCursor::new(self.inner.clone(), cursor))
+  }
+
+  /// Get all nodes reachable within a maximum depth, skipping excluded nodes
+  /// (treated as deleted) and excluded edges
+  #[napi]
+  pub fn reachable_from_excluding(
+    &self,
+    source: i64,
+    max_depth: i64,
+    edge_type: Option<String>,
+    excluded_nodes: Option<Vec<i64>>,
+    excluded_edges: Option<Vec<JsFullEdge>>,
+  ) -> Result<Vec<i64>> {
+    let excluded_nodes: HashSet<NodeId> = excluded_nodes
+      .unwrap_or_default()
+      .into_iter()
+      .map(|id| id as NodeId)
+      .collect();
+    let excluded_edges: HashSet<Edge> = excluded_edges
+      .unwrap_or_default()
+      .into_iter()
+      .map(|edge| Edge {
+        src: edge.src as NodeId,
+        etype: edge.etype,
+        dst: edge.dst as NodeId,
+      })
+      .collect();
+    self.with_kite(|ray| {
+      let nodes = ray
+        .reachable_from_excluding(
+          source as NodeId,
+          max_depth as usize,
+          edge_type.as_deref(),
+          &excluded_nodes,
+          &excluded_edges,
+        )
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+      Ok(nodes.into_iter().map(|id| id as i64).collect())
+    })
+  }
+
+  /// Get the declared unit of each prop of a node type
+  ///
+  /// Props declared without a unit are left out.
+  #[napi]
+  pub fn prop_units(&self, node_type: String) -> Result<HashMap<String, String>> {
+    self.with_kite(|ray| {
+      ray
+        .prop_units(&node_type)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
   /// Get all node type names
   #[napi]
   pub fn node_types(&self) -> Result<Vec<String>> {
@@ -808,6 +2108,7 @@ impl Kite {
         delta_edges_deleted: s.delta_edges_deleted as i64,
         wal_segment: s.wal_segment as i64,
         wal_bytes: s.wal_bytes as i64,
+        fragmentation: s.fragmentation,
         recommend_compact: s.recommend_compact,
         mvcc_stats: s.mvcc_stats.map(|stats| MvccStats {
           active_transactions: stats.active_transactions as i64,
@@ -817,85 +2118,232 @@ impl Kite {
           last_gc_time: stats.last_gc_time as i64,
           committed_writes_size: stats.committed_writes_size as i64,
           committed_writes_pruned: stats.committed_writes_pruned as i64,
+          version_chains: stats.version_chains as i64,
+          versions_retained: stats.versions_retained as i64,
+          chain_depth_max: stats.chain_depth_max as i64,
+          chain_depth_p50: stats.chain_depth_p50 as i64,
+          chain_depth_p99: stats.chain_depth_p99 as i64,
         }),
       })
     })
   }
 
+  /// Count and size WAL records by type since the last checkpoint
+  ///
+  /// Sorted by bytes, largest first.
+  #[napi]
+  pub fn wal_record_breakdown(&self) -> Result<Vec<JsWalRecordStats>> {
+    self.with_kite(|ray| {
+      ray
+        .wal_record_breakdown()
+        .map(|breakdown| breakdown.into_iter().map(Into::into).collect())
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
+  /// Active secondary indexes: unique and ordered props per node type,
+  /// then one vector store per property holding embeddings
+  #[napi]
+  pub fn list_indexes(&self) -> Result<Vec<JsIndexInfo>> {
+    self.with_kite(|ray| {
+      ray
+        .list_indexes()
+        .map(|indexes| indexes.into_iter().map(Into::into).collect())
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })
+  }
+
+  /// Recoverable issues noticed while opening (e.g. WAL crash recovery)
+  #[napi]
+  pub fn open_warnings(&self) -> Result<Vec<String>> {
+    self.with_kite(|ray| Ok(ray.open_warnings().to_vec()))
+  }
+
   /// Get a human-readable description of the database
   #[napi]
   pub fn describe(&self) -> Result<String> {
     self.with_kite(|ray| Ok(ray.describe()))
   }
 
+  /// Compare the live schema against `target` (only `nodes` and `edges` are read)
+  ///
+  /// Read-only; returns the type and prop changes a migration would apply.
+  #[napi]
+  pub fn schema_diff(&self, target: JsKiteOptions) -> Result<Vec<JsSchemaChange>> {
+    let mut target_opts = KiteOptions::new();
+    for node in &target.nodes {
+      target_opts.nodes.push(node_spec_to_def(node)?.0);
+    }
+    for edge in &target.edges {
+      target_opts.edges.push(edge_spec_to_def(edge)?);
+    }
+    self.with_kite(|ray| {
+      Ok(
+        ray
+          .schema_diff(&target_opts)
+          .into_iter()
+          .map(schema_change_to_js)
+          .collect(),
+      )
+    })
+  }
+
   /// Check database integrity
+  ///
+  /// Vector dimension and NaN/Inf problems are warnings unless `strict` is
+  /// true, in which case they are errors.
   #[napi]
-  pub fn check(&self) -> Result<CheckResult> {
+  pub fn check(&self, strict: Option<bool>) -> Result<CheckResult> {
     self.with_kite(|ray| {
-      let result = ray.check().map_err(|e| Error::from_reason(e.to_string()))?;
+      let result = if strict.unwrap_or(false) {
+        ray.check_strict()
+      } else {
+        ray.check()
+      }
+      .map_err(|e| Error::from_reason(e.to_string()))?;
       Ok(CheckResult::from(result))
     })
   }
 
+  /// Check database integrity and repair what can be fixed
+  ///
+  /// Dangling edges are only removed when `allowDestructive` is true.
+  #[napi]
+  pub fn check_and_repair(&self, allow_destructive: Option<bool>) -> Result<RepairResult> {
+    self.with_kite(|ray| {
+      let result = ray
+        .check_and_repair(allow_destructive.unwrap_or(false))
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+      Ok(RepairResult::from(result))
+    })
+  }
+
   /// Begin a transaction
+  ///
+  /// If this thread already has one, joins it instead: each nested begin
+  /// needs its own commit or rollback, and only the outermost one ends it.
   #[napi]
-  pub fn begin(&self, read_only: Option<bool>) -> Result<i64> {
+  pub fn begin(&self, read_only: Option<bool>) -> Result<i64, ErrorCode> {
     let read_only = read_only.unwrap_or(false);
     let guard = self.inner.read();
     let ray = guard
       .as_ref()
-      .ok_or_else(|| Error::from_reason("Kite is closed"))?;
+      .ok_or_else(|| closed_error("Kite is closed"))?;
 
     ray
       .raw()
       .begin(read_only)
       .map(|txid| txid as i64)
-      .map_err(|e| Error::from_reason(format!("Failed to begin transaction: {e}")))
+      .map_err(|e| kite_error_with("Failed to begin transaction", e))
+  }
+
+  /// Begin a read-only transaction pinned to a past MVCC generation
+  ///
+  /// Fails if the generation's history is no longer retained (past
+  /// generations are only kept with `mvccRetainHistory`).
+  #[napi]
+  pub fn begin_read_at(&self, generation: i64) -> Result<i64, ErrorCode> {
+    let generation = u64::try_from(generation).map_err(|_| {
+      Error::new(
+        ErrorCode::InvalidQuery,
+        "generation must be non-negative".to_string(),
+      )
+    })?;
+    let guard = self.inner.read();
+    let ray = guard
+      .as_ref()
+      .ok_or_else(|| closed_error("Kite is closed"))?;
+
+    ray
+      .raw()
+      .begin_read_at(generation)
+      .map(|txid| txid as i64)
+      .map_err(|e| kite_error_with("Failed to begin transaction", e))
+  }
+
+  /// Current MVCC generation (null when MVCC is disabled)
+  #[napi]
+  pub fn current_generation(&self) -> Result<Option<i64>> {
+    self.with_kite(|ray| Ok(ray.raw().current_generation().map(|g| g as i64)))
   }
 
   /// Begin a bulk-load transaction (fast path, MVCC disabled)
   #[napi]
-  pub fn begin_bulk(&self) -> Result<i64> {
+  pub fn begin_bulk(&self) -> Result<i64, ErrorCode> {
     let guard = self.inner.read();
     let ray = guard
       .as_ref()
-      .ok_or_else(|| Error::from_reason("Kite is closed"))?;
+      .ok_or_else(|| closed_error("Kite is closed"))?;
 
     ray
       .raw()
       .begin_bulk()
       .map(|txid| txid as i64)
-      .map_err(|e| Error::from_reason(format!("Failed to begin bulk transaction: {e}")))
+      .map_err(|e| kite_error_with("Failed to begin bulk transaction", e))
   }
 
   /// Commit the current transaction
+  ///
+  /// A commit that lost an MVCC conflict throws with `code` set to
+  /// `KITE_CONFLICT`; the transaction is gone and can be retried.
   #[napi]
-  pub fn commit(&self) -> Result<()> {
-    self.with_kite_mut(|ray| {
-      ray
-        .raw()
-        .commit()
-        .map_err(|e| Error::from_reason(format!("Failed to commit: {e}")))
+  pub fn commit(&self) -> Result<(), ErrorCode> {
+    self.with_raw_coded(|db| {
+      db.commit()
+        .map_err(|e| kite_error_with("Failed to commit", e))
+    })
+  }
+
+  /// Commit the current transaction and summarize what it changed
+  ///
+  /// Closing a nested transaction level commits nothing and returns zeros.
+  #[napi]
+  pub fn commit_stats(&self) -> Result<JsCommitStats, ErrorCode> {
+    self.with_raw_coded(|db| {
+      db.commit_stats()
+        .map(JsCommitStats::from)
+        .map_err(|e| kite_error_with("Failed to commit", e))
     })
   }
 
   /// Rollback the current transaction
   #[napi]
-  pub fn rollback(&self) -> Result<()> {
-    self.with_kite_mut(|ray| {
-      ray
-        .raw()
-        .rollback()
-        .map_err(|e| Error::from_reason(format!("Failed to rollback: {e}")))
+  pub fn rollback(&self) -> Result<(), ErrorCode> {
+    self.with_raw_coded(|db| {
+      db.rollback()
+        .map_err(|e| kite_error_with("Failed to rollback", e))
     })
   }
 
+  /// Mark a point in the current transaction that `rollbackTo` can return to
+  ///
+  /// Names may repeat; `rollbackTo` uses the most recent one.
+  #[napi]
+  pub fn savepoint(&self, name: String) -> Result<(), ErrorCode> {
+    self.with_raw_coded(|db| db.savepoint(&name).map_err(kite_error))
+  }
+
+  /// Undo the current transaction's changes since savepoint `name`
+  ///
+  /// The transaction stays open and commit still writes everything done
+  /// before the savepoint.
+  #[napi]
+  pub fn rollback_to(&self, name: String) -> Result<(), ErrorCode> {
+    self.with_raw_coded(|db| db.rollback_to_savepoint(&name).map_err(kite_error))
+  }
+
   /// Check if there's an active transaction
   #[napi]
   pub fn has_transaction(&self) -> Result<bool> {
     self.with_kite(|ray| Ok(ray.raw().has_transaction()))
   }
 
+  /// Id, mode and snapshot of the active transaction (null if none)
+  #[napi]
+  pub fn transaction_info(&self) -> Result<Option<JsTransactionInfo>> {
+    self.with_kite(|ray| Ok(ray.raw().transaction_info().map(JsTransactionInfo::from)))
+  }
+
   /// Primary replication status when role=primary, else null.
   #[napi]
   pub fn primary_replication_status(&self) -> Result<Option<JsPrimaryReplicationStatus>> {
@@ -957,127 +2405,58 @@ impl Kite {
     })
   }
 
-  /// Execute a batch of operations atomically
+  /// Discard WAL records already in the snapshot, e.g. after a backup.
+  /// Fails if there are uncheckpointed changes. Returns the bytes discarded.
   #[napi]
-  pub fn batch(&self, env: Env, ops: Vec<Object>) -> Result<Vec<Object<'_>>> {
-    let mut rust_ops = Vec::with_capacity(ops.len());
-
-    for op in ops {
-      let op_name: Option<String> = op.get_named_property("op").ok();
-      let op_name = match op_name {
-        Some(name) => name,
-        None => op.get_named_property("type")?,
-      };
+  pub fn truncate_wal_to_snapshot(&self) -> Result<i64> {
+    self.with_kite(|ray| {
+      ray
+        .raw()
+        .truncate_wal_to_snapshot()
+        .map(|bytes| bytes as i64)
+        .map_err(|e| Error::from_reason(format!("Failed to truncate WAL: {e}")))
+    })
+  }
 
-      match op_name.as_str() {
-        "createNode" => {
-          let node_type: String = op.get_named_property("nodeType")?;
-          let key: Unknown = op.get_named_property("key")?;
-          let props: Option<Object> = op.get_named_property("props")?;
-          let key_suffix = {
-            let spec = self.key_spec(&node_type)?;
-            key_suffix_from_js(&env, spec.as_ref(), key)?
-          };
-          let props_map = js_props_to_map(&env, props)?;
-          rust_ops.push(BatchOp::CreateNode {
-            node_type,
-            key_suffix,
-            props: props_map,
-          });
-        }
-        "deleteNode" => {
-          let node_id: i64 = op.get_named_property("nodeId")?;
-          rust_ops.push(BatchOp::DeleteNode {
-            node_id: node_id as NodeId,
-          });
-        }
-        "link" => {
-          let src: i64 = op.get_named_property("src")?;
-          let dst: i64 = op.get_named_property("dst")?;
-          let edge_type: String = op.get_named_property("edgeType")?;
-          rust_ops.push(BatchOp::Link {
-            src: src as NodeId,
-            edge_type,
-            dst: dst as NodeId,
-          });
-        }
-        "linkWithProps" => {
-          let src: i64 = op.get_named_property("src")?;
-          let dst: i64 = op.get_named_property("dst")?;
-          let edge_type: String = op.get_named_property("edgeType")?;
-          let props: Option<Object> = op.get_named_property("props")?;
-          let props_map = js_props_to_map(&env, props)?;
-          rust_ops.push(BatchOp::LinkWithProps {
-            src: src as NodeId,
-            edge_type,
-            dst: dst as NodeId,
-            props: props_map,
-          });
-        }
-        "unlink" => {
-          let src: i64 = op.get_named_property("src")?;
-          let dst: i64 = op.get_named_property("dst")?;
-          let edge_type: String = op.get_named_property("edgeType")?;
-          rust_ops.push(BatchOp::Unlink {
-            src: src as NodeId,
-            edge_type,
-            dst: dst as NodeId,
-          });
-        }
-        "setProp" => {
-          let node_id: i64 = op.get_named_property("nodeId")?;
-          let prop_name: String = op.get_named_property("propName")?;
-          let value: Unknown = op.get_named_property("value")?;
-          let prop_value = js_value_to_prop_value(&env, value)?;
-          rust_ops.push(BatchOp::SetProp {
-            node_id: node_id as NodeId,
-            prop_name,
-            value: prop_value,
-          });
-        }
-        "setEdgeProp" => {
-          let src: i64 = op.get_named_property("src")?;
-          let dst: i64 = op.get_named_property("dst")?;
-          let edge_type: String = op.get_named_property("edgeType")?;
-          let prop_name: String = op.get_named_property("propName")?;
-          let value: Unknown = op.get_named_property("value")?;
-          let prop_value = js_value_to_prop_value(&env, value)?;
-          rust_ops.push(BatchOp::SetEdgeProp {
-            src: src as NodeId,
-            edge_type,
-            dst: dst as NodeId,
-            prop_name,
-            value: prop_value,
-          });
-        }
-        "setEdgeProps" => {
-          let src: i64 = op.get_named_property("src")?;
-          let dst: i64 = op.get_named_property("dst")?;
-          let edge_type: String = op.get_named_property("edgeType")?;
-          let props: Option<Object> = op.get_named_property("props")?;
-          let props_map = js_props_to_map(&env, props)?;
-          rust_ops.push(BatchOp::SetEdgeProps {
-            src: src as NodeId,
-            edge_type,
-            dst: dst as NodeId,
-            props: props_map,
-          });
-        }
-        "delProp" => {
-          let node_id: i64 = op.get_named_property("nodeId")?;
-          let prop_name: String = op.get_named_property("propName")?;
-          rust_ops.push(BatchOp::DelProp {
-            node_id: node_id as NodeId,
-            prop_name,
-          });
-        }
-        other => {
-          return Err(Error::from_reason(format!("Unknown batch op: {other}")));
-        }
-      }
+  /// Execute a batch of operations
+  ///
+  /// Atomic by default. With `bestEffort: true` each op is applied in its own
+  /// transaction and the results carry `ok` plus `error` for failed ops, so a
+  /// few bad rows don't abort the rest.
+  ///
+  /// A `createAndLink` op creates a node and links it to existing nodes given
+  /// as `links: [{ edgeType, nodeId, incoming? }]` (`incoming` points the
+  /// edge at the new node).
+  ///
+  /// Each result has a `type` and an `affected` count (0 when the op was a
+  /// no-op), plus `id` for created nodes, `created` for links, `changed`
+  /// for prop sets and `deleted` for deletes and unlinks.
+  #[napi]
+  pub fn batch(
+    &self,
+    env: Env,
+    ops: Vec<Object>,
+    options: Option<JsBatchOptions>,
+  ) -> Result<Vec<Object<'_>>> {
+    if options
+      .and_then(|options| options.best_effort)
+      .unwrap_or(false)
+    {
+      return self.batch_best_effort(&env, ops);
     }
 
-    let results = self.with_kite_mut(|ray| execute_batch_ops(ray, rust_ops))?;
+    let rust_ops = ops
+      .into_iter()
+      .map(|op| self.parse_batch_op(&env, op))
+      .collect::<Result<Vec<_>>>()?;
+    let results = self.with_kite_mut(|ray| {
+      ray
+        .with_write_tx(|ray| {
+          let ops = with_next_keys(ray, rust_ops)?;
+          ray.batch(ops)
+        })
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })?;
 
     let mut out = Vec::with_capacity(results.len());
     for result in results {
@@ -1086,6 +2465,95 @@ impl Kite {
     Ok(out)
   }
 
+  /// Execute a batch atomically, replaying it when the commit loses an
+  /// MVCC conflict
+  ///
+  /// Makes up to `maxAttempts` attempts (default: 3). If every attempt
+  /// conflicts, the error thrown has `code` `KITE_CONFLICT`; other errors
+  /// are thrown right away.
+  #[napi]
+  pub fn batch_retry(
+    &self,
+    env: Env,
+    ops: Vec<Object>,
+    max_attempts: Option<i64>,
+  ) -> Result<Vec<Object<'_>>, ErrorCode> {
+    let max_attempts = max_attempts.unwrap_or(3);
+    if max_attempts <= 0 {
+      return Err(Error::new(
+        ErrorCode::InvalidQuery,
+        "maxAttempts must be positive".to_string(),
+      ));
+    }
+    let rust_ops = ops
+      .into_iter()
+      .map(|op| self.parse_batch_op(&env, op))
+      .collect::<Result<Vec<_>>>()
+      .map_err(napi_error)?;
+    let results = {
+      let mut guard = self.inner.write();
+      let ray = guard
+        .as_mut()
+        .ok_or_else(|| closed_error("Kite is closed"))?;
+      // Each attempt allocates its keys in its own transaction
+      let mut attempts = 1;
+      loop {
+        let attempt = ray.with_write_tx(|ray| {
+          let ops = with_next_keys(ray, rust_ops.clone())?;
+          ray.batch(ops)
+        });
+        match attempt {
+          Err(e) if e.is_conflict() && attempts < max_attempts => attempts += 1,
+          result => break result.map_err(kite_error)?,
+        }
+      }
+    };
+
+    let mut out = Vec::with_capacity(results.len());
+    for result in results {
+      out.push(batch_result_to_js(&env, result).map_err(napi_error)?);
+    }
+    Ok(out)
+  }
+
+  /// Check batch ops against the schema without applying them
+  ///
+  /// Returns `{ index, error }` for every op that `batch` would reject up
+  /// front: malformed ops or keys, unknown node or edge types, invalid or
+  /// missing required props, forbidden self-loops and deletes of unknown
+  /// props. An empty array means the batch passed validation.
+  #[napi]
+  pub fn batch_validate(&self, env: Env, ops: Vec<Object>) -> Result<Vec<Object<'_>>> {
+    let mut errors = Vec::new();
+    let mut rust_ops = Vec::with_capacity(ops.len());
+    let mut indexes = Vec::with_capacity(ops.len());
+    for (index, op) in ops.into_iter().enumerate() {
+      match self.parse_batch_op(&env, op) {
+        Ok((op, _)) => {
+          rust_ops.push(op);
+          indexes.push(index);
+        }
+        Err(e) => errors.push((index, e.reason.clone())),
+      }
+    }
+    self.with_kite(|ray| {
+      for (i, e) in ray.batch_validate(&rust_ops) {
+        errors.push((indexes[i], e.to_string()));
+      }
+      Ok(())
+    })?;
+    errors.sort_by_key(|(index, _)| *index);
+
+    let mut out = Vec::with_capacity(errors.len());
+    for (index, error) in errors {
+      let mut obj = Object::new(&env)?;
+      obj.set_named_property("index", index as u32)?;
+      obj.set_named_property("error", error)?;
+      out.push(obj);
+    }
+    Ok(out)
+  }
+
   /// Begin a traversal from a node ID
   #[napi]
   pub fn from(&self, node_id: i64) -> Result<KiteTraversal> {
@@ -1097,6 +2565,9 @@ impl Kite {
       selected_props: None,
       where_edge: None,
       where_node: None,
+      node_prefixes: None,
+      include_archived: false,
+      as_of: None,
     })
   }
 
@@ -1111,9 +2582,32 @@ impl Kite {
       selected_props: None,
       where_edge: None,
       where_node: None,
+      node_prefixes: None,
+      include_archived: false,
+      as_of: None,
     })
   }
 
+  /// Start an exploration session rooted at a node
+  ///
+  /// The session's `expand` only returns nodes it hasn't returned before.
+  #[napi]
+  pub fn exploration(&self, root: i64) -> Result<KiteExploration> {
+    let session = self.with_kite(|ray| {
+      ray
+        .exploration(root as NodeId)
+        .map_err(|e| Error::from_reason(e.to_string()))
+    })?;
+    Ok(KiteExploration::new(self.inner.clone(), session))
+  }
+
+  /// Open a cursor that walks all node IDs in batches via `next(batchSize)`
+  #[napi]
+  pub fn node_cursor(&self) -> Result<KiteNodeCursor> {
+    let cursor = self.with_kite(|ray| Ok(ray.raw().node_cursor()))?;
+    Ok(KiteNodeCursor::new(self.inner.clone(), cursor))
+  }
+
   /// Begin a path finding query
   #[napi]
   pub fn path(&self, source: i64, target: i64) -> Result<KitePath> {
@@ -1165,6 +2659,7 @@ impl napi::Task for OpenKiteTask {
     kite_opts.mvcc = self.options.mvcc.unwrap_or(false);
     kite_opts.mvcc_gc_interval_ms = self.options.mvcc_gc_interval_ms.map(|v| v as u64);
     kite_opts.mvcc_retention_ms = self.options.mvcc_retention_ms.map(|v| v as u64);
+    kite_opts.mvcc_retain_history = self.options.mvcc_retain_history.unwrap_or(false);
     kite_opts.mvcc_max_chain_depth = self.options.mvcc_max_chain_depth.map(|v| v as usize);
     if let Some(mode) = self.options.sync_mode.take() {
       kite_opts.sync_mode = mode.into();
@@ -1182,6 +2677,9 @@ impl napi::Task for OpenKiteTask {
         kite_opts.wal_size = Some((wal_size_mb as usize).saturating_mul(1024 * 1024));
       }
     }
+    if let Some(path) = self.options.wal_path.take() {
+      kite_opts.wal_path = Some(path.into());
+    }
     if let Some(threshold) = self.options.checkpoint_threshold {
       kite_opts.checkpoint_threshold = Some(threshold.clamp(0.0, 1.0));
     }
@@ -1217,28 +2715,13 @@ impl napi::Task for OpenKiteTask {
     }
 
     for node in &self.options.nodes {
-      let key_spec = Arc::new(parse_key_spec(&node.name, node.key.clone())?);
-      let prefix = key_spec.prefix().to_string();
-
-      let mut node_def = NodeDef::new(&node.name, &prefix);
-      if let Some(props) = node.props.as_ref() {
-        for (prop_name, prop_spec) in props {
-          node_def = node_def.prop(prop_spec_to_def(prop_name, prop_spec)?);
-        }
-      }
-
-      node_specs.insert(node.name.clone(), Arc::clone(&key_spec));
+      let (node_def, key_spec) = node_spec_to_def(node)?;
+      node_specs.insert(node.name.clone(), key_spec);
       kite_opts.nodes.push(node_def);
     }
 
     for edge in &self.options.edges {
-      let mut edge_def = EdgeDef::new(&edge.name);
-      if let Some(props) = edge.props.as_ref() {
-        for (prop_name, prop_spec) in props {
-          edge_def = edge_def.prop(prop_spec_to_def(prop_name, prop_spec)?);
-        }
-      }
-      kite_opts.edges.push(edge_def);
+      kite_opts.edges.push(edge_spec_to_def(edge)?);
     }
 
     let ray =
@@ -1256,6 +2739,7 @@ impl napi::Task for OpenKiteTask {
     Ok(Kite {
       inner: Arc::new(RwLock::new(Some(ray))),
       node_specs: Arc::new(node_specs),
+      virtual_props: RwLock::new(HashMap::new()),
     })
   }
 }
@@ -0,0 +1,112 @@
+//! Pull-based node cursors
+
+use napi::bindgen_prelude::*;
+use napi_derive::napi;
+use parking_lot::RwLock;
+use std::sync::Arc;
+
+use crate::api::kite::Kite as RustKite;
+use crate::api::traversal::ReachableCursor;
+use crate::core::single_file::NodeCursor;
+
+// =============================================================================
+// Node Cursor
+// =============================================================================
+
+/// Cursor that walks every node ID in ascending order, one batch per call
+///
+/// Only the requested batch is materialized, so huge node sets can be walked
+/// with bounded memory at the caller's pace. Writes and checkpoints between
+/// calls are fine: later batches skip deleted nodes and include new ones.
+#[napi]
+pub struct KiteNodeCursor {
+  ray: Arc<RwLock<Option<RustKite>>>,
+  cursor: NodeCursor,
+}
+
+impl KiteNodeCursor {
+  pub fn new(ray: Arc<RwLock<Option<RustKite>>>, cursor: NodeCursor) -> Self {
+    Self { ray, cursor }
+  }
+}
+
+#[napi]
+impl KiteNodeCursor {
+  /// Fetch up to `batchSize` more node IDs; an empty array means caught up
+  ///
+  /// Archived nodes are skipped, as in `all` and `countNodes`. Throws if
+  /// node IDs were reset under the cursor, since its position no longer
+  /// refers to the same nodes.
+  #[napi]
+  pub fn next(&mut self, batch_size: i64) -> Result<Vec<i64>> {
+    let guard = self.ray.read();
+    let ray = guard
+      .as_ref()
+      .ok_or_else(|| Error::from_reason("Kite is closed"))?;
+    // A batch of only archived nodes must not read as caught up
+    loop {
+      let batch = self
+        .cursor
+        .next_batch(ray.raw(), batch_size.max(0) as usize)
+        .map_err(|e| Error::from_reason(e.to_string()))?;
+      if batch.is_empty() {
+        return Ok(Vec::new());
+      }
+      let live: Vec<i64> = batch
+        .into_iter()
+        .filter(|&id| !ray.is_archived(id))
+        .map(|id| id as i64)
+        .collect();
+      if !live.is_empty() {
+        return Ok(live);
+      }
+    }
+  }
+}
+
+// =============================================================================
+// Reachability Cursor
+// =============================================================================
+
+/// Cursor that walks the nodes reachable from a source one BFS level per call
+///
+/// The visited set and frontier stay on the native side between calls, so a
+/// UI can render each level as it arrives and stop once it has enough.
+#[napi]
+pub struct KiteReachableCursor {
+  ray: Arc<RwLock<Option<RustKite>>>,
+  cursor: ReachableCursor,
+}
+
+impl KiteReachableCursor {
+  pub fn new(ray: Arc<RwLock<Option<RustKite>>>, cursor: ReachableCursor) -> Self {
+    Self { ray, cursor }
+  }
+}
+
+#[napi]
+impl KiteReachableCursor {
+  /// IDs of the nodes first reached at the next depth; an empty array means
+  /// everything reachable has been returned
+  #[napi]
+  pub fn next_level(&mut self) -> Result<Vec<i64>> {
+    let guard = self.ray.read();
+    let ray = guard
+      .as_ref()
+      .ok_or_else(|| Error::from_reason("Kite is closed"))?;
+    let level = ray.next_reachable_level(&mut self.cursor);
+    Ok(level.into_iter().map(|id| id as i64).collect())
+  }
+
+  /// Depth of the last non-empty level returned (0 before the first)
+  #[napi]
+  pub fn depth(&self) -> i64 {
+    self.cursor.depth() as i64
+  }
+
+  /// Whether every reachable node has been returned
+  #[napi]
+  pub fn is_done(&self) -> bool {
+    self.cursor.is_done()
+  }
+}
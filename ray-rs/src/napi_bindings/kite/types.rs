@@ -15,19 +15,29 @@ use super::super::database::{JsPropValue, JsReplicationRole, JsSyncMode};
 /// Property specification for a node or edge type
 #[napi(object)]
 pub struct JsPropSpec {
-  /// Property type: "string", "int", "float", "bool", "vector", "any"
+  /// Property type: "string", "int", "float", "bool", "enum", "vector", "json", "any"
   pub r#type: String,
   /// Whether the property is optional (default: false)
   pub optional: Option<bool>,
   /// Default value if not provided
   pub r#default: Option<JsPropValue>,
+  /// Allowed values for "enum" props
+  pub allowed: Option<Vec<String>>,
+  /// Reject values already held by another node of the same type (default: false)
+  pub unique: Option<bool>,
+  /// Keep a sorted index for `findByPropRange` (default: false)
+  pub ordered: Option<bool>,
+  /// Unit the values are measured in, e.g. "meters"
+  pub unit: Option<String>,
+  /// L2-normalize every written vector ("vector" node props only, default: false)
+  pub normalize: Option<bool>,
 }
 
 /// Key specification for a node type
 #[napi(object)]
 #[derive(Clone)]
 pub struct JsKeySpec {
-  /// Key generation strategy: "prefix", "template", "parts"
+  /// Key generation strategy: "prefix", "template", "parts", "autoincrement"
   pub kind: String,
   /// Key prefix (e.g., "User:")
   pub prefix: Option<String>,
@@ -57,6 +67,78 @@ pub struct JsEdgeSpec {
   pub name: String,
   /// Property definitions
   pub props: Option<HashMap<String, JsPropSpec>>,
+  /// Reject edges from a node to itself (default: false)
+  pub no_self_loops: Option<bool>,
+}
+
+/// Options for `batch`
+#[napi(object)]
+pub struct JsBatchOptions {
+  /// Apply each op independently and report per-op `ok`/`error` instead of
+  /// failing the whole batch (default: false)
+  pub best_effort: Option<bool>,
+}
+
+/// Options for reading a node
+#[napi(object)]
+pub struct JsGetOptions {
+  /// Merge props computed by `defineVirtualProp` functions; only `get` and
+  /// `getById` take this option (default: false)
+  pub include_virtual: Option<bool>,
+  /// Return the node even if it is archived (default: false)
+  pub include_archived: Option<bool>,
+  /// Return only `id`/`key`/`type` and load props on first access; the
+  /// database must stay open while the node is used (default: false)
+  pub lazy: Option<bool>,
+  /// Attach `$units` mapping prop names to their declared units (default: false)
+  pub include_units: Option<bool>,
+  /// Rename props in the result, as `{ internalName: externalName }`; can't
+  /// rename onto `id`, `key`, `type`, `$units`, `$out` or `$in`, or be
+  /// combined with `lazy`
+  pub rename: Option<HashMap<String, String>>,
+  /// Return only the props named in `rename` (default: false)
+  pub rename_only: Option<bool>,
+  /// Attach `$out` and `$in`, mapping each of these edge types to the ids
+  /// of the node's neighbors over it, read under the same lock as the node
+  pub edges: Option<Vec<String>>,
+}
+
+/// Options for `getByIds`
+#[napi(object)]
+pub struct JsGetByIdsOptions {
+  /// Return `null` in place of ids with no node, so results line up with the
+  /// input (default: false, missing ids are dropped)
+  pub include_missing: Option<bool>,
+  /// Return archived nodes instead of treating them as missing (default:
+  /// false)
+  pub include_archived: Option<bool>,
+}
+
+/// Options for listing nodes or edges
+#[napi(object)]
+pub struct JsListOptions {
+  /// Include archived nodes, and edges touching them (default: false)
+  pub include_archived: Option<bool>,
+  /// Rename node props in the result, as `{ internalName: externalName }`;
+  /// can't rename onto `id`, `key` or `type`
+  pub rename: Option<HashMap<String, String>>,
+  /// Return only the node props named in `rename` (default: false)
+  pub rename_only: Option<bool>,
+}
+
+/// Options for `setEdgeProp`
+#[napi(object)]
+pub struct JsSetEdgePropOptions {
+  /// Create the edge first if it doesn't exist (default: false)
+  pub upsert_edge: Option<bool>,
+}
+
+/// Options for `setPropBulk`
+#[napi(object)]
+pub struct JsSetPropBulkOptions {
+  /// Fail (writing nothing) if any id has no node, instead of skipping it
+  /// (default: false)
+  pub strict: Option<bool>,
 }
 
 /// Options for opening a Kite database
@@ -76,6 +158,9 @@ pub struct JsKiteOptions {
   pub mvcc_gc_interval_ms: Option<i64>,
   /// MVCC retention in ms
   pub mvcc_retention_ms: Option<i64>,
+  /// Record version history on every commit so beginReadAt can serve past
+  /// generations (costs a version per write)
+  pub mvcc_retain_history: Option<bool>,
   /// MVCC max version chain depth
   pub mvcc_max_chain_depth: Option<i64>,
   /// Sync mode: "Full", "Normal", or "Off" (default: "Full")
@@ -86,6 +171,8 @@ pub struct JsKiteOptions {
   pub group_commit_window_ms: Option<i64>,
   /// WAL size in megabytes (default: 1MB)
   pub wal_size_mb: Option<i64>,
+  /// Separate WAL file location (applies when creating the database)
+  pub wal_path: Option<String>,
   /// WAL usage threshold (0.0-1.0) to trigger auto-checkpoint
   pub checkpoint_threshold: Option<f64>,
   /// On close, checkpoint if WAL usage is at or above this threshold (default: 0.2)
@@ -105,3 +192,72 @@ pub struct JsKiteOptions {
   /// Minimum retained segment age in milliseconds (primary role only)
   pub replication_retention_min_ms: Option<i64>,
 }
+
+/// Options for `link`
+#[napi(object)]
+pub struct JsLinkOptions {
+  /// Start of the edge's validity window, ms since the Unix epoch (inclusive)
+  pub valid_from: Option<i64>,
+  /// End of the edge's validity window, ms since the Unix epoch (exclusive)
+  pub valid_to: Option<i64>,
+}
+
+/// Options for `linkByKey`
+#[napi(object)]
+pub struct JsLinkByKeyOptions {
+  /// Create endpoint nodes whose keys don't exist yet (default: false).
+  /// Fails if an endpoint to create has required props.
+  pub create_missing: Option<bool>,
+}
+
+/// Endpoint IDs resolved (or created) by `linkByKey`
+#[napi(object)]
+pub struct JsLinkByKeyResult {
+  pub src: i64,
+  pub dst: i64,
+}
+
+/// Options for `mergeNodes`
+#[napi(object)]
+pub struct JsMergeOptions {
+  /// Which value wins when both nodes set a prop: "keepWins" or "removeWins"
+  /// (default: "keepWins")
+  pub prop_strategy: Option<String>,
+}
+
+/// Counts reported by `linkMany`
+#[napi(object)]
+pub struct JsLinkManyResult {
+  /// Edges created
+  pub added: i64,
+  /// Edges that already existed and were left as they are
+  pub existing: i64,
+}
+
+/// Counts reported by `mergeNodes`
+#[napi(object)]
+pub struct JsMergeResult {
+  /// Edges re-pointed onto the kept node
+  pub edges_moved: i64,
+  /// Edges dropped because they became self-loops on a `noSelfLoops` type
+  pub self_loops_dropped: i64,
+  /// Props written to the kept node
+  pub props_merged: i64,
+}
+
+/// One step returned by `schemaDiff`
+#[napi(object)]
+pub struct JsSchemaChange {
+  /// "addType", "removeType", "addProp", "removeProp", or "changePropType"
+  pub op: String,
+  /// "node" or "edge"
+  pub kind: String,
+  /// Node or edge type name
+  pub name: String,
+  /// Prop name (prop changes only)
+  pub prop: Option<String>,
+  /// Target type of the prop ("addProp" and "changePropType")
+  pub prop_type: Option<String>,
+  /// Live type of the prop ("changePropType" only)
+  pub previous_type: Option<String>,
+}
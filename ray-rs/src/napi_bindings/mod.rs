@@ -3,25 +3,30 @@
 //! Exposes SingleFileDB and related types to Node.js/Bun.
 
 pub mod database;
+pub mod error;
 pub mod kite;
 pub mod traversal;
 pub mod vector;
 
 pub use database::{
-  backup_info, collect_metrics, create_backup, create_offline_backup, health_check, open_database,
-  restore_backup, BackupOptions, BackupResult, CacheLayerMetrics, CacheMetrics, CheckResult,
-  CompressionOptions, DataMetrics, Database, DatabaseMetrics, DbStats, EdgePage, EdgeWithProps,
-  HealthCheckEntry, HealthCheckResult, JsCompressionType, JsEdge, JsFullEdge, JsNodeProp,
-  JsPropValue, MemoryMetrics, MvccMetrics, MvccStats, NodePage, NodeWithProps,
-  OfflineBackupOptions, OpenOptions, PaginationOptions, PropType, RestoreOptions,
-  SingleFileOptimizeOptions, StreamOptions, VacuumOptions,
+  backup_info, collect_metrics, create_backup, create_offline_backup, health_check, list_backups,
+  open_database, restore_backup, BackupOptions, BackupResult, CacheLayerMetrics, CacheMetrics,
+  CheckResult, CompressionOptions, DataMetrics, Database, DatabaseMetrics, DbStats, EdgePage,
+  EdgeWithProps, HealthCheckEntry, HealthCheckResult, JsCommitStats, JsCompressionType, JsEdge,
+  JsFullEdge, JsIndexInfo, JsNodeKey, JsNodeProp, JsPropValue, JsTransactionInfo, JsWalRecordStats,
+  MemoryMetrics, MvccMetrics, MvccStats, NodePage, NodeWithProps, OfflineBackupOptions,
+  OpenOptions, PaginationOptions, PropType, RestoreOptions, SingleFileOptimizeOptions,
+  StreamOptions, VacuumOptions,
 };
 
+pub use error::ErrorCode;
+
 pub use kite::{
   kite, kite_sync, JsEdgeSpec, JsKeySpec, JsKiteOptions, JsNodeSpec, JsPathEdge, JsPathResult,
-  JsPropSpec, Kite, KiteInsertBuilder, KiteInsertExecutorMany, KiteInsertExecutorSingle, KitePath,
-  KiteTraversal, KiteUpdateBuilder, KiteUpdateEdgeBuilder, KiteUpsertBuilder,
-  KiteUpsertByIdBuilder, KiteUpsertEdgeBuilder, KiteUpsertExecutorMany, KiteUpsertExecutorSingle,
+  JsPropSpec, Kite, KiteExploration, KiteInsertBuilder, KiteInsertExecutorMany,
+  KiteInsertExecutorSingle, KiteNodeCursor, KitePath, KiteTraversal, KiteUpdateBuilder,
+  KiteUpdateEdgeBuilder, KiteUpsertBuilder, KiteUpsertByIdBuilder, KiteUpsertEdgeBuilder,
+  KiteUpsertExecutorMany, KiteUpsertExecutorSingle,
 };
 
 pub use traversal::{
@@ -47,6 +47,18 @@ impl From<TraversalDirection> for JsTraversalDirection {
   }
 }
 
+/// What a `walk` visitor wants to happen next
+#[napi(string_enum = "camelCase")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum JsWalkControl {
+  /// Keep walking, expanding this node's neighbors
+  Continue,
+  /// Keep walking, but don't expand this node's neighbors
+  SkipChildren,
+  /// End the walk immediately
+  Stop,
+}
+
 // ============================================================================
 // Traversal Result Types
 // ============================================================================
@@ -65,17 +77,46 @@ pub struct JsTraversalResult {
   pub edge_dst: Option<i64>,
   /// Edge type used (if any)
   pub edge_type: Option<u32>,
+  /// How the edge was followed: "Out" from its source or "In" from its
+  /// destination (if any). Tells the two apart in `Both` traversals.
+  pub via_direction: Option<JsTraversalDirection>,
+  /// Reached through a node whose neighbors were sampled, so the results
+  /// are approximate (see `maxNeighborsPerNode`)
+  pub sampled: bool,
+}
+
+/// A node reached by a weighted top-k traversal
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct JsTopKResult {
+  /// The node ID that was reached
+  pub node_id: i64,
+  /// Smallest accumulated edge weight along a path that reached it
+  pub cost: f64,
+}
+
+/// A node of a spanning tree, with the node it was first discovered from
+#[napi(object)]
+#[derive(Debug, Clone)]
+pub struct JsSpanningTreeNode {
+  /// The node ID
+  pub node_id: i64,
+  /// Node this one was first reached from (null for the root)
+  pub parent_id: Option<i64>,
+  /// Hops from the root
+  pub depth: u32,
 }
 
 impl From<TraversalResult> for JsTraversalResult {
   fn from(result: TraversalResult) -> Self {
-    let (edge_src, edge_dst, edge_type) = match result.edge {
+    let (edge_src, edge_dst, edge_type, via_direction) = match result.edge {
       Some(edge) => (
         Some(edge.src as i64),
         Some(edge.dst as i64),
         Some(edge.etype),
+        Some(edge.via_direction.into()),
       ),
-      None => (None, None, None),
+      None => (None, None, None, None),
     };
 
     Self {
@@ -84,6 +125,8 @@ impl From<TraversalResult> for JsTraversalResult {
       edge_src,
       edge_dst,
       edge_type,
+      via_direction,
+      sampled: result.sampled,
     }
   }
 }
@@ -100,6 +143,12 @@ pub struct JsTraverseOptions {
   pub max_depth: u32,
   /// Whether to only visit unique nodes (default: true)
   pub unique: Option<bool>,
+  /// Expand at most this many neighbors per node, sampling nodes with more
+  /// (default: expand all). Results are approximate once sampling kicks in.
+  pub max_neighbors_per_node: Option<u32>,
+  /// Seed for neighbor sampling; the same seed picks the same neighbors
+  /// (default: 0)
+  pub sample_seed: Option<i64>,
 }
 
 impl From<JsTraverseOptions> for TraverseOptions {
@@ -114,6 +163,8 @@ impl From<JsTraverseOptions> for TraverseOptions {
       unique: opts.unique.unwrap_or(true),
       where_edge: None,
       where_node: None,
+      max_neighbors_per_node: opts.max_neighbors_per_node.map(|max| max as usize),
+      sample_seed: opts.sample_seed.unwrap_or(0) as u64,
     }
   }
 }
@@ -609,6 +660,8 @@ impl JsGraphAccessor {
       min_depth: Some(1),
       max_depth,
       unique: Some(true),
+      max_neighbors_per_node: None,
+      sample_seed: None,
     };
 
     self
@@ -933,6 +986,8 @@ mod tests {
         min_depth: Some(1),
         max_depth: 2,
         unique: Some(true),
+        max_neighbors_per_node: None,
+        sample_seed: None,
       },
     );
 
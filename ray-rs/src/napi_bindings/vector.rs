@@ -282,22 +282,26 @@ impl JsIvfIndex {
 
   /// Insert a vector into the index
   ///
-  /// The index must be trained first.
+  /// The index must be trained first. The vector goes to its nearest
+  /// existing centroid without retraining, so recall degrades as the data
+  /// drifts from the training set; check needsRetrain() and rebuild
+  /// periodically. With `upsert`, an indexed ID is moved instead of
+  /// rejected.
   #[napi]
-  pub fn insert(&self, vector_id: i64, vector: Vec<f64>) -> Result<()> {
+  pub fn insert(&self, vector_id: i64, vector: Vec<f64>, upsert: Option<bool>) -> Result<()> {
     let mut index = self
       .inner
       .write()
       .map_err(|e| Error::from_reason(e.to_string()))?;
     let vector_f32: Vec<f32> = vector.iter().map(|&v| v as f32).collect();
     index
-      .insert(vector_id as u64, &vector_f32)
+      .insert(vector_id as u64, &vector_f32, upsert.unwrap_or(false))
       .map_err(|e| Error::from_reason(format!("Failed to insert vector: {e}")))
   }
 
   /// Delete a vector from the index
   ///
-  /// Requires the vector data to determine which cluster to remove from.
+  /// The vector data is no longer needed; see remove().
   #[napi]
   pub fn delete(&self, vector_id: i64, vector: Vec<f64>) -> Result<bool> {
     let mut index = self
@@ -308,6 +312,26 @@ impl JsIvfIndex {
     Ok(index.delete(vector_id as u64, &vector_f32))
   }
 
+  /// Remove a vector by ID (no vector data needed)
+  #[napi]
+  pub fn remove(&self, vector_id: i64) -> Result<bool> {
+    let mut index = self
+      .inner
+      .write()
+      .map_err(|e| Error::from_reason(e.to_string()))?;
+    Ok(index.remove(vector_id as u64))
+  }
+
+  /// Whether inserts and removes since training warrant a full retrain
+  #[napi]
+  pub fn needs_retrain(&self) -> Result<bool> {
+    let index = self
+      .inner
+      .read()
+      .map_err(|e| Error::from_reason(e.to_string()))?;
+    Ok(index.needs_retrain())
+  }
+
   /// Clear all data from the index
   #[napi]
   pub fn clear(&self) -> Result<()> {
@@ -31,11 +31,14 @@ use super::options::{
   OfflineBackupOptions, OpenOptions, PaginationOptions, RestoreOptions, RuntimeProfile,
   SingleFileOptimizeOptions, StreamOptions,
 };
-use super::stats::{CacheStats, CheckResult, DatabaseMetrics, DbStats, HealthCheckResult};
-use super::traversal::{PyPathEdge, PyPathResult, PyTraversalResult};
+use super::stats::{
+  CacheStats, CheckResult, CommitStats, DatabaseMetrics, DbStats, HealthCheckResult,
+};
+use super::traversal::{PyPathEdge, PyPathResult, PySpanningTreeNode, PyTraversalResult};
 use super::types::{
   Edge, EdgePage, EdgeWithProps, FullEdge, NodePage, NodeProp, NodeWithProps, PropValue,
 };
+use super::vector::PyBruteForceResult;
 
 type EdgePropsInput = (i64, u32, i64, Vec<(u32, PropValue)>);
 
@@ -287,6 +290,14 @@ impl PyDatabase {
     })
   }
 
+  fn commit_stats(&self) -> PyResult<CommitStats> {
+    dispatch!(
+      self,
+      |db| transaction::commit_stats_single_file(db),
+      |_db| { unreachable!("multi-file database support removed") }
+    )
+  }
+
   fn rollback(&self) -> PyResult<()> {
     dispatch!(self, |db| transaction::rollback_single_file(db), |_db| {
       unreachable!("multi-file database support removed")
@@ -579,6 +590,17 @@ impl PyDatabase {
     )
   }
 
+  /// Find keyed nodes whose key matches a glob (`*` any run, `?` one char)
+  ///
+  /// Patterns starting with a wildcard scan every key.
+  fn find_keys(&self, pattern: &str, limit: usize) -> PyResult<Vec<(i64, String)>> {
+    dispatch_ok!(
+      self,
+      |db| nodes::find_keys_single(db, pattern, limit),
+      |db| nodes::find_keys_single(db, pattern, limit)
+    )
+  }
+
   fn list_nodes(&self) -> PyResult<Vec<i64>> {
     dispatch_ok!(self, |db| nodes::list_nodes_single(db), |db| {
       nodes::list_nodes_single(db)
@@ -817,21 +839,21 @@ impl PyDatabase {
     )
   }
 
-  #[pyo3(name = "get_out_edges")]
-  fn out_edges(&self, node_id: i64) -> PyResult<Vec<Edge>> {
+  #[pyo3(name = "get_out_edges", signature = (node_id, etype=None))]
+  fn out_edges(&self, node_id: i64, etype: Option<u32>) -> PyResult<Vec<Edge>> {
     dispatch_ok!(
       self,
-      |db| edges::out_edges_single(db, node_id as NodeId),
-      |db| edges::out_edges_single(db, node_id as NodeId)
+      |db| edges::out_edges_single(db, node_id as NodeId, etype.map(|e| e as ETypeId)),
+      |db| edges::out_edges_single(db, node_id as NodeId, etype.map(|e| e as ETypeId))
     )
   }
 
-  #[pyo3(name = "get_in_edges")]
-  fn in_edges(&self, node_id: i64) -> PyResult<Vec<Edge>> {
+  #[pyo3(name = "get_in_edges", signature = (node_id, etype=None))]
+  fn in_edges(&self, node_id: i64, etype: Option<u32>) -> PyResult<Vec<Edge>> {
     dispatch_ok!(
       self,
-      |db| edges::in_edges_single(db, node_id as NodeId),
-      |db| edges::in_edges_single(db, node_id as NodeId)
+      |db| edges::in_edges_single(db, node_id as NodeId, etype.map(|e| e as ETypeId)),
+      |db| edges::in_edges_single(db, node_id as NodeId, etype.map(|e| e as ETypeId))
     )
   }
 
@@ -867,6 +889,24 @@ impl PyDatabase {
     )
   }
 
+  /// Count edges from nodes in `set_a` to nodes in `set_b`
+  #[pyo3(signature = (set_a, set_b, etype=None, undirected=false))]
+  fn count_edges_between(
+    &self,
+    set_a: Vec<i64>,
+    set_b: Vec<i64>,
+    etype: Option<u32>,
+    undirected: bool,
+  ) -> PyResult<i64> {
+    let set_a: Vec<NodeId> = set_a.into_iter().map(|id| id as NodeId).collect();
+    let set_b: Vec<NodeId> = set_b.into_iter().map(|id| id as NodeId).collect();
+    dispatch_ok!(
+      self,
+      |db| db.count_edges_between(&set_a, &set_b, etype, undirected) as i64,
+      |db| db.count_edges_between(&set_a, &set_b, etype, undirected) as i64
+    )
+  }
+
   #[pyo3(signature = (etype=None))]
   fn list_edges(&self, etype: Option<u32>) -> PyResult<Vec<FullEdge>> {
     dispatch_ok!(
@@ -928,6 +968,31 @@ impl PyDatabase {
     )
   }
 
+  #[pyo3(name = "get_node_prop_by_key")]
+  fn node_prop_by_key(&self, key: &str, key_name: &str) -> PyResult<Option<PropValue>> {
+    dispatch!(
+      self,
+      |db| properties::node_prop_by_key_single(db, key, key_name),
+      |db| properties::node_prop_by_key_single(db, key, key_name)
+    )
+  }
+
+  fn set_node_prop_by_key(&self, key: &str, key_name: &str, value: PropValue) -> PyResult<()> {
+    dispatch_tx!(
+      self,
+      |db| properties::set_node_prop_by_key_single(db, key, key_name, value.into()),
+      |h| properties::set_node_prop_by_key_single(h, key, key_name, value.clone().into())
+    )
+  }
+
+  fn delete_node_prop_by_key(&self, key: &str, key_name: &str) -> PyResult<()> {
+    dispatch_tx!(
+      self,
+      |db| properties::delete_node_prop_by_key_single(db, key, key_name),
+      |h| properties::delete_node_prop_by_key_single(h, key, key_name)
+    )
+  }
+
   #[pyo3(name = "get_node_props")]
   fn node_props(&self, node_id: i64) -> PyResult<Option<Vec<NodeProp>>> {
     dispatch_ok!(
@@ -1124,6 +1189,19 @@ impl PyDatabase {
     })
   }
 
+  /// Reject (or allow again) edges from a node to itself for an edge type
+  ///
+  /// Written in the current transaction and persisted with the database.
+  fn set_etype_no_self_loops(&self, etype: u32, enabled: bool) -> PyResult<()> {
+    dispatch_tx!(
+      self,
+      |db| db
+        .set_etype_no_self_loops(etype as ETypeId, enabled)
+        .map_err(|e| PyRuntimeError::new_err(format!("Failed to set no_self_loops: {e}"))),
+      |_h| Ok(())
+    )
+  }
+
   #[pyo3(name = "get_or_create_propkey")]
   fn ensure_propkey(&self, name: &str) -> PyResult<u32> {
     dispatch_ok!(self, |db| schema::ensure_propkey_single(db, name), |db| {
@@ -1214,6 +1292,31 @@ impl PyDatabase {
     )
   }
 
+  /// Overwrite specific dimensions of an existing vector
+  fn update_node_vector_dims(
+    &self,
+    node_id: i64,
+    prop_key_id: u32,
+    updates: Vec<(usize, f64)>,
+  ) -> PyResult<()> {
+    let updates: Vec<(usize, f32)> = updates.iter().map(|&(i, v)| (i, v as f32)).collect();
+    dispatch_tx!(
+      self,
+      |db| vectors::update_node_vector_dims_single(
+        db,
+        node_id as NodeId,
+        prop_key_id as PropKeyId,
+        &updates
+      ),
+      |h| vectors::update_node_vector_dims_single(
+        h,
+        node_id as NodeId,
+        prop_key_id as PropKeyId,
+        &updates
+      )
+    )
+  }
+
   #[pyo3(name = "get_node_vector")]
   fn node_vector(&self, node_id: i64, prop_key_id: u32) -> PyResult<Option<Vec<f64>>> {
     dispatch_ok!(
@@ -1239,6 +1342,22 @@ impl PyDatabase {
     )
   }
 
+  /// Find the k node vectors nearest to a query, including vectors
+  /// set or deleted in the current transaction
+  fn search_node_vectors(
+    &self,
+    prop_key_id: u32,
+    query: Vec<f64>,
+    k: usize,
+  ) -> PyResult<Vec<PyBruteForceResult>> {
+    let query: Vec<f32> = query.iter().map(|&v| v as f32).collect();
+    dispatch!(
+      self,
+      |db| vectors::search_node_vectors_single(db, prop_key_id as PropKeyId, &query, k),
+      |db| vectors::search_node_vectors_single(db, prop_key_id as PropKeyId, &query, k)
+    )
+  }
+
   // ==========================================================================
   // Traversal Operations
   // ==========================================================================
@@ -1362,6 +1481,35 @@ impl PyDatabase {
     )
   }
 
+  /// Breadth-first spanning tree rooted at `source`: each reachable node once,
+  /// with the node it was first discovered from
+  #[pyo3(signature = (source, max_depth, etype=None, direction=None))]
+  fn spanning_tree(
+    &self,
+    source: i64,
+    max_depth: u32,
+    etype: Option<u32>,
+    direction: Option<String>,
+  ) -> PyResult<Vec<PySpanningTreeNode>> {
+    dispatch_ok!(
+      self,
+      |db| graph_traversal::spanning_tree_single(
+        db,
+        source as NodeId,
+        etype,
+        direction.clone(),
+        max_depth
+      ),
+      |db| graph_traversal::spanning_tree_single(
+        db,
+        source as NodeId,
+        etype,
+        direction.clone(),
+        max_depth
+      )
+    )
+  }
+
   #[pyo3(signature = (source, target, etype=None, max_depth=None, direction=None))]
   fn find_path_bfs(
     &self,
@@ -1490,6 +1638,16 @@ impl PyDatabase {
     dispatch!(self, |db| maintenance::checkpoint_single(db), |_db| Ok(()))
   }
 
+  /// Discard WAL records already in the snapshot, e.g. after a backup.
+  /// Fails if there are uncheckpointed changes. Returns the bytes discarded.
+  fn truncate_wal_to_snapshot(&self) -> PyResult<u64> {
+    dispatch!(
+      self,
+      |db| maintenance::truncate_wal_to_snapshot_single(db),
+      |_db| Ok(0)
+    )
+  }
+
   fn background_checkpoint(&self) -> PyResult<()> {
     dispatch!(
       self,
@@ -1543,8 +1701,9 @@ impl PyDatabase {
     })
   }
 
-  fn check(&self) -> PyResult<CheckResult> {
-    dispatch_ok!(self, |db| maintenance::check_single(db), |_db| {
+  #[pyo3(signature = (strict=false))]
+  fn check(&self, strict: bool) -> PyResult<CheckResult> {
+    dispatch_ok!(self, |db| maintenance::check_single(db, strict), |_db| {
       unreachable!("multi-file database support removed")
     })
   }
@@ -1637,6 +1796,26 @@ impl PyDatabase {
     )
   }
 
+  fn cache_trim_query(&self, max_entries: usize) -> PyResult<usize> {
+    dispatch_ok!(self, |db| cache::cache_trim_query(db, max_entries), |_db| 0)
+  }
+
+  fn cache_trim_traversal(&self, max_entries: usize) -> PyResult<usize> {
+    dispatch_ok!(
+      self,
+      |db| cache::cache_trim_traversal(db, max_entries),
+      |_db| 0
+    )
+  }
+
+  fn cache_trim_property(&self, max_entries: usize) -> PyResult<usize> {
+    dispatch_ok!(
+      self,
+      |db| cache::cache_trim_property(db, max_entries),
+      |_db| 0
+    )
+  }
+
   fn cache_stats(&self) -> PyResult<Option<CacheStats>> {
     dispatch_ok!(self, |db| cache::cache_stats(db), |_db| None)
   }
@@ -1769,6 +1948,16 @@ impl PyDatabase {
     )
   }
 
+  #[pyo3(signature = (dir, options=None))]
+  fn export_sharded(&self, dir: String, options: Option<ExportOptions>) -> PyResult<ExportResult> {
+    let opts = options.unwrap_or_default();
+    dispatch!(
+      self,
+      |db| export_import::export_sharded_single(db, dir.clone(), opts.clone()),
+      |db| export_import::export_sharded_single(db, dir.clone(), opts.clone())
+    )
+  }
+
   #[pyo3(signature = (path, options=None))]
   fn import_from_json(
     &self,
@@ -1782,6 +1971,16 @@ impl PyDatabase {
       |db| export_import::import_from_json_single(db, path.clone(), opts.clone())
     )
   }
+
+  #[pyo3(signature = (dir, options=None))]
+  fn import_sharded(&self, dir: String, options: Option<ImportOptions>) -> PyResult<ImportResult> {
+    let opts = options.unwrap_or_default();
+    dispatch!(
+      self,
+      |db| export_import::import_sharded_single(db, dir.clone(), opts.clone()),
+      |db| export_import::import_sharded_single(db, dir.clone(), opts.clone())
+    )
+  }
 }
 
 // ============================================================================
@@ -2532,6 +2731,13 @@ pub fn backup_info(backup_path: String) -> PyResult<BackupResult> {
     .map_err(|e| PyRuntimeError::new_err(e.to_string()))
 }
 
+#[pyfunction]
+pub fn list_backups(dir: String) -> PyResult<Vec<BackupResult>> {
+  core_backup::list_backups(dir)
+    .map(|backups| backups.into_iter().map(BackupResult::from).collect())
+    .map_err(|e| PyRuntimeError::new_err(e.to_string()))
+}
+
 #[pyfunction]
 #[pyo3(signature = (db_path, backup_path, options=None))]
 pub fn create_offline_backup(
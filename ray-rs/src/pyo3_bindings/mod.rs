@@ -72,6 +72,7 @@ pub fn kitedb(m: &Bound<'_, PyModule>) -> PyResult<()> {
   // Stats classes
   m.add_class::<stats::DbStats>()?;
   m.add_class::<stats::CheckResult>()?;
+  m.add_class::<stats::CommitStats>()?;
   m.add_class::<stats::CacheStats>()?;
   m.add_class::<stats::CacheLayerMetrics>()?;
   m.add_class::<stats::CacheMetrics>()?;
@@ -103,6 +104,7 @@ pub fn kitedb(m: &Bound<'_, PyModule>) -> PyResult<()> {
 
   // Traversal result classes
   m.add_class::<traversal::PyTraversalResult>()?;
+  m.add_class::<traversal::PySpanningTreeNode>()?;
   m.add_class::<traversal::PyPathResult>()?;
   m.add_class::<traversal::PyPathEdge>()?;
 
@@ -160,6 +162,7 @@ pub fn kitedb(m: &Bound<'_, PyModule>) -> PyResult<()> {
   m.add_function(wrap_pyfunction!(database::create_backup, m)?)?;
   m.add_function(wrap_pyfunction!(database::restore_backup, m)?)?;
   m.add_function(wrap_pyfunction!(database::backup_info, m)?)?;
+  m.add_function(wrap_pyfunction!(database::list_backups, m)?)?;
   m.add_function(wrap_pyfunction!(database::create_offline_backup, m)?)?;
   m.add_function(wrap_pyfunction!(version, m)?)?;
   m.add_function(wrap_pyfunction!(vector::brute_force_search, m)?)?;
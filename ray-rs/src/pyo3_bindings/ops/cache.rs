@@ -25,6 +25,12 @@ pub trait CacheOps {
   fn cache_clear_property_impl(&self);
   /// Clear only the traversal cache
   fn cache_clear_traversal_impl(&self);
+  /// Trim the query cache to a maximum number of entries
+  fn cache_trim_query_impl(&self, max_entries: usize) -> usize;
+  /// Trim the traversal cache to a maximum number of entries
+  fn cache_trim_traversal_impl(&self, max_entries: usize) -> usize;
+  /// Trim the node and edge property caches to a maximum number of entries each
+  fn cache_trim_property_impl(&self, max_entries: usize) -> usize;
   /// Get cache statistics
   fn cache_stats_impl(&self) -> Option<CacheStats>;
   /// Reset cache statistics
@@ -71,6 +77,18 @@ pub fn cache_clear_traversal(db: &RustSingleFileDB) {
   db.cache_clear_traversal();
 }
 
+pub fn cache_trim_query(db: &RustSingleFileDB, max_entries: usize) -> usize {
+  db.cache_trim_query(max_entries)
+}
+
+pub fn cache_trim_traversal(db: &RustSingleFileDB, max_entries: usize) -> usize {
+  db.cache_trim_traversal(max_entries)
+}
+
+pub fn cache_trim_property(db: &RustSingleFileDB, max_entries: usize) -> usize {
+  db.cache_trim_property(max_entries)
+}
+
 pub fn cache_stats(db: &RustSingleFileDB) -> Option<CacheStats> {
   db.cache_stats().map(|s| CacheStats {
     property_cache_hits: s.property_cache_hits as i64,
@@ -82,6 +100,9 @@ pub fn cache_stats(db: &RustSingleFileDB) -> Option<CacheStats> {
     query_cache_hits: s.query_cache_hits as i64,
     query_cache_misses: s.query_cache_misses as i64,
     query_cache_size: s.query_cache_size as i64,
+    key_cache_hits: s.key_cache_hits as i64,
+    key_cache_misses: s.key_cache_misses as i64,
+    key_cache_size: s.key_cache_size as i64,
   })
 }
 
@@ -87,8 +87,12 @@ pub fn edge_exists_single(db: &RustSingleFileDB, src: NodeId, etype: ETypeId, ds
 }
 
 /// Get out edges on single-file database
-pub fn out_edges_single(db: &RustSingleFileDB, node_id: NodeId) -> Vec<Edge> {
-  db.out_edges(node_id)
+pub fn out_edges_single(
+  db: &RustSingleFileDB,
+  node_id: NodeId,
+  etype: Option<ETypeId>,
+) -> Vec<Edge> {
+  db.out_edges_filtered(node_id, etype)
     .into_iter()
     .map(|(etype, dst)| Edge {
       etype,
@@ -98,8 +102,12 @@ pub fn out_edges_single(db: &RustSingleFileDB, node_id: NodeId) -> Vec<Edge> {
 }
 
 /// Get in edges on single-file database
-pub fn in_edges_single(db: &RustSingleFileDB, node_id: NodeId) -> Vec<Edge> {
-  db.in_edges(node_id)
+pub fn in_edges_single(
+  db: &RustSingleFileDB,
+  node_id: NodeId,
+  etype: Option<ETypeId>,
+) -> Vec<Edge> {
+  db.in_edges_filtered(node_id, etype)
     .into_iter()
     .map(|(etype, src)| Edge {
       etype,
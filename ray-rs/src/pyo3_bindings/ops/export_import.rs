@@ -16,8 +16,14 @@ pub trait ExportImportOps {
   /// Export to JSONL file
   fn export_to_jsonl_impl(&self, path: String, options: ExportOptions) -> PyResult<ExportResult>;
 
+  /// Export to a directory of JSONL shards
+  fn export_sharded_impl(&self, dir: String, options: ExportOptions) -> PyResult<ExportResult>;
+
   /// Import from JSON file
   fn import_from_json_impl(&self, path: String, options: ImportOptions) -> PyResult<ImportResult>;
+
+  /// Import from a sharded export directory
+  fn import_sharded_impl(&self, dir: String, options: ImportOptions) -> PyResult<ImportResult>;
 }
 
 // ============================================================================
@@ -68,6 +74,25 @@ pub fn export_to_jsonl_single(
   })
 }
 
+pub fn export_sharded_single(
+  db: &RustSingleFileDB,
+  dir: String,
+  options: ExportOptions,
+) -> PyResult<ExportResult> {
+  let opts = options.to_rust();
+  let nodes_per_shard = opts.nodes_per_shard;
+  let data = ray_export::export_to_object_single(db, opts)
+    .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+
+  let result = ray_export::export_sharded(&data, dir, nodes_per_shard)
+    .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+
+  Ok(ExportResult {
+    node_count: result.node_count as i64,
+    edge_count: result.edge_count as i64,
+  })
+}
+
 pub fn import_from_object_single(
   db: &RustSingleFileDB,
   data: &ray_export::ExportedDatabase,
@@ -102,3 +127,22 @@ pub fn import_from_json_single(
     skipped: result.skipped as i64,
   })
 }
+
+pub fn import_sharded_single(
+  db: &RustSingleFileDB,
+  dir: String,
+  options: ImportOptions,
+) -> PyResult<ImportResult> {
+  let opts = options.to_rust();
+  let parsed =
+    ray_export::import_sharded(dir).map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+
+  let result = ray_export::import_from_object_single(db, &parsed, opts)
+    .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
+
+  Ok(ImportResult {
+    node_count: result.node_count as i64,
+    edge_count: result.edge_count as i64,
+    skipped: result.skipped as i64,
+  })
+}
@@ -5,13 +5,15 @@ use std::collections::HashSet;
 
 use crate::api::pathfinding::{bfs, dijkstra, PathConfig};
 use crate::api::traversal::{
-  TraversalBuilder as RustTraversalBuilder, TraversalDirection, TraverseOptions,
+  spanning_tree, TraversalBuilder as RustTraversalBuilder, TraversalDirection, TraverseOptions,
 };
 use crate::core::single_file::SingleFileDB as RustSingleFileDB;
 use crate::types::{ETypeId, Edge, NodeId};
 
 use crate::pyo3_bindings::helpers::neighbors_from_single_file;
-use crate::pyo3_bindings::traversal::{PyPathResult, PyTraversalResult as TraversalResult};
+use crate::pyo3_bindings::traversal::{
+  PyPathResult, PySpanningTreeNode, PyTraversalResult as TraversalResult,
+};
 
 /// Trait for graph traversal operations
 pub trait GraphTraversalOps {
@@ -279,6 +281,8 @@ pub fn traverse_single(
     unique: unique.unwrap_or(true),
     where_edge: None,
     where_node: None,
+    max_neighbors_per_node: None,
+    sample_seed: 0,
   };
 
   let neighbors = |nid: NodeId, d: TraversalDirection, et: Option<ETypeId>| -> Vec<Edge> {
@@ -289,9 +293,20 @@ pub fn traverse_single(
     .traverse(etype, opts)
     .execute(neighbors)
     .map(|r| {
-      let (edge_src, edge_dst, edge_type) = match r.edge {
-        Some(e) => (Some(e.src as i64), Some(e.dst as i64), Some(e.etype)),
-        None => (None, None, None),
+      let (edge_src, edge_dst, edge_type, via_direction) = match r.edge {
+        Some(e) => {
+          let via = match e.via_direction {
+            TraversalDirection::In => "in",
+            _ => "out",
+          };
+          (
+            Some(e.src as i64),
+            Some(e.dst as i64),
+            Some(e.etype),
+            Some(via.to_string()),
+          )
+        }
+        None => (None, None, None, None),
       };
       TraversalResult {
         node_id: r.node_id as i64,
@@ -299,11 +314,37 @@ pub fn traverse_single(
         edge_src,
         edge_dst,
         edge_type,
+        via_direction,
       }
     })
     .collect()
 }
 
+pub fn spanning_tree_single(
+  db: &RustSingleFileDB,
+  source: NodeId,
+  etype: Option<u32>,
+  direction: Option<String>,
+  max_depth: u32,
+) -> Vec<PySpanningTreeNode> {
+  let dir = match direction.as_deref() {
+    Some("in") => TraversalDirection::In,
+    Some("both") => TraversalDirection::Both,
+    _ => TraversalDirection::Out,
+  };
+
+  spanning_tree(source, etype, dir, max_depth as usize, |nid, d, et| {
+    neighbors_from_single_file(db, nid, d, et)
+  })
+  .into_iter()
+  .map(|node| PySpanningTreeNode {
+    node_id: node.node_id as i64,
+    parent_id: node.parent_id.map(|id| id as i64),
+    depth: node.depth as u32,
+  })
+  .collect()
+}
+
 pub fn find_path_bfs_single(
   db: &RustSingleFileDB,
   source: NodeId,
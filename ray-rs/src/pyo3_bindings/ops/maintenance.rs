@@ -59,6 +59,11 @@ pub fn vacuum_single(db: &RustSingleFileDB, options: Option<RustVacuumOptions>)
     .map_err(|e| PyRuntimeError::new_err(format!("Failed to vacuum: {e}")))
 }
 
+pub fn truncate_wal_to_snapshot_single(db: &RustSingleFileDB) -> PyResult<u64> {
+  db.truncate_wal_to_snapshot()
+    .map_err(|e| PyRuntimeError::new_err(format!("Failed to truncate WAL: {e}")))
+}
+
 pub fn stats_single(db: &RustSingleFileDB) -> DbStats {
   let s = db.stats();
   DbStats {
@@ -72,6 +77,7 @@ pub fn stats_single(db: &RustSingleFileDB) -> DbStats {
     delta_edges_deleted: s.delta_edges_deleted as i64,
     wal_segment: s.wal_segment as i64,
     wal_bytes: s.wal_bytes as i64,
+    fragmentation: s.fragmentation,
     recommend_compact: s.recommend_compact,
     mvcc_stats: s.mvcc_stats.map(|stats| MvccStats {
       active_transactions: stats.active_transactions as i64,
@@ -81,10 +87,19 @@ pub fn stats_single(db: &RustSingleFileDB) -> DbStats {
       last_gc_time: stats.last_gc_time as i64,
       committed_writes_size: stats.committed_writes_size as i64,
       committed_writes_pruned: stats.committed_writes_pruned as i64,
+      version_chains: stats.version_chains as i64,
+      versions_retained: stats.versions_retained as i64,
+      chain_depth_max: stats.chain_depth_max as i64,
+      chain_depth_p50: stats.chain_depth_p50 as i64,
+      chain_depth_p99: stats.chain_depth_p99 as i64,
     }),
   }
 }
 
-pub fn check_single(db: &RustSingleFileDB) -> CheckResult {
-  db.check().into()
+pub fn check_single(db: &RustSingleFileDB, strict: bool) -> CheckResult {
+  if strict {
+    db.check_strict().into()
+  } else {
+    db.check().into()
+  }
 }
@@ -125,6 +125,14 @@ pub fn upsert_node_by_id_single(
   Ok(node_id as i64)
 }
 
+/// Find keys matching a glob pattern on single-file database
+pub fn find_keys_single(db: &RustSingleFileDB, pattern: &str, limit: usize) -> Vec<(i64, String)> {
+  db.find_keys(pattern, limit)
+    .into_iter()
+    .map(|(id, key)| (id as i64, key))
+    .collect()
+}
+
 /// List nodes with key prefix on single-file database
 pub fn list_nodes_with_prefix_single(db: &RustSingleFileDB, prefix: &str) -> Vec<i64> {
   db.list_nodes()
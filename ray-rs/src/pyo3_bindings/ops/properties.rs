@@ -74,6 +74,38 @@ pub fn set_node_prop_by_name_single(
     .map_err(|e| PyRuntimeError::new_err(format!("Failed to set property: {e}")))
 }
 
+/// Get node property by node key on single-file database
+pub fn node_prop_by_key_single(
+  db: &RustSingleFileDB,
+  key: &str,
+  key_name: &str,
+) -> PyResult<Option<PropValue>> {
+  db.node_prop_by_key(key, key_name)
+    .map(|value| value.map(|v| v.into()))
+    .map_err(|e| PyRuntimeError::new_err(format!("Failed to get property: {e}")))
+}
+
+/// Set node property by node key on single-file database
+pub fn set_node_prop_by_key_single(
+  db: &RustSingleFileDB,
+  key: &str,
+  key_name: &str,
+  value: CorePropValue,
+) -> PyResult<()> {
+  db.set_node_prop_by_key(key, key_name, value)
+    .map_err(|e| PyRuntimeError::new_err(format!("Failed to set property: {e}")))
+}
+
+/// Delete node property by node key on single-file database
+pub fn delete_node_prop_by_key_single(
+  db: &RustSingleFileDB,
+  key: &str,
+  key_name: &str,
+) -> PyResult<()> {
+  db.delete_node_prop_by_key(key, key_name)
+    .map_err(|e| PyRuntimeError::new_err(format!("Failed to delete property: {e}")))
+}
+
 /// Get node property on single-file database
 pub fn node_prop_single(
   db: &RustSingleFileDB,
@@ -4,6 +4,7 @@ use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
 
 use crate::core::single_file::SingleFileDB as RustSingleFileDB;
+use crate::pyo3_bindings::stats::CommitStats;
 
 /// Trait for transaction operations
 pub trait TransactionOps {
@@ -45,6 +46,13 @@ pub fn commit_single_file(db: &RustSingleFileDB) -> PyResult<()> {
     .map_err(|e| PyRuntimeError::new_err(format!("Failed to commit: {e}")))
 }
 
+/// Commit transaction on single-file database and summarize it
+pub fn commit_stats_single_file(db: &RustSingleFileDB) -> PyResult<CommitStats> {
+  db.commit_stats()
+    .map(CommitStats::from)
+    .map_err(|e| PyRuntimeError::new_err(format!("Failed to commit: {e}")))
+}
+
 /// Rollback transaction on single-file database
 pub fn rollback_single_file(db: &RustSingleFileDB) -> PyResult<()> {
   db.rollback()
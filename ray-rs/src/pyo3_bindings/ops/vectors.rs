@@ -4,6 +4,7 @@ use pyo3::exceptions::PyRuntimeError;
 use pyo3::prelude::*;
 
 use crate::core::single_file::SingleFileDB as RustSingleFileDB;
+use crate::pyo3_bindings::vector::PyBruteForceResult;
 use crate::types::{NodeId, PropKeyId};
 
 /// Trait for vector operations
@@ -32,6 +33,16 @@ pub fn set_node_vector_single(
     .map_err(|e| PyRuntimeError::new_err(format!("Failed to set vector: {e}")))
 }
 
+pub fn update_node_vector_dims_single(
+  db: &RustSingleFileDB,
+  node_id: NodeId,
+  prop_key_id: PropKeyId,
+  updates: &[(usize, f32)],
+) -> PyResult<()> {
+  db.update_node_vector_dims(node_id, prop_key_id, updates)
+    .map_err(|e| PyRuntimeError::new_err(format!("Failed to update vector: {e}")))
+}
+
 pub fn node_vector_single(
   db: &RustSingleFileDB,
   node_id: NodeId,
@@ -57,3 +68,24 @@ pub fn has_node_vector_single(
 ) -> bool {
   db.has_node_vector(node_id, prop_key_id)
 }
+
+pub fn search_node_vectors_single(
+  db: &RustSingleFileDB,
+  prop_key_id: PropKeyId,
+  query: &[f32],
+  k: usize,
+) -> PyResult<Vec<PyBruteForceResult>> {
+  let hits = db
+    .search_node_vectors(prop_key_id, query, k)
+    .map_err(|e| PyRuntimeError::new_err(format!("Failed to search vectors: {e}")))?;
+  Ok(
+    hits
+      .into_iter()
+      .map(|hit| PyBruteForceResult {
+        node_id: hit.node_id as i64,
+        distance: hit.distance as f64,
+        similarity: hit.similarity as f64,
+      })
+      .collect(),
+  )
+}
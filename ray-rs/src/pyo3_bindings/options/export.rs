@@ -14,31 +14,48 @@ pub struct ExportOptions {
   #[pyo3(get, set)]
   pub include_schema: Option<bool>,
   #[pyo3(get, set)]
+  pub include_vectors: Option<bool>,
+  #[pyo3(get, set)]
   pub pretty: Option<bool>,
+  #[pyo3(get, set)]
+  pub labels: Option<Vec<String>>,
+  #[pyo3(get, set)]
+  pub nodes_per_shard: Option<i64>,
 }
 
 #[pymethods]
 impl ExportOptions {
   #[new]
-  #[pyo3(signature = (include_nodes=None, include_edges=None, include_schema=None, pretty=None))]
+  #[pyo3(signature = (include_nodes=None, include_edges=None, include_schema=None, include_vectors=None, pretty=None, labels=None, nodes_per_shard=None))]
   fn new(
     include_nodes: Option<bool>,
     include_edges: Option<bool>,
     include_schema: Option<bool>,
+    include_vectors: Option<bool>,
     pretty: Option<bool>,
+    labels: Option<Vec<String>>,
+    nodes_per_shard: Option<i64>,
   ) -> Self {
     Self {
       include_nodes,
       include_edges,
       include_schema,
+      include_vectors,
       pretty,
+      labels,
+      nodes_per_shard,
     }
   }
 
   fn __repr__(&self) -> String {
     format!(
-      "ExportOptions(include_nodes={:?}, include_edges={:?}, pretty={:?})",
-      self.include_nodes, self.include_edges, self.pretty
+      "ExportOptions(include_nodes={:?}, include_edges={:?}, include_vectors={:?}, pretty={:?}, labels={:?}, nodes_per_shard={:?})",
+      self.include_nodes,
+      self.include_edges,
+      self.include_vectors,
+      self.pretty,
+      self.labels,
+      self.nodes_per_shard
     )
   }
 }
@@ -56,9 +73,18 @@ impl ExportOptions {
     if let Some(v) = self.include_schema {
       opts.include_schema = v;
     }
+    if let Some(v) = self.include_vectors {
+      opts.include_vectors = v;
+    }
     if let Some(v) = self.pretty {
       opts.pretty = v;
     }
+    opts.labels = self.labels;
+    if let Some(v) = self.nodes_per_shard {
+      if v > 0 {
+        opts.nodes_per_shard = v as usize;
+      }
+    }
     opts
   }
 }
@@ -186,13 +212,19 @@ mod tests {
       include_nodes: Some(true),
       include_edges: Some(false),
       include_schema: Some(true),
+      include_vectors: Some(true),
       pretty: Some(true),
+      labels: Some(vec!["Public".to_string()]),
+      nodes_per_shard: Some(10),
     };
     let rust = opts.to_rust();
     assert!(rust.include_nodes);
     assert!(!rust.include_edges);
     assert!(rust.include_schema);
+    assert!(rust.include_vectors);
     assert!(rust.pretty);
+    assert_eq!(rust.labels, Some(vec!["Public".to_string()]));
+    assert_eq!(rust.nodes_per_shard, 10);
   }
 
   #[test]
@@ -191,6 +191,9 @@ pub struct OpenOptions {
   /// Minimum retained segment age in milliseconds (primary role only)
   #[pyo3(get, set)]
   pub replication_retention_min_ms: Option<i64>,
+  /// Separate WAL file location (applies when creating the database)
+  #[pyo3(get, set)]
+  pub wal_path: Option<String>,
 }
 
 #[pymethods]
@@ -226,7 +229,8 @@ impl OpenOptions {
         replication_source_sidecar_path=None,
         replication_segment_max_bytes=None,
         replication_retention_min_entries=None,
-        replication_retention_min_ms=None
+        replication_retention_min_ms=None,
+        wal_path=None
     ))]
   #[allow(clippy::too_many_arguments)]
   fn new(
@@ -260,6 +264,7 @@ impl OpenOptions {
     replication_segment_max_bytes: Option<i64>,
     replication_retention_min_entries: Option<i64>,
     replication_retention_min_ms: Option<i64>,
+    wal_path: Option<String>,
   ) -> Self {
     Self {
       read_only,
@@ -292,6 +297,7 @@ impl OpenOptions {
       replication_segment_max_bytes,
       replication_retention_min_entries,
       replication_retention_min_ms,
+      wal_path,
     }
   }
 
@@ -398,6 +404,9 @@ impl OpenOptions {
       })?;
       rust_opts = rust_opts.replication_role(role);
     }
+    if let Some(ref path) = self.wal_path {
+      rust_opts = rust_opts.wal_path(path);
+    }
     if let Some(ref path) = self.replication_sidecar_path {
       rust_opts = rust_opts.replication_sidecar_path(path);
     }
@@ -491,6 +500,7 @@ impl OpenOptions {
       replication_retention_min_ms: opts
         .replication_retention_min_ms
         .and_then(|v| i64::try_from(v).ok()),
+      wal_path: opts.wal_path.map(|p| p.to_string_lossy().to_string()),
     }
   }
 }
@@ -10,18 +10,27 @@ use pyo3::prelude::*;
 pub struct StreamOptions {
   #[pyo3(get, set)]
   pub batch_size: Option<i64>,
+  /// Only stream edges whose endpoints are both in this node set
+  #[pyo3(get, set)]
+  pub node_filter: Option<Vec<i64>>,
 }
 
 #[pymethods]
 impl StreamOptions {
   #[new]
-  #[pyo3(signature = (batch_size=None))]
-  fn new(batch_size: Option<i64>) -> Self {
-    Self { batch_size }
+  #[pyo3(signature = (batch_size=None, node_filter=None))]
+  fn new(batch_size: Option<i64>, node_filter: Option<Vec<i64>>) -> Self {
+    Self {
+      batch_size,
+      node_filter,
+    }
   }
 
   fn __repr__(&self) -> String {
-    format!("StreamOptions(batch_size={:?})", self.batch_size)
+    format!(
+      "StreamOptions(batch_size={:?}, node_filter={:?})",
+      self.batch_size, self.node_filter
+    )
   }
 }
 
@@ -34,6 +43,9 @@ impl StreamOptions {
     }
     Ok(streaming::StreamOptions {
       batch_size: batch_size as usize,
+      node_filter: self
+        .node_filter
+        .map(|ids| ids.into_iter().map(|id| id as u64).collect()),
     })
   }
 }
@@ -93,6 +105,7 @@ mod tests {
   fn test_stream_options_with_batch_size() {
     let opts = StreamOptions {
       batch_size: Some(100),
+      node_filter: None,
     };
     let rust = opts.to_rust().expect("expected value");
     assert_eq!(rust.batch_size, 100);
@@ -102,6 +115,7 @@ mod tests {
   fn test_stream_options_negative_batch_size() {
     let opts = StreamOptions {
       batch_size: Some(-1),
+      node_filter: None,
     };
     assert!(opts.to_rust().is_err());
   }
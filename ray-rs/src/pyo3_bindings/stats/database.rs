@@ -1,7 +1,7 @@
 //! Database statistics types for Python bindings
 
 use super::metrics::MvccStats;
-use crate::types::CheckResult as RustCheckResult;
+use crate::types::{CheckResult as RustCheckResult, CommitStats as RustCommitStats};
 use pyo3::prelude::*;
 
 /// Database statistics
@@ -29,6 +29,8 @@ pub struct DbStats {
   #[pyo3(get)]
   pub wal_bytes: i64,
   #[pyo3(get)]
+  pub fragmentation: f64,
+  #[pyo3(get)]
   pub recommend_compact: bool,
   #[pyo3(get)]
   pub mvcc_stats: Option<MvccStats>,
@@ -48,10 +50,11 @@ impl DbStats {
 
   fn __repr__(&self) -> String {
     format!(
-      "DbStats(nodes={}, edges={}, wal_bytes={}, recommend_compact={})",
+      "DbStats(nodes={}, edges={}, wal_bytes={}, fragmentation={:.3}, recommend_compact={})",
       self.node_count(),
       self.edge_count(),
       self.wal_bytes,
+      self.fragmentation,
       self.recommend_compact
     )
   }
@@ -115,6 +118,56 @@ impl From<RustCheckResult> for CheckResult {
   }
 }
 
+/// What a committed transaction changed
+#[pyclass(name = "CommitStats")]
+#[derive(Debug, Clone)]
+pub struct CommitStats {
+  #[pyo3(get)]
+  pub nodes_created: i64,
+  #[pyo3(get)]
+  pub nodes_deleted: i64,
+  #[pyo3(get)]
+  pub edges_added: i64,
+  #[pyo3(get)]
+  pub edges_deleted: i64,
+  #[pyo3(get)]
+  pub props_set: i64,
+  #[pyo3(get)]
+  pub duration_ms: i64,
+  #[pyo3(get)]
+  pub wal_bytes_written: i64,
+}
+
+#[pymethods]
+impl CommitStats {
+  fn __repr__(&self) -> String {
+    format!(
+      "CommitStats(nodes_created={}, nodes_deleted={}, edges_added={}, edges_deleted={}, props_set={}, duration_ms={}, wal_bytes_written={})",
+      self.nodes_created,
+      self.nodes_deleted,
+      self.edges_added,
+      self.edges_deleted,
+      self.props_set,
+      self.duration_ms,
+      self.wal_bytes_written
+    )
+  }
+}
+
+impl From<RustCommitStats> for CommitStats {
+  fn from(stats: RustCommitStats) -> Self {
+    CommitStats {
+      nodes_created: stats.nodes_created as i64,
+      nodes_deleted: stats.nodes_deleted as i64,
+      edges_added: stats.edges_added as i64,
+      edges_deleted: stats.edges_deleted as i64,
+      props_set: stats.props_set as i64,
+      duration_ms: stats.duration_ms as i64,
+      wal_bytes_written: stats.wal_bytes_written as i64,
+    }
+  }
+}
+
 /// Cache statistics
 #[pyclass(name = "CacheStats")]
 #[derive(Debug, Clone)]
@@ -137,6 +190,12 @@ pub struct CacheStats {
   pub query_cache_misses: i64,
   #[pyo3(get)]
   pub query_cache_size: i64,
+  #[pyo3(get)]
+  pub key_cache_hits: i64,
+  #[pyo3(get)]
+  pub key_cache_misses: i64,
+  #[pyo3(get)]
+  pub key_cache_size: i64,
 }
 
 #[pymethods]
@@ -171,10 +230,23 @@ impl CacheStats {
     }
   }
 
+  /// Get key lookup cache hit rate
+  fn key_hit_rate(&self) -> f64 {
+    let total = self.key_cache_hits + self.key_cache_misses;
+    if total == 0 {
+      0.0
+    } else {
+      self.key_cache_hits as f64 / total as f64
+    }
+  }
+
   fn __repr__(&self) -> String {
     format!(
-      "CacheStats(property_hits={}, traversal_hits={}, query_hits={})",
-      self.property_cache_hits, self.traversal_cache_hits, self.query_cache_hits
+      "CacheStats(property_hits={}, traversal_hits={}, query_hits={}, key_hits={})",
+      self.property_cache_hits,
+      self.traversal_cache_hits,
+      self.query_cache_hits,
+      self.key_cache_hits
     )
   }
 }
@@ -196,6 +268,7 @@ mod tests {
       delta_edges_deleted: 10,
       wal_segment: 0,
       wal_bytes: 1024,
+      fragmentation: 0.05,
       recommend_compact: false,
       mvcc_stats: None,
     };
@@ -229,9 +302,13 @@ mod tests {
       query_cache_hits: 50,
       query_cache_misses: 50,
       query_cache_size: 100,
+      key_cache_hits: 9,
+      key_cache_misses: 1,
+      key_cache_size: 1,
     };
     assert!((stats.property_hit_rate() - 0.8).abs() < 0.001);
     assert!((stats.traversal_hit_rate() - 0.0).abs() < 0.001);
     assert!((stats.query_hit_rate() - 0.5).abs() < 0.001);
+    assert!((stats.key_hit_rate() - 0.9).abs() < 0.001);
   }
 }
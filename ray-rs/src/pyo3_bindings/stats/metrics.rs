@@ -331,6 +331,16 @@ pub struct MvccStats {
   pub committed_writes_size: i64,
   #[pyo3(get)]
   pub committed_writes_pruned: i64,
+  #[pyo3(get)]
+  pub version_chains: i64,
+  #[pyo3(get)]
+  pub versions_retained: i64,
+  #[pyo3(get)]
+  pub chain_depth_max: i64,
+  #[pyo3(get)]
+  pub chain_depth_p50: i64,
+  #[pyo3(get)]
+  pub chain_depth_p99: i64,
 }
 
 #[pymethods]
@@ -419,6 +429,10 @@ pub struct DatabaseMetrics {
   #[pyo3(get)]
   pub memory: MemoryMetrics,
   #[pyo3(get)]
+  pub write_rate: f64,
+  #[pyo3(get)]
+  pub read_rate: f64,
+  #[pyo3(get)]
   pub collected_at: i64,
 }
 
@@ -446,6 +460,8 @@ impl From<core_metrics::DatabaseMetrics> for DatabaseMetrics {
       mvcc: metrics.mvcc.map(Into::into),
       replication: metrics.replication.into(),
       memory: metrics.memory.into(),
+      write_rate: metrics.write_rate,
+      read_rate: metrics.read_rate,
       collected_at: metrics.collected_at_ms,
     }
   }
@@ -11,7 +11,7 @@ pub mod database;
 pub mod metrics;
 
 // Re-export all stats types for convenience
-pub use database::{CacheStats, CheckResult, DbStats};
+pub use database::{CacheStats, CheckResult, CommitStats, DbStats};
 pub use metrics::{
   CacheLayerMetrics, CacheMetrics, DataMetrics, DatabaseMetrics, HealthCheckEntry,
   HealthCheckResult, MemoryMetrics, MvccMetrics, MvccStats, PrimaryReplicationMetrics,
@@ -25,6 +25,10 @@ pub struct PyTraversalResult {
   /// Edge type used (if any)
   #[pyo3(get)]
   pub edge_type: Option<u32>,
+  /// How the edge was followed: "out" from its source or "in" from its
+  /// destination (if any)
+  #[pyo3(get)]
+  pub via_direction: Option<String>,
 }
 
 #[pymethods]
@@ -37,6 +41,35 @@ impl PyTraversalResult {
   }
 }
 
+// ============================================================================
+// Spanning Tree Node
+// ============================================================================
+
+/// A node of a spanning tree, with the node it was first discovered from
+#[pyclass(name = "SpanningTreeNode")]
+#[derive(Debug, Clone)]
+pub struct PySpanningTreeNode {
+  /// The node ID
+  #[pyo3(get)]
+  pub node_id: i64,
+  /// Node this one was first reached from (None for the root)
+  #[pyo3(get)]
+  pub parent_id: Option<i64>,
+  /// Hops from the root
+  #[pyo3(get)]
+  pub depth: u32,
+}
+
+#[pymethods]
+impl PySpanningTreeNode {
+  fn __repr__(&self) -> String {
+    format!(
+      "SpanningTreeNode(node_id={}, parent_id={:?}, depth={})",
+      self.node_id, self.parent_id, self.depth
+    )
+  }
+}
+
 // ============================================================================
 // Path Result
 // ============================================================================
@@ -12,6 +12,7 @@ pub enum PropType {
   Float,
   String,
   Vector,
+  Json,
 }
 
 /// Property value wrapper for Python
@@ -113,6 +114,14 @@ impl PropValue {
     }
   }
 
+  /// Create a JSON document value from its serialized text
+  #[staticmethod]
+  fn json(value: String) -> PyResult<Self> {
+    serde_json::from_str::<serde_json::Value>(&value)
+      .map_err(|e| pyo3::exceptions::PyValueError::new_err(format!("Invalid JSON: {e}")))?;
+    Ok(Self::json_text(value))
+  }
+
   /// Get the Python value
   fn value(&self, py: Python<'_>) -> PyResult<PyObject> {
     use pyo3::IntoPyObjectExt;
@@ -131,6 +140,10 @@ impl PropValue {
         .clone()
         .unwrap_or_default()
         .into_py_any(py),
+      "json" => py
+        .import("json")?
+        .call_method1("loads", (self.string_value.clone().unwrap_or_default(),))?
+        .into_py_any(py),
       _ => Ok(py.None()),
     }
   }
@@ -149,6 +162,10 @@ impl PropValue {
         "PropValue(vector, len={})",
         self.vector_value.as_ref().map(|v| v.len()).unwrap_or(0)
       ),
+      "json" => format!(
+        "PropValue(json, {})",
+        self.string_value.clone().unwrap_or_default()
+      ),
       _ => "PropValue(unknown)".to_string(),
     }
   }
@@ -185,6 +202,18 @@ impl PropValue {
   pub fn new_vector(value: Vec<f64>) -> Self {
     Self::vector(value)
   }
+
+  /// JSON value from already-validated serialized text
+  fn json_text(value: String) -> Self {
+    PropValue {
+      prop_type: "json".to_string(),
+      bool_value: None,
+      int_value: None,
+      float_value: None,
+      string_value: Some(value),
+      vector_value: None,
+    }
+  }
 }
 
 impl From<CorePropValue> for PropValue {
@@ -196,6 +225,7 @@ impl From<CorePropValue> for PropValue {
       CorePropValue::F64(v) => PropValue::float_val(v),
       CorePropValue::String(v) => PropValue::string(v),
       CorePropValue::VectorF32(v) => PropValue::vector(v.iter().map(|&x| x as f64).collect()),
+      CorePropValue::Json(v) => PropValue::json_text(v.to_string()),
     }
   }
 }
@@ -212,6 +242,11 @@ impl From<PropValue> for CorePropValue {
         let vector = value.vector_value.unwrap_or_default();
         CorePropValue::VectorF32(vector.iter().map(|&x| x as f32).collect())
       }
+      "json" => value
+        .string_value
+        .and_then(|text| serde_json::from_str(&text).ok())
+        .map(CorePropValue::Json)
+        .unwrap_or(CorePropValue::Null),
       _ => CorePropValue::Null,
     }
   }
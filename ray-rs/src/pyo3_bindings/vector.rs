@@ -386,15 +386,16 @@ impl PyIvfIndex {
       .map_err(|e| PyRuntimeError::new_err(format!("Failed to train index: {e}")))
   }
 
-  /// Insert a vector into the index
-  fn insert(&self, vector_id: i64, vector: Vec<f64>) -> PyResult<()> {
+  /// Insert a vector into the index; with `upsert`, an indexed ID is moved
+  #[pyo3(signature = (vector_id, vector, upsert=false))]
+  fn insert(&self, vector_id: i64, vector: Vec<f64>, upsert: bool) -> PyResult<()> {
     let mut index = self
       .inner
       .write()
       .map_err(|e| PyRuntimeError::new_err(e.to_string()))?;
     let vector_f32: Vec<f32> = vector.iter().map(|&v| v as f32).collect();
     index
-      .insert(vector_id as u64, &vector_f32)
+      .insert(vector_id as u64, &vector_f32, upsert)
       .map_err(|e| PyRuntimeError::new_err(format!("Failed to insert vector: {e}")))
   }
 
@@ -147,6 +147,7 @@ impl PrimaryReplication {
       initial
     };
 
+    ensure_segment_files_present(&sidecar_path, &manifest)?;
     ensure_active_segment_metadata(&mut manifest);
     if reconcile_manifest_head_from_active_segment(&sidecar_path, &mut manifest)? {
       // Recover append state when manifest head lagged a flushed segment tail.
@@ -786,6 +787,27 @@ fn ensure_active_segment_metadata(manifest: &mut ReplicationManifest) {
   });
 }
 
+/// Fail when the manifest lists log entries in a segment file that is gone.
+/// Unlike a missing sidecar directory this can't be rebuilt: the entries
+/// replicas still need were lost.
+fn ensure_segment_files_present(sidecar_path: &Path, manifest: &ReplicationManifest) -> Result<()> {
+  for segment in &manifest.segments {
+    let has_entries = segment.end_log_index > 0 && segment.end_log_index >= segment.start_log_index;
+    let segment_path = sidecar_path.join(segment_file_name(segment.id));
+    if has_entries && !segment_path.exists() {
+      return Err(KiteError::InvalidReplication(format!(
+        "replication sidecar {} is corrupt: manifest references missing segment file {} \
+         (log entries {}..={}); restore the sidecar from a backup or remove it and reseed replicas",
+        sidecar_path.display(),
+        segment_path.display(),
+        segment.start_log_index,
+        segment.end_log_index
+      )));
+    }
+  }
+  Ok(())
+}
+
 fn segment_file_name(id: u64) -> String {
   format!("segment-{id:020}.rlog")
 }
@@ -1,11 +1,16 @@
 //! Streaming and pagination helpers
 
+use std::collections::HashSet;
+
 use crate::core::single_file::SingleFileDB;
 use crate::types::{Edge, NodeId};
 
 #[derive(Debug, Clone, Default)]
 pub struct StreamOptions {
   pub batch_size: usize,
+  /// Only stream edges whose source and destination are both in this set
+  /// (edge streams only)
+  pub node_filter: Option<HashSet<NodeId>>,
 }
 
 #[derive(Debug, Clone, Default)]
@@ -56,6 +61,11 @@ pub fn stream_edges_single(db: &SingleFileDB, options: StreamOptions) -> Vec<Vec
   let mut batches: Vec<Vec<Edge>> = Vec::new();
   let mut current: Vec<Edge> = Vec::with_capacity(batch_size);
   for edge in db.list_edges(None) {
+    if let Some(nodes) = options.node_filter.as_ref() {
+      if !nodes.contains(&edge.src) || !nodes.contains(&edge.dst) {
+        continue;
+      }
+    }
     current.push(Edge {
       src: edge.src,
       etype: edge.etype,
@@ -188,3 +198,48 @@ pub fn edges_page_single(db: &SingleFileDB, options: PaginationOptions) -> Page<
     total: None,
   }
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+  use crate::core::single_file::{close_single_file, open_single_file, SingleFileOpenOptions};
+  use tempfile::tempdir;
+
+  #[test]
+  fn test_stream_edges_node_filter_keeps_edges_among_set() {
+    let temp_dir = tempdir().expect("expected value");
+    let db_path = temp_dir.path().join("stream-filter.kitedb");
+    let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("expected value");
+
+    db.begin(false).expect("expected value");
+    let a = db.create_node(None).expect("expected value");
+    let b = db.create_node(None).expect("expected value");
+    let c = db.create_node(None).expect("expected value");
+    let etype = db.define_etype("KNOWS").expect("expected value");
+    db.add_edge(a, etype, b).expect("expected value");
+    db.add_edge(b, etype, a).expect("expected value");
+    db.add_edge(b, etype, c).expect("expected value");
+    db.add_edge(c, etype, a).expect("expected value");
+    db.commit().expect("expected value");
+
+    let batches = stream_edges_single(
+      &db,
+      StreamOptions {
+        batch_size: 1,
+        node_filter: Some(HashSet::from([a, b])),
+      },
+    );
+    let mut edges: Vec<(NodeId, NodeId)> = batches
+      .iter()
+      .flatten()
+      .map(|edge| (edge.src, edge.dst))
+      .collect();
+    edges.sort_unstable();
+    assert_eq!(batches.len(), 2);
+    assert_eq!(edges, vec![(a, b), (b, a)]);
+
+    let all = stream_edges_single(&db, StreamOptions::default());
+    assert_eq!(all.iter().map(Vec::len).sum::<usize>(), 4);
+    close_single_file(db).expect("expected value");
+  }
+}
@@ -5,6 +5,7 @@
 #[cfg(feature = "napi")]
 use napi_derive::napi;
 use serde::{Deserialize, Serialize};
+use std::cmp::Ordering;
 use std::collections::{BTreeSet, HashMap, HashSet};
 use std::fmt;
 
@@ -118,13 +119,15 @@ pub enum SectionId {
   VectorData = 26,
   VectorStoreIndex = 27,
   VectorStoreData = 28,
+  Metadata = 29,
 }
 
 impl SectionId {
   pub const COUNT_V1: usize = 23;
   pub const COUNT_V2: usize = 25;
   pub const COUNT_V3: usize = 27;
-  pub const COUNT: usize = 29;
+  pub const COUNT_V4: usize = 29;
+  pub const COUNT: usize = 30;
 
   pub fn from_u32(v: u32) -> Option<Self> {
     match v {
@@ -157,6 +160,7 @@ impl SectionId {
       26 => Some(Self::VectorData),
       27 => Some(Self::VectorStoreIndex),
       28 => Some(Self::VectorStoreData),
+      29 => Some(Self::Metadata),
       _ => None,
     }
   }
@@ -200,6 +204,7 @@ pub enum WalRecordType {
   CreateNode = 10,
   DeleteNode = 11,
   CreateNodesBatch = 12,
+  SetNodeKey = 13,
   AddEdge = 20,
   DeleteEdge = 21,
   AddEdgeProps = 22,
@@ -221,6 +226,8 @@ pub enum WalRecordType {
   BatchVectors = 62,
   SealFragment = 63,
   CompactFragments = 64,
+  // Database metadata
+  SetMetadata = 70,
 }
 
 impl WalRecordType {
@@ -232,6 +239,7 @@ impl WalRecordType {
       10 => Some(Self::CreateNode),
       11 => Some(Self::DeleteNode),
       12 => Some(Self::CreateNodesBatch),
+      13 => Some(Self::SetNodeKey),
       20 => Some(Self::AddEdge),
       21 => Some(Self::DeleteEdge),
       22 => Some(Self::AddEdgeProps),
@@ -252,6 +260,7 @@ impl WalRecordType {
       62 => Some(Self::BatchVectors),
       63 => Some(Self::SealFragment),
       64 => Some(Self::CompactFragments),
+      70 => Some(Self::SetMetadata),
       _ => None,
     }
   }
@@ -286,6 +295,7 @@ pub enum PropValueTag {
   F64 = 3,
   String = 4,
   VectorF32 = 5, // Normalized float32 vector for embeddings
+  Json = 6,      // JSON document, stored as serialized text
 }
 
 impl PropValueTag {
@@ -297,6 +307,7 @@ impl PropValueTag {
       3 => Some(Self::F64),
       4 => Some(Self::String),
       5 => Some(Self::VectorF32),
+      6 => Some(Self::Json),
       _ => None,
     }
   }
@@ -311,6 +322,8 @@ pub enum PropValue {
   F64(f64),
   String(String),
   VectorF32(Vec<f32>),
+  /// Structured sub-document, stored as serialized JSON
+  Json(serde_json::Value),
 }
 
 /// Shared property value for internal storage
@@ -334,8 +347,116 @@ impl PropValue {
       PropValue::F64(_) => PropValueTag::F64,
       PropValue::String(_) => PropValueTag::String,
       PropValue::VectorF32(_) => PropValueTag::VectorF32,
+      PropValue::Json(_) => PropValueTag::Json,
     }
   }
+
+  /// Look up a nested value inside a `Json` document
+  ///
+  /// `path` is a dotted path such as `address.city` or `tags.0`, optionally
+  /// prefixed with `$.`; numeric segments index into arrays. An empty path
+  /// (or `$`) returns the whole document. Non-JSON values always yield `None`.
+  pub fn json_path(&self, path: &str) -> Option<&serde_json::Value> {
+    let PropValue::Json(doc) = self else {
+      return None;
+    };
+    let path = path.strip_prefix('$').unwrap_or(path);
+    let path = path.strip_prefix('.').unwrap_or(path);
+    if path.is_empty() {
+      return Some(doc);
+    }
+    path
+      .split('.')
+      .try_fold(doc, |current, segment| match current {
+        serde_json::Value::Object(map) => map.get(segment),
+        serde_json::Value::Array(items) => items.get(segment.parse::<usize>().ok()?),
+        _ => None,
+      })
+  }
+
+  /// Compare two values under the shared sort order (see [`compare_props`])
+  pub fn total_cmp(&self, other: &PropValue) -> Ordering {
+    compare_props(self, other)
+  }
+
+  /// Position of the value's type in the cross-type order
+  fn sort_rank(&self) -> u8 {
+    match self {
+      PropValue::Null => 0,
+      PropValue::Bool(_) => 1,
+      PropValue::I64(_) | PropValue::F64(_) => 2,
+      PropValue::String(_) => 3,
+      PropValue::VectorF32(_) => 4,
+      PropValue::Json(_) => 5,
+    }
+  }
+}
+
+/// Total order over property values, shared by everything that sorts them
+///
+/// Values of different types order by type first:
+/// `Null < Bool < numbers < String < VectorF32 < Json`. Within a type:
+///
+/// - `false < true`
+/// - `I64` and `F64` compare by numeric value, exactly (no lossy casts).
+///   Floats follow `f64::total_cmp`, so `-0.0 < 0.0` and NaN sorts after
+///   every other number. When an integer and a float are numerically equal,
+///   the integer sorts first.
+/// - strings compare bytewise (UTF-8 code point order)
+/// - vectors compare element-wise with `f32::total_cmp`, then by length
+/// - JSON documents compare by their serialized text
+///
+/// `PropValue` deliberately implements neither `Ord` nor `PartialOrd`: its
+/// derived `PartialEq` treats `0.0 == -0.0` and `NaN != NaN`, which this order
+/// can't agree with. Use `compare_props` with `sort_by` instead.
+pub fn compare_props(a: &PropValue, b: &PropValue) -> Ordering {
+  match (a, b) {
+    (PropValue::Null, PropValue::Null) => Ordering::Equal,
+    (PropValue::Bool(a), PropValue::Bool(b)) => a.cmp(b),
+    (PropValue::I64(a), PropValue::I64(b)) => a.cmp(b),
+    (PropValue::F64(a), PropValue::F64(b)) => a.total_cmp(b),
+    (PropValue::I64(a), PropValue::F64(b)) => cmp_i64_f64(*a, *b).then(Ordering::Less),
+    (PropValue::F64(a), PropValue::I64(b)) => cmp_i64_f64(*b, *a).reverse().then(Ordering::Greater),
+    (PropValue::String(a), PropValue::String(b)) => a.cmp(b),
+    (PropValue::VectorF32(a), PropValue::VectorF32(b)) => a
+      .iter()
+      .zip(b.iter())
+      .map(|(x, y)| x.total_cmp(y))
+      .find(|ord| ord.is_ne())
+      .unwrap_or_else(|| a.len().cmp(&b.len())),
+    (PropValue::Json(a), PropValue::Json(b)) => a.to_string().cmp(&b.to_string()),
+    _ => a.sort_rank().cmp(&b.sort_rank()),
+  }
+}
+
+/// Exact numeric comparison of an integer against a float
+fn cmp_i64_f64(int: i64, float: f64) -> Ordering {
+  // 2^63 is exactly representable; every i64 lies in [-2^63, 2^63)
+  const TWO_POW_63: f64 = 9_223_372_036_854_775_808.0;
+  if float.is_nan() {
+    return if float.is_sign_negative() {
+      Ordering::Greater
+    } else {
+      Ordering::Less
+    };
+  }
+  if float >= TWO_POW_63 {
+    return Ordering::Less;
+  }
+  if float < -TWO_POW_63 {
+    return Ordering::Greater;
+  }
+  let whole = float.trunc();
+  // In range, so the cast is exact
+  int.cmp(&(whole as i64)).then_with(|| {
+    if float > whole {
+      Ordering::Less
+    } else if float < whole {
+      Ordering::Greater
+    } else {
+      Ordering::Equal
+    }
+  })
 }
 
 /// Fixed-width disk encoding for properties (16 bytes)
@@ -420,6 +541,9 @@ pub struct DeltaState {
   // Pending vector operations (keyed by (node_id, prop_key_id))
   // Some(vec) = set, None = delete
   pub pending_vectors: HashMap<(NodeId, PropKeyId), Option<VectorRef>>,
+
+  // Database metadata writes (None = removed)
+  pub metadata: HashMap<String, Option<Vec<u8>>>,
 }
 
 // ============================================================================
@@ -517,6 +641,9 @@ pub struct CacheStats {
   pub query_cache_hits: u64,
   pub query_cache_misses: u64,
   pub query_cache_size: usize,
+  pub key_cache_hits: u64,
+  pub key_cache_misses: u64,
+  pub key_cache_size: usize,
 }
 
 // ============================================================================
@@ -536,6 +663,12 @@ pub struct DbStats {
   pub delta_edges_deleted: usize,
   pub wal_segment: u64,
   pub wal_bytes: u64,
+  /// Snapshot nodes and edges deleted in the delta, as a fraction of all
+  /// snapshot nodes and edges (0.0 for an empty snapshot). Deleted entries
+  /// stay in the snapshot until the next checkpoint rewrites it.
+  pub fragmentation: f64,
+  /// Set when `fragmentation` reaches `COMPACT_FRAGMENTATION_RATIO` (10%) or
+  /// the WAL buffer is at least `COMPACT_WAL_USAGE_RATIO` (80%) full
   pub recommend_compact: bool,
   pub mvcc_stats: Option<MvccStats>,
 }
@@ -549,6 +682,110 @@ pub struct MvccStats {
   pub last_gc_time: u64,
   pub committed_writes_size: usize,
   pub committed_writes_pruned: usize,
+  /// Keys that currently have a version chain
+  pub version_chains: usize,
+  /// Versions retained across all chains
+  pub versions_retained: usize,
+  /// Longest version chain
+  pub chain_depth_max: usize,
+  /// Median version chain length
+  pub chain_depth_p50: usize,
+  /// 99th percentile version chain length
+  pub chain_depth_p99: usize,
+}
+
+/// WAL usage by one record type (see `wal_record_breakdown`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WalRecordStats {
+  pub record_type: WalRecordType,
+  pub count: u64,
+  /// Bytes including record padding
+  pub bytes: u64,
+}
+
+/// Kind of secondary index (see `list_indexes`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum IndexKind {
+  /// Value -> node claims enforcing a `unique` prop
+  Unique,
+  /// Sorted value -> nodes map backing range scans over an `ordered` prop
+  Ordered,
+  /// Vector store holding the embeddings of one property key
+  Vector,
+}
+
+impl IndexKind {
+  pub fn as_str(self) -> &'static str {
+    match self {
+      IndexKind::Unique => "unique",
+      IndexKind::Ordered => "ordered",
+      IndexKind::Vector => "vector",
+    }
+  }
+}
+
+/// One active secondary index (see `list_indexes`)
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IndexInfo {
+  pub kind: IndexKind,
+  /// Owning node type (`None` for vector stores, which span every type)
+  pub node_type: Option<String>,
+  /// Indexed property
+  pub prop_name: String,
+  /// Indexed nodes, or live vectors for a vector store
+  pub entries: u64,
+  /// Rough in-memory footprint; ignores allocator and hash table overhead
+  pub estimated_bytes: u64,
+}
+
+/// The calling thread's active transaction (see `transaction_info`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TransactionInfo {
+  pub txid: TxId,
+  pub read_only: bool,
+  /// MVCC timestamp the transaction reads at (0 when MVCC is disabled)
+  pub snapshot_ts: u64,
+}
+
+/// What a committed transaction changed (see `commit_stats`)
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct CommitStats {
+  pub nodes_created: u64,
+  pub nodes_deleted: u64,
+  pub edges_added: u64,
+  pub edges_deleted: u64,
+  /// Node and edge props set (deletes aren't counted)
+  pub props_set: u64,
+  /// Time from `begin` to the end of the commit
+  pub duration_ms: u64,
+  /// Bytes of WAL records written, BEGIN and COMMIT included
+  pub wal_bytes_written: u64,
+}
+
+impl CommitStats {
+  /// Tally the changes in a transaction's pending delta
+  pub(crate) fn from_pending(pending: &DeltaState) -> Self {
+    let count_set = |props: &HashMap<PropKeyId, Option<PropValueRef>>| {
+      props.values().filter(|value| value.is_some()).count() as u64
+    };
+    let node_props_set: u64 = pending
+      .created_nodes
+      .values()
+      .chain(pending.modified_nodes.values())
+      .filter_map(|node| node.props.as_ref())
+      .map(count_set)
+      .sum();
+    let edge_props_set: u64 = pending.edge_props.values().map(count_set).sum();
+    Self {
+      nodes_created: pending.created_nodes.len() as u64,
+      nodes_deleted: pending.deleted_nodes.len() as u64,
+      edges_added: pending.out_add.values().map(|set| set.len() as u64).sum(),
+      edges_deleted: pending.out_del.values().map(|set| set.len() as u64).sum(),
+      props_set: node_props_set + edge_props_set,
+      duration_ms: 0,
+      wal_bytes_written: 0,
+    }
+  }
 }
 
 /// Database check result
@@ -559,6 +796,17 @@ pub struct CheckResult {
   pub warnings: Vec<String>,
 }
 
+/// Database check-and-repair result
+#[derive(Debug, Clone)]
+pub struct RepairResult {
+  /// Repairs that were applied
+  pub repaired: Vec<String>,
+  /// Problems found but left in place (destructive repairs not allowed)
+  pub skipped: Vec<String>,
+  /// Integrity check after repairs
+  pub check: CheckResult,
+}
+
 // ============================================================================
 // Transaction State
 // ============================================================================
@@ -643,6 +891,21 @@ pub enum TxKey {
   },
 }
 
+impl TxKey {
+  /// Coarse kind of data the key guards, for conflict diagnostics
+  pub fn category(&self) -> &'static str {
+    match self {
+      TxKey::Node(_) => "node",
+      TxKey::Edge { .. } => "edge",
+      TxKey::NodeProp { .. } => "node_prop",
+      TxKey::EdgeProp { .. } => "edge_prop",
+      TxKey::Key(_) => "node_key",
+      TxKey::NeighborsOut { .. } | TxKey::NeighborsIn { .. } => "neighbors",
+      TxKey::NodeLabels(_) | TxKey::NodeLabel { .. } => "node_label",
+    }
+  }
+}
+
 impl fmt::Display for TxKey {
   fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
     match self {
@@ -745,6 +1008,10 @@ pub struct DbHeaderV1 {
   pub wal_secondary_head: u64,
   pub active_wal_region: u8,      // 0=primary, 1=secondary
   pub checkpoint_in_progress: u8, // for crash recovery
+  /// Random id assigned at creation; 0 for databases created before it existed
+  pub db_id: u64,
+  /// Path of the external WAL file (set with `DB_FLAG_EXTERNAL_WAL`)
+  pub wal_path: Option<String>,
 }
 
 /// Size of fixed header fields before reserved area (in bytes)
@@ -767,3 +1034,72 @@ pub struct NodeOpts {
   pub labels: Option<Vec<LabelId>>,
   pub props: Option<HashMap<PropKeyId, PropValue>>,
 }
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_compare_props_cross_type_order() {
+    let ordered = [
+      PropValue::Null,
+      PropValue::Bool(false),
+      PropValue::Bool(true),
+      PropValue::F64(f64::NEG_INFINITY),
+      PropValue::I64(i64::MIN),
+      PropValue::I64(-1),
+      PropValue::F64(-0.5),
+      PropValue::I64(0),
+      PropValue::F64(-0.0),
+      PropValue::F64(0.0),
+      PropValue::F64(0.5),
+      PropValue::I64(1),
+      PropValue::F64(1.0),
+      PropValue::I64(i64::MAX),
+      PropValue::F64(9_223_372_036_854_775_808.0),
+      PropValue::F64(f64::INFINITY),
+      PropValue::F64(f64::NAN),
+      PropValue::String(String::new()),
+      PropValue::String("a".to_string()),
+      PropValue::String("b".to_string()),
+      PropValue::VectorF32(vec![]),
+      PropValue::VectorF32(vec![0.5]),
+      PropValue::VectorF32(vec![0.5, 0.0]),
+      PropValue::VectorF32(vec![1.0]),
+      PropValue::Json(serde_json::json!([])),
+      PropValue::Json(serde_json::json!({"a": 1})),
+    ];
+
+    for (i, a) in ordered.iter().enumerate() {
+      for (j, b) in ordered.iter().enumerate() {
+        assert_eq!(compare_props(a, b), i.cmp(&j), "{a:?} vs {b:?}");
+      }
+    }
+  }
+
+  #[test]
+  fn test_compare_props_exact_for_large_integers() {
+    // 2^53 + 1 is not representable as f64; a lossy cast would call these equal
+    let int = PropValue::I64((1 << 53) + 1);
+    let float = PropValue::F64((1u64 << 53) as f64);
+    assert_eq!(compare_props(&int, &float), Ordering::Greater);
+    assert_eq!(compare_props(&float, &int), Ordering::Less);
+
+    let mut values = vec![
+      PropValue::String("x".to_string()),
+      PropValue::I64(3),
+      PropValue::Null,
+      PropValue::F64(2.5),
+    ];
+    values.sort_by(compare_props);
+    assert_eq!(
+      values,
+      vec![
+        PropValue::Null,
+        PropValue::F64(2.5),
+        PropValue::I64(3),
+        PropValue::String("x".to_string()),
+      ]
+    );
+  }
+}
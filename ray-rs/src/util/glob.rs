@@ -0,0 +1,69 @@
+//! Glob matching for node keys
+//!
+//! `*` matches any run of characters (including none) and `?` exactly one.
+//! There is no escaping and no character classes.
+
+/// Text before the first wildcard, which every match starts with
+pub fn literal_prefix(pattern: &str) -> &str {
+  let end = pattern.find(['*', '?']).unwrap_or(pattern.len());
+  &pattern[..end]
+}
+
+/// Whether all of `text` matches `pattern`
+pub fn glob_match(pattern: &str, text: &str) -> bool {
+  let pattern: Vec<char> = pattern.chars().collect();
+  let text: Vec<char> = text.chars().collect();
+  let (mut p, mut t) = (0, 0);
+  // Pattern position after the last `*`, and the text position it resumes at
+  let mut backtrack: Option<(usize, usize)> = None;
+
+  while t < text.len() {
+    match pattern.get(p) {
+      Some('*') => {
+        p += 1;
+        backtrack = Some((p, t));
+      }
+      Some(&c) if c == '?' || c == text[t] => {
+        p += 1;
+        t += 1;
+      }
+      _ => match backtrack {
+        // Let the last `*` swallow one more character and retry
+        Some((after_star, resume)) => {
+          p = after_star;
+          t = resume + 1;
+          backtrack = Some((after_star, t));
+        }
+        None => return false,
+      },
+    }
+  }
+  pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  #[test]
+  fn test_literal_prefix() {
+    assert_eq!(literal_prefix("user:admin*"), "user:admin");
+    assert_eq!(literal_prefix("user:?x"), "user:");
+    assert_eq!(literal_prefix("*admin"), "");
+    assert_eq!(literal_prefix("user:1"), "user:1");
+  }
+
+  #[test]
+  fn test_glob_match() {
+    assert!(glob_match("user:admin*", "user:admin"));
+    assert!(glob_match("user:admin*", "user:admin42"));
+    assert!(!glob_match("user:admin*", "user:bob"));
+    assert!(glob_match("user:?", "user:é"));
+    assert!(!glob_match("user:?", "user:ab"));
+    assert!(glob_match("*:a*b", "user:aXbYb"));
+    assert!(!glob_match("*:a*b", "user:aXbYc"));
+    assert!(glob_match("**", ""));
+    assert!(glob_match("user:1", "user:1"));
+    assert!(!glob_match("user:1", "user:12"));
+  }
+}
@@ -5,8 +5,10 @@
 pub mod binary;
 pub mod compression;
 pub mod crc;
+pub mod glob;
 pub mod hash;
 pub mod heap;
+pub mod hll;
 pub mod mmap;
 
 // Re-export commonly used items
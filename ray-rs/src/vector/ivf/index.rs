@@ -20,6 +20,14 @@ use crate::vector::types::{
 
 use super::kmeans::{kmeans_parallel, KMeansConfig};
 
+/// Updates beyond the initial load (as a fraction of indexed vectors) after
+/// which `needs_retrain` reports drift
+const RETRAIN_UPDATE_RATIO: f32 = 0.3;
+
+/// Largest-cluster to average-cluster size ratio after which
+/// `needs_retrain` reports imbalance
+const RETRAIN_IMBALANCE_RATIO: f32 = 4.0;
+
 // ============================================================================
 // IVF Index
 // ============================================================================
@@ -41,6 +49,12 @@ pub struct IvfIndex {
   training_vectors: Option<Vec<f32>>,
   /// Number of training vectors
   training_count: usize,
+  /// Cluster and list position of each indexed vector
+  positions: HashMap<u64, (usize, usize)>,
+  /// Inserts, deletes and removes since the last training
+  incremental_updates: usize,
+  /// Number of vectors the centroids were trained on
+  trained_vectors: usize,
 }
 
 impl IvfIndex {
@@ -54,6 +68,9 @@ impl IvfIndex {
       trained: false,
       training_vectors: Some(Vec::new()),
       training_count: 0,
+      positions: HashMap::new(),
+      incremental_updates: 0,
+      trained_vectors: 0,
     }
   }
 
@@ -64,14 +81,27 @@ impl IvfIndex {
 
   /// Create an IVF index from serialized data
   ///
-  /// Used by deserialization to reconstruct an index.
+  /// Used by deserialization to reconstruct an index. `incremental_updates`
+  /// and `trained_vectors` restore the drift state behind
+  /// [`needs_retrain`](Self::needs_retrain).
   pub fn from_serialized(
     config: IvfConfig,
     centroids: Vec<f32>,
     inverted_lists: HashMap<usize, Vec<u64>>,
     dimensions: usize,
     trained: bool,
+    incremental_updates: usize,
+    trained_vectors: usize,
   ) -> Self {
+    let positions = inverted_lists
+      .iter()
+      .flat_map(|(&cluster, list)| {
+        list
+          .iter()
+          .enumerate()
+          .map(move |(idx, &vector_id)| (vector_id, (cluster, idx)))
+      })
+      .collect();
     Self {
       config,
       centroids,
@@ -80,6 +110,9 @@ impl IvfIndex {
       trained,
       training_vectors: None,
       training_count: 0,
+      positions,
+      incremental_updates,
+      trained_vectors,
     }
   }
 
@@ -150,7 +183,9 @@ impl IvfIndex {
 
     self.trained = true;
     self.training_vectors = None;
+    self.trained_vectors = self.training_count;
     self.training_count = 0;
+    self.incremental_updates = 0;
 
     Ok(())
   }
@@ -158,7 +193,15 @@ impl IvfIndex {
   /// Insert a vector into the index
   ///
   /// The vector should already be stored in the manifest; this just adds it to the index.
-  pub fn insert(&mut self, vector_id: u64, vector: &[f32]) -> Result<(), IvfError> {
+  /// It goes to its nearest current centroid without retraining. Centroids
+  /// stay fixed, so as the data drifts away from the training distribution,
+  /// lists become unbalanced and probing `n_probe` clusters finds fewer true
+  /// neighbors (lower recall); [`needs_retrain`](Self::needs_retrain) says
+  /// when to rebuild.
+  ///
+  /// With `upsert`, an indexed `vector_id` is moved to its new cluster;
+  /// otherwise inserting it again fails with [`IvfError::AlreadyIndexed`].
+  pub fn insert(&mut self, vector_id: u64, vector: &[f32], upsert: bool) -> Result<(), IvfError> {
     if !self.trained {
       return Err(IvfError::NotTrained);
     }
@@ -170,39 +213,71 @@ impl IvfIndex {
       });
     }
 
+    if self.positions.contains_key(&vector_id) {
+      if !upsert {
+        return Err(IvfError::AlreadyIndexed(vector_id));
+      }
+      self.remove_from_lists(vector_id);
+    }
+
     // Find nearest centroid
     let cluster = self.find_nearest_centroid(vector);
 
     // Add to inverted list
-    self
-      .inverted_lists
-      .entry(cluster)
-      .or_default()
-      .push(vector_id);
+    let list = self.inverted_lists.entry(cluster).or_default();
+    self.positions.insert(vector_id, (cluster, list.len()));
+    list.push(vector_id);
+    self.incremental_updates += 1;
 
     Ok(())
   }
 
   /// Delete a vector from the index
   ///
-  /// Returns true if deleted, false if not found.
-  pub fn delete(&mut self, vector_id: u64, vector: &[f32]) -> bool {
+  /// Returns true if deleted, false if not found. The vector data is no
+  /// longer needed to find its cluster; see [`Self::remove`].
+  pub fn delete(&mut self, vector_id: u64, _vector: &[f32]) -> bool {
+    self.remove(vector_id)
+  }
+
+  /// Remove a vector by ID without needing its data
+  ///
+  /// Returns true if the vector was indexed.
+  pub fn remove(&mut self, vector_id: u64) -> bool {
     if !self.trained {
       return false;
     }
-
-    // Find which cluster it's in
-    let cluster = self.find_nearest_centroid(vector);
-
-    if let Some(list) = self.inverted_lists.get_mut(&cluster) {
-      if let Some(idx) = list.iter().position(|&id| id == vector_id) {
-        // Remove from list (swap with last for O(1))
-        list.swap_remove(idx);
-        return true;
-      }
+    let removed = self.remove_from_lists(vector_id);
+    if removed {
+      self.incremental_updates += 1;
     }
+    removed
+  }
 
-    false
+  /// Whether incremental updates have drifted enough to warrant retraining
+  ///
+  /// Heuristic: inserts, deletes and removes since training count as drift
+  /// once they exceed the number of training vectors, since loading those is
+  /// how an index is built. True once the drift exceeds 30% of the indexed
+  /// vectors, or the largest inverted list grows past 4x the average list
+  /// size. Both signal that the fixed centroids no longer partition the data
+  /// well.
+  pub fn needs_retrain(&self) -> bool {
+    if !self.trained {
+      return false;
+    }
+    let drift = self
+      .incremental_updates
+      .saturating_sub(self.trained_vectors);
+    let stats = self.stats();
+    if stats.total_vectors == 0 {
+      return drift > 0;
+    }
+    if drift as f32 > RETRAIN_UPDATE_RATIO * stats.total_vectors as f32 {
+      return true;
+    }
+    stats.total_vectors >= self.config.n_clusters
+      && stats.max_cluster_size as f32 > RETRAIN_IMBALANCE_RATIO * stats.avg_vectors_per_cluster
   }
 
   /// Search for k nearest neighbors
@@ -450,7 +525,7 @@ impl IvfIndex {
       let offset = local_row_idx * manifest.config.dimensions;
       let vector = &row_group.data[offset..offset + manifest.config.dimensions];
 
-      self.insert(vector_id, vector)?;
+      self.insert(vector_id, vector, false)?;
     }
 
     Ok(())
@@ -498,12 +573,40 @@ impl IvfIndex {
     self.trained = false;
     self.training_vectors = Some(Vec::new());
     self.training_count = 0;
+    self.positions.clear();
+    self.incremental_updates = 0;
+    self.trained_vectors = 0;
   }
 
   // ========================================================================
   // Helper Methods
   // ========================================================================
 
+  /// Drop `vector_id` from the inverted list holding it, in O(1)
+  fn remove_from_lists(&mut self, vector_id: u64) -> bool {
+    let Some((cluster, idx)) = self.positions.remove(&vector_id) else {
+      return false;
+    };
+    let Some(list) = self.inverted_lists.get_mut(&cluster) else {
+      return false;
+    };
+    list.swap_remove(idx);
+    if let Some(&moved) = list.get(idx) {
+      self.positions.insert(moved, (cluster, idx));
+    }
+    true
+  }
+
+  /// Inserts, deletes and removes since the last training
+  pub fn incremental_updates(&self) -> usize {
+    self.incremental_updates
+  }
+
+  /// Number of vectors the centroids were trained on
+  pub fn trained_vectors(&self) -> usize {
+    self.trained_vectors
+  }
+
   /// Find nearest centroid for a vector
   fn find_nearest_centroid(&self, vector: &[f32]) -> usize {
     let distance_fn = self.config.metric.distance_fn();
@@ -748,6 +851,7 @@ pub enum IvfError {
   NotEnoughTrainingVectors { n: usize, k: usize },
   DimensionMismatch { expected: usize, got: usize },
   TrainingFailed(String),
+  AlreadyIndexed(u64),
 }
 
 impl std::fmt::Display for IvfError {
@@ -763,6 +867,7 @@ impl std::fmt::Display for IvfError {
         write!(f, "Dimension mismatch: expected {expected}, got {got}")
       }
       IvfError::TrainingFailed(msg) => write!(f, "Training failed: {msg}"),
+      IvfError::AlreadyIndexed(id) => write!(f, "Vector {id} is already indexed"),
     }
   }
 }
@@ -853,7 +958,7 @@ mod tests {
 
     // Insert
     let vector = vec![5.0, 0.0, 0.0, 1.0];
-    index.insert(0, &vector).expect("expected value");
+    index.insert(0, &vector, false).expect("expected value");
 
     let stats = index.stats();
     assert_eq!(stats.total_vectors, 1);
@@ -864,7 +969,7 @@ mod tests {
     let mut index = create_test_index(4, 2);
 
     let vector = vec![1.0, 0.0, 0.0, 0.0];
-    let result = index.insert(0, &vector);
+    let result = index.insert(0, &vector, false);
 
     assert!(matches!(result, Err(IvfError::NotTrained)));
   }
@@ -885,7 +990,7 @@ mod tests {
 
     // Insert and delete
     let vector = vec![5.0, 0.0, 0.0, 1.0];
-    index.insert(0, &vector).expect("expected value");
+    index.insert(0, &vector, false).expect("expected value");
     assert!(index.delete(0, &vector));
     assert!(!index.delete(0, &vector)); // Already deleted
 
@@ -893,6 +998,62 @@ mod tests {
     assert_eq!(stats.total_vectors, 0);
   }
 
+  #[test]
+  fn test_ivf_incremental_updates_and_retrain_hint() {
+    let mut index = create_test_index(4, 2);
+
+    let mut vectors = Vec::new();
+    for i in 0..10 {
+      vectors.extend_from_slice(&[i as f32, 0.0, 0.0, 1.0]);
+    }
+    index
+      .add_training_vectors(&vectors, 10)
+      .expect("expected value");
+    index.train().expect("expected value");
+    for i in 0..10u64 {
+      index
+        .insert(i, &[i as f32, 0.0, 0.0, 1.0], false)
+        .expect("expected value");
+    }
+    // Loading the training set is the build, not drift
+    assert_eq!(index.incremental_updates(), 10);
+    assert_eq!(index.trained_vectors(), 10);
+    assert!(!index.needs_retrain());
+
+    assert!(matches!(
+      index.insert(3, &[8.0, 0.0, 0.0, 1.0], false),
+      Err(IvfError::AlreadyIndexed(3))
+    ));
+    // Upserting an indexed ID moves it rather than duplicating it
+    index
+      .insert(3, &[8.0, 0.0, 0.0, 1.0], true)
+      .expect("expected value");
+    assert_eq!(index.stats().total_vectors, 10);
+    assert!(index.remove(3));
+    assert!(!index.remove(3));
+    assert_eq!(index.stats().total_vectors, 9);
+    assert_eq!(index.incremental_updates(), 12);
+    assert!(!index.needs_retrain());
+
+    // A third update is drift past 30% of the 9 indexed vectors
+    assert!(index.delete(4, &[4.0, 0.0, 0.0, 1.0]));
+    assert!(index.needs_retrain());
+
+    // Removal stays correct as swap_remove reorders the lists
+    for i in [0u64, 9, 5, 1, 2, 6, 7, 8] {
+      assert!(index.remove(i), "vector {i} should be indexed");
+    }
+    assert_eq!(index.stats().total_vectors, 0);
+
+    index.clear();
+    assert!(!index.needs_retrain());
+    assert_eq!(index.incremental_updates(), 0);
+    assert!(matches!(
+      index.insert(1, &[1.0, 0.0, 0.0, 1.0], true),
+      Err(IvfError::NotTrained)
+    ));
+  }
+
   #[test]
   fn test_ivf_stats() {
     let mut index = create_test_index(4, 2);
@@ -187,7 +187,9 @@ pub fn ivf_serialized_size(index: &IvfIndex) -> usize {
 ///   - trained (1)
 ///   - reserved (1)
 ///   - metric (1): 0=cosine, 1=euclidean, 2=dot
-///   - reserved (13)
+///   - incremental_updates (8): inserts/deletes since training
+///   - trained_vectors (4): vectors the centroids were trained on
+///   - reserved (1)
 /// - centroid_count (4) - actual number of f32 values in centroids
 /// - Centroids (centroid_count * 4 bytes)
 /// - num_lists (4)
@@ -207,7 +209,9 @@ pub fn serialize_ivf(index: &IvfIndex) -> Vec<u8> {
   buffer.push(if index.trained { 1 } else { 0 });
   buffer.push(0); // reserved
   buffer.push(metric_to_u8(index.config.metric));
-  buffer.extend_from_slice(&[0u8; 13]); // reserved
+  buffer.extend_from_slice(&(index.incremental_updates() as u64).to_le_bytes());
+  buffer.extend_from_slice(&(index.trained_vectors() as u32).to_le_bytes());
+  buffer.push(0); // reserved
 
   // Centroid count + Centroids
   buffer.extend_from_slice(&(index.centroids.len() as u32).to_le_bytes());
@@ -257,7 +261,12 @@ pub fn deserialize_ivf(buffer: &[u8]) -> Result<IvfIndex, SerializeError> {
   offset += 1; // skip reserved
   let metric = u8_to_metric(buffer[offset])?;
   offset += 1;
-  offset += 13; // skip reserved
+  // Zero in indexes written before drift was persisted
+  let incremental_updates = read_u64_at(buffer, offset, "IVF incremental updates")? as usize;
+  offset += 8;
+  let trained_vectors = read_u32_at(buffer, offset, "IVF trained vectors")? as usize;
+  offset += 4;
+  offset += 1; // skip reserved
 
   let config = IvfConfig {
     n_clusters,
@@ -316,6 +325,8 @@ pub fn deserialize_ivf(buffer: &[u8]) -> Result<IvfIndex, SerializeError> {
     inverted_lists,
     dimensions,
     trained,
+    incremental_updates,
+    trained_vectors,
   ))
 }
 
@@ -743,6 +754,38 @@ mod tests {
     );
   }
 
+  #[test]
+  fn test_ivf_round_trip_keeps_drift_state() {
+    let config = IvfConfig::new(2).with_metric(DistanceMetric::Euclidean);
+    let mut index = IvfIndex::new(4, config);
+    let mut vectors = Vec::new();
+    for i in 0..4 {
+      vectors.extend_from_slice(&[i as f32, 0.0, 0.0, 1.0]);
+    }
+    index
+      .add_training_vectors(&vectors, 4)
+      .expect("expected value");
+    index.train().expect("expected value");
+    for i in 0..6u64 {
+      index
+        .insert(i, &[i as f32, 0.0, 0.0, 1.0], false)
+        .expect("expected value");
+    }
+    assert!(index.remove(1));
+    assert!(index.needs_retrain());
+
+    let serialized = serialize_ivf(&index);
+    let mut deserialized = deserialize_ivf(&serialized).expect("expected value");
+
+    assert_eq!(deserialized.incremental_updates(), 7);
+    assert_eq!(deserialized.trained_vectors(), 4);
+    assert!(deserialized.needs_retrain());
+    // Removal by ID works on the rebuilt index
+    assert!(deserialized.remove(4));
+    assert!(!deserialized.remove(1));
+    assert_eq!(deserialized.stats().total_vectors, 4);
+  }
+
   #[test]
   fn test_manifest_round_trip_empty() {
     let config = VectorStoreConfig::new(4)
@@ -2,7 +2,9 @@ use std::collections::HashSet;
 use std::env;
 use std::sync::{Arc, Barrier};
 
-use kitedb::core::single_file::{close_single_file, open_single_file, SingleFileOpenOptions};
+use kitedb::core::single_file::{
+  close_single_file, open_single_file, SingleFileDB, SingleFileOpenOptions,
+};
 use kitedb::replication::primary::default_replication_sidecar_path;
 use kitedb::replication::types::CommitToken;
 use kitedb::replication::types::ReplicationRole;
@@ -229,3 +231,82 @@ fn crash_after_commit_token_return_keeps_token_durable_on_reopen() {
 
   close_single_file(reopened).expect("close reopened primary");
 }
+
+#[test]
+fn missing_sidecar_directory_is_reported_only_after_primary_commits() {
+  let dir = tempfile::tempdir().expect("tempdir");
+  let db_path = dir.path().join("phase-b-missing-sidecar.kitedb");
+  let sidecar = default_replication_sidecar_path(&db_path);
+  let options = || {
+    SingleFileOpenOptions::new()
+      .replication_role(ReplicationRole::Primary)
+      .create_if_missing(false)
+  };
+  let sidecar_warning = |db: &SingleFileDB| {
+    db.open_warnings()
+      .iter()
+      .any(|warning| warning.contains("sidecar"))
+  };
+
+  // Written before the database was a primary
+  let db = open_single_file(&db_path, SingleFileOpenOptions::new()).expect("open db");
+  db.begin(false).expect("begin");
+  db.create_node(Some("n0")).expect("create node");
+  db.commit().expect("commit");
+  close_single_file(db).expect("close db");
+
+  let db = open_single_file(&db_path, options()).expect("open as primary");
+  assert!(!sidecar_warning(&db));
+  close_single_file(db).expect("close db");
+
+  // No commit as primary yet, so nothing was lost
+  std::fs::remove_dir_all(&sidecar).expect("remove sidecar");
+  let db = open_single_file(&db_path, options()).expect("reopen recreates sidecar");
+  assert!(sidecar.is_dir());
+  assert!(!sidecar_warning(&db));
+  db.begin(false).expect("begin");
+  db.create_node(Some("n1")).expect("create node");
+  db.commit_with_token().expect("commit").expect("token");
+  close_single_file(db).expect("close db");
+
+  std::fs::remove_dir_all(&sidecar).expect("remove sidecar");
+  let db = open_single_file(&db_path, options()).expect("reopen recreates sidecar");
+  assert!(sidecar.is_dir());
+  assert!(sidecar_warning(&db));
+  assert!(db.node_by_key("n1").is_some());
+  close_single_file(db).expect("close db");
+}
+
+#[test]
+fn missing_manifest_segment_is_reported_as_corruption() {
+  let dir = tempfile::tempdir().expect("tempdir");
+  let db_path = dir.path().join("phase-b-missing-segment.kitedb");
+  let sidecar = default_replication_sidecar_path(&db_path);
+  let options = || {
+    SingleFileOpenOptions::new()
+      .replication_role(ReplicationRole::Primary)
+      .replication_segment_max_bytes(1)
+  };
+
+  let db = open_single_file(&db_path, options()).expect("open db");
+  for i in 0..3 {
+    db.begin(false).expect("begin");
+    db.create_node(Some(&format!("n{i}"))).expect("create node");
+    db.commit_with_token().expect("commit").expect("token");
+  }
+  close_single_file(db).expect("close db");
+
+  let mut segments: Vec<_> = std::fs::read_dir(&sidecar)
+    .expect("read sidecar")
+    .map(|entry| entry.expect("entry").path())
+    .filter(|path| path.extension().is_some_and(|ext| ext == "rlog"))
+    .collect();
+  segments.sort();
+  assert!(segments.len() > 1, "expected rolled segments: {segments:?}");
+  std::fs::remove_file(&segments[0]).expect("remove segment");
+
+  let err = open_single_file(&db_path, options())
+    .err()
+    .expect("open with missing segment should fail");
+  assert!(err.to_string().contains("corrupt"), "{err}");
+}
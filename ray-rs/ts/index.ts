@@ -46,10 +46,16 @@ export {
   int,
   float,
   bool,
+  oneOf,
   vector,
   any,
   optional,
   withDefault,
+  unique,
+  ordered,
+  withUnit,
+  normalized,
+  noSelfLoops,
   defineNode,
   defineEdge,
 } from './schema'
@@ -84,16 +90,34 @@ import {
 } from '../index'
 
 import type {
+  JsBatchOptions,
+  JsGetOptions,
+  JsGetByIdsOptions,
   JsKiteOptions,
+  JsListOptions,
+  JsSetEdgePropOptions,
+  JsSetPropBulkOptions,
   JsNodeSpec,
   JsEdgeSpec,
   JsPropSpec,
   JsPropValue,
   JsSyncMode,
+  JsTraversalDirection,
   JsTraverseOptions,
   JsPathResult,
   JsFullEdge,
+  JsLinkByKeyOptions,
+  JsLinkOptions,
+  JsLinkByKeyResult,
+  JsLinkManyResult,
+  JsMergeOptions,
+  JsMergeResult,
+  JsSchemaChange,
+  PaginationOptions,
   Database,
+  KiteExploration,
+  KiteNodeCursor,
+  KiteReachableCursor,
   KiteInsertExecutorSingle,
   KiteInsertExecutorMany,
   KiteUpsertExecutorSingle,
@@ -119,8 +143,16 @@ declare module '../index' {
   interface Kite {
     get_ref(nodeType: string, key: unknown): object | null
     get_id(nodeType: string, key: unknown): number | null
-    get_by_id(nodeId: number, props?: Array<string> | undefined | null): object | null
-    get_by_ids(nodeIds: Array<number>, props?: Array<string> | undefined | null): Array<object>
+    get_by_id(
+      nodeId: number,
+      props?: Array<string> | undefined | null,
+      options?: JsGetOptions | undefined | null,
+    ): object | null
+    get_by_ids(
+      nodeIds: Array<number>,
+      props?: Array<string> | undefined | null,
+      options?: JsGetByIdsOptions | undefined | null,
+    ): Array<object | null>
     get_prop(nodeId: number, propName: string): JsPropValue | null
     get_edge_prop(src: number, edgeType: string, dst: number, propName: string): JsPropValue | null
     get_edge_props(src: number, edgeType: string, dst: number): Record<string, JsPropValue>
@@ -153,6 +185,12 @@ type UpsertExecutorMany<N extends NodeSpec> = Omit<KiteUpsertExecutorMany, 'retu
   returning(): Array<InferNode<N>>
 }
 
+/** Node read with `{ lazy: true }`: props load on first access */
+export type LazyNode<T = NodeObject> = T & {
+  /** Load every prop at once and return them as a plain object */
+  materialize(): T
+}
+
 function withToArray<T, U = T>(items: T[], toArray?: () => U[]): ArrayWithToArray<T, U> {
   const output = items as ArrayWithToArray<T, U>
   if (!output.toArray) {
@@ -165,6 +203,89 @@ function isRecord(value: unknown): value is Record<string, unknown> {
   return value !== null && typeof value === 'object' && !Array.isArray(value)
 }
 
+function runBatchOperation(db: Kite, op: any): unknown {
+  if (typeof op === 'function') {
+    return op(db)
+  }
+  if (op && typeof op.returning === 'function') {
+    return op.returning()
+  }
+  if (op && typeof op.execute === 'function') {
+    return op.execute()
+  }
+  throw new Error('Unsupported batch operation')
+}
+
+/** Keys a lazy node never loads from the database: probed by `await` and `JSON.stringify` */
+const NOT_LAZY_PROPS = new Set(['then', 'toJSON'])
+
+/**
+ * Wrap a node read with `{ lazy: true }` so each prop is fetched from `db` on
+ * first access and cached. The node holds on to `db` and its node id, so
+ * `db` must stay open while the node is used; once the node is deleted its
+ * props read as `undefined`, even if another node takes its key.
+ *
+ * `in` and property descriptors load a single prop like a read does; key
+ * enumeration (`Object.keys`, spread, `JSON.stringify`) loads every prop.
+ */
+function lazyNode(db: NativeKite, node: Record<string, unknown>, options?: JsGetOptions): LazyNode {
+  const id = node.id as number
+  // Edges were attached by the initial read; prop loads don't need them again
+  const readOptions = { ...options, lazy: false, edges: undefined }
+  const loaded = new Map<string, unknown>()
+  let materialized = false
+
+  const isLazy = (target: Record<string, unknown>, prop: string | symbol): prop is string =>
+    typeof prop === 'string' && prop !== 'materialize' && !NOT_LAZY_PROPS.has(prop) && !(prop in target)
+
+  const load = (prop: string): unknown => {
+    if (!materialized && !loaded.has(prop)) {
+      const found = db.get_by_id(id, [prop], readOptions) as Record<string, unknown> | null
+      loaded.set(prop, found?.[prop])
+    }
+    return loaded.get(prop)
+  }
+
+  const loadAll = () => {
+    if (!materialized) {
+      const full = db.get_by_id(id, undefined, readOptions) as Record<string, unknown> | null
+      loaded.clear()
+      for (const [name, value] of Object.entries(full ?? {})) {
+        if (!(name in node)) loaded.set(name, value)
+      }
+      materialized = true
+    }
+  }
+
+  const materialize = (): NodeObject => {
+    loadAll()
+    return { ...node, ...Object.fromEntries(loaded) } as NodeObject
+  }
+
+  return new Proxy(node, {
+    get(target, prop, receiver) {
+      if (prop === 'materialize') return materialize
+      return isLazy(target, prop) ? load(prop) : Reflect.get(target, prop, receiver)
+    },
+    has(target, prop) {
+      return isLazy(target, prop) ? load(prop) !== undefined : Reflect.has(target, prop)
+    },
+    ownKeys(target) {
+      loadAll()
+      const keys = Reflect.ownKeys(target)
+      for (const [name, value] of loaded) {
+        if (value !== undefined && !(name in target)) keys.push(name)
+      }
+      return keys
+    },
+    getOwnPropertyDescriptor(target, prop) {
+      if (!isLazy(target, prop)) return Reflect.getOwnPropertyDescriptor(target, prop)
+      const value = load(prop)
+      return value === undefined ? undefined : { value, writable: true, enumerable: true, configurable: true }
+    },
+  }) as LazyNode
+}
+
 function nodeName(nodeType: NodeLike): string {
   return typeof nodeType === 'string' ? nodeType : nodeType.name
 }
@@ -317,6 +438,21 @@ export class KiteTraversal extends NativeKiteTraversal {
     )
   }
 
+  allowedNodeTypes(nodeTypes: Array<NodeLike>): KiteTraversal {
+    return KiteTraversal.wrap(
+      super.allowedNodeTypes(nodeTypes.map(nodeName)),
+      (this as { __db?: Kite }).__db,
+    )
+  }
+
+  includeArchived(): KiteTraversal {
+    return KiteTraversal.wrap(super.includeArchived(), (this as { __db?: Kite }).__db)
+  }
+
+  asOf(timestamp: number): KiteTraversal {
+    return KiteTraversal.wrap(super.asOf(timestamp), (this as { __db?: Kite }).__db)
+  }
+
   take(limit: number): KiteTraversal {
     return KiteTraversal.wrap(super.take(limit), (this as { __db?: Kite }).__db)
   }
@@ -566,13 +702,22 @@ export class Kite extends NativeKite {
     return super.checkpoint()
   }
 
-  batch(operations: Array<any>): any[] {
+  /**
+   * Execute a batch of operations, atomically by default.
+   *
+   * With `{ bestEffort: true }` each operation is applied on its own and the
+   * results are `{ ok: true, type, affected, ... }` or `{ ok: false, error }`
+   * per operation, so a few bad rows don't abort a large load. Builders and
+   * functions report `type: 'custom'` with their return value in `value`.
+   */
+  batch(operations: Array<any>, options?: JsBatchOptions): any[] {
     if (operations.length === 0) {
       return []
     }
 
     const nativeOps = new Set([
       'createNode',
+      'createAndLink',
       'deleteNode',
       'link',
       'linkWithProps',
@@ -591,7 +736,25 @@ export class Kite extends NativeKite {
     })
 
     if (isNativeBatch) {
-      return super.batch(operations as Array<object>) as unknown as any[]
+      return super.batch(operations as Array<object>, options) as unknown as any[]
+    }
+
+    if (options?.bestEffort) {
+      if (this.hasTransaction()) {
+        throw new Error('Best-effort batch cannot run inside a transaction')
+      }
+      return operations.map((op) => {
+        try {
+          const value = runBatchOperation(this, op)
+          if (value && typeof (value as { then?: unknown }).then === 'function') {
+            throw new Error('Batch operations must be synchronous')
+          }
+          const affected = Array.isArray(value) ? value.length : 1
+          return { ok: true, type: 'custom', affected, value }
+        } catch (err) {
+          return { ok: false, error: err instanceof Error ? err.message : String(err) }
+        }
+      })
     }
 
     const inTransaction = this.hasTransaction()
@@ -602,16 +765,7 @@ export class Kite extends NativeKite {
     try {
       const results: any[] = []
       for (const op of operations) {
-        let value
-        if (typeof op === 'function') {
-          value = op(this)
-        } else if (op && typeof op.returning === 'function') {
-          value = op.returning()
-        } else if (op && typeof op.execute === 'function') {
-          value = op.execute()
-        } else {
-          throw new Error('Unsupported batch operation')
-        }
+        const value = runBatchOperation(this, op)
 
         if (value && typeof (value as { then?: unknown }).then === 'function') {
           if (!inTransaction) {
@@ -688,8 +842,16 @@ export class Kite extends NativeKite {
     return results
   }
 
-  get(nodeType: NodeLike, key: unknown, props?: NodePropsSelection): object | null {
-    return super.get(nodeName(nodeType), key, props)
+  /**
+   * Get a node by key
+   *
+   * With `{ lazy: true }` only `id`/`key`/`type` are read up front and other
+   * props are fetched on first access; call `materialize()` to load them all.
+   * The database must stay open while a lazy node is used.
+   */
+  get(nodeType: NodeLike, key: unknown, props?: NodePropsSelection, options?: JsGetOptions): object | null {
+    const node = super.get(nodeName(nodeType), key, props, options)
+    return node && options?.lazy ? lazyNode(this, node as Record<string, unknown>, options) : node
   }
 
   getRef(nodeType: NodeLike, key: unknown): object | null {
@@ -700,27 +862,183 @@ export class Kite extends NativeKite {
     return super.get_id(nodeName(nodeType), key)
   }
 
-  getById(nodeId: number, props?: NodePropsSelection): object | null {
-    return super.get_by_id(nodeId, props)
+  getById(nodeId: number, props?: NodePropsSelection, options?: JsGetOptions): object | null {
+    const node = super.get_by_id(nodeId, props, options)
+    return node && options?.lazy ? lazyNode(this, node as Record<string, unknown>, options) : node
+  }
+
+  /**
+   * Register a computed prop for a node type, merged into nodes read with
+   * `{ includeVirtual: true }`. Each virtual prop adds a JS callback per node
+   * read, so it is only applied when asked for, and only by `get` and
+   * `getById`; bulk reads return stored props only.
+   *
+   * @example
+   * ```typescript
+   * db.defineVirtualProp(user, 'fullName', (p) => `${p.first} ${p.last}`)
+   * db.get(user, 'alice', undefined, { includeVirtual: true })
+   * ```
+   */
+  defineVirtualProp(
+    nodeType: NodeLike,
+    name: string,
+    compute: (props: Record<string, unknown>) => unknown,
+  ): void {
+    super.defineVirtualProp(nodeName(nodeType), name, compute)
+  }
+
+  /**
+   * Watch a prop on a node type. `callback` runs after each commit that
+   * changes it, once per changed node; unset values are `null`. Returns an
+   * id to pass to `offPropChange`.
+   *
+   * @example
+   * ```typescript
+   * const id = db.onPropChange(user, 'name', ({ nodeId, oldValue, newValue }) => {
+   *   console.log(`${nodeId}: ${oldValue} -> ${newValue}`)
+   * })
+   * db.offPropChange(id)
+   * ```
+   */
+  onPropChange(
+    nodeType: NodeLike,
+    propName: string,
+    callback: (change: { nodeId: number; oldValue: unknown; newValue: unknown }) => unknown,
+  ): number {
+    return super.onPropChange(nodeName(nodeType), propName, callback)
+  }
+
+  schemaDiff(target: KiteOptions): Array<JsSchemaChange> {
+    return super.schemaDiff(optionsToNative(target))
   }
 
-  getByIds(nodeIds: Array<NodeIdLike>, props?: NodePropsSelection): Array<object> {
+  getMany(nodeType: NodeLike, keys: Array<unknown>, props?: NodePropsSelection): Array<object | null> {
+    return super.getMany(nodeName(nodeType), keys, props)
+  }
+
+  getByIds(nodeIds: Array<NodeIdLike>, props?: NodePropsSelection, options?: JsGetByIdsOptions): Array<object | null> {
     const ids = nodeIds.map((id) => nodeId(id))
-    return super.get_by_ids(ids, props)
+    return super.get_by_ids(ids, props, options)
+  }
+
+  neighbors(
+    node: NodeIdLike,
+    edgeType?: EdgeLike | null,
+    direction?: JsTraversalDirection | null,
+    props?: NodePropsSelection,
+  ): Array<NodeObject> {
+    return super.neighbors(nodeId(node), edgeNameOptional(edgeType), direction, props) as Array<NodeObject>
+  }
+
+  degree(node: NodeIdLike, direction?: JsTraversalDirection | null, edgeType?: EdgeLike | null): number {
+    return super.degree(nodeId(node), direction, edgeNameOptional(edgeType))
+  }
+
+  degrees(
+    nodes: Array<NodeIdLike>,
+    direction?: JsTraversalDirection | null,
+    edgeType?: EdgeLike | null,
+  ): Record<string, number> {
+    return super.degrees(
+      nodes.map((node) => nodeId(node)),
+      direction,
+      edgeNameOptional(edgeType),
+    )
   }
 
   getProp(node: NodeIdLike, propName: string): JsPropValue | null {
     return super.get_prop(nodeId(node), propName)
   }
 
+  getPropInt(node: NodeIdLike, propName: string): number | null {
+    return super.getPropInt(nodeId(node), propName)
+  }
+
+  getPropFloat(node: NodeIdLike, propName: string): number | null {
+    return super.getPropFloat(nodeId(node), propName)
+  }
+
+  getPropString(node: NodeIdLike, propName: string): string | null {
+    return super.getPropString(nodeId(node), propName)
+  }
+
+  getPropBool(node: NodeIdLike, propName: string): boolean | null {
+    return super.getPropBool(nodeId(node), propName)
+  }
+
+  hasProp(node: NodeIdLike, propName: string): boolean {
+    return super.hasProp(nodeId(node), propName)
+  }
+
+  propCount(node: NodeIdLike): number {
+    return super.propCount(nodeId(node))
+  }
+
+  nodeVersion(node: NodeIdLike): string | null {
+    return super.nodeVersion(nodeId(node))
+  }
+
+  getIfChanged(
+    node: NodeIdLike,
+    knownVersion: string,
+  ): { notModified: boolean; node: object | null; version: string | null } {
+    return super.getIfChanged(nodeId(node), knownVersion)
+  }
+
   setProp(node: NodeIdLike, propName: string, value: unknown): void {
     return super.setProp(nodeId(node), propName, value)
   }
 
+  getPropByKey(nodeType: NodeLike, key: unknown, propName: string): JsPropValue | null {
+    return super.getPropByKey(nodeName(nodeType), key, propName)
+  }
+
+  setPropByKey(nodeType: NodeLike, key: unknown, propName: string, value: unknown): void {
+    return super.setPropByKey(nodeName(nodeType), key, propName, value)
+  }
+
+  delPropByKey(nodeType: NodeLike, key: unknown, propName: string): void {
+    return super.delPropByKey(nodeName(nodeType), key, propName)
+  }
+
   setProps(node: NodeIdLike, props: Record<string, unknown>): void {
     return super.setProps(nodeId(node), props)
   }
 
+  setPropBulk(
+    nodes: Array<NodeIdLike>,
+    propName: string,
+    value: unknown,
+    options?: JsSetPropBulkOptions | null,
+  ): number {
+    return super.setPropBulk(
+      nodes.map((node) => nodeId(node)),
+      propName,
+      value,
+      options,
+    )
+  }
+
+  updateWhere(nodeType: NodeLike, matchProp: string, matchValue: unknown, setProps: Record<string, unknown>): number {
+    return super.updateWhere(nodeName(nodeType), matchProp, matchValue, setProps)
+  }
+
+  archive(node: NodeIdLike): boolean {
+    return super.archive(nodeId(node))
+  }
+
+  unarchive(node: NodeIdLike): boolean {
+    return super.unarchive(nodeId(node))
+  }
+
+  isArchived(node: NodeIdLike): boolean {
+    return super.isArchived(nodeId(node))
+  }
+
+  mergeNodes(keep: NodeIdLike, remove: NodeIdLike, options?: JsMergeOptions | null): JsMergeResult {
+    return super.mergeNodes(nodeId(keep), nodeId(remove), options)
+  }
+
   deleteByKey(nodeType: NodeLike, key: unknown): boolean {
     return super.deleteByKey(nodeName(nodeType), key)
   }
@@ -737,6 +1055,10 @@ export class Kite extends NativeKite {
     return KiteUpsertBuilder.wrap(super.upsert(nodeName(nodeType)))
   }
 
+  getOrInsert(nodeType: NodeLike, key: unknown, props?: object | null): { node: NodeObject; created: boolean } {
+    return super.getOrInsert(nodeName(nodeType), key, props) as { node: NodeObject; created: boolean }
+  }
+
   updateByKey(nodeType: NodeLike, key: unknown): KiteUpdateBuilder {
     return super.updateByKey(nodeName(nodeType), key)
   }
@@ -749,13 +1071,20 @@ export class Kite extends NativeKite {
     return super.upsertById(nodeName(nodeType), nodeId)
   }
 
-  link(src: NodeIdLike, edgeType: EdgeLike, dst: NodeIdLike, props?: object | null): void
+  link(
+    src: NodeIdLike,
+    edgeType: EdgeLike,
+    dst: NodeIdLike,
+    props?: object | null,
+    options?: JsLinkOptions | null,
+  ): void
   link(src: NodeIdLike): KiteLinkBuilder
   link(
     src: NodeIdLike,
     edgeType?: EdgeLike,
     dst?: NodeIdLike,
     props?: object | null,
+    options?: JsLinkOptions | null,
   ): void | KiteLinkBuilder {
     if (!edgeType || dst === undefined) {
       return new KiteLinkBuilder(this, nodeId(src))
@@ -765,9 +1094,34 @@ export class Kite extends NativeKite {
       edgeName(edgeType),
       nodeId(dst),
       props as object | undefined | null,
+      options,
     )
   }
 
+  linkMany(
+    edges: Array<{ src: NodeIdLike; edgeType: EdgeLike; dst: NodeIdLike; props?: object | null }>,
+  ): JsLinkManyResult {
+    return super.linkMany(
+      edges.map((edge) => ({
+        src: nodeId(edge.src),
+        edgeType: edgeName(edge.edgeType),
+        dst: nodeId(edge.dst),
+        props: edge.props,
+      })),
+    )
+  }
+
+  linkByKey(
+    srcType: NodeLike,
+    srcKey: unknown,
+    edgeType: EdgeLike,
+    dstType: NodeLike,
+    dstKey: unknown,
+    options?: JsLinkByKeyOptions | null,
+  ): JsLinkByKeyResult {
+    return super.linkByKey(nodeName(srcType), srcKey, edgeName(edgeType), nodeName(dstType), dstKey, options)
+  }
+
   unlink(src: NodeIdLike, edgeType: EdgeLike, dst: NodeIdLike): boolean {
     return super.unlink(nodeId(src), edgeName(edgeType), nodeId(dst))
   }
@@ -776,11 +1130,17 @@ export class Kite extends NativeKite {
     return super.hasEdge(nodeId(src), edgeName(edgeType), nodeId(dst))
   }
 
+  hasEdges(edges: Array<{ src: NodeIdLike; edgeType: EdgeLike; dst: NodeIdLike }>): boolean[] {
+    return super.hasEdges(
+      edges.map((edge) => ({ src: nodeId(edge.src), edgeType: edgeName(edge.edgeType), dst: nodeId(edge.dst) })),
+    )
+  }
+
   getEdgeProp(src: NodeIdLike, edgeType: EdgeLike, dst: NodeIdLike, propName: string): JsPropValue | null {
     return super.get_edge_prop(nodeId(src), edgeName(edgeType), nodeId(dst), propName)
   }
 
-  getEdgeProps(src: NodeIdLike, edgeType: EdgeLike, dst: NodeIdLike): Record<string, JsPropValue> {
+  getEdgeProps(src: NodeIdLike, edgeType: EdgeLike, dst: NodeIdLike): Record<string, JsPropValue> | null {
     return super.get_edge_props(nodeId(src), edgeName(edgeType), nodeId(dst))
   }
 
@@ -790,8 +1150,9 @@ export class Kite extends NativeKite {
     dst: NodeIdLike,
     propName: string,
     value: unknown,
-  ): void {
-    return super.setEdgeProp(nodeId(src), edgeName(edgeType), nodeId(dst), propName, value)
+    options?: JsSetEdgePropOptions | null,
+  ): boolean {
+    return super.setEdgeProp(nodeId(src), edgeName(edgeType), nodeId(dst), propName, value, options)
   }
 
   setEdgeProps(
@@ -815,20 +1176,31 @@ export class Kite extends NativeKite {
     return super.upsertEdge(nodeId(src), edgeName(edgeType), nodeId(dst))
   }
 
-  all(nodeType: NodeLike): Array<object> {
-    return super.all(nodeName(nodeType))
+  all(nodeType: NodeLike, options?: JsListOptions | null): Array<object> {
+    return super.all(nodeName(nodeType), options)
+  }
+
+  allPaged(
+    nodeType: NodeLike,
+    options?: PaginationOptions | null,
+  ): { items: Array<object>; nextCursor?: string | null; hasMore: boolean } {
+    return super.allPaged(nodeName(nodeType), options)
   }
 
   countNodes(nodeType?: NodeLike | null): number {
     return super.countNodes(nodeNameOptional(nodeType))
   }
 
+  distinctCount(nodeType: NodeLike, propName: string, approximate?: boolean | null): number {
+    return super.distinctCount(nodeName(nodeType), propName, approximate)
+  }
+
   countEdges(edgeType?: EdgeLike | null): number {
     return super.countEdges(edgeNameOptional(edgeType))
   }
 
-  allEdges(edgeType?: EdgeLike | null): Array<JsFullEdge> {
-    return super.allEdges(edgeNameOptional(edgeType))
+  allEdges(edgeType?: EdgeLike | null, options?: JsListOptions | null): Array<JsFullEdge> {
+    return super.allEdges(edgeNameOptional(edgeType), options)
   }
 
   hasPath(source: NodeIdLike, target: NodeIdLike, edgeType?: EdgeLike | null): boolean {
@@ -839,6 +1211,26 @@ export class Kite extends NativeKite {
     return super.reachableFrom(nodeId(source), maxDepth, edgeNameOptional(edgeType))
   }
 
+  reachableCursor(source: NodeIdLike, edgeType?: EdgeLike | null): KiteReachableCursor {
+    return super.reachableCursor(nodeId(source), edgeNameOptional(edgeType))
+  }
+
+  reachableFromExcluding(
+    source: NodeIdLike,
+    maxDepth: number,
+    edgeType?: EdgeLike | null,
+    excludedNodes?: Array<NodeIdLike> | null,
+    excludedEdges?: Array<JsFullEdge> | null,
+  ): Array<number> {
+    return super.reachableFromExcluding(
+      nodeId(source),
+      maxDepth,
+      edgeNameOptional(edgeType),
+      excludedNodes?.map(nodeId),
+      excludedEdges,
+    )
+  }
+
   from(node: NodeIdLike): KiteTraversal {
     return KiteTraversal.wrap(super.from(nodeId(node)), this)
   }
@@ -847,6 +1239,10 @@ export class Kite extends NativeKite {
     return KiteTraversal.wrap(super.fromNodes(nodeIds.map((id) => nodeId(id))), this)
   }
 
+  exploration(root: NodeIdLike): KiteExploration {
+    return super.exploration(nodeId(root))
+  }
+
   path(source: NodeIdLike, target: NodeIdLike): KitePath {
     return KitePath.wrap(super.path(nodeId(source), nodeId(target)))
   }
@@ -863,25 +1259,61 @@ export class Kite extends NativeKite {
 }
 
 export interface Kite {
+  get<N extends NodeSpec>(
+    nodeType: N,
+    key: InferNodeInsert<N>['key'],
+    props: Array<keyof InferNode<N>> | Array<string> | undefined,
+    options: JsGetOptions & { lazy: true },
+  ): LazyNode<InferNode<N>> | null
   get<N extends NodeSpec>(
     nodeType: N,
     key: InferNodeInsert<N>['key'],
     props?: Array<keyof InferNode<N>> | Array<string>,
+    options?: JsGetOptions,
   ): InferNode<N> | null
   getRef<N extends NodeSpec>(nodeType: N, key: InferNodeInsert<N>['key']): NodeRef<N> | null
   getId<N extends NodeSpec>(nodeType: N, key: InferNodeInsert<N>['key']): number | null
-  getById(nodeId: number, props?: Array<string>): NodeObject | null
-  getByIds(nodeIds: Array<NodeIdLike>, props?: Array<string>): Array<NodeObject>
+  getById(nodeId: number, props: Array<string> | undefined, options: JsGetOptions & { lazy: true }): LazyNode | null
+  getById(nodeId: number, props?: Array<string>, options?: JsGetOptions): NodeObject | null
+  getMany<N extends NodeSpec>(
+    nodeType: N,
+    keys: Array<InferNodeInsert<N>['key']>,
+    props?: Array<keyof InferNode<N>> | Array<string>,
+  ): Array<InferNode<N> | null>
+  getByIds(
+    nodeIds: Array<NodeIdLike>,
+    props: Array<string> | undefined,
+    options: JsGetByIdsOptions & { includeMissing: true },
+  ): Array<NodeObject | null>
+  getByIds(nodeIds: Array<NodeIdLike>, props?: Array<string>, options?: JsGetByIdsOptions): Array<NodeObject>
   delete<N extends NodeSpec>(nodeType: N, key: InferNodeInsert<N>['key']): boolean
   insert<N extends NodeSpec>(nodeType: N): KiteInsertBuilder<N>
   upsert<N extends NodeSpec>(nodeType: N): KiteUpsertBuilder<N>
+  getOrInsert<N extends NodeSpec>(
+    nodeType: N,
+    key: InferNodeInsert<N>['key'],
+    props?: Omit<InferNodeInsert<N>, 'key'> | null,
+  ): { node: InferNode<N>; created: boolean }
   update<N extends NodeSpec>(nodeType: N, key: InferNodeInsert<N>['key']): KiteUpdateBuilder
   updateByKey<N extends NodeSpec>(nodeType: N, key: InferNodeInsert<N>['key']): KiteUpdateBuilder
   upsertById<N extends NodeSpec>(nodeType: N, nodeId: number): KiteUpsertByIdBuilder
-  all<N extends NodeSpec>(nodeType: N): Array<InferNode<N>>
+  all<N extends NodeSpec>(nodeType: N, options?: JsListOptions | null): Array<InferNode<N>>
+  allPaged<N extends NodeSpec>(
+    nodeType: N,
+    options?: PaginationOptions | null,
+  ): { items: Array<InferNode<N>>; nextCursor?: string | null; hasMore: boolean }
   countNodes(nodeType?: NodeLike | null): number
+  distinctCount(nodeType: NodeLike, propName: string, approximate?: boolean | null): number
+  getPropByKey<N extends NodeSpec>(nodeType: N, key: InferNodeInsert<N>['key'], propName: string): JsPropValue | null
+  setPropByKey<N extends NodeSpec>(
+    nodeType: N,
+    key: InferNodeInsert<N>['key'],
+    propName: string,
+    value: unknown,
+  ): void
+  delPropByKey<N extends NodeSpec>(nodeType: N, key: InferNodeInsert<N>['key'], propName: string): void
   countEdges(edgeType?: EdgeLike | null): number
-  allEdges(edgeType?: EdgeLike | null): Array<JsFullEdge>
+  allEdges(edgeType?: EdgeLike | null, options?: JsListOptions | null): Array<JsFullEdge>
   link<E extends EdgeSpec>(
     src: NodeIdLike,
     edgeType: E,
@@ -889,17 +1321,27 @@ export interface Kite {
     props?: InferEdgeProps<E> | object | null,
   ): void
   link(src: NodeIdLike): KiteLinkBuilder
+  linkByKey<S extends NodeSpec, D extends NodeSpec>(
+    srcType: S,
+    srcKey: InferNodeInsert<S>['key'],
+    edgeType: EdgeLike,
+    dstType: D,
+    dstKey: InferNodeInsert<D>['key'],
+    options?: JsLinkByKeyOptions | null,
+  ): JsLinkByKeyResult
   unlink(src: NodeIdLike, edgeType: EdgeLike, dst: NodeIdLike): boolean
   hasEdge(src: NodeIdLike, edgeType: EdgeLike, dst: NodeIdLike): boolean
+  hasEdges(edges: Array<{ src: NodeIdLike; edgeType: EdgeLike; dst: NodeIdLike }>): boolean[]
   getEdgeProp(src: NodeIdLike, edgeType: EdgeLike, dst: NodeIdLike, propName: string): JsPropValue | null
-  getEdgeProps(src: NodeIdLike, edgeType: EdgeLike, dst: NodeIdLike): Record<string, JsPropValue>
+  getEdgeProps(src: NodeIdLike, edgeType: EdgeLike, dst: NodeIdLike): Record<string, JsPropValue> | null
   setEdgeProp(
     src: NodeIdLike,
     edgeType: EdgeLike,
     dst: NodeIdLike,
     propName: string,
     value: unknown,
-  ): void
+    options?: JsSetEdgePropOptions | null,
+  ): boolean
   batchAdaptive(
     operations: Array<any>,
     options?: { maxBatch?: number; minBatch?: number; autoCheckpointOnWalFull?: boolean } | null,
@@ -916,8 +1358,17 @@ export interface Kite {
   upsertEdge(src: NodeIdLike, edgeType: EdgeLike, dst: NodeIdLike): KiteUpsertEdgeBuilder
   hasPath(source: NodeIdLike, target: NodeIdLike, edgeType?: EdgeLike | null): boolean
   reachableFrom(source: NodeIdLike, maxDepth: number, edgeType?: EdgeLike | null): Array<number>
+  reachableCursor(source: NodeIdLike, edgeType?: EdgeLike | null): KiteReachableCursor
+  reachableFromExcluding(
+    source: NodeIdLike,
+    maxDepth: number,
+    edgeType?: EdgeLike | null,
+    excludedNodes?: Array<NodeIdLike> | null,
+    excludedEdges?: Array<JsFullEdge> | null,
+  ): Array<number>
   from(node: NodeIdLike): KiteTraversal
   fromNodes(nodeIds: Array<NodeIdLike>): KiteTraversal
+  exploration(root: NodeIdLike): KiteExploration
   path(source: NodeIdLike, target: NodeIdLike): KitePath
   pathToAny(source: NodeIdLike, targets: Array<NodeIdLike>): KitePath
   shortestPath(source: NodeIdLike): KiteShortestPathBuilder
@@ -930,6 +1381,9 @@ export interface KiteTraversal {
   ['in'](edgeType?: EdgeLike | null): KiteTraversal
   both(edgeType?: EdgeLike | null): KiteTraversal
   traverse(edgeType: EdgeLike | undefined | null, options: JsTraverseOptions): KiteTraversal
+  allowedNodeTypes(nodeTypes: Array<NodeLike>): KiteTraversal
+  includeArchived(): KiteTraversal
+  asOf(timestamp: number): KiteTraversal
   take(limit: number): KiteTraversal
   select(props: Array<string>): KiteTraversal
   nodes(): ArrayWithToArray<number, NodeObject>
@@ -1017,6 +1471,9 @@ export function bulkWrite<T>(
 export {
   Database,
   VectorIndex,
+  KiteExploration,
+  KiteNodeCursor,
+  KiteReachableCursor,
   KiteInsertExecutorSingle,
   KiteInsertExecutorMany,
   KiteUpdateBuilder,
@@ -1033,6 +1490,8 @@ export {
   JsDistanceMetric as DistanceMetric,
   JsAggregation as Aggregation,
   JsSyncMode as SyncMode,
+  JsLockMode as LockMode,
+  JsWalkControl as WalkControl,
   JsCompressionType as CompressionType,
   PropType as PropValueType,
 } from '../index'
@@ -1046,6 +1505,7 @@ export {
   createBackup,
   restoreBackup,
   backupInfo,
+  listBackups,
   createOfflineBackup,
   collectMetrics,
   collectReplicationLogTransportJson,
@@ -1100,6 +1560,7 @@ export type {
   // Database
   DbStats,
   CheckResult,
+  RepairResult,
   OpenOptions,
   // Export/Import
   ExportOptions,
@@ -1126,6 +1587,11 @@ export type {
   MemoryMetrics,
   MvccMetrics,
   MvccStats,
+  JsWalRecordStats as WalRecordStats,
+  JsIndexInfo as IndexInfo,
+  JsNodeKey as NodeKey,
+  JsCommitStats as CommitStats,
+  JsTransactionInfo as TransactionInfo,
   HealthCheckResult,
   HealthCheckEntry,
   OtlpHttpExportResult,
@@ -1134,11 +1600,23 @@ export type {
   JsTraverseOptions as TraverseOptions,
   JsTraversalStep as TraversalStep,
   JsTraversalResult as TraversalResult,
+  JsTopKResult as TopKResult,
+  JsSpanningTreeNode as SpanningTreeNode,
+  JsLinkByKeyOptions as LinkByKeyOptions,
+  JsLinkOptions as LinkOptions,
+  JsLinkByKeyResult as LinkByKeyResult,
+  JsLinkManyResult as LinkManyResult,
+  JsGetByIdsOptions as GetByIdsOptions,
+  JsSetPropBulkOptions as SetPropBulkOptions,
+  JsMergeOptions as MergeOptions,
+  JsMergeResult as MergeResult,
+  JsSchemaChange as SchemaChange,
   // Pathfinding
   JsPathConfig as PathConfig,
   JsPathResult as PathResult,
   JsPathEdge as PathEdge,
   // Vectors
+  VectorDimUpdate,
   VectorIndexOptions,
   VectorIndexStats,
   VectorSearchHit,
@@ -1193,6 +1671,8 @@ export interface KiteOptions {
   groupCommitWindowMs?: number
   /** WAL size in megabytes (default: 1MB) */
   walSizeMb?: number
+  /** Separate WAL file location, e.g. on faster storage (applies when creating the database) */
+  walPath?: string
   /** WAL usage threshold (0.0-1.0) to trigger auto-checkpoint */
   checkpointThreshold?: number
   /** Replication role */
@@ -1220,6 +1700,11 @@ function propSpecToNative(spec: PropSpec): JsPropSpec {
     type: spec.type,
     optional: spec.optional,
     default: spec.default as JsPropValue | undefined,
+    allowed: spec.allowed,
+    unique: spec.unique,
+    ordered: spec.ordered,
+    unit: spec.unit,
+    normalize: spec.normalize,
   }
 }
 
@@ -1253,6 +1738,7 @@ function edgeSpecToNative(spec: EdgeSpec): JsEdgeSpec {
   return {
     name: spec.name,
     props,
+    noSelfLoops: spec.noSelfLoops,
   }
 }
 
@@ -1281,6 +1767,7 @@ function optionsToNative(options: KiteOptions): JsKiteOptions {
     groupCommitEnabled: options.groupCommitEnabled,
     groupCommitWindowMs: options.groupCommitWindowMs,
     walSizeMb: options.walSizeMb,
+    walPath: options.walPath,
     checkpointThreshold: options.checkpointThreshold,
   }
 
@@ -27,7 +27,7 @@
 // =============================================================================
 
 /** Property type identifiers */
-export type PropType = 'string' | 'int' | 'float' | 'bool' | 'vector' | 'any'
+export type PropType = 'string' | 'int' | 'float' | 'bool' | 'enum' | 'vector' | 'json' | 'any'
 
 /** Property specification */
 export interface PropSpec<T extends PropType = PropType> {
@@ -37,6 +37,16 @@ export interface PropSpec<T extends PropType = PropType> {
   optional?: boolean
   /** Default value for this property */
   default?: unknown
+  /** Allowed values (for 'enum' properties) */
+  allowed?: string[]
+  /** Whether values must be unique across nodes of the type */
+  unique?: boolean
+  /** Whether values are kept in a sorted index for range queries */
+  ordered?: boolean
+  /** Unit of measure for the values, e.g. 'ms' or 'kg' */
+  unit?: string
+  /** Whether written vectors are L2-normalized (vector node props only) */
+  normalize?: boolean
 }
 
 // =============================================================================
@@ -54,6 +64,7 @@ export interface PropSpec<T extends PropType = PropType> {
  * const age = optional(int('age'))   // optional int
  * const score = float('score')       // required float
  * const active = bool('active')      // required bool
+ * const status = oneOf('status', ['active', 'inactive'])  // enum
  * const embedding = vector('embedding', 1536)  // vector with dimensions
  * ```
  */
@@ -81,15 +92,30 @@ export const prop = {
    */
   bool: (_name: string): PropSpec<'bool'> => ({ type: 'bool' }),
 
+  /**
+   * Enum property.
+   * Stored as strings; writes outside `allowed` are rejected.
+   *
+   * @param _name - Property name
+   * @param allowed - Allowed values
+   */
+  enum: (_name: string, allowed: string[]): PropSpec<'enum'> => ({ type: 'enum', allowed }),
+
   /**
    * Vector property for embeddings.
-   * Stored as Float32 arrays.
+   * Write a `Float32Array`; a plain array is stored as JSON instead.
    *
    * @param _name - Property name
    * @param _dimensions - Vector dimensions (for documentation/validation)
    */
   vector: (_name: string, _dimensions?: number): PropSpec<'vector'> => ({ type: 'vector' }),
 
+  /**
+   * JSON document property.
+   * Plain objects and arrays are stored as structured JSON; query with `findWhere` and a `jsonPath`.
+   */
+  json: (_name: string): PropSpec<'json'> => ({ type: 'json' }),
+
   /**
    * Any property (schema-less).
    * Accepts any value type.
@@ -102,6 +128,7 @@ export const string = prop.string
 export const int = prop.int
 export const float = prop.float
 export const bool = prop.bool
+export const oneOf = prop.enum
 export const vector = prop.vector
 export const any = prop.any
 
@@ -129,14 +156,69 @@ export function withDefault<T extends PropSpec>(spec: T, value: unknown): T {
   return { ...spec, default: value }
 }
 
+/**
+ * Require a node property's values to be unique across its node type.
+ *
+ * @example
+ * ```typescript
+ * const email = unique(string('email'))
+ * ```
+ */
+export function unique<T extends PropSpec>(spec: T): T & { unique: true } {
+  return { ...spec, unique: true }
+}
+
+/**
+ * Keep a node property in a sorted index so `findByPropRange` can query it.
+ * The index is held in memory and rebuilt when the database is opened.
+ *
+ * @example
+ * ```typescript
+ * const age = ordered(int('age'))
+ * ```
+ */
+export function ordered<T extends PropSpec>(spec: T): T & { ordered: true } {
+  return { ...spec, ordered: true }
+}
+
+/**
+ * Declare the unit of measure for a property. Writers can pass a `$units`
+ * object alongside props, and a unit that doesn't match is rejected.
+ *
+ * @example
+ * ```typescript
+ * const latency = withUnit(float('latency'), 'ms')
+ * ```
+ */
+export function withUnit<T extends PropSpec>(spec: T, unit: string): T & { unit: string } {
+  return { ...spec, unit }
+}
+
+/**
+ * L2-normalize every vector written to a vector node property. Cosine
+ * search over the property then compares by dot product, skipping the
+ * per-vector norm.
+ *
+ * @example
+ * ```typescript
+ * const embedding = normalized(vector('embedding', 1536))
+ * ```
+ */
+export function normalized<T extends PropSpec<'vector'>>(spec: T): T & { normalize: true } {
+  return { ...spec, normalize: true }
+}
+
 // =============================================================================
 // Key Specification
 // =============================================================================
 
 /** Key generation strategy */
 export interface KeySpec {
-  /** Key generation kind */
-  kind: 'prefix' | 'template' | 'parts'
+  /**
+   * Key generation kind. 'autoincrement' keys are the prefix plus a number
+   * the database allocates when an insert omits the key (pass `null`).
+   */
+  kind: 'prefix' | 'template' | 'parts' | 'autoincrement'
   /** Key prefix (for all kinds) */
   prefix?: string
   /** Template string with {field} placeholders (for 'template' kind) */
@@ -182,6 +264,7 @@ export interface NodeConfig<
    * key: { kind: 'prefix', prefix: 'user:' }
    * key: { kind: 'template', template: 'user:{org}:{id}' }
    * key: { kind: 'parts', fields: ['org', 'id'], separator: ':' }
+   * key: { kind: 'autoincrement', prefix: 'ticket:' }
    * ```
    */
   key?: ((arg: K) => string) | KeySpec
@@ -263,6 +346,8 @@ export interface EdgeSpec<
   name: string
   /** Property definitions */
   props?: P
+  /** Whether edges from a node to itself are rejected */
+  noSelfLoops?: boolean
 }
 
 /**
@@ -293,6 +378,18 @@ export function edge<
   return { name, props }
 }
 
+/**
+ * Reject edges of this type that connect a node to itself.
+ *
+ * @example
+ * ```typescript
+ * const follows = noSelfLoops(edge('follows'))
+ * ```
+ */
+export function noSelfLoops<E extends EdgeSpec>(spec: E): E & { noSelfLoops: true } {
+  return { ...spec, noSelfLoops: true }
+}
+
 // =============================================================================
 // Aliases for backwards compatibility
 // =============================================================================
@@ -315,9 +412,13 @@ type PropValue<S extends PropSpec> = S['type'] extends 'string'
       ? number
       : S['type'] extends 'bool'
         ? boolean
-        : S['type'] extends 'vector'
-          ? Array<number>
-          : unknown
+        : S['type'] extends 'enum'
+          ? string
+          : S['type'] extends 'vector'
+            ? Float32Array
+            : S['type'] extends 'json'
+              ? Record<string, unknown> | Array<unknown>
+              : unknown
 
 type OptionalKeys<P extends Record<string, PropSpec>> = {
   [K in keyof P]: P[K] extends { optional: true } ? K : never